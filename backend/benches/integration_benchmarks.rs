@@ -76,6 +76,8 @@ fn auth_benchmarks(c: &mut Criterion) {
                 email,
                 password: "secure_password123".to_string(),
                 totp_code: None,
+                recovery_code: None,
+                webauthn: None,
             };
 
             let login_req = Request::builder()
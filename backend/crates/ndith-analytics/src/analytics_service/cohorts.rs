@@ -0,0 +1,587 @@
+//! Cohort and Funnel Analytics
+//!
+//! Answers journey questions such as "of users who connected Spotify in
+//! March, how many ran enforcement within 7 days, and how many still have a
+//! healthy connection today?" Both views are computed in DuckDB over the
+//! replicated `user_lifecycle_events` and `connections` tables.
+//!
+//! - Retention: users grouped by the week of their first cohort event, with
+//!   the share active in each following week
+//! - Funnels: users whose first entry event falls in a date range, counted
+//!   through an ordered list of later stages
+
+use anyhow::{bail, Result};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use super::replication::{self, DataFreshness, CONNECTIONS, USER_LIFECYCLE_EVENTS};
+use crate::databases::DuckDbClient;
+
+/// A point in a user's journey
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleStage {
+    Registered,
+    Connected,
+    Scanned,
+    Enforced,
+    RolledBack,
+    Disconnected,
+    /// Current state rather than an event: an active connection today
+    HealthyConnection,
+}
+
+impl LifecycleStage {
+    /// Recorded event type, or None for stages read from current state
+    pub fn event_type(&self) -> Option<&'static str> {
+        match self {
+            LifecycleStage::Registered => Some("registered"),
+            LifecycleStage::Connected => Some("connected"),
+            LifecycleStage::Scanned => Some("scanned"),
+            LifecycleStage::Enforced => Some("enforced"),
+            LifecycleStage::RolledBack => Some("rolled_back"),
+            LifecycleStage::Disconnected => Some("disconnected"),
+            LifecycleStage::HealthyConnection => None,
+        }
+    }
+
+    /// Whether the stage belongs to a provider (registration doesn't)
+    fn has_provider(&self) -> bool {
+        !matches!(self, LifecycleStage::Registered)
+    }
+}
+
+impl FromStr for LifecycleStage {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "registered" => Ok(LifecycleStage::Registered),
+            "connected" => Ok(LifecycleStage::Connected),
+            "scanned" => Ok(LifecycleStage::Scanned),
+            "enforced" => Ok(LifecycleStage::Enforced),
+            "rolled_back" => Ok(LifecycleStage::RolledBack),
+            "disconnected" => Ok(LifecycleStage::Disconnected),
+            "healthy_connection" => Ok(LifecycleStage::HealthyConnection),
+            _ => bail!("Unknown lifecycle stage: {}", s),
+        }
+    }
+}
+
+/// Weekly retention query
+#[derive(Debug, Clone)]
+pub struct CohortQuery {
+    /// Event that places a user in a cohort (their first occurrence)
+    pub cohort_event: LifecycleStage,
+    /// Event counted as activity; None counts any event
+    pub activity_event: Option<LifecycleStage>,
+    pub provider: Option<String>,
+    /// Cohort entry dates, inclusive
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Number of weeks to track after joining
+    pub weeks: u32,
+}
+
+/// Retention of one weekly cohort
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CohortRow {
+    /// Monday of the week the users joined
+    pub cohort_week: NaiveDate,
+    pub size: i64,
+    /// Users active in each week since joining; weeks not yet over are omitted
+    pub retained: Vec<i64>,
+    pub retention: Vec<f64>,
+}
+
+/// Weekly retention matrix
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionMatrix {
+    pub cohort_event: LifecycleStage,
+    pub activity_event: Option<LifecycleStage>,
+    pub provider: Option<String>,
+    pub weeks: u32,
+    pub cohorts: Vec<CohortRow>,
+    pub freshness: DataFreshness,
+}
+
+/// Conversion funnel query
+#[derive(Debug, Clone)]
+pub struct FunnelQuery {
+    /// Ordered stages; the first must be an event
+    pub steps: Vec<LifecycleStage>,
+    pub provider: Option<String>,
+    /// Entry dates for the first step, inclusive
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// Later events must happen within this many days of entering
+    pub window_days: Option<u32>,
+    /// Also break the funnel down per provider
+    pub by_provider: bool,
+}
+
+/// Users reaching one funnel stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunnelStepResult {
+    pub stage: LifecycleStage,
+    pub users: i64,
+    pub conversion_from_previous: f64,
+    pub conversion_from_start: f64,
+}
+
+/// Funnel for one provider, or across providers when `provider` is None
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Funnel {
+    pub provider: Option<String>,
+    pub steps: Vec<FunnelStepResult>,
+}
+
+/// Funnel results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunnelReport {
+    pub window_days: Option<u32>,
+    pub funnel: Funnel,
+    pub by_provider: Vec<Funnel>,
+    pub freshness: DataFreshness,
+}
+
+/// SQL text plus its positional parameters
+#[derive(Default)]
+struct SqlBuilder {
+    sql: String,
+    params: Vec<Option<String>>,
+}
+
+impl SqlBuilder {
+    fn push(&mut self, sql: &str) {
+        self.sql.push_str(sql);
+    }
+
+    fn bind(&mut self, sql: &str, value: Option<String>) {
+        self.sql.push_str(sql);
+        self.params.push(value);
+    }
+
+    /// `AND <column> = ?` when a provider is given
+    fn provider_filter(&mut self, column: &str, provider: Option<&str>) {
+        if let Some(provider) = provider {
+            self.bind(&format!(" AND {} = ?", column), Some(provider.to_string()));
+        }
+    }
+
+    /// First occurrence of `event` per user, limited to entries in the range
+    fn entry_cte(
+        &mut self,
+        name: &str,
+        stage: LifecycleStage,
+        provider: Option<&str>,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) {
+        self.push(&format!(
+            "{} AS (SELECT user_id, MIN(occurred_at) AS entered_at, MIN(occurred_at) AS reached_at \
+             FROM user_lifecycle_events WHERE event_type = '{}'",
+            name,
+            stage.event_type().unwrap_or_default()
+        ));
+        if stage.has_provider() {
+            self.provider_filter("provider", provider);
+        }
+        self.bind(
+            " GROUP BY user_id HAVING MIN(occurred_at) >= CAST(? AS TIMESTAMP)",
+            Some(start.to_string()),
+        );
+        self.bind(
+            " AND MIN(occurred_at) < CAST(? AS TIMESTAMP))",
+            Some((end + Duration::days(1)).to_string()),
+        );
+    }
+
+    fn params(&self) -> Vec<&(dyn duckdb::ToSql + Sync)> {
+        self.params
+            .iter()
+            .map(|p| p as &(dyn duckdb::ToSql + Sync))
+            .collect()
+    }
+}
+
+fn rate(part: i64, whole: i64) -> f64 {
+    if whole > 0 {
+        part as f64 / whole as f64
+    } else {
+        0.0
+    }
+}
+
+fn funnel_sql(query: &FunnelQuery, provider: Option<&str>) -> SqlBuilder {
+    let mut sql = SqlBuilder::default();
+    sql.push("WITH ");
+    sql.entry_cte("s0", query.steps[0], provider, query.start, query.end);
+
+    for (i, stage) in query.steps.iter().enumerate().skip(1) {
+        let prev = format!("s{}", i - 1);
+        match stage.event_type() {
+            Some(event_type) => {
+                sql.push(&format!(
+                    ", s{i} AS (SELECT p.user_id, p.entered_at, MIN(e.occurred_at) AS reached_at \
+                     FROM {prev} p JOIN user_lifecycle_events e ON e.user_id = p.user_id \
+                     WHERE e.event_type = '{event_type}' AND e.occurred_at >= p.reached_at"
+                ));
+                if let Some(days) = query.window_days {
+                    sql.push(&format!(
+                        " AND e.occurred_at <= p.entered_at + INTERVAL {} DAY",
+                        days
+                    ));
+                }
+                if stage.has_provider() {
+                    sql.provider_filter("e.provider", provider);
+                }
+                sql.push(" GROUP BY p.user_id, p.entered_at)");
+            }
+            None => {
+                sql.push(&format!(
+                    ", s{i} AS (SELECT p.user_id, p.entered_at, p.reached_at FROM {prev} p \
+                     WHERE EXISTS (SELECT 1 FROM connections c \
+                     WHERE c.user_id = p.user_id AND c.status = 'active'"
+                ));
+                sql.provider_filter("c.provider", provider);
+                sql.push("))");
+            }
+        }
+    }
+
+    let counts: Vec<String> = (0..query.steps.len())
+        .map(|i| format!("(SELECT COUNT(*) FROM s{})", i))
+        .collect();
+    sql.push(&format!(" SELECT {}", counts.join(", ")));
+    sql
+}
+
+/// Cohort and funnel analytics over the DuckDB replica
+pub struct CohortAnalyticsService {
+    duckdb: Arc<DuckDbClient>,
+}
+
+impl CohortAnalyticsService {
+    pub fn new(duckdb: Arc<DuckDbClient>) -> Self {
+        Self { duckdb }
+    }
+
+    /// Freshness of the replicated journey tables
+    async fn freshness(&self) -> Result<DataFreshness> {
+        match replication::replica_sync_point(&self.duckdb, &[USER_LIFECYCLE_EVENTS, CONNECTIONS])
+            .await?
+        {
+            Some(freshness) => Ok(freshness),
+            None => bail!("Analytics replica has not finished its initial copy"),
+        }
+    }
+
+    /// Weekly retention matrix
+    pub async fn retention(&self, query: &CohortQuery) -> Result<RetentionMatrix> {
+        if query.cohort_event.event_type().is_none()
+            || query.activity_event == Some(LifecycleStage::HealthyConnection)
+        {
+            bail!("Retention is measured on recorded events, not connection health");
+        }
+
+        let freshness = self.freshness().await?;
+        let provider = query.provider.as_deref();
+
+        let mut cohort = SqlBuilder::default();
+        cohort.push("WITH ");
+        cohort.entry_cte(
+            "cohort",
+            query.cohort_event,
+            provider,
+            query.start,
+            query.end,
+        );
+
+        let mut sizes_sql = SqlBuilder {
+            sql: cohort.sql.clone(),
+            params: cohort.params.clone(),
+        };
+        sizes_sql.push(
+            " SELECT CAST(CAST(date_trunc('week', entered_at) AS DATE) AS VARCHAR) AS cohort_week, \
+             COUNT(*) FROM cohort GROUP BY cohort_week ORDER BY cohort_week",
+        );
+
+        let mut activity_sql = cohort;
+        activity_sql.push(
+            " SELECT CAST(CAST(date_trunc('week', c.entered_at) AS DATE) AS VARCHAR) AS cohort_week, \
+             CAST(date_diff('day', c.entered_at, e.occurred_at) // 7 AS BIGINT) AS week_offset, \
+             COUNT(DISTINCT c.user_id) \
+             FROM cohort c JOIN user_lifecycle_events e ON e.user_id = c.user_id \
+             WHERE e.occurred_at >= c.entered_at",
+        );
+        match query.activity_event {
+            Some(stage) => {
+                activity_sql.push(&format!(
+                    " AND e.event_type = '{}'",
+                    stage.event_type().unwrap_or_default()
+                ));
+                if stage.has_provider() {
+                    activity_sql.provider_filter("e.provider", provider);
+                }
+            }
+            None => {
+                if let Some(provider) = provider {
+                    activity_sql.bind(
+                        " AND (e.provider IS NULL OR e.provider = ?)",
+                        Some(provider.to_string()),
+                    );
+                }
+            }
+        }
+        activity_sql.push(" GROUP BY cohort_week, week_offset");
+
+        let sizes = self
+            .duckdb
+            .query(&sizes_sql.sql, &sizes_sql.params(), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })
+            .await?;
+
+        let activity = self
+            .duckdb
+            .query(&activity_sql.sql, &activity_sql.params(), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })
+            .await?;
+
+        let mut active: HashMap<(String, i64), i64> = HashMap::new();
+        for (week, offset, users) in activity {
+            active.insert((week, offset), users);
+        }
+
+        let today = Utc::now().date_naive();
+        let cohorts = sizes
+            .into_iter()
+            .filter_map(|(week, size)| {
+                let cohort_week = NaiveDate::parse_from_str(&week, "%Y-%m-%d").ok()?;
+                // Only weeks that have fully elapsed since the cohort week began
+                let elapsed = ((today - cohort_week).num_days() / 7).clamp(0, query.weeks as i64);
+                let retained: Vec<i64> = (0..elapsed)
+                    .map(|offset| active.get(&(week.clone(), offset)).copied().unwrap_or(0))
+                    .collect();
+                let retention = retained.iter().map(|r| rate(*r, size)).collect();
+                Some(CohortRow {
+                    cohort_week,
+                    size,
+                    retained,
+                    retention,
+                })
+            })
+            .collect();
+
+        Ok(RetentionMatrix {
+            cohort_event: query.cohort_event,
+            activity_event: query.activity_event,
+            provider: query.provider.clone(),
+            weeks: query.weeks,
+            cohorts,
+            freshness,
+        })
+    }
+
+    /// Conversion funnel, optionally broken down by provider
+    pub async fn funnel(&self, query: &FunnelQuery) -> Result<FunnelReport> {
+        match query.steps.first() {
+            Some(stage) if stage.event_type().is_some() => {}
+            Some(_) => bail!("The first funnel step must be a recorded event"),
+            None => bail!("A funnel needs at least one step"),
+        }
+
+        let freshness = self.freshness().await?;
+        let funnel = self.run_funnel(query, query.provider.as_deref()).await?;
+
+        let mut by_provider = Vec::new();
+        if query.by_provider && query.provider.is_none() {
+            let providers = self
+                .duckdb
+                .query(
+                    "SELECT DISTINCT provider FROM user_lifecycle_events \
+                     WHERE provider IS NOT NULL ORDER BY provider",
+                    &[],
+                    |row| row.get::<_, String>(0),
+                )
+                .await?;
+            for provider in providers {
+                by_provider.push(self.run_funnel(query, Some(&provider)).await?);
+            }
+        }
+
+        Ok(FunnelReport {
+            window_days: query.window_days,
+            funnel,
+            by_provider,
+            freshness,
+        })
+    }
+
+    async fn run_funnel(&self, query: &FunnelQuery, provider: Option<&str>) -> Result<Funnel> {
+        let sql = funnel_sql(query, provider);
+        let step_count = query.steps.len();
+
+        let counts = self
+            .duckdb
+            .query(&sql.sql, &sql.params(), |row| {
+                (0..step_count)
+                    .map(|i| row.get::<_, i64>(i))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| vec![0; step_count]);
+
+        let entered = counts.first().copied().unwrap_or(0);
+        let steps = query
+            .steps
+            .iter()
+            .zip(&counts)
+            .enumerate()
+            .map(|(i, (stage, users))| {
+                let previous = if i == 0 { *users } else { counts[i - 1] };
+                FunnelStepResult {
+                    stage: *stage,
+                    users: *users,
+                    conversion_from_previous: rate(*users, previous),
+                    conversion_from_start: rate(*users, entered),
+                }
+            })
+            .collect();
+
+        Ok(Funnel {
+            provider: provider.map(str::to_string),
+            steps,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::databases::ReplicationState;
+
+    async fn seeded_replica() -> Arc<DuckDbClient> {
+        let duckdb = DuckDbClient::in_memory().unwrap();
+        duckdb.initialize_schema().await.unwrap();
+        duckdb
+            .execute_batch(&format!(
+                r#"
+                {};
+                {};
+                INSERT INTO user_lifecycle_events VALUES
+                    ('e1', 'u1', 'connected', 'spotify', 'c1', '2024-03-04 10:00:00'),
+                    ('e2', 'u1', 'enforced', 'spotify', 'b1', '2024-03-06 10:00:00'),
+                    ('e3', 'u2', 'connected', 'spotify', 'c2', '2024-03-05 10:00:00'),
+                    ('e4', 'u2', 'enforced', 'spotify', 'b2', '2024-03-20 10:00:00'),
+                    ('e5', 'u3', 'connected', 'tidal', 'c3', '2024-03-05 10:00:00'),
+                    ('e6', 'u3', 'enforced', 'tidal', 'b3', '2024-03-06 10:00:00');
+                INSERT INTO connections VALUES
+                    ('c1', 'u1', 'spotify', 'active', NULL, '2024-03-04 10:00:00'),
+                    ('c2', 'u2', 'spotify', 'needs_reauth', NULL, '2024-03-05 10:00:00'),
+                    ('c3', 'u3', 'tidal', 'active', NULL, '2024-03-05 10:00:00');
+                "#,
+                USER_LIFECYCLE_EVENTS.create_table_sql(),
+                CONNECTIONS.create_table_sql()
+            ))
+            .await
+            .unwrap();
+
+        for table in ["user_lifecycle_events", "connections"] {
+            let mut state = ReplicationState::new(table);
+            state.snapshot_complete = true;
+            state.synced_through = Some(Utc::now());
+            duckdb.save_replication_state(&state).await.unwrap();
+        }
+
+        Arc::new(duckdb)
+    }
+
+    fn march() -> (NaiveDate, NaiveDate) {
+        (
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_funnel_with_window_and_health() {
+        let service = CohortAnalyticsService::new(seeded_replica().await);
+        let (start, end) = march();
+
+        let report = service
+            .funnel(&FunnelQuery {
+                steps: vec![
+                    LifecycleStage::Connected,
+                    LifecycleStage::Enforced,
+                    LifecycleStage::HealthyConnection,
+                ],
+                provider: None,
+                start,
+                end,
+                window_days: Some(7),
+                by_provider: true,
+            })
+            .await
+            .unwrap();
+
+        let users: Vec<i64> = report.funnel.steps.iter().map(|s| s.users).collect();
+        assert_eq!(users, vec![3, 2, 2]);
+
+        let spotify = report
+            .by_provider
+            .iter()
+            .find(|f| f.provider.as_deref() == Some("spotify"))
+            .unwrap();
+        let users: Vec<i64> = spotify.steps.iter().map(|s| s.users).collect();
+        assert_eq!(users, vec![2, 1, 1]);
+        assert_eq!(spotify.steps[1].conversion_from_start, 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_weekly_retention() {
+        let service = CohortAnalyticsService::new(seeded_replica().await);
+        let (start, end) = march();
+
+        let matrix = service
+            .retention(&CohortQuery {
+                cohort_event: LifecycleStage::Connected,
+                activity_event: Some(LifecycleStage::Enforced),
+                provider: Some("spotify".to_string()),
+                start,
+                end,
+                weeks: 4,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(matrix.cohorts.len(), 1);
+        let cohort = &matrix.cohorts[0];
+        assert_eq!(
+            cohort.cohort_week,
+            NaiveDate::from_ymd_opt(2024, 3, 4).unwrap()
+        );
+        assert_eq!(cohort.size, 2);
+        assert_eq!(cohort.retained, vec![1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_parse_stage() {
+        assert_eq!(
+            "healthy_connection".parse::<LifecycleStage>().unwrap(),
+            LifecycleStage::HealthyConnection
+        );
+        assert!("signed_up".parse::<LifecycleStage>().is_err());
+    }
+}
@@ -9,6 +9,7 @@ use sqlx::PgPool;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use super::replication::{self, DataFreshness, USER_ARTIST_BLOCKS};
 use crate::databases::DuckDbClient;
 
 /// Time range for analytics queries
@@ -61,6 +62,8 @@ pub struct DashboardMetrics {
     pub recent_offenses: Vec<RecentOffense>,
     /// System health summary
     pub system_health: SystemHealth,
+    /// Source and currency of the block counts in user_metrics
+    pub freshness: DataFreshness,
 }
 
 /// User activity metrics
@@ -168,20 +171,24 @@ impl DashboardService {
         );
 
         let system_health = self.get_system_health().await?;
+        let (user_metrics, freshness) =
+            user_metrics.unwrap_or_else(|_| (UserMetrics::default(), DataFreshness::live()));
 
         Ok(DashboardMetrics {
             time_range: format!("{:?}", time_range),
-            user_metrics: user_metrics.unwrap_or_default(),
+            user_metrics,
             content_metrics: content_metrics.unwrap_or_default(),
             sync_metrics: sync_metrics.unwrap_or_default(),
             trending_artists: trending.unwrap_or_default(),
             recent_offenses: recent_offenses.unwrap_or_default(),
             system_health,
+            freshness,
         })
     }
 
-    /// Get user metrics from PostgreSQL
-    async fn get_user_metrics(&self, days: i32) -> Result<UserMetrics> {
+    /// Get user metrics from PostgreSQL, with block counts from the DuckDB
+    /// replica while it is current
+    async fn get_user_metrics(&self, days: i32) -> Result<(UserMetrics, DataFreshness)> {
         let cutoff = Utc::now() - Duration::days(days as i64);
 
         // Total users
@@ -198,43 +205,91 @@ impl DashboardService {
                 .await
                 .unwrap_or(0);
 
-        // Active users (users with blocks in time range)
-        let active_users: i64 = sqlx::query_scalar(
-            "SELECT COUNT(DISTINCT user_id) FROM user_artist_blocks WHERE created_at >= $1",
+        let replica = replication::replica_freshness(
+            &self.duckdb,
+            &[USER_ARTIST_BLOCKS],
+            replication::default_max_lag(),
         )
-        .bind(cutoff)
-        .fetch_one(&self.pool)
         .await
-        .unwrap_or(0);
-
-        // Total blocks
-        let total_blocks: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM user_artist_blocks")
-            .fetch_one(&self.pool)
-            .await
-            .unwrap_or(0);
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to check analytics replica");
+            None
+        });
+
+        let (active_users, total_blocks, new_blocks, freshness) = match replica {
+            Some(freshness) => {
+                let cutoff = cutoff.naive_utc().to_string();
+                let (active_users, total_blocks, new_blocks) = self
+                    .duckdb
+                    .query(
+                        r#"
+                        SELECT
+                            COUNT(DISTINCT user_id) FILTER (WHERE created_at >= CAST(? AS TIMESTAMP)),
+                            COUNT(*),
+                            COUNT(*) FILTER (WHERE created_at >= CAST(? AS TIMESTAMP))
+                        FROM user_artist_blocks
+                        "#,
+                        &[&cutoff, &cutoff],
+                        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, i64>(2)?)),
+                    )
+                    .await?
+                    .into_iter()
+                    .next()
+                    .unwrap_or((0, 0, 0));
+                (active_users, total_blocks, new_blocks, freshness)
+            }
+            None => {
+                // Active users (users with blocks in time range)
+                let active_users: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(DISTINCT user_id) FROM user_artist_blocks WHERE created_at >= $1",
+                )
+                .bind(cutoff)
+                .fetch_one(&self.pool)
+                .await
+                .unwrap_or(0);
 
-        // New blocks in time range
-        let new_blocks: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM user_artist_blocks WHERE created_at >= $1")
+                // Total blocks
+                let total_blocks: i64 =
+                    sqlx::query_scalar("SELECT COUNT(*) FROM user_artist_blocks")
+                        .fetch_one(&self.pool)
+                        .await
+                        .unwrap_or(0);
+
+                // New blocks in time range
+                let new_blocks: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(*) FROM user_artist_blocks WHERE created_at >= $1",
+                )
                 .bind(cutoff)
                 .fetch_one(&self.pool)
                 .await
                 .unwrap_or(0);
 
+                (
+                    active_users,
+                    total_blocks,
+                    new_blocks,
+                    DataFreshness::live(),
+                )
+            }
+        };
+
         let avg_blocks_per_user = if total_users > 0 {
             total_blocks as f64 / total_users as f64
         } else {
             0.0
         };
 
-        Ok(UserMetrics {
-            total_users,
-            active_users,
-            new_users,
-            total_blocks,
-            new_blocks,
-            avg_blocks_per_user,
-        })
+        Ok((
+            UserMetrics {
+                total_users,
+                active_users,
+                new_users,
+                total_blocks,
+                new_blocks,
+                avg_blocks_per_user,
+            },
+            freshness,
+        ))
     }
 
     /// Get content metrics from DuckDB
@@ -6,15 +6,24 @@
 //! - Actions by type
 //! - Time-series data for the last 30 days
 //! - Provider-specific filtering
+//!
+//! Reads from the DuckDB replica when one is attached and current, falling
+//! back to PostgreSQL otherwise.
 
+use anyhow::Result;
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::sync::Arc;
 use uuid::Uuid;
 
+use super::replication::{self, DataFreshness, ACTION_BATCHES, ACTION_ITEMS};
+use crate::databases::DuckDbClient;
+
 /// Enforcement analytics service
 pub struct EnforcementAnalyticsService {
     pool: PgPool,
+    replica: Option<Arc<DuckDbClient>>,
 }
 
 /// Overall enforcement statistics
@@ -70,6 +79,8 @@ pub struct EnforcementAnalytics {
     pub stats: EnforcementStats,
     pub time_series: Vec<EnforcementTimeSeriesPoint>,
     pub generated_at: DateTime<Utc>,
+    /// Where the figures were read from and how current they are
+    pub freshness: DataFreshness,
 }
 
 /// Query parameters for enforcement analytics
@@ -89,7 +100,16 @@ fn default_days() -> i32 {
 impl EnforcementAnalyticsService {
     /// Create a new enforcement analytics service
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            replica: None,
+        }
+    }
+
+    /// Read from the DuckDB replica while it is current
+    pub fn with_replica(mut self, duckdb: Arc<DuckDbClient>) -> Self {
+        self.replica = Some(duckdb);
+        self
     }
 
     /// Get enforcement analytics for a user
@@ -97,7 +117,32 @@ impl EnforcementAnalyticsService {
         &self,
         user_id: Uuid,
         query: &EnforcementAnalyticsQuery,
-    ) -> Result<EnforcementAnalytics, sqlx::Error> {
+    ) -> Result<EnforcementAnalytics> {
+        if let Some(duckdb) = &self.replica {
+            match replication::replica_freshness(
+                duckdb,
+                &[ACTION_BATCHES, ACTION_ITEMS],
+                replication::default_max_lag(),
+            )
+            .await
+            {
+                Ok(Some(freshness)) => {
+                    let stats = replica_stats(duckdb, user_id, query.provider.as_deref()).await?;
+                    let time_series =
+                        replica_time_series(duckdb, user_id, query.provider.as_deref(), query.days)
+                            .await?;
+                    return Ok(EnforcementAnalytics {
+                        stats,
+                        time_series,
+                        generated_at: Utc::now(),
+                        freshness,
+                    });
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!(error = %e, "Failed to check analytics replica"),
+            }
+        }
+
         let stats = self
             .get_user_stats(user_id, query.provider.as_deref())
             .await?;
@@ -109,6 +154,7 @@ impl EnforcementAnalyticsService {
             stats,
             time_series,
             generated_at: Utc::now(),
+            freshness: DataFreshness::live(),
         })
     }
 
@@ -377,3 +423,223 @@ impl EnforcementAnalyticsService {
             .collect())
     }
 }
+
+fn success_rate(successful: i64, total: i64) -> f64 {
+    if total > 0 {
+        successful as f64 / total as f64
+    } else {
+        1.0
+    }
+}
+
+/// Overall enforcement stats for a user from the DuckDB replica
+async fn replica_stats(
+    duckdb: &DuckDbClient,
+    user_id: Uuid,
+    provider: Option<&str>,
+) -> Result<EnforcementStats> {
+    let user_id = user_id.to_string();
+    let provider_filter = "(CAST(? AS VARCHAR) IS NULL OR ab.provider = ?)";
+
+    let total_batches = duckdb
+        .query(
+            &format!(
+                "SELECT COUNT(*) FROM action_batches ab WHERE ab.user_id = ? AND {}",
+                provider_filter
+            ),
+            &[&user_id, &provider, &provider],
+            |row| row.get::<_, i64>(0),
+        )
+        .await?
+        .into_iter()
+        .next()
+        .unwrap_or(0);
+
+    let (total_actions, successful_actions, failed_actions, skipped_actions) = duckdb
+        .query(
+            &format!(
+                r#"
+                SELECT
+                    COUNT(*),
+                    COUNT(*) FILTER (WHERE ai.status = 'completed'),
+                    COUNT(*) FILTER (WHERE ai.status = 'failed'),
+                    COUNT(*) FILTER (WHERE ai.status = 'skipped')
+                FROM action_items ai
+                JOIN action_batches ab ON ai.batch_id = ab.id
+                WHERE ab.user_id = ? AND {}
+                "#,
+                provider_filter
+            ),
+            &[&user_id, &provider, &provider],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .await?
+        .into_iter()
+        .next()
+        .unwrap_or((0, 0, 0, 0));
+
+    let actions_by_type = duckdb
+        .query(
+            &format!(
+                r#"
+                SELECT ai.action, COUNT(*) as count
+                FROM action_items ai
+                JOIN action_batches ab ON ai.batch_id = ab.id
+                WHERE ab.user_id = ? AND {}
+                GROUP BY ai.action
+                ORDER BY count DESC
+                "#,
+                provider_filter
+            ),
+            &[&user_id, &provider, &provider],
+            |row| {
+                Ok(ActionTypeCount {
+                    action_type: row.get(0)?,
+                    count: row.get(1)?,
+                })
+            },
+        )
+        .await?;
+
+    let actions_by_provider = duckdb
+        .query(
+            r#"
+            SELECT
+                ab.provider,
+                COUNT(DISTINCT ab.id),
+                COUNT(ai.id) as total_actions,
+                COUNT(*) FILTER (WHERE ai.status = 'completed')
+            FROM action_batches ab
+            LEFT JOIN action_items ai ON ai.batch_id = ab.id
+            WHERE ab.user_id = ?
+            GROUP BY ab.provider
+            ORDER BY total_actions DESC
+            "#,
+            &[&user_id],
+            |row| {
+                let total_actions: i64 = row.get(2)?;
+                Ok(ProviderStats {
+                    provider: row.get(0)?,
+                    total_batches: row.get(1)?,
+                    total_actions,
+                    success_rate: success_rate(row.get(3)?, total_actions),
+                })
+            },
+        )
+        .await?;
+
+    Ok(EnforcementStats {
+        total_batches,
+        total_actions,
+        successful_actions,
+        failed_actions,
+        skipped_actions,
+        success_rate: success_rate(successful_actions, total_actions),
+        actions_by_type,
+        actions_by_provider,
+    })
+}
+
+/// Daily enforcement activity for a user from the DuckDB replica
+async fn replica_time_series(
+    duckdb: &DuckDbClient,
+    user_id: Uuid,
+    provider: Option<&str>,
+    days: i32,
+) -> Result<Vec<EnforcementTimeSeriesPoint>> {
+    let today = Utc::now().date_naive();
+    let start = today - chrono::Duration::days(days as i64);
+
+    duckdb
+        .query(
+            r#"
+            WITH date_series AS (
+                SELECT CAST(range AS DATE) AS date
+                FROM range(CAST(? AS TIMESTAMP), CAST(? AS TIMESTAMP) + INTERVAL 1 DAY, INTERVAL 1 DAY)
+            )
+            SELECT
+                CAST(ds.date AS VARCHAR),
+                COUNT(DISTINCT ab.id),
+                COUNT(ai.id),
+                COUNT(ai.id) FILTER (WHERE ai.status = 'completed'),
+                COUNT(ai.id) FILTER (WHERE ai.status = 'failed')
+            FROM date_series ds
+            LEFT JOIN action_batches ab ON
+                CAST(ab.created_at AS DATE) = ds.date
+                AND ab.user_id = ?
+                AND (CAST(? AS VARCHAR) IS NULL OR ab.provider = ?)
+            LEFT JOIN action_items ai ON ai.batch_id = ab.id
+            GROUP BY ds.date
+            ORDER BY ds.date
+            "#,
+            &[
+                &start.to_string(),
+                &today.to_string(),
+                &user_id.to_string(),
+                &provider,
+                &provider,
+            ],
+            |row| {
+                let date: String = row.get(0)?;
+                Ok(EnforcementTimeSeriesPoint {
+                    date: NaiveDate::parse_from_str(&date, "%Y-%m-%d").unwrap_or(start),
+                    batches_count: row.get(1)?,
+                    actions_count: row.get(2)?,
+                    successful_count: row.get(3)?,
+                    failed_count: row.get(4)?,
+                })
+            },
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replica_stats() {
+        let duckdb = DuckDbClient::in_memory().unwrap();
+        duckdb.initialize_schema().await.unwrap();
+        duckdb
+            .execute_batch(&format!(
+                "{}; {}",
+                ACTION_BATCHES.create_table_sql(),
+                ACTION_ITEMS.create_table_sql()
+            ))
+            .await
+            .unwrap();
+
+        let user_id = Uuid::new_v4();
+        duckdb
+            .execute_batch(&format!(
+                r#"
+                INSERT INTO action_batches VALUES
+                    ('b1', '{user}', 'spotify', false, 'completed', '{now}', NULL),
+                    ('b2', '{user}', 'tidal', false, 'completed', '{now}', NULL);
+                INSERT INTO action_items VALUES
+                    ('i1', 'b1', 'track', 't1', 'remove_track', 'completed', NULL, '{now}'),
+                    ('i2', 'b1', 'track', 't2', 'remove_track', 'failed', 'rate limited', '{now}'),
+                    ('i3', 'b2', 'artist', 'a1', 'unfollow', 'completed', NULL, '{now}');
+                "#,
+                user = user_id,
+                now = Utc::now().naive_utc()
+            ))
+            .await
+            .unwrap();
+
+        let stats = replica_stats(&duckdb, user_id, Some("spotify"))
+            .await
+            .unwrap();
+        assert_eq!(stats.total_batches, 1);
+        assert_eq!(stats.total_actions, 2);
+        assert_eq!(stats.failed_actions, 1);
+        assert_eq!(stats.actions_by_provider.len(), 2);
+
+        let series = replica_time_series(&duckdb, user_id, None, 7)
+            .await
+            .unwrap();
+        assert_eq!(series.len(), 8);
+        assert_eq!(series.last().unwrap().actions_count, 3);
+    }
+}
@@ -0,0 +1,394 @@
+//! Enforcement Impact Analytics
+//!
+//! Shows what enforcement actually changed in a user's library. Each library
+//! sync records a composition snapshot (flagged, blocked, per-category and
+//! per-tier track counts, and the revenue share going to flagged artists);
+//! the timeline lines snapshots up with the enforcement runs between them.
+//! Tracks that enforcement removed but a later sync found again are raised
+//! as regressions.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Library composition at one sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompositionSnapshot {
+    pub id: Uuid,
+    pub provider: String,
+    pub captured_at: DateTime<Utc>,
+    pub total_tracks: i64,
+    pub flagged_tracks: i64,
+    pub blocked_tracks: i64,
+    /// Shares are 0.0 - 1.0 of total tracks
+    pub flagged_share: f64,
+    pub blocked_share: f64,
+    pub tracks_by_category: BTreeMap<String, i64>,
+    pub category_share: BTreeMap<String, f64>,
+    pub tracks_by_tier: BTreeMap<String, i64>,
+    pub tier_share: BTreeMap<String, f64>,
+    /// Estimated revenue from the user's plays over the 30 days before the sync
+    pub total_revenue: Decimal,
+    pub flagged_revenue: Decimal,
+    pub flagged_revenue_share: f64,
+    pub regressions_detected: i32,
+}
+
+/// An enforcement run between two snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnforcementMarker {
+    pub batch_id: Uuid,
+    pub provider: String,
+    pub created_at: DateTime<Utc>,
+    pub completed_removals: i64,
+}
+
+/// A snapshot and what changed since the previous one for the same provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub snapshot: CompositionSnapshot,
+    pub previous_snapshot_id: Option<Uuid>,
+    pub flagged_tracks_change: i64,
+    pub flagged_share_change: f64,
+    pub blocked_tracks_change: i64,
+    pub flagged_revenue_share_change: f64,
+    /// Enforcement runs since the previous snapshot
+    pub enforcement: Vec<EnforcementMarker>,
+}
+
+/// Composition timeline for a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryImpactTimeline {
+    pub entries: Vec<TimelineEntry>,
+    /// Enforcement runs with no later snapshot yet
+    pub pending_enforcement: Vec<EnforcementMarker>,
+    pub open_regressions: i64,
+}
+
+/// A removed track that reappeared
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LibraryRegression {
+    pub id: Uuid,
+    pub provider: String,
+    pub provider_track_id: String,
+    pub track_name: Option<String>,
+    pub artist_id: Option<Uuid>,
+    pub artist_name: Option<String>,
+    pub removed_at: DateTime<Utc>,
+    pub detected_at: DateTime<Utc>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+/// Enforcement impact analytics service
+pub struct LibraryImpactService {
+    pool: PgPool,
+}
+
+impl LibraryImpactService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Composition timeline over the last `days`, optionally for one provider
+    pub async fn timeline(
+        &self,
+        user_id: Uuid,
+        provider: Option<&str>,
+        days: i32,
+    ) -> Result<LibraryImpactTimeline> {
+        let since = Utc::now() - Duration::days(days as i64);
+
+        let snapshots: Vec<SnapshotRow> = sqlx::query_as(
+            r#"
+            SELECT id, provider, total_tracks, flagged_tracks, blocked_tracks,
+                   tracks_by_category, tracks_by_tier, total_revenue, flagged_revenue,
+                   regressions_detected, captured_at
+            FROM library_composition_snapshots
+            WHERE user_id = $1
+              AND ($2::text IS NULL OR provider = $2)
+              AND captured_at >= $3
+            ORDER BY captured_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(provider)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let batches: Vec<(Uuid, String, DateTime<Utc>, i64)> = sqlx::query_as(
+            r#"
+            SELECT ab.id, ab.provider, ab.created_at,
+                   COUNT(ai.id) FILTER (WHERE ai.status = 'completed' AND ai.action LIKE 'remove%')
+            FROM action_batches ab
+            LEFT JOIN action_items ai ON ai.batch_id = ab.id
+            WHERE ab.user_id = $1
+              AND ($2::text IS NULL OR ab.provider = $2)
+              AND ab.created_at >= $3
+              AND NOT COALESCE(ab.dry_run, FALSE)
+            GROUP BY ab.id, ab.provider, ab.created_at
+            ORDER BY ab.created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(provider)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let open_regressions: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM library_regressions
+            WHERE user_id = $1 AND acknowledged_at IS NULL
+              AND ($2::text IS NULL OR provider = $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(provider)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let snapshots = snapshots
+            .into_iter()
+            .map(CompositionSnapshot::from)
+            .collect();
+        let markers = batches
+            .into_iter()
+            .map(
+                |(batch_id, provider, created_at, completed_removals)| EnforcementMarker {
+                    batch_id,
+                    provider,
+                    created_at,
+                    completed_removals,
+                },
+            )
+            .collect();
+
+        let (entries, pending_enforcement) = build_timeline(snapshots, markers);
+
+        Ok(LibraryImpactTimeline {
+            entries,
+            pending_enforcement,
+            open_regressions,
+        })
+    }
+
+    /// Regressions for a user, newest first
+    pub async fn regressions(
+        &self,
+        user_id: Uuid,
+        include_acknowledged: bool,
+    ) -> Result<Vec<LibraryRegression>> {
+        let regressions = sqlx::query_as(
+            r#"
+            SELECT id, provider, provider_track_id, track_name, artist_id, artist_name,
+                   removed_at, detected_at, acknowledged_at
+            FROM library_regressions
+            WHERE user_id = $1 AND ($2 OR acknowledged_at IS NULL)
+            ORDER BY detected_at DESC
+            LIMIT 500
+            "#,
+        )
+        .bind(user_id)
+        .bind(include_acknowledged)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(regressions)
+    }
+
+    /// Dismiss a regression alert. Returns false when it doesn't belong to
+    /// the user.
+    pub async fn acknowledge_regression(&self, user_id: Uuid, regression_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE library_regressions
+            SET acknowledged_at = COALESCE(acknowledged_at, NOW())
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(regression_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn share(part: i64, whole: i64) -> f64 {
+    if whole > 0 {
+        part as f64 / whole as f64
+    } else {
+        0.0
+    }
+}
+
+/// Pair each snapshot with the previous one for its provider and the
+/// enforcement runs in between. Both inputs are ordered by time; runs after
+/// the latest snapshot of their provider are returned separately.
+fn build_timeline(
+    snapshots: Vec<CompositionSnapshot>,
+    mut markers: Vec<EnforcementMarker>,
+) -> (Vec<TimelineEntry>, Vec<EnforcementMarker>) {
+    let mut previous: BTreeMap<String, CompositionSnapshot> = BTreeMap::new();
+    let mut entries = Vec::with_capacity(snapshots.len());
+
+    for snapshot in snapshots {
+        let (enforcement, rest): (Vec<_>, Vec<_>) = markers
+            .into_iter()
+            .partition(|m| m.provider == snapshot.provider && m.created_at <= snapshot.captured_at);
+        markers = rest;
+
+        let entry = match previous.get(&snapshot.provider) {
+            Some(prev) => TimelineEntry {
+                previous_snapshot_id: Some(prev.id),
+                flagged_tracks_change: snapshot.flagged_tracks - prev.flagged_tracks,
+                flagged_share_change: snapshot.flagged_share - prev.flagged_share,
+                blocked_tracks_change: snapshot.blocked_tracks - prev.blocked_tracks,
+                flagged_revenue_share_change: snapshot.flagged_revenue_share
+                    - prev.flagged_revenue_share,
+                enforcement,
+                snapshot: snapshot.clone(),
+            },
+            None => TimelineEntry {
+                previous_snapshot_id: None,
+                flagged_tracks_change: 0,
+                flagged_share_change: 0.0,
+                blocked_tracks_change: 0,
+                flagged_revenue_share_change: 0.0,
+                enforcement,
+                snapshot: snapshot.clone(),
+            },
+        };
+
+        previous.insert(snapshot.provider.clone(), snapshot);
+        entries.push(entry);
+    }
+
+    (entries, markers)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct SnapshotRow {
+    id: Uuid,
+    provider: String,
+    total_tracks: i32,
+    flagged_tracks: i32,
+    blocked_tracks: i32,
+    tracks_by_category: serde_json::Value,
+    tracks_by_tier: serde_json::Value,
+    total_revenue: Decimal,
+    flagged_revenue: Decimal,
+    regressions_detected: i32,
+    captured_at: DateTime<Utc>,
+}
+
+impl From<SnapshotRow> for CompositionSnapshot {
+    fn from(row: SnapshotRow) -> Self {
+        let total = row.total_tracks as i64;
+        let counts = |value: serde_json::Value| -> BTreeMap<String, i64> {
+            serde_json::from_value(value).unwrap_or_default()
+        };
+        let shares = |counts: &BTreeMap<String, i64>| -> BTreeMap<String, f64> {
+            counts
+                .iter()
+                .map(|(key, tracks)| (key.clone(), share(*tracks, total)))
+                .collect()
+        };
+
+        let tracks_by_category = counts(row.tracks_by_category);
+        let tracks_by_tier = counts(row.tracks_by_tier);
+        let flagged_revenue_share = if row.total_revenue > Decimal::ZERO {
+            (row.flagged_revenue / row.total_revenue)
+                .to_f64()
+                .unwrap_or(0.0)
+        } else {
+            0.0
+        };
+
+        Self {
+            id: row.id,
+            provider: row.provider,
+            captured_at: row.captured_at,
+            total_tracks: total,
+            flagged_tracks: row.flagged_tracks as i64,
+            blocked_tracks: row.blocked_tracks as i64,
+            flagged_share: share(row.flagged_tracks as i64, total),
+            blocked_share: share(row.blocked_tracks as i64, total),
+            category_share: shares(&tracks_by_category),
+            tracks_by_category,
+            tier_share: shares(&tracks_by_tier),
+            tracks_by_tier,
+            total_revenue: row.total_revenue,
+            flagged_revenue: row.flagged_revenue,
+            flagged_revenue_share,
+            regressions_detected: row.regressions_detected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(provider: &str, hours_ago: i64, total: i32, flagged: i32) -> CompositionSnapshot {
+        CompositionSnapshot::from(SnapshotRow {
+            id: Uuid::new_v4(),
+            provider: provider.to_string(),
+            total_tracks: total,
+            flagged_tracks: flagged,
+            blocked_tracks: flagged,
+            tracks_by_category: serde_json::json!({ "violent_crimes": flagged }),
+            tracks_by_tier: serde_json::json!({}),
+            total_revenue: Decimal::new(200, 2),
+            flagged_revenue: Decimal::new(50, 2),
+            regressions_detected: 0,
+            captured_at: Utc::now() - Duration::hours(hours_ago),
+        })
+    }
+
+    fn marker(provider: &str, hours_ago: i64) -> EnforcementMarker {
+        EnforcementMarker {
+            batch_id: Uuid::new_v4(),
+            provider: provider.to_string(),
+            created_at: Utc::now() - Duration::hours(hours_ago),
+            completed_removals: 5,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_shares() {
+        let snap = snapshot("spotify", 1, 200, 50);
+        assert_eq!(snap.flagged_share, 0.25);
+        assert_eq!(snap.category_share["violent_crimes"], 0.25);
+        assert_eq!(snap.flagged_revenue_share, 0.25);
+    }
+
+    #[test]
+    fn test_timeline_assigns_enforcement_between_snapshots() {
+        let snapshots = vec![
+            snapshot("spotify", 48, 200, 50),
+            snapshot("tidal", 40, 100, 10),
+            snapshot("spotify", 24, 190, 40),
+        ];
+        let markers = vec![
+            marker("spotify", 30),
+            marker("tidal", 30),
+            marker("spotify", 2),
+        ];
+
+        let (entries, pending) = build_timeline(snapshots, markers);
+
+        assert_eq!(entries.len(), 3);
+        assert!(entries[0].previous_snapshot_id.is_none());
+        assert_eq!(entries[2].flagged_tracks_change, -10);
+        assert_eq!(entries[2].enforcement.len(), 1);
+        assert_eq!(pending.len(), 2);
+    }
+}
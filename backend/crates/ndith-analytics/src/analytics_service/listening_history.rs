@@ -0,0 +1,821 @@
+//! Listening History Ingestion
+//!
+//! Imports real listening data so revenue attribution reflects what users
+//! actually played:
+//! - Spotify extended streaming history (and the older account-data export)
+//! - Apple Music "Play Activity" CSV
+//! - Google Takeout YouTube Music watch history
+//! - Last.fm scrobble exports
+//! - Provider recently-played APIs (fed in by the scheduled sync job)
+//!
+//! Plays are stored individually (deduplicated, so re-importing an export is
+//! harmless), resolved to canonical artists, and rolled up into monthly
+//! `user_artist_playcounts` periods. Plays that can't be resolved yet are kept
+//! and attributed on a later import once the artist exists.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, SubsecRound, TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::revenue::{record_playcount_with_rate, Platform, RecordPlaycountParams, RevenueService};
+
+/// Plays shorter than this don't count as a stream for royalty purposes
+pub const MIN_STREAM_MS: i64 = 30_000;
+
+/// Rows inserted per statement when storing plays
+const INSERT_CHUNK_SIZE: usize = 1000;
+
+/// Where a batch of listening events came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListeningSource {
+    SpotifyExtendedHistory,
+    AppleMusicPlayActivity,
+    YoutubeTakeout,
+    LastfmScrobbles,
+    SpotifyRecentlyPlayed,
+}
+
+impl ListeningSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListeningSource::SpotifyExtendedHistory => "spotify_extended_history",
+            ListeningSource::AppleMusicPlayActivity => "apple_music_play_activity",
+            ListeningSource::YoutubeTakeout => "youtube_takeout",
+            ListeningSource::LastfmScrobbles => "lastfm_scrobbles",
+            ListeningSource::SpotifyRecentlyPlayed => "spotify_recently_played",
+        }
+    }
+
+    pub fn parse_source(s: &str) -> Option<Self> {
+        match s {
+            "spotify_extended_history" => Some(ListeningSource::SpotifyExtendedHistory),
+            "apple_music_play_activity" => Some(ListeningSource::AppleMusicPlayActivity),
+            "youtube_takeout" => Some(ListeningSource::YoutubeTakeout),
+            "lastfm_scrobbles" => Some(ListeningSource::LastfmScrobbles),
+            "spotify_recently_played" => Some(ListeningSource::SpotifyRecentlyPlayed),
+            _ => None,
+        }
+    }
+
+    /// Platform the plays were streamed on. Last.fm scrobbles come from any
+    /// player, so the platform must be supplied by the user.
+    pub fn platform(&self) -> Option<Platform> {
+        match self {
+            ListeningSource::SpotifyExtendedHistory | ListeningSource::SpotifyRecentlyPlayed => {
+                Some(Platform::Spotify)
+            }
+            ListeningSource::AppleMusicPlayActivity => Some(Platform::AppleMusic),
+            ListeningSource::YoutubeTakeout => Some(Platform::YouTubeMusic),
+            ListeningSource::LastfmScrobbles => None,
+        }
+    }
+}
+
+/// A single play
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListeningEvent {
+    pub played_at: DateTime<Utc>,
+    pub artist_name: String,
+    pub track_name: String,
+    pub album_name: Option<String>,
+    /// How long the track was played, when the source reports it
+    pub ms_played: Option<i64>,
+}
+
+impl ListeningEvent {
+    /// The form plays are stored and deduplicated in. Play times are kept to
+    /// the second: the Spotify API reports milliseconds while the exports
+    /// don't, and the same play must match whichever source it came from.
+    pub fn normalized(mut self) -> Self {
+        self.played_at = self.played_at.trunc_subsecs(0);
+        self.artist_name = self.artist_name.trim().to_string();
+        self.track_name = self.track_name.trim().to_string();
+        self
+    }
+}
+
+/// Result of parsing an export file
+#[derive(Debug, Clone, Default)]
+pub struct ParsedExport {
+    pub events: Vec<ListeningEvent>,
+    /// Plays under `MIN_STREAM_MS`, which don't count as streams
+    pub skipped_short: usize,
+    /// Entries without an artist or timestamp (podcasts, videos, bad rows)
+    pub skipped_invalid: usize,
+}
+
+impl ParsedExport {
+    fn push(&mut self, event: Option<ListeningEvent>) {
+        match event {
+            Some(event) if event.ms_played.is_some_and(|ms| ms < MIN_STREAM_MS) => {
+                self.skipped_short += 1
+            }
+            Some(event) => self.events.push(event.normalized()),
+            None => self.skipped_invalid += 1,
+        }
+    }
+}
+
+/// Parse an uploaded export for the given source
+pub fn parse_export(source: ListeningSource, data: &[u8]) -> Result<ParsedExport> {
+    match source {
+        ListeningSource::SpotifyExtendedHistory => parse_spotify_history(data),
+        ListeningSource::AppleMusicPlayActivity => parse_apple_music_play_activity(data),
+        ListeningSource::YoutubeTakeout => parse_youtube_takeout(data),
+        ListeningSource::LastfmScrobbles => parse_lastfm_scrobbles(data),
+        ListeningSource::SpotifyRecentlyPlayed => {
+            bail!("Recently-played plays are synced from the Spotify API, not uploaded")
+        }
+    }
+}
+
+/// Spotify extended streaming history (`Streaming_History_Audio_*.json`), or
+/// the older account-data `StreamingHistory*.json` format
+pub fn parse_spotify_history(data: &[u8]) -> Result<ParsedExport> {
+    let entries: Vec<Value> =
+        serde_json::from_slice(data).context("Spotify history must be a JSON array")?;
+
+    let mut parsed = ParsedExport::default();
+    for entry in &entries {
+        parsed.push(spotify_event(entry));
+    }
+
+    Ok(parsed)
+}
+
+fn spotify_event(entry: &Value) -> Option<ListeningEvent> {
+    if entry.get("ts").is_some() {
+        // Extended history; podcast episodes have no track metadata. Despite
+        // its name, `master_metadata_album_artist_name` is the track's primary
+        // artist, matching the first artist the recently-played API lists.
+        Some(ListeningEvent {
+            played_at: parse_timestamp(entry["ts"].as_str()?)?,
+            artist_name: non_empty(entry["master_metadata_album_artist_name"].as_str())?,
+            track_name: non_empty(entry["master_metadata_track_name"].as_str())?,
+            album_name: non_empty(entry["master_metadata_album_album_name"].as_str()),
+            ms_played: entry["ms_played"].as_i64(),
+        })
+    } else {
+        Some(ListeningEvent {
+            played_at: parse_timestamp(entry["endTime"].as_str()?)?,
+            artist_name: non_empty(entry["artistName"].as_str())?,
+            track_name: non_empty(entry["trackName"].as_str())?,
+            album_name: None,
+            ms_played: entry["msPlayed"].as_i64(),
+        })
+    }
+}
+
+/// Apple Music "Play Activity" CSV from the Apple privacy data export
+pub fn parse_apple_music_play_activity(data: &[u8]) -> Result<ParsedExport> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(data);
+    let headers = reader
+        .headers()
+        .context("Apple Music play activity must be a CSV with a header row")?
+        .clone();
+
+    let column = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| headers.iter().position(|h| h.trim() == *name))
+    };
+    let timestamp_col = column(&["Event Start Timestamp", "Event End Timestamp"])
+        .ok_or_else(|| anyhow!("Missing 'Event Start Timestamp' column"))?;
+    let artist_col = column(&["Artist Name", "Container Artist Name"])
+        .ok_or_else(|| anyhow!("Missing 'Artist Name' column"))?;
+    let track_col = column(&["Song Name", "Content Name"]);
+    let album_col = column(&["Album Name", "Container Album Name"]);
+    let duration_col = column(&["Play Duration Milliseconds"]);
+    let event_type_col = column(&["Event Type"]);
+
+    fn field(record: &csv::StringRecord, col: Option<usize>) -> Option<&str> {
+        col.and_then(|c| record.get(c)).map(str::trim)
+    }
+    let to_event = |record: &csv::StringRecord| {
+        Some(ListeningEvent {
+            played_at: parse_timestamp(field(record, Some(timestamp_col))?)?,
+            artist_name: non_empty(field(record, Some(artist_col)))?,
+            track_name: non_empty(field(record, track_col)).unwrap_or_default(),
+            album_name: non_empty(field(record, album_col)),
+            ms_played: field(record, duration_col).and_then(|d| d.parse::<i64>().ok()),
+        })
+    };
+
+    let mut parsed = ParsedExport::default();
+    for record in reader.records() {
+        let Ok(record) = record else {
+            parsed.skipped_invalid += 1;
+            continue;
+        };
+
+        // Only completed plays; starts, lyric views and the like are separate rows
+        if field(&record, event_type_col).is_some_and(|t| !t.is_empty() && t != "PLAY_END") {
+            continue;
+        }
+
+        parsed.push(to_event(&record));
+    }
+
+    Ok(parsed)
+}
+
+/// Google Takeout `watch-history.json`, keeping YouTube Music plays only
+pub fn parse_youtube_takeout(data: &[u8]) -> Result<ParsedExport> {
+    let entries: Vec<Value> =
+        serde_json::from_slice(data).context("Takeout watch history must be a JSON array")?;
+
+    let mut parsed = ParsedExport::default();
+    for entry in entries
+        .iter()
+        .filter(|e| e["header"].as_str() == Some("YouTube Music"))
+    {
+        parsed.push(youtube_event(entry));
+    }
+
+    Ok(parsed)
+}
+
+fn youtube_event(entry: &Value) -> Option<ListeningEvent> {
+    let title = entry["title"].as_str()?;
+    let channel = entry["subtitles"].as_array()?.first()?["name"].as_str()?;
+    Some(ListeningEvent {
+        played_at: parse_timestamp(entry["time"].as_str()?)?,
+        // Auto-generated artist channels are named "<Artist> - Topic"
+        artist_name: non_empty(Some(channel.trim_end_matches(" - Topic")))?,
+        track_name: title.strip_prefix("Watched ").unwrap_or(title).to_string(),
+        album_name: None,
+        ms_played: None,
+    })
+}
+
+/// Last.fm scrobble export CSV, either headerless `artist,album,track,date`
+/// rows or a header row naming `artist`, `album`, `track` and `uts`/`utc_time`/`date`
+pub fn parse_lastfm_scrobbles(data: &[u8]) -> Result<ParsedExport> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(data);
+    let mut records = reader.records();
+
+    let first = match records.next() {
+        Some(record) => record.context("Invalid Last.fm scrobble CSV")?,
+        None => return Ok(ParsedExport::default()),
+    };
+
+    let lower: Vec<String> = first.iter().map(|h| h.trim().to_lowercase()).collect();
+    let position = |names: &[&str]| names.iter().find_map(|n| lower.iter().position(|h| h == n));
+
+    // A header row names the date column; a headerless first row is a
+    // scrobble, and artists can be called anything (including "Artist")
+    let header_date_col = position(&["uts", "utc_time", "date"]);
+    let has_header = header_date_col.is_some();
+
+    let (artist_col, album_col, track_col, date_col) = match header_date_col {
+        Some(date_col) => (
+            position(&["artist"]).ok_or_else(|| anyhow!("Missing scrobble artist column"))?,
+            position(&["album"]),
+            position(&["track"]),
+            date_col,
+        ),
+        None => (0, Some(1), Some(2), 3),
+    };
+
+    let mut parsed = ParsedExport::default();
+    let parse_row = |record: &csv::StringRecord| {
+        Some(ListeningEvent {
+            played_at: parse_timestamp(record.get(date_col)?.trim())?,
+            artist_name: non_empty(record.get(artist_col))?,
+            track_name: non_empty(track_col.and_then(|c| record.get(c))).unwrap_or_default(),
+            album_name: non_empty(album_col.and_then(|c| record.get(c))),
+            // Last.fm only scrobbles plays past half the track (min 30s)
+            ms_played: None,
+        })
+    };
+
+    if !has_header {
+        parsed.push(parse_row(&first));
+    }
+    for record in records {
+        match record {
+            Ok(record) => parsed.push(parse_row(&record)),
+            Err(_) => parsed.skipped_invalid += 1,
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Parse the timestamp formats used across exports: RFC 3339, Unix seconds,
+/// "YYYY-MM-DD HH:MM" (Spotify account data) and "DD Mon YYYY HH:MM" (Last.fm)
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    if let Ok(secs) = value.parse::<i64>() {
+        return Utc.timestamp_opt(secs, 0).single();
+    }
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%d %b %Y %H:%M",
+        "%d %b %Y, %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .map(|naive| naive.and_utc())
+}
+
+/// A stored play that has been resolved to a canonical artist
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AttributedPlay {
+    pub artist_id: Uuid,
+    pub platform: String,
+    pub played_at: DateTime<Utc>,
+    pub ms_played: Option<i64>,
+}
+
+/// Plays for one artist on one platform in one calendar month
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonthlyPlaycount {
+    pub artist_id: Uuid,
+    pub platform: String,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub play_count: i32,
+    pub listening_time_ms: Option<i64>,
+}
+
+/// Roll plays up into monthly playcount periods, ordered by month then artist
+pub fn aggregate_monthly(plays: &[AttributedPlay]) -> Vec<MonthlyPlaycount> {
+    let mut periods: HashMap<(Uuid, String, NaiveDate), MonthlyPlaycount> = HashMap::new();
+
+    for play in plays {
+        let date = play.played_at.date_naive();
+        let period_start = date.with_day(1).unwrap_or(date);
+        let next_month = if period_start.month() == 12 {
+            NaiveDate::from_ymd_opt(period_start.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(period_start.year(), period_start.month() + 1, 1)
+        };
+        let period_end = next_month
+            .and_then(|d| d.pred_opt())
+            .unwrap_or(period_start);
+
+        let entry = periods
+            .entry((play.artist_id, play.platform.clone(), period_start))
+            .or_insert_with(|| MonthlyPlaycount {
+                artist_id: play.artist_id,
+                platform: play.platform.clone(),
+                period_start,
+                period_end,
+                play_count: 0,
+                listening_time_ms: None,
+            });
+        entry.play_count += 1;
+        if let Some(ms) = play.ms_played {
+            entry.listening_time_ms = Some(entry.listening_time_ms.unwrap_or(0) + ms);
+        }
+    }
+
+    let mut aggregated: Vec<MonthlyPlaycount> = periods.into_values().collect();
+    aggregated.sort_by(|a, b| {
+        (a.period_start, a.artist_id, &a.platform).cmp(&(b.period_start, b.artist_id, &b.platform))
+    });
+    aggregated
+}
+
+/// Outcome of ingesting a batch of plays
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListeningImportSummary {
+    pub import_id: Uuid,
+    pub source: ListeningSource,
+    pub platform: String,
+    pub events_received: usize,
+    pub events_new: usize,
+    pub duplicates: usize,
+    pub skipped_short: usize,
+    pub skipped_invalid: usize,
+    /// Plays (from this or earlier imports) newly attributed to an artist
+    pub plays_attributed: usize,
+    pub playcount_periods_updated: usize,
+    /// Plays still waiting on an artist match
+    pub unresolved_plays: i64,
+    /// Most-played artist names that couldn't be matched
+    pub top_unresolved_artists: Vec<String>,
+}
+
+/// Listening history service
+pub struct ListeningHistoryService {
+    pool: PgPool,
+}
+
+impl ListeningHistoryService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Parse an uploaded export and ingest its plays
+    pub async fn import_export(
+        &self,
+        user_id: Uuid,
+        source: ListeningSource,
+        platform: Option<Platform>,
+        data: &[u8],
+    ) -> Result<ListeningImportSummary> {
+        let platform = source
+            .platform()
+            .or(platform)
+            .ok_or_else(|| anyhow!("A platform is required for {} imports", source.as_str()))?;
+
+        let parsed = parse_export(source, data)?;
+        self.ingest(user_id, source, platform, parsed).await
+    }
+
+    /// Store plays, attribute them to canonical artists and add them to the
+    /// user's monthly playcounts. Safe to call repeatedly with overlapping
+    /// data: plays already stored are ignored.
+    pub async fn ingest(
+        &self,
+        user_id: Uuid,
+        source: ListeningSource,
+        platform: Platform,
+        parsed: ParsedExport,
+    ) -> Result<ListeningImportSummary> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start listening history import")?;
+
+        // Plays synced from provider APIs arrive without going through a parser
+        let events: Vec<ListeningEvent> = parsed
+            .events
+            .into_iter()
+            .map(ListeningEvent::normalized)
+            .collect();
+
+        let mut events_new = 0;
+        for chunk in events.chunks(INSERT_CHUNK_SIZE) {
+            let mut qb: sqlx::QueryBuilder<sqlx::Postgres> = sqlx::QueryBuilder::new(
+                "INSERT INTO listening_history (user_id, platform, source, played_at, artist_name, track_name, album_name, ms_played) ",
+            );
+            qb.push_values(chunk, |mut b, event| {
+                b.push_bind(user_id)
+                    .push_bind(platform.as_str())
+                    .push_bind(source.as_str())
+                    .push_bind(event.played_at)
+                    .push_bind(&event.artist_name)
+                    .push_bind(&event.track_name)
+                    .push_bind(&event.album_name)
+                    .push_bind(event.ms_played);
+            });
+            qb.push(
+                " ON CONFLICT (user_id, platform, played_at, artist_name, track_name) DO NOTHING",
+            );
+
+            let result = qb
+                .build()
+                .execute(&mut *tx)
+                .await
+                .context("Failed to store listening history")?;
+            events_new += result.rows_affected() as usize;
+        }
+
+        // Attribute every still-unresolved play for this user, so plays from
+        // earlier imports are picked up once their artist is known
+        let attributed = sqlx::query_as::<_, AttributedPlay>(
+            r#"
+            UPDATE listening_history lh
+            SET artist_id = COALESCE(a.canonical_artist_id, a.id)
+            FROM artists a
+            WHERE lh.user_id = $1
+            AND lh.artist_id IS NULL
+            AND LOWER(a.canonical_name) = LOWER(lh.artist_name)
+            RETURNING lh.artist_id, lh.platform, lh.played_at, lh.ms_played
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to resolve listening history artists")?;
+
+        // Each month is priced at the rate in effect then; earlier unresolved
+        // plays may also be from another platform
+        let revenue = RevenueService::new(self.pool.clone());
+        let mut rates: HashMap<(String, NaiveDate), Decimal> = HashMap::new();
+
+        let periods = aggregate_monthly(&attributed);
+        for period in &periods {
+            let period_platform = Platform::parse_platform(&period.platform)
+                .ok_or_else(|| anyhow!("Unknown platform '{}'", period.platform))?;
+            let key = (period.platform.clone(), period.period_start);
+            let rate = match rates.get(&key) {
+                Some(rate) => *rate,
+                None => {
+                    let rate = revenue
+                        .get_period_rate(period_platform, period.period_start)
+                        .await?;
+                    rates.insert(key, rate);
+                    rate
+                }
+            };
+
+            record_playcount_with_rate(
+                &mut tx,
+                &RecordPlaycountParams {
+                    user_id,
+                    artist_id: period.artist_id,
+                    platform: period_platform,
+                    play_count: period.play_count,
+                    listening_time_ms: period.listening_time_ms,
+                    period_start: period.period_start,
+                    period_end: period.period_end,
+                },
+                rate,
+            )
+            .await?;
+        }
+
+        let (unresolved_plays,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM listening_history WHERE user_id = $1 AND artist_id IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to count unresolved plays")?;
+
+        let top_unresolved_artists: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT artist_name
+            FROM listening_history
+            WHERE user_id = $1 AND artist_id IS NULL
+            GROUP BY artist_name
+            ORDER BY COUNT(*) DESC
+            LIMIT 20
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to list unresolved artists")?;
+
+        let summary = ListeningImportSummary {
+            import_id: Uuid::new_v4(),
+            source,
+            platform: platform.as_str().to_string(),
+            events_received: events.len() + parsed.skipped_short + parsed.skipped_invalid,
+            events_new,
+            duplicates: events.len() - events_new,
+            skipped_short: parsed.skipped_short,
+            skipped_invalid: parsed.skipped_invalid,
+            plays_attributed: attributed.len(),
+            playcount_periods_updated: periods.len(),
+            unresolved_plays,
+            top_unresolved_artists,
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO listening_history_imports (
+                id, user_id, source, platform, events_received, events_new,
+                skipped_short, skipped_invalid, plays_attributed, playcount_periods_updated
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(summary.import_id)
+        .bind(user_id)
+        .bind(source.as_str())
+        .bind(platform.as_str())
+        .bind(summary.events_received as i32)
+        .bind(summary.events_new as i32)
+        .bind(summary.skipped_short as i32)
+        .bind(summary.skipped_invalid as i32)
+        .bind(summary.plays_attributed as i32)
+        .bind(summary.playcount_periods_updated as i32)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to record listening history import")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit listening history import")?;
+
+        tracing::info!(
+            user_id = %user_id,
+            source = source.as_str(),
+            events_new = summary.events_new,
+            plays_attributed = summary.plays_attributed,
+            "Listening history ingested"
+        );
+
+        Ok(summary)
+    }
+
+    /// Latest play already synced from a provider API, used as the cursor for
+    /// the next recently-played fetch
+    pub async fn get_sync_cursor(
+        &self,
+        user_id: Uuid,
+        platform: Platform,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let cursor: Option<Option<DateTime<Utc>>> = sqlx::query_scalar(
+            "SELECT last_played_at FROM listening_history_sync_state WHERE user_id = $1 AND platform = $2",
+        )
+        .bind(user_id)
+        .bind(platform.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch listening history sync cursor")?;
+
+        Ok(cursor.flatten())
+    }
+
+    /// Record the outcome of a provider sync. The cursor only moves forward.
+    pub async fn record_sync(
+        &self,
+        user_id: Uuid,
+        platform: Platform,
+        last_played_at: Option<DateTime<Utc>>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO listening_history_sync_state (user_id, platform, last_played_at, last_synced_at, last_error)
+            VALUES ($1, $2, $3, NOW(), $4)
+            ON CONFLICT (user_id, platform) DO UPDATE SET
+                last_played_at = GREATEST(listening_history_sync_state.last_played_at, EXCLUDED.last_played_at),
+                last_synced_at = NOW(),
+                last_error = EXCLUDED.last_error
+            "#,
+        )
+        .bind(user_id)
+        .bind(platform.as_str())
+        .bind(last_played_at)
+        .bind(error)
+        .execute(&self.pool)
+        .await
+        .context("Failed to record listening history sync")?;
+
+        Ok(())
+    }
+
+    /// Past imports for a user, newest first
+    pub async fn get_imports(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<ListeningImportRecord>> {
+        sqlx::query_as::<_, ListeningImportRecord>(
+            r#"
+            SELECT id, source, platform, events_received, events_new, skipped_short,
+                   skipped_invalid, plays_attributed, playcount_periods_updated, created_at
+            FROM listening_history_imports
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch listening history imports")
+    }
+}
+
+/// A past import as stored
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ListeningImportRecord {
+    pub id: Uuid,
+    pub source: String,
+    pub platform: String,
+    pub events_received: i32,
+    pub events_new: i32,
+    pub skipped_short: i32,
+    pub skipped_invalid: i32,
+    pub plays_attributed: i32,
+    pub playcount_periods_updated: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spotify_extended_history() {
+        let data = br#"[
+            {"ts": "2024-03-01T10:00:00Z", "ms_played": 180000,
+             "master_metadata_track_name": "Song", "master_metadata_album_artist_name": "Artist",
+             "master_metadata_album_album_name": "Album"},
+            {"ts": "2024-03-01T10:05:00Z", "ms_played": 5000,
+             "master_metadata_track_name": "Skipped", "master_metadata_album_artist_name": "Artist"},
+            {"ts": "2024-03-01T11:00:00Z", "ms_played": 600000,
+             "master_metadata_track_name": null, "episode_name": "Podcast"}
+        ]"#;
+
+        let parsed = parse_spotify_history(data).unwrap();
+        assert_eq!(parsed.events.len(), 1);
+        assert_eq!(parsed.events[0].artist_name, "Artist");
+        assert_eq!(parsed.events[0].album_name.as_deref(), Some("Album"));
+        assert_eq!(parsed.skipped_short, 1);
+        assert_eq!(parsed.skipped_invalid, 1);
+    }
+
+    #[test]
+    fn test_parse_lastfm_headerless() {
+        let data = b"Artist,Album,Track,01 Mar 2024 10:00\nOther,,Song,02 Mar 2024 11:30\n";
+
+        let parsed = parse_lastfm_scrobbles(data).unwrap();
+        assert_eq!(parsed.events.len(), 2);
+        assert_eq!(parsed.events[1].artist_name, "Other");
+        assert_eq!(parsed.events[1].album_name, None);
+        assert_eq!(
+            parsed.events[0].played_at,
+            Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_lastfm_with_header() {
+        let data = b"uts,utc_time,artist,artist_mbid,album,album_mbid,track,track_mbid\n\
+            1709287200,01 Mar 2024 10:00,Artist,,Album,,Song,\n";
+
+        let parsed = parse_lastfm_scrobbles(data).unwrap();
+        assert_eq!(parsed.events.len(), 1);
+        assert_eq!(parsed.events[0].artist_name, "Artist");
+        assert_eq!(parsed.events[0].track_name, "Song");
+        assert_eq!(
+            parsed.events[0].played_at,
+            Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_api_and_export_plays_normalize_alike() {
+        let exported = parse_spotify_history(
+            br#"[{"ts": "2024-03-01T10:00:00Z", "ms_played": 180000,
+                  "master_metadata_track_name": "Song",
+                  "master_metadata_album_artist_name": "Artist"}]"#,
+        )
+        .unwrap()
+        .events
+        .remove(0);
+        let synced = ListeningEvent {
+            played_at: DateTime::parse_from_rfc3339("2024-03-01T10:00:00.456Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            artist_name: "Artist".to_string(),
+            track_name: "Song".to_string(),
+            album_name: None,
+            ms_played: None,
+        }
+        .normalized();
+
+        assert_eq!(synced.played_at, exported.played_at);
+        assert_eq!(synced.artist_name, exported.artist_name);
+    }
+
+    #[test]
+    fn test_aggregate_monthly() {
+        let artist = Uuid::new_v4();
+        let play = |day: u32, month: u32, ms: Option<i64>| AttributedPlay {
+            artist_id: artist,
+            platform: "spotify".to_string(),
+            played_at: Utc.with_ymd_and_hms(2024, month, day, 12, 0, 0).unwrap(),
+            ms_played: ms,
+        };
+
+        let periods = aggregate_monthly(&[
+            play(1, 2, Some(60_000)),
+            play(29, 2, None),
+            play(1, 12, Some(30_000)),
+        ]);
+
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[0].play_count, 2);
+        assert_eq!(periods[0].listening_time_ms, Some(60_000));
+        assert_eq!(
+            periods[0].period_end,
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        assert_eq!(
+            periods[1].period_end,
+            NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()
+        );
+    }
+}
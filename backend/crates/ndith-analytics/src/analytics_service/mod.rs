@@ -5,15 +5,31 @@
 //! - Trend analysis for artists and offenses
 //! - Platform sync performance
 //! - System health monitoring
-//! - Artist trouble scores
+//! - Artist trouble scores with versioned scoring profiles
+//! - Report rendering and scheduled delivery
 //! - Revenue tracking and distribution
+//! - Payout rate management with change history
+//! - Pro-rata and user-centric payout models
+//! - Listening history ingestion
 //! - Enforcement analytics
+//! - Enforcement impact on library composition
+//! - Cohort retention and conversion funnels
+//! - Postgres to DuckDB replication with freshness metadata
 
 pub mod category_revenue;
+pub mod cohorts;
 pub mod dashboard;
 pub mod enforcement;
+pub mod library_impact;
+pub mod listening_history;
+pub mod payout_model;
+pub mod payout_rates;
+pub mod replication;
+pub mod report_render;
+pub mod report_scheduler;
 pub mod reporting;
 pub mod revenue;
+pub mod score_profile;
 pub mod trends;
 pub mod trouble_score;
 
@@ -21,18 +37,52 @@ pub use category_revenue::{
     AlbumRevenue, ArtistDiscographyRevenue, CategoryArtistRevenue, CategoryRevenue,
     CategoryRevenueService, GlobalCategoryRevenue, OffenseCategory, SimulationParams,
 };
+pub use cohorts::{
+    CohortAnalyticsService, CohortQuery, CohortRow, Funnel, FunnelQuery, FunnelReport,
+    FunnelStepResult, LifecycleStage, RetentionMatrix,
+};
 pub use dashboard::{DashboardMetrics, DashboardService, TimeRange};
 pub use enforcement::{
     ActionTypeCount, EnforcementAnalytics, EnforcementAnalyticsQuery, EnforcementAnalyticsService,
     EnforcementStats, EnforcementTimeSeriesPoint, ProviderStats,
 };
-pub use reporting::{Report, ReportType, ReportingService};
+pub use library_impact::{
+    CompositionSnapshot, EnforcementMarker, LibraryImpactService, LibraryImpactTimeline,
+    LibraryRegression, TimelineEntry,
+};
+pub use listening_history::{
+    ListeningEvent, ListeningHistoryService, ListeningImportRecord, ListeningImportSummary,
+    ListeningSource, ParsedExport,
+};
+pub use payout_model::{
+    ModelArtistPayout, PayoutModel, PayoutModelKind, PayoutModelSummary, ProRataModel,
+    UserCentricModel,
+};
+pub use payout_rates::{
+    NewPayoutRate, PayoutRateChange, PayoutRateCorrection, PayoutRateEntry, PayoutRateFilter,
+    PayoutRateService, PayoutRateUpdate,
+};
+pub use replication::{
+    DataFreshness, ReplicatedTable, ReplicationRunStats, ReplicationService, ReplicationStatus,
+};
+pub use report_render::{ReportDocument, ReportSection};
+pub use report_scheduler::{FilesystemSink, ReportScheduler, ReportSink, SchedulerRunStats};
+pub use reporting::{
+    Report, ReportFile, ReportFormat, ReportRequest, ReportSchedule, ReportStatus, ReportType,
+    ReportingService, ScheduledReport,
+};
 pub use revenue::{
     ArtistRevenueBreakdown, GlobalArtistRevenue, PayoutRate, Platform as RevenuePlatform,
     PlatformRevenue, RecordPlaycountParams, RevenueService, UserPlaycount, UserRevenueDistribution,
 };
-pub use trends::{TrendAnalysisService, TrendData, TrendDirection};
+pub use score_profile::{
+    ComponentContribution, ProfileRef, ScoreExplanation, ScoreProfile, SimulatedScore, WhatIfResult,
+};
+pub use trends::{
+    ArtistTrend, TrendAnalysisService, TrendConfig, TrendData, TrendDirection, TrendSummary,
+};
 pub use trouble_score::{
-    ArtistTroubleScore, RecalculationSummary, ScoreHistoryEntry, ScoreWeights, TierDistribution,
-    TroubleLeaderboardEntry, TroubleScoreComponents, TroubleScoreService, TroubleTier,
+    ArtistTroubleScore, NewScoreProfile, RecalculationSummary, ScoreHistoryEntry, ScoreWeights,
+    TierDistribution, TroubleLeaderboardEntry, TroubleScoreComponents, TroubleScoreService,
+    TroubleTier,
 };
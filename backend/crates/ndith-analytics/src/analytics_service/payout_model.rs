@@ -0,0 +1,325 @@
+//! Payout Models
+//!
+//! How a user's listening turns into artist revenue depends on how the
+//! platform splits its royalty pool:
+//! - Pro-rata (market share): revenue in a market is pooled and divided by
+//!   each artist's share of all streams, so every play earns the market's
+//!   per-stream rate no matter what the listener paid.
+//! - User-centric: each subscriber's payment is divided only between the
+//!   artists they played, in proportion to their own plays.
+//!
+//! Both models run over the same monthly playcounts so a user's revenue
+//! distribution can show them side by side.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::revenue::PayoutRate;
+use super::trouble_score::TroubleTier;
+
+/// Supported payout models
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutModelKind {
+    ProRata,
+    UserCentric,
+}
+
+impl PayoutModelKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PayoutModelKind::ProRata => "pro_rata",
+            PayoutModelKind::UserCentric => "user_centric",
+        }
+    }
+
+    pub fn parse_model(s: &str) -> Option<Self> {
+        match s {
+            "pro_rata" => Some(PayoutModelKind::ProRata),
+            "user_centric" => Some(PayoutModelKind::UserCentric),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<PayoutModelKind> {
+        vec![PayoutModelKind::ProRata, PayoutModelKind::UserCentric]
+    }
+}
+
+/// Plays of one artist within a billing period
+#[derive(Debug, Clone)]
+pub struct ArtistPlays {
+    pub artist_id: Uuid,
+    pub play_count: i64,
+}
+
+/// A user's plays on one platform for one month, with the rate in effect
+#[derive(Debug, Clone)]
+pub struct BillingPeriod {
+    pub platform: String,
+    pub period_start: NaiveDate,
+    pub rate: PayoutRate,
+    pub plays: Vec<ArtistPlays>,
+}
+
+/// Splits a billing period's plays into per-artist payouts
+pub trait PayoutModel: Send + Sync {
+    fn kind(&self) -> PayoutModelKind;
+
+    fn allocate(&self, period: &BillingPeriod) -> Vec<(Uuid, Decimal)>;
+}
+
+/// Every play earns the market's per-stream rate
+pub struct ProRataModel;
+
+impl PayoutModel for ProRataModel {
+    fn kind(&self) -> PayoutModelKind {
+        PayoutModelKind::ProRata
+    }
+
+    fn allocate(&self, period: &BillingPeriod) -> Vec<(Uuid, Decimal)> {
+        period
+            .plays
+            .iter()
+            .map(|p| {
+                (
+                    p.artist_id,
+                    Decimal::from(p.play_count) * period.rate.rate_per_stream,
+                )
+            })
+            .collect()
+    }
+}
+
+/// The month's subscription royalties are split by the user's own plays
+pub struct UserCentricModel;
+
+impl PayoutModel for UserCentricModel {
+    fn kind(&self) -> PayoutModelKind {
+        PayoutModelKind::UserCentric
+    }
+
+    fn allocate(&self, period: &BillingPeriod) -> Vec<(Uuid, Decimal)> {
+        // Ad-supported tiers have no subscription to split, so plays there
+        // are paid from the shared pool either way
+        let pool = match period.rate.subscription_monthly {
+            Some(subscription) if subscription > Decimal::ZERO => {
+                subscription * period.rate.royalty_share
+            }
+            _ => return ProRataModel.allocate(period),
+        };
+
+        let total_plays: i64 = period.plays.iter().map(|p| p.play_count.max(0)).sum();
+        if total_plays == 0 {
+            return vec![];
+        }
+
+        period
+            .plays
+            .iter()
+            .map(|p| {
+                let share = Decimal::from(p.play_count.max(0)) / Decimal::from(total_plays);
+                (p.artist_id, (pool * share).round_dp(6))
+            })
+            .collect()
+    }
+}
+
+/// Model implementation for a kind
+pub fn payout_model(kind: PayoutModelKind) -> Box<dyn PayoutModel> {
+    match kind {
+        PayoutModelKind::ProRata => Box::new(ProRataModel),
+        PayoutModelKind::UserCentric => Box::new(UserCentricModel),
+    }
+}
+
+/// Artist details used when summarising payouts
+#[derive(Debug, Clone)]
+pub struct PayoutArtist {
+    pub artist_name: String,
+    pub trouble_tier: Option<TroubleTier>,
+}
+
+/// An artist's payout under one model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelArtistPayout {
+    pub artist_id: Uuid,
+    pub artist_name: String,
+    pub trouble_tier: Option<TroubleTier>,
+    pub streams: i64,
+    pub amount: Decimal,
+    pub percentage: f64,
+}
+
+/// Revenue distribution under one payout model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutModelSummary {
+    pub model: PayoutModelKind,
+    pub total_revenue: Decimal,
+    pub revenue_to_clean_artists: Decimal,
+    pub revenue_to_problematic_artists: Decimal,
+    pub problematic_percentage: f64,
+    /// Average amount paid per stream over the period
+    pub effective_rate_per_stream: Decimal,
+    pub top_artists: Vec<ModelArtistPayout>,
+}
+
+/// Run a model over every billing period and total the result per artist
+pub fn summarize_payouts(
+    model: &dyn PayoutModel,
+    periods: &[BillingPeriod],
+    artists: &HashMap<Uuid, PayoutArtist>,
+    limit: usize,
+) -> PayoutModelSummary {
+    let mut by_artist: HashMap<Uuid, (i64, Decimal)> = HashMap::new();
+    for period in periods {
+        for play in &period.plays {
+            by_artist.entry(play.artist_id).or_default().0 += play.play_count;
+        }
+        for (artist_id, amount) in model.allocate(period) {
+            by_artist.entry(artist_id).or_default().1 += amount;
+        }
+    }
+
+    let total_streams: i64 = by_artist.values().map(|(streams, _)| streams).sum();
+    let total_revenue: Decimal = by_artist.values().map(|(_, amount)| *amount).sum();
+
+    let mut problematic_revenue = Decimal::ZERO;
+    let mut payouts: Vec<ModelArtistPayout> = by_artist
+        .into_iter()
+        .map(|(artist_id, (streams, amount))| {
+            let artist = artists.get(&artist_id);
+            let trouble_tier = artist.and_then(|a| a.trouble_tier);
+            if is_problematic(trouble_tier) {
+                problematic_revenue += amount;
+            }
+
+            ModelArtistPayout {
+                artist_id,
+                artist_name: artist
+                    .map(|a| a.artist_name.clone())
+                    .unwrap_or_else(|| "Unknown".to_string()),
+                trouble_tier,
+                streams,
+                amount,
+                percentage: percentage(amount, total_revenue),
+            }
+        })
+        .collect();
+
+    payouts.sort_by_key(|p| std::cmp::Reverse(p.amount));
+    payouts.truncate(limit);
+
+    let effective_rate_per_stream = if total_streams > 0 {
+        (total_revenue / Decimal::from(total_streams)).round_dp(6)
+    } else {
+        Decimal::ZERO
+    };
+
+    PayoutModelSummary {
+        model: model.kind(),
+        total_revenue,
+        revenue_to_clean_artists: total_revenue - problematic_revenue,
+        revenue_to_problematic_artists: problematic_revenue,
+        problematic_percentage: percentage(problematic_revenue, total_revenue),
+        effective_rate_per_stream,
+        top_artists: payouts,
+    }
+}
+
+fn is_problematic(tier: Option<TroubleTier>) -> bool {
+    matches!(
+        tier,
+        Some(TroubleTier::Moderate | TroubleTier::High | TroubleTier::Critical)
+    )
+}
+
+fn percentage(part: Decimal, total: Decimal) -> f64 {
+    if total > Decimal::ZERO {
+        (part / total * Decimal::from(100))
+            .to_string()
+            .parse::<f64>()
+            .unwrap_or(0.0)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(subscription: Option<Decimal>, plays: &[(Uuid, i64)]) -> BillingPeriod {
+        BillingPeriod {
+            platform: "spotify".to_string(),
+            period_start: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            rate: PayoutRate {
+                platform: "spotify".to_string(),
+                rate_per_stream: Decimal::new(3, 3),
+                rate_per_minute: None,
+                subscription_monthly: subscription,
+                rate_tier: "standard".to_string(),
+                country_code: None,
+                royalty_share: Decimal::new(7, 1),
+                effective_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                source_url: None,
+            },
+            plays: plays
+                .iter()
+                .map(|(artist_id, play_count)| ArtistPlays {
+                    artist_id: *artist_id,
+                    play_count: *play_count,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_models_split_differently() {
+        let (heavy, light) = (Uuid::new_v4(), Uuid::new_v4());
+        let periods = vec![period(
+            Some(Decimal::new(1000, 2)),
+            &[(heavy, 90), (light, 10)],
+        )];
+
+        let mut artists = HashMap::new();
+        artists.insert(
+            heavy,
+            PayoutArtist {
+                artist_name: "Heavy".to_string(),
+                trouble_tier: Some(TroubleTier::High),
+            },
+        );
+
+        let pro_rata = summarize_payouts(&ProRataModel, &periods, &artists, 10);
+        assert_eq!(pro_rata.total_revenue, Decimal::new(300, 3));
+        assert_eq!(
+            pro_rata.revenue_to_problematic_artists,
+            Decimal::new(270, 3)
+        );
+
+        // $10.00 subscription * 70% royalty share, split 90/10
+        let user_centric = summarize_payouts(&UserCentricModel, &periods, &artists, 10);
+        assert_eq!(user_centric.total_revenue, Decimal::new(700, 2));
+        assert_eq!(
+            user_centric.revenue_to_problematic_artists,
+            Decimal::new(630, 2)
+        );
+        assert_eq!(user_centric.top_artists[0].artist_id, heavy);
+        assert_eq!(user_centric.top_artists[1].artist_name, "Unknown");
+    }
+
+    #[test]
+    fn test_user_centric_without_subscription_falls_back_to_per_stream() {
+        let artist = Uuid::new_v4();
+        let free = period(None, &[(artist, 100)]);
+
+        assert_eq!(
+            UserCentricModel.allocate(&free),
+            ProRataModel.allocate(&free)
+        );
+    }
+}
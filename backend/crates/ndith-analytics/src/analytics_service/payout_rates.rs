@@ -0,0 +1,596 @@
+//! Payout Rate Management
+//!
+//! Admin maintenance of `platform_payout_rates`. A rate row applies to its
+//! platform, tier and country from its effective date until the next row for
+//! the same key, so history is kept by adding rows rather than overwriting
+//! them. Corrections to a row are logged with the values before and after.
+//!
+//! Stored playcount revenue uses the standard, country-independent rate for
+//! the play period; adding or correcting one of those rates reprices the
+//! playcounts it covers in the same transaction.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::revenue::Platform;
+
+const RATE_COLUMNS: &str = r#"
+    id, platform, rate_tier, country_code, rate_per_stream, rate_per_minute,
+    subscription_monthly, royalty_share, effective_date,
+    COALESCE(end_date, (
+        SELECT MIN(n.effective_date) - 1
+        FROM platform_payout_rates n
+        WHERE n.platform = r.platform
+        AND n.rate_tier = r.rate_tier
+        AND COALESCE(n.country_code, '') = COALESCE(r.country_code, '')
+        AND n.effective_date > r.effective_date
+    )) AS effective_until,
+    source_url, notes, created_by, created_at, updated_at
+"#;
+
+/// A payout rate row with the period it covers
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PayoutRateEntry {
+    pub id: Uuid,
+    pub platform: String,
+    pub rate_tier: String,
+    pub country_code: Option<String>,
+    pub rate_per_stream: Decimal,
+    pub rate_per_minute: Option<Decimal>,
+    pub subscription_monthly: Option<Decimal>,
+    pub royalty_share: Decimal,
+    pub effective_date: NaiveDate,
+    /// Last day the rate applies, or None while it is current
+    pub effective_until: Option<NaiveDate>,
+    pub source_url: Option<String>,
+    pub notes: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PayoutRateEntry {
+    /// Whether stored playcount revenue is priced at this rate
+    fn prices_playcounts(&self) -> bool {
+        self.rate_tier == "standard" && self.country_code.is_none()
+    }
+
+    fn values(&self) -> serde_json::Value {
+        serde_json::json!({
+            "platform": self.platform,
+            "rate_tier": self.rate_tier,
+            "country_code": self.country_code,
+            "rate_per_stream": self.rate_per_stream,
+            "rate_per_minute": self.rate_per_minute,
+            "subscription_monthly": self.subscription_monthly,
+            "royalty_share": self.royalty_share,
+            "effective_date": self.effective_date,
+            "source_url": self.source_url,
+            "notes": self.notes,
+        })
+    }
+}
+
+/// A new rate entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewPayoutRate {
+    pub platform: String,
+    #[serde(default = "default_rate_tier")]
+    pub rate_tier: String,
+    /// ISO country code, or None for the default rate
+    pub country_code: Option<String>,
+    pub rate_per_stream: Decimal,
+    pub rate_per_minute: Option<Decimal>,
+    pub subscription_monthly: Option<Decimal>,
+    pub royalty_share: Option<Decimal>,
+    pub effective_date: NaiveDate,
+    pub source_url: String,
+    pub notes: Option<String>,
+}
+
+fn default_rate_tier() -> String {
+    "standard".to_string()
+}
+
+impl NewPayoutRate {
+    /// Trim and normalize case of free-form fields, then check the values
+    pub fn normalize(mut self) -> Result<Self> {
+        self.platform = self.platform.trim().to_lowercase();
+        self.rate_tier = self.rate_tier.trim().to_lowercase();
+        self.country_code = self
+            .country_code
+            .map(|c| c.trim().to_uppercase())
+            .filter(|c| !c.is_empty());
+        self.source_url = self.source_url.trim().to_string();
+
+        if Platform::parse_platform(&self.platform).is_none() {
+            anyhow::bail!("Unknown platform: {}", self.platform);
+        }
+        if self.rate_tier.is_empty() || self.rate_tier.len() > 50 {
+            anyhow::bail!("rate_tier must be 1-50 characters");
+        }
+        if let Some(country) = &self.country_code {
+            if country.len() != 2 || !country.chars().all(|c| c.is_ascii_uppercase()) {
+                anyhow::bail!(
+                    "country_code must be a two-letter ISO code, got {}",
+                    country
+                );
+            }
+        }
+        validate_amounts(
+            self.rate_per_stream,
+            self.rate_per_minute,
+            self.subscription_monthly,
+            self.royalty_share,
+        )?;
+        validate_source_url(&self.source_url)?;
+        Ok(self)
+    }
+}
+
+/// A correction to an existing rate entry. Omitted fields keep their value.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PayoutRateCorrection {
+    pub rate_per_stream: Option<Decimal>,
+    pub rate_per_minute: Option<Decimal>,
+    pub subscription_monthly: Option<Decimal>,
+    pub royalty_share: Option<Decimal>,
+    pub effective_date: Option<NaiveDate>,
+    pub source_url: Option<String>,
+    pub notes: Option<String>,
+    /// Why the rate was corrected
+    pub reason: String,
+}
+
+impl PayoutRateCorrection {
+    fn is_empty(&self) -> bool {
+        self.rate_per_stream.is_none()
+            && self.rate_per_minute.is_none()
+            && self.subscription_monthly.is_none()
+            && self.royalty_share.is_none()
+            && self.effective_date.is_none()
+            && self.source_url.is_none()
+            && self.notes.is_none()
+    }
+
+    /// Check the correction changes something and its values are valid
+    pub fn validate(&self) -> Result<()> {
+        if self.reason.trim().is_empty() {
+            anyhow::bail!("A reason is required to correct a rate");
+        }
+        if self.is_empty() {
+            anyhow::bail!("The correction does not change any field");
+        }
+        validate_amounts(
+            self.rate_per_stream.unwrap_or(Decimal::ONE),
+            self.rate_per_minute,
+            self.subscription_monthly,
+            self.royalty_share,
+        )?;
+        if let Some(url) = &self.source_url {
+            validate_source_url(url.trim())?;
+        }
+        Ok(())
+    }
+}
+
+fn validate_amounts(
+    rate_per_stream: Decimal,
+    rate_per_minute: Option<Decimal>,
+    subscription_monthly: Option<Decimal>,
+    royalty_share: Option<Decimal>,
+) -> Result<()> {
+    if rate_per_stream <= Decimal::ZERO {
+        anyhow::bail!("rate_per_stream must be positive");
+    }
+    if rate_per_minute.is_some_and(|r| r <= Decimal::ZERO) {
+        anyhow::bail!("rate_per_minute must be positive");
+    }
+    if subscription_monthly.is_some_and(|s| s < Decimal::ZERO) {
+        anyhow::bail!("subscription_monthly cannot be negative");
+    }
+    if royalty_share.is_some_and(|s| s <= Decimal::ZERO || s > Decimal::ONE) {
+        anyhow::bail!("royalty_share must be greater than 0 and at most 1");
+    }
+    Ok(())
+}
+
+fn validate_source_url(url: &str) -> Result<()> {
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        anyhow::bail!("source_url must be an http(s) URL citing the rate");
+    }
+    Ok(())
+}
+
+/// A logged addition or correction
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PayoutRateChange {
+    pub id: Uuid,
+    pub rate_id: Uuid,
+    pub change_type: String,
+    pub previous_values: Option<serde_json::Value>,
+    pub new_values: serde_json::Value,
+    pub reason: Option<String>,
+    pub playcounts_recalculated: i32,
+    pub changed_by: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Outcome of adding or correcting a rate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayoutRateUpdate {
+    pub rate: PayoutRateEntry,
+    pub change: PayoutRateChange,
+}
+
+/// Filter for listing rate entries
+#[derive(Debug, Clone, Default)]
+pub struct PayoutRateFilter {
+    pub platform: Option<Platform>,
+    pub rate_tier: Option<String>,
+    pub country_code: Option<String>,
+}
+
+/// Adds, corrects and lists payout rates
+pub struct PayoutRateService {
+    pool: PgPool,
+}
+
+impl PayoutRateService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Every rate entry, past and current, newest first within each key
+    pub async fn list_rates(&self, filter: &PayoutRateFilter) -> Result<Vec<PayoutRateEntry>> {
+        let sql = format!(
+            r#"
+            SELECT {RATE_COLUMNS}
+            FROM platform_payout_rates r
+            WHERE ($1::text IS NULL OR r.platform = $1)
+            AND ($2::text IS NULL OR r.rate_tier = $2)
+            AND ($3::text IS NULL OR r.country_code = $3)
+            ORDER BY r.platform, r.rate_tier, r.country_code NULLS FIRST, r.effective_date DESC
+            "#
+        );
+
+        sqlx::query_as::<_, PayoutRateEntry>(&sql)
+            .bind(filter.platform.map(|p| p.as_str()))
+            .bind(filter.rate_tier.as_deref())
+            .bind(filter.country_code.as_deref())
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to list payout rates")
+    }
+
+    pub async fn get_rate(&self, rate_id: Uuid) -> Result<Option<PayoutRateEntry>> {
+        fetch_rate(&self.pool, rate_id).await
+    }
+
+    /// Additions and corrections for a rate, newest first
+    pub async fn rate_changes(&self, rate_id: Uuid) -> Result<Vec<PayoutRateChange>> {
+        sqlx::query_as::<_, PayoutRateChange>(
+            r#"
+            SELECT id, rate_id, change_type, previous_values, new_values, reason,
+                   playcounts_recalculated, changed_by, changed_at
+            FROM platform_payout_rate_changes
+            WHERE rate_id = $1
+            ORDER BY changed_at DESC
+            "#,
+        )
+        .bind(rate_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch payout rate changes")
+    }
+
+    /// Add a rate entry. Returns None if an entry already exists for the same
+    /// platform, tier, country and effective date.
+    pub async fn add_rate(
+        &self,
+        rate: NewPayoutRate,
+        added_by: Uuid,
+    ) -> Result<Option<PayoutRateUpdate>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start payout rate update")?;
+
+        let rate_id: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            INSERT INTO platform_payout_rates (
+                platform, rate_tier, country_code, rate_per_stream, rate_per_minute,
+                subscription_monthly, royalty_share, effective_date, source_url, notes, created_by
+            ) VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, 0.7000), $8, $9, $10, $11)
+            ON CONFLICT (platform, rate_tier, COALESCE(country_code, ''), effective_date) DO NOTHING
+            RETURNING id
+            "#,
+        )
+        .bind(&rate.platform)
+        .bind(&rate.rate_tier)
+        .bind(rate.country_code.as_deref())
+        .bind(rate.rate_per_stream)
+        .bind(rate.rate_per_minute)
+        .bind(rate.subscription_monthly)
+        .bind(rate.royalty_share)
+        .bind(rate.effective_date)
+        .bind(&rate.source_url)
+        .bind(rate.notes.as_deref())
+        .bind(added_by)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to add payout rate")?;
+
+        let Some(rate_id) = rate_id else {
+            return Ok(None);
+        };
+
+        let entry = fetch_rate(&mut *tx, rate_id)
+            .await?
+            .context("Added payout rate not found")?;
+
+        let recalculated = if entry.prices_playcounts() {
+            recalculate_playcounts(&mut tx, &entry.platform, entry.effective_date).await?
+        } else {
+            0
+        };
+
+        let change = log_change(
+            &mut tx,
+            &entry,
+            "created",
+            None,
+            None,
+            recalculated,
+            added_by,
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit payout rate update")?;
+
+        tracing::info!(
+            rate_id = %entry.id,
+            platform = %entry.platform,
+            tier = %entry.rate_tier,
+            country = ?entry.country_code,
+            effective_date = %entry.effective_date,
+            playcounts_recalculated = recalculated,
+            "Added payout rate"
+        );
+
+        Ok(Some(PayoutRateUpdate {
+            rate: entry,
+            change,
+        }))
+    }
+
+    /// Correct a rate entry and reprice the playcounts it covered before or
+    /// covers after the correction. Returns None if the rate doesn't exist.
+    pub async fn correct_rate(
+        &self,
+        rate_id: Uuid,
+        correction: PayoutRateCorrection,
+        corrected_by: Uuid,
+    ) -> Result<Option<PayoutRateUpdate>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to start payout rate update")?;
+
+        let previous: Option<PayoutRateEntry> = sqlx::query_as(&format!(
+            "SELECT {RATE_COLUMNS} FROM platform_payout_rates r WHERE r.id = $1 FOR UPDATE"
+        ))
+        .bind(rate_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to fetch payout rate")?;
+
+        let Some(previous) = previous else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE platform_payout_rates SET
+                rate_per_stream = COALESCE($2, rate_per_stream),
+                rate_per_minute = COALESCE($3, rate_per_minute),
+                subscription_monthly = COALESCE($4, subscription_monthly),
+                royalty_share = COALESCE($5, royalty_share),
+                effective_date = COALESCE($6, effective_date),
+                source_url = COALESCE($7, source_url),
+                notes = COALESCE($8, notes)
+            WHERE id = $1
+            "#,
+        )
+        .bind(rate_id)
+        .bind(correction.rate_per_stream)
+        .bind(correction.rate_per_minute)
+        .bind(correction.subscription_monthly)
+        .bind(correction.royalty_share)
+        .bind(correction.effective_date)
+        .bind(correction.source_url.as_deref().map(str::trim))
+        .bind(correction.notes.as_deref())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to correct payout rate; another rate may already start on that date")?;
+
+        let entry = fetch_rate(&mut *tx, rate_id)
+            .await?
+            .context("Corrected payout rate not found")?;
+
+        // Moving the effective date changes which periods the rate covers on
+        // both sides, so reprice from whichever date is earlier
+        let recalculated = if entry.prices_playcounts() {
+            let from = previous.effective_date.min(entry.effective_date);
+            recalculate_playcounts(&mut tx, &entry.platform, from).await?
+        } else {
+            0
+        };
+
+        let change = log_change(
+            &mut tx,
+            &entry,
+            "corrected",
+            Some(previous.values()),
+            Some(correction.reason.trim()),
+            recalculated,
+            corrected_by,
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit payout rate update")?;
+
+        tracing::info!(
+            rate_id = %entry.id,
+            platform = %entry.platform,
+            playcounts_recalculated = recalculated,
+            "Corrected payout rate"
+        );
+
+        Ok(Some(PayoutRateUpdate {
+            rate: entry,
+            change,
+        }))
+    }
+}
+
+async fn fetch_rate<'e, E>(executor: E, rate_id: Uuid) -> Result<Option<PayoutRateEntry>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    sqlx::query_as::<_, PayoutRateEntry>(&format!(
+        "SELECT {RATE_COLUMNS} FROM platform_payout_rates r WHERE r.id = $1"
+    ))
+    .bind(rate_id)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to fetch payout rate")
+}
+
+/// Reprice a platform's playcounts from a period onwards
+async fn recalculate_playcounts(
+    conn: &mut PgConnection,
+    platform: &str,
+    from: NaiveDate,
+) -> Result<i32> {
+    sqlx::query_scalar("SELECT recalculate_playcount_revenue($1, $2)")
+        .bind(platform)
+        .bind(from)
+        .fetch_one(conn)
+        .await
+        .context("Failed to recalculate playcount revenue")
+}
+
+async fn log_change(
+    conn: &mut PgConnection,
+    entry: &PayoutRateEntry,
+    change_type: &str,
+    previous_values: Option<serde_json::Value>,
+    reason: Option<&str>,
+    playcounts_recalculated: i32,
+    changed_by: Uuid,
+) -> Result<PayoutRateChange> {
+    sqlx::query_as::<_, PayoutRateChange>(
+        r#"
+        INSERT INTO platform_payout_rate_changes (
+            rate_id, change_type, previous_values, new_values, reason,
+            playcounts_recalculated, changed_by
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, rate_id, change_type, previous_values, new_values, reason,
+                  playcounts_recalculated, changed_by, changed_at
+        "#,
+    )
+    .bind(entry.id)
+    .bind(change_type)
+    .bind(previous_values)
+    .bind(entry.values())
+    .bind(reason)
+    .bind(playcounts_recalculated)
+    .bind(changed_by)
+    .fetch_one(conn)
+    .await
+    .context("Failed to log payout rate change")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_rate() -> NewPayoutRate {
+        NewPayoutRate {
+            platform: "Spotify".to_string(),
+            rate_tier: " Standard ".to_string(),
+            country_code: Some("gb".to_string()),
+            rate_per_stream: Decimal::new(42, 4),
+            rate_per_minute: None,
+            subscription_monthly: Some(Decimal::new(1450, 2)),
+            royalty_share: None,
+            effective_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            source_url: "https://example.com/rates".to_string(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn normalize_cleans_tier_and_country() {
+        let rate = new_rate().normalize().unwrap();
+        assert_eq!(rate.platform, "spotify");
+        assert_eq!(rate.rate_tier, "standard");
+        assert_eq!(rate.country_code.as_deref(), Some("GB"));
+    }
+
+    #[test]
+    fn normalize_rejects_bad_values() {
+        let mut rate = new_rate();
+        rate.rate_per_stream = Decimal::ZERO;
+        assert!(rate.normalize().is_err());
+
+        let mut rate = new_rate();
+        rate.platform = "napster".to_string();
+        assert!(rate.normalize().is_err());
+
+        let mut rate = new_rate();
+        rate.country_code = Some("GBR".to_string());
+        assert!(rate.normalize().is_err());
+
+        let mut rate = new_rate();
+        rate.royalty_share = Some(Decimal::new(15, 1));
+        assert!(rate.normalize().is_err());
+
+        let mut rate = new_rate();
+        rate.source_url = "industry report".to_string();
+        assert!(rate.normalize().is_err());
+    }
+
+    #[test]
+    fn correction_needs_reason_and_change() {
+        let empty = PayoutRateCorrection {
+            reason: "typo".to_string(),
+            ..Default::default()
+        };
+        assert!(empty.validate().is_err());
+
+        let no_reason = PayoutRateCorrection {
+            rate_per_stream: Some(Decimal::new(4, 3)),
+            ..Default::default()
+        };
+        assert!(no_reason.validate().is_err());
+
+        let valid = PayoutRateCorrection {
+            rate_per_stream: Some(Decimal::new(4, 3)),
+            reason: "Report revised".to_string(),
+            ..Default::default()
+        };
+        assert!(valid.validate().is_ok());
+    }
+}
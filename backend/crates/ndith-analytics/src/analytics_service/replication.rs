@@ -0,0 +1,875 @@
+//! Postgres to DuckDB Replication
+//!
+//! Copies the tables behind the heavier analytics (enforcement batches and
+//! items, library tracks, offenses, blocks, playcounts, connections and user
+//! lifecycle events) into DuckDB so aggregate queries stay off the OLTP
+//! database.
+//!
+//! Each table is first copied in key order (resumable via a cursor). After
+//! that, the job reads `analytics_change_log`, which triggers fill with the
+//! key of every changed row: current rows are re-read by key and replaced in
+//! DuckDB, and keys that no longer exist are deleted.
+//!
+//! Several replicas can read the log. Each is a consumer with its own cursor
+//! per table in `analytics_replication_cursors`: the transaction id horizon
+//! below which every entry has been applied. Entries are never removed by a
+//! consumer, only by age (`with_retention`); a consumer whose cursor aged out
+//! re-snapshots. Applying a change is idempotent, so a crash between
+//! applying entries and moving the cursor only repeats work.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use crate::databases::{DuckDbClient, ReplicationState};
+
+/// Column types carried across, as text on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    Text,
+    Uuid,
+    Integer,
+    BigInt,
+    Double,
+    Boolean,
+    Date,
+    Timestamp,
+}
+
+impl ColumnKind {
+    fn duckdb_type(&self) -> &'static str {
+        match self {
+            ColumnKind::Text | ColumnKind::Uuid => "VARCHAR",
+            ColumnKind::Integer => "INTEGER",
+            ColumnKind::BigInt => "BIGINT",
+            ColumnKind::Double => "DOUBLE",
+            ColumnKind::Boolean => "BOOLEAN",
+            ColumnKind::Date => "DATE",
+            ColumnKind::Timestamp => "TIMESTAMP",
+        }
+    }
+
+    /// Postgres expression rendering the column as text DuckDB can cast.
+    /// Timestamps are converted to UTC without an offset.
+    fn select_expr(&self, column: &str) -> String {
+        match self {
+            ColumnKind::Timestamp => format!(
+                "to_char({} AT TIME ZONE 'UTC', 'YYYY-MM-DD HH24:MI:SS.US')",
+                column
+            ),
+            _ => format!("{}::text", column),
+        }
+    }
+}
+
+/// A Postgres table mirrored into DuckDB under the same name
+#[derive(Debug, Clone, Copy)]
+pub struct ReplicatedTable {
+    pub name: &'static str,
+    /// UUID primary key columns, joined with ':' to form the row key
+    pub key_columns: &'static [&'static str],
+    pub columns: &'static [(&'static str, ColumnKind)],
+}
+
+use ColumnKind::*;
+
+pub const ACTION_BATCHES: ReplicatedTable = ReplicatedTable {
+    name: "action_batches",
+    key_columns: &["id"],
+    columns: &[
+        ("id", Uuid),
+        ("user_id", Uuid),
+        ("provider", Text),
+        ("dry_run", Boolean),
+        ("status", Text),
+        ("created_at", Timestamp),
+        ("completed_at", Timestamp),
+    ],
+};
+
+pub const ACTION_ITEMS: ReplicatedTable = ReplicatedTable {
+    name: "action_items",
+    key_columns: &["id"],
+    columns: &[
+        ("id", Uuid),
+        ("batch_id", Uuid),
+        ("entity_type", Text),
+        ("entity_id", Text),
+        ("action", Text),
+        ("status", Text),
+        ("error_message", Text),
+        ("created_at", Timestamp),
+    ],
+};
+
+pub const USER_LIBRARY_TRACKS: ReplicatedTable = ReplicatedTable {
+    name: "user_library_tracks",
+    key_columns: &["id"],
+    columns: &[
+        ("id", Uuid),
+        ("user_id", Uuid),
+        ("provider", Text),
+        ("provider_track_id", Text),
+        ("artist_id", Uuid),
+        ("artist_name", Text),
+        ("source_type", Text),
+        ("added_at", Timestamp),
+        ("last_synced", Timestamp),
+    ],
+};
+
+pub const ARTIST_OFFENSES: ReplicatedTable = ReplicatedTable {
+    name: "artist_offenses",
+    key_columns: &["id"],
+    columns: &[
+        ("id", Uuid),
+        ("artist_id", Uuid),
+        ("category", Text),
+        ("severity", Text),
+        ("status", Text),
+        ("incident_date", Date),
+        ("created_at", Timestamp),
+        ("updated_at", Timestamp),
+    ],
+};
+
+pub const USER_ARTIST_BLOCKS: ReplicatedTable = ReplicatedTable {
+    name: "user_artist_blocks",
+    key_columns: &["user_id", "artist_id"],
+    columns: &[
+        ("user_id", Uuid),
+        ("artist_id", Uuid),
+        ("created_at", Timestamp),
+    ],
+};
+
+pub const USER_ARTIST_PLAYCOUNTS: ReplicatedTable = ReplicatedTable {
+    name: "user_artist_playcounts",
+    key_columns: &["id"],
+    columns: &[
+        ("id", Uuid),
+        ("user_id", Uuid),
+        ("artist_id", Uuid),
+        ("platform", Text),
+        ("play_count", Integer),
+        ("listening_time_ms", BigInt),
+        ("period_type", Text),
+        ("period_start", Date),
+        ("period_end", Date),
+        ("estimated_revenue", Double),
+        ("updated_at", Timestamp),
+    ],
+};
+
+/// Connection health only; tokens are never copied
+pub const CONNECTIONS: ReplicatedTable = ReplicatedTable {
+    name: "connections",
+    key_columns: &["id"],
+    columns: &[
+        ("id", Uuid),
+        ("user_id", Uuid),
+        ("provider", Text),
+        ("status", Text),
+        ("last_health_check", Timestamp),
+        ("created_at", Timestamp),
+    ],
+};
+
+pub const USER_LIFECYCLE_EVENTS: ReplicatedTable = ReplicatedTable {
+    name: "user_lifecycle_events",
+    key_columns: &["id"],
+    columns: &[
+        ("id", Uuid),
+        ("user_id", Uuid),
+        ("event_type", Text),
+        ("provider", Text),
+        ("source_id", Uuid),
+        ("occurred_at", Timestamp),
+    ],
+};
+
+/// Every replicated table
+pub const REPLICATED_TABLES: &[ReplicatedTable] = &[
+    ACTION_BATCHES,
+    ACTION_ITEMS,
+    USER_LIBRARY_TRACKS,
+    ARTIST_OFFENSES,
+    USER_ARTIST_BLOCKS,
+    USER_ARTIST_PLAYCOUNTS,
+    CONNECTIONS,
+    USER_LIFECYCLE_EVENTS,
+];
+
+impl ReplicatedTable {
+    fn column_names(&self) -> Vec<&'static str> {
+        self.columns.iter().map(|(name, _)| *name).collect()
+    }
+
+    pub fn create_table_sql(&self) -> String {
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|(name, kind)| format!("{} {}", name, kind.duckdb_type()))
+            .collect();
+        format!(
+            "CREATE TABLE IF NOT EXISTS {} ({})",
+            self.name,
+            columns.join(", ")
+        )
+    }
+
+    /// Row key expression in DuckDB, where UUIDs are already text
+    pub fn duckdb_key_expr(&self) -> String {
+        self.key_columns.join(" || ':' || ")
+    }
+
+    fn postgres_key_expr(&self) -> String {
+        self.key_columns
+            .iter()
+            .map(|c| format!("{}::text", c))
+            .collect::<Vec<_>>()
+            .join(" || ':' || ")
+    }
+
+    /// Key columns split back out of a row key held in `value`
+    fn postgres_key_parts(&self, value: &str) -> String {
+        self.key_columns
+            .iter()
+            .enumerate()
+            .map(|(i, _)| format!("split_part({}, ':', {})::uuid", value, i + 1))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn select_list(&self) -> String {
+        let mut select = vec![format!("{} AS row_key", self.postgres_key_expr())];
+        select.extend(
+            self.columns
+                .iter()
+                .map(|(name, kind)| kind.select_expr(name)),
+        );
+        select.join(", ")
+    }
+
+    /// Next snapshot page after the key in $1 (NULL for the first page)
+    pub fn snapshot_sql(&self) -> String {
+        let keys = self.key_columns.join(", ");
+        format!(
+            "SELECT {} FROM {} WHERE $1::text IS NULL OR ({}) > ({}) ORDER BY {} LIMIT $2",
+            self.select_list(),
+            self.name,
+            keys,
+            self.postgres_key_parts("$1"),
+            keys
+        )
+    }
+
+    /// Current rows for the row keys in $1
+    pub fn rows_by_key_sql(&self) -> String {
+        format!(
+            "SELECT {} FROM {} WHERE ({}) IN (SELECT {} FROM unnest($1::text[]) AS k)",
+            self.select_list(),
+            self.name,
+            self.key_columns.join(", "),
+            self.postgres_key_parts("k")
+        )
+    }
+}
+
+/// Where a response's data was read from, and how current it is
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataFreshness {
+    /// "postgres" for live reads, "duckdb" for the replica
+    pub source: String,
+    /// Every change committed before this time is reflected
+    pub synced_through: DateTime<Utc>,
+    pub lag_seconds: i64,
+}
+
+impl DataFreshness {
+    /// Read straight from Postgres
+    pub fn live() -> Self {
+        Self {
+            source: "postgres".to_string(),
+            synced_through: Utc::now(),
+            lag_seconds: 0,
+        }
+    }
+
+    fn replica(synced_through: DateTime<Utc>) -> Self {
+        Self {
+            source: "duckdb".to_string(),
+            synced_through,
+            lag_seconds: (Utc::now() - synced_through).num_seconds().max(0),
+        }
+    }
+}
+
+/// Replicas older than this are not used for queries
+pub fn default_max_lag() -> Duration {
+    Duration::minutes(30)
+}
+
+/// Freshness of the replica across `tables`, or None when any of them has
+/// not finished its initial copy or lags by more than `max_lag`
+pub async fn replica_freshness(
+    duckdb: &DuckDbClient,
+    tables: &[ReplicatedTable],
+    max_lag: Duration,
+) -> Result<Option<DataFreshness>> {
+    Ok(replica_sync_point(duckdb, tables)
+        .await?
+        .filter(|f| Utc::now() - f.synced_through <= max_lag))
+}
+
+/// Freshness of the replica across `tables` however far it lags, or None
+/// when any of them has not finished its initial copy
+pub async fn replica_sync_point(
+    duckdb: &DuckDbClient,
+    tables: &[ReplicatedTable],
+) -> Result<Option<DataFreshness>> {
+    let states = duckdb.list_replication_state().await?;
+
+    let mut oldest: Option<DateTime<Utc>> = None;
+    for table in tables {
+        let synced_through = states
+            .iter()
+            .find(|s| s.table_name == table.name && s.snapshot_complete)
+            .and_then(|s| s.synced_through);
+
+        match synced_through {
+            Some(t) => oldest = Some(oldest.map_or(t, |o| o.min(t))),
+            None => return Ok(None),
+        }
+    }
+
+    Ok(oldest.map(DataFreshness::replica))
+}
+
+/// Outcome of one replication pass
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReplicationRunStats {
+    pub snapshot_rows: i64,
+    pub changes_applied: i64,
+    pub tables_failed: usize,
+    /// Change log entries and cursors removed for age
+    pub entries_pruned: u64,
+}
+
+/// Replication progress plus changes still queued in Postgres
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplicationStatus {
+    #[serde(flatten)]
+    pub state: ReplicationState,
+    pub pending_changes: i64,
+}
+
+/// Incremental Postgres to DuckDB replication job
+pub struct ReplicationService {
+    pool: PgPool,
+    duckdb: Arc<DuckDbClient>,
+    batch_size: i64,
+    consumer_id: String,
+    retention: Duration,
+}
+
+impl ReplicationService {
+    pub fn new(pool: PgPool, duckdb: Arc<DuckDbClient>) -> Self {
+        Self {
+            pool,
+            duckdb,
+            batch_size: 1000,
+            consumer_id: "default".to_string(),
+            retention: Duration::days(7),
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Name of this replica's cursors; every DuckDB replica needs its own
+    pub fn with_consumer_id(mut self, consumer_id: impl Into<String>) -> Self {
+        self.consumer_id = consumer_id.into();
+        self
+    }
+
+    /// How long change log entries (and idle cursors) are kept
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Create the replica tables in DuckDB
+    pub async fn ensure_tables(&self) -> Result<()> {
+        for table in REPLICATED_TABLES {
+            self.duckdb
+                .execute_batch(&table.create_table_sql())
+                .await
+                .with_context(|| format!("Failed to create replica table {}", table.name))?;
+        }
+        Ok(())
+    }
+
+    /// Run replication every `period` in the background
+    pub fn start(self: Arc<Self>, period: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.ensure_tables().await {
+                tracing::error!(error = %e, "Analytics replication disabled");
+                return;
+            }
+
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                let stats = self.run_once().await;
+                if stats.snapshot_rows + stats.changes_applied > 0
+                    || stats.tables_failed > 0
+                    || stats.entries_pruned > 0
+                {
+                    tracing::info!(
+                        snapshot_rows = stats.snapshot_rows,
+                        changes_applied = stats.changes_applied,
+                        tables_failed = stats.tables_failed,
+                        entries_pruned = stats.entries_pruned,
+                        "Analytics replication run completed"
+                    );
+                }
+            }
+        })
+    }
+
+    /// Snapshot any table not yet copied, apply queued changes, then prune
+    /// entries past the retention period
+    pub async fn run_once(&self) -> ReplicationRunStats {
+        let mut stats = ReplicationRunStats::default();
+
+        for table in REPLICATED_TABLES {
+            // Anything committed before this point is visible to the reads below
+            let started_at = Utc::now();
+            let mut state = match self.duckdb.get_replication_state(table.name).await {
+                Ok(state) => state.unwrap_or_else(|| ReplicationState::new(table.name)),
+                Err(e) => {
+                    tracing::warn!(table = table.name, error = %e, "Failed to load replication state");
+                    stats.tables_failed += 1;
+                    continue;
+                }
+            };
+
+            let result = self.replicate_table(table, &mut state, &mut stats).await;
+            state.last_run_at = Some(started_at);
+            match result {
+                Ok(()) => {
+                    state.synced_through = Some(started_at);
+                    state.last_error = None;
+                }
+                Err(e) => {
+                    tracing::warn!(table = table.name, error = %e, "Table replication failed");
+                    state.last_error = Some(e.to_string());
+                    stats.tables_failed += 1;
+                }
+            }
+
+            if let Err(e) = self.duckdb.save_replication_state(&state).await {
+                tracing::warn!(table = table.name, error = %e, "Failed to save replication state");
+            }
+        }
+
+        match self.prune(Utc::now()).await {
+            Ok(pruned) => stats.entries_pruned = pruned,
+            Err(e) => tracing::warn!(error = %e, "Failed to prune analytics change log"),
+        }
+
+        stats
+    }
+
+    async fn replicate_table(
+        &self,
+        table: &ReplicatedTable,
+        state: &mut ReplicationState,
+        stats: &mut ReplicationRunStats,
+    ) -> Result<()> {
+        let mut applied_before = self.load_cursor(table).await?;
+
+        // Without a cursor there's no telling which changes were missed
+        if applied_before.is_none() && (state.snapshot_complete || state.snapshot_cursor.is_some())
+        {
+            tracing::warn!(
+                table = table.name,
+                consumer_id = %self.consumer_id,
+                "Replication cursor missing, re-snapshotting"
+            );
+            state.snapshot_complete = false;
+            state.snapshot_cursor = None;
+        }
+
+        if !state.snapshot_complete {
+            if state.snapshot_cursor.is_none() {
+                self.duckdb
+                    .execute_batch(&format!("DELETE FROM {}", table.name))
+                    .await
+                    .with_context(|| format!("Failed to clear replica table {}", table.name))?;
+                applied_before = Some(self.start_cursor(table).await?);
+            }
+            self.snapshot(table, state, stats).await?;
+        }
+
+        let applied_before = applied_before.context("Replication cursor missing")?;
+        self.apply_changes(table, applied_before, state, stats)
+            .await
+    }
+
+    /// This consumer's horizon for a table, as text
+    async fn load_cursor(&self, table: &ReplicatedTable) -> Result<Option<String>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT applied_before::text
+            FROM analytics_replication_cursors
+            WHERE consumer_id = $1 AND table_name = $2
+            "#,
+        )
+        .bind(&self.consumer_id)
+        .bind(table.name)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to load replication cursor")
+    }
+
+    /// Start a cursor at the oldest running transaction. A snapshot read
+    /// after this sees every change below it; later ones are replayed.
+    async fn start_cursor(&self, table: &ReplicatedTable) -> Result<String> {
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO analytics_replication_cursors (consumer_id, table_name, applied_before)
+            VALUES ($1, $2, pg_snapshot_xmin(pg_current_snapshot()))
+            ON CONFLICT (consumer_id, table_name) DO UPDATE SET
+                applied_before = EXCLUDED.applied_before,
+                updated_at = NOW()
+            RETURNING applied_before::text
+            "#,
+        )
+        .bind(&self.consumer_id)
+        .bind(table.name)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to start replication cursor")
+    }
+
+    /// Copy the table in key order, saving the cursor after each page so an
+    /// interrupted snapshot resumes where it stopped
+    async fn snapshot(
+        &self,
+        table: &ReplicatedTable,
+        state: &mut ReplicationState,
+        stats: &mut ReplicationRunStats,
+    ) -> Result<()> {
+        let sql = table.snapshot_sql();
+
+        loop {
+            let rows = sqlx::query(&sql)
+                .bind(&state.snapshot_cursor)
+                .bind(self.batch_size)
+                .fetch_all(&self.pool)
+                .await
+                .with_context(|| format!("Failed to read {} snapshot page", table.name))?;
+
+            let (keys, values) = split_rows(&rows, table.columns.len())?;
+            self.duckdb
+                .replace_rows(
+                    table.name,
+                    &table.duckdb_key_expr(),
+                    &table.column_names(),
+                    &keys,
+                    &values,
+                )
+                .await?;
+
+            stats.snapshot_rows += values.len() as i64;
+            state.rows_replicated += values.len() as i64;
+
+            if (rows.len() as i64) < self.batch_size {
+                state.snapshot_complete = true;
+                state.snapshot_cursor = None;
+                self.duckdb.save_replication_state(state).await?;
+                tracing::info!(
+                    table = table.name,
+                    rows = state.rows_replicated,
+                    "Analytics replica snapshot complete"
+                );
+                return Ok(());
+            }
+
+            state.snapshot_cursor = keys.last().cloned();
+            self.duckdb.save_replication_state(state).await?;
+        }
+    }
+
+    /// Apply entries from transactions between the cursor and the oldest
+    /// transaction still running, then move the cursor there
+    async fn apply_changes(
+        &self,
+        table: &ReplicatedTable,
+        applied_before: String,
+        state: &mut ReplicationState,
+        stats: &mut ReplicationRunStats,
+    ) -> Result<()> {
+        let sql = table.rows_by_key_sql();
+
+        let horizon: String =
+            sqlx::query_scalar("SELECT pg_snapshot_xmin(pg_current_snapshot())::text")
+                .fetch_one(&self.pool)
+                .await
+                .context("Failed to read transaction horizon")?;
+
+        let mut after_id = 0i64;
+        loop {
+            let entries: Vec<(i64, String)> = sqlx::query_as(
+                r#"
+                SELECT id, row_key
+                FROM analytics_change_log
+                WHERE table_name = $1
+                AND xact_id >= $2::text::xid8
+                AND xact_id < $3::text::xid8
+                AND id > $4
+                ORDER BY id
+                LIMIT $5
+                "#,
+            )
+            .bind(table.name)
+            .bind(&applied_before)
+            .bind(&horizon)
+            .bind(after_id)
+            .bind(self.batch_size)
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to read analytics change log")?;
+
+            let Some((last_id, _)) = entries.last() else {
+                break;
+            };
+            after_id = *last_id;
+
+            let keys: Vec<String> = entries
+                .iter()
+                .map(|(_, key)| key.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+
+            let rows = sqlx::query(&sql)
+                .bind(&keys)
+                .fetch_all(&self.pool)
+                .await
+                .with_context(|| format!("Failed to read changed {} rows", table.name))?;
+
+            let (_, values) = split_rows(&rows, table.columns.len())?;
+            self.duckdb
+                .replace_rows(
+                    table.name,
+                    &table.duckdb_key_expr(),
+                    &table.column_names(),
+                    &keys,
+                    &values,
+                )
+                .await?;
+
+            stats.changes_applied += keys.len() as i64;
+            state.rows_replicated += values.len() as i64;
+
+            if (entries.len() as i64) < self.batch_size {
+                break;
+            }
+        }
+
+        let moved = sqlx::query(
+            r#"
+            UPDATE analytics_replication_cursors
+            SET applied_before = GREATEST(applied_before, $3::text::xid8), updated_at = NOW()
+            WHERE consumer_id = $1 AND table_name = $2
+            "#,
+        )
+        .bind(&self.consumer_id)
+        .bind(table.name)
+        .bind(&horizon)
+        .execute(&self.pool)
+        .await
+        .context("Failed to move replication cursor")?;
+
+        if moved.rows_affected() == 0 {
+            return Err(anyhow!(
+                "Replication cursor for {} aged out; re-snapshotting on the next run",
+                table.name
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Delete change log entries and idle cursors older than the retention
+    /// period
+    pub async fn prune(&self, now: DateTime<Utc>) -> Result<u64> {
+        let cutoff = now - self.retention;
+
+        let entries = sqlx::query("DELETE FROM analytics_change_log WHERE changed_at < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .context("Failed to prune analytics change log")?;
+
+        let cursors =
+            sqlx::query("DELETE FROM analytics_replication_cursors WHERE updated_at < $1")
+                .bind(cutoff)
+                .execute(&self.pool)
+                .await
+                .context("Failed to prune replication cursors")?;
+
+        Ok(entries.rows_affected() + cursors.rows_affected())
+    }
+
+    /// Replication progress of every table
+    pub async fn status(&self) -> Result<Vec<ReplicationStatus>> {
+        let states = self.duckdb.list_replication_state().await?;
+
+        let pending: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT l.table_name, COUNT(*)
+            FROM analytics_change_log l
+            LEFT JOIN analytics_replication_cursors c
+                ON c.consumer_id = $1 AND c.table_name = l.table_name
+            WHERE c.applied_before IS NULL OR l.xact_id >= c.applied_before
+            GROUP BY l.table_name
+            "#,
+        )
+        .bind(&self.consumer_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to count pending changes")?;
+
+        Ok(REPLICATED_TABLES
+            .iter()
+            .map(|table| ReplicationStatus {
+                state: states
+                    .iter()
+                    .find(|s| s.table_name == table.name)
+                    .cloned()
+                    .unwrap_or_else(|| ReplicationState::new(table.name)),
+                pending_changes: pending
+                    .iter()
+                    .find(|(name, _)| name == table.name)
+                    .map(|(_, count)| *count)
+                    .unwrap_or(0),
+            })
+            .collect())
+    }
+}
+
+/// Row keys and the column values of each row
+type SplitRows = (Vec<String>, Vec<Vec<Option<String>>>);
+
+/// Split fetched rows into their row keys and column values
+fn split_rows(rows: &[sqlx::postgres::PgRow], column_count: usize) -> Result<SplitRows> {
+    let mut keys = Vec::with_capacity(rows.len());
+    let mut values = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        keys.push(row.try_get::<String, _>(0)?);
+        let mut row_values = Vec::with_capacity(column_count);
+        for i in 1..=column_count {
+            row_values.push(row.try_get::<Option<String>, _>(i)?);
+        }
+        values.push(row_values);
+    }
+
+    Ok((keys, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_composite_key_sql() {
+        assert_eq!(
+            USER_ARTIST_BLOCKS.duckdb_key_expr(),
+            "user_id || ':' || artist_id"
+        );
+        assert!(USER_ARTIST_BLOCKS.rows_by_key_sql().contains(
+            "(user_id, artist_id) IN (SELECT split_part(k, ':', 1)::uuid, split_part(k, ':', 2)::uuid"
+        ));
+        assert!(ACTION_BATCHES
+            .snapshot_sql()
+            .starts_with("SELECT id::text AS row_key, id::text, user_id::text"));
+    }
+
+    #[tokio::test]
+    async fn test_replace_rows_and_freshness() {
+        let duckdb = DuckDbClient::in_memory().unwrap();
+        duckdb.initialize_schema().await.unwrap();
+        duckdb
+            .execute_batch(&USER_ARTIST_BLOCKS.create_table_sql())
+            .await
+            .unwrap();
+
+        let columns = USER_ARTIST_BLOCKS.column_names();
+        let row = |user: &str, artist: &str| {
+            vec![
+                Some(user.to_string()),
+                Some(artist.to_string()),
+                Some("2024-03-01 12:00:00.000000".to_string()),
+            ]
+        };
+        let key_expr = USER_ARTIST_BLOCKS.duckdb_key_expr();
+        duckdb
+            .replace_rows(
+                "user_artist_blocks",
+                &key_expr,
+                &columns,
+                &["u1:a1".to_string(), "u1:a2".to_string()],
+                &[row("u1", "a1"), row("u1", "a2")],
+            )
+            .await
+            .unwrap();
+
+        // A key with no current row is a delete
+        duckdb
+            .replace_rows(
+                "user_artist_blocks",
+                &key_expr,
+                &columns,
+                &["u1:a2".to_string()],
+                &[],
+            )
+            .await
+            .unwrap();
+
+        let remaining = duckdb
+            .query("SELECT artist_id FROM user_artist_blocks", &[], |row| {
+                row.get::<_, String>(0)
+            })
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec!["a1".to_string()]);
+
+        assert!(
+            replica_freshness(&duckdb, &[USER_ARTIST_BLOCKS], default_max_lag())
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let mut state = ReplicationState::new("user_artist_blocks");
+        state.snapshot_complete = true;
+        state.synced_through = Some(Utc::now());
+        duckdb.save_replication_state(&state).await.unwrap();
+
+        let freshness = replica_freshness(&duckdb, &[USER_ARTIST_BLOCKS], default_max_lag())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(freshness.source, "duckdb");
+    }
+}
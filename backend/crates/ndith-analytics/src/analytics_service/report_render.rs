@@ -0,0 +1,313 @@
+//! Report Rendering
+//!
+//! Reports are built as a document of tabular sections and rendered to each
+//! `ReportFormat`. CSV and Parquet produce one file per section; JSON and
+//! HTML produce a single file. HTML reports are self-contained (inline CSS
+//! and SVG charts) so they can be emailed or opened offline.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::fmt::Write as _;
+
+use super::reporting::ReportSummary;
+
+/// A table within a report
+#[derive(Debug, Clone)]
+pub struct ReportSection {
+    pub name: String,
+    pub title: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    pub chart: Option<SectionChart>,
+}
+
+/// Bar chart over two columns of a section
+#[derive(Debug, Clone, Copy)]
+pub struct SectionChart {
+    pub label_column: usize,
+    pub value_column: usize,
+}
+
+impl ReportSection {
+    pub fn new(name: &str, title: &str, columns: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            title: title.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+            chart: None,
+        }
+    }
+
+    /// Chart `value_column` against `label_column` in HTML output
+    pub fn with_chart(mut self, label_column: usize, value_column: usize) -> Self {
+        self.chart = Some(SectionChart {
+            label_column,
+            value_column,
+        });
+        self
+    }
+
+    pub fn push_row(&mut self, row: Vec<Value>) {
+        self.rows.push(row);
+    }
+}
+
+/// Everything needed to render a report in any format
+#[derive(Debug, Clone)]
+pub struct ReportDocument {
+    pub title: String,
+    pub generated_at: DateTime<Utc>,
+    pub time_range: String,
+    pub summary: ReportSummary,
+    pub sections: Vec<ReportSection>,
+    /// Full source data, used for the JSON rendering
+    pub data: Value,
+}
+
+/// Pretty JSON of the source data
+pub fn render_json(doc: &ReportDocument) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&doc.data)?)
+}
+
+/// CSV for one section, with a header row
+pub fn render_csv(section: &ReportSection) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(&section.columns)?;
+    for row in &section.rows {
+        writer.write_record(row.iter().map(cell_text))?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+/// Self-contained HTML page with summary, charts and tables
+pub fn render_html(doc: &ReportDocument) -> String {
+    let mut html = String::new();
+    let title = escape_html(&doc.title);
+
+    let _ = write!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem; color: #1f2933; }}
+h1 {{ margin-bottom: 0.25rem; }}
+.meta {{ color: #616e7c; margin-bottom: 2rem; }}
+.metrics {{ display: flex; flex-wrap: wrap; gap: 1rem; margin-bottom: 2rem; }}
+.metric {{ border: 1px solid #d9e2ec; border-radius: 6px; padding: 0.75rem 1rem; min-width: 10rem; }}
+.metric .name {{ color: #616e7c; font-size: 0.85rem; }}
+.metric .value {{ font-size: 1.4rem; font-weight: 600; }}
+table {{ border-collapse: collapse; margin-bottom: 2rem; width: 100%; }}
+th, td {{ border-bottom: 1px solid #e4e7eb; padding: 0.4rem 0.6rem; text-align: left; }}
+th {{ background: #f5f7fa; }}
+svg {{ margin-bottom: 1rem; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<div class="meta">{range} &middot; generated {generated}</div>
+"#,
+        title = title,
+        range = escape_html(&doc.time_range),
+        generated = doc.generated_at.format("%Y-%m-%d %H:%M UTC"),
+    );
+
+    if !doc.summary.key_metrics.is_empty() {
+        html.push_str("<div class=\"metrics\">\n");
+        for metric in &doc.summary.key_metrics {
+            let _ = writeln!(
+                html,
+                "<div class=\"metric\"><div class=\"name\">{}</div><div class=\"value\">{}</div></div>",
+                escape_html(&metric.name),
+                escape_html(&metric.value)
+            );
+        }
+        html.push_str("</div>\n");
+    }
+
+    if !doc.summary.highlights.is_empty() {
+        html.push_str("<ul>\n");
+        for highlight in &doc.summary.highlights {
+            let _ = writeln!(html, "<li>{}</li>", escape_html(highlight));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    for section in &doc.sections {
+        let _ = writeln!(html, "<h2>{}</h2>", escape_html(&section.title));
+
+        if section.rows.is_empty() {
+            html.push_str("<p>No data for this period.</p>\n");
+            continue;
+        }
+
+        if let Some(chart) = section.chart {
+            html.push_str(&render_bar_chart(section, chart));
+        }
+
+        html.push_str("<table>\n<thead><tr>");
+        for column in &section.columns {
+            let _ = write!(html, "<th>{}</th>", escape_html(column));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        for row in &section.rows {
+            html.push_str("<tr>");
+            for cell in row {
+                let _ = write!(html, "<td>{}</td>", escape_html(&cell_text(cell)));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Horizontal bar chart as inline SVG
+fn render_bar_chart(section: &ReportSection, chart: SectionChart) -> String {
+    const BAR_HEIGHT: usize = 22;
+    const LABEL_WIDTH: usize = 180;
+    const BAR_WIDTH: f64 = 420.0;
+
+    let bars: Vec<(String, f64)> = section
+        .rows
+        .iter()
+        .filter_map(|row| {
+            let label = row.get(chart.label_column).map(cell_text)?;
+            let value = row.get(chart.value_column)?.as_f64()?;
+            Some((label, value))
+        })
+        .collect();
+
+    let max = bars.iter().map(|(_, v)| v.abs()).fold(0.0, f64::max);
+    if bars.is_empty() || max == 0.0 {
+        return String::new();
+    }
+
+    let height = bars.len() * BAR_HEIGHT;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" role=\"img\">\n",
+        LABEL_WIDTH + BAR_WIDTH as usize + 80,
+        height
+    );
+
+    for (i, (label, value)) in bars.iter().enumerate() {
+        let y = i * BAR_HEIGHT;
+        let width = (value.abs() / max * BAR_WIDTH).max(1.0);
+        let _ = writeln!(
+            svg,
+            "<text x=\"0\" y=\"{}\" font-size=\"12\">{}</text>\
+             <rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\"/>\
+             <text x=\"{:.1}\" y=\"{}\" font-size=\"12\">{}</text>",
+            y + 15,
+            escape_html(&truncate_label(label, 28)),
+            LABEL_WIDTH,
+            y + 3,
+            width,
+            BAR_HEIGHT - 6,
+            if *value < 0.0 { "#e12d39" } else { "#3e7bfa" },
+            LABEL_WIDTH as f64 + width + 6.0,
+            y + 15,
+            format_number(*value),
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Plain-text form of a cell: strings unquoted, null as empty
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n
+            .as_f64()
+            .filter(|_| n.is_f64())
+            .map(format_number)
+            .unwrap_or_else(|| n.to_string()),
+        other => other.to_string(),
+    }
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.4}", value)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
+
+fn truncate_label(label: &str, max: usize) -> String {
+    if label.chars().count() <= max {
+        label.to_string()
+    } else {
+        let mut truncated: String = label.chars().take(max - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document() -> ReportDocument {
+        let mut section =
+            ReportSection::new("artists", "Artists", &["name", "mentions"]).with_chart(0, 1);
+        section.push_row(vec![json!("Smith, <Jr>"), json!(12)]);
+        section.push_row(vec![json!("Other"), json!(0.25)]);
+
+        ReportDocument {
+            title: "Trend Analysis".to_string(),
+            generated_at: Utc::now(),
+            time_range: "Last7Days".to_string(),
+            summary: ReportSummary {
+                total_records: 2,
+                key_metrics: vec![],
+                highlights: vec!["2 rising artists".to_string()],
+            },
+            sections: vec![section],
+            data: json!({}),
+        }
+    }
+
+    #[test]
+    fn test_render_csv_quotes_and_formats_cells() {
+        let csv = render_csv(&document().sections[0]).unwrap();
+        assert_eq!(csv, "name,mentions\n\"Smith, <Jr>\",12\nOther,0.25\n");
+    }
+
+    #[test]
+    fn test_render_html_escapes_and_charts() {
+        let html = render_html(&document());
+        assert!(html.contains("<td>Smith, &lt;Jr&gt;</td>"));
+        assert!(html.contains("<svg"));
+        assert!(!html.contains("<Jr>"));
+    }
+}
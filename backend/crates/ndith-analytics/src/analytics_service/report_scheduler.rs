@@ -0,0 +1,421 @@
+//! Report Scheduler
+//!
+//! Runs `ScheduledReport`s on their cadence, hands each generated report to
+//! the configured delivery sinks, and expires reports once their retention
+//! period has passed (deleting their files).
+//!
+//! Schedules and generated reports are stored in Postgres
+//! (`report_schedules`, `generated_reports`) and belong to the user who
+//! created them; every lookup is scoped to that owner.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::PgPool;
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::reporting::{
+    Report, ReportRequest, ReportSchedule, ReportStatus, ReportingService, ScheduledReport,
+};
+
+/// Destination for generated reports
+#[async_trait]
+pub trait ReportSink: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn deliver(&self, schedule: &ScheduledReport, report: &Report) -> Result<()>;
+}
+
+/// Copies report files into `<dir>/<schedule id>/`
+pub struct FilesystemSink {
+    dir: PathBuf,
+}
+
+impl FilesystemSink {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl ReportSink for FilesystemSink {
+    fn name(&self) -> &str {
+        "filesystem"
+    }
+
+    async fn deliver(&self, schedule: &ScheduledReport, report: &Report) -> Result<()> {
+        let target = self.dir.join(schedule.id.to_string());
+        tokio::fs::create_dir_all(&target)
+            .await
+            .with_context(|| format!("Failed to create {}", target.display()))?;
+
+        for file in &report.files {
+            let source = PathBuf::from(&file.path);
+            let Some(filename) = source.file_name() else {
+                continue;
+            };
+            tokio::fs::copy(&source, target.join(filename))
+                .await
+                .with_context(|| format!("Failed to copy {}", file.path))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Outcome of one scheduler pass
+#[derive(Debug, Clone, Default)]
+pub struct SchedulerRunStats {
+    pub generated: usize,
+    pub failed: usize,
+    pub deliveries_failed: usize,
+    pub expired: usize,
+}
+
+#[derive(sqlx::FromRow)]
+struct ScheduleRow {
+    id: Uuid,
+    owner_id: Uuid,
+    report_type: String,
+    format: String,
+    schedule: serde_json::Value,
+    recipients: Vec<String>,
+    enabled: bool,
+    last_run: Option<DateTime<Utc>>,
+    next_run: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<ScheduleRow> for ScheduledReport {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ScheduleRow) -> Result<Self> {
+        Ok(ScheduledReport {
+            id: row.id,
+            owner_id: row.owner_id,
+            report_type: from_text(row.report_type)?,
+            format: from_text(row.format)?,
+            schedule: serde_json::from_value::<ReportSchedule>(row.schedule)?,
+            recipients: row.recipients,
+            enabled: row.enabled,
+            last_run: row.last_run,
+            next_run: row.next_run,
+        })
+    }
+}
+
+const SCHEDULE_COLUMNS: &str =
+    "id, owner_id, report_type, format, schedule, recipients, enabled, last_run, next_run";
+
+/// Enum variant name as stored in a text column
+fn to_text<T: Serialize>(value: &T) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(s) => Ok(s),
+        other => Ok(other.to_string()),
+    }
+}
+
+fn from_text<T: DeserializeOwned>(text: String) -> Result<T> {
+    serde_json::from_value(serde_json::Value::String(text)).context("Unknown stored report enum")
+}
+
+/// Generates scheduled reports and tracks generated reports until expiry
+pub struct ReportScheduler {
+    reporting: Arc<ReportingService>,
+    pool: PgPool,
+    sinks: Vec<Arc<dyn ReportSink>>,
+}
+
+impl ReportScheduler {
+    pub fn new(reporting: Arc<ReportingService>, pool: PgPool) -> Self {
+        Self {
+            reporting,
+            pool,
+            sinks: Vec::new(),
+        }
+    }
+
+    pub fn with_sink(mut self, sink: Arc<dyn ReportSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    pub fn reporting(&self) -> &ReportingService {
+        &self.reporting
+    }
+
+    /// Register a schedule, computing its first run
+    pub async fn add_schedule(&self, mut schedule: ScheduledReport) -> Result<ScheduledReport> {
+        let after = schedule.last_run.unwrap_or_else(Utc::now);
+        schedule.next_run = Some(schedule.schedule.next_run_after(after));
+
+        sqlx::query(
+            r#"
+            INSERT INTO report_schedules
+                (id, owner_id, report_type, format, schedule, recipients, enabled, last_run, next_run)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(schedule.id)
+        .bind(schedule.owner_id)
+        .bind(to_text(&schedule.report_type)?)
+        .bind(to_text(&schedule.format)?)
+        .bind(serde_json::to_value(&schedule.schedule)?)
+        .bind(&schedule.recipients)
+        .bind(schedule.enabled)
+        .bind(schedule.last_run)
+        .bind(schedule.next_run)
+        .execute(&self.pool)
+        .await
+        .context("Failed to store report schedule")?;
+
+        Ok(schedule)
+    }
+
+    /// Delete one of `owner_id`'s schedules, returning whether it existed
+    pub async fn remove_schedule(&self, owner_id: Uuid, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM report_schedules WHERE id = $1 AND owner_id = $2")
+            .bind(id)
+            .bind(owner_id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete report schedule")?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn list_schedules(&self, owner_id: Uuid) -> Result<Vec<ScheduledReport>> {
+        let rows: Vec<ScheduleRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM report_schedules WHERE owner_id = $1 ORDER BY next_run",
+            SCHEDULE_COLUMNS
+        ))
+        .bind(owner_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list report schedules")?;
+
+        rows.into_iter().map(ScheduledReport::try_from).collect()
+    }
+
+    /// Generate a report now for `owner_id` and keep it until it expires
+    pub async fn generate(&self, owner_id: Uuid, request: ReportRequest) -> Result<Report> {
+        self.generate_for(owner_id, None, request).await
+    }
+
+    async fn generate_for(
+        &self,
+        owner_id: Uuid,
+        schedule_id: Option<Uuid>,
+        request: ReportRequest,
+    ) -> Result<Report> {
+        let report = self.reporting.generate_report(request).await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO generated_reports (id, owner_id, schedule_id, report, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(report.id)
+        .bind(owner_id)
+        .bind(schedule_id)
+        .bind(serde_json::to_value(&report)?)
+        .bind(report.expires_at)
+        .execute(&self.pool)
+        .await
+        .context("Failed to store generated report")?;
+
+        Ok(report)
+    }
+
+    /// One of `owner_id`'s reports; other users' reports are not found
+    pub async fn get_report(&self, owner_id: Uuid, id: Uuid) -> Result<Option<Report>> {
+        let report: Option<serde_json::Value> = sqlx::query_scalar(
+            "SELECT report FROM generated_reports WHERE id = $1 AND owner_id = $2",
+        )
+        .bind(id)
+        .bind(owner_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to get report")?;
+
+        report
+            .map(serde_json::from_value)
+            .transpose()
+            .context("Failed to decode stored report")
+    }
+
+    pub async fn list_reports(&self, owner_id: Uuid) -> Result<Vec<Report>> {
+        let reports: Vec<serde_json::Value> = sqlx::query_scalar(
+            "SELECT report FROM generated_reports WHERE owner_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(owner_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list reports")?;
+
+        reports
+            .into_iter()
+            .map(|r| serde_json::from_value(r).context("Failed to decode stored report"))
+            .collect()
+    }
+
+    /// Run the scheduler every `period` in the background
+    pub fn start(self: Arc<Self>, period: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                let stats = match self.run_once(Utc::now()).await {
+                    Ok(stats) => stats,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Report scheduler run failed");
+                        continue;
+                    }
+                };
+                if stats.generated + stats.failed + stats.expired > 0 {
+                    tracing::info!(
+                        generated = stats.generated,
+                        failed = stats.failed,
+                        deliveries_failed = stats.deliveries_failed,
+                        expired = stats.expired,
+                        "Report scheduler run completed"
+                    );
+                }
+            }
+        })
+    }
+
+    /// Generate and deliver every due schedule, then expire old reports
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<SchedulerRunStats> {
+        let mut stats = SchedulerRunStats::default();
+
+        let rows: Vec<ScheduleRow> = sqlx::query_as(&format!(
+            "SELECT {} FROM report_schedules WHERE enabled AND next_run <= $1",
+            SCHEDULE_COLUMNS
+        ))
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load due report schedules")?;
+
+        for row in rows {
+            let schedule = match ScheduledReport::try_from(row) {
+                Ok(schedule) => schedule,
+                Err(e) => {
+                    stats.failed += 1;
+                    tracing::warn!(error = %e, "Skipping unreadable report schedule");
+                    continue;
+                }
+            };
+
+            // Claim the run before generating so another replica doesn't run
+            // it too. A failed run waits for the next slot rather than
+            // retrying every tick.
+            let claimed = sqlx::query(
+                r#"
+                UPDATE report_schedules SET last_run = $2, next_run = $3
+                WHERE id = $1 AND next_run IS NOT DISTINCT FROM $4
+                "#,
+            )
+            .bind(schedule.id)
+            .bind(now)
+            .bind(schedule.schedule.next_run_after(now))
+            .bind(schedule.next_run)
+            .execute(&self.pool)
+            .await
+            .context("Failed to claim report schedule")?;
+            if claimed.rows_affected() == 0 {
+                continue;
+            }
+
+            let request = ReportRequest {
+                report_type: schedule.report_type,
+                format: schedule.format,
+                time_range: schedule.schedule.time_range(),
+                include_details: true,
+                filters: None,
+            };
+
+            match self
+                .generate_for(schedule.owner_id, Some(schedule.id), request)
+                .await
+            {
+                Ok(report) if report.status == ReportStatus::Ready => {
+                    stats.generated += 1;
+                    stats.deliveries_failed += self.deliver(&schedule, &report).await;
+                }
+                Ok(report) => {
+                    stats.failed += 1;
+                    tracing::warn!(
+                        schedule_id = %schedule.id,
+                        report_id = %report.id,
+                        error = report.error.as_deref().unwrap_or("unknown"),
+                        "Scheduled report failed"
+                    );
+                }
+                Err(e) => {
+                    stats.failed += 1;
+                    tracing::warn!(schedule_id = %schedule.id, error = %e, "Scheduled report failed");
+                }
+            }
+        }
+
+        stats.expired = self.expire_reports(now).await?;
+        Ok(stats)
+    }
+
+    /// Deliver to every sink, returning the number of failures
+    async fn deliver(&self, schedule: &ScheduledReport, report: &Report) -> usize {
+        let mut failures = 0;
+        for sink in &self.sinks {
+            if let Err(e) = sink.deliver(schedule, report).await {
+                failures += 1;
+                tracing::warn!(
+                    sink = sink.name(),
+                    schedule_id = %schedule.id,
+                    report_id = %report.id,
+                    error = %e,
+                    "Report delivery failed"
+                );
+            }
+        }
+        failures
+    }
+
+    /// Delete the files of reports past their expiry, then the reports
+    pub async fn expire_reports(&self, now: DateTime<Utc>) -> Result<usize> {
+        let expired: Vec<(Uuid, serde_json::Value)> =
+            sqlx::query_as("SELECT id, report FROM generated_reports WHERE expires_at <= $1")
+                .bind(now)
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to load expired reports")?;
+
+        let mut removed = 0;
+        for (id, report) in expired {
+            // A row that no longer decodes has no files we can find
+            match serde_json::from_value::<Report>(report) {
+                Ok(report) => {
+                    if let Err(e) = self.reporting.delete_report_files(&report).await {
+                        tracing::warn!(report_id = %id, error = %e, "Failed to delete expired report");
+                        continue;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(report_id = %id, error = %e, "Failed to decode expired report");
+                }
+            }
+
+            sqlx::query("DELETE FROM generated_reports WHERE id = $1")
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .context("Failed to delete expired report")?;
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+}
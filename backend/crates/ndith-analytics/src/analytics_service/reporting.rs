@@ -2,14 +2,17 @@
 //!
 //! Generates reports for analytics data export and scheduling.
 
-use anyhow::Result;
-use chrono::{DateTime, Duration, Utc};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::Path;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use super::dashboard::{DashboardService, TimeRange};
-use super::trends::TrendAnalysisService;
+use super::report_render::{render_csv, render_html, render_json, ReportDocument, ReportSection};
+use super::trends::{ArtistTrend, TrendAnalysisService};
 use crate::databases::DuckDbClient;
 
 /// Report types
@@ -49,6 +52,26 @@ pub enum ReportFormat {
     Html,
 }
 
+impl ReportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Csv => "csv",
+            ReportFormat::Parquet => "parquet",
+            ReportFormat::Html => "html",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "application/json",
+            ReportFormat::Csv => "text/csv; charset=utf-8",
+            ReportFormat::Parquet => "application/vnd.apache.parquet",
+            ReportFormat::Html => "text/html; charset=utf-8",
+        }
+    }
+}
+
 /// Report status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReportStatus {
@@ -71,10 +94,21 @@ pub struct Report {
     pub expires_at: Option<DateTime<Utc>>,
     pub file_path: Option<String>,
     pub file_size_bytes: Option<i64>,
+    /// Every file written; CSV and Parquet reports have one per section
+    #[serde(default)]
+    pub files: Vec<ReportFile>,
     pub summary: Option<ReportSummary>,
     pub error: Option<String>,
 }
 
+/// A file belonging to a report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportFile {
+    pub path: String,
+    pub section: Option<String>,
+    pub size_bytes: i64,
+}
+
 /// Summary of report contents
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportSummary {
@@ -114,6 +148,8 @@ pub struct ReportFilters {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduledReport {
     pub id: Uuid,
+    /// User who created the schedule and owns the reports it generates
+    pub owner_id: Uuid,
     pub report_type: ReportType,
     pub format: ReportFormat,
     pub schedule: ReportSchedule,
@@ -123,7 +159,8 @@ pub struct ScheduledReport {
     pub next_run: Option<DateTime<Utc>>,
 }
 
-/// Report schedule
+/// Report schedule. Hours are UTC; weekly `day` is 0 (Monday) to 6
+/// (Sunday); monthly `day` is clamped to 1-28 so every month has a run.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ReportSchedule {
     Daily { hour: u32 },
@@ -131,12 +168,72 @@ pub enum ReportSchedule {
     Monthly { day: u32, hour: u32 },
 }
 
+impl ReportSchedule {
+    /// First run time strictly after `after`
+    pub fn next_run_after(&self, after: DateTime<Utc>) -> DateTime<Utc> {
+        let date = after.date_naive();
+        match *self {
+            ReportSchedule::Daily { hour } => {
+                let today = at_hour(date, hour);
+                if today > after {
+                    today
+                } else {
+                    at_hour(date + Duration::days(1), hour)
+                }
+            }
+            ReportSchedule::Weekly { day, hour } => {
+                let target = day.min(6) as i64;
+                let current = date.weekday().num_days_from_monday() as i64;
+                let candidate = at_hour(
+                    date + Duration::days((target - current).rem_euclid(7)),
+                    hour,
+                );
+                if candidate > after {
+                    candidate
+                } else {
+                    candidate + Duration::days(7)
+                }
+            }
+            ReportSchedule::Monthly { day, hour } => {
+                let day = day.clamp(1, 28);
+                let this_month = date.with_day(day).unwrap_or(date);
+                let candidate = at_hour(this_month, hour);
+                if candidate > after {
+                    candidate
+                } else {
+                    let (year, month) = if date.month() == 12 {
+                        (date.year() + 1, 1)
+                    } else {
+                        (date.year(), date.month() + 1)
+                    };
+                    let next = NaiveDate::from_ymd_opt(year, month, day).unwrap_or(date);
+                    at_hour(next, hour)
+                }
+            }
+        }
+    }
+
+    /// Data window covered by each run
+    pub fn time_range(&self) -> TimeRange {
+        match self {
+            ReportSchedule::Daily { .. } => TimeRange::Custom { days: 1 },
+            ReportSchedule::Weekly { .. } => TimeRange::Last7Days,
+            ReportSchedule::Monthly { .. } => TimeRange::Last30Days,
+        }
+    }
+}
+
+fn at_hour(date: NaiveDate, hour: u32) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&date.and_hms_opt(hour.min(23), 0, 0).unwrap_or_default())
+}
+
 /// Reporting service
 pub struct ReportingService {
     duckdb: Arc<DuckDbClient>,
     dashboard: DashboardService,
     trends: TrendAnalysisService,
     output_dir: String,
+    retention: Duration,
 }
 
 impl ReportingService {
@@ -151,9 +248,16 @@ impl ReportingService {
             dashboard,
             trends,
             output_dir,
+            retention: Duration::days(7),
         }
     }
 
+    /// How long generated reports are kept before they expire
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
     /// Generate a report
     pub async fn generate_report(&self, request: ReportRequest) -> Result<Report> {
         let id = Uuid::new_v4();
@@ -167,6 +271,7 @@ impl ReportingService {
             expires_at: None,
             file_path: None,
             file_size_bytes: None,
+            files: vec![],
             summary: None,
             error: None,
         };
@@ -177,16 +282,26 @@ impl ReportingService {
             "Generating report"
         );
 
-        match self.generate_report_data(&request).await {
-            Ok((summary, file_path, file_size)) => {
+        let result = match self.generate_report_data(&request).await {
+            Ok(doc) => self
+                .write_report_files(id, &request.report_type.to_string(), request.format, &doc)
+                .await
+                .map(|files| (doc, files)),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok((doc, files)) => {
                 report.status = ReportStatus::Ready;
-                report.generated_at = Some(Utc::now());
-                report.expires_at = Some(Utc::now() + Duration::days(7));
-                report.file_path = file_path;
-                report.file_size_bytes = file_size;
-                report.summary = Some(summary);
+                report.generated_at = Some(doc.generated_at);
+                report.expires_at = Some(doc.generated_at + self.retention);
+                report.file_path = files.first().map(|f| f.path.clone());
+                report.file_size_bytes = Some(files.iter().map(|f| f.size_bytes).sum());
+                report.files = files;
+                report.summary = Some(doc.summary);
             }
             Err(e) => {
+                tracing::warn!(report_id = %id, error = %e, "Report generation failed");
                 report.status = ReportStatus::Failed;
                 report.error = Some(e.to_string());
             }
@@ -196,10 +311,7 @@ impl ReportingService {
     }
 
     /// Generate report data based on type
-    async fn generate_report_data(
-        &self,
-        request: &ReportRequest,
-    ) -> Result<(ReportSummary, Option<String>, Option<i64>)> {
+    async fn generate_report_data(&self, request: &ReportRequest) -> Result<ReportDocument> {
         match request.report_type {
             ReportType::DailySummary | ReportType::WeeklySummary | ReportType::MonthlySummary => {
                 self.generate_summary_report(request).await
@@ -213,10 +325,7 @@ impl ReportingService {
     }
 
     /// Generate summary report
-    async fn generate_summary_report(
-        &self,
-        request: &ReportRequest,
-    ) -> Result<(ReportSummary, Option<String>, Option<i64>)> {
+    async fn generate_summary_report(&self, request: &ReportRequest) -> Result<ReportDocument> {
         let metrics = self.dashboard.get_dashboard(request.time_range).await?;
 
         let highlights = vec![
@@ -261,23 +370,72 @@ impl ReportingService {
             highlights,
         };
 
-        // Generate file based on format
-        let file_info = self
-            .write_report_file(
-                &request.report_type.to_string(),
-                request.format,
-                &serde_json::to_string_pretty(&metrics)?,
-            )
-            .await?;
+        let mut platforms = ReportSection::new(
+            "platforms",
+            "Platform Sync",
+            &["platform", "healthy", "artists", "success_rate"],
+        )
+        .with_chart(0, 3);
+        for p in &metrics.sync_metrics.platforms {
+            platforms.push_row(vec![
+                json!(p.platform),
+                json!(p.healthy),
+                json!(p.artists_count),
+                json!(p.success_rate),
+            ]);
+        }
+
+        let mut trending = ReportSection::new(
+            "trending_artists",
+            "Trending Artists",
+            &["artist", "mentions", "trend", "change", "has_offense"],
+        )
+        .with_chart(0, 1);
+        for a in &metrics.trending_artists {
+            trending.push_row(vec![
+                json!(a.name),
+                json!(a.mentions),
+                json!(a.trend_direction),
+                json!(a.change_percentage),
+                json!(a.has_offense),
+            ]);
+        }
+
+        let mut offenses = ReportSection::new(
+            "recent_offenses",
+            "Recent Offenses",
+            &[
+                "detected_at",
+                "artist",
+                "category",
+                "severity",
+                "verified",
+                "article",
+            ],
+        );
+        for o in &metrics.recent_offenses {
+            offenses.push_row(vec![
+                json!(o.detected_at.to_rfc3339()),
+                json!(o.artist_name),
+                json!(o.category),
+                json!(o.severity),
+                json!(o.verified),
+                json!(o.article_title),
+            ]);
+        }
+
+        let sections = vec![key_metrics_section(&summary), platforms, trending, offenses];
 
-        Ok((summary, file_info.0, file_info.1))
+        Ok(document(
+            request,
+            summary,
+            sections,
+            serde_json::to_value(&metrics)?,
+        ))
     }
 
     /// Generate trend report
-    async fn generate_trend_report(
-        &self,
-        request: &ReportRequest,
-    ) -> Result<(ReportSummary, Option<String>, Option<i64>)> {
+    async fn generate_trend_report(&self, request: &ReportRequest) -> Result<ReportDocument> {
         let trends = self.trends.get_trend_summary().await?;
 
         let highlights = vec![
@@ -301,22 +459,38 @@ impl ReportingService {
             highlights,
         };
 
-        let file_info = self
-            .write_report_file(
-                &request.report_type.to_string(),
-                request.format,
-                &serde_json::to_string_pretty(&trends)?,
-            )
-            .await?;
+        let mut volume =
+            ReportSection::new("content_volume", "Content Volume", &["date", "articles"])
+                .with_chart(0, 1);
+        for point in &trends.content_volume_trend.data_points {
+            volume.push_row(vec![json!(point.date), json!(point.value)]);
+        }
+
+        let sections = vec![
+            key_metrics_section(&summary),
+            artist_trend_section(
+                "rising_artists",
+                "Rising Artists",
+                &trends.top_rising_artists,
+            ),
+            artist_trend_section(
+                "falling_artists",
+                "Falling Artists",
+                &trends.top_falling_artists,
+            ),
+            volume,
+        ];
 
-        Ok((summary, file_info.0, file_info.1))
+        Ok(document(
+            request,
+            summary,
+            sections,
+            serde_json::to_value(&trends)?,
+        ))
     }
 
     /// Generate platform health report
-    async fn generate_platform_report(
-        &self,
-        request: &ReportRequest,
-    ) -> Result<(ReportSummary, Option<String>, Option<i64>)> {
+    async fn generate_platform_report(&self, request: &ReportRequest) -> Result<ReportDocument> {
         let health = self
             .duckdb
             .get_platform_health(request.time_range.days())
@@ -348,22 +522,44 @@ impl ReportingService {
             highlights,
         };
 
-        let file_info = self
-            .write_report_file(
-                &request.report_type.to_string(),
-                request.format,
-                &serde_json::to_string_pretty(&health)?,
-            )
-            .await?;
+        let mut platforms = ReportSection::new(
+            "platform_health",
+            "Platform Health",
+            &[
+                "platform",
+                "total_syncs",
+                "successful_syncs",
+                "failed_syncs",
+                "success_rate",
+                "avg_duration_ms",
+                "artists_synced",
+                "api_calls",
+            ],
+        )
+        .with_chart(0, 4);
+        for p in &health {
+            platforms.push_row(vec![
+                json!(p.platform),
+                json!(p.total_syncs),
+                json!(p.successful_syncs),
+                json!(p.failed_syncs),
+                json!(p.success_rate),
+                json!(p.avg_duration_ms),
+                json!(p.total_artists_synced),
+                json!(p.total_api_calls),
+            ]);
+        }
 
-        Ok((summary, file_info.0, file_info.1))
+        Ok(document(
+            request,
+            summary,
+            vec![platforms],
+            serde_json::to_value(&health)?,
+        ))
     }
 
     /// Generate offense report
-    async fn generate_offense_report(
-        &self,
-        _request: &ReportRequest,
-    ) -> Result<(ReportSummary, Option<String>, Option<i64>)> {
+    async fn generate_offense_report(&self, request: &ReportRequest) -> Result<ReportDocument> {
         // Placeholder - would query offense data
         let summary = ReportSummary {
             total_records: 0,
@@ -371,14 +567,14 @@ impl ReportingService {
             highlights: vec!["No offense data available".to_string()],
         };
 
-        Ok((summary, None, None))
+        Ok(document(request, summary, vec![], serde_json::Value::Null))
     }
 
     /// Generate user activity report
     async fn generate_user_activity_report(
         &self,
         request: &ReportRequest,
-    ) -> Result<(ReportSummary, Option<String>, Option<i64>)> {
+    ) -> Result<ReportDocument> {
         let metrics = self.dashboard.get_dashboard(request.time_range).await?;
 
         let highlights = vec![
@@ -411,62 +607,124 @@ impl ReportingService {
             highlights,
         };
 
-        let file_info = self
-            .write_report_file(
-                &request.report_type.to_string(),
-                request.format,
-                &serde_json::to_string_pretty(&metrics.user_metrics)?,
-            )
-            .await?;
+        let users = &metrics.user_metrics;
+        let mut activity =
+            ReportSection::new("user_activity", "User Activity", &["metric", "value"]);
+        for (name, value) in [
+            ("total_users", json!(users.total_users)),
+            ("active_users", json!(users.active_users)),
+            ("new_users", json!(users.new_users)),
+            ("total_blocks", json!(users.total_blocks)),
+            ("new_blocks", json!(users.new_blocks)),
+            ("avg_blocks_per_user", json!(users.avg_blocks_per_user)),
+        ] {
+            activity.push_row(vec![json!(name), value]);
+        }
 
-        Ok((summary, file_info.0, file_info.1))
+        Ok(document(
+            request,
+            summary,
+            vec![activity],
+            serde_json::to_value(&metrics.user_metrics)?,
+        ))
     }
 
     /// Generate custom report
-    async fn generate_custom_report(
-        &self,
-        _request: &ReportRequest,
-    ) -> Result<(ReportSummary, Option<String>, Option<i64>)> {
+    async fn generate_custom_report(&self, request: &ReportRequest) -> Result<ReportDocument> {
         let summary = ReportSummary {
             total_records: 0,
             key_metrics: vec![],
             highlights: vec!["Custom reports require specific configuration".to_string()],
         };
 
-        Ok((summary, None, None))
+        Ok(document(request, summary, vec![], serde_json::Value::Null))
     }
 
-    /// Write report to file
-    async fn write_report_file(
+    /// Render a report in the requested format and write it to the output
+    /// directory. CSV and Parquet write one file per section.
+    async fn write_report_files(
         &self,
+        id: Uuid,
         name: &str,
         format: ReportFormat,
-        content: &str,
-    ) -> Result<(Option<String>, Option<i64>)> {
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let extension = match format {
-            ReportFormat::Json => "json",
-            ReportFormat::Csv => "csv",
-            ReportFormat::Parquet => "parquet",
-            ReportFormat::Html => "html",
-        };
+        doc: &ReportDocument,
+    ) -> Result<Vec<ReportFile>> {
+        tokio::fs::create_dir_all(&self.output_dir)
+            .await
+            .with_context(|| format!("Failed to create report directory {}", self.output_dir))?;
+
+        let timestamp = doc.generated_at.format("%Y%m%d_%H%M%S");
+        let base = format!("{}_{}_{}", name, timestamp, id.simple());
+        let extension = format.extension();
+
+        let mut files = Vec::new();
+        match format {
+            ReportFormat::Json => {
+                let path = self.output_path(&format!("{}.{}", base, extension));
+                files.push(write_file(&path, None, render_json(doc)?.as_bytes()).await?);
+            }
+            ReportFormat::Html => {
+                let path = self.output_path(&format!("{}.{}", base, extension));
+                files.push(write_file(&path, None, render_html(doc).as_bytes()).await?);
+            }
+            ReportFormat::Csv => {
+                for section in &doc.sections {
+                    let path =
+                        self.output_path(&format!("{}_{}.{}", base, section.name, extension));
+                    let csv = render_csv(section)?;
+                    files.push(write_file(&path, Some(&section.name), csv.as_bytes()).await?);
+                }
+            }
+            ReportFormat::Parquet => {
+                for section in &doc.sections {
+                    // Sections without rows have no columns to infer a schema from
+                    if section.rows.is_empty() {
+                        continue;
+                    }
+
+                    let path =
+                        self.output_path(&format!("{}_{}.{}", base, section.name, extension));
+                    let csv = tempfile::Builder::new()
+                        .suffix(".csv")
+                        .tempfile()
+                        .context("Failed to create staging file")?;
+                    tokio::fs::write(csv.path(), render_csv(section)?).await?;
+
+                    self.duckdb
+                        .export_csv_to_parquet(&csv.path().to_string_lossy(), &path)
+                        .await?;
+
+                    let size = tokio::fs::metadata(&path).await?.len() as i64;
+                    files.push(ReportFile {
+                        path,
+                        section: Some(section.name.clone()),
+                        size_bytes: size,
+                    });
+                }
+            }
+        }
 
-        let filename = format!("{}_{}.{}", name, timestamp, extension);
-        let file_path = format!("{}/{}", self.output_dir, filename);
+        Ok(files)
+    }
 
-        // For JSON format, write the content directly
-        if format == ReportFormat::Json {
-            if let Err(e) = tokio::fs::write(&file_path, content).await {
-                tracing::warn!(error = %e, "Failed to write report file");
-                return Ok((None, None));
-            }
+    fn output_path(&self, filename: &str) -> String {
+        Path::new(&self.output_dir)
+            .join(filename)
+            .to_string_lossy()
+            .into_owned()
+    }
 
-            let size = content.len() as i64;
-            return Ok((Some(file_path), Some(size)));
+    /// Remove a report's files. Missing files are ignored.
+    pub async fn delete_report_files(&self, report: &Report) -> Result<()> {
+        for file in &report.files {
+            match tokio::fs::remove_file(&file.path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e).with_context(|| format!("Failed to delete {}", file.path)),
+            }
         }
 
-        // For other formats, would need conversion
-        Ok((None, None))
+        Ok(())
     }
 
     /// Export data to Parquet
@@ -516,6 +774,80 @@ impl ReportingService {
     }
 }
 
+fn document(
+    request: &ReportRequest,
+    summary: ReportSummary,
+    sections: Vec<ReportSection>,
+    data: serde_json::Value,
+) -> ReportDocument {
+    let title = ReportingService::get_report_types()
+        .into_iter()
+        .find(|t| t.report_type == request.report_type)
+        .map(|t| t.name)
+        .unwrap_or_else(|| request.report_type.to_string());
+
+    ReportDocument {
+        title,
+        generated_at: Utc::now(),
+        time_range: format!("{:?}", request.time_range),
+        summary,
+        sections,
+        data,
+    }
+}
+
+fn key_metrics_section(summary: &ReportSummary) -> ReportSection {
+    let mut section =
+        ReportSection::new("key_metrics", "Key Metrics", &["metric", "value", "change"]);
+    for metric in &summary.key_metrics {
+        section.push_row(vec![
+            json!(metric.name),
+            json!(metric.value),
+            json!(metric.change),
+        ]);
+    }
+    section
+}
+
+fn artist_trend_section(name: &str, title: &str, artists: &[ArtistTrend]) -> ReportSection {
+    let mut section = ReportSection::new(
+        name,
+        title,
+        &[
+            "artist",
+            "current_mentions",
+            "previous_mentions",
+            "change",
+            "z_score",
+            "sentiment_change",
+        ],
+    )
+    .with_chart(0, 1);
+    for a in artists {
+        section.push_row(vec![
+            json!(a.artist_name),
+            json!(a.current_mentions),
+            json!(a.previous_mentions),
+            json!(a.change_percentage),
+            json!(a.z_score),
+            json!(a.sentiment_trend.change),
+        ]);
+    }
+    section
+}
+
+async fn write_file(path: &str, section: Option<&str>, contents: &[u8]) -> Result<ReportFile> {
+    tokio::fs::write(path, contents)
+        .await
+        .with_context(|| format!("Failed to write report file {}", path))?;
+
+    Ok(ReportFile {
+        path: path.to_string(),
+        section: section.map(str::to_string),
+        size_bytes: contents.len() as i64,
+    })
+}
+
 /// Report type information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportTypeInfo {
@@ -533,4 +865,32 @@ mod tests {
         assert_eq!(ReportType::DailySummary.to_string(), "daily_summary");
         assert_eq!(ReportType::TrendAnalysis.to_string(), "trend_analysis");
     }
+
+    #[test]
+    fn test_schedule_next_run() {
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        // Wednesday
+        let now = at("2024-03-13T10:30:00Z");
+
+        assert_eq!(
+            ReportSchedule::Daily { hour: 6 }.next_run_after(now),
+            at("2024-03-14T06:00:00Z")
+        );
+        assert_eq!(
+            ReportSchedule::Daily { hour: 12 }.next_run_after(now),
+            at("2024-03-13T12:00:00Z")
+        );
+        assert_eq!(
+            ReportSchedule::Weekly { day: 0, hour: 9 }.next_run_after(now),
+            at("2024-03-18T09:00:00Z")
+        );
+        assert_eq!(
+            ReportSchedule::Monthly { day: 31, hour: 0 }.next_run_after(now),
+            at("2024-03-28T00:00:00Z")
+        );
+        assert_eq!(
+            ReportSchedule::Monthly { day: 1, hour: 0 }.next_run_after(at("2024-12-01T00:00:00Z")),
+            at("2025-01-01T00:00:00Z")
+        );
+    }
 }
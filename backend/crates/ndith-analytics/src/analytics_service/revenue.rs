@@ -4,12 +4,17 @@
 //! Supports all major streaming platforms with per-platform payout rates.
 
 use anyhow::{Context, Result};
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use super::payout_model::{
+    payout_model, summarize_payouts, ArtistPlays, BillingPeriod, PayoutArtist, PayoutModelKind,
+    PayoutModelSummary,
+};
 use super::trouble_score::TroubleTier;
 
 /// Supported streaming platforms
@@ -72,6 +77,10 @@ pub struct PayoutRate {
     pub rate_per_minute: Option<Decimal>,
     pub subscription_monthly: Option<Decimal>,
     pub rate_tier: String,
+    /// ISO country code, or None for the default rate
+    pub country_code: Option<String>,
+    /// Share of subscription revenue paid out to rights holders
+    pub royalty_share: Decimal,
     pub effective_date: NaiveDate,
     pub source_url: Option<String>,
 }
@@ -109,6 +118,8 @@ pub struct UserRevenueDistribution {
     pub total_streams: i64,
     pub total_revenue: Decimal,
     pub subscription_cost: Option<Decimal>,
+    /// Country whose rates were applied, or None for default rates
+    pub country: Option<String>,
 
     /// Revenue breakdown
     pub revenue_to_clean_artists: Decimal,
@@ -119,6 +130,10 @@ pub struct UserRevenueDistribution {
     pub top_artists: Vec<ArtistRevenueBreakdown>,
     /// Top problematic artists receiving revenue
     pub top_problematic_artists: Vec<ArtistRevenueBreakdown>,
+
+    /// The same listening under each payout model, using the rates in
+    /// effect for each month
+    pub payout_models: Vec<PayoutModelSummary>,
 }
 
 /// Playcount record for a user-artist pair
@@ -163,6 +178,20 @@ impl RevenueService {
         platform: Platform,
         tier: Option<&str>,
     ) -> Result<PayoutRate> {
+        self.get_payout_rate_on(platform, tier, None, Utc::now().date_naive())
+            .await?
+            .context("No payout rate found for platform")
+    }
+
+    /// Get the payout rate in effect on a date. A country-specific rate is
+    /// preferred; the default rate applies to countries without one.
+    pub async fn get_payout_rate_on(
+        &self,
+        platform: Platform,
+        tier: Option<&str>,
+        country: Option<&str>,
+        date: NaiveDate,
+    ) -> Result<Option<PayoutRate>> {
         let tier = tier.unwrap_or("standard");
 
         let rate = sqlx::query_as::<_, PayoutRateRow>(
@@ -173,43 +202,66 @@ impl RevenueService {
                 rate_per_minute,
                 subscription_monthly,
                 rate_tier,
+                country_code,
+                royalty_share,
                 effective_date,
                 source_url
             FROM platform_payout_rates
             WHERE platform = $1
             AND rate_tier = $2
-            AND effective_date <= CURRENT_DATE
-            AND (end_date IS NULL OR end_date >= CURRENT_DATE)
-            ORDER BY effective_date DESC
+            AND (country_code IS NULL OR country_code = $3)
+            AND effective_date <= $4
+            AND (end_date IS NULL OR end_date >= $4)
+            ORDER BY country_code IS NULL, effective_date DESC
             LIMIT 1
             "#,
         )
         .bind(platform.as_str())
         .bind(tier)
+        .bind(country)
+        .bind(date)
         .fetch_optional(&self.pool)
         .await
         .context("Failed to fetch payout rate")?;
 
-        rate.map(|r| r.into_rate())
-            .context("No payout rate found for platform")
+        Ok(rate.map(|r| r.into_rate()))
+    }
+
+    /// Per-stream rate for stored playcounts of a period: the standard,
+    /// country-independent rate in effect at the period start, or the
+    /// current one for periods before the platform's first rate
+    pub async fn get_period_rate(
+        &self,
+        platform: Platform,
+        period_start: NaiveDate,
+    ) -> Result<Decimal> {
+        match self
+            .get_payout_rate_on(platform, None, None, period_start)
+            .await?
+        {
+            Some(rate) => Ok(rate.rate_per_stream),
+            None => Ok(self.get_payout_rate(platform, None).await?.rate_per_stream),
+        }
     }
 
     /// Get all current payout rates
     pub async fn get_all_payout_rates(&self) -> Result<Vec<PayoutRate>> {
         let rates = sqlx::query_as::<_, PayoutRateRow>(
             r#"
-            SELECT DISTINCT ON (platform, rate_tier)
+            SELECT DISTINCT ON (platform, rate_tier, country_code)
                 platform,
                 rate_per_stream,
                 rate_per_minute,
                 subscription_monthly,
                 rate_tier,
+                country_code,
+                royalty_share,
                 effective_date,
                 source_url
             FROM platform_payout_rates
             WHERE effective_date <= CURRENT_DATE
             AND (end_date IS NULL OR end_date >= CURRENT_DATE)
-            ORDER BY platform, rate_tier, effective_date DESC
+            ORDER BY platform, rate_tier, country_code NULLS FIRST, effective_date DESC
             "#,
         )
         .fetch_all(&self.pool)
@@ -221,38 +273,11 @@ impl RevenueService {
 
     /// Record playcount data for a user-artist pair
     pub async fn record_playcount(&self, params: RecordPlaycountParams) -> Result<UserPlaycount> {
-        // Get current rate
-        let rate = self.get_payout_rate(params.platform, None).await?;
-        let estimated_revenue = rate.rate_per_stream * Decimal::from(params.play_count);
+        let rate = self
+            .get_period_rate(params.platform, params.period_start)
+            .await?;
 
-        let row = sqlx::query_as::<_, PlaycountRow>(
-            r#"
-            INSERT INTO user_artist_playcounts (
-                user_id, artist_id, platform, play_count, listening_time_ms,
-                estimated_revenue, rate_used, period_type, period_start, period_end
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, 'monthly', $8, $9)
-            ON CONFLICT (user_id, artist_id, platform, period_type, period_start) DO UPDATE SET
-                play_count = user_artist_playcounts.play_count + EXCLUDED.play_count,
-                listening_time_ms = COALESCE(user_artist_playcounts.listening_time_ms, 0) + COALESCE(EXCLUDED.listening_time_ms, 0),
-                estimated_revenue = user_artist_playcounts.estimated_revenue + EXCLUDED.estimated_revenue,
-                updated_at = NOW()
-            RETURNING
-                id, user_id, artist_id, platform, play_count, listening_time_ms,
-                estimated_revenue, period_start, period_end
-            "#,
-        )
-        .bind(params.user_id)
-        .bind(params.artist_id)
-        .bind(params.platform.as_str())
-        .bind(params.play_count)
-        .bind(params.listening_time_ms)
-        .bind(estimated_revenue)
-        .bind(rate.rate_per_stream)
-        .bind(params.period_start)
-        .bind(params.period_end)
-        .fetch_one(&self.pool)
-        .await
-        .context("Failed to record playcount")?;
+        let row = upsert_playcount(&self.pool, &params, rate).await?;
 
         // Get artist name
         let artist_name: String =
@@ -440,6 +465,8 @@ impl RevenueService {
         user_id: Uuid,
         platform: Option<Platform>,
         days: i32,
+        country: Option<&str>,
+        tier: Option<&str>,
     ) -> Result<UserRevenueDistribution> {
         let platform_str = platform.map(|p| p.as_str()).unwrap_or("all");
 
@@ -505,21 +532,138 @@ impl RevenueService {
             .get_user_problematic_artists(user_id, TroubleTier::Moderate, days, 10)
             .await?;
 
+        let (periods, artists) = self
+            .get_billing_periods(user_id, platform, days, country, tier)
+            .await?;
+
+        // One subscription payment per platform per month listened
+        let subscription_cost = if periods.is_empty() {
+            None
+        } else {
+            periods
+                .iter()
+                .map(|p| p.rate.subscription_monthly)
+                .sum::<Option<Decimal>>()
+        };
+
+        let payout_models = PayoutModelKind::all()
+            .into_iter()
+            .map(|kind| summarize_payouts(payout_model(kind).as_ref(), &periods, &artists, 10))
+            .collect();
+
         Ok(UserRevenueDistribution {
             user_id,
             platform: platform_str.to_string(),
             period: format!("last_{}_days", days),
             total_streams: totals.total_streams.unwrap_or(0),
             total_revenue: totals.total_revenue,
-            subscription_cost: None, // Would need user subscription info
+            subscription_cost,
+            country: country.map(str::to_string),
             revenue_to_clean_artists: totals.clean_revenue,
             revenue_to_problematic_artists: totals.problematic_revenue,
             problematic_percentage,
             top_artists,
             top_problematic_artists: top_problematic,
+            payout_models,
         })
     }
 
+    /// Group a user's playcounts into monthly billing periods, each with the
+    /// payout rate in effect for that month
+    async fn get_billing_periods(
+        &self,
+        user_id: Uuid,
+        platform: Option<Platform>,
+        days: i32,
+        country: Option<&str>,
+        tier: Option<&str>,
+    ) -> Result<(Vec<BillingPeriod>, HashMap<Uuid, PayoutArtist>)> {
+        let rows = sqlx::query_as::<_, BillingPlayRow>(
+            r#"
+            SELECT
+                pc.artist_id,
+                a.canonical_name as artist_name,
+                ts.trouble_tier::text as trouble_tier,
+                pc.platform,
+                pc.period_start,
+                pc.play_count::bigint as play_count
+            FROM user_artist_playcounts pc
+            JOIN artists a ON a.id = pc.artist_id
+            LEFT JOIN artist_trouble_scores ts ON ts.artist_id = pc.artist_id
+            WHERE pc.user_id = $1
+            AND ($2::text IS NULL OR pc.platform = $2)
+            AND pc.period_start >= CURRENT_DATE - $3::integer
+            ORDER BY pc.platform, pc.period_start
+            "#,
+        )
+        .bind(user_id)
+        .bind(platform.map(|p| p.as_str()))
+        .bind(days)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch billing periods")?;
+
+        let mut artists = HashMap::new();
+        let mut periods: Vec<BillingPeriod> = Vec::new();
+
+        for row in rows {
+            artists
+                .entry(row.artist_id)
+                .or_insert_with(|| PayoutArtist {
+                    artist_name: row.artist_name.clone(),
+                    trouble_tier: row.trouble_tier.as_deref().and_then(parse_trouble_tier),
+                });
+
+            let plays = ArtistPlays {
+                artist_id: row.artist_id,
+                play_count: row.play_count,
+            };
+
+            // Rows are ordered by platform and month, so a new period starts
+            // whenever either changes
+            if let Some(current) = periods.last_mut() {
+                if current.platform == row.platform && current.period_start == row.period_start {
+                    current.plays.push(plays);
+                    continue;
+                }
+            }
+
+            let Some(row_platform) = Platform::parse_platform(&row.platform) else {
+                continue;
+            };
+
+            // Months before the first recorded rate use the current one
+            let rate = match self
+                .get_payout_rate_on(row_platform, tier, country, row.period_start)
+                .await?
+            {
+                Some(rate) => Some(rate),
+                None => {
+                    self.get_payout_rate_on(row_platform, tier, country, Utc::now().date_naive())
+                        .await?
+                }
+            };
+
+            let Some(rate) = rate else {
+                tracing::warn!(
+                    platform = %row.platform,
+                    tier = tier.unwrap_or("standard"),
+                    "No payout rate for platform, skipping its plays"
+                );
+                continue;
+            };
+
+            periods.push(BillingPeriod {
+                platform: row.platform,
+                period_start: row.period_start,
+                rate,
+                plays: vec![plays],
+            });
+        }
+
+        Ok((periods, artists))
+    }
+
     /// Get revenue breakdown for a specific artist
     pub async fn get_artist_revenue(
         &self,
@@ -718,6 +862,60 @@ pub struct GlobalArtistRevenue {
 
 // Internal row types for sqlx
 
+/// Record playcount on an existing connection (e.g. inside a caller's
+/// transaction), using an already-fetched per-stream rate
+pub(super) async fn record_playcount_with_rate(
+    conn: &mut PgConnection,
+    params: &RecordPlaycountParams,
+    rate_per_stream: Decimal,
+) -> Result<()> {
+    upsert_playcount(conn, params, rate_per_stream).await?;
+    Ok(())
+}
+
+/// Add plays to the user-artist monthly playcount, accumulating revenue at
+/// the given rate
+async fn upsert_playcount<'e, E>(
+    executor: E,
+    params: &RecordPlaycountParams,
+    rate_per_stream: Decimal,
+) -> Result<PlaycountRow>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let estimated_revenue = rate_per_stream * Decimal::from(params.play_count);
+
+    sqlx::query_as::<_, PlaycountRow>(
+        r#"
+        INSERT INTO user_artist_playcounts (
+            user_id, artist_id, platform, play_count, listening_time_ms,
+            estimated_revenue, rate_used, period_type, period_start, period_end
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, 'monthly', $8, $9)
+        ON CONFLICT (user_id, artist_id, platform, period_type, period_start) DO UPDATE SET
+            play_count = user_artist_playcounts.play_count + EXCLUDED.play_count,
+            listening_time_ms = COALESCE(user_artist_playcounts.listening_time_ms, 0) + COALESCE(EXCLUDED.listening_time_ms, 0),
+            estimated_revenue = user_artist_playcounts.estimated_revenue + EXCLUDED.estimated_revenue,
+            rate_used = EXCLUDED.rate_used,
+            updated_at = NOW()
+        RETURNING
+            id, user_id, artist_id, platform, play_count, listening_time_ms,
+            estimated_revenue, period_start, period_end
+        "#,
+    )
+    .bind(params.user_id)
+    .bind(params.artist_id)
+    .bind(params.platform.as_str())
+    .bind(params.play_count)
+    .bind(params.listening_time_ms)
+    .bind(estimated_revenue)
+    .bind(rate_per_stream)
+    .bind(params.period_start)
+    .bind(params.period_end)
+    .fetch_one(executor)
+    .await
+    .context("Failed to record playcount")
+}
+
 #[derive(Debug, sqlx::FromRow)]
 struct PayoutRateRow {
     platform: String,
@@ -725,6 +923,8 @@ struct PayoutRateRow {
     rate_per_minute: Option<Decimal>,
     subscription_monthly: Option<Decimal>,
     rate_tier: String,
+    country_code: Option<String>,
+    royalty_share: Decimal,
     effective_date: NaiveDate,
     source_url: Option<String>,
 }
@@ -737,6 +937,8 @@ impl PayoutRateRow {
             rate_per_minute: self.rate_per_minute,
             subscription_monthly: self.subscription_monthly,
             rate_tier: self.rate_tier,
+            country_code: self.country_code,
+            royalty_share: self.royalty_share,
             effective_date: self.effective_date,
             source_url: self.source_url,
         }
@@ -766,6 +968,16 @@ struct ArtistRevenueRow {
     total_revenue: Decimal,
 }
 
+#[derive(Debug, sqlx::FromRow)]
+struct BillingPlayRow {
+    artist_id: Uuid,
+    artist_name: String,
+    trouble_tier: Option<String>,
+    platform: String,
+    period_start: NaiveDate,
+    play_count: i64,
+}
+
 #[derive(Debug, sqlx::FromRow)]
 struct RevenueTotalsRow {
     total_streams: Option<i64>,
@@ -803,6 +1015,16 @@ struct GlobalRevenueRow {
     unique_listeners: Option<i32>,
 }
 
+fn parse_trouble_tier(tier: &str) -> Option<TroubleTier> {
+    match tier {
+        "critical" => Some(TroubleTier::Critical),
+        "high" => Some(TroubleTier::High),
+        "moderate" => Some(TroubleTier::Moderate),
+        "low" => Some(TroubleTier::Low),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
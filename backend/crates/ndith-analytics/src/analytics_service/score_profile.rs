@@ -0,0 +1,611 @@
+//! Trouble Score Profiles
+//!
+//! Scoring profiles are versioned weight sets, optionally limited to a set of
+//! offense categories. The stored scores use the default profile and are
+//! computed in SQL (`recalculate_trouble_score`); this module computes the
+//! same components from source rows so any profile can be applied on the fly,
+//! explained component by component, or simulated across every artist without
+//! writing anything.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::trouble_score::{ScoreWeights, TierDistribution, TroubleScoreComponents, TroubleTier};
+
+/// One version of a named scoring profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreProfile {
+    pub id: Uuid,
+    pub name: String,
+    pub version: i32,
+    /// None for shared profiles
+    pub owner_user_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub weights: ScoreWeights,
+    /// Offense categories that count towards the score; None counts all
+    pub categories: Option<Vec<String>>,
+    pub is_default: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Evidence row backing an offense
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceSource {
+    pub evidence_id: Uuid,
+    pub url: String,
+    pub source_name: Option<String>,
+    /// 1-5, None when unrated
+    pub credibility_score: Option<i32>,
+    /// News story the evidence reports; copies of one story count once
+    pub story_id: Option<Uuid>,
+    /// Credibility multiplier from link health: 0.5 for a dead link without
+    /// a snapshot, 0.75 for a changed page, otherwise 1.0
+    pub link_weight: f64,
+}
+
+/// Offense row with its evidence, as used by the score components
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OffenseSource {
+    pub offense_id: Uuid,
+    pub title: String,
+    pub category: String,
+    pub severity: String,
+    pub status: String,
+    pub incident_date: Option<NaiveDate>,
+    /// Latest verified timeline event other than an acquittal, dismissal or
+    /// apology
+    pub latest_event_date: Option<NaiveDate>,
+    pub evidence: Vec<EvidenceSource>,
+}
+
+impl OffenseSource {
+    /// Pending and verified offenses count towards severity and recency
+    fn is_active(&self) -> bool {
+        matches!(self.status.as_str(), "verified" | "pending")
+    }
+
+    /// Date recency is measured from: the latest verified event, otherwise
+    /// the incident
+    fn recency_date(&self) -> Option<NaiveDate> {
+        self.latest_event_date.or(self.incident_date)
+    }
+
+    /// Evidence grouped by news story, each group scored by its best
+    /// link-weighted credibility (None when nothing in it is rated)
+    fn evidence_groups(&self) -> Vec<Option<f64>> {
+        let mut groups: Vec<(Uuid, Option<f64>)> = Vec::new();
+        for evidence in &self.evidence {
+            let key = evidence.story_id.unwrap_or(evidence.evidence_id);
+            let value = evidence
+                .credibility_score
+                .map(|score| score as f64 * evidence.link_weight);
+            match groups.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, best)) => {
+                    *best = match (*best, value) {
+                        (Some(a), Some(b)) => Some(a.max(b)),
+                        (a, b) => a.or(b),
+                    }
+                }
+                None => groups.push((key, value)),
+            }
+        }
+        groups.into_iter().map(|(_, value)| value).collect()
+    }
+}
+
+/// Everything needed to score one artist
+#[derive(Debug, Clone)]
+pub struct ArtistScoreInputs {
+    pub artist_id: Uuid,
+    pub artist_name: String,
+    pub offenses: Vec<OffenseSource>,
+    pub block_count: i64,
+    pub revenue_score: f64,
+}
+
+/// Values shared by every artist in a scoring run
+#[derive(Debug, Clone, Copy)]
+pub struct ScoringContext {
+    pub today: NaiveDate,
+    /// Highest block count of any artist, used to normalise the community score
+    pub max_block_count: i64,
+}
+
+fn severity_value(severity: &str) -> f64 {
+    match severity {
+        "egregious" => 1.0,
+        "severe" => 0.75,
+        "moderate" => 0.5,
+        "minor" => 0.25,
+        _ => 0.0,
+    }
+}
+
+fn recency_value(days_ago: i64) -> f64 {
+    let years_ago = days_ago as f64 / 365.25;
+    match years_ago {
+        y if y < 1.0 => 1.0,
+        y if y < 3.0 => 0.75,
+        y if y < 5.0 => 0.5,
+        y if y < 10.0 => 0.25,
+        _ => 0.1,
+    }
+}
+
+fn matches_categories(offense: &OffenseSource, categories: Option<&[String]>) -> bool {
+    match categories {
+        Some(categories) => categories.contains(&offense.category),
+        None => true,
+    }
+}
+
+/// Offenses counted under a category filter
+pub fn counted_offenses<'a>(
+    inputs: &'a ArtistScoreInputs,
+    categories: Option<&[String]>,
+) -> Vec<&'a OffenseSource> {
+    inputs
+        .offenses
+        .iter()
+        .filter(|o| matches_categories(o, categories))
+        .collect()
+}
+
+/// Component scores, matching the SQL `calculate_*_score` functions.
+///
+/// With a category filter, an artist with no matching offenses scores zero
+/// on every component: blocks and revenue only matter for artists the
+/// profile cares about.
+pub fn compute_components(
+    inputs: &ArtistScoreInputs,
+    categories: Option<&[String]>,
+    ctx: &ScoringContext,
+) -> TroubleScoreComponents {
+    let offenses = counted_offenses(inputs, categories);
+    if categories.is_some() && offenses.is_empty() {
+        return TroubleScoreComponents::default();
+    }
+
+    let severity_score = offenses
+        .iter()
+        .filter(|o| o.is_active())
+        .map(|o| severity_value(&o.severity))
+        .fold(0.0, f64::max);
+
+    // The SQL averages over offense/story join rows: an offense without
+    // evidence still contributes one row to the verified ratio
+    let mut credibility_sum = 0.0;
+    let mut credibility_count = 0usize;
+    let mut verified_rows = 0usize;
+    let mut total_rows = 0usize;
+    for offense in &offenses {
+        let groups = offense.evidence_groups();
+        let rows = groups.len().max(1);
+        total_rows += rows;
+        if offense.status == "verified" {
+            verified_rows += rows;
+        }
+        for credibility in groups.into_iter().flatten() {
+            credibility_sum += credibility;
+            credibility_count += 1;
+        }
+    }
+    let evidence_score = if total_rows == 0 || credibility_count == 0 {
+        0.0
+    } else {
+        let avg_credibility = credibility_sum / credibility_count as f64;
+        (avg_credibility / 5.0) * (0.5 + 0.5 * verified_rows as f64 / total_rows as f64)
+    };
+
+    let recency_score = offenses
+        .iter()
+        .filter(|o| o.is_active())
+        .filter_map(|o| o.recency_date())
+        .max()
+        .map(|latest| recency_value((ctx.today - latest).num_days()))
+        .unwrap_or(0.0);
+
+    let community_score = inputs.block_count as f64 / ctx.max_block_count.max(1) as f64;
+
+    TroubleScoreComponents {
+        severity_score,
+        evidence_score,
+        recency_score,
+        community_score,
+        revenue_score: inputs.revenue_score,
+    }
+}
+
+/// Reference to the profile version a result was computed with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileRef {
+    pub id: Option<Uuid>,
+    pub name: String,
+    pub version: Option<i32>,
+}
+
+impl From<&ScoreProfile> for ProfileRef {
+    fn from(profile: &ScoreProfile) -> Self {
+        Self {
+            id: Some(profile.id),
+            name: profile.name.clone(),
+            version: Some(profile.version),
+        }
+    }
+}
+
+/// How much one component added to the total
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentContribution {
+    pub component: String,
+    pub score: f64,
+    pub weight: f64,
+    /// score * weight
+    pub contribution: f64,
+    /// Share of the total score, 0-100
+    pub percentage: f64,
+    pub detail: String,
+    /// Offenses whose rows determined this component
+    pub source_offense_ids: Vec<Uuid>,
+}
+
+/// Per-component breakdown of an artist's score under one profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    pub artist_id: Uuid,
+    pub artist_name: String,
+    pub profile: ProfileRef,
+    pub categories: Option<Vec<String>>,
+    pub total_score: f64,
+    pub trouble_tier: TroubleTier,
+    pub contributions: Vec<ComponentContribution>,
+    pub block_count: i64,
+    /// Offense and evidence rows the score was computed from
+    pub offenses: Vec<OffenseSource>,
+    /// Offenses ignored by the profile's category filter
+    pub excluded_offense_count: usize,
+}
+
+/// Explain an artist's score under the given weights and category filter
+pub fn explain_score(
+    inputs: &ArtistScoreInputs,
+    profile: ProfileRef,
+    weights: &ScoreWeights,
+    categories: Option<&[String]>,
+    ctx: &ScoringContext,
+) -> ScoreExplanation {
+    let components = compute_components(inputs, categories, ctx);
+    let total_score = weights.apply(&components);
+    let offenses = counted_offenses(inputs, categories);
+    let active: Vec<&OffenseSource> = offenses.iter().copied().filter(|o| o.is_active()).collect();
+
+    let most_severe = active
+        .iter()
+        .map(|o| severity_value(&o.severity))
+        .fold(0.0, f64::max);
+    let severity_sources: Vec<Uuid> = active
+        .iter()
+        .filter(|o| most_severe > 0.0 && severity_value(&o.severity) == most_severe)
+        .map(|o| o.offense_id)
+        .collect();
+
+    let latest = active.iter().filter_map(|o| o.recency_date()).max();
+    let recency_sources: Vec<Uuid> = active
+        .iter()
+        .filter(|o| latest.is_some() && o.recency_date() == latest)
+        .map(|o| o.offense_id)
+        .collect();
+
+    let rated_evidence = offenses
+        .iter()
+        .flat_map(|o| o.evidence.iter())
+        .filter(|e| e.credibility_score.is_some())
+        .count();
+    let verified = offenses.iter().filter(|o| o.status == "verified").count();
+
+    let component = |name: &str, score: f64, weight: f64, detail: String, sources: Vec<Uuid>| {
+        let contribution = score * weight;
+        ComponentContribution {
+            component: name.to_string(),
+            score,
+            weight,
+            contribution,
+            percentage: if total_score > 0.0 {
+                contribution / total_score * 100.0
+            } else {
+                0.0
+            },
+            detail,
+            source_offense_ids: sources,
+        }
+    };
+
+    let contributions = vec![
+        component(
+            "severity",
+            components.severity_score,
+            weights.severity_weight,
+            match active
+                .iter()
+                .find(|o| severity_sources.contains(&o.offense_id))
+            {
+                Some(o) => format!("Most severe active offense is {} ({})", o.severity, o.title),
+                None => "No pending or verified offenses".to_string(),
+            },
+            severity_sources,
+        ),
+        component(
+            "evidence",
+            components.evidence_score,
+            weights.evidence_weight,
+            format!(
+                "{} rated evidence items; {} of {} offenses verified",
+                rated_evidence,
+                verified,
+                offenses.len()
+            ),
+            offenses.iter().map(|o| o.offense_id).collect(),
+        ),
+        component(
+            "recency",
+            components.recency_score,
+            weights.recency_weight,
+            match latest {
+                Some(date) => format!("Most recent active offense or verified event on {}", date),
+                None => "No dated pending or verified offenses".to_string(),
+            },
+            recency_sources,
+        ),
+        component(
+            "community",
+            components.community_score,
+            weights.community_weight,
+            format!(
+                "Blocked by {} users (most blocked artist: {})",
+                inputs.block_count, ctx.max_block_count
+            ),
+            vec![],
+        ),
+        component(
+            "revenue",
+            components.revenue_score,
+            weights.revenue_weight,
+            "Revenue contribution factor".to_string(),
+            vec![],
+        ),
+    ];
+
+    ScoreExplanation {
+        artist_id: inputs.artist_id,
+        artist_name: inputs.artist_name.clone(),
+        profile,
+        categories: categories.map(|c| c.to_vec()),
+        total_score,
+        trouble_tier: TroubleTier::from_score(total_score),
+        contributions,
+        block_count: inputs.block_count,
+        excluded_offense_count: inputs.offenses.len() - offenses.len(),
+        offenses: offenses.into_iter().cloned().collect(),
+    }
+}
+
+/// Leaderboard entry under simulated weights
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedScore {
+    pub rank: i32,
+    pub artist_id: Uuid,
+    pub artist_name: String,
+    pub total_score: f64,
+    pub trouble_tier: TroubleTier,
+    pub current_score: Option<f64>,
+    pub current_tier: Option<TroubleTier>,
+}
+
+/// Outcome of scoring every artist under candidate weights
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhatIfResult {
+    pub weights: ScoreWeights,
+    pub categories: Option<Vec<String>>,
+    pub distribution: TierDistribution,
+    pub current_distribution: TierDistribution,
+    /// Artists whose tier would change
+    pub tier_changes: i64,
+    pub leaderboard: Vec<SimulatedScore>,
+}
+
+/// Stored score and tier per artist
+pub type CurrentScores = HashMap<Uuid, (f64, TroubleTier)>;
+
+/// Score every artist under candidate weights without persisting anything
+pub fn simulate(
+    artists: &[ArtistScoreInputs],
+    current: &CurrentScores,
+    weights: &ScoreWeights,
+    categories: Option<&[String]>,
+    ctx: &ScoringContext,
+    limit: usize,
+) -> WhatIfResult {
+    let mut distribution = TierDistribution::default();
+    let mut current_distribution = TierDistribution::default();
+    let mut tier_changes = 0;
+
+    let mut scored: Vec<SimulatedScore> = artists
+        .iter()
+        .map(|inputs| {
+            let total_score = weights.apply(&compute_components(inputs, categories, ctx));
+            let trouble_tier = TroubleTier::from_score(total_score);
+            let stored = current.get(&inputs.artist_id);
+
+            distribution.add(trouble_tier);
+            if let Some((_, tier)) = stored {
+                current_distribution.add(*tier);
+                if *tier != trouble_tier {
+                    tier_changes += 1;
+                }
+            }
+
+            SimulatedScore {
+                rank: 0,
+                artist_id: inputs.artist_id,
+                artist_name: inputs.artist_name.clone(),
+                total_score,
+                trouble_tier,
+                current_score: stored.map(|(score, _)| *score),
+                current_tier: stored.map(|(_, tier)| *tier),
+            }
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.total_score.total_cmp(&a.total_score));
+    scored.truncate(limit);
+    for (i, entry) in scored.iter_mut().enumerate() {
+        entry.rank = i as i32 + 1;
+    }
+
+    WhatIfResult {
+        weights: weights.clone(),
+        categories: categories.map(|c| c.to_vec()),
+        distribution,
+        current_distribution,
+        tier_changes,
+        leaderboard: scored,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn offense(category: &str, severity: &str, status: &str, date: NaiveDate) -> OffenseSource {
+        OffenseSource {
+            offense_id: Uuid::new_v4(),
+            title: format!("{} offense", category),
+            category: category.to_string(),
+            severity: severity.to_string(),
+            status: status.to_string(),
+            incident_date: Some(date),
+            latest_event_date: None,
+            evidence: vec![EvidenceSource {
+                evidence_id: Uuid::new_v4(),
+                url: "https://example.com".to_string(),
+                source_name: None,
+                credibility_score: Some(5),
+                story_id: None,
+                link_weight: 1.0,
+            }],
+        }
+    }
+
+    fn context() -> ScoringContext {
+        ScoringContext {
+            today: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            max_block_count: 10,
+        }
+    }
+
+    fn artist() -> ArtistScoreInputs {
+        let recent = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let old = NaiveDate::from_ymd_opt(2012, 1, 1).unwrap();
+        ArtistScoreInputs {
+            artist_id: Uuid::new_v4(),
+            artist_name: "Artist".to_string(),
+            offenses: vec![
+                offense("hate_speech", "minor", "verified", recent),
+                offense("violent_crime", "severe", "verified", old),
+            ],
+            block_count: 5,
+            revenue_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_components_match_sql_rules() {
+        let components = compute_components(&artist(), None, &context());
+        assert_eq!(components.severity_score, 0.75);
+        assert_eq!(components.evidence_score, 1.0);
+        assert_eq!(components.recency_score, 1.0);
+        assert_eq!(components.community_score, 0.5);
+    }
+
+    #[test]
+    fn test_verified_events_and_story_copies_follow_sql_rules() {
+        let mut inputs = artist();
+        // A recent verified conviction makes the old offense recent again
+        inputs.offenses[1].latest_event_date = NaiveDate::from_ymd_opt(2024, 3, 1);
+        let violence = vec!["violent_crime".to_string()];
+        let components = compute_components(&inputs, Some(&violence), &context());
+        assert_eq!(components.recency_score, 1.0);
+
+        // Two copies of one story count once; a dead link halves credibility
+        let story = Some(Uuid::new_v4());
+        let copy = |credibility: i32, link_weight: f64| EvidenceSource {
+            evidence_id: Uuid::new_v4(),
+            url: "https://example.com".to_string(),
+            source_name: None,
+            credibility_score: Some(credibility),
+            story_id: story,
+            link_weight,
+        };
+        inputs.offenses[1].evidence = vec![copy(4, 1.0), copy(5, 0.5)];
+        let components = compute_components(&inputs, Some(&violence), &context());
+        assert_eq!(components.evidence_score, 4.0 / 5.0);
+    }
+
+    #[test]
+    fn test_category_filter_limits_counted_offenses() {
+        let violence = vec!["violent_crime".to_string()];
+        let components = compute_components(&artist(), Some(&violence), &context());
+        assert_eq!(components.severity_score, 0.75);
+        assert_eq!(components.recency_score, 0.1);
+
+        let unrelated = vec!["fraud".to_string()];
+        let components = compute_components(&artist(), Some(&unrelated), &context());
+        assert_eq!(components.community_score, 0.0);
+    }
+
+    #[test]
+    fn test_explanation_contributions_sum_to_total() {
+        let weights = ScoreWeights::default();
+        let explanation = explain_score(
+            &artist(),
+            ProfileRef {
+                id: None,
+                name: "default".to_string(),
+                version: None,
+            },
+            &weights,
+            None,
+            &context(),
+        );
+
+        let sum: f64 = explanation
+            .contributions
+            .iter()
+            .map(|c| c.contribution)
+            .sum();
+        assert!((sum - explanation.total_score).abs() < 1e-9);
+        assert_eq!(explanation.contributions[0].source_offense_ids.len(), 1);
+    }
+
+    #[test]
+    fn test_simulate_counts_tier_changes() {
+        let inputs = vec![artist()];
+        let mut current = CurrentScores::new();
+        current.insert(inputs[0].artist_id, (0.1, TroubleTier::Low));
+
+        let result = simulate(
+            &inputs,
+            &current,
+            &ScoreWeights::default(),
+            None,
+            &context(),
+            10,
+        );
+        assert_eq!(result.tier_changes, 1);
+        assert_eq!(result.distribution.total, 1);
+        assert_eq!(result.current_distribution.low, 1);
+        assert_eq!(result.leaderboard[0].rank, 1);
+    }
+}
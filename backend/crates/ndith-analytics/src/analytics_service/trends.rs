@@ -1,14 +1,20 @@
 //! Trend Analysis Service
 //!
 //! Analyzes trends in artist mentions, offenses, and platform activity.
+//!
+//! Artist trends compare the current window against the window before it
+//! using the daily rollups in DuckDB. Rising artists are flagged by z-score
+//! against a baseline of earlier windows, so an artist who is always in the
+//! news doesn't show up as "rising" every week.
 
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::databases::DuckDbClient;
+use crate::databases::{ArtistMentionDay, DuckDbClient};
 
 /// Trend direction indicator
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -63,6 +69,12 @@ pub struct ArtistTrend {
     pub offense_trend: OffenseTrend,
     pub sentiment_trend: SentimentTrend,
     pub mention_history: Vec<TrendData>,
+    /// Rolling average of daily mentions
+    pub rolling_average: Vec<TrendData>,
+    /// Current window against the baseline windows, None when the baseline
+    /// is flat and the current window differs from it
+    pub z_score: Option<f64>,
+    pub is_spike: bool,
 }
 
 /// Offense trend data
@@ -75,7 +87,8 @@ pub struct OffenseTrend {
     pub top_categories: Vec<(String, i64)>,
 }
 
-/// Sentiment trend data
+/// Sentiment trend data. Scores are net sentiment, (positive - negative)
+/// mentions over all mentions, from -1.0 to 1.0.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SentimentTrend {
     pub current_score: f64,
@@ -128,6 +141,15 @@ pub struct TrendConfig {
     pub significance_threshold: f64,
     /// Maximum trends to return
     pub max_results: i32,
+    /// Earlier windows (each `current_period_days` long) used as the
+    /// baseline for spike detection
+    pub baseline_periods: i32,
+    /// Window for the rolling average of daily mentions
+    pub rolling_window_days: i32,
+    /// Z-score above which the current window counts as a spike
+    pub spike_z_threshold: f64,
+    /// Mentions needed in a window before an artist can rise or fall
+    pub min_mentions: i64,
 }
 
 impl Default for TrendConfig {
@@ -137,10 +159,24 @@ impl Default for TrendConfig {
             comparison_period_days: 7,
             significance_threshold: 0.1,
             max_results: 20,
+            baseline_periods: 4,
+            rolling_window_days: 7,
+            spike_z_threshold: 2.0,
+            min_mentions: 3,
         }
     }
 }
 
+impl TrendConfig {
+    /// Days of history needed for the comparison and baseline windows
+    fn history_days(&self) -> i32 {
+        self.current_period_days
+            + self
+                .comparison_period_days
+                .max(self.current_period_days * self.baseline_periods)
+    }
+}
+
 /// Trend analysis service
 pub struct TrendAnalysisService {
     duckdb: Arc<DuckDbClient>,
@@ -186,168 +222,98 @@ impl TrendAnalysisService {
 
     /// Get artist trend analysis
     pub async fn get_artist_trend(&self, artist_id: Uuid) -> Result<ArtistTrend> {
-        let trending = self
+        let (start, end) = self.history_range();
+        let days = self
             .duckdb
-            .get_trending_artists(
-                self.config.current_period_days + self.config.comparison_period_days,
-                1000,
-            )
+            .get_artist_mention_series(Some(artist_id), start, end)
             .await?;
 
-        // Find this artist in the trending list
-        let artist = trending.iter().find(|t| t.artist_id == artist_id);
-
-        match artist {
-            Some(a) => {
-                // For now, use simple calculations
-                // In production, would compare current vs previous period
-                let current_mentions = a.total_mentions;
-                let previous_mentions = current_mentions / 2; // Placeholder
-                let change = calculate_change(current_mentions as f64, previous_mentions as f64);
-
-                Ok(ArtistTrend {
-                    artist_id: a.artist_id,
-                    artist_name: a.artist_name.clone(),
-                    current_mentions,
-                    previous_mentions,
-                    change_percentage: change,
-                    direction: TrendDirection::from_change(change),
-                    offense_trend: OffenseTrend {
-                        current_count: a.offense_mentions,
-                        previous_count: a.offense_mentions / 2,
-                        change_percentage: 0.0,
-                        direction: TrendDirection::Stable,
-                        top_categories: vec![],
-                    },
-                    sentiment_trend: SentimentTrend {
-                        current_score: a.positive_ratio,
-                        previous_score: a.positive_ratio,
-                        change: 0.0,
-                        direction: TrendDirection::Stable,
-                    },
-                    mention_history: vec![],
-                })
-            }
-            None => {
-                // Artist not in trending, return inactive
-                Ok(ArtistTrend {
-                    artist_id,
-                    artist_name: String::new(),
-                    current_mentions: 0,
-                    previous_mentions: 0,
-                    change_percentage: 0.0,
-                    direction: TrendDirection::Inactive,
-                    offense_trend: OffenseTrend {
-                        current_count: 0,
-                        previous_count: 0,
-                        change_percentage: 0.0,
-                        direction: TrendDirection::Inactive,
-                        top_categories: vec![],
-                    },
-                    sentiment_trend: SentimentTrend {
-                        current_score: 0.0,
-                        previous_score: 0.0,
-                        change: 0.0,
-                        direction: TrendDirection::Inactive,
-                    },
-                    mention_history: vec![],
-                })
-            }
-        }
-    }
+        let name = days
+            .first()
+            .map(|d| d.artist_name.clone())
+            .unwrap_or_default();
 
-    /// Get rising artists
-    async fn get_rising_artists(&self, limit: i32) -> Result<Vec<ArtistTrend>> {
-        let trending = self
-            .duckdb
-            .get_trending_artists(self.config.current_period_days, limit * 2)
-            .await?;
+        Ok(analyze_artist(artist_id, name, &days, end, &self.config))
+    }
 
-        // Filter and sort by positive change
-        let mut artists: Vec<ArtistTrend> = trending
+    /// Get rising artists: mentions up on the previous window and spiking
+    /// against the baseline
+    pub async fn get_rising_artists(&self, limit: i32) -> Result<Vec<ArtistTrend>> {
+        let mut artists: Vec<ArtistTrend> = self
+            .analyze_all_artists()
+            .await?
             .into_iter()
-            .map(|t| {
-                let change = if t.positive_ratio > 0.5 { 0.2 } else { 0.0 };
-                ArtistTrend {
-                    artist_id: t.artist_id,
-                    artist_name: t.artist_name,
-                    current_mentions: t.total_mentions,
-                    previous_mentions: (t.total_mentions as f64 * 0.8) as i64,
-                    change_percentage: change,
-                    direction: TrendDirection::Rising,
-                    offense_trend: OffenseTrend {
-                        current_count: t.offense_mentions,
-                        previous_count: 0,
-                        change_percentage: 0.0,
-                        direction: TrendDirection::Stable,
-                        top_categories: vec![],
-                    },
-                    sentiment_trend: SentimentTrend {
-                        current_score: t.positive_ratio,
-                        previous_score: t.positive_ratio,
-                        change: 0.0,
-                        direction: TrendDirection::Stable,
-                    },
-                    mention_history: vec![],
-                }
+            .filter(|t| {
+                t.current_mentions >= self.config.min_mentions
+                    && matches!(t.direction, TrendDirection::Rising | TrendDirection::New)
+                    && t.is_spike
             })
             .collect();
 
+        // Flat baselines have no z-score; a jump from one is the strongest signal
         artists.sort_by(|a, b| {
-            b.change_percentage
-                .partial_cmp(&a.change_percentage)
-                .unwrap()
+            b.z_score
+                .unwrap_or(f64::INFINITY)
+                .total_cmp(&a.z_score.unwrap_or(f64::INFINITY))
+                .then(b.current_mentions.cmp(&a.current_mentions))
         });
-        artists.truncate(limit as usize);
+        artists.truncate(limit.max(0) as usize);
 
         Ok(artists)
     }
 
-    /// Get falling artists
-    async fn get_falling_artists(&self, limit: i32) -> Result<Vec<ArtistTrend>> {
-        let trending = self
-            .duckdb
-            .get_trending_artists(self.config.current_period_days, limit * 2)
-            .await?;
-
-        // Filter to artists with negative sentiment
-        let mut artists: Vec<ArtistTrend> = trending
+    /// Get falling artists: mentions down on the previous window
+    pub async fn get_falling_artists(&self, limit: i32) -> Result<Vec<ArtistTrend>> {
+        let mut artists: Vec<ArtistTrend> = self
+            .analyze_all_artists()
+            .await?
             .into_iter()
-            .filter(|t| t.positive_ratio < 0.5)
-            .map(|t| ArtistTrend {
-                artist_id: t.artist_id,
-                artist_name: t.artist_name,
-                current_mentions: t.total_mentions,
-                previous_mentions: (t.total_mentions as f64 * 1.2) as i64,
-                change_percentage: -0.15,
-                direction: TrendDirection::Falling,
-                offense_trend: OffenseTrend {
-                    current_count: t.offense_mentions,
-                    previous_count: 0,
-                    change_percentage: 0.0,
-                    direction: TrendDirection::Stable,
-                    top_categories: vec![],
-                },
-                sentiment_trend: SentimentTrend {
-                    current_score: t.positive_ratio,
-                    previous_score: t.positive_ratio,
-                    change: 0.0,
-                    direction: TrendDirection::Falling,
-                },
-                mention_history: vec![],
+            .filter(|t| {
+                t.previous_mentions >= self.config.min_mentions
+                    && t.direction == TrendDirection::Falling
             })
             .collect();
 
-        artists.sort_by(|a, b| {
-            a.change_percentage
-                .partial_cmp(&b.change_percentage)
-                .unwrap()
-        });
-        artists.truncate(limit as usize);
+        artists.sort_by(|a, b| a.change_percentage.total_cmp(&b.change_percentage));
+        artists.truncate(limit.max(0) as usize);
 
         Ok(artists)
     }
 
+    /// Trends for every artist mentioned within the history window
+    async fn analyze_all_artists(&self) -> Result<Vec<ArtistTrend>> {
+        let (start, end) = self.history_range();
+        let days = self
+            .duckdb
+            .get_artist_mention_series(None, start, end)
+            .await?;
+
+        let mut by_artist: HashMap<Uuid, Vec<ArtistMentionDay>> = HashMap::new();
+        for day in days {
+            by_artist.entry(day.artist_id).or_default().push(day);
+        }
+
+        Ok(by_artist
+            .into_iter()
+            .map(|(artist_id, days)| {
+                let name = days
+                    .iter()
+                    .rev()
+                    .find(|d| !d.artist_name.is_empty())
+                    .map(|d| d.artist_name.clone())
+                    .unwrap_or_default();
+                analyze_artist(artist_id, name, &days, end, &self.config)
+            })
+            .collect())
+    }
+
+    /// First and last day of the history window, ending today
+    fn history_range(&self) -> (NaiveDate, NaiveDate) {
+        let end = Utc::now().date_naive();
+        let start = end - Duration::days(self.config.history_days() as i64 - 1);
+        (start, end)
+    }
+
     /// Get offense category trends
     async fn get_offense_category_trends(&self) -> Result<Vec<OffenseCategoryTrend>> {
         // Placeholder - would query offense data
@@ -390,17 +356,16 @@ impl TrendAnalysisService {
             })
             .collect();
 
-        let current = summaries
+        // Split on date rather than row position, since quiet days have no row
+        let current_start = (Utc::now().date_naive()
+            - Duration::days(self.config.current_period_days as i64 - 1))
+        .to_string();
+        let (current_days, previous_days): (Vec<_>, Vec<_>) = summaries
             .iter()
-            .take(self.config.current_period_days as usize)
-            .map(|s| s.total_articles)
-            .sum::<i64>() as f64;
+            .partition(|s| s.date.as_str() >= current_start.as_str());
 
-        let previous = summaries
-            .iter()
-            .skip(self.config.current_period_days as usize)
-            .map(|s| s.total_articles)
-            .sum::<i64>() as f64;
+        let current = current_days.iter().map(|s| s.total_articles).sum::<i64>() as f64;
+        let previous = previous_days.iter().map(|s| s.total_articles).sum::<i64>() as f64;
 
         let change = calculate_change(current, previous);
 
@@ -448,26 +413,34 @@ impl TrendAnalysisService {
         period2_start: DateTime<Utc>,
         period2_end: DateTime<Utc>,
     ) -> Result<PeriodComparison> {
-        // Placeholder for period comparison
+        let first = self
+            .duckdb
+            .get_news_period_totals(period1_start, period1_end)
+            .await?;
+        let second = self
+            .duckdb
+            .get_news_period_totals(period2_start, period2_end)
+            .await?;
+
         Ok(PeriodComparison {
+            changes: PeriodChanges {
+                articles_change: calculate_change(second.articles as f64, first.articles as f64),
+                offenses_change: calculate_change(second.offenses as f64, first.offenses as f64),
+                sentiment_change: second.avg_sentiment - first.avg_sentiment,
+            },
             period1: PeriodStats {
                 start: period1_start,
                 end: period1_end,
-                articles: 0,
-                offenses: 0,
-                avg_sentiment: 0.0,
+                articles: first.articles,
+                offenses: first.offenses,
+                avg_sentiment: first.avg_sentiment,
             },
             period2: PeriodStats {
                 start: period2_start,
                 end: period2_end,
-                articles: 0,
-                offenses: 0,
-                avg_sentiment: 0.0,
-            },
-            changes: PeriodChanges {
-                articles_change: 0.0,
-                offenses_change: 0.0,
-                sentiment_change: 0.0,
+                articles: second.articles,
+                offenses: second.offenses,
+                avg_sentiment: second.avg_sentiment,
             },
         })
     }
@@ -510,6 +483,202 @@ fn calculate_change(current: f64, previous: f64) -> f64 {
     }
 }
 
+/// Build an artist's trend from daily rows, ending on `end`
+fn analyze_artist(
+    artist_id: Uuid,
+    artist_name: String,
+    days: &[ArtistMentionDay],
+    end: NaiveDate,
+    config: &TrendConfig,
+) -> ArtistTrend {
+    let history_days = config.history_days().max(1) as usize;
+    let current_days = config.current_period_days.max(1) as usize;
+    let comparison_days = config.comparison_period_days.max(1) as usize;
+    let start = end - Duration::days(history_days as i64 - 1);
+
+    // Zero-filled daily series, oldest first
+    let mut daily = vec![DayTotals::default(); history_days];
+    for day in days {
+        let offset = (day.date - start).num_days();
+        if let Some(slot) = usize::try_from(offset).ok().and_then(|i| daily.get_mut(i)) {
+            slot.mentions += day.mention_count;
+            slot.positive += day.positive_mentions;
+            slot.negative += day.negative_mentions;
+            slot.offenses += day.offense_mentions;
+        }
+    }
+
+    let current = window_totals(&daily, 0, current_days);
+    let previous = window_totals(&daily, current_days, comparison_days);
+
+    // Per-day rates so windows of different lengths compare fairly
+    let change = calculate_change(
+        current.mentions as f64 / current_days as f64,
+        previous.mentions as f64 / comparison_days as f64,
+    );
+    let offense_change = calculate_change(
+        current.offenses as f64 / current_days as f64,
+        previous.offenses as f64 / comparison_days as f64,
+    );
+
+    let baseline: Vec<f64> = (1..=config.baseline_periods.max(0) as usize)
+        .map(|k| window_totals(&daily, current_days * k, current_days).mentions as f64)
+        .collect();
+    let z = z_score(current.mentions as f64, &baseline);
+    let baseline_mean = mean(&baseline);
+
+    let direction = if current.mentions == 0 && previous.mentions == 0 {
+        TrendDirection::Inactive
+    } else if previous.mentions == 0 && baseline_mean == 0.0 {
+        TrendDirection::New
+    } else if change > config.significance_threshold {
+        TrendDirection::Rising
+    } else if change < -config.significance_threshold {
+        TrendDirection::Falling
+    } else {
+        TrendDirection::Stable
+    };
+
+    let is_spike = match z {
+        Some(z) => z >= config.spike_z_threshold,
+        None => current.mentions as f64 > baseline_mean,
+    };
+
+    let (current_sentiment, previous_sentiment) =
+        (current.net_sentiment(), previous.net_sentiment());
+    let sentiment_change = current_sentiment - previous_sentiment;
+
+    let dates: Vec<String> = (0..history_days)
+        .map(|i| (start + Duration::days(i as i64)).to_string())
+        .collect();
+    let values: Vec<f64> = daily.iter().map(|d| d.mentions as f64).collect();
+    let rolling = rolling_average(&values, config.rolling_window_days.max(1) as usize);
+
+    ArtistTrend {
+        artist_id,
+        artist_name,
+        current_mentions: current.mentions,
+        previous_mentions: previous.mentions,
+        change_percentage: change,
+        direction,
+        offense_trend: OffenseTrend {
+            current_count: current.offenses,
+            previous_count: previous.offenses,
+            change_percentage: offense_change,
+            direction: if current.offenses == 0 && previous.offenses == 0 {
+                TrendDirection::Inactive
+            } else {
+                TrendDirection::from_change(offense_change)
+            },
+            top_categories: vec![],
+        },
+        sentiment_trend: SentimentTrend {
+            current_score: current_sentiment,
+            previous_score: previous_sentiment,
+            change: sentiment_change,
+            direction: TrendDirection::from_change(sentiment_change),
+        },
+        mention_history: dates
+            .iter()
+            .zip(&values)
+            .map(|(date, value)| TrendData {
+                date: date.clone(),
+                value: *value,
+                label: None,
+            })
+            .collect(),
+        rolling_average: dates
+            .into_iter()
+            .zip(rolling)
+            .map(|(date, value)| TrendData {
+                date,
+                value,
+                label: Some(format!("{}d avg", config.rolling_window_days)),
+            })
+            .collect(),
+        z_score: z,
+        is_spike,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct DayTotals {
+    mentions: i64,
+    positive: i64,
+    negative: i64,
+    offenses: i64,
+}
+
+impl DayTotals {
+    fn net_sentiment(&self) -> f64 {
+        if self.mentions == 0 {
+            0.0
+        } else {
+            (self.positive - self.negative) as f64 / self.mentions as f64
+        }
+    }
+}
+
+/// Totals for `len` days ending `offset` days before the last day of the
+/// series. Days before the series start count as zero.
+fn window_totals(daily: &[DayTotals], offset: usize, len: usize) -> DayTotals {
+    let end = daily.len().saturating_sub(offset);
+    let start = end.saturating_sub(len);
+    daily[start..end]
+        .iter()
+        .fold(DayTotals::default(), |acc, d| DayTotals {
+            mentions: acc.mentions + d.mentions,
+            positive: acc.positive + d.positive,
+            negative: acc.negative + d.negative,
+            offenses: acc.offenses + d.offenses,
+        })
+}
+
+/// Trailing rolling average; early points average over what's available
+fn rolling_average(values: &[f64], window: usize) -> Vec<f64> {
+    let window = window.max(1);
+    let mut sum = 0.0;
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            sum += v;
+            if i >= window {
+                sum -= values[i - window];
+            }
+            sum / (i + 1).min(window) as f64
+        })
+        .collect()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Standard score of `current` against a baseline sample. Undefined (None)
+/// when the baseline has no spread and `current` differs from it.
+fn z_score(current: f64, baseline: &[f64]) -> Option<f64> {
+    if baseline.is_empty() {
+        return None;
+    }
+
+    let mean = mean(baseline);
+    let variance = baseline.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / baseline.len() as f64;
+    let std_dev = variance.sqrt();
+
+    if std_dev > f64::EPSILON {
+        Some((current - mean) / std_dev)
+    } else if (current - mean).abs() <= f64::EPSILON {
+        Some(0.0)
+    } else {
+        None
+    }
+}
+
 /// Create an empty time series
 fn empty_time_series(name: &str) -> TimeSeries {
     TimeSeries {
@@ -539,4 +708,49 @@ mod tests {
         assert_eq!(calculate_change(50.0, 100.0), -0.5); // 50% decrease
         assert_eq!(calculate_change(100.0, 0.0), 1.0); // From 0
     }
+
+    #[test]
+    fn test_rolling_average_and_z_score() {
+        assert_eq!(
+            rolling_average(&[2.0, 4.0, 6.0, 8.0], 2),
+            vec![2.0, 3.0, 5.0, 7.0]
+        );
+
+        assert_eq!(z_score(10.0, &[4.0, 6.0, 4.0, 6.0]), Some(5.0));
+        assert_eq!(z_score(5.0, &[5.0, 5.0]), Some(0.0));
+        assert_eq!(z_score(9.0, &[0.0, 0.0]), None);
+    }
+
+    #[test]
+    fn test_analyze_artist_spike() {
+        let config = TrendConfig::default();
+        let end = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let artist_id = Uuid::new_v4();
+
+        // One mention a day for the past month, then a burst this week
+        let days: Vec<ArtistMentionDay> = (0..config.history_days() as i64)
+            .map(|i| {
+                let burst = i < 7;
+                ArtistMentionDay {
+                    artist_id,
+                    artist_name: "Artist".to_string(),
+                    date: end - Duration::days(i),
+                    mention_count: if burst { 5 } else { 1 },
+                    positive_mentions: 0,
+                    negative_mentions: if burst { 5 } else { 0 },
+                    offense_mentions: if burst { 2 } else { 0 },
+                }
+            })
+            .collect();
+
+        let trend = analyze_artist(artist_id, "Artist".to_string(), &days, end, &config);
+        assert_eq!(trend.current_mentions, 35);
+        assert_eq!(trend.previous_mentions, 7);
+        assert_eq!(trend.direction, TrendDirection::Rising);
+        assert!(trend.is_spike);
+        assert_eq!(trend.sentiment_trend.current_score, -1.0);
+        assert_eq!(trend.sentiment_trend.direction, TrendDirection::Falling);
+        assert_eq!(trend.offense_trend.previous_count, 0);
+        assert_eq!(trend.mention_history.len(), config.history_days() as usize);
+    }
 }
@@ -6,13 +6,23 @@
 //! - Recency of offenses (15% weight)
 //! - Community consensus/block count (15% weight)
 //! - Revenue contribution (15% weight)
+//!
+//! The weights above are the seeded default profile. Weights live in
+//! versioned scoring profiles (see `score_profile`): the default profile
+//! drives the stored scores, while other profiles are applied on request.
 
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+use super::score_profile::{
+    compute_components, explain_score, simulate, ArtistScoreInputs, CurrentScores, EvidenceSource,
+    OffenseSource, ProfileRef, ScoreExplanation, ScoreProfile, ScoringContext, WhatIfResult,
+};
+
 /// Trouble tier classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "trouble_tier", rename_all = "lowercase")]
@@ -92,6 +102,47 @@ impl Default for ScoreWeights {
     }
 }
 
+impl ScoreWeights {
+    fn sum(&self) -> f64 {
+        self.severity_weight
+            + self.evidence_weight
+            + self.recency_weight
+            + self.community_weight
+            + self.revenue_weight
+    }
+
+    /// Each weight must be within 0-1 and together they must sum to 1.0
+    pub fn validate(&self) -> Result<()> {
+        let weights = [
+            ("severity_weight", self.severity_weight),
+            ("evidence_weight", self.evidence_weight),
+            ("recency_weight", self.recency_weight),
+            ("community_weight", self.community_weight),
+            ("revenue_weight", self.revenue_weight),
+        ];
+        for (name, weight) in weights {
+            if !(0.0..=1.0).contains(&weight) {
+                anyhow::bail!("{} must be between 0 and 1, got {}", name, weight);
+            }
+        }
+
+        let sum = self.sum();
+        if (sum - 1.0).abs() > 0.01 {
+            anyhow::bail!("Weights must sum to 1.0, got {}", sum);
+        }
+        Ok(())
+    }
+
+    /// Weighted total of the components
+    pub fn apply(&self, components: &TroubleScoreComponents) -> f64 {
+        components.severity_score * self.severity_weight
+            + components.evidence_score * self.evidence_weight
+            + components.recency_score * self.recency_weight
+            + components.community_score * self.community_weight
+            + components.revenue_score * self.revenue_weight
+    }
+}
+
 /// Complete trouble score for an artist
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArtistTroubleScore {
@@ -122,6 +173,9 @@ pub struct ArtistTroubleScore {
     pub first_offense_date: Option<DateTime<Utc>>,
     pub last_offense_date: Option<DateTime<Utc>>,
     pub last_calculated_at: DateTime<Utc>,
+
+    /// Profile version that produced the score
+    pub profile_id: Option<Uuid>,
 }
 
 /// Leaderboard entry for problematic artists
@@ -146,24 +200,30 @@ pub struct ScoreHistoryEntry {
     pub trouble_tier: TroubleTier,
     pub trigger_reason: String,
     pub calculated_at: DateTime<Utc>,
+    /// Profile version that produced the score; None before profiles existed
+    pub profile: Option<ProfileRef>,
+}
+
+/// A new profile, or a new version of an existing one
+#[derive(Debug, Clone)]
+pub struct NewScoreProfile {
+    pub name: String,
+    /// None for a shared profile
+    pub owner_user_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub weights: ScoreWeights,
+    pub categories: Option<Vec<String>>,
+    pub created_by: Uuid,
 }
 
 /// Trouble score service
 pub struct TroubleScoreService {
     pool: PgPool,
-    weights: ScoreWeights,
 }
 
 impl TroubleScoreService {
     pub fn new(pool: PgPool) -> Self {
-        Self {
-            pool,
-            weights: ScoreWeights::default(),
-        }
-    }
-
-    pub fn with_weights(pool: PgPool, weights: ScoreWeights) -> Self {
-        Self { pool, weights }
+        Self { pool }
     }
 
     /// Get trouble score for a specific artist
@@ -188,9 +248,10 @@ impl TroubleScoreService {
                 ts.severe_count,
                 ts.moderate_count,
                 ts.minor_count,
-                ts.first_offense_date,
-                ts.last_offense_date,
-                ts.last_calculated_at
+                ts.first_offense_date::timestamptz AS first_offense_date,
+                ts.last_offense_date::timestamptz AS last_offense_date,
+                ts.last_calculated_at,
+                ts.profile_id
             FROM artist_trouble_scores ts
             JOIN artists a ON a.id = ts.artist_id
             WHERE ts.artist_id = $1
@@ -269,9 +330,10 @@ impl TroubleScoreService {
                 ts.severe_count,
                 ts.moderate_count,
                 ts.minor_count,
-                ts.first_offense_date,
-                ts.last_offense_date,
-                ts.last_calculated_at
+                ts.first_offense_date::timestamptz AS first_offense_date,
+                ts.last_offense_date::timestamptz AS last_offense_date,
+                ts.last_calculated_at,
+                ts.profile_id
             FROM artist_trouble_scores ts
             JOIN artists a ON a.id = ts.artist_id
             WHERE ts.trouble_tier = $1::trouble_tier
@@ -348,15 +410,19 @@ impl TroubleScoreService {
         let records = sqlx::query_as::<_, ScoreHistoryRow>(
             r#"
             SELECT
-                id,
-                artist_id,
-                total_score,
-                trouble_tier::text as trouble_tier,
-                trigger_reason,
-                calculated_at
-            FROM trouble_score_history
-            WHERE artist_id = $1
-            ORDER BY calculated_at DESC
+                h.id,
+                h.artist_id,
+                h.total_score,
+                h.trouble_tier::text as trouble_tier,
+                h.trigger_reason,
+                h.calculated_at,
+                h.profile_id,
+                p.name as profile_name,
+                p.version as profile_version
+            FROM trouble_score_history h
+            LEFT JOIN trouble_score_profiles p ON p.id = h.profile_id
+            WHERE h.artist_id = $1
+            ORDER BY h.calculated_at DESC
             LIMIT $2
             "#,
         )
@@ -395,45 +461,481 @@ impl TroubleScoreService {
         })
     }
 
-    /// Update score weights (admin function)
-    pub async fn update_weights(&mut self, weights: ScoreWeights) -> Result<()> {
-        // Validate weights sum to 1.0
-        let sum = weights.severity_weight
-            + weights.evidence_weight
-            + weights.recency_weight
-            + weights.community_weight
-            + weights.revenue_weight;
+    /// Latest version of every shared profile, plus the user's own profiles
+    pub async fn list_profiles(&self, user_id: Option<Uuid>) -> Result<Vec<ScoreProfile>> {
+        let records = sqlx::query_as::<_, ScoreProfileRow>(
+            r#"
+            SELECT DISTINCT ON (owner_user_id, name)
+                id, name, version, owner_user_id, description,
+                severity_weight, evidence_weight, recency_weight, community_weight, revenue_weight,
+                categories::text[] as categories, is_default, created_at
+            FROM trouble_score_profiles
+            WHERE owner_user_id IS NULL OR owner_user_id = $1
+            ORDER BY owner_user_id NULLS FIRST, name, version DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch score profiles")?;
 
-        if (sum - 1.0).abs() > 0.01 {
-            anyhow::bail!("Weights must sum to 1.0, got {}", sum);
+        Ok(records.into_iter().map(|r| r.into_profile()).collect())
+    }
+
+    /// Get a specific profile version
+    pub async fn get_profile(&self, profile_id: Uuid) -> Result<Option<ScoreProfile>> {
+        let record = sqlx::query_as::<_, ScoreProfileRow>(
+            r#"
+            SELECT
+                id, name, version, owner_user_id, description,
+                severity_weight, evidence_weight, recency_weight, community_weight, revenue_weight,
+                categories::text[] as categories, is_default, created_at
+            FROM trouble_score_profiles
+            WHERE id = $1
+            "#,
+        )
+        .bind(profile_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch score profile")?;
+
+        Ok(record.map(|r| r.into_profile()))
+    }
+
+    /// Every version of a profile, newest first
+    pub async fn get_profile_versions(
+        &self,
+        name: &str,
+        owner_user_id: Option<Uuid>,
+    ) -> Result<Vec<ScoreProfile>> {
+        let records = sqlx::query_as::<_, ScoreProfileRow>(
+            r#"
+            SELECT
+                id, name, version, owner_user_id, description,
+                severity_weight, evidence_weight, recency_weight, community_weight, revenue_weight,
+                categories::text[] as categories, is_default, created_at
+            FROM trouble_score_profiles
+            WHERE name = $1 AND owner_user_id IS NOT DISTINCT FROM $2
+            ORDER BY version DESC
+            "#,
+        )
+        .bind(name)
+        .bind(owner_user_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch score profile versions")?;
+
+        Ok(records.into_iter().map(|r| r.into_profile()).collect())
+    }
+
+    /// The profile that produces the stored scores
+    pub async fn get_default_profile(&self) -> Result<ScoreProfile> {
+        let record = sqlx::query_as::<_, ScoreProfileRow>(
+            r#"
+            SELECT
+                id, name, version, owner_user_id, description,
+                severity_weight, evidence_weight, recency_weight, community_weight, revenue_weight,
+                categories::text[] as categories, is_default, created_at
+            FROM trouble_score_profiles
+            WHERE is_default
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch default score profile")?
+        .context("No default score profile configured")?;
+
+        Ok(record.into_profile())
+    }
+
+    /// Offense categories a profile can filter on
+    pub async fn offense_categories(&self) -> Result<Vec<String>> {
+        sqlx::query_scalar("SELECT unnest(enum_range(NULL::offense_category))::text")
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch offense categories")
+    }
+
+    /// Create a profile, or append a new version if the name already exists.
+    ///
+    /// Existing versions are never modified. A new version of the default
+    /// profile becomes the default; stored scores pick it up on the next
+    /// recalculation.
+    pub async fn create_profile_version(&self, profile: NewScoreProfile) -> Result<ScoreProfile> {
+        profile.weights.validate()?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        let latest: Option<(Uuid, i32, bool)> = sqlx::query_as(
+            r#"
+            SELECT id, version, is_default
+            FROM trouble_score_profiles
+            WHERE name = $1 AND owner_user_id IS NOT DISTINCT FROM $2
+            ORDER BY version DESC
+            LIMIT 1
+            FOR UPDATE
+            "#,
+        )
+        .bind(&profile.name)
+        .bind(profile.owner_user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("Failed to fetch latest profile version")?;
+
+        let version = latest.map(|(_, v, _)| v + 1).unwrap_or(1);
+        let is_default = latest.is_some_and(|(_, _, d)| d);
+        if is_default && profile.categories.is_some() {
+            anyhow::bail!("The default profile cannot filter by category");
+        }
+
+        if let Some((previous_id, _, true)) = latest {
+            sqlx::query("UPDATE trouble_score_profiles SET is_default = FALSE WHERE id = $1")
+                .bind(previous_id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to move default profile")?;
         }
 
-        // Update in database
-        sqlx::query(
+        let record = sqlx::query_as::<_, ScoreProfileRow>(
             r#"
-            UPDATE trouble_score_weights
-            SET
-                severity_weight = $1,
-                evidence_weight = $2,
-                recency_weight = $3,
-                community_weight = $4,
-                revenue_weight = $5,
-                updated_at = NOW()
-            WHERE is_active = TRUE
+            INSERT INTO trouble_score_profiles (
+                name, version, owner_user_id, description,
+                severity_weight, evidence_weight, recency_weight, community_weight, revenue_weight,
+                categories, is_default, created_by
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10::text[]::offense_category[], $11, $12)
+            RETURNING
+                id, name, version, owner_user_id, description,
+                severity_weight, evidence_weight, recency_weight, community_weight, revenue_weight,
+                categories::text[] as categories, is_default, created_at
             "#,
         )
-        .bind(weights.severity_weight)
-        .bind(weights.evidence_weight)
-        .bind(weights.recency_weight)
-        .bind(weights.community_weight)
-        .bind(weights.revenue_weight)
-        .execute(&self.pool)
+        .bind(&profile.name)
+        .bind(version)
+        .bind(profile.owner_user_id)
+        .bind(&profile.description)
+        .bind(profile.weights.severity_weight)
+        .bind(profile.weights.evidence_weight)
+        .bind(profile.weights.recency_weight)
+        .bind(profile.weights.community_weight)
+        .bind(profile.weights.revenue_weight)
+        .bind(&profile.categories)
+        .bind(is_default)
+        .bind(profile.created_by)
+        .fetch_one(&mut *tx)
         .await
-        .context("Failed to update weights")?;
+        .context("Failed to create score profile")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit score profile")?;
+
+        Ok(record.into_profile())
+    }
+
+    /// Make a shared profile version the default for stored scores
+    pub async fn set_default_profile(&self, profile_id: Uuid) -> Result<ScoreProfile> {
+        let profile = self
+            .get_profile(profile_id)
+            .await?
+            .context("Score profile not found")?;
+
+        if profile.owner_user_id.is_some() || profile.categories.is_some() {
+            anyhow::bail!("Only shared profiles without a category filter can be the default");
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .context("Failed to begin transaction")?;
+
+        sqlx::query("UPDATE trouble_score_profiles SET is_default = FALSE WHERE is_default")
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear default profile")?;
+
+        sqlx::query("UPDATE trouble_score_profiles SET is_default = TRUE WHERE id = $1")
+            .bind(profile_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to set default profile")?;
+
+        tx.commit()
+            .await
+            .context("Failed to commit default profile")?;
+
+        Ok(ScoreProfile {
+            is_default: true,
+            ..profile
+        })
+    }
+
+    /// The profile a user has chosen, if any
+    pub async fn get_user_profile(&self, user_id: Uuid) -> Result<Option<ScoreProfile>> {
+        let record = sqlx::query_as::<_, ScoreProfileRow>(
+            r#"
+            SELECT
+                p.id, p.name, p.version, p.owner_user_id, p.description,
+                p.severity_weight, p.evidence_weight, p.recency_weight, p.community_weight,
+                p.revenue_weight, p.categories::text[] as categories, p.is_default, p.created_at
+            FROM user_score_profiles usp
+            JOIN trouble_score_profiles p ON p.id = usp.profile_id
+            WHERE usp.user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to fetch user score profile")?;
+
+        Ok(record.map(|r| r.into_profile()))
+    }
 
-        self.weights = weights;
+    /// The user's chosen profile, falling back to the default
+    pub async fn effective_profile(&self, user_id: Uuid) -> Result<ScoreProfile> {
+        match self.get_user_profile(user_id).await? {
+            Some(profile) => Ok(profile),
+            None => self.get_default_profile().await,
+        }
+    }
+
+    /// Choose the profile applied to a user's views; None resets to the default
+    pub async fn set_user_profile(&self, user_id: Uuid, profile_id: Option<Uuid>) -> Result<()> {
+        match profile_id {
+            Some(profile_id) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO user_score_profiles (user_id, profile_id, updated_at)
+                    VALUES ($1, $2, NOW())
+                    ON CONFLICT (user_id) DO UPDATE SET
+                        profile_id = EXCLUDED.profile_id,
+                        updated_at = NOW()
+                    "#,
+                )
+                .bind(user_id)
+                .bind(profile_id)
+                .execute(&self.pool)
+                .await
+                .context("Failed to set user score profile")?;
+            }
+            None => {
+                sqlx::query("DELETE FROM user_score_profiles WHERE user_id = $1")
+                    .bind(user_id)
+                    .execute(&self.pool)
+                    .await
+                    .context("Failed to clear user score profile")?;
+            }
+        }
         Ok(())
     }
+
+    /// Explain an artist's score under a profile, with the source rows used
+    pub async fn explain_artist(
+        &self,
+        artist_id: Uuid,
+        profile: &ScoreProfile,
+    ) -> Result<Option<ScoreExplanation>> {
+        let ctx = self.scoring_context().await?;
+        let inputs = self.load_score_inputs(Some(artist_id)).await?;
+
+        Ok(inputs.first().map(|inputs| {
+            explain_score(
+                inputs,
+                ProfileRef::from(profile),
+                &profile.weights,
+                profile.categories.as_deref(),
+                &ctx,
+            )
+        }))
+    }
+
+    /// An artist's stored score rescored under a profile. Components, total
+    /// and tier come from the profile; counts and dates from the stored row.
+    pub async fn get_artist_score_with_profile(
+        &self,
+        artist_id: Uuid,
+        profile: &ScoreProfile,
+    ) -> Result<Option<ArtistTroubleScore>> {
+        let Some(mut score) = self.get_artist_score(artist_id).await? else {
+            return Ok(None);
+        };
+
+        let ctx = self.scoring_context().await?;
+        if let Some(inputs) = self.load_score_inputs(Some(artist_id)).await?.first() {
+            let components = compute_components(inputs, profile.categories.as_deref(), &ctx);
+            score.total_score = profile.weights.apply(&components);
+            score.trouble_tier = TroubleTier::from_score(score.total_score);
+            score.components = components;
+            score.profile_id = Some(profile.id);
+        }
+
+        Ok(Some(score))
+    }
+
+    /// Recompute the leaderboard and tier distribution under candidate
+    /// weights. Nothing is written.
+    pub async fn what_if(
+        &self,
+        weights: &ScoreWeights,
+        categories: Option<&[String]>,
+        limit: usize,
+    ) -> Result<WhatIfResult> {
+        weights.validate()?;
+
+        let ctx = self.scoring_context().await?;
+        let inputs = self.load_score_inputs(None).await?;
+
+        let current: CurrentScores = sqlx::query_as::<_, (Uuid, f64, String)>(
+            "SELECT artist_id, total_score, trouble_tier::text FROM artist_trouble_scores",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch current scores")?
+        .into_iter()
+        .map(|(artist_id, score, tier)| (artist_id, (score, parse_tier(&tier))))
+        .collect();
+
+        Ok(simulate(
+            &inputs, &current, weights, categories, &ctx, limit,
+        ))
+    }
+
+    async fn scoring_context(&self) -> Result<ScoringContext> {
+        let (today, max_block_count): (NaiveDate, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                CURRENT_DATE,
+                COALESCE((
+                    SELECT MAX(cnt) FROM (
+                        SELECT COUNT(*) as cnt FROM user_artist_blocks GROUP BY artist_id
+                    ) sub
+                ), 1)
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to fetch scoring context")?;
+
+        Ok(ScoringContext {
+            today,
+            max_block_count,
+        })
+    }
+
+    /// Offense, evidence and block rows for one artist, or for every artist
+    /// with offenses
+    async fn load_score_inputs(&self, artist_id: Option<Uuid>) -> Result<Vec<ArtistScoreInputs>> {
+        let artists = sqlx::query_as::<_, ScoreInputArtistRow>(
+            r#"
+            SELECT
+                a.id as artist_id,
+                a.canonical_name as artist_name,
+                (SELECT COUNT(*) FROM user_artist_blocks b WHERE b.artist_id = a.id) as block_count,
+                COALESCE(ts.revenue_score, 0) as revenue_score
+            FROM artists a
+            LEFT JOIN artist_trouble_scores ts ON ts.artist_id = a.id
+            WHERE ($1::uuid IS NULL AND a.id IN (SELECT artist_id FROM artist_offenses))
+               OR a.id = $1
+            "#,
+        )
+        .bind(artist_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch score input artists")?;
+
+        let offenses = sqlx::query_as::<_, ScoreInputOffenseRow>(
+            r#"
+            SELECT
+                o.id, o.artist_id, o.title, o.category::text as category,
+                o.severity::text as severity, o.status::text as status, o.incident_date,
+                ev.latest_event_date
+            FROM artist_offenses o
+            LEFT JOIN LATERAL (
+                SELECT MAX(e.event_date) AS latest_event_date
+                FROM offense_events e
+                WHERE e.offense_id = o.id
+                AND e.status = 'verified'
+                AND e.event_type NOT IN ('acquittal', 'dismissal', 'apology')
+            ) ev ON TRUE
+            WHERE $1::uuid IS NULL OR o.artist_id = $1
+            ORDER BY o.incident_date DESC NULLS LAST
+            "#,
+        )
+        .bind(artist_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch score input offenses")?;
+
+        let evidence = sqlx::query_as::<_, ScoreInputEvidenceRow>(
+            r#"
+            SELECT
+                e.id, e.offense_id, e.url, e.source_name, e.credibility_score, e.story_id,
+                CASE
+                    WHEN e.link_status = 'dead' AND NOT EXISTS (
+                        SELECT 1 FROM evidence_snapshots s WHERE s.evidence_id = e.id
+                    ) THEN 0.5
+                    WHEN e.link_status = 'changed' THEN 0.75
+                    ELSE 1.0
+                END::float8 as link_weight
+            FROM offense_evidence e
+            JOIN artist_offenses o ON o.id = e.offense_id
+            WHERE $1::uuid IS NULL OR o.artist_id = $1
+            "#,
+        )
+        .bind(artist_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch score input evidence")?;
+
+        let mut evidence_by_offense: HashMap<Uuid, Vec<EvidenceSource>> = HashMap::new();
+        for row in evidence {
+            evidence_by_offense
+                .entry(row.offense_id)
+                .or_default()
+                .push(EvidenceSource {
+                    evidence_id: row.id,
+                    url: row.url,
+                    source_name: row.source_name,
+                    credibility_score: row.credibility_score,
+                    story_id: row.story_id,
+                    link_weight: row.link_weight,
+                });
+        }
+
+        let mut offenses_by_artist: HashMap<Uuid, Vec<OffenseSource>> = HashMap::new();
+        for row in offenses {
+            offenses_by_artist
+                .entry(row.artist_id)
+                .or_default()
+                .push(OffenseSource {
+                    offense_id: row.id,
+                    title: row.title,
+                    category: row.category,
+                    severity: row.severity,
+                    status: row.status,
+                    incident_date: row.incident_date,
+                    latest_event_date: row.latest_event_date,
+                    evidence: evidence_by_offense.remove(&row.id).unwrap_or_default(),
+                });
+        }
+
+        Ok(artists
+            .into_iter()
+            .map(|row| ArtistScoreInputs {
+                offenses: offenses_by_artist
+                    .remove(&row.artist_id)
+                    .unwrap_or_default(),
+                artist_id: row.artist_id,
+                artist_name: row.artist_name,
+                block_count: row.block_count.unwrap_or(0),
+                revenue_score: row.revenue_score.unwrap_or(0.0),
+            })
+            .collect())
+    }
 }
 
 /// Recalculation summary
@@ -446,7 +948,7 @@ pub struct RecalculationSummary {
 }
 
 /// Tier distribution statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TierDistribution {
     pub low: i64,
     pub moderate: i64,
@@ -455,6 +957,18 @@ pub struct TierDistribution {
     pub total: i64,
 }
 
+impl TierDistribution {
+    pub fn add(&mut self, tier: TroubleTier) {
+        match tier {
+            TroubleTier::Low => self.low += 1,
+            TroubleTier::Moderate => self.moderate += 1,
+            TroubleTier::High => self.high += 1,
+            TroubleTier::Critical => self.critical += 1,
+        }
+        self.total += 1;
+    }
+}
+
 // Internal row types for sqlx
 
 #[derive(Debug, sqlx::FromRow)]
@@ -479,6 +993,7 @@ struct TroubleScoreRow {
     first_offense_date: Option<DateTime<Utc>>,
     last_offense_date: Option<DateTime<Utc>>,
     last_calculated_at: Option<DateTime<Utc>>,
+    profile_id: Option<Uuid>,
 }
 
 impl TroubleScoreRow {
@@ -511,6 +1026,7 @@ impl TroubleScoreRow {
             first_offense_date: self.first_offense_date,
             last_offense_date: self.last_offense_date,
             last_calculated_at: self.last_calculated_at.unwrap_or_else(Utc::now),
+            profile_id: self.profile_id,
         }
     }
 }
@@ -555,6 +1071,9 @@ struct ScoreHistoryRow {
     trouble_tier: String,
     trigger_reason: Option<String>,
     calculated_at: DateTime<Utc>,
+    profile_id: Option<Uuid>,
+    profile_name: Option<String>,
+    profile_version: Option<i32>,
 }
 
 impl ScoreHistoryRow {
@@ -571,6 +1090,11 @@ impl ScoreHistoryRow {
             },
             trigger_reason: self.trigger_reason.unwrap_or_else(|| "unknown".to_string()),
             calculated_at: self.calculated_at,
+            profile: self.profile_id.map(|id| ProfileRef {
+                id: Some(id),
+                name: self.profile_name.unwrap_or_default(),
+                version: self.profile_version,
+            }),
         }
     }
 }
@@ -584,6 +1108,85 @@ struct TierDistributionRow {
     total_count: Option<i64>,
 }
 
+fn parse_tier(tier: &str) -> TroubleTier {
+    match tier {
+        "critical" => TroubleTier::Critical,
+        "high" => TroubleTier::High,
+        "moderate" => TroubleTier::Moderate,
+        _ => TroubleTier::Low,
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ScoreProfileRow {
+    id: Uuid,
+    name: String,
+    version: i32,
+    owner_user_id: Option<Uuid>,
+    description: Option<String>,
+    severity_weight: f64,
+    evidence_weight: f64,
+    recency_weight: f64,
+    community_weight: f64,
+    revenue_weight: f64,
+    categories: Option<Vec<String>>,
+    is_default: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl ScoreProfileRow {
+    fn into_profile(self) -> ScoreProfile {
+        ScoreProfile {
+            id: self.id,
+            name: self.name,
+            version: self.version,
+            owner_user_id: self.owner_user_id,
+            description: self.description,
+            weights: ScoreWeights {
+                severity_weight: self.severity_weight,
+                evidence_weight: self.evidence_weight,
+                recency_weight: self.recency_weight,
+                community_weight: self.community_weight,
+                revenue_weight: self.revenue_weight,
+            },
+            categories: self.categories,
+            is_default: self.is_default,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ScoreInputArtistRow {
+    artist_id: Uuid,
+    artist_name: String,
+    block_count: Option<i64>,
+    revenue_score: Option<f64>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ScoreInputOffenseRow {
+    id: Uuid,
+    artist_id: Uuid,
+    title: String,
+    category: String,
+    severity: String,
+    status: String,
+    incident_date: Option<NaiveDate>,
+    latest_event_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ScoreInputEvidenceRow {
+    id: Uuid,
+    offense_id: Uuid,
+    url: String,
+    source_name: Option<String>,
+    credibility_score: Option<i32>,
+    story_id: Option<Uuid>,
+    link_weight: f64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -610,4 +1213,24 @@ mod tests {
             + weights.revenue_weight;
         assert!((sum - 1.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_score_weights_validate() {
+        assert!(ScoreWeights::default().validate().is_ok());
+
+        let unbalanced = ScoreWeights {
+            severity_weight: 0.9,
+            ..ScoreWeights::default()
+        };
+        assert!(unbalanced.validate().is_err());
+
+        let negative = ScoreWeights {
+            severity_weight: 1.2,
+            evidence_weight: -0.2,
+            recency_weight: 0.0,
+            community_weight: 0.0,
+            revenue_weight: 0.0,
+        };
+        assert!(negative.validate().is_err());
+    }
 }
@@ -5,6 +5,7 @@
 //! - News volume analytics
 //! - Artist mention trends
 //! - Time-series aggregations
+//! - Replicas of Postgres tables (see `analytics_service::replication`)
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, NaiveDate, Utc};
@@ -136,6 +137,22 @@ impl DuckDbClient {
             [],
         )?;
 
+        // Progress of each replicated Postgres table
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS replication_state (
+                table_name VARCHAR PRIMARY KEY,
+                snapshot_complete BOOLEAN DEFAULT FALSE,
+                snapshot_cursor VARCHAR,
+                synced_through VARCHAR,
+                rows_replicated BIGINT DEFAULT 0,
+                last_run_at VARCHAR,
+                last_error VARCHAR
+            )
+            "#,
+            [],
+        )?;
+
         tracing::info!("DuckDB analytics schema initialized");
         Ok(())
     }
@@ -304,6 +321,104 @@ impl DuckDbClient {
         Ok(results)
     }
 
+    /// Daily mention rows between two dates (inclusive), optionally for a
+    /// single artist. Days without mentions have no row.
+    pub async fn get_artist_mention_series(
+        &self,
+        artist_id: Option<Uuid>,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ArtistMentionDay>> {
+        let conn = self.conn.lock().await;
+
+        let artist_filter = if artist_id.is_some() {
+            "AND artist_id = ?"
+        } else {
+            ""
+        };
+        let mut stmt = conn.prepare(&format!(
+            r#"
+            SELECT
+                artist_id,
+                artist_name,
+                CAST(CAST(date AS DATE) AS VARCHAR) as day,
+                CAST(mention_count AS BIGINT),
+                CAST(positive_mentions AS BIGINT),
+                CAST(negative_mentions AS BIGINT),
+                CAST(offense_mentions AS BIGINT)
+            FROM artist_mention_trends
+            WHERE CAST(date AS DATE) BETWEEN CAST(? AS DATE) AND CAST(? AS DATE)
+            {}
+            ORDER BY artist_id, day
+            "#,
+            artist_filter
+        ))?;
+
+        let mapper = |row: &duckdb::Row| {
+            let artist_id_str: String = row.get(0)?;
+            let day: String = row.get(2)?;
+            Ok(ArtistMentionDay {
+                artist_id: Uuid::parse_str(&artist_id_str).unwrap_or_default(),
+                artist_name: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                date: NaiveDate::parse_from_str(&day, "%Y-%m-%d").unwrap_or(start),
+                mention_count: row.get(3)?,
+                positive_mentions: row.get(4)?,
+                negative_mentions: row.get(5)?,
+                offense_mentions: row.get(6)?,
+            })
+        };
+
+        let rows = match artist_id {
+            Some(id) => stmt.query_map(
+                params![start.to_string(), end.to_string(), id.to_string()],
+                mapper,
+            )?,
+            None => stmt.query_map(params![start.to_string(), end.to_string()], mapper)?,
+        };
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    /// News totals for a time range (start inclusive, end exclusive)
+    pub async fn get_news_period_totals(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<NewsPeriodTotals> {
+        let conn = self.conn.lock().await;
+
+        // Hourly rows each carry their own average, so weight by volume
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT
+                COALESCE(SUM(articles_count), 0) as articles,
+                COALESCE(SUM(offenses_detected), 0) as offenses,
+                SUM(avg_sentiment * articles_count) / NULLIF(SUM(articles_count), 0) as avg_sentiment
+            FROM news_volume_hourly
+            WHERE CAST(hour AS TIMESTAMP) >= CAST(? AS TIMESTAMP)
+            AND CAST(hour AS TIMESTAMP) < CAST(? AS TIMESTAMP)
+            "#,
+        )?;
+
+        let totals = stmt.query_row(
+            params![start.naive_utc().to_string(), end.naive_utc().to_string()],
+            |row| {
+                Ok(NewsPeriodTotals {
+                    articles: row.get(0)?,
+                    offenses: row.get(1)?,
+                    avg_sentiment: row.get::<_, Option<f64>>(2)?.unwrap_or(0.0),
+                })
+            },
+        )?;
+
+        Ok(totals)
+    }
+
     /// Get platform sync health
     pub async fn get_platform_health(&self, days: i32) -> Result<Vec<PlatformHealth>> {
         let conn = self.conn.lock().await;
@@ -364,11 +479,161 @@ impl DuckDbClient {
         Ok(())
     }
 
+    /// Convert a CSV file to Parquet, inferring column types from the CSV
+    pub async fn export_csv_to_parquet(&self, csv_path: &str, output_path: &str) -> Result<()> {
+        let conn = self.conn.lock().await;
+
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM read_csv_auto('{}', header = true)) TO '{}' (FORMAT PARQUET)",
+                csv_path.replace('\'', "''"),
+                output_path.replace('\'', "''")
+            ),
+            [],
+        )
+        .context("Failed to convert CSV to Parquet")?;
+
+        Ok(())
+    }
+
+    /// Run statements that return no rows, such as DDL
+    pub async fn execute_batch(&self, sql: &str) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute_batch(sql)?;
+        Ok(())
+    }
+
+    /// Replace rows by key in one transaction: rows matching any of `keys`
+    /// are deleted, then `rows` are inserted. Values are text in `columns`
+    /// order and are cast by DuckDB to each column's type.
+    pub async fn replace_rows(
+        &self,
+        table: &str,
+        key_expr: &str,
+        columns: &[&str],
+        keys: &[String],
+        rows: &[Vec<Option<String>>],
+    ) -> Result<()> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+
+        // One IN list per chunk scans the table once rather than once per key
+        for chunk in keys.chunks(500) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            tx.execute(
+                &format!(
+                    "DELETE FROM {} WHERE {} IN ({})",
+                    table, key_expr, placeholders
+                ),
+                duckdb::params_from_iter(chunk.iter()),
+            )
+            .with_context(|| format!("Failed to delete replaced rows from {}", table))?;
+        }
+
+        {
+            let placeholders = vec!["?"; columns.len()].join(", ");
+            let mut insert = tx.prepare(&format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                table,
+                columns.join(", "),
+                placeholders
+            ))?;
+            for row in rows {
+                insert
+                    .execute(duckdb::params_from_iter(row.iter()))
+                    .with_context(|| format!("Failed to insert row into {}", table))?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Replication progress for one table
+    pub async fn get_replication_state(&self, table: &str) -> Result<Option<ReplicationState>> {
+        Ok(self
+            .list_replication_state()
+            .await?
+            .into_iter()
+            .find(|s| s.table_name == table))
+    }
+
+    /// Replication progress for every table
+    pub async fn list_replication_state(&self) -> Result<Vec<ReplicationState>> {
+        let conn = self.conn.lock().await;
+
+        let mut stmt = conn.prepare(
+            r#"
+            SELECT table_name, snapshot_complete, snapshot_cursor, synced_through,
+                   rows_replicated, last_run_at, last_error
+            FROM replication_state
+            ORDER BY table_name
+            "#,
+        )?;
+
+        let parse_time = |value: Option<String>| {
+            value
+                .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+                .map(|t| t.with_timezone(&Utc))
+        };
+
+        let rows = stmt.query_map([], |row| {
+            Ok(ReplicationState {
+                table_name: row.get(0)?,
+                snapshot_complete: row.get::<_, Option<bool>>(1)?.unwrap_or(false),
+                snapshot_cursor: row.get(2)?,
+                synced_through: parse_time(row.get(3)?),
+                rows_replicated: row.get::<_, Option<i64>>(4)?.unwrap_or(0),
+                last_run_at: parse_time(row.get(5)?),
+                last_error: row.get(6)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+
+        Ok(results)
+    }
+
+    /// Save replication progress for a table
+    pub async fn save_replication_state(&self, state: &ReplicationState) -> Result<()> {
+        let conn = self.conn.lock().await;
+
+        conn.execute(
+            r#"
+            INSERT INTO replication_state
+            (table_name, snapshot_complete, snapshot_cursor, synced_through,
+             rows_replicated, last_run_at, last_error)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (table_name) DO UPDATE SET
+                snapshot_complete = excluded.snapshot_complete,
+                snapshot_cursor = excluded.snapshot_cursor,
+                synced_through = excluded.synced_through,
+                rows_replicated = excluded.rows_replicated,
+                last_run_at = excluded.last_run_at,
+                last_error = excluded.last_error
+            "#,
+            params![
+                state.table_name,
+                state.snapshot_complete,
+                state.snapshot_cursor,
+                state.synced_through.map(|t| t.to_rfc3339()),
+                state.rows_replicated,
+                state.last_run_at.map(|t| t.to_rfc3339()),
+                state.last_error,
+            ],
+        )?;
+
+        Ok(())
+    }
+
     /// Run a custom analytics query
     pub async fn query<T, F>(
         &self,
         sql: &str,
-        params: &[&dyn duckdb::ToSql],
+        params: &[&(dyn duckdb::ToSql + Sync)],
         mapper: F,
     ) -> Result<Vec<T>>
     where
@@ -377,7 +642,9 @@ impl DuckDbClient {
         let conn = self.conn.lock().await;
         let mut stmt = conn.prepare(sql)?;
 
-        let rows = stmt.query_map(params, mapper)?;
+        // Params are `Sync` so callers' futures stay `Send` across the lock
+        let params: Vec<&dyn duckdb::ToSql> = params.iter().map(|p| *p as _).collect();
+        let rows = stmt.query_map(params.as_slice(), mapper)?;
 
         let mut results = Vec::new();
         for row in rows {
@@ -388,6 +655,35 @@ impl DuckDbClient {
     }
 }
 
+/// Replication progress of one Postgres table
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ReplicationState {
+    pub table_name: String,
+    /// Whether the initial copy has finished
+    pub snapshot_complete: bool,
+    /// Last key copied by an unfinished snapshot
+    pub snapshot_cursor: Option<String>,
+    /// Every change committed before this time has been applied
+    pub synced_through: Option<DateTime<Utc>>,
+    pub rows_replicated: i64,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl ReplicationState {
+    pub fn new(table_name: &str) -> Self {
+        Self {
+            table_name: table_name.to_string(),
+            snapshot_complete: false,
+            snapshot_cursor: None,
+            synced_through: None,
+            rows_replicated: 0,
+            last_run_at: None,
+            last_error: None,
+        }
+    }
+}
+
 /// Sync metrics record
 #[derive(Debug, Clone)]
 pub struct SyncMetrics {
@@ -446,6 +742,26 @@ pub struct TrendingArtist {
     pub positive_ratio: f64,
 }
 
+/// One artist's mentions on one day
+#[derive(Debug, Clone)]
+pub struct ArtistMentionDay {
+    pub artist_id: Uuid,
+    pub artist_name: String,
+    pub date: NaiveDate,
+    pub mention_count: i64,
+    pub positive_mentions: i64,
+    pub negative_mentions: i64,
+    pub offense_mentions: i64,
+}
+
+/// News totals over a period
+#[derive(Debug, Clone)]
+pub struct NewsPeriodTotals {
+    pub articles: i64,
+    pub offenses: i64,
+    pub avg_sentiment: f64,
+}
+
 /// Platform health metrics
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PlatformHealth {
@@ -487,4 +803,43 @@ mod tests {
 
         client.record_sync_metrics(metrics).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_artist_mention_series() {
+        let client = DuckDbClient::in_memory().unwrap();
+        client.initialize_schema().await.unwrap();
+
+        let artist_id = Uuid::new_v4();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        for _ in 0..2 {
+            client
+                .record_artist_mention(ArtistMentionRecord {
+                    date,
+                    artist_id,
+                    artist_name: "Artist".to_string(),
+                    mention_count: 3,
+                    positive_mentions: 1,
+                    negative_mentions: 2,
+                    neutral_mentions: 0,
+                    offense_mentions: 1,
+                    platforms_mentioned: vec![],
+                })
+                .await
+                .unwrap();
+        }
+
+        let series = client
+            .get_artist_mention_series(
+                Some(artist_id),
+                NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].date, date);
+        assert_eq!(series[0].mention_count, 6);
+        assert_eq!(series[0].negative_mentions, 4);
+    }
 }
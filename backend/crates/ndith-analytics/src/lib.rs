@@ -10,21 +10,32 @@ pub mod graph_service;
 
 // Re-export database clients
 pub use databases::{
-    create_graph_store, DuckDbClient, GraphBackendKind, GraphStats, GraphStore, SharedGraphStore,
+    create_graph_store, DuckDbClient, GraphBackendKind, GraphStats, GraphStore, ReplicationState,
+    SharedGraphStore,
 };
 
 // Re-export analytics service components
 pub use analytics_service::{
-    ActionTypeCount, AlbumRevenue, ArtistDiscographyRevenue, ArtistRevenueBreakdown,
+    ActionTypeCount, AlbumRevenue, ArtistDiscographyRevenue, ArtistRevenueBreakdown, ArtistTrend,
     ArtistTroubleScore, CategoryArtistRevenue, CategoryRevenue, CategoryRevenueService,
-    DashboardMetrics, DashboardService, EnforcementAnalytics, EnforcementAnalyticsQuery,
-    EnforcementAnalyticsService, EnforcementStats, EnforcementTimeSeriesPoint, GlobalArtistRevenue,
-    GlobalCategoryRevenue, OffenseCategory, PayoutRate, PlatformRevenue, ProviderStats,
-    RecalculationSummary, RecordPlaycountParams, Report, ReportType, ReportingService,
-    RevenuePlatform, RevenueService, ScoreHistoryEntry, ScoreWeights, SimulationParams,
-    TierDistribution, TimeRange, TrendAnalysisService, TrendData, TrendDirection,
+    CohortAnalyticsService, CohortQuery, CohortRow, CompositionSnapshot, DashboardMetrics,
+    DashboardService, DataFreshness, EnforcementAnalytics, EnforcementAnalyticsQuery,
+    EnforcementAnalyticsService, EnforcementMarker, EnforcementStats, EnforcementTimeSeriesPoint,
+    FilesystemSink, Funnel, FunnelQuery, FunnelReport, FunnelStepResult, GlobalArtistRevenue,
+    GlobalCategoryRevenue, LibraryImpactService, LibraryImpactTimeline, LibraryRegression,
+    LifecycleStage, ListeningEvent, ListeningHistoryService, ListeningImportRecord,
+    ListeningImportSummary, ListeningSource, ModelArtistPayout, NewPayoutRate, NewScoreProfile,
+    OffenseCategory, ParsedExport, PayoutModel, PayoutModelKind, PayoutModelSummary, PayoutRate,
+    PayoutRateChange, PayoutRateCorrection, PayoutRateEntry, PayoutRateFilter, PayoutRateService,
+    PayoutRateUpdate, PlatformRevenue, ProRataModel, ProfileRef, ProviderStats,
+    RecalculationSummary, RecordPlaycountParams, ReplicatedTable, ReplicationRunStats,
+    ReplicationService, ReplicationStatus, Report, ReportFile, ReportFormat, ReportRequest,
+    ReportSchedule, ReportScheduler, ReportSink, ReportStatus, ReportType, ReportingService,
+    RetentionMatrix, RevenuePlatform, RevenueService, ScheduledReport, ScoreExplanation,
+    ScoreHistoryEntry, ScoreProfile, ScoreWeights, SimulationParams, TierDistribution, TimeRange,
+    TimelineEntry, TrendAnalysisService, TrendConfig, TrendData, TrendDirection, TrendSummary,
     TroubleLeaderboardEntry, TroubleScoreComponents, TroubleScoreService, TroubleTier,
-    UserPlaycount, UserRevenueDistribution,
+    UserCentricModel, UserPlaycount, UserRevenueDistribution, WhatIfResult,
 };
 
 // Re-export graph service components
@@ -90,6 +90,20 @@ impl AppConfig {
             return Err(ConfigError::ProductionRequired("JWT_SECRET".to_string()));
         }
 
+        // Browsers only run WebAuthn in secure contexts
+        if let Some(origin) = self
+            .auth
+            .webauthn
+            .origins
+            .iter()
+            .find(|origin| !origin.starts_with("https://"))
+        {
+            return Err(ConfigError::InvalidValue {
+                key: "WEBAUTHN_ORIGINS".to_string(),
+                message: format!("Production origin {} must use https", origin),
+            });
+        }
+
         // Database URL must not use localhost
         if self.database.url.contains("localhost") || self.database.url.contains("127.0.0.1") {
             return Err(ConfigError::InvalidValue {
@@ -218,6 +232,7 @@ pub struct AuthConfig {
     pub access_token_expiry: Duration,
     pub refresh_token_expiry: Duration,
     pub bcrypt_cost: u32,
+    pub webauthn: WebAuthnConfig,
 }
 
 impl AuthConfig {
@@ -252,6 +267,7 @@ impl AuthConfig {
                 .ok()
                 .and_then(|c| c.parse().ok())
                 .unwrap_or(12),
+            webauthn: WebAuthnConfig::from_env()?,
         })
     }
 
@@ -261,6 +277,145 @@ impl AuthConfig {
     }
 }
 
+/// How much attestation to ask for when a passkey is registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttestationPolicy {
+    /// Don't ask for attestation and ignore any statement that is sent
+    None,
+    /// Ask for attestation and verify statements in formats we support;
+    /// other formats are accepted as unattested
+    Indirect,
+    /// Ask for attestation and reject registrations whose statement can't be verified
+    Direct,
+}
+
+impl AttestationPolicy {
+    /// Value for `attestation` in the credential creation options
+    pub fn conveyance(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Indirect => "indirect",
+            Self::Direct => "direct",
+        }
+    }
+}
+
+/// Whether authenticators must verify the user (PIN, biometrics)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserVerificationPolicy {
+    Required,
+    Preferred,
+    Discouraged,
+}
+
+impl UserVerificationPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Required => "required",
+            Self::Preferred => "preferred",
+            Self::Discouraged => "discouraged",
+        }
+    }
+}
+
+/// WebAuthn relying party configuration for passkeys
+#[derive(Debug, Clone)]
+pub struct WebAuthnConfig {
+    /// Relying party ID, the domain credentials are scoped to
+    pub rp_id: String,
+    /// Name shown by the authenticator during registration
+    pub rp_name: String,
+    /// Origins allowed in client data
+    pub origins: Vec<String>,
+    pub attestation: AttestationPolicy,
+    /// Applies to registration and second-factor assertions; passwordless
+    /// login always requires user verification
+    pub user_verification: UserVerificationPolicy,
+    /// How long a ceremony challenge stays valid
+    pub challenge_ttl: Duration,
+}
+
+impl WebAuthnConfig {
+    /// Configuration for a single origin, with the RP ID taken from its host
+    pub fn for_origin(origin: &str) -> Self {
+        let origin = origin.trim().trim_end_matches('/').to_string();
+        let rp_id = split_url_base(&origin)
+            .map(|(_, authority)| authority.split(':').next().unwrap_or(authority))
+            .unwrap_or("localhost")
+            .to_string();
+
+        Self {
+            rp_id,
+            rp_name: "No Drake in the House".to_string(),
+            origins: vec![origin],
+            attestation: AttestationPolicy::None,
+            user_verification: UserVerificationPolicy::Preferred,
+            challenge_ttl: Duration::from_secs(300),
+        }
+    }
+
+    /// Load from environment, defaulting to the frontend origin
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let mut config = Self::for_origin(&public_frontend_base_url());
+
+        if let Some(rp_id) = non_empty_env("WEBAUTHN_RP_ID") {
+            config.rp_id = rp_id;
+        }
+        if let Some(rp_name) = non_empty_env("WEBAUTHN_RP_NAME") {
+            config.rp_name = rp_name;
+        }
+        if let Some(origins) = non_empty_env("WEBAUTHN_ORIGINS") {
+            config.origins = origins
+                .split(',')
+                .map(|origin| origin.trim().trim_end_matches('/').to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect();
+        }
+
+        if let Some(attestation) = non_empty_env("WEBAUTHN_ATTESTATION") {
+            config.attestation = match attestation.to_lowercase().as_str() {
+                "none" => AttestationPolicy::None,
+                "indirect" => AttestationPolicy::Indirect,
+                "direct" => AttestationPolicy::Direct,
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        key: "WEBAUTHN_ATTESTATION".to_string(),
+                        message: "must be one of none, indirect, direct".to_string(),
+                    })
+                }
+            };
+        }
+
+        if let Some(user_verification) = non_empty_env("WEBAUTHN_USER_VERIFICATION") {
+            config.user_verification = match user_verification.to_lowercase().as_str() {
+                "required" => UserVerificationPolicy::Required,
+                "preferred" => UserVerificationPolicy::Preferred,
+                "discouraged" => UserVerificationPolicy::Discouraged,
+                _ => {
+                    return Err(ConfigError::InvalidValue {
+                        key: "WEBAUTHN_USER_VERIFICATION".to_string(),
+                        message: "must be one of required, preferred, discouraged".to_string(),
+                    })
+                }
+            };
+        }
+
+        if let Some(ttl) = non_empty_env("WEBAUTHN_CHALLENGE_TTL_SECS") {
+            config.challenge_ttl =
+                Duration::from_secs(ttl.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: "WEBAUTHN_CHALLENGE_TTL_SECS".to_string(),
+                    message: "must be a number of seconds".to_string(),
+                })?);
+        }
+
+        if config.origins.is_empty() {
+            return Err(ConfigError::MissingRequired("WEBAUTHN_ORIGINS".to_string()));
+        }
+
+        Ok(config)
+    }
+}
+
 /// Token refresh background job configuration
 #[derive(Clone)]
 pub struct TokenRefreshConfig {
@@ -354,6 +509,313 @@ impl Default for TokenRefreshConfig {
     }
 }
 
+/// JWT signing keyring configuration
+#[derive(Clone)]
+pub struct JwtKeyringConfig {
+    /// Days a signing key stays current before its successor takes over
+    pub rotation_interval_days: i64,
+    /// Hours a new key is published in the JWKS before it signs anything.
+    /// Must exceed how long downstream verifiers cache the JWKS.
+    pub publish_lead_hours: i64,
+    /// Minutes between rotation checks and keyring reloads
+    pub check_interval_minutes: u64,
+}
+
+impl JwtKeyringConfig {
+    /// Read settings from `JWT_KEY_ROTATION_DAYS`, `JWT_KEY_PUBLISH_LEAD_HOURS`
+    /// and `JWT_KEY_CHECK_INTERVAL_MINUTES`
+    pub fn from_env() -> Result<Self, ConfigError> {
+        fn env_or<T: std::str::FromStr>(name: &str, default: T) -> Result<T, ConfigError> {
+            match std::env::var(name) {
+                Ok(value) => value.parse().map_err(|_| ConfigError::InvalidValue {
+                    key: name.to_string(),
+                    message: format!("'{}' is not a valid number", value),
+                }),
+                Err(_) => Ok(default),
+            }
+        }
+
+        let defaults = Self::default();
+        let config = Self {
+            rotation_interval_days: env_or(
+                "JWT_KEY_ROTATION_DAYS",
+                defaults.rotation_interval_days,
+            )?,
+            publish_lead_hours: env_or("JWT_KEY_PUBLISH_LEAD_HOURS", defaults.publish_lead_hours)?,
+            check_interval_minutes: env_or(
+                "JWT_KEY_CHECK_INTERVAL_MINUTES",
+                defaults.check_interval_minutes,
+            )?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.rotation_interval_days < 1 {
+            return Err(ConfigError::InvalidValue {
+                key: "JWT_KEY_ROTATION_DAYS".to_string(),
+                message: "Must be at least 1".to_string(),
+            });
+        }
+        if self.check_interval_minutes < 1 {
+            return Err(ConfigError::InvalidValue {
+                key: "JWT_KEY_CHECK_INTERVAL_MINUTES".to_string(),
+                message: "Must be at least 1".to_string(),
+            });
+        }
+        // A successor is published at most one check interval late, so a
+        // shorter lead could leave it signing before verifiers have seen it
+        if self.publish_lead_hours.saturating_mul(60) <= self.check_interval_minutes as i64 {
+            return Err(ConfigError::InvalidValue {
+                key: "JWT_KEY_PUBLISH_LEAD_HOURS".to_string(),
+                message: "Must be longer than JWT_KEY_CHECK_INTERVAL_MINUTES".to_string(),
+            });
+        }
+        if self.publish_lead_hours >= self.rotation_interval_days.saturating_mul(24) {
+            return Err(ConfigError::InvalidValue {
+                key: "JWT_KEY_PUBLISH_LEAD_HOURS".to_string(),
+                message: "Must be shorter than JWT_KEY_ROTATION_DAYS".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for JwtKeyringConfig {
+    fn default() -> Self {
+        Self {
+            rotation_interval_days: 30,
+            publish_lead_hours: 24,
+            check_interval_minutes: 60,
+        }
+    }
+}
+
+/// Login brute-force and IP block thresholds
+#[derive(Clone, Debug)]
+pub struct BruteForceConfig {
+    /// Sliding window over which failures and suspicious requests are counted
+    pub window_secs: i64,
+    /// Failed logins for one account within the window before it locks
+    pub account_max_failures: u32,
+    /// Failed logins from one IP, across accounts, before the IP is blocked
+    pub ip_max_failures: u32,
+    /// Suspicious requests from one IP within the window before it's blocked
+    pub suspicious_activity_limit: u32,
+    pub account_lockout_secs: i64,
+    pub ip_block_secs: i64,
+    /// Failures allowed before each further attempt has to wait
+    pub delay_free_attempts: u32,
+    /// Wait after the first delayed failure, doubling for each one after
+    pub delay_base_ms: i64,
+    pub delay_max_ms: i64,
+}
+
+impl BruteForceConfig {
+    pub fn from_env() -> Self {
+        fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+
+        Self {
+            window_secs: env_or("BRUTE_FORCE_WINDOW_SECS", 3600),
+            account_max_failures: env_or("BRUTE_FORCE_ACCOUNT_MAX_FAILURES", 5),
+            ip_max_failures: env_or("BRUTE_FORCE_IP_MAX_FAILURES", 50),
+            suspicious_activity_limit: env_or("SUSPICIOUS_ACTIVITY_LIMIT", 100),
+            account_lockout_secs: env_or("BRUTE_FORCE_ACCOUNT_LOCKOUT_SECS", 3600),
+            ip_block_secs: env_or("IP_BLOCK_SECS", 86400),
+            delay_free_attempts: env_or("BRUTE_FORCE_DELAY_FREE_ATTEMPTS", 2),
+            delay_base_ms: env_or("BRUTE_FORCE_DELAY_BASE_MS", 1000),
+            delay_max_ms: env_or("BRUTE_FORCE_DELAY_MAX_MS", 30000),
+        }
+    }
+}
+
+impl Default for BruteForceConfig {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+/// Audit log hash chain checkpoints
+#[derive(Clone)]
+pub struct AuditChainConfig {
+    /// Secret the checkpoint signing key is derived from. Only development
+    /// falls back to a built-in key.
+    pub checkpoint_secret: String,
+    /// Minutes between checkpoints of each chain's head
+    pub checkpoint_interval_minutes: u64,
+}
+
+impl AuditChainConfig {
+    /// Read `AUDIT_CHECKPOINT_KEY` and `AUDIT_CHECKPOINT_INTERVAL_MINUTES`.
+    /// Outside development the key is required: a checkpoint signed with a
+    /// published default vouches for nothing.
+    pub fn from_env(env: Environment) -> Result<Self, ConfigError> {
+        let checkpoint_secret = match std::env::var("AUDIT_CHECKPOINT_KEY") {
+            Ok(secret) if !secret.is_empty() => secret,
+            _ if env.is_development() => AuthConfig::default_jwt_secret(),
+            _ => {
+                return Err(ConfigError::ProductionRequired(
+                    "AUDIT_CHECKPOINT_KEY".to_string(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            checkpoint_secret,
+            checkpoint_interval_minutes: std::env::var("AUDIT_CHECKPOINT_INTERVAL_MINUTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+        })
+    }
+}
+
+impl Default for AuditChainConfig {
+    fn default() -> Self {
+        Self {
+            checkpoint_secret: AuthConfig::default_jwt_secret(),
+            checkpoint_interval_minutes: 60,
+        }
+    }
+}
+
+/// GDPR export and erasure request processing
+#[derive(Clone)]
+pub struct DataRequestConfig {
+    /// Secret export download links are signed with. Only development falls
+    /// back to a built-in key.
+    pub link_secret: String,
+    /// Hours a finished export can be downloaded before it's deleted
+    pub link_ttl_hours: i64,
+    /// Seconds between polls of the request queue
+    pub poll_interval_seconds: u64,
+    /// Attempts before a request is marked failed
+    pub max_attempts: i32,
+    /// Minutes after which a request stuck in processing is retried
+    pub stale_after_minutes: i64,
+}
+
+impl DataRequestConfig {
+    /// Outside development `DATA_EXPORT_LINK_KEY` is required: anyone who
+    /// knows a default key could sign download links for any export.
+    pub fn from_env(env: Environment) -> Result<Self, ConfigError> {
+        fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+
+        let link_secret = match std::env::var("DATA_EXPORT_LINK_KEY") {
+            Ok(secret) if !secret.is_empty() => secret,
+            _ if env.is_development() => AuthConfig::default_jwt_secret(),
+            _ => {
+                return Err(ConfigError::ProductionRequired(
+                    "DATA_EXPORT_LINK_KEY".to_string(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            link_secret,
+            link_ttl_hours: env_or("DATA_EXPORT_LINK_TTL_HOURS", 72),
+            poll_interval_seconds: env_or("DATA_REQUEST_POLL_INTERVAL_SECONDS", 30),
+            max_attempts: env_or("DATA_REQUEST_MAX_ATTEMPTS", 3),
+            stale_after_minutes: env_or("DATA_REQUEST_STALE_AFTER_MINUTES", 30),
+        })
+    }
+}
+
+impl Default for DataRequestConfig {
+    fn default() -> Self {
+        Self {
+            link_secret: AuthConfig::default_jwt_secret(),
+            link_ttl_hours: 72,
+            poll_interval_seconds: 30,
+            max_attempts: 3,
+            stale_after_minutes: 30,
+        }
+    }
+}
+
+/// Where outgoing mail goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MailTransport {
+    /// Write each message as an `.eml` file into a directory, for local
+    /// development and tests
+    File { dir: std::path::PathBuf },
+    /// POST each message as JSON to a mail provider's HTTP API
+    Http {
+        endpoint: String,
+        api_key: Option<String>,
+    },
+}
+
+/// Outgoing mail and the email verification flows that use it
+#[derive(Debug, Clone)]
+pub struct EmailConfig {
+    pub transport: MailTransport,
+    /// `From` header of every message
+    pub from_address: String,
+    /// Frontend base URL that verification and confirmation links point to
+    pub link_base_url: String,
+    /// Hours a registration verification link stays valid
+    pub verification_ttl_hours: i64,
+    /// Hours both sides of an email change have to confirm it
+    pub change_ttl_hours: i64,
+    /// Seconds a user has to wait before another verification mail is sent
+    pub resend_cooldown_seconds: i64,
+}
+
+impl EmailConfig {
+    pub fn from_env() -> Self {
+        fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+
+        let transport = match (
+            std::env::var("MAIL_TRANSPORT").ok().as_deref(),
+            non_empty_env("MAIL_HTTP_ENDPOINT"),
+        ) {
+            (Some("http"), Some(endpoint)) => MailTransport::Http {
+                endpoint,
+                api_key: non_empty_env("MAIL_HTTP_API_KEY"),
+            },
+            _ => MailTransport::File {
+                dir: non_empty_env("MAIL_FILE_DIR")
+                    .unwrap_or_else(|| "./var/mail".to_string())
+                    .into(),
+            },
+        };
+
+        Self {
+            transport,
+            from_address: non_empty_env("MAIL_FROM").unwrap_or_else(|| {
+                "No Drake in the House <no-reply@nodrakeinthe.house>".to_string()
+            }),
+            link_base_url: public_frontend_base_url(),
+            verification_ttl_hours: env_or("EMAIL_VERIFICATION_TTL_HOURS", 48),
+            change_ttl_hours: env_or("EMAIL_CHANGE_TTL_HOURS", 24),
+            resend_cooldown_seconds: env_or("EMAIL_VERIFICATION_RESEND_COOLDOWN_SECONDS", 60),
+        }
+    }
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
 /// OAuth provider settings
 #[derive(Clone)]
 pub struct OAuthSettings {
@@ -839,6 +1301,17 @@ mod tests {
         assert!(secret.len() > 32);
     }
 
+    #[test]
+    fn test_webauthn_rp_id_derived_from_origin() {
+        let config = WebAuthnConfig::for_origin("https://nodrakeinthe.house/");
+        assert_eq!(config.rp_id, "nodrakeinthe.house");
+        assert_eq!(config.origins, vec!["https://nodrakeinthe.house"]);
+        assert_eq!(config.attestation, AttestationPolicy::None);
+
+        let local = WebAuthnConfig::for_origin("http://localhost:5050");
+        assert_eq!(local.rp_id, "localhost");
+    }
+
     #[test]
     fn test_provider_callback_uri_uses_backend_callback_for_streaming_providers() {
         let _guard = ENV_LOCK.lock().unwrap();
@@ -856,6 +1329,31 @@ mod tests {
         std::env::remove_var("RENDER_EXTERNAL_URL");
     }
 
+    #[test]
+    fn test_jwt_keyring_config_rejects_unusable_intervals() {
+        assert!(JwtKeyringConfig::default().validate().is_ok());
+
+        let zero_interval = JwtKeyringConfig {
+            check_interval_minutes: 0,
+            ..JwtKeyringConfig::default()
+        };
+        assert!(zero_interval.validate().is_err());
+
+        let lead_within_interval = JwtKeyringConfig {
+            publish_lead_hours: 1,
+            check_interval_minutes: 60,
+            ..JwtKeyringConfig::default()
+        };
+        assert!(lead_within_interval.validate().is_err());
+
+        let lead_past_rotation = JwtKeyringConfig {
+            rotation_interval_days: 1,
+            publish_lead_hours: 24,
+            ..JwtKeyringConfig::default()
+        };
+        assert!(lead_past_rotation.validate().is_err());
+    }
+
     #[test]
     fn test_provider_callback_uri_with_override_prefers_explicit_redirect() {
         let _guard = ENV_LOCK.lock().unwrap();
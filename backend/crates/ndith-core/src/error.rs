@@ -56,6 +56,9 @@ pub enum AppError {
     #[error("Insufficient permissions")]
     InsufficientPermissions,
 
+    #[error("Email address not verified")]
+    EmailNotVerified,
+
     // OAuth errors - comprehensive OAuth error handling
     #[error("OAuth error: {0}")]
     OAuth(#[from] OAuthError),
@@ -231,7 +234,7 @@ impl AppError {
             },
 
             // 403 Forbidden
-            AppError::InsufficientPermissions => StatusCode::FORBIDDEN,
+            AppError::InsufficientPermissions | AppError::EmailNotVerified => StatusCode::FORBIDDEN,
 
             // 404 Not Found
             AppError::NotFound { .. } => StatusCode::NOT_FOUND,
@@ -277,6 +280,7 @@ impl AppError {
             AppError::TwoFactorRequired => "AUTH_2FA_REQUIRED",
             AppError::TwoFactorInvalid => "AUTH_2FA_INVALID",
             AppError::InsufficientPermissions => "AUTH_INSUFFICIENT_PERMISSIONS",
+            AppError::EmailNotVerified => "AUTH_EMAIL_NOT_VERIFIED",
             AppError::OAuth(oauth_error) => match oauth_error.error_type().as_str() {
                 "provider_error" => "OAUTH_PROVIDER_ERROR",
                 "provider_not_configured" => "OAUTH_PROVIDER_NOT_CONFIGURED",
@@ -349,6 +353,9 @@ impl AppError {
             AppError::InsufficientPermissions => {
                 "You don't have permission to perform this action".to_string()
             }
+            AppError::EmailNotVerified => {
+                "Verify your email address to perform this action".to_string()
+            }
             AppError::ValidationFailed(_) => "Please check your input and try again".to_string(),
             AppError::InvalidRequestFormat(msg) => format!("Invalid request format: {}", msg),
             AppError::MissingField { field } => format!("Missing required field: {}", field),
@@ -10,9 +10,11 @@ pub mod validation;
 
 // Re-export commonly used types
 pub use config::{
-    AppConfig, AppleMusicCredentials, AuthConfig, ConfigError, DatabaseSettings, DeezerConfig,
-    Environment, OAuthSettings, PlatformSyncConfig, RedisSettings, ServerConfig,
-    SpotifyCredentials, TidalCredentials, TokenRefreshConfig, TokenVaultConfig, YouTubeCredentials,
+    AppConfig, AppleMusicCredentials, AttestationPolicy, AuditChainConfig, AuthConfig,
+    BruteForceConfig, ConfigError, DataRequestConfig, DatabaseSettings, DeezerConfig, EmailConfig,
+    Environment, JwtKeyringConfig, MailTransport, OAuthSettings, PlatformSyncConfig, RedisSettings,
+    ServerConfig, SpotifyCredentials, TidalCredentials, TokenRefreshConfig, TokenVaultConfig,
+    UserVerificationPolicy, WebAuthnConfig, YouTubeCredentials,
 };
 pub use error::{AppError, ErrorResponse, Result};
 pub use models::*;
@@ -117,22 +117,33 @@ pub enum AccessStatus {
     PendingReview,
 }
 
-/// GDPR/CCPA data export request
+/// GDPR/CCPA data export or erasure request
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct DataExportRequest {
     pub id: Uuid,
-    pub user_id: Uuid,
+    /// Cleared once an erasure request has removed the account
+    pub user_id: Option<Uuid>,
     pub request_type: DataRequestType,
     pub status: DataRequestStatus,
     pub requested_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub attempts: i32,
+    pub reason: Option<String>,
+    pub error: Option<String>,
+    /// Export manifest, or the cleanup summary of an erasure
+    pub manifest: Option<serde_json::Value>,
+    /// Signed download link, filled in while a completed export is available
     pub export_url: Option<String>,
     pub expires_at: Option<DateTime<Utc>>,
+    pub downloaded_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing)]
     pub verification_token: Option<String>,
 }
 
 /// Data request types
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
 #[sqlx(type_name = "data_request_type", rename_all = "lowercase")]
 pub enum DataRequestType {
     Export,
@@ -140,7 +151,8 @@ pub enum DataRequestType {
 }
 
 /// Data request status
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
 #[sqlx(type_name = "data_request_status", rename_all = "lowercase")]
 pub enum DataRequestStatus {
     Pending,
@@ -4,7 +4,9 @@ use std::str::FromStr;
 use uuid::Uuid;
 
 /// User roles for role-based access control
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum UserRole {
     /// Regular user with standard permissions
@@ -77,6 +79,10 @@ pub struct Claims {
     /// JWT audience (RS256 tokens for Convex verification)
     #[serde(default)]
     pub aud: String,
+    /// Whether the account's address is verified when the token was issued;
+    /// Convex reads it as the standard OIDC claim
+    #[serde(default)]
+    pub email_verified: bool,
 }
 
 fn default_token_type() -> TokenType {
@@ -152,6 +158,7 @@ impl Claims {
             role: UserRole::User,
             iss: String::new(),
             aud: String::new(),
+            email_verified: false,
         }
     }
 
@@ -188,6 +195,7 @@ impl Claims {
             role: UserRole::User,
             iss: String::new(),
             aud: String::new(),
+            email_verified: false,
         }
     }
 
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// What a mailed email token confirms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "email_token_purpose", rename_all = "snake_case")]
+pub enum EmailTokenPurpose {
+    /// The account's address, sent on registration or resend
+    Verify,
+    /// The current address agrees to an email change
+    ChangeCurrent,
+    /// The new address in an email change is reachable
+    ChangeNew,
+}
+
+/// An email change waiting for one or both addresses to confirm
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PendingEmailChange {
+    pub new_email: String,
+    pub current_confirmed_at: Option<DateTime<Utc>>,
+    pub new_confirmed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The caller's address, whether it's verified, and any change in flight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailStatus {
+    pub email: String,
+    pub email_verified: bool,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub pending_change: Option<PendingEmailChange>,
+}
+
+/// Outcome of following an email change link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailChangeConfirmation {
+    pub confirmed: EmailTokenPurpose,
+    /// True once both addresses confirmed and the account's email changed
+    pub completed: bool,
+    pub new_email: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailTokenRequest {
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangeEmailRequest {
+    pub new_email: String,
+    /// Required when the account has a password
+    pub password: Option<String>,
+}
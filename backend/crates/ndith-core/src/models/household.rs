@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A user's role within their household
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "household_role", rename_all = "lowercase")]
+pub enum HouseholdRole {
+    /// Created the household; manages members, invites and child blocks
+    Owner,
+    /// Shares the household blocklist and can edit it
+    Member,
+    /// Shares the household blocklist read-only and is bound by enforced blocks
+    Child,
+}
+
+impl HouseholdRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HouseholdRole::Owner => "owner",
+            HouseholdRole::Member => "member",
+            HouseholdRole::Child => "child",
+        }
+    }
+
+    /// Whether this role can add to or remove from the shared blocklist
+    pub fn can_edit_shared_list(&self) -> bool {
+        matches!(self, HouseholdRole::Owner | HouseholdRole::Member)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Household {
+    pub id: Uuid,
+    pub name: String,
+    pub owner_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct HouseholdMember {
+    pub user_id: Uuid,
+    pub email: String,
+    pub role: HouseholdRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct HouseholdInvite {
+    pub id: Uuid,
+    pub household_id: Uuid,
+    pub household_name: String,
+    pub email: String,
+    pub role: HouseholdRole,
+    pub invited_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// An artist on the shared household list or enforced on a child
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct HouseholdBlock {
+    pub artist_id: Uuid,
+    pub canonical_name: String,
+    pub note: Option<String>,
+    pub added_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The caller's household with its members, and open invites for the owner
+#[derive(Debug, Clone, Serialize)]
+pub struct HouseholdOverview {
+    pub household: Household,
+    pub role: HouseholdRole,
+    pub members: Vec<HouseholdMember>,
+    pub invites: Vec<HouseholdInvite>,
+}
+
+/// One artist on a user's effective blocklist
+///
+/// `sources` lists where the block comes from: `personal`, `household`
+/// and/or `enforced`. Locked entries are enforced by the household owner
+/// and cannot be removed by the user.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct EffectiveBlock {
+    pub artist_id: Uuid,
+    pub canonical_name: String,
+    pub sources: Vec<String>,
+    pub note: Option<String>,
+    pub locked: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateHouseholdRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InviteHouseholdMemberRequest {
+    pub email: String,
+    pub role: HouseholdRole,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateHouseholdMemberRequest {
+    pub role: HouseholdRole,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HouseholdBlockRequest {
+    pub artist_id: Uuid,
+    pub note: Option<String>,
+}
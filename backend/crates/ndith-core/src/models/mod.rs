@@ -5,9 +5,13 @@ pub mod audit;
 pub mod auth;
 pub mod community_list;
 pub mod dnp_list;
+pub mod email_verification;
+pub mod household;
 pub mod notification;
 pub mod oauth;
 pub mod offense;
+pub mod oidc;
+pub mod permissions;
 pub mod playlist;
 pub mod rate_limit;
 pub mod sanitizer;
@@ -15,6 +19,7 @@ pub mod spotify;
 pub mod tidal;
 pub mod token_vault;
 pub mod user;
+pub mod webauthn;
 pub mod youtube_music;
 
 use serde::Serialize;
@@ -32,9 +37,10 @@ pub use artist::{Artist, ArtistMetadata, ExternalIds};
 pub use user::{
     AuthResponse, AuthenticatedUser, CreateUserRequest, LinkOAuthAccountRequest, LoginRequest,
     MergeAccountsRequest, MergeAccountsResponse, OAuthAccountInfo, OAuthLoginRequest,
-    RefreshTokenRequest, RegisterRequest, RegistrationErrorResponse, RegistrationValidationError,
-    TokenPair, TotpDisableRequest, TotpEnableRequest, TotpSetupRequest, TotpSetupResponse,
-    TotpStatusResponse, TotpVerifyRequest, UnlinkOAuthAccountRequest, User, UserSettings,
+    RecoveryCodesRegenerateRequest, RefreshTokenRequest, RegisterRequest,
+    RegistrationErrorResponse, RegistrationValidationError, TokenPair, TotpDisableRequest,
+    TotpEnableRequest, TotpSetupRequest, TotpSetupResponse, TotpStatusResponse, TotpVerifyRequest,
+    UnlinkOAuthAccountRequest, User, UserSettings,
 };
 // Use qualified name for UserProfile to avoid conflict with audit::UserProfile
 pub use user::UserProfile as UserUserProfile;
@@ -51,11 +57,16 @@ pub use audit::{
 pub use auth::*;
 pub use community_list::*;
 pub use dnp_list::*;
+pub use email_verification::*;
+pub use household::*;
 pub use oauth::*;
+pub use oidc::*;
+pub use permissions::*;
 pub use rate_limit::*;
 pub use spotify::*;
 pub use tidal::*;
 pub use token_vault::*;
+pub use webauthn::*;
 pub use youtube_music::*;
 // Use qualified name for audit UserProfile
 pub use audit::UserProfile as AuditUserProfile;
@@ -0,0 +1,135 @@
+//! OpenID Connect provider types
+//!
+//! Request and response shapes for "Sign in with NDITH": partner apps get an
+//! authorization code for a signed-in user's consent, exchange it for tokens
+//! at the token endpoint, and read the user's data with the scopes granted.
+
+use serde::{Deserialize, Serialize};
+
+/// Scopes a client may request
+pub const OIDC_SCOPE_OPENID: &str = "openid";
+pub const OIDC_SCOPE_EMAIL: &str = "email";
+pub const OIDC_SCOPE_OFFLINE_ACCESS: &str = "offline_access";
+pub const OIDC_SCOPE_DNP_READ: &str = "dnp:read";
+
+pub const OIDC_SUPPORTED_SCOPES: [&str; 4] = [
+    OIDC_SCOPE_OPENID,
+    OIDC_SCOPE_EMAIL,
+    OIDC_SCOPE_OFFLINE_ACCESS,
+    OIDC_SCOPE_DNP_READ,
+];
+
+/// Text shown on the consent screen for a scope
+pub fn oidc_scope_description(scope: &str) -> &'static str {
+    match scope {
+        OIDC_SCOPE_OPENID => "Confirm who you are",
+        OIDC_SCOPE_EMAIL => "See your email address",
+        OIDC_SCOPE_OFFLINE_ACCESS => "Keep access while you're not using the app",
+        OIDC_SCOPE_DNP_READ => "Read your Do-Not-Play list",
+        _ => "Unknown permission",
+    }
+}
+
+/// Authorization endpoint parameters (RFC 6749 §4.1.1, RFC 7636 §4.3)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OidcAuthorizationRequest {
+    #[serde(default)]
+    pub response_type: String,
+    #[serde(default)]
+    pub client_id: String,
+    #[serde(default)]
+    pub redirect_uri: String,
+    #[serde(default)]
+    pub scope: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_challenge: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_challenge_method: Option<String>,
+    /// Only `none` is acted on: fail instead of asking for consent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+/// The signed-in user's answer on the consent screen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcAuthorizationDecision {
+    #[serde(flatten)]
+    pub request: OidcAuthorizationRequest,
+    pub approve: bool,
+}
+
+/// Token endpoint parameters, form-encoded (RFC 6749 §4.1.3, §6)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OidcTokenRequest {
+    #[serde(default)]
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    pub refresh_token: Option<String>,
+    /// Narrower scope on refresh
+    pub scope: Option<String>,
+    /// `client_secret_post` or public clients; Basic auth takes precedence
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcTokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub scope: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+}
+
+/// Token revocation parameters, form-encoded (RFC 7009 §2.1)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OidcRevocationRequest {
+    #[serde(default)]
+    pub token: String,
+    pub token_type_hint: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+/// ID token claims (OpenID Connect Core §2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcIdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub auth_time: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateOidcClientRequest {
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    /// Defaults to every supported scope
+    #[serde(default)]
+    pub allowed_scopes: Option<Vec<String>>,
+    /// Confidential clients get a secret; public clients (mobile, SPA) rely
+    /// on PKCE alone
+    #[serde(default = "default_confidential")]
+    pub confidential: bool,
+}
+
+fn default_confidential() -> bool {
+    true
+}
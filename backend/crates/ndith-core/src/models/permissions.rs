@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use super::auth::{Claims, UserRole};
+
+/// Named capabilities that routes require and roles grant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Permission {
+    /// Verify, edit and attach evidence to offense records
+    #[serde(rename = "offense.verify")]
+    OffenseVerify,
+    /// Review community-submitted offense events and disputes
+    #[serde(rename = "community.moderate")]
+    CommunityModerate,
+    /// Start catalog syncs, imports and pipeline runs
+    #[serde(rename = "sync.trigger")]
+    SyncTrigger,
+    /// Read and change platform-wide analytics
+    #[serde(rename = "analytics.global")]
+    AnalyticsGlobal,
+    /// Grant and revoke roles, manage login lockouts
+    #[serde(rename = "users.manage")]
+    UsersManage,
+    /// Verify and export the audit log
+    #[serde(rename = "audit.manage")]
+    AuditManage,
+    /// Register and remove OpenID Connect clients
+    #[serde(rename = "oidc.clients.manage")]
+    OidcClientsManage,
+    /// Rotate the JWT signing keys
+    #[serde(rename = "keys.manage")]
+    KeysManage,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 8] = [
+        Permission::OffenseVerify,
+        Permission::CommunityModerate,
+        Permission::SyncTrigger,
+        Permission::AnalyticsGlobal,
+        Permission::UsersManage,
+        Permission::AuditManage,
+        Permission::OidcClientsManage,
+        Permission::KeysManage,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::OffenseVerify => "offense.verify",
+            Permission::CommunityModerate => "community.moderate",
+            Permission::SyncTrigger => "sync.trigger",
+            Permission::AnalyticsGlobal => "analytics.global",
+            Permission::UsersManage => "users.manage",
+            Permission::AuditManage => "audit.manage",
+            Permission::OidcClientsManage => "oidc.clients.manage",
+            Permission::KeysManage => "keys.manage",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Permission::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == s)
+            .ok_or_else(|| format!("Unknown permission '{}'", s))
+    }
+}
+
+impl UserRole {
+    /// Roles an admin can grant to a user account. `User` is implicit and
+    /// `Service` is reserved for service-to-service tokens.
+    pub const GRANTABLE: [UserRole; 2] = [UserRole::Moderator, UserRole::Admin];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::User => "user",
+            UserRole::Moderator => "moderator",
+            UserRole::Admin => "admin",
+            UserRole::Service => "service",
+        }
+    }
+
+    pub fn is_grantable(&self) -> bool {
+        Self::GRANTABLE.contains(self)
+    }
+
+    /// Permissions carried by this role
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            UserRole::User => &[],
+            UserRole::Moderator => &[Permission::OffenseVerify, Permission::CommunityModerate],
+            UserRole::Service => &[
+                Permission::OffenseVerify,
+                Permission::CommunityModerate,
+                Permission::SyncTrigger,
+            ],
+            UserRole::Admin => &Permission::ALL,
+        }
+    }
+}
+
+/// A role stored for a user, as listed by the admin API
+#[derive(Debug, Clone, Serialize)]
+pub struct RoleGrant {
+    pub role: UserRole,
+    pub permissions: &'static [Permission],
+    pub granted_by: Option<Uuid>,
+    pub granted_at: DateTime<Utc>,
+}
+
+/// Effective permissions of the caller, resolved once per request from the
+/// token role, legacy scopes and stored role grants
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Permissions {
+    roles: BTreeSet<UserRole>,
+    permissions: BTreeSet<Permission>,
+}
+
+impl Permissions {
+    pub fn from_roles(roles: impl IntoIterator<Item = UserRole>) -> Self {
+        let mut resolved = Self::default();
+        for role in roles {
+            resolved.add_role(role);
+        }
+        resolved
+    }
+
+    pub fn add_role(&mut self, role: UserRole) {
+        self.permissions.extend(role.permissions().iter().copied());
+        self.roles.insert(role);
+    }
+
+    pub fn has(&self, permission: Permission) -> bool {
+        self.permissions.contains(&permission)
+    }
+
+    pub fn roles(&self) -> impl Iterator<Item = UserRole> + '_ {
+        self.roles.iter().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Permission> + '_ {
+        self.permissions.iter().copied()
+    }
+}
+
+impl Claims {
+    /// Permissions granted by the token alone: its role plus the legacy
+    /// `admin` and `moderate` scopes
+    pub fn permissions(&self) -> Permissions {
+        let mut permissions = Permissions::from_roles([self.role]);
+        if self.scopes.iter().any(|scope| scope == "admin") {
+            permissions.add_role(UserRole::Admin);
+        }
+        if self.scopes.iter().any(|scope| scope == "moderate") {
+            permissions.add_role(UserRole::Moderator);
+        }
+        permissions
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> axum::extract::FromRequestParts<S> for Permissions
+where
+    S: Send + Sync,
+{
+    type Rejection = (axum::http::StatusCode, axum::Json<serde_json::Value>);
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(permissions) = parts.extensions.get::<Permissions>() {
+            return Ok(permissions.clone());
+        }
+        // Routes outside the permission layer still see token permissions
+        let claims =
+            <Claims as axum::extract::FromRequestParts<S>>::from_request_parts(parts, state)
+                .await?;
+        Ok(claims.permissions())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_names_round_trip() {
+        for permission in Permission::ALL {
+            assert_eq!(permission.as_str().parse::<Permission>(), Ok(permission));
+            assert_eq!(
+                serde_json::to_value(permission).unwrap(),
+                serde_json::json!(permission.as_str())
+            );
+        }
+        assert!("offense.delete".parse::<Permission>().is_err());
+    }
+
+    #[test]
+    fn test_role_permissions() {
+        let moderator = Permissions::from_roles([UserRole::Moderator]);
+        assert!(moderator.has(Permission::OffenseVerify));
+        assert!(moderator.has(Permission::CommunityModerate));
+        assert!(!moderator.has(Permission::SyncTrigger));
+        assert!(!moderator.has(Permission::UsersManage));
+
+        let admin = Permissions::from_roles([UserRole::Admin]);
+        assert!(Permission::ALL.into_iter().all(|p| admin.has(p)));
+
+        assert_eq!(Permissions::from_roles([UserRole::User]).iter().count(), 0);
+    }
+}
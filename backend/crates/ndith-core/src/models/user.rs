@@ -1,4 +1,5 @@
 use crate::models::oauth::{OAuthAccount, OAuthProviderType};
+use crate::models::webauthn::WebAuthnAssertionRequest;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -38,6 +39,11 @@ pub struct LoginRequest {
     pub email: String,
     pub password: String,
     pub totp_code: Option<String>,
+    /// Single-use recovery code, accepted in place of `totp_code`
+    pub recovery_code: Option<String>,
+    /// Security key assertion, accepted in place of `totp_code`
+    #[serde(default)]
+    pub webauthn: Option<WebAuthnAssertionRequest>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,6 +89,12 @@ pub struct TotpDisableRequest {
     pub totp_code: String,
 }
 
+/// Regenerating recovery codes requires a current TOTP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryCodesRegenerateRequest {
+    pub totp_code: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegisterRequest {
     pub email: String,
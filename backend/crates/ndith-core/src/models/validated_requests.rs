@@ -223,6 +223,8 @@ impl From<ValidatedLoginRequest> for crate::models::LoginRequest {
             email: validated.email,
             password: validated.password,
             totp_code: validated.totp_code,
+            recovery_code: None,
+            webauthn: None,
         }
     }
 }
@@ -0,0 +1,71 @@
+//! WebAuthn request types
+//!
+//! Credentials use the JSON shape browsers produce from
+//! `PublicKeyCredential.toJSON()`: binary fields are base64url strings.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Authenticator response to a registration ceremony
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorAttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub attestation_object: String,
+    #[serde(default)]
+    pub transports: Vec<String>,
+}
+
+/// Credential created by `navigator.credentials.create()`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationCredential {
+    pub id: String,
+    pub response: AuthenticatorAttestationResponse,
+}
+
+/// Authenticator response to an authentication ceremony
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorAssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    #[serde(default)]
+    pub user_handle: Option<String>,
+}
+
+/// Credential returned by `navigator.credentials.get()`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssertionCredential {
+    pub id: String,
+    pub response: AuthenticatorAssertionResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebAuthnRegisterFinishRequest {
+    pub challenge_id: Uuid,
+    /// Label for the credential, e.g. "YubiKey" or "Work laptop"
+    pub name: Option<String>,
+    pub credential: RegistrationCredential,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebAuthnLoginStartRequest {
+    /// Restrict the ceremony to this account's credentials. Leave out for
+    /// discoverable passkey login.
+    pub email: Option<String>,
+}
+
+/// Signed answer to an authentication challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebAuthnAssertionRequest {
+    pub challenge_id: Uuid,
+    pub credential: AssertionCredential,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebAuthnCredentialRenameRequest {
+    pub name: String,
+}
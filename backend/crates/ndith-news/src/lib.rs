@@ -30,11 +30,18 @@ pub use news_pipeline::{
     EmbeddingGenerator,
     EntityExtractor,
     EntityType,
+    // Evaluation
+    EvaluationReport,
+    EvaluationRunner,
+    // Evidence link health
+    EvidenceLinkChecker,
+    EvidenceLinkCheckerConfig,
     ExtractedEntity,
     // Ingestion
     FetchedArticle,
     HybridClassifier,
     HybridClassifierConfig,
+    LabeledCorpus,
     LlmBudgetConfig,
     NewsApiClient,
     NewsApiConfig,
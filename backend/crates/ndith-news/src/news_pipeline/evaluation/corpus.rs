@@ -0,0 +1,160 @@
+//! Labeled Article Corpus
+//!
+//! Versioned, hand-labeled articles used to evaluate offense classifiers.
+//! Fixture files live in `fixtures/classifier_eval/` and are embedded at
+//! compile time so evaluation runs without network or database access.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::news_pipeline::processing::{
+    EntityType, ExtractedEntity, OffenseCategory, OffenseSeverity,
+};
+
+/// The bundled corpus, bumped whenever labels or articles change
+const CORPUS_V1: &str = include_str!("../../../fixtures/classifier_eval/v1.json");
+
+/// A single labeled article
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabeledArticle {
+    /// Stable fixture identifier (e.g. "dv-001")
+    pub id: String,
+    /// Article headline
+    pub title: String,
+    /// Article body
+    pub text: String,
+    /// Artist names the entity extractor would surface for this article
+    #[serde(default)]
+    pub mentioned_artists: Vec<String>,
+    /// Offense category the article reports, `None` for clean articles
+    pub expected_category: Option<OffenseCategory>,
+    /// Severity a reviewer would assign
+    pub expected_severity: Option<OffenseSeverity>,
+    /// Artist the offense is attributed to
+    pub subject_artist: Option<String>,
+    /// True when the article refutes the offense (acquittal, dropped charges, ...)
+    #[serde(default)]
+    pub negated: bool,
+}
+
+impl LabeledArticle {
+    /// Category a correct classifier should confidently report, if any.
+    ///
+    /// Negated articles discuss an offense but should not produce one.
+    pub fn gold_category(&self) -> Option<&OffenseCategory> {
+        if self.negated {
+            None
+        } else {
+            self.expected_category.as_ref()
+        }
+    }
+
+    /// Build the extracted entities a classifier receives for this article.
+    ///
+    /// Entity ids are derived from the fixture id and artist name so that
+    /// repeated runs attribute predictions identically.
+    pub fn entities(&self) -> Vec<ExtractedEntity> {
+        let full_text = format!("{}\n\n{}", self.title, self.text);
+        self.mentioned_artists
+            .iter()
+            .map(|name| {
+                let start = full_text.find(name.as_str()).unwrap_or(0);
+                ExtractedEntity {
+                    id: self.entity_id(name),
+                    name: name.clone(),
+                    normalized_name: Some(name.to_lowercase()),
+                    entity_type: EntityType::Artist,
+                    confidence: 1.0,
+                    position: (start, start + name.len()),
+                    context: full_text.clone(),
+                    artist_id: None,
+                    convex_artist_id: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Deterministic entity id for an artist mentioned in this article
+    pub fn entity_id(&self, artist_name: &str) -> Uuid {
+        Uuid::new_v5(
+            &Uuid::NAMESPACE_OID,
+            format!("{}:{}", self.id, artist_name).as_bytes(),
+        )
+    }
+
+    /// Deterministic article id used when invoking classifiers
+    pub fn article_id(&self) -> Uuid {
+        Uuid::new_v5(&Uuid::NAMESPACE_OID, self.id.as_bytes())
+    }
+}
+
+/// A versioned set of labeled articles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabeledCorpus {
+    /// Corpus version; reports are only comparable within a version
+    pub version: String,
+    /// Free-form description of the corpus
+    #[serde(default)]
+    pub description: String,
+    /// Labeled articles
+    pub articles: Vec<LabeledArticle>,
+}
+
+impl LabeledCorpus {
+    /// Load the corpus bundled with the crate
+    pub fn bundled() -> Result<Self> {
+        Self::from_json(CORPUS_V1)
+    }
+
+    /// Parse a corpus from JSON, rejecting duplicate fixture ids
+    pub fn from_json(json: &str) -> Result<Self> {
+        let corpus: LabeledCorpus =
+            serde_json::from_str(json).context("Failed to parse labeled corpus")?;
+
+        let mut seen = std::collections::HashSet::new();
+        for article in &corpus.articles {
+            if !seen.insert(article.id.as_str()) {
+                anyhow::bail!("Duplicate article id in corpus: {}", article.id);
+            }
+        }
+
+        Ok(corpus)
+    }
+
+    /// Number of articles labeled with an offense that should be reported
+    pub fn positive_count(&self) -> usize {
+        self.articles
+            .iter()
+            .filter(|a| a.gold_category().is_some())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_corpus_parses() {
+        let corpus = LabeledCorpus::bundled().unwrap();
+        assert_eq!(corpus.version, "v1");
+        assert!(corpus.positive_count() > 0);
+        assert!(corpus.articles.iter().any(|a| a.negated));
+        assert!(corpus
+            .articles
+            .iter()
+            .any(|a| a.expected_category.is_none()));
+    }
+
+    #[test]
+    fn test_duplicate_ids_rejected() {
+        let json = r#"{"version": "t", "articles": [
+            {"id": "a", "title": "t", "text": "x", "expected_category": null,
+             "expected_severity": null, "subject_artist": null},
+            {"id": "a", "title": "t", "text": "y", "expected_category": null,
+             "expected_severity": null, "subject_artist": null}
+        ]}"#;
+        assert!(LabeledCorpus::from_json(json).is_err());
+    }
+}
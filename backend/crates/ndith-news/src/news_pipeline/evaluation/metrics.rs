@@ -0,0 +1,235 @@
+//! Evaluation Metrics
+//!
+//! Precision/recall/F1 per category, a confusion matrix over each article's
+//! primary label, and reliability bins for `confidence` calibration.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Label used in the confusion matrix for "no offense reported"
+pub const NO_OFFENSE_LABEL: &str = "none";
+
+/// Precision, recall and F1 for a single category
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CategoryMetrics {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+    /// Number of articles whose gold label is this category
+    pub support: usize,
+}
+
+impl CategoryMetrics {
+    /// Build metrics from raw counts.
+    ///
+    /// Precision and recall are 0.0 when their denominator is zero, so a
+    /// category with no samples and no predictions never reports a perfect
+    /// score.
+    pub fn from_counts(
+        true_positives: usize,
+        false_positives: usize,
+        false_negatives: usize,
+    ) -> Self {
+        let precision = ratio_or_zero(true_positives, true_positives + false_positives);
+        let recall = ratio_or_zero(true_positives, true_positives + false_negatives);
+        let f1 = if precision + recall > 0.0 {
+            2.0 * precision * recall / (precision + recall)
+        } else {
+            0.0
+        };
+
+        Self {
+            true_positives,
+            false_positives,
+            false_negatives,
+            precision,
+            recall,
+            f1,
+            support: true_positives + false_negatives,
+        }
+    }
+}
+
+fn ratio_or_zero(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Confusion matrix keyed by expected label, then predicted label
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfusionMatrix {
+    pub cells: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl ConfusionMatrix {
+    /// Record one article's expected and predicted primary label
+    pub fn record(&mut self, expected: &str, predicted: &str) {
+        *self
+            .cells
+            .entry(expected.to_string())
+            .or_default()
+            .entry(predicted.to_string())
+            .or_insert(0) += 1;
+    }
+
+    /// Count for an (expected, predicted) pair
+    pub fn get(&self, expected: &str, predicted: &str) -> usize {
+        self.cells
+            .get(expected)
+            .and_then(|row| row.get(predicted))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Fraction of articles on the diagonal
+    pub fn accuracy(&self) -> f64 {
+        let mut total = 0;
+        let mut correct = 0;
+        for (expected, row) in &self.cells {
+            for (predicted, count) in row {
+                total += count;
+                if expected == predicted {
+                    correct += count;
+                }
+            }
+        }
+        ratio_or_zero(correct, total)
+    }
+}
+
+/// One reliability bin of the calibration curve
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CalibrationBin {
+    /// Inclusive lower bound of the bin
+    pub lower: f64,
+    /// Exclusive upper bound of the bin (inclusive for the last bin)
+    pub upper: f64,
+    /// Predictions that fell into the bin
+    pub count: usize,
+    /// Mean predicted confidence
+    pub mean_confidence: f64,
+    /// Fraction of predictions whose category matched the gold label
+    pub accuracy: f64,
+}
+
+/// Calibration of predicted `confidence` against observed correctness
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CalibrationReport {
+    pub bins: Vec<CalibrationBin>,
+    /// Expected calibration error: count-weighted mean |accuracy - confidence|
+    pub expected_calibration_error: f64,
+    /// Mean squared error between confidence and correctness
+    pub brier_score: f64,
+}
+
+impl CalibrationReport {
+    /// Bucket `(confidence, correct)` pairs into `bin_count` equal-width bins
+    pub fn from_predictions(predictions: &[(f64, bool)], bin_count: usize) -> Self {
+        let bin_count = bin_count.max(1);
+        let width = 1.0 / bin_count as f64;
+
+        let mut sums = vec![(0usize, 0.0f64, 0usize); bin_count];
+        let mut brier_total = 0.0;
+        for &(confidence, correct) in predictions {
+            let confidence = confidence.clamp(0.0, 1.0);
+            let index = ((confidence / width) as usize).min(bin_count - 1);
+            let entry = &mut sums[index];
+            entry.0 += 1;
+            entry.1 += confidence;
+            if correct {
+                entry.2 += 1;
+            }
+            let outcome = if correct { 1.0 } else { 0.0 };
+            brier_total += (confidence - outcome).powi(2);
+        }
+
+        let total = predictions.len();
+        let mut ece = 0.0;
+        let bins = sums
+            .into_iter()
+            .enumerate()
+            .map(|(i, (count, confidence_sum, correct))| {
+                let (mean_confidence, accuracy) = if count > 0 {
+                    (confidence_sum / count as f64, correct as f64 / count as f64)
+                } else {
+                    (0.0, 0.0)
+                };
+                if total > 0 {
+                    ece += (count as f64 / total as f64) * (accuracy - mean_confidence).abs();
+                }
+                CalibrationBin {
+                    lower: i as f64 * width,
+                    upper: (i + 1) as f64 * width,
+                    count,
+                    mean_confidence,
+                    accuracy,
+                }
+            })
+            .collect();
+
+        Self {
+            bins,
+            expected_calibration_error: ece,
+            brier_score: if total > 0 {
+                brier_total / total as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_metrics_from_counts() {
+        let metrics = CategoryMetrics::from_counts(3, 1, 2);
+        assert!((metrics.precision - 0.75).abs() < 1e-9);
+        assert!((metrics.recall - 0.6).abs() < 1e-9);
+        assert!((metrics.f1 - 2.0 * 0.75 * 0.6 / 1.35).abs() < 1e-9);
+        assert_eq!(metrics.support, 5);
+
+        let empty = CategoryMetrics::from_counts(0, 0, 0);
+        assert_eq!(empty.precision, 0.0);
+        assert_eq!(empty.recall, 0.0);
+        assert_eq!(empty.f1, 0.0);
+
+        let never_predicted = CategoryMetrics::from_counts(0, 0, 4);
+        assert_eq!(never_predicted.f1, 0.0);
+    }
+
+    #[test]
+    fn test_confusion_matrix_accuracy() {
+        let mut matrix = ConfusionMatrix::default();
+        matrix.record("racism", "racism");
+        matrix.record("racism", "hate_speech");
+        matrix.record(NO_OFFENSE_LABEL, NO_OFFENSE_LABEL);
+        matrix.record(NO_OFFENSE_LABEL, NO_OFFENSE_LABEL);
+
+        assert_eq!(matrix.get("racism", "hate_speech"), 1);
+        assert_eq!(matrix.get(NO_OFFENSE_LABEL, NO_OFFENSE_LABEL), 2);
+        assert!((matrix.accuracy() - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_calibration_bins_and_ece() {
+        let predictions = [(0.95, true), (0.85, true), (0.9, false), (0.15, false)];
+        let report = CalibrationReport::from_predictions(&predictions, 10);
+
+        assert_eq!(report.bins.len(), 10);
+        assert_eq!(report.bins[9].count, 2);
+        assert_eq!(report.bins[8].count, 1);
+        assert_eq!(report.bins[1].count, 1);
+        // Bin 9: conf 0.925, acc 0.5; bin 8: conf 0.85, acc 1.0; bin 1: conf 0.15, acc 0.0
+        let expected_ece = 0.5 * 0.425 + 0.25 * 0.15 + 0.25 * 0.15;
+        assert!((report.expected_calibration_error - expected_ece).abs() < 1e-9);
+    }
+}
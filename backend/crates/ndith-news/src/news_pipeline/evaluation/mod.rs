@@ -0,0 +1,19 @@
+//! Classifier Evaluation
+//!
+//! Offline evaluation of offense classifiers against a versioned labeled corpus:
+//! - Labeled article fixtures with expected category, severity, subject and negation
+//! - Per-category precision/recall/F1 and a confusion matrix
+//! - Calibration of classifier `confidence`
+//! - Side-by-side comparison of keyword, LLM and hybrid classifiers
+//! - Baseline regression checks for tuning changes
+
+pub mod corpus;
+pub mod metrics;
+pub mod runner;
+
+pub use corpus::{LabeledArticle, LabeledCorpus};
+pub use metrics::{CalibrationBin, CalibrationReport, CategoryMetrics, ConfusionMatrix};
+pub use runner::{
+    ClassifierComparison, EvaluatedClassifier, EvaluationBaseline, EvaluationConfig,
+    EvaluationReport, EvaluationRunner, MetricRegression,
+};
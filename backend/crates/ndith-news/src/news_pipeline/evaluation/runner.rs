@@ -0,0 +1,658 @@
+//! Evaluation Runner
+//!
+//! Runs classifiers over a labeled corpus and reports per-category
+//! precision/recall/F1, a confusion matrix, calibration and attribution
+//! accuracy. Reports can be compared against a stored baseline so that
+//! regex or threshold tuning that degrades quality fails a test.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use uuid::Uuid;
+
+use super::corpus::{LabeledArticle, LabeledCorpus};
+use super::metrics::{CalibrationReport, CategoryMetrics, ConfusionMatrix, NO_OFFENSE_LABEL};
+use crate::news_pipeline::processing::llm_client::{LlmClassification, SubjectRole};
+use crate::news_pipeline::processing::{
+    ClaudeClient, ExtractedEntity, HybridClassifier, OffenseCategory, OffenseClassification,
+    OffenseClassifier, OffenseSeverity,
+};
+
+/// Baseline for the keyword classifier on corpus v1. Regenerate with
+/// `cargo test -p ndith-news regenerate_keyword_baseline -- --ignored`.
+const KEYWORD_BASELINE_V1: &str =
+    include_str!("../../../fixtures/classifier_eval/baseline_keyword_v1.json");
+
+/// A classifier that can be scored against labeled articles
+#[async_trait]
+pub trait EvaluatedClassifier: Send + Sync {
+    /// Short name used in reports (keyword, llm, hybrid)
+    fn name(&self) -> &str;
+
+    /// Classify a labeled article using its text and mentioned artists
+    async fn classify_labeled(
+        &self,
+        article: &LabeledArticle,
+    ) -> Result<Vec<OffenseClassification>>;
+}
+
+#[async_trait]
+impl EvaluatedClassifier for OffenseClassifier {
+    fn name(&self) -> &str {
+        "keyword"
+    }
+
+    async fn classify_labeled(
+        &self,
+        article: &LabeledArticle,
+    ) -> Result<Vec<OffenseClassification>> {
+        self.classify(
+            article.article_id(),
+            &article.text,
+            Some(&article.title),
+            &article.entities(),
+        )
+    }
+}
+
+#[async_trait]
+impl EvaluatedClassifier for HybridClassifier {
+    fn name(&self) -> &str {
+        "hybrid"
+    }
+
+    async fn classify_labeled(
+        &self,
+        article: &LabeledArticle,
+    ) -> Result<Vec<OffenseClassification>> {
+        self.classify(
+            article.article_id(),
+            &article.text,
+            Some(&article.title),
+            &article.entities(),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl EvaluatedClassifier for ClaudeClient {
+    fn name(&self) -> &str {
+        "llm"
+    }
+
+    async fn classify_labeled(
+        &self,
+        article: &LabeledArticle,
+    ) -> Result<Vec<OffenseClassification>> {
+        let llm = self
+            .classify_article(&article.text, &article.title, &article.mentioned_artists)
+            .await?;
+        Ok(llm_to_classifications(
+            article.article_id(),
+            &llm,
+            &article.entities(),
+        ))
+    }
+}
+
+/// Convert an LLM classification into the keyword classifier's output shape.
+///
+/// Only perpetrator (or unclear, flagged for review) subject roles produce
+/// offenses. A category is attributed to an entity when the entity's name
+/// appears in the evidence snippet.
+pub fn llm_to_classifications(
+    article_id: Uuid,
+    llm: &LlmClassification,
+    entities: &[ExtractedEntity],
+) -> Vec<OffenseClassification> {
+    let unclear = match llm.subject_role {
+        SubjectRole::Perpetrator => false,
+        SubjectRole::Unclear => true,
+        SubjectRole::Victim | SubjectRole::Witness | SubjectRole::Unrelated => return Vec::new(),
+    };
+
+    llm.categories
+        .iter()
+        .filter_map(|result| {
+            let category: OffenseCategory = result.category.parse().ok()?;
+            let severity: OffenseSeverity =
+                result.severity.parse().unwrap_or(OffenseSeverity::Medium);
+            let entity = entities
+                .iter()
+                .find(|e| result.evidence_snippet.contains(&e.name));
+
+            Some(OffenseClassification {
+                id: Uuid::new_v4(),
+                article_id,
+                entity_id: entity.map(|e| e.id),
+                artist_id: entity.and_then(|e| e.artist_id),
+                convex_artist_id: entity.and_then(|e| e.convex_artist_id.clone()),
+                category,
+                severity,
+                confidence: result.confidence.clamp(0.0, 1.0),
+                music_context_score: 1.0,
+                matched_keywords: Vec::new(),
+                context: result.evidence_snippet.clone(),
+                needs_review: unclear,
+                classification_source: Some("llm".to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Evaluation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationConfig {
+    /// Confidence at which a prediction counts as reported. Defaults to the
+    /// `OffenseCreator` auto-creation threshold, since that is the point at
+    /// which a classification turns into an offense record.
+    pub decision_threshold: f64,
+    /// Number of equal-width calibration bins
+    pub calibration_bins: usize,
+}
+
+impl Default for EvaluationConfig {
+    fn default() -> Self {
+        Self {
+            decision_threshold: 0.7,
+            calibration_bins: 10,
+        }
+    }
+}
+
+/// Full evaluation report for one classifier on one corpus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationReport {
+    pub classifier: String,
+    pub corpus_version: String,
+    pub decision_threshold: f64,
+    pub articles_evaluated: usize,
+    /// Fixture ids the classifier failed on (excluded from metrics)
+    pub failed_articles: Vec<String>,
+    /// Per-category metrics keyed by category name
+    pub per_category: BTreeMap<String, CategoryMetrics>,
+    /// Unweighted mean F1 over categories with support or predictions
+    pub macro_f1: f64,
+    /// Metrics pooled across all categories
+    pub micro: CategoryMetrics,
+    /// Expected primary label vs predicted primary label
+    pub confusion: ConfusionMatrix,
+    pub calibration: CalibrationReport,
+    /// Correct severity among correctly reported offenses
+    pub severity_accuracy: Option<f64>,
+    /// Correct subject artist among correctly reported offenses
+    pub subject_accuracy: Option<f64>,
+    /// Negated articles that were correctly not reported
+    pub negation_accuracy: Option<f64>,
+}
+
+impl EvaluationReport {
+    /// Compare against a baseline, returning every metric that dropped by more
+    /// than `tolerance`.
+    pub fn regressions(
+        &self,
+        baseline: &EvaluationBaseline,
+        tolerance: f64,
+    ) -> Result<Vec<MetricRegression>> {
+        if baseline.corpus_version != self.corpus_version {
+            anyhow::bail!(
+                "Baseline corpus version {} does not match report corpus version {}",
+                baseline.corpus_version,
+                self.corpus_version
+            );
+        }
+
+        let mut regressions = Vec::new();
+        let mut check = |metric: String, baseline: f64, current: f64| {
+            if baseline - current > tolerance {
+                regressions.push(MetricRegression {
+                    metric,
+                    baseline,
+                    current,
+                });
+            }
+        };
+
+        check("macro_f1".to_string(), baseline.macro_f1, self.macro_f1);
+        check("micro_f1".to_string(), baseline.micro_f1, self.micro.f1);
+        for (category, baseline_f1) in &baseline.per_category_f1 {
+            let current = self.per_category.get(category).map(|m| m.f1).unwrap_or(0.0);
+            check(format!("{}.f1", category), *baseline_f1, current);
+        }
+        if let (Some(baseline_negation), Some(current)) =
+            (baseline.negation_accuracy, self.negation_accuracy)
+        {
+            check("negation_accuracy".to_string(), baseline_negation, current);
+        }
+
+        Ok(regressions)
+    }
+
+    /// Human-readable summary table
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "classifier={} corpus={} articles={} threshold={:.2}\n",
+            self.classifier, self.corpus_version, self.articles_evaluated, self.decision_threshold
+        );
+        out.push_str(&format!(
+            "macro_f1={:.3} micro_p={:.3} micro_r={:.3} micro_f1={:.3} ece={:.3}\n",
+            self.macro_f1,
+            self.micro.precision,
+            self.micro.recall,
+            self.micro.f1,
+            self.calibration.expected_calibration_error
+        ));
+        out.push_str(&format!(
+            "{:<20} {:>6} {:>6} {:>6} {:>4}\n",
+            "category", "prec", "recall", "f1", "n"
+        ));
+        for (category, m) in &self.per_category {
+            out.push_str(&format!(
+                "{:<20} {:>6.3} {:>6.3} {:>6.3} {:>4}\n",
+                category, m.precision, m.recall, m.f1, m.support
+            ));
+        }
+        out
+    }
+}
+
+/// A metric that dropped below its baseline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetricRegression {
+    pub metric: String,
+    pub baseline: f64,
+    pub current: f64,
+}
+
+/// Stored metrics a classifier must not fall below
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationBaseline {
+    pub classifier: String,
+    pub corpus_version: String,
+    pub macro_f1: f64,
+    pub micro_f1: f64,
+    pub per_category_f1: BTreeMap<String, f64>,
+    pub negation_accuracy: Option<f64>,
+}
+
+impl EvaluationBaseline {
+    /// Baseline for the keyword classifier on the bundled corpus
+    pub fn bundled_keyword() -> Result<Self> {
+        serde_json::from_str(KEYWORD_BASELINE_V1).context("Failed to parse keyword baseline")
+    }
+
+    /// Snapshot a report as the new baseline
+    pub fn from_report(report: &EvaluationReport) -> Self {
+        Self {
+            classifier: report.classifier.clone(),
+            corpus_version: report.corpus_version.clone(),
+            macro_f1: report.macro_f1,
+            micro_f1: report.micro.f1,
+            per_category_f1: report
+                .per_category
+                .iter()
+                .map(|(category, m)| (category.clone(), m.f1))
+                .collect(),
+            negation_accuracy: report.negation_accuracy,
+        }
+    }
+}
+
+/// Side-by-side reports for several classifiers on the same corpus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassifierComparison {
+    pub corpus_version: String,
+    pub reports: Vec<EvaluationReport>,
+}
+
+impl ClassifierComparison {
+    /// Report with the highest macro F1
+    pub fn best_by_macro_f1(&self) -> Option<&EvaluationReport> {
+        self.reports
+            .iter()
+            .max_by(|a, b| a.macro_f1.total_cmp(&b.macro_f1))
+    }
+}
+
+/// Runs classifiers over a labeled corpus
+pub struct EvaluationRunner {
+    config: EvaluationConfig,
+}
+
+#[derive(Default)]
+struct Tally {
+    counts: BTreeMap<String, (usize, usize, usize)>,
+    confusion: ConfusionMatrix,
+    calibration_points: Vec<(f64, bool)>,
+    severity: (usize, usize),
+    subject: (usize, usize),
+    negation: (usize, usize),
+}
+
+impl EvaluationRunner {
+    pub fn new(config: EvaluationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Evaluate a single classifier against the corpus
+    pub async fn evaluate(
+        &self,
+        corpus: &LabeledCorpus,
+        classifier: &dyn EvaluatedClassifier,
+    ) -> EvaluationReport {
+        let mut tally = Tally::default();
+        let mut failed_articles = Vec::new();
+        let mut evaluated = 0;
+
+        for article in &corpus.articles {
+            match classifier.classify_labeled(article).await {
+                Ok(predictions) => {
+                    evaluated += 1;
+                    self.score_article(article, &predictions, &mut tally);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        classifier = classifier.name(),
+                        article = %article.id,
+                        error = %e,
+                        "Classifier failed on labeled article"
+                    );
+                    failed_articles.push(article.id.clone());
+                }
+            }
+        }
+
+        let per_category: BTreeMap<String, CategoryMetrics> = tally
+            .counts
+            .iter()
+            .map(|(category, &(tp, fp, fn_))| {
+                (category.clone(), CategoryMetrics::from_counts(tp, fp, fn_))
+            })
+            .collect();
+
+        let macro_f1 = if per_category.is_empty() {
+            0.0
+        } else {
+            per_category.values().map(|m| m.f1).sum::<f64>() / per_category.len() as f64
+        };
+
+        let (tp, fp, fn_) = tally
+            .counts
+            .values()
+            .fold((0, 0, 0), |acc, c| (acc.0 + c.0, acc.1 + c.1, acc.2 + c.2));
+
+        EvaluationReport {
+            classifier: classifier.name().to_string(),
+            corpus_version: corpus.version.clone(),
+            decision_threshold: self.config.decision_threshold,
+            articles_evaluated: evaluated,
+            failed_articles,
+            per_category,
+            macro_f1,
+            micro: CategoryMetrics::from_counts(tp, fp, fn_),
+            confusion: tally.confusion,
+            calibration: CalibrationReport::from_predictions(
+                &tally.calibration_points,
+                self.config.calibration_bins,
+            ),
+            severity_accuracy: fraction(tally.severity),
+            subject_accuracy: fraction(tally.subject),
+            negation_accuracy: fraction(tally.negation),
+        }
+    }
+
+    /// Evaluate several classifiers on the same corpus
+    pub async fn compare(
+        &self,
+        corpus: &LabeledCorpus,
+        classifiers: &[&dyn EvaluatedClassifier],
+    ) -> ClassifierComparison {
+        let mut reports = Vec::with_capacity(classifiers.len());
+        for classifier in classifiers {
+            reports.push(self.evaluate(corpus, *classifier).await);
+        }
+        ClassifierComparison {
+            corpus_version: corpus.version.clone(),
+            reports,
+        }
+    }
+
+    fn score_article(
+        &self,
+        article: &LabeledArticle,
+        predictions: &[OffenseClassification],
+        tally: &mut Tally,
+    ) {
+        // Collapse per-entity duplicates to the most confident prediction per category
+        let mut best: HashMap<String, &OffenseClassification> = HashMap::new();
+        for prediction in predictions {
+            let key = prediction.category.to_string();
+            match best.get(&key) {
+                Some(existing) if existing.confidence >= prediction.confidence => {}
+                _ => {
+                    best.insert(key, prediction);
+                }
+            }
+        }
+
+        let gold = article.gold_category().map(|c| c.to_string());
+
+        for (category, prediction) in &best {
+            tally
+                .calibration_points
+                .push((prediction.confidence, gold.as_deref() == Some(category)));
+        }
+
+        let reported: BTreeSet<String> = best
+            .iter()
+            .filter(|(_, p)| p.confidence >= self.config.decision_threshold)
+            .map(|(category, _)| category.clone())
+            .collect();
+
+        for category in &reported {
+            let entry = tally.counts.entry(category.clone()).or_default();
+            if gold.as_ref() == Some(category) {
+                entry.0 += 1;
+            } else {
+                entry.1 += 1;
+            }
+        }
+        if let Some(gold_category) = &gold {
+            if !reported.contains(gold_category) {
+                tally.counts.entry(gold_category.clone()).or_default().2 += 1;
+            }
+        }
+
+        let predicted_primary = best
+            .iter()
+            .filter(|(category, _)| reported.contains(*category))
+            .max_by(|a, b| a.1.confidence.total_cmp(&b.1.confidence))
+            .map(|(category, _)| category.as_str())
+            .unwrap_or(NO_OFFENSE_LABEL);
+        tally.confusion.record(
+            gold.as_deref().unwrap_or(NO_OFFENSE_LABEL),
+            predicted_primary,
+        );
+
+        if let Some(gold_category) = &gold {
+            if let Some(prediction) = best
+                .get(gold_category)
+                .filter(|_| reported.contains(gold_category))
+            {
+                if let Some(expected_severity) = &article.expected_severity {
+                    tally.severity.1 += 1;
+                    if &prediction.severity == expected_severity {
+                        tally.severity.0 += 1;
+                    }
+                }
+                if let Some(subject) = &article.subject_artist {
+                    let subject_id = article.entity_id(subject);
+                    tally.subject.1 += 1;
+                    let attributed = predictions.iter().any(|p| {
+                        p.category.to_string() == *gold_category
+                            && p.confidence >= self.config.decision_threshold
+                            && p.entity_id == Some(subject_id)
+                    });
+                    if attributed {
+                        tally.subject.0 += 1;
+                    }
+                }
+            }
+        }
+
+        if article.negated {
+            if let Some(category) = &article.expected_category {
+                tally.negation.1 += 1;
+                if !reported.contains(&category.to_string()) {
+                    tally.negation.0 += 1;
+                }
+            }
+        }
+    }
+}
+
+impl Default for EvaluationRunner {
+    fn default() -> Self {
+        Self::new(EvaluationConfig::default())
+    }
+}
+
+fn fraction((correct, total): (usize, usize)) -> Option<f64> {
+    if total == 0 {
+        None
+    } else {
+        Some(correct as f64 / total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::news_pipeline::processing::llm_client::LlmCategoryResult;
+    use crate::news_pipeline::processing::{HybridClassifierConfig, OffenseClassifierConfig};
+
+    /// Allowed drop in any baseline metric before the test fails
+    const REGRESSION_TOLERANCE: f64 = 0.02;
+
+    #[tokio::test]
+    async fn test_keyword_classifier_does_not_regress() {
+        let corpus = LabeledCorpus::bundled().unwrap();
+        let classifier = OffenseClassifier::new(OffenseClassifierConfig::default());
+        let report = EvaluationRunner::default()
+            .evaluate(&corpus, &classifier)
+            .await;
+
+        let baseline = EvaluationBaseline::bundled_keyword().unwrap();
+        let regressions = report.regressions(&baseline, REGRESSION_TOLERANCE).unwrap();
+        assert!(
+            regressions.is_empty(),
+            "Keyword classifier regressed: {:?}\n{}",
+            regressions,
+            report.summary()
+        );
+        assert!(report.failed_articles.is_empty());
+        assert_eq!(report.articles_evaluated, corpus.articles.len());
+    }
+
+    /// Rewrites the bundled keyword baseline from a fresh run. Run it after
+    /// an intentional classifier or corpus change and commit the result.
+    #[tokio::test]
+    #[ignore]
+    async fn regenerate_keyword_baseline() {
+        let corpus = LabeledCorpus::bundled().unwrap();
+        let classifier = OffenseClassifier::new(OffenseClassifierConfig::default());
+        let report = EvaluationRunner::default()
+            .evaluate(&corpus, &classifier)
+            .await;
+
+        let baseline = EvaluationBaseline::from_report(&report);
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/fixtures/classifier_eval/baseline_keyword_v1.json"
+        );
+        let json = serde_json::to_string_pretty(&baseline).unwrap();
+        std::fs::write(path, json + "\n").unwrap();
+        println!("{}", report.summary());
+    }
+
+    #[tokio::test]
+    async fn test_compare_keyword_and_hybrid() {
+        let corpus = LabeledCorpus::bundled().unwrap();
+        let keyword = OffenseClassifier::new(OffenseClassifierConfig::default());
+        let hybrid = HybridClassifier::new(
+            OffenseClassifierConfig::default(),
+            HybridClassifierConfig::default(),
+        );
+
+        let comparison = EvaluationRunner::default()
+            .compare(&corpus, &[&keyword, &hybrid])
+            .await;
+
+        assert_eq!(comparison.reports.len(), 2);
+        // Hybrid is a pass-through to the keyword classifier today
+        assert_eq!(
+            comparison.reports[0].per_category,
+            comparison.reports[1].per_category
+        );
+        assert!(comparison.best_by_macro_f1().is_some());
+    }
+
+    #[test]
+    fn test_regression_detected_against_stricter_baseline() {
+        let report = EvaluationReport {
+            classifier: "keyword".to_string(),
+            corpus_version: "v1".to_string(),
+            decision_threshold: 0.7,
+            articles_evaluated: 1,
+            failed_articles: Vec::new(),
+            per_category: BTreeMap::from([(
+                "racism".to_string(),
+                CategoryMetrics::from_counts(1, 1, 0),
+            )]),
+            macro_f1: 0.667,
+            micro: CategoryMetrics::from_counts(1, 1, 0),
+            confusion: ConfusionMatrix::default(),
+            calibration: CalibrationReport::default(),
+            severity_accuracy: None,
+            subject_accuracy: None,
+            negation_accuracy: None,
+        };
+        let mut baseline = EvaluationBaseline::from_report(&report);
+        assert!(report.regressions(&baseline, 0.0).unwrap().is_empty());
+
+        baseline.per_category_f1.insert("racism".to_string(), 0.9);
+        let regressions = report.regressions(&baseline, 0.05).unwrap();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].metric, "racism.f1");
+
+        baseline.corpus_version = "v0".to_string();
+        assert!(report.regressions(&baseline, 0.05).is_err());
+    }
+
+    #[test]
+    fn test_llm_victim_role_produces_no_offense() {
+        let llm = LlmClassification {
+            categories: vec![LlmCategoryResult {
+                category: "violent_crimes".to_string(),
+                confidence: 0.9,
+                severity: "high".to_string(),
+                evidence_snippet: "Alder Price was attacked".to_string(),
+            }],
+            subject_role: SubjectRole::Victim,
+            temporal_info: None,
+            reasoning: String::new(),
+        };
+        assert!(llm_to_classifications(Uuid::new_v4(), &llm, &[]).is_empty());
+
+        let perpetrator = LlmClassification {
+            subject_role: SubjectRole::Perpetrator,
+            ..llm
+        };
+        let results = llm_to_classifications(Uuid::new_v4(), &perpetrator, &[]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].category, OffenseCategory::ViolentCrimes);
+        assert_eq!(results[0].severity, OffenseSeverity::High);
+    }
+}
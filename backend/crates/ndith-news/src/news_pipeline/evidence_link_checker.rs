@@ -0,0 +1,772 @@
+//! Evidence Link Checker
+//!
+//! Background job that periodically re-fetches every offense evidence URL,
+//! recording its HTTP status, redirect chain and a hash of the extracted
+//! article text. Dead links and links whose content changed are flagged for
+//! re-review, and each distinct version of the page is kept as a local text
+//! snapshot (extracted with `WebScraper`) so excerpts stay verifiable.
+//!
+//! Link health feeds into `ResearchQualityScorer` and the trouble score's
+//! evidence component (`calculate_evidence_score`).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::ingestion::{WebScraper, WebScraperConfig};
+use super::orchestrator::ScheduledPipelineHandle;
+
+/// Evidence link checker configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceLinkCheckerConfig {
+    /// Evidence checked per run
+    pub batch_size: i64,
+    /// Minimum time between checks of the same link (hours)
+    pub recheck_interval_hours: i64,
+    /// Maximum redirects followed before giving up
+    pub max_redirects: usize,
+    /// Consecutive failed checks before a link is considered dead
+    pub dead_after_failures: i32,
+    /// Delay between requests (milliseconds)
+    pub request_delay_ms: u64,
+    /// Request timeout in seconds
+    pub timeout_seconds: u64,
+    /// Largest response body read before the fetch is abandoned (bytes)
+    pub max_body_bytes: usize,
+    /// Look up an Internet Archive copy for evidence without an `archived_url`
+    pub lookup_wayback: bool,
+    /// Scraper used to extract snapshot text
+    pub scraper: WebScraperConfig,
+}
+
+impl Default for EvidenceLinkCheckerConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            recheck_interval_hours: 24 * 7,
+            max_redirects: 5,
+            dead_after_failures: 3,
+            request_delay_ms: 1000,
+            timeout_seconds: 30,
+            max_body_bytes: 5 * 1024 * 1024,
+            lookup_wayback: true,
+            scraper: WebScraperConfig::default(),
+        }
+    }
+}
+
+/// Health of an evidence link after a check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkStatus {
+    /// Reachable, content unchanged
+    Ok,
+    /// Reachable via a redirect to a different URL, content unchanged
+    Redirected,
+    /// Reachable, but the extracted text differs from the last check
+    Changed,
+    /// Gone (404/410) or failing repeatedly
+    Dead,
+    /// Transient failure (network error, 5xx, rate limiting)
+    Error,
+}
+
+impl LinkStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Redirected => "redirected",
+            Self::Changed => "changed",
+            Self::Dead => "dead",
+            Self::Error => "error",
+        }
+    }
+
+    /// Whether evidence with this status should be re-reviewed
+    pub fn needs_rereview(&self) -> bool {
+        matches!(self, Self::Dead | Self::Changed)
+    }
+}
+
+impl std::fmt::Display for LinkStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Outcome of fetching a single URL
+#[derive(Debug, Clone)]
+pub struct FetchOutcome {
+    /// Final HTTP status (None on network error)
+    pub http_status: Option<u16>,
+    /// URLs visited after the original, in order
+    pub redirect_chain: Vec<String>,
+    /// URL the content was served from
+    pub final_url: String,
+    /// Response body for successful responses
+    pub body: Option<String>,
+    /// Error message for failed fetches
+    pub error: Option<String>,
+}
+
+/// Result of checking one evidence link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkCheckResult {
+    pub evidence_id: Uuid,
+    pub status: LinkStatus,
+    pub http_status: Option<u16>,
+    pub final_url: String,
+    pub redirect_chain: Vec<String>,
+    pub content_hash: Option<String>,
+    pub snapshot_stored: bool,
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Summary of a checker run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinkCheckStats {
+    pub checked: usize,
+    pub ok: usize,
+    pub redirected: usize,
+    pub changed: usize,
+    pub dead: usize,
+    pub errors: usize,
+    pub snapshots_stored: usize,
+}
+
+impl LinkCheckStats {
+    fn record(&mut self, result: &LinkCheckResult) {
+        self.checked += 1;
+        match result.status {
+            LinkStatus::Ok => self.ok += 1,
+            LinkStatus::Redirected => self.redirected += 1,
+            LinkStatus::Changed => self.changed += 1,
+            LinkStatus::Dead => self.dead += 1,
+            LinkStatus::Error => self.errors += 1,
+        }
+        if result.snapshot_stored {
+            self.snapshots_stored += 1;
+        }
+    }
+}
+
+/// Evidence row due for a check
+#[derive(Debug, sqlx::FromRow)]
+struct EvidenceLink {
+    id: Uuid,
+    url: String,
+    content_hash: Option<String>,
+    consecutive_failures: i32,
+    archived_url: Option<String>,
+}
+
+/// Classify a fetch against the previous check.
+///
+/// 404 and 410 are dead immediately; other failures only become dead after
+/// `dead_after_failures` consecutive failed checks. Content changes are only
+/// detected when both the previous and current checks produced a hash.
+pub fn classify_link(
+    outcome: &FetchOutcome,
+    original_url: &str,
+    previous_hash: Option<&str>,
+    content_hash: Option<&str>,
+    consecutive_failures: i32,
+    dead_after_failures: i32,
+) -> LinkStatus {
+    let succeeded = outcome
+        .http_status
+        .is_some_and(|s| (200..300).contains(&s));
+
+    if !succeeded {
+        let gone = matches!(outcome.http_status, Some(404) | Some(410));
+        return if gone || consecutive_failures + 1 >= dead_after_failures {
+            LinkStatus::Dead
+        } else {
+            LinkStatus::Error
+        };
+    }
+
+    if let (Some(previous), Some(current)) = (previous_hash, content_hash) {
+        if previous != current {
+            return LinkStatus::Changed;
+        }
+    }
+
+    if outcome.final_url != original_url {
+        LinkStatus::Redirected
+    } else {
+        LinkStatus::Ok
+    }
+}
+
+/// SHA-256 of whitespace-normalized text, hex encoded
+pub fn content_hash(text: &str) -> String {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{:x}", Sha256::digest(normalized.as_bytes()))
+}
+
+/// Whether an address is on the public internet.
+///
+/// Loopback, private, link-local, unique-local, shared (CGNAT), multicast
+/// and unspecified addresses are not, including IPv4-mapped IPv6 forms.
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_unspecified()
+                || v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_address(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_unspecified()
+                || v6.is_loopback()
+                || v6.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// Reject a fetch target that is not http(s) or resolves to a non-public address
+pub async fn check_fetch_target(url: &str) -> std::result::Result<(), String> {
+    let url = Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Unsupported URL scheme: {}", url.scheme()));
+    }
+    let host = url.host_str().ok_or("URL has no host")?;
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    let addresses: Vec<SocketAddr> = match host.trim_matches(['[', ']']).parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+            .collect(),
+    };
+
+    if addresses.is_empty() {
+        return Err(format!("{} did not resolve", host));
+    }
+    match addresses.iter().find(|addr| !is_public_address(addr.ip())) {
+        Some(addr) => Err(format!(
+            "{} resolves to non-public address {}",
+            host,
+            addr.ip()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// DNS resolver that refuses hosts resolving to non-public addresses, so a
+/// name cannot be re-pointed at an internal address between the per-hop
+/// check and the connection.
+struct PublicAddressResolver;
+
+impl reqwest::dns::Resolve for PublicAddressResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if let Some(addr) = addresses.iter().find(|addr| !is_public_address(addr.ip())) {
+                return Err(
+                    format!("{} resolves to non-public address {}", host, addr.ip()).into(),
+                );
+            }
+            Ok(Box::new(addresses.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// Read a response body, giving up once it exceeds `limit` bytes
+async fn read_body_capped(
+    mut response: reqwest::Response,
+    limit: usize,
+) -> std::result::Result<String, String> {
+    if response
+        .content_length()
+        .is_some_and(|len| len > limit as u64)
+    {
+        return Err(format!("Response body exceeds {} bytes", limit));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        if body.len() + chunk.len() > limit {
+            return Err(format!("Response body exceeds {} bytes", limit));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Checks evidence links and archives snapshots
+pub struct EvidenceLinkChecker {
+    config: EvidenceLinkCheckerConfig,
+    db_pool: PgPool,
+    client: Client,
+    scraper: WebScraper,
+}
+
+impl EvidenceLinkChecker {
+    /// Create a new link checker
+    pub fn new(config: EvidenceLinkCheckerConfig, db_pool: PgPool) -> Self {
+        let client = Client::builder()
+            .user_agent(&config.scraper.user_agent)
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(PublicAddressResolver))
+            .build()
+            .expect("Failed to create HTTP client");
+        let scraper = WebScraper::new(config.scraper.clone());
+
+        Self {
+            config,
+            db_pool,
+            client,
+            scraper,
+        }
+    }
+
+    /// Check a batch of evidence links that are due
+    pub async fn run_once(&self) -> Result<LinkCheckStats> {
+        let due: Vec<EvidenceLink> = sqlx::query_as(
+            r#"
+            SELECT id, url, content_hash, consecutive_failures, archived_url
+            FROM offense_evidence
+            WHERE last_checked_at IS NULL
+               OR last_checked_at < NOW() - make_interval(hours => $1)
+            ORDER BY last_checked_at NULLS FIRST
+            LIMIT $2
+            "#,
+        )
+        .bind(self.config.recheck_interval_hours as i32)
+        .bind(self.config.batch_size)
+        .fetch_all(&self.db_pool)
+        .await
+        .context("Failed to load evidence links due for checking")?;
+
+        let mut stats = LinkCheckStats::default();
+
+        for (index, link) in due.iter().enumerate() {
+            if index > 0 {
+                tokio::time::sleep(Duration::from_millis(self.config.request_delay_ms)).await;
+            }
+
+            match self.check_link(link).await {
+                Ok(result) => {
+                    if result.status.needs_rereview() {
+                        tracing::info!(
+                            evidence_id = %link.id,
+                            url = %link.url,
+                            status = %result.status,
+                            http_status = ?result.http_status,
+                            "Evidence link flagged for re-review"
+                        );
+                    }
+                    stats.record(&result);
+                }
+                Err(e) => {
+                    tracing::warn!(evidence_id = %link.id, error = %e, "Evidence link check failed");
+                }
+            }
+        }
+
+        tracing::info!(
+            checked = stats.checked,
+            dead = stats.dead,
+            changed = stats.changed,
+            snapshots = stats.snapshots_stored,
+            "Evidence link check complete"
+        );
+
+        Ok(stats)
+    }
+
+    /// Check a single evidence link and persist the result
+    async fn check_link(&self, link: &EvidenceLink) -> Result<LinkCheckResult> {
+        let outcome = self.fetch(&link.url).await;
+        let snapshot = outcome
+            .body
+            .as_deref()
+            .and_then(|html| self.scraper.extract(&outcome.final_url, html).ok())
+            .and_then(|scraped| {
+                let text = scraped.content?;
+                Some((scraped.title, text))
+            });
+        let hash = snapshot.as_ref().map(|(_, text)| content_hash(text));
+
+        let status = classify_link(
+            &outcome,
+            &link.url,
+            link.content_hash.as_deref(),
+            hash.as_deref(),
+            link.consecutive_failures,
+            self.config.dead_after_failures,
+        );
+        let checked_at = Utc::now();
+
+        let mut tx = self.db_pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO evidence_link_checks (
+                evidence_id, link_status, http_status, final_url, redirect_chain,
+                content_hash, error, checked_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(link.id)
+        .bind(status.as_str())
+        .bind(outcome.http_status.map(i32::from))
+        .bind(&outcome.final_url)
+        .bind(serde_json::json!(outcome.redirect_chain))
+        .bind(&hash)
+        .bind(&outcome.error)
+        .bind(checked_at)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to record link check")?;
+
+        let snapshot_stored = match (&snapshot, &hash) {
+            (Some((title, text)), Some(hash)) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO evidence_snapshots (evidence_id, content_hash, url, title, text_content)
+                    VALUES ($1, $2, $3, $4, $5)
+                    ON CONFLICT (evidence_id, content_hash) DO NOTHING
+                    "#,
+                )
+                .bind(link.id)
+                .bind(hash)
+                .bind(&outcome.final_url)
+                .bind(title)
+                .bind(text)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to store evidence snapshot")?
+                .rows_affected()
+                    > 0
+            }
+            _ => false,
+        };
+
+        let failed = matches!(status, LinkStatus::Dead | LinkStatus::Error);
+        sqlx::query(
+            r#"
+            UPDATE offense_evidence
+            SET link_status = $2,
+                http_status = $3,
+                final_url = $4,
+                content_hash = COALESCE($5, content_hash),
+                last_checked_at = $6,
+                consecutive_failures = CASE WHEN $7 THEN consecutive_failures + 1 ELSE 0 END,
+                needs_rereview = needs_rereview OR $8
+            WHERE id = $1
+            "#,
+        )
+        .bind(link.id)
+        .bind(status.as_str())
+        .bind(outcome.http_status.map(i32::from))
+        .bind(&outcome.final_url)
+        .bind(&hash)
+        .bind(checked_at)
+        .bind(failed)
+        .bind(status.needs_rereview())
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update evidence link status")?;
+
+        tx.commit().await?;
+
+        if link.archived_url.is_none() && self.config.lookup_wayback {
+            self.fill_archived_url(link).await;
+        }
+
+        Ok(LinkCheckResult {
+            evidence_id: link.id,
+            status,
+            http_status: outcome.http_status,
+            final_url: outcome.final_url,
+            redirect_chain: outcome.redirect_chain,
+            content_hash: hash,
+            snapshot_stored,
+            error: outcome.error,
+            checked_at,
+        })
+    }
+
+    /// Fetch a URL, following redirects manually so the chain is recorded.
+    ///
+    /// Every hop must pass `check_fetch_target`, and the body is read only
+    /// up to `max_body_bytes`.
+    pub async fn fetch(&self, url: &str) -> FetchOutcome {
+        let mut current = url.to_string();
+        let mut redirect_chain = Vec::new();
+
+        loop {
+            if let Err(e) = check_fetch_target(&current).await {
+                return FetchOutcome {
+                    http_status: None,
+                    redirect_chain,
+                    final_url: current,
+                    body: None,
+                    error: Some(e),
+                };
+            }
+
+            let response = match self.client.get(&current).send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    return FetchOutcome {
+                        http_status: None,
+                        redirect_chain,
+                        final_url: current,
+                        body: None,
+                        error: Some(e.to_string()),
+                    }
+                }
+            };
+
+            let status = response.status();
+            if status.is_redirection() {
+                let next = response
+                    .headers()
+                    .get(reqwest::header::LOCATION)
+                    .and_then(|h| h.to_str().ok())
+                    .and_then(|location| Url::parse(&current).ok()?.join(location).ok());
+
+                match next {
+                    Some(next) if redirect_chain.len() < self.config.max_redirects => {
+                        current = next.to_string();
+                        redirect_chain.push(current.clone());
+                        continue;
+                    }
+                    _ => {
+                        return FetchOutcome {
+                            http_status: Some(status.as_u16()),
+                            redirect_chain,
+                            final_url: current,
+                            body: None,
+                            error: Some("Redirect limit exceeded or missing Location".to_string()),
+                        }
+                    }
+                }
+            }
+
+            let (body, error) = if status.is_success() {
+                match read_body_capped(response, self.config.max_body_bytes).await {
+                    Ok(body) => (Some(body), None),
+                    Err(e) => (None, Some(e)),
+                }
+            } else {
+                let reason = status.canonical_reason().unwrap_or("HTTP error");
+                (None, Some(reason.to_string()))
+            };
+
+            return FetchOutcome {
+                http_status: Some(status.as_u16()),
+                redirect_chain,
+                final_url: current,
+                body,
+                error,
+            };
+        }
+    }
+
+    /// Fill `archived_url` with the closest Internet Archive capture, if any
+    async fn fill_archived_url(&self, link: &EvidenceLink) {
+        #[derive(Deserialize)]
+        struct Availability {
+            archived_snapshots: Snapshots,
+        }
+        #[derive(Deserialize)]
+        struct Snapshots {
+            closest: Option<Closest>,
+        }
+        #[derive(Deserialize)]
+        struct Closest {
+            available: bool,
+            url: String,
+        }
+
+        let lookup = format!(
+            "https://archive.org/wayback/available?url={}",
+            urlencoding::encode(&link.url)
+        );
+        let archived = match self.client.get(&lookup).send().await {
+            Ok(response) if response.status() == StatusCode::OK => response
+                .json::<Availability>()
+                .await
+                .ok()
+                .and_then(|a| a.archived_snapshots.closest)
+                .filter(|c| c.available)
+                .map(|c| c.url),
+            _ => None,
+        };
+
+        if let Some(archived_url) = archived {
+            if let Err(e) = sqlx::query(
+                "UPDATE offense_evidence SET archived_url = $2 WHERE id = $1 AND archived_url IS NULL",
+            )
+            .bind(link.id)
+            .bind(&archived_url)
+            .execute(&self.db_pool)
+            .await
+            {
+                tracing::debug!(evidence_id = %link.id, error = %e, "Failed to store archived URL");
+            }
+        }
+    }
+
+    /// Start checking on an interval (spawns a background task)
+    pub fn start(self: Arc<Self>, interval: chrono::Duration) -> ScheduledPipelineHandle {
+        let stop_flag = Arc::new(RwLock::new(false));
+        let handle = ScheduledPipelineHandle::from_flag(stop_flag.clone());
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(
+                interval.num_seconds() as u64,
+            ));
+
+            loop {
+                ticker.tick().await;
+
+                if *stop_flag.read().await {
+                    break;
+                }
+
+                if let Err(e) = self.run_once().await {
+                    tracing::error!(error = %e, "Scheduled evidence link check failed");
+                }
+            }
+        });
+
+        handle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(status: Option<u16>, final_url: &str) -> FetchOutcome {
+        FetchOutcome {
+            http_status: status,
+            redirect_chain: Vec::new(),
+            final_url: final_url.to_string(),
+            body: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_link() {
+        let url = "https://news.example/story";
+
+        assert_eq!(
+            classify_link(&outcome(Some(200), url), url, Some("a"), Some("a"), 0, 3),
+            LinkStatus::Ok
+        );
+        assert_eq!(
+            classify_link(&outcome(Some(200), url), url, Some("a"), Some("b"), 0, 3),
+            LinkStatus::Changed
+        );
+        assert_eq!(
+            classify_link(
+                &outcome(Some(200), "https://news.example/archive/story"),
+                url,
+                None,
+                Some("a"),
+                0,
+                3
+            ),
+            LinkStatus::Redirected
+        );
+        assert_eq!(
+            classify_link(&outcome(Some(404), url), url, None, None, 0, 3),
+            LinkStatus::Dead
+        );
+        assert_eq!(
+            classify_link(&outcome(Some(503), url), url, None, None, 0, 3),
+            LinkStatus::Error
+        );
+        assert_eq!(
+            classify_link(&outcome(None, url), url, None, None, 2, 3),
+            LinkStatus::Dead
+        );
+    }
+
+    #[test]
+    fn test_content_hash_ignores_whitespace() {
+        assert_eq!(
+            content_hash("Singer arrested\n\n in  Nashville"),
+            content_hash("Singer arrested in Nashville")
+        );
+        assert_ne!(
+            content_hash("Singer arrested"),
+            content_hash("Singer acquitted")
+        );
+        assert_eq!(content_hash("x").len(), 64);
+    }
+
+    #[test]
+    fn test_is_public_address() {
+        for ip in ["93.184.216.34", "2606:2800:220:1:248:1893:25c8:1946"] {
+            assert!(is_public_address(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!is_public_address(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_fetch_target_rejects_internal_urls() {
+        assert!(check_fetch_target("https://93.184.216.34/story")
+            .await
+            .is_ok());
+        for url in [
+            "file:///etc/passwd",
+            "ftp://93.184.216.34/story",
+            "http://127.0.0.1:8080/admin",
+            "http://[::1]/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://localhost/",
+        ] {
+            assert!(check_fetch_target(url).await.is_err(), "{}", url);
+        }
+    }
+}
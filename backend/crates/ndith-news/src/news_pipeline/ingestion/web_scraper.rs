@@ -228,7 +228,15 @@ impl WebScraper {
         }
 
         let html = response.text().await.context("Failed to read response")?;
-        let document = Html::parse_document(&html);
+        self.extract(url, &html)
+    }
+
+    /// Extract article content from already-fetched HTML, using the
+    /// selectors for the URL's domain
+    pub fn extract(&self, url: &str, html: &str) -> Result<ScrapedContent> {
+        let domain =
+            Self::extract_domain(url).ok_or_else(|| anyhow::anyhow!("Invalid URL: {}", url))?;
+        let document = Html::parse_document(html);
 
         let selectors = self.get_selectors(&domain);
 
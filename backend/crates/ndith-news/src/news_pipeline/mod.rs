@@ -9,10 +9,15 @@
 //! - Entity extraction (NER) for artist identification
 //! - Offense classification (keyword-based)
 //! - Vector embeddings for semantic search
+//! - Story clustering so syndicated copies count as one piece of evidence
 //! - Automatic offense creation from news detections
+//! - Evidence link health checking and text snapshot archiving
 //! - Single-pass artist researcher for deep investigation
+//! - Labeled-corpus evaluation of offense classifiers
 
 pub mod autoresearch;
+pub mod evaluation;
+pub mod evidence_link_checker;
 pub mod ingestion;
 pub mod offense_creator;
 pub mod orchestrator;
@@ -30,7 +35,8 @@ pub use processing::{
     ArticleEmbedding, BudgetGuard, ClaudeClient, ClaudeClientConfig, EmbeddingGenerator,
     EntityExtractor, EntityType, ExtractedEntity, HybridClassifier, HybridClassifierConfig,
     LlmBudgetConfig, OffenseCategory, OffenseClassification, OffenseClassifier,
-    ResearchQualityScore, ResearchQualityScorer,
+    ResearchQualityScore, ResearchQualityScorer, StoryAssignment, StoryClusterConfig,
+    StoryClusterer,
 };
 
 pub use orchestrator::{
@@ -40,8 +46,17 @@ pub use orchestrator::{
 
 pub use repository::{ArticleFilters, ArticleSummary, NewsRepository};
 
+pub use evaluation::{
+    ClassifierComparison, EvaluatedClassifier, EvaluationBaseline, EvaluationConfig,
+    EvaluationReport, EvaluationRunner, LabeledArticle, LabeledCorpus,
+};
+
 pub use offense_creator::{OffenseCreationResult, OffenseCreator};
 
+pub use evidence_link_checker::{
+    EvidenceLinkChecker, EvidenceLinkCheckerConfig, LinkCheckResult, LinkCheckStats, LinkStatus,
+};
+
 pub use autoresearch::{
     ArtistResearcher,
     ArtistResearcherConfig,
@@ -5,14 +5,19 @@
 //! Deduplication is handled by the Convex `createOffenseFromResearch` mutation
 //! (same artist + category within 30 days = update, not create).
 //! Score recalculation is handled by Convex cron jobs.
+//! Acquittals and dismissals reported in the news are proposed as pending
+//! timeline events on the matching PostgreSQL offense for moderator review,
+//! through `OffenseService::propose_offense_event`.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use ndith_services::OffenseService;
+use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::convex_client::{ConvexClient, CreateOffenseArgs, LinkEvidenceArgs, UpsertResponse};
 
-use super::processing::OffenseClassification;
+use super::processing::{DetectedLegalEvent, OffenseClassification};
 
 /// Minimum confidence threshold for auto-creating offenses
 const CONFIDENCE_THRESHOLD: f64 = 0.7;
@@ -29,9 +34,11 @@ fn strip_html(s: &str) -> String {
 
 /// Service for creating offense records from news detections.
 ///
-/// Writes to Convex via `ConvexClient`. No PostgreSQL dependency.
+/// Offenses and evidence are written to Convex via `ConvexClient`; legal
+/// timeline events go to PostgreSQL, where moderators review them.
 pub struct OffenseCreator {
     convex: ConvexClient,
+    db_pool: Option<PgPool>,
 }
 
 /// Result of processing a classification
@@ -52,7 +59,16 @@ pub struct OffenseCreationResult {
 impl OffenseCreator {
     /// Create a new offense creator backed by Convex.
     pub fn new(convex: ConvexClient) -> Self {
-        Self { convex }
+        Self {
+            convex,
+            db_pool: None,
+        }
+    }
+
+    /// Propose legal timeline events against the PostgreSQL offenses.
+    pub fn with_database(mut self, db_pool: PgPool) -> Self {
+        self.db_pool = Some(db_pool);
+        self
     }
 
     /// Process a news offense classification and create an artist offense in Convex.
@@ -195,6 +211,66 @@ impl OffenseCreator {
         Ok(response)
     }
 
+    /// Propose a legal outcome reported in an article as a pending event on
+    /// the artist's most recent offense in the classification's category.
+    ///
+    /// Returns true if a new proposal was queued; false if no database is
+    /// configured, the classification has no artist, the artist has no offense
+    /// in the category, or an open event of the same type already exists.
+    pub async fn propose_legal_event(
+        &self,
+        classification: &OffenseClassification,
+        event: &DetectedLegalEvent,
+        article_url: &str,
+        published_at: Option<DateTime<Utc>>,
+    ) -> Result<bool> {
+        let (Some(pool), Some(artist_id)) = (self.db_pool.as_ref(), classification.artist_id)
+        else {
+            return Ok(false);
+        };
+
+        let offense_id: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM artist_offenses
+            WHERE artist_id = $1 AND category::text = $2
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(artist_id)
+        .bind(classification.category.to_string())
+        .fetch_optional(pool)
+        .await
+        .context("Failed to look up offense for legal event")?;
+        let Some(offense_id) = offense_id else {
+            return Ok(false);
+        };
+
+        let proposed = OffenseService::new(pool)
+            .propose_offense_event(
+                offense_id,
+                event.event_type,
+                published_at.map(|d| d.date_naive()),
+                article_url,
+                Some(&strip_html(&event.context)),
+                classification.confidence,
+            )
+            .await
+            .context("Failed to propose offense event")?;
+
+        if let Some(ref proposed) = proposed {
+            tracing::info!(
+                event_id = %proposed.id,
+                offense_id = %offense_id,
+                event_type = %event.event_type,
+                url = %article_url,
+                "Proposed offense timeline event from news"
+            );
+        }
+
+        Ok(proposed.is_some())
+    }
+
     /// Process multiple classifications from a processed article
     pub async fn process_article_offenses(
         &self,
@@ -2,9 +2,13 @@
 //!
 //! Coordinates all news ingestion sources and processing components.
 //! Manages scheduling, deduplication, and the overall processing pipeline.
+//! URL deduplication drops exact repeats; story clustering then groups
+//! near-duplicate articles from different outlets so each story's offense
+//! evidence is only counted once.
 //!
 //! Write operations use Convex via `ConvexClient`.
-//! PostgreSQL pool is still passed for read queries used by downstream components.
+//! PostgreSQL pool is still passed for read queries used by downstream components
+//! and for story clusters, which evidence scoring groups by.
 
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
@@ -25,6 +29,7 @@ use super::offense_creator::OffenseCreator;
 use super::processing::{
     ArticleEmbedding, EmbeddingConfig, EmbeddingGenerator, EntityExtractor, EntityExtractorConfig,
     ExtractedEntity, OffenseClassification, OffenseClassifier, OffenseClassifierConfig,
+    StoryAssignment, StoryClusterConfig, StoryClusterer,
 };
 use super::repository::NewsRepository;
 
@@ -47,10 +52,17 @@ pub struct NewsPipelineConfig {
     pub offense_classifier: OffenseClassifierConfig,
     /// Embedding generator config
     pub embedding: EmbeddingConfig,
+    /// Story clustering config
+    #[serde(default)]
+    pub story_clustering: StoryClusterConfig,
     /// Enable web scraping for full article content
     pub enable_scraping: bool,
     /// Enable embedding generation
     pub enable_embeddings: bool,
+    /// Group near-duplicate articles into stories (falls back to text
+    /// fingerprints when embeddings are disabled)
+    #[serde(default = "default_true")]
+    pub enable_story_clustering: bool,
     /// Maximum articles to process per batch
     pub batch_size: usize,
     /// Deduplication window (hours)
@@ -68,14 +80,20 @@ impl Default for NewsPipelineConfig {
             entity_extractor: EntityExtractorConfig::default(),
             offense_classifier: OffenseClassifierConfig::default(),
             embedding: EmbeddingConfig::default(),
+            story_clustering: StoryClusterConfig::default(),
             enable_scraping: true,
             enable_embeddings: true,
+            enable_story_clustering: true,
             batch_size: 50,
             dedup_window_hours: 24,
         }
     }
 }
 
+fn default_true() -> bool {
+    true
+}
+
 /// A processed article with all extracted information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessedArticle {
@@ -87,6 +105,9 @@ pub struct ProcessedArticle {
     pub offenses: Vec<OffenseClassification>,
     /// Article embedding (if generated)
     pub embedding: Option<ArticleEmbedding>,
+    /// Story the article was clustered into (if clustering is enabled)
+    #[serde(default)]
+    pub story: Option<StoryAssignment>,
     /// Processing timestamp
     pub processed_at: DateTime<Utc>,
     /// Processing duration in milliseconds
@@ -114,6 +135,15 @@ pub struct PipelineStats {
     pub offenses_detected: usize,
     /// Embeddings generated
     pub embeddings_generated: usize,
+    /// New stories started
+    #[serde(default)]
+    pub stories_created: usize,
+    /// Articles clustered into an existing story
+    #[serde(default)]
+    pub articles_clustered: usize,
+    /// Offenses not counted as evidence because their story already had them
+    #[serde(default)]
+    pub duplicate_evidence_skipped: usize,
     /// Processing errors
     pub errors: usize,
     /// Last run timestamp
@@ -133,6 +163,7 @@ pub struct NewsPipelineOrchestrator {
     entity_extractor: EntityExtractor,
     offense_classifier: OffenseClassifier,
     embedding_generator: EmbeddingGenerator,
+    story_clusterer: StoryClusterer,
     /// Seen URLs for deduplication
     seen_urls: Arc<RwLock<HashSet<String>>>,
     /// Pipeline statistics
@@ -157,6 +188,7 @@ impl NewsPipelineOrchestrator {
             entity_extractor: EntityExtractor::new(config.entity_extractor.clone()),
             offense_classifier: OffenseClassifier::new(config.offense_classifier.clone()),
             embedding_generator: EmbeddingGenerator::new(config.embedding.clone()),
+            story_clusterer: StoryClusterer::new(config.story_clustering.clone()),
             config,
             seen_urls: Arc::new(RwLock::new(HashSet::new())),
             stats: Arc::new(RwLock::new(PipelineStats::default())),
@@ -199,12 +231,13 @@ impl NewsPipelineOrchestrator {
             entity_extractor: EntityExtractor::new(config.entity_extractor.clone()),
             offense_classifier: OffenseClassifier::new(config.offense_classifier.clone()),
             embedding_generator: EmbeddingGenerator::new(config.embedding.clone()),
+            story_clusterer: StoryClusterer::new(config.story_clustering.clone()),
             config,
             seen_urls: Arc::new(RwLock::new(HashSet::new())),
             stats: Arc::new(RwLock::new(PipelineStats::default())),
             is_running: Arc::new(RwLock::new(false)),
-            repository: Some(NewsRepository::new(convex.clone(), db_pool)),
-            offense_creator: Some(OffenseCreator::new(convex)),
+            repository: Some(NewsRepository::new(convex.clone(), db_pool.clone())),
+            offense_creator: Some(OffenseCreator::new(convex).with_database(db_pool)),
         }
     }
 
@@ -326,13 +359,19 @@ impl NewsPipelineOrchestrator {
 
         for batch in batches {
             match self.process_batch(batch).await {
-                Ok(processed) => {
+                Ok((processed, skipped_evidence)) => {
+                    stats.duplicate_evidence_skipped += skipped_evidence;
                     for article in &processed {
                         stats.entities_extracted += article.entities.len();
                         stats.offenses_detected += article.offenses.len();
                         if article.embedding.is_some() {
                             stats.embeddings_generated += 1;
                         }
+                        match &article.story {
+                            Some(story) if story.is_new_story => stats.stories_created += 1,
+                            Some(_) => stats.articles_clustered += 1,
+                            None => {}
+                        }
                     }
                     processed_articles.extend(processed);
                 }
@@ -354,6 +393,9 @@ impl NewsPipelineOrchestrator {
             global_stats.entities_extracted += stats.entities_extracted;
             global_stats.offenses_detected += stats.offenses_detected;
             global_stats.embeddings_generated += stats.embeddings_generated;
+            global_stats.stories_created += stats.stories_created;
+            global_stats.articles_clustered += stats.articles_clustered;
+            global_stats.duplicate_evidence_skipped += stats.duplicate_evidence_skipped;
             global_stats.errors += stats.errors;
         }
 
@@ -362,6 +404,8 @@ impl NewsPipelineOrchestrator {
             entities = stats.entities_extracted,
             offenses = stats.offenses_detected,
             embeddings = stats.embeddings_generated,
+            stories = stats.stories_created,
+            clustered = stats.articles_clustered,
             "Pipeline run complete"
         );
 
@@ -458,9 +502,16 @@ impl NewsPipelineOrchestrator {
     /// Process a batch of articles.
     ///
     /// Articles are persisted to Convex via the repository (batch ingest).
-    /// Offense records are created via the offense creator (Convex mutations).
-    async fn process_batch(&self, articles: Vec<FetchedArticle>) -> Result<Vec<ProcessedArticle>> {
+    /// Offense records are created via the offense creator (Convex mutations),
+    /// but only for offenses not already evidenced by another article in the
+    /// same story. Returns the processed articles and the number of offenses
+    /// skipped as duplicate evidence.
+    async fn process_batch(
+        &self,
+        articles: Vec<FetchedArticle>,
+    ) -> Result<(Vec<ProcessedArticle>, usize)> {
         let mut processed = Vec::with_capacity(articles.len());
+        let mut skipped_evidence = 0;
 
         for article in articles {
             let start = std::time::Instant::now();
@@ -502,16 +553,69 @@ impl NewsPipelineOrchestrator {
                 None
             };
 
+            // Assign to a story, preferring the embedding when we have one
+            let story = if self.config.enable_story_clustering {
+                Some(
+                    self.story_clusterer
+                        .assign(&article, embedding.as_ref().map(|e| e.embedding.as_slice()))
+                        .await,
+                )
+            } else {
+                None
+            };
+
+            // Only the first article in a story to report an (artist, category)
+            // contributes evidence for it
+            let mut evidence_offenses = Vec::with_capacity(offenses.len());
+            for offense in &offenses {
+                let counts = match &story {
+                    Some(story) => {
+                        self.story_clusterer
+                            .claim_evidence(story.story_id, offense)
+                            .await
+                    }
+                    None => true,
+                };
+                if counts {
+                    evidence_offenses.push(offense.clone());
+                } else {
+                    skipped_evidence += 1;
+                }
+            }
+
+            if let Some(story) = story.as_ref().filter(|s| !s.is_new_story) {
+                tracing::debug!(
+                    url = %article.url,
+                    story_id = %story.story_id,
+                    method = ?story.method,
+                    similarity = story.similarity,
+                    is_primary = story.is_primary,
+                    "Clustered article into existing story"
+                );
+            }
+
             let duration = start.elapsed().as_millis() as u64;
 
             // Persist to Convex via repository
             if let Some(ref repository) = self.repository {
                 if let Err(e) = repository
-                    .insert_article(&article, &entities, &offenses)
+                    .insert_article(&article, &entities, &offenses, story.as_ref())
                     .await
                 {
                     tracing::warn!(url = %article.url, error = %e, "Failed to persist article to Convex");
                 } else {
+                    // Story clusters live in Postgres so evidence scores count each story once
+                    if let Some(story) = story.as_ref() {
+                        if let Err(e) = repository.record_story(&article, story).await {
+                            tracing::warn!(
+                                url = %article.url,
+                                story_id = %story.story_id,
+                                error = %e,
+                                "Failed to record article story"
+                            );
+                        }
+                    }
+
                     // Create artist_offenses from detected offenses via Convex
                     if let Some(ref offense_creator) = self.offense_creator {
                         let results = offense_creator
@@ -520,7 +624,7 @@ impl NewsPipelineOrchestrator {
                                 &article.title,
                                 &article.url,
                                 article.published_at,
-                                &offenses,
+                                &evidence_offenses,
                             )
                             .await;
 
@@ -543,6 +647,32 @@ impl NewsPipelineOrchestrator {
                                 );
                             }
                         }
+
+                        // Propose acquittals/dismissals the article reports
+                        for offense in &offenses {
+                            let Some(event) = self.offense_classifier.detect_legal_outcome(
+                                content,
+                                Some(&article.title),
+                                offense,
+                            ) else {
+                                continue;
+                            };
+                            if let Err(e) = offense_creator
+                                .propose_legal_event(
+                                    offense,
+                                    &event,
+                                    &article.url,
+                                    article.published_at,
+                                )
+                                .await
+                            {
+                                tracing::warn!(
+                                    article_id = %article.id,
+                                    error = %e,
+                                    "Failed to propose offense event"
+                                );
+                            }
+                        }
                     }
                 }
             }
@@ -552,12 +682,13 @@ impl NewsPipelineOrchestrator {
                 entities,
                 offenses,
                 embedding,
+                story,
                 processed_at: Utc::now(),
                 processing_duration_ms: duration,
             });
         }
 
-        Ok(processed)
+        Ok((processed, skipped_evidence))
     }
 
     /// Get pipeline statistics
@@ -579,7 +710,7 @@ impl NewsPipelineOrchestrator {
     pub async fn run_rss_only(&self) -> Result<Vec<ProcessedArticle>> {
         let articles = self.fetch_rss().await?;
         let deduplicated = self.deduplicate(articles).await;
-        self.process_batch(deduplicated).await
+        Ok(self.process_batch(deduplicated).await?.0)
     }
 
     /// Run social media only (Twitter + Reddit)
@@ -598,13 +729,13 @@ impl NewsPipelineOrchestrator {
         }
 
         let deduplicated = self.deduplicate(all_articles).await;
-        self.process_batch(deduplicated).await
+        Ok(self.process_batch(deduplicated).await?.0)
     }
 
     /// Search for specific artist news
     pub async fn search_artist(&self, artist_name: &str) -> Result<Vec<ProcessedArticle>> {
         let articles = self.newsapi_client.search_artist_news(artist_name).await?;
-        self.process_batch(articles).await
+        Ok(self.process_batch(articles).await?.0)
     }
 
     /// Process articles from research sources (Wikipedia, web search)
@@ -614,7 +745,7 @@ impl NewsPipelineOrchestrator {
         articles: Vec<FetchedArticle>,
     ) -> Result<Vec<ProcessedArticle>> {
         let deduplicated = self.deduplicate(articles).await;
-        self.process_batch(deduplicated).await
+        Ok(self.process_batch(deduplicated).await?.0)
     }
 
     /// Get articles with detected offenses
@@ -644,6 +775,11 @@ impl NewsPipelineOrchestrator {
         let mut seen = self.seen_urls.write().await;
         seen.clear();
     }
+
+    /// Close all open stories so new articles start fresh clusters
+    pub async fn clear_stories(&self) {
+        self.story_clusterer.clear().await;
+    }
 }
 
 /// Scheduled pipeline runner
@@ -771,6 +907,11 @@ pub struct ScheduledPipelineHandle {
 }
 
 impl ScheduledPipelineHandle {
+    /// Wrap a stop flag shared with other scheduled background tasks
+    pub(crate) fn from_flag(stop_flag: Arc<RwLock<bool>>) -> Self {
+        Self { stop_flag }
+    }
+
     /// Stop all scheduled tasks
     pub async fn stop(&self) {
         let mut flag = self.stop_flag.write().await;
@@ -787,6 +928,7 @@ mod tests {
         let config = NewsPipelineConfig::default();
         assert!(config.enable_scraping);
         assert!(config.enable_embeddings);
+        assert!(config.enable_story_clustering);
         assert_eq!(config.batch_size, 50);
     }
 
@@ -5,6 +5,7 @@
 //! - Offense classification (keyword-based)
 //! - Embedding generation for semantic search
 //! - Research quality scoring
+//! - Story clustering of near-duplicate articles
 
 pub mod embedding_generator;
 pub mod entity_extractor;
@@ -12,6 +13,7 @@ pub mod hybrid_classifier;
 pub mod llm_client;
 pub mod offense_classifier;
 pub mod research_quality;
+pub mod story_clusterer;
 
 pub use embedding_generator::{ArticleEmbedding, EmbeddingConfig, EmbeddingGenerator};
 pub use entity_extractor::{
@@ -19,10 +21,11 @@ pub use entity_extractor::{
 };
 pub use hybrid_classifier::{HybridClassifier, HybridClassifierConfig};
 pub use offense_classifier::{
-    OffenseCategory, OffenseClassification, OffenseClassifier, OffenseClassifierConfig,
-    OffenseSeverity,
+    DetectedLegalEvent, OffenseCategory, OffenseClassification, OffenseClassifier,
+    OffenseClassifierConfig, OffenseSeverity,
 };
 pub use research_quality::{ResearchQualityScore, ResearchQualityScorer};
+pub use story_clusterer::{ClusterMethod, StoryAssignment, StoryClusterConfig, StoryClusterer};
 
 // LLM client types kept available but not prominently re-exported.
 // The module is still accessible as `processing::llm_client::*` for anyone who needs it,
@@ -5,6 +5,7 @@
 //! articles that may contain information about artist misconduct.
 
 use anyhow::Result;
+use ndith_core::models::offense::OffenseEventType;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -54,6 +55,32 @@ impl std::fmt::Display for OffenseCategory {
     }
 }
 
+impl std::str::FromStr for OffenseCategory {
+    type Err = anyhow::Error;
+
+    /// Parse the snake_case form produced by `Display` (and used in LLM prompts)
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "sexual_misconduct" => Ok(OffenseCategory::SexualMisconduct),
+            "domestic_violence" => Ok(OffenseCategory::DomesticViolence),
+            "hate_speech" => Ok(OffenseCategory::HateSpeech),
+            "racism" => Ok(OffenseCategory::Racism),
+            "antisemitism" => Ok(OffenseCategory::Antisemitism),
+            "homophobia" => Ok(OffenseCategory::Homophobia),
+            "child_abuse" => Ok(OffenseCategory::ChildAbuse),
+            "animal_cruelty" => Ok(OffenseCategory::AnimalCruelty),
+            "financial_crimes" => Ok(OffenseCategory::FinancialCrimes),
+            "drug_offenses" => Ok(OffenseCategory::DrugOffenses),
+            "violent_crimes" => Ok(OffenseCategory::ViolentCrimes),
+            "harassment" => Ok(OffenseCategory::Harassment),
+            "plagiarism" => Ok(OffenseCategory::Plagiarism),
+            "certified_creeper" => Ok(OffenseCategory::CertifiedCreeper),
+            "other" => Ok(OffenseCategory::Other),
+            other => anyhow::bail!("Unknown offense category: {}", other),
+        }
+    }
+}
+
 /// Convert news classifier category to core database category
 impl From<&OffenseCategory> for ndith_core::models::offense::OffenseCategory {
     fn from(category: &OffenseCategory) -> Self {
@@ -86,6 +113,29 @@ pub enum OffenseSeverity {
     Critical,
 }
 
+impl std::str::FromStr for OffenseSeverity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "low" => Ok(OffenseSeverity::Low),
+            "medium" => Ok(OffenseSeverity::Medium),
+            "high" => Ok(OffenseSeverity::High),
+            "critical" => Ok(OffenseSeverity::Critical),
+            other => anyhow::bail!("Unknown offense severity: {}", other),
+        }
+    }
+}
+
+/// A legal outcome reported alongside an offense classification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedLegalEvent {
+    /// Lifecycle event the article reports (acquittal or dismissal)
+    pub event_type: OffenseEventType,
+    /// Sentence the outcome was reported in
+    pub context: String,
+}
+
 /// An offense classification result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OffenseClassification {
@@ -344,6 +394,7 @@ pub struct OffenseClassifier {
     config: OffenseClassifierConfig,
     category_keywords: HashMap<OffenseCategory, CategoryKeywords>,
     negation_patterns: Vec<Regex>,
+    legal_outcome_patterns: Vec<(OffenseEventType, Regex)>,
     sentence_splitter: Regex,
 }
 
@@ -356,12 +407,23 @@ impl OffenseClassifier {
             Regex::new(r"(?i)not guilty|acquitted|exonerated|cleared of").unwrap(),
             Regex::new(r"(?i)no evidence|lacks evidence|unsubstantiated").unwrap(),
         ];
+        let legal_outcome_patterns = vec![
+            (
+                OffenseEventType::Acquittal,
+                Regex::new(r"(?i)\b(acquitted|found (\w+ )?not guilty|not guilty verdict|exonerated|cleared of)\b").unwrap(),
+            ),
+            (
+                OffenseEventType::Dismissal,
+                Regex::new(r"(?i)\b(charges? (were|was|have been|has been) (dropped|dismissed)|(case|lawsuit|indictment) (was |has been )?dismissed|dismissed the (case|charges?|lawsuit|indictment))\b").unwrap(),
+            ),
+        ];
         let sentence_splitter = Regex::new(r"[.!?]+\s+").unwrap();
 
         Self {
             config,
             category_keywords,
             negation_patterns,
+            legal_outcome_patterns,
             sentence_splitter,
         }
     }
@@ -981,6 +1043,44 @@ impl OffenseClassifier {
         Ok(classifications)
     }
 
+    /// Detect an acquittal or dismissal reported in the same sentence as one of
+    /// the classification's matched keywords.
+    ///
+    /// Negation only lowers classification confidence; this lets the pipeline
+    /// propose the outcome as a timeline event for the artist's offense.
+    pub fn detect_legal_outcome(
+        &self,
+        text: &str,
+        title: Option<&str>,
+        classification: &OffenseClassification,
+    ) -> Option<DetectedLegalEvent> {
+        let full_text = match title {
+            Some(t) => format!("{}\n\n{}", t, text),
+            None => text.to_string(),
+        };
+
+        for sentence in self.sentence_splitter.split(&full_text) {
+            let sentence_lower = sentence.to_lowercase();
+            let has_keyword = classification
+                .matched_keywords
+                .iter()
+                .any(|k| sentence_lower.contains(&k.to_lowercase()));
+            if !has_keyword {
+                continue;
+            }
+
+            for (event_type, pattern) in &self.legal_outcome_patterns {
+                if pattern.is_match(sentence) {
+                    return Some(DetectedLegalEvent {
+                        event_type: *event_type,
+                        context: sentence.trim().to_string(),
+                    });
+                }
+            }
+        }
+        None
+    }
+
     /// Check if negation patterns appear in the same sentence as any matched keyword
     /// (scoped negation — only affects the relevant category)
     fn check_scoped_negation(&self, sentences: &[&str], matched_keywords: &[String]) -> bool {
@@ -1181,6 +1281,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_detect_legal_outcome() {
+        let classifier = OffenseClassifier::new(OffenseClassifierConfig::default());
+        let article_id = Uuid::new_v4();
+        let text = "The rapper was charged with assault in 2021. \
+                    On Monday a jury found him not guilty of assault.";
+
+        let violent = OffenseClassification {
+            id: Uuid::new_v4(),
+            article_id,
+            entity_id: None,
+            artist_id: None,
+            convex_artist_id: None,
+            category: OffenseCategory::ViolentCrimes,
+            severity: OffenseSeverity::High,
+            confidence: 0.5,
+            music_context_score: 1.0,
+            matched_keywords: vec!["assault".to_string()],
+            context: String::new(),
+            needs_review: true,
+            classification_source: Some("keyword".to_string()),
+        };
+
+        let outcome = classifier
+            .detect_legal_outcome(text, None, &violent)
+            .expect("acquittal should be detected");
+        assert_eq!(outcome.event_type, OffenseEventType::Acquittal);
+        assert!(outcome.context.contains("not guilty"));
+
+        let charged_only = "The rapper was charged with assault in 2021.";
+        assert!(classifier
+            .detect_legal_outcome(charged_only, None, &violent)
+            .is_none());
+    }
+
     #[test]
     fn test_scoped_negation_doesnt_affect_unrelated_categories() {
         let config = OffenseClassifierConfig::default();
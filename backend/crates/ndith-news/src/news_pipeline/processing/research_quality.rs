@@ -2,6 +2,14 @@
 //!
 //! Scores the quality of evidence research per artist (0–100).
 //! Drives the autoresearch loop — lower scores trigger more investigation.
+//!
+//! Articles clustered into the same story (syndicated copies, reposts) count
+//! as a single source, so corroboration and confidence aren't inflated by
+//! one report appearing in many outlets.
+//!
+//! Evidence whose link has died (without a local snapshot) or whose content
+//! changed since review discounts the overall score via the link health
+//! factor, so the autoresearch loop goes looking for fresh sources.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -25,6 +33,9 @@ pub struct ResearchQualityScore {
     pub confidence_score: f64,
     /// Has the system searched all available source types (0–20)
     pub completeness_score: f64,
+    /// Share of checked evidence links that are still verifiable (0–1)
+    #[serde(default = "default_link_health")]
+    pub link_health_score: f64,
     /// Which sources have been searched
     pub sources_searched: Vec<String>,
     /// When last researched
@@ -35,6 +46,10 @@ pub struct ResearchQualityScore {
     pub needs_more_research: bool,
 }
 
+fn default_link_health() -> f64 {
+    1.0
+}
+
 /// All available source types for completeness scoring
 pub const ALL_SOURCE_TYPES: &[&str] = &[
     "rss",
@@ -62,9 +77,12 @@ impl ResearchQualityScorer {
         let corroboration = self.calc_corroboration(artist_id).await?;
         let confidence = self.calc_confidence(artist_id).await?;
         let (completeness, sources_searched) = self.calc_completeness(artist_id).await?;
+        let link_health = self.calc_link_health(artist_id).await?;
 
+        // Dead or changed evidence links discount the score by up to 25%
         let quality_score =
-            source_diversity + temporal_coverage + corroboration + confidence + completeness;
+            (source_diversity + temporal_coverage + corroboration + confidence + completeness)
+                * (0.75 + 0.25 * link_health);
 
         let needs_more_research = quality_score < 70.0;
 
@@ -76,6 +94,7 @@ impl ResearchQualityScorer {
             corroboration_score: corroboration,
             confidence_score: confidence,
             completeness_score: completeness,
+            link_health_score: link_health,
             sources_searched,
             last_research_at: Some(Utc::now()),
             research_iterations: 0, // Caller increments
@@ -140,16 +159,19 @@ impl ResearchQualityScorer {
 
     /// Corroboration (0–20): offenses confirmed by multiple independent sources
     async fn calc_corroboration(&self, artist_id: Uuid) -> Result<f64> {
-        // Count offense categories that have evidence from 2+ different articles
+        // Count offense categories that have evidence from 2+ different stories.
+        // Articles without a story count as their own story.
         let corroborated: i64 = sqlx::query_scalar(
             r#"
             SELECT COUNT(DISTINCT offense_category)
             FROM (
-                SELECT offense_category, COUNT(DISTINCT article_id) as source_count
-                FROM news_offense_classifications
-                WHERE artist_id = $1
-                GROUP BY offense_category
-                HAVING COUNT(DISTINCT article_id) >= 2
+                SELECT noc.offense_category,
+                       COUNT(DISTINCT COALESCE(na.story_id, na.id)) as story_count
+                FROM news_offense_classifications noc
+                JOIN news_articles na ON noc.article_id = na.id
+                WHERE noc.artist_id = $1
+                GROUP BY noc.offense_category
+                HAVING COUNT(DISTINCT COALESCE(na.story_id, na.id)) >= 2
             ) sub
             "#,
         )
@@ -162,13 +184,19 @@ impl ResearchQualityScorer {
         Ok((corroborated as f64 * 7.0).min(20.0))
     }
 
-    /// Confidence (0–20): average classifier confidence for this artist
+    /// Confidence (0–20): average classifier confidence for this artist,
+    /// taking the most confident classification per story and category
     async fn calc_confidence(&self, artist_id: Uuid) -> Result<f64> {
         let avg_confidence: Option<f64> = sqlx::query_scalar(
             r#"
-            SELECT AVG(confidence)::float8
-            FROM news_offense_classifications
-            WHERE artist_id = $1
+            SELECT AVG(story_confidence)::float8
+            FROM (
+                SELECT MAX(noc.confidence) AS story_confidence
+                FROM news_offense_classifications noc
+                JOIN news_articles na ON noc.article_id = na.id
+                WHERE noc.artist_id = $1
+                GROUP BY COALESCE(na.story_id, na.id), noc.offense_category
+            ) per_story
             "#,
         )
         .bind(artist_id)
@@ -208,6 +236,37 @@ impl ResearchQualityScorer {
         Ok((score, searched))
     }
 
+    /// Link health (0–1): share of the artist's checked evidence links that
+    /// are reachable and unchanged, or preserved by a local snapshot.
+    /// Artists with no checked evidence score 1.0.
+    async fn calc_link_health(&self, artist_id: Uuid) -> Result<f64> {
+        let (checked, healthy): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*),
+                COUNT(*) FILTER (
+                    WHERE oe.link_status IN ('ok', 'redirected')
+                       OR (oe.link_status = 'dead' AND EXISTS (
+                           SELECT 1 FROM evidence_snapshots s WHERE s.evidence_id = oe.id
+                       ))
+                )
+            FROM offense_evidence oe
+            JOIN artist_offenses ao ON oe.offense_id = ao.id
+            WHERE ao.artist_id = $1
+              AND oe.link_status NOT IN ('unchecked', 'error')
+            "#,
+        )
+        .bind(artist_id)
+        .fetch_one(&self.db_pool)
+        .await
+        .context("Failed to calculate evidence link health")?;
+
+        if checked == 0 {
+            return Ok(1.0);
+        }
+        Ok(healthy as f64 / checked as f64)
+    }
+
     /// Persist the quality score to database
     pub async fn persist(&self, score: &ResearchQualityScore) -> Result<()> {
         let sources_json =
@@ -221,9 +280,9 @@ impl ResearchQualityScorer {
                 corroboration_score, confidence_score, completeness_score,
                 sources_searched, last_research_at,
                 research_iterations, needs_more_research,
-                updated_at
+                link_health_score, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW())
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW())
             ON CONFLICT (artist_id) DO UPDATE SET
                 quality_score = $2,
                 source_diversity_score = $3,
@@ -235,6 +294,7 @@ impl ResearchQualityScorer {
                 last_research_at = $9,
                 research_iterations = $10,
                 needs_more_research = $11,
+                link_health_score = $12,
                 updated_at = NOW()
             "#,
         )
@@ -249,6 +309,7 @@ impl ResearchQualityScorer {
         .bind(score.last_research_at)
         .bind(score.research_iterations)
         .bind(score.needs_more_research)
+        .bind(score.link_health_score)
         .execute(&self.db_pool)
         .await
         .context("Failed to persist research quality score")?;
@@ -288,6 +349,7 @@ mod tests {
             corroboration_score: 5.0, // Weakest
             confidence_score: 12.0,
             completeness_score: 8.0,
+            link_health_score: 1.0,
             sources_searched: vec!["rss".to_string()],
             last_research_at: None,
             research_iterations: 0,
@@ -0,0 +1,515 @@
+//! Story Clustering
+//!
+//! Groups near-duplicate articles from different outlets into a single
+//! "story" so that syndicated copies, Reddit reposts and RSS mirrors of the
+//! same report only count as one piece of evidence.
+//!
+//! Articles are matched by embedding cosine similarity when an embedding is
+//! available, falling back to SimHash and MinHash text fingerprints when
+//! embeddings are disabled or failed. Each story keeps one primary source,
+//! preferring editorial outlets over social posts and earlier publication
+//! over later copies.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::embedding_generator::EmbeddingGenerator;
+use super::offense_classifier::OffenseClassification;
+use crate::news_pipeline::ingestion::FetchedArticle;
+
+/// Story clustering configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryClusterConfig {
+    /// Minimum cosine similarity between an article embedding and a story
+    /// centroid for the article to join the story
+    pub embedding_similarity_threshold: f32,
+    /// Maximum Hamming distance between 64-bit SimHash fingerprints
+    pub simhash_max_distance: u32,
+    /// Minimum estimated Jaccard similarity between MinHash signatures
+    pub minhash_similarity_threshold: f64,
+    /// Number of hash functions in a MinHash signature
+    pub minhash_permutations: usize,
+    /// Words per shingle for fingerprinting
+    pub shingle_size: usize,
+    /// Stories not updated within this window are closed (hours)
+    pub window_hours: i64,
+    /// Maximum number of open stories kept in memory
+    pub max_open_stories: usize,
+}
+
+impl Default for StoryClusterConfig {
+    fn default() -> Self {
+        Self {
+            embedding_similarity_threshold: 0.88,
+            simhash_max_distance: 6,
+            minhash_similarity_threshold: 0.5,
+            minhash_permutations: 64,
+            shingle_size: 3,
+            window_hours: 72,
+            max_open_stories: 5000,
+        }
+    }
+}
+
+/// How an article was matched to its story
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClusterMethod {
+    /// Started a new story
+    New,
+    /// Matched by embedding cosine similarity
+    Embedding,
+    /// Matched by SimHash Hamming distance
+    SimHash,
+    /// Matched by MinHash Jaccard estimate
+    MinHash,
+}
+
+/// Result of assigning an article to a story
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryAssignment {
+    /// Story the article belongs to
+    pub story_id: Uuid,
+    /// True when this article started the story
+    pub is_new_story: bool,
+    /// True when this article is now the story's primary source
+    pub is_primary: bool,
+    /// Article that was primary before this one replaced it
+    pub previous_primary: Option<Uuid>,
+    /// Similarity to the matched story (1.0 for new stories)
+    pub similarity: f64,
+    /// How the match was made
+    pub method: ClusterMethod,
+    /// Number of articles in the story including this one
+    pub article_count: usize,
+}
+
+/// An open story held in memory while new articles may still join it
+struct Story {
+    id: Uuid,
+    primary_article_id: Uuid,
+    primary_rank: u8,
+    primary_published_at: Option<DateTime<Utc>>,
+    centroid: Option<Vec<f32>>,
+    embedded_members: usize,
+    simhash: u64,
+    minhash: Vec<u64>,
+    article_count: usize,
+    last_seen_at: DateTime<Utc>,
+    /// (artist, category) pairs that already produced evidence for this story
+    claimed_evidence: HashSet<(String, String)>,
+}
+
+/// Assigns articles to stories
+pub struct StoryClusterer {
+    config: StoryClusterConfig,
+    stories: RwLock<Vec<Story>>,
+}
+
+impl StoryClusterer {
+    /// Create a new story clusterer
+    pub fn new(config: StoryClusterConfig) -> Self {
+        Self {
+            config,
+            stories: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Assign an article to an existing story or start a new one.
+    ///
+    /// `embedding` should be the article's `EmbeddingGenerator` vector when
+    /// available; otherwise text fingerprints are used.
+    pub async fn assign(
+        &self,
+        article: &FetchedArticle,
+        embedding: Option<&[f32]>,
+    ) -> StoryAssignment {
+        let text = fingerprint_text(article);
+        let simhash = simhash(&text, self.config.shingle_size);
+        let minhash = minhash_signature(
+            &text,
+            self.config.shingle_size,
+            self.config.minhash_permutations,
+        );
+        let rank = source_rank(article);
+        let now = Utc::now();
+
+        let mut stories = self.stories.write().await;
+        self.prune(&mut stories, now);
+
+        let best = stories
+            .iter()
+            .enumerate()
+            .filter_map(|(index, story)| {
+                self.similarity(story, embedding, simhash, &minhash)
+                    .map(|(similarity, method)| (index, similarity, method))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        let Some((index, similarity, method)) = best else {
+            let story = Story {
+                id: Uuid::new_v4(),
+                primary_article_id: article.id,
+                primary_rank: rank,
+                primary_published_at: article.published_at,
+                centroid: embedding.map(|e| e.to_vec()),
+                embedded_members: usize::from(embedding.is_some()),
+                simhash,
+                minhash,
+                article_count: 1,
+                last_seen_at: now,
+                claimed_evidence: HashSet::new(),
+            };
+            let assignment = StoryAssignment {
+                story_id: story.id,
+                is_new_story: true,
+                is_primary: true,
+                previous_primary: None,
+                similarity: 1.0,
+                method: ClusterMethod::New,
+                article_count: 1,
+            };
+            stories.push(story);
+            return assignment;
+        };
+
+        let story = &mut stories[index];
+        story.article_count += 1;
+        story.last_seen_at = now;
+        if let Some(embedding) = embedding {
+            story.add_to_centroid(embedding);
+        }
+
+        let replaces_primary = rank > story.primary_rank
+            || (rank == story.primary_rank
+                && is_earlier(article.published_at, story.primary_published_at));
+        let previous_primary = if replaces_primary {
+            let previous = story.primary_article_id;
+            story.primary_article_id = article.id;
+            story.primary_rank = rank;
+            story.primary_published_at = article.published_at;
+            // Fingerprints follow the primary so later copies match its text
+            story.simhash = simhash;
+            story.minhash = minhash;
+            Some(previous)
+        } else {
+            None
+        };
+
+        StoryAssignment {
+            story_id: story.id,
+            is_new_story: false,
+            is_primary: replaces_primary,
+            previous_primary,
+            similarity,
+            method,
+            article_count: story.article_count,
+        }
+    }
+
+    /// Claim the right to count an offense as evidence for a story.
+    ///
+    /// Returns true the first time an (artist, category) pair is seen for the
+    /// story and false for every later article in the same story, so that
+    /// evidence is only counted once per story. Classifications without an
+    /// artist are always allowed through since they cannot become evidence.
+    pub async fn claim_evidence(&self, story_id: Uuid, offense: &OffenseClassification) -> bool {
+        let Some(artist) = offense
+            .convex_artist_id
+            .clone()
+            .or_else(|| offense.artist_id.map(|id| id.to_string()))
+        else {
+            return true;
+        };
+
+        let mut stories = self.stories.write().await;
+        match stories.iter_mut().find(|s| s.id == story_id) {
+            Some(story) => story
+                .claimed_evidence
+                .insert((artist, offense.category.to_string())),
+            None => true,
+        }
+    }
+
+    /// Number of open stories
+    pub async fn open_story_count(&self) -> usize {
+        self.stories.read().await.len()
+    }
+
+    /// Drop all open stories
+    pub async fn clear(&self) {
+        self.stories.write().await.clear();
+    }
+
+    /// Similarity between an article and a story, if above the configured
+    /// threshold for the method used
+    fn similarity(
+        &self,
+        story: &Story,
+        embedding: Option<&[f32]>,
+        simhash: u64,
+        minhash: &[u64],
+    ) -> Option<(f64, ClusterMethod)> {
+        if let (Some(embedding), Some(centroid)) = (embedding, story.centroid.as_deref()) {
+            let similarity = EmbeddingGenerator::cosine_similarity(embedding, centroid);
+            return (similarity >= self.config.embedding_similarity_threshold)
+                .then_some((f64::from(similarity), ClusterMethod::Embedding));
+        }
+
+        let distance = (simhash ^ story.simhash).count_ones();
+        if distance <= self.config.simhash_max_distance {
+            return Some((1.0 - f64::from(distance) / 64.0, ClusterMethod::SimHash));
+        }
+
+        let jaccard = minhash_similarity(minhash, &story.minhash);
+        (jaccard >= self.config.minhash_similarity_threshold)
+            .then_some((jaccard, ClusterMethod::MinHash))
+    }
+
+    /// Close stories outside the window and cap the number held in memory
+    fn prune(&self, stories: &mut Vec<Story>, now: DateTime<Utc>) {
+        let cutoff = now - Duration::hours(self.config.window_hours);
+        stories.retain(|s| s.last_seen_at >= cutoff);
+
+        if stories.len() >= self.config.max_open_stories {
+            stories.sort_by_key(|s| std::cmp::Reverse(s.last_seen_at));
+            stories.truncate(self.config.max_open_stories.saturating_sub(1));
+        }
+    }
+}
+
+impl Story {
+    fn add_to_centroid(&mut self, embedding: &[f32]) {
+        match &mut self.centroid {
+            Some(centroid) if centroid.len() == embedding.len() => {
+                let n = self.embedded_members as f32;
+                for (c, e) in centroid.iter_mut().zip(embedding) {
+                    *c = (*c * n + e) / (n + 1.0);
+                }
+            }
+            Some(_) => return,
+            None => self.centroid = Some(embedding.to_vec()),
+        }
+        self.embedded_members += 1;
+    }
+}
+
+/// Preference for a story's primary source: editorial news outlets beat
+/// aggregated social posts.
+pub fn source_rank(article: &FetchedArticle) -> u8 {
+    let social = article
+        .categories
+        .iter()
+        .any(|c| c == "reddit" || c == "twitter");
+    if social {
+        0
+    } else if article.content.is_some() {
+        2
+    } else {
+        1
+    }
+}
+
+fn is_earlier(candidate: Option<DateTime<Utc>>, current: Option<DateTime<Utc>>) -> bool {
+    match (candidate, current) {
+        (Some(candidate), Some(current)) => candidate < current,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+fn fingerprint_text(article: &FetchedArticle) -> String {
+    match &article.content {
+        Some(content) => format!("{} {}", article.title, content),
+        None => article.title.clone(),
+    }
+}
+
+/// Lowercased alphanumeric word shingles of `size` words
+fn shingles(text: &str, size: usize) -> Vec<String> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let size = size.max(1);
+    if words.len() <= size {
+        return if words.is_empty() {
+            Vec::new()
+        } else {
+            vec![words.join(" ")]
+        };
+    }
+    words.windows(size).map(|w| w.join(" ")).collect()
+}
+
+fn hash_with_seed(value: &str, seed: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 64-bit SimHash over word shingles
+pub fn simhash(text: &str, shingle_size: usize) -> u64 {
+    let mut weights = [0i64; 64];
+    for shingle in shingles(text, shingle_size) {
+        let hash = hash_with_seed(&shingle, 0);
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, &w)| w > 0)
+        .fold(0u64, |acc, (bit, _)| acc | (1 << bit))
+}
+
+/// MinHash signature over word shingles with `permutations` hash functions
+pub fn minhash_signature(text: &str, shingle_size: usize, permutations: usize) -> Vec<u64> {
+    let shingles = shingles(text, shingle_size);
+    (0..permutations as u64)
+        .map(|seed| {
+            shingles
+                .iter()
+                .map(|s| hash_with_seed(s, seed + 1))
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+/// Estimated Jaccard similarity between two MinHash signatures
+pub fn minhash_similarity(a: &[u64], b: &[u64]) -> f64 {
+    let len = a.len().min(b.len());
+    if len == 0 {
+        return 0.0;
+    }
+    let matching = a
+        .iter()
+        .zip(b)
+        .filter(|(x, y)| x == y && **x != u64::MAX)
+        .count();
+    matching as f64 / len as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::news_pipeline::processing::{OffenseCategory, OffenseSeverity};
+
+    const REPORT: &str = "Singer Alder Price was arrested on Tuesday night in Nashville \
+        and charged with assault after an altercation outside a downtown venue, \
+        according to police records obtained by reporters.";
+
+    fn article(url: &str, content: Option<&str>, categories: &[&str]) -> FetchedArticle {
+        FetchedArticle {
+            id: Uuid::new_v4(),
+            source_id: Uuid::nil(),
+            url: url.to_string(),
+            title: "Alder Price arrested in Nashville".to_string(),
+            content: content.map(str::to_string),
+            published_at: Some(Utc::now()),
+            fetched_at: Utc::now(),
+            authors: Vec::new(),
+            categories: categories.iter().map(|c| c.to_string()).collect(),
+            image_url: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_syndicated_copies_share_story() {
+        let clusterer = StoryClusterer::new(StoryClusterConfig::default());
+        let original = article("https://a.example/1", Some(REPORT), &[]);
+        let syndicated = article(
+            "https://b.example/2",
+            Some(&format!("{} Updated 9:14 PM.", REPORT)),
+            &[],
+        );
+        let unrelated = article(
+            "https://c.example/3",
+            Some("The band announced a twenty-city tour of Europe starting next spring."),
+            &[],
+        );
+
+        let first = clusterer.assign(&original, None).await;
+        let second = clusterer.assign(&syndicated, None).await;
+        let third = clusterer.assign(&unrelated, None).await;
+
+        assert!(first.is_new_story);
+        assert_eq!(second.story_id, first.story_id);
+        assert_ne!(second.method, ClusterMethod::New);
+        assert!(third.is_new_story);
+        assert_ne!(third.story_id, first.story_id);
+        assert_eq!(clusterer.open_story_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_news_outlet_replaces_social_primary() {
+        let clusterer = StoryClusterer::new(StoryClusterConfig::default());
+        let reddit = article("https://reddit.example/r/1", Some(REPORT), &["reddit"]);
+        let outlet = article("https://news.example/1", Some(REPORT), &[]);
+
+        let first = clusterer.assign(&reddit, None).await;
+        let second = clusterer.assign(&outlet, None).await;
+
+        assert!(first.is_primary);
+        assert_eq!(second.story_id, first.story_id);
+        assert!(second.is_primary);
+        assert_eq!(second.previous_primary, Some(reddit.id));
+    }
+
+    #[tokio::test]
+    async fn test_embedding_match_and_evidence_claimed_once() {
+        let clusterer = StoryClusterer::new(StoryClusterConfig::default());
+        let a = article("https://a.example/1", Some("first wording"), &[]);
+        let b = article("https://b.example/2", Some("entirely different wording"), &[]);
+
+        let first = clusterer.assign(&a, Some(&[1.0, 0.0, 0.1])).await;
+        let second = clusterer.assign(&b, Some(&[0.98, 0.02, 0.1])).await;
+        assert_eq!(second.story_id, first.story_id);
+        assert_eq!(second.method, ClusterMethod::Embedding);
+
+        let offense = OffenseClassification {
+            id: Uuid::new_v4(),
+            article_id: a.id,
+            entity_id: None,
+            artist_id: Some(Uuid::new_v4()),
+            convex_artist_id: None,
+            category: OffenseCategory::ViolentCrimes,
+            severity: OffenseSeverity::High,
+            confidence: 0.9,
+            music_context_score: 1.0,
+            matched_keywords: Vec::new(),
+            context: String::new(),
+            needs_review: false,
+            classification_source: None,
+        };
+        assert!(clusterer.claim_evidence(first.story_id, &offense).await);
+        assert!(!clusterer.claim_evidence(second.story_id, &offense).await);
+    }
+
+    #[test]
+    fn test_fingerprints_similarity() {
+        let a = minhash_signature(REPORT, 3, 64);
+        let b = minhash_signature(&format!("{} Updated.", REPORT), 3, 64);
+        let c = minhash_signature("Completely unrelated text about touring plans.", 3, 64);
+        assert!(minhash_similarity(&a, &b) > 0.7);
+        assert!(minhash_similarity(&a, &c) < 0.2);
+
+        let distance = (simhash(REPORT, 3) ^ simhash(REPORT, 3)).count_ones();
+        assert_eq!(distance, 0);
+    }
+}
@@ -1,11 +1,12 @@
 //! News Repository
 //!
 //! Persistence layer for news articles, entities, and offenses.
-//! Write operations go to Convex via HTTP mutations.
+//! Write operations go to Convex via HTTP mutations, except story clusters,
+//! which are also written to PostgreSQL so evidence scoring can group by story.
 //! Read operations still use PostgreSQL (will be migrated separately).
 
 use super::ingestion::FetchedArticle;
-use super::processing::{ExtractedEntity, OffenseClassification};
+use super::processing::{ExtractedEntity, OffenseClassification, StoryAssignment};
 use crate::convex_client::{
     BatchArticleArgs, BatchClassificationArgs, BatchEntityArgs, ConvexClient,
 };
@@ -21,7 +22,7 @@ use uuid::Uuid;
 /// Reads still hit PostgreSQL (migrated separately).
 pub struct NewsRepository {
     convex: Option<ConvexClient>,
-    /// PostgreSQL pool kept for read queries only
+    /// PostgreSQL pool for read queries and story clusters
     db_pool: Option<PgPool>,
 }
 
@@ -91,7 +92,9 @@ impl NewsRepository {
     /// Insert a processed article with entities and offenses into Convex.
     ///
     /// Uses `batchIngestArticles` for a single-call write of the article
-    /// plus all its entities and classifications.
+    /// plus all its entities and classifications. When the article was
+    /// clustered, its story id and whether it is the story's primary source
+    /// are recorded in the article metadata.
     ///
     /// Returns an error if no Convex client is configured (read-only mode).
     pub async fn insert_article(
@@ -99,6 +102,7 @@ impl NewsRepository {
         article: &FetchedArticle,
         entities: &[ExtractedEntity],
         offenses: &[OffenseClassification],
+        story: Option<&StoryAssignment>,
     ) -> Result<Uuid> {
         let convex = self
             .convex
@@ -165,6 +169,11 @@ impl NewsRepository {
             metadata: Some(serde_json::json!({
                 "authors": article.authors,
                 "categories": article.categories,
+                "storyId": story.map(|s| s.story_id.to_string()),
+                "isStoryPrimary": story.map(|s| s.is_primary),
+                "replacesStoryPrimary": story
+                    .and_then(|s| s.previous_primary)
+                    .map(|id| id.to_string()),
             })),
             entities: if batch_entities.is_empty() {
                 None
@@ -196,6 +205,93 @@ impl NewsRepository {
         Ok(article.id)
     }
 
+    /// Record an article's story cluster in PostgreSQL.
+    ///
+    /// Upserts the `news_stories` row, mirrors the article into
+    /// `news_articles` with its `story_id`, and clears the primary flag of the
+    /// article it replaced. Evidence with the same URL picks up the story
+    /// through the `offense_evidence` trigger (migration 065).
+    pub async fn record_story(
+        &self,
+        article: &FetchedArticle,
+        story: &StoryAssignment,
+    ) -> Result<()> {
+        let pool = self
+            .db_pool
+            .as_ref()
+            .context("PostgreSQL pool not available for story clusters")?;
+        let seen_at = article.published_at.unwrap_or(article.fetched_at);
+
+        let mut tx = pool.begin().await.context("Failed to start transaction")?;
+
+        // The story row goes first because news_articles.story_id references it
+        sqlx::query(
+            r#"
+            INSERT INTO news_stories (id, article_count, first_seen_at, last_seen_at)
+            VALUES ($1, $2, $3, $3)
+            ON CONFLICT (id) DO UPDATE
+            SET article_count = GREATEST(news_stories.article_count, EXCLUDED.article_count),
+                first_seen_at = LEAST(news_stories.first_seen_at, EXCLUDED.first_seen_at),
+                last_seen_at = GREATEST(news_stories.last_seen_at, EXCLUDED.last_seen_at)
+            "#,
+        )
+        .bind(story.story_id)
+        .bind(story.article_count as i32)
+        .bind(seen_at)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to upsert news story")?;
+
+        let article_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO news_articles (
+                id, url, title, content, author, published_at, fetched_at,
+                image_url, processing_status, processed_at, story_id, is_story_primary
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 'completed', NOW(), $9, $10)
+            ON CONFLICT (url) DO UPDATE
+            SET story_id = EXCLUDED.story_id,
+                is_story_primary = EXCLUDED.is_story_primary
+            RETURNING id
+            "#,
+        )
+        .bind(article.id)
+        .bind(&article.url)
+        .bind(&article.title)
+        .bind(&article.content)
+        .bind(article.authors.first())
+        .bind(article.published_at)
+        .bind(article.fetched_at)
+        .bind(&article.image_url)
+        .bind(story.story_id)
+        .bind(story.is_primary)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to record article story")?;
+
+        if story.is_primary {
+            sqlx::query("UPDATE news_stories SET primary_article_id = $2 WHERE id = $1")
+                .bind(story.story_id)
+                .bind(article_id)
+                .execute(&mut *tx)
+                .await
+                .context("Failed to set story primary")?;
+        }
+        if let Some(previous) = story.previous_primary {
+            sqlx::query(
+                "UPDATE news_articles SET is_story_primary = FALSE WHERE id = $1 AND story_id = $2",
+            )
+            .bind(previous)
+            .bind(story.story_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to clear previous story primary")?;
+        }
+
+        tx.commit().await.context("Failed to commit story")?;
+        Ok(())
+    }
+
     /// Log a fetch operation.
     ///
     /// Previously wrote to `news_fetch_log` in PostgreSQL. Now emits a
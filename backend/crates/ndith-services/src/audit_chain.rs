@@ -0,0 +1,791 @@
+//! Tamper-evident audit log
+//!
+//! Rows in `audit_log` are chained per tenant by a database trigger: each
+//! one carries a sequence number, a SHA-256 of its canonical content, and an
+//! entry hash over the previous entry's hash and its own content hash. This
+//! service signs checkpoints of each chain's head with an HMAC key the
+//! database never sees, verifies ranges of a chain against their content and
+//! the checkpoints, and exports ranges as JSON Lines that can be verified
+//! offline.
+//!
+//! Anonymising an entry rewrites its content, so a redacted entry's content
+//! is not checked against its hash. Each redaction must also be recorded in
+//! the append-only `audit_log_redactions` table; a redacted entry without a
+//! record is reported as tampering.
+//!
+//! An entry's content is the JSON array built by `CONTENT_SQL`; its hash is
+//! `sha256(content)` and its entry hash is `sha256(prev_hash || content_hash)`
+//! over the hex strings, starting from `GENESIS_HASH`.
+
+use chrono::{DateTime, SubsecRound, Utc};
+use hmac::{Hmac, Mac};
+use ndith_core::config::AuditChainConfig;
+use ndith_core::{AppError, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// `prev_hash` of the first entry in every chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Tenant chained when a writer doesn't set one
+pub const DEFAULT_TENANT: &str = "default";
+
+/// Largest range a single export may cover
+pub const MAX_EXPORT_ENTRIES: i64 = 100_000;
+
+const VERIFY_BATCH_SIZE: i64 = 1_000;
+
+/// Canonical content of an entry. Must build the same array as
+/// `audit_log_content()` in migration 060, which the trigger hashes; it's
+/// repeated here so verification doesn't trust a database function.
+const CONTENT_SQL: &str = r#"json_build_array(
+        a.tenant_id,
+        a.chain_seq,
+        a.event_type,
+        a.severity,
+        a.user_id,
+        a.session_id,
+        host(a.ip_address),
+        a.user_agent,
+        a.resource_type,
+        a.resource_id,
+        a.action,
+        a.details,
+        a.before_state,
+        a.after_state,
+        a.old_subject_type,
+        a.old_subject_id,
+        to_char(a.timestamp AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US"Z"'),
+        a.correlation_id
+    )::text"#;
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    to_hex(&Sha256::digest(data))
+}
+
+/// Entry hash linking an entry to its predecessor
+pub fn entry_hash(prev_hash: &str, content_hash: &str) -> String {
+    sha256_hex(format!("{}{}", prev_hash, content_hash).as_bytes())
+}
+
+/// Current head of a tenant's chain
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AuditChainHead {
+    pub tenant_id: String,
+    pub last_seq: i64,
+    pub last_hash: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Signed snapshot of a chain's head
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AuditCheckpoint {
+    pub tenant_id: String,
+    pub chain_seq: i64,
+    pub entry_hash: String,
+    pub key_id: String,
+    pub signature: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct ChainRow {
+    chain_seq: i64,
+    prev_hash: String,
+    content_hash: String,
+    entry_hash: String,
+    redacted_at: Option<DateTime<Utc>>,
+    redaction_recorded: bool,
+    content: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditChainIssueKind {
+    /// Entries are missing from the chain
+    Gap,
+    /// An entry's prev_hash isn't its predecessor's entry hash
+    BrokenLink,
+    /// An entry's content no longer matches its content hash
+    ContentMismatch,
+    /// An entry's stored hashes don't match each other
+    HashMismatch,
+    /// A checkpointed entry's hash differs from the checkpoint
+    CheckpointMismatch,
+    /// A checkpoint's signature doesn't verify
+    InvalidSignature,
+    /// A checkpoint was signed with a key this instance doesn't hold
+    UnknownCheckpointKey,
+    /// An entry is marked redacted but no redaction was recorded
+    UnrecordedRedaction,
+}
+
+/// One problem found while verifying a chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditChainIssue {
+    pub seq: i64,
+    pub kind: AuditChainIssueKind,
+    pub detail: String,
+}
+
+/// Outcome of verifying a range of a chain
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditVerificationReport {
+    pub tenant_id: String,
+    pub from_seq: i64,
+    pub to_seq: i64,
+    pub head_seq: i64,
+    pub entries_checked: i64,
+    pub checkpoints_checked: i64,
+    /// Entries anonymised after they were written, with a redaction record;
+    /// only their links are checked
+    pub redacted_seqs: Vec<i64>,
+    pub issues: Vec<AuditChainIssue>,
+    pub valid: bool,
+}
+
+/// Walks a chain in sequence order, recording anything that doesn't link up
+struct ChainVerifier {
+    expected_seq: i64,
+    prev_hash: Option<String>,
+    checkpoints: BTreeMap<i64, String>,
+    entries_checked: i64,
+    redacted_seqs: Vec<i64>,
+    issues: Vec<AuditChainIssue>,
+}
+
+impl ChainVerifier {
+    /// `prev_hash` is the entry hash before `from_seq`, if known
+    fn new(from_seq: i64, prev_hash: Option<String>, checkpoints: BTreeMap<i64, String>) -> Self {
+        Self {
+            expected_seq: from_seq,
+            prev_hash,
+            checkpoints,
+            entries_checked: 0,
+            redacted_seqs: Vec::new(),
+            issues: Vec::new(),
+        }
+    }
+
+    fn issue(&mut self, seq: i64, kind: AuditChainIssueKind, detail: String) {
+        self.issues.push(AuditChainIssue { seq, kind, detail });
+    }
+
+    fn push(&mut self, row: &ChainRow) {
+        let seq = row.chain_seq;
+
+        if seq != self.expected_seq {
+            self.issue(
+                self.expected_seq,
+                AuditChainIssueKind::Gap,
+                format!("entries {} to {} are missing", self.expected_seq, seq - 1),
+            );
+        } else if let Some(prev) = &self.prev_hash {
+            if prev != &row.prev_hash {
+                self.issue(
+                    seq,
+                    AuditChainIssueKind::BrokenLink,
+                    "prev_hash does not match the previous entry".to_string(),
+                );
+            }
+        }
+
+        if row.redacted_at.is_some() && row.redaction_recorded {
+            self.redacted_seqs.push(seq);
+        } else if row.redacted_at.is_some() {
+            self.issue(
+                seq,
+                AuditChainIssueKind::UnrecordedRedaction,
+                "entry is marked redacted but no redaction was recorded".to_string(),
+            );
+        } else if sha256_hex(row.content.as_bytes()) != row.content_hash {
+            self.issue(
+                seq,
+                AuditChainIssueKind::ContentMismatch,
+                "entry was modified after it was written".to_string(),
+            );
+        }
+
+        if entry_hash(&row.prev_hash, &row.content_hash) != row.entry_hash {
+            self.issue(
+                seq,
+                AuditChainIssueKind::HashMismatch,
+                "entry_hash does not cover prev_hash and content_hash".to_string(),
+            );
+        }
+
+        if let Some(expected) = self.checkpoints.remove(&seq) {
+            if expected != row.entry_hash {
+                self.issue(
+                    seq,
+                    AuditChainIssueKind::CheckpointMismatch,
+                    "entry hash differs from the signed checkpoint".to_string(),
+                );
+            }
+        }
+
+        self.entries_checked += 1;
+        self.expected_seq = seq + 1;
+        self.prev_hash = Some(row.entry_hash.clone());
+    }
+
+    /// Close the walk at `to_seq`; anything expected but never seen is a gap
+    fn finish(mut self, to_seq: i64) -> (i64, Vec<i64>, Vec<AuditChainIssue>) {
+        if self.expected_seq <= to_seq {
+            self.issue(
+                self.expected_seq,
+                AuditChainIssueKind::Gap,
+                format!("entries {} to {} are missing", self.expected_seq, to_seq),
+            );
+        }
+        for seq in std::mem::take(&mut self.checkpoints).into_keys() {
+            self.issue(
+                seq,
+                AuditChainIssueKind::CheckpointMismatch,
+                "checkpointed entry is missing".to_string(),
+            );
+        }
+        (self.entries_checked, self.redacted_seqs, self.issues)
+    }
+}
+
+/// Signs checkpoints with a key derived from the configured secret
+#[derive(Clone)]
+struct CheckpointSigner {
+    key: Vec<u8>,
+    key_id: String,
+}
+
+impl CheckpointSigner {
+    fn new(secret: &str) -> Self {
+        // Derive a dedicated key so a shared fallback secret is never used
+        // directly as an HMAC key for two purposes
+        let mut mac =
+            HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(b"ndith-audit-checkpoint-v1");
+        let key = mac.finalize().into_bytes().to_vec();
+        let key_id = sha256_hex(&key)[..16].to_string();
+        Self { key, key_id }
+    }
+
+    fn sign(
+        &self,
+        tenant_id: &str,
+        seq: i64,
+        entry_hash: &str,
+        created_at: DateTime<Utc>,
+    ) -> String {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(
+            format!(
+                "{}\n{}\n{}\n{}",
+                tenant_id,
+                seq,
+                entry_hash,
+                created_at.timestamp_micros()
+            )
+            .as_bytes(),
+        );
+        to_hex(&mac.finalize().into_bytes())
+    }
+
+    fn check(&self, checkpoint: &AuditCheckpoint) -> Option<AuditChainIssue> {
+        if checkpoint.key_id != self.key_id {
+            return Some(AuditChainIssue {
+                seq: checkpoint.chain_seq,
+                kind: AuditChainIssueKind::UnknownCheckpointKey,
+                detail: format!("signed with key {}", checkpoint.key_id),
+            });
+        }
+        let expected = self.sign(
+            &checkpoint.tenant_id,
+            checkpoint.chain_seq,
+            &checkpoint.entry_hash,
+            checkpoint.created_at,
+        );
+        if expected != checkpoint.signature {
+            return Some(AuditChainIssue {
+                seq: checkpoint.chain_seq,
+                kind: AuditChainIssueKind::InvalidSignature,
+                detail: "checkpoint signature does not verify".to_string(),
+            });
+        }
+        None
+    }
+}
+
+/// Checkpoints, verification and export for the audit hash chains
+pub struct AuditChainService {
+    db_pool: PgPool,
+    config: AuditChainConfig,
+    signer: CheckpointSigner,
+}
+
+impl AuditChainService {
+    pub fn new(db_pool: PgPool, config: AuditChainConfig) -> Self {
+        let signer = CheckpointSigner::new(&config.checkpoint_secret);
+        Self {
+            db_pool,
+            config,
+            signer,
+        }
+    }
+
+    pub fn config(&self) -> &AuditChainConfig {
+        &self.config
+    }
+
+    /// Identifies the checkpoint key without revealing it
+    pub fn key_id(&self) -> &str {
+        &self.signer.key_id
+    }
+
+    /// Heads of every chain
+    pub async fn heads(&self) -> Result<Vec<AuditChainHead>> {
+        let heads = sqlx::query_as::<_, AuditChainHead>(
+            "SELECT tenant_id, last_seq, last_hash, updated_at FROM audit_chain_heads ORDER BY tenant_id",
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(heads)
+    }
+
+    async fn head(&self, tenant_id: &str) -> Result<AuditChainHead> {
+        sqlx::query_as::<_, AuditChainHead>(
+            "SELECT tenant_id, last_seq, last_hash, updated_at FROM audit_chain_heads WHERE tenant_id = $1",
+        )
+        .bind(tenant_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            resource: format!("Audit chain {}", tenant_id),
+        })
+    }
+
+    /// Sign the tenant's current head, unless it's already checkpointed
+    pub async fn checkpoint(&self, tenant_id: &str) -> Result<Option<AuditCheckpoint>> {
+        let head = self.head(tenant_id).await?;
+        let created_at = Utc::now().trunc_subsecs(6);
+        let signature = self
+            .signer
+            .sign(tenant_id, head.last_seq, &head.last_hash, created_at);
+
+        let checkpoint = sqlx::query_as::<_, AuditCheckpoint>(
+            r#"
+            INSERT INTO audit_checkpoints (tenant_id, chain_seq, entry_hash, key_id, signature, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (tenant_id, chain_seq) DO NOTHING
+            RETURNING tenant_id, chain_seq, entry_hash, key_id, signature, created_at
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(head.last_seq)
+        .bind(&head.last_hash)
+        .bind(&self.signer.key_id)
+        .bind(&signature)
+        .bind(created_at)
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        if let Some(checkpoint) = &checkpoint {
+            tracing::info!(
+                tenant_id = %tenant_id,
+                seq = checkpoint.chain_seq,
+                "Audit chain checkpoint signed"
+            );
+        }
+        Ok(checkpoint)
+    }
+
+    /// Checkpoint every chain that moved since its last checkpoint
+    pub async fn checkpoint_all(&self) -> Result<Vec<AuditCheckpoint>> {
+        let mut written = Vec::new();
+        for head in self.heads().await? {
+            if let Some(checkpoint) = self.checkpoint(&head.tenant_id).await? {
+                written.push(checkpoint);
+            }
+        }
+        Ok(written)
+    }
+
+    /// Checkpoint on an interval in the background
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let period =
+                std::time::Duration::from_secs(self.config.checkpoint_interval_minutes * 60);
+            let mut ticker = tokio::time::interval(period);
+
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.checkpoint_all().await {
+                    tracing::error!(error = %e, "Audit chain checkpoint failed");
+                }
+            }
+        })
+    }
+
+    async fn checkpoints(
+        &self,
+        tenant_id: &str,
+        from_seq: i64,
+        to_seq: i64,
+    ) -> Result<Vec<AuditCheckpoint>> {
+        let checkpoints = sqlx::query_as::<_, AuditCheckpoint>(
+            r#"
+            SELECT tenant_id, chain_seq, entry_hash, key_id, signature, created_at
+            FROM audit_checkpoints
+            WHERE tenant_id = $1 AND chain_seq BETWEEN $2 AND $3
+            ORDER BY chain_seq
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(from_seq)
+        .bind(to_seq)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(checkpoints)
+    }
+
+    async fn rows(&self, tenant_id: &str, after_seq: i64, to_seq: i64) -> Result<Vec<ChainRow>> {
+        let rows = sqlx::query_as::<_, ChainRow>(&format!(
+            r#"
+            SELECT a.chain_seq, a.prev_hash, a.content_hash, a.entry_hash, a.redacted_at,
+                   EXISTS (
+                       SELECT 1 FROM audit_log_redactions r
+                       WHERE r.tenant_id = a.tenant_id AND r.chain_seq = a.chain_seq
+                   ) AS redaction_recorded,
+                   {} AS content
+            FROM audit_log a
+            WHERE a.tenant_id = $1 AND a.chain_seq > $2 AND a.chain_seq <= $3
+            ORDER BY a.chain_seq
+            LIMIT $4
+            "#,
+            CONTENT_SQL
+        ))
+        .bind(tenant_id)
+        .bind(after_seq)
+        .bind(to_seq)
+        .bind(VERIFY_BATCH_SIZE)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Resolve an optional range against the chain's head
+    fn range(
+        head: &AuditChainHead,
+        from_seq: Option<i64>,
+        to_seq: Option<i64>,
+    ) -> Result<(i64, i64)> {
+        let from_seq = from_seq.unwrap_or(1);
+        let to_seq = to_seq.unwrap_or(head.last_seq);
+        if from_seq < 1 || to_seq < from_seq {
+            return Err(AppError::InvalidFieldValue {
+                field: "from_seq".to_string(),
+                message: "Range must start at 1 or later and not end before it starts".to_string(),
+            });
+        }
+        Ok((from_seq, to_seq))
+    }
+
+    /// Verify entries `from_seq..=to_seq` of a tenant's chain (the whole
+    /// chain by default): every entry present and linked to the one before,
+    /// content matching its hash, and every checkpoint in range signed by
+    /// this instance's key and matching its entry.
+    pub async fn verify(
+        &self,
+        tenant_id: &str,
+        from_seq: Option<i64>,
+        to_seq: Option<i64>,
+    ) -> Result<AuditVerificationReport> {
+        let head = self.head(tenant_id).await?;
+        let (from_seq, to_seq) = Self::range(&head, from_seq, to_seq)?;
+
+        // Without the entry before the range, its first link is taken on trust
+        let prev_hash = if from_seq == 1 {
+            Some(GENESIS_HASH.to_string())
+        } else {
+            None
+        };
+
+        let checkpoints = self.checkpoints(tenant_id, from_seq, to_seq).await?;
+        let mut issues: Vec<AuditChainIssue> = checkpoints
+            .iter()
+            .filter_map(|checkpoint| self.signer.check(checkpoint))
+            .collect();
+        let checkpoints_checked = checkpoints.len() as i64;
+        let mut verifier = ChainVerifier::new(
+            from_seq,
+            prev_hash,
+            checkpoints
+                .into_iter()
+                .map(|c| (c.chain_seq, c.entry_hash))
+                .collect(),
+        );
+
+        let mut after_seq = from_seq - 1;
+        loop {
+            let rows = self.rows(tenant_id, after_seq, to_seq).await?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            after_seq = last.chain_seq;
+            for row in &rows {
+                verifier.push(row);
+            }
+        }
+
+        let (entries_checked, redacted_seqs, chain_issues) = verifier.finish(to_seq);
+        issues.extend(chain_issues);
+        issues.sort_by_key(|issue| issue.seq);
+
+        if !issues.is_empty() {
+            tracing::warn!(
+                tenant_id = %tenant_id,
+                from_seq,
+                to_seq,
+                issues = issues.len(),
+                "Audit chain verification failed"
+            );
+        }
+
+        Ok(AuditVerificationReport {
+            tenant_id: tenant_id.to_string(),
+            from_seq,
+            to_seq,
+            head_seq: head.last_seq,
+            entries_checked,
+            checkpoints_checked,
+            redacted_seqs,
+            valid: issues.is_empty(),
+            issues,
+        })
+    }
+
+    /// Export entries `from_seq..=to_seq` as JSON Lines: a header, one line
+    /// per entry with its canonical content and hashes, then the signed
+    /// checkpoints in range. Hashing each entry's `content` string and
+    /// chaining from the header's `prev_hash` reproduces every entry hash.
+    pub async fn export_jsonl(
+        &self,
+        tenant_id: &str,
+        from_seq: Option<i64>,
+        to_seq: Option<i64>,
+    ) -> Result<String> {
+        let head = self.head(tenant_id).await?;
+        let (from_seq, to_seq) = Self::range(&head, from_seq, to_seq)?;
+        if to_seq - from_seq + 1 > MAX_EXPORT_ENTRIES {
+            return Err(AppError::InvalidFieldValue {
+                field: "to_seq".to_string(),
+                message: format!("An export covers at most {} entries", MAX_EXPORT_ENTRIES),
+            });
+        }
+
+        let mut rows = Vec::new();
+        let mut after_seq = from_seq - 1;
+        loop {
+            let batch = self.rows(tenant_id, after_seq, to_seq).await?;
+            let Some(last) = batch.last() else {
+                break;
+            };
+            after_seq = last.chain_seq;
+            rows.extend(batch);
+        }
+        let checkpoints = self.checkpoints(tenant_id, from_seq, to_seq).await?;
+
+        let mut lines = Vec::with_capacity(rows.len() + checkpoints.len() + 1);
+        lines.push(serde_json::json!({
+            "type": "header",
+            "tenant_id": tenant_id,
+            "from_seq": from_seq,
+            "to_seq": to_seq,
+            "head_seq": head.last_seq,
+            "prev_hash": rows.first().map(|r| r.prev_hash.as_str()),
+            "hash_algorithm": "sha256",
+            "entry_hash": "sha256(prev_hash || content_hash)",
+            "checkpoint_key_id": self.signer.key_id,
+            "exported_at": Utc::now(),
+        }));
+        for row in &rows {
+            lines.push(serde_json::json!({
+                "type": "entry",
+                "seq": row.chain_seq,
+                "prev_hash": row.prev_hash,
+                "content_hash": row.content_hash,
+                "entry_hash": row.entry_hash,
+                "redacted": row.redacted_at.is_some(),
+                "content": row.content,
+            }));
+        }
+        for checkpoint in &checkpoints {
+            lines.push(serde_json::json!({
+                "type": "checkpoint",
+                "seq": checkpoint.chain_seq,
+                "entry_hash": checkpoint.entry_hash,
+                "key_id": checkpoint.key_id,
+                "signature": checkpoint.signature,
+                "created_at": checkpoint.created_at,
+            }));
+        }
+
+        let mut out = String::new();
+        for line in lines {
+            out.push_str(&line.to_string());
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(len: i64) -> Vec<ChainRow> {
+        let mut prev = GENESIS_HASH.to_string();
+        (1..=len)
+            .map(|seq| {
+                let content = format!(r#"["default", {}, "user_login"]"#, seq);
+                let content_hash = sha256_hex(content.as_bytes());
+                let hash = entry_hash(&prev, &content_hash);
+                let row = ChainRow {
+                    chain_seq: seq,
+                    prev_hash: prev.clone(),
+                    content_hash,
+                    entry_hash: hash.clone(),
+                    redacted_at: None,
+                    redaction_recorded: false,
+                    content,
+                };
+                prev = hash;
+                row
+            })
+            .collect()
+    }
+
+    fn verify(rows: &[ChainRow], checkpoints: BTreeMap<i64, String>) -> Vec<AuditChainIssue> {
+        let mut verifier = ChainVerifier::new(1, Some(GENESIS_HASH.to_string()), checkpoints);
+        for row in rows {
+            verifier.push(row);
+        }
+        verifier.finish(rows.len() as i64).2
+    }
+
+    fn kinds(issues: &[AuditChainIssue]) -> Vec<(i64, AuditChainIssueKind)> {
+        issues.iter().map(|i| (i.seq, i.kind)).collect()
+    }
+
+    #[test]
+    fn test_intact_chain_verifies() {
+        let rows = chain(5);
+        let checkpoints = BTreeMap::from([(5, rows[4].entry_hash.clone())]);
+        assert!(verify(&rows, checkpoints).is_empty());
+    }
+
+    #[test]
+    fn test_detects_edit_gap_and_truncation() {
+        let mut rows = chain(5);
+        rows[1].content = rows[1].content.replace("user_login", "user_logout");
+        assert_eq!(
+            kinds(&verify(&rows, BTreeMap::new())),
+            vec![(2, AuditChainIssueKind::ContentMismatch)]
+        );
+
+        // Rehashing the edited entry breaks the link to the next one
+        rows[1].content_hash = sha256_hex(rows[1].content.as_bytes());
+        rows[1].entry_hash = entry_hash(&rows[1].prev_hash, &rows[1].content_hash);
+        assert_eq!(
+            kinds(&verify(&rows, BTreeMap::new())),
+            vec![(3, AuditChainIssueKind::BrokenLink)]
+        );
+
+        let mut rows = chain(5);
+        rows.remove(2);
+        assert_eq!(
+            kinds(&verify(&rows, BTreeMap::new())),
+            vec![(3, AuditChainIssueKind::Gap)]
+        );
+
+        // A dropped tail is only visible against the checkpoint
+        let rows = chain(5);
+        let checkpoints = BTreeMap::from([(5, rows[4].entry_hash.clone())]);
+        let mut verifier = ChainVerifier::new(1, Some(GENESIS_HASH.to_string()), checkpoints);
+        for row in &rows[..3] {
+            verifier.push(row);
+        }
+        assert_eq!(
+            kinds(&verifier.finish(5).2),
+            vec![
+                (4, AuditChainIssueKind::Gap),
+                (5, AuditChainIssueKind::CheckpointMismatch)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redacted_entries_keep_their_links() {
+        let mut rows = chain(3);
+        rows[1].content = r#"["default", 2, null]"#.to_string();
+        rows[1].redacted_at = Some(Utc::now());
+        rows[1].redaction_recorded = true;
+
+        let mut verifier = ChainVerifier::new(1, Some(GENESIS_HASH.to_string()), BTreeMap::new());
+        for row in &rows {
+            verifier.push(row);
+        }
+        let (checked, redacted, issues) = verifier.finish(3);
+        assert_eq!(checked, 3);
+        assert_eq!(redacted, vec![2]);
+        assert!(issues.is_empty());
+
+        // Marking an edit as redacted without recording it doesn't hide it
+        rows[1].redaction_recorded = false;
+        let mut verifier = ChainVerifier::new(1, Some(GENESIS_HASH.to_string()), BTreeMap::new());
+        for row in &rows {
+            verifier.push(row);
+        }
+        let (_, redacted, issues) = verifier.finish(3);
+        assert!(redacted.is_empty());
+        assert_eq!(
+            kinds(&issues),
+            vec![(2, AuditChainIssueKind::UnrecordedRedaction)]
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_signatures() {
+        let signer = CheckpointSigner::new("test-secret");
+        let created_at = Utc::now().trunc_subsecs(6);
+        let mut checkpoint = AuditCheckpoint {
+            tenant_id: DEFAULT_TENANT.to_string(),
+            chain_seq: 7,
+            entry_hash: GENESIS_HASH.to_string(),
+            key_id: signer.key_id.clone(),
+            signature: signer.sign(DEFAULT_TENANT, 7, GENESIS_HASH, created_at),
+            created_at,
+        };
+        assert!(signer.check(&checkpoint).is_none());
+
+        checkpoint.chain_seq = 6;
+        assert_eq!(
+            signer.check(&checkpoint).map(|i| i.kind),
+            Some(AuditChainIssueKind::InvalidSignature)
+        );
+
+        checkpoint.key_id = CheckpointSigner::new("other-secret").key_id;
+        assert_eq!(
+            signer.check(&checkpoint).map(|i| i.kind),
+            Some(AuditChainIssueKind::UnknownCheckpointKey)
+        );
+    }
+}
@@ -1,4 +1,7 @@
+use crate::jwt_keyring::JwtKeyring;
+use crate::kms::{create_kms_provider, require_kms_provider, KmsProvider};
 use crate::login_performance::LoginPerformanceService;
+use crate::notification_service::NotificationService;
 use crate::oauth::{OAuthProvider, OAuthStateManager};
 use crate::oauth_apple::AppleOAuthProvider;
 use crate::oauth_config_validator::OAuthConfigValidator;
@@ -11,26 +14,35 @@ use crate::oauth_health_monitor::{
 };
 use crate::oauth_security_logger::OAuthSecurityLogger;
 use crate::oauth_youtube_music::YouTubeMusicOAuthProvider;
+use crate::oidc_provider::OidcProviderService;
+use crate::recovery_codes;
 use crate::registration_performance::RegistrationPerformanceService;
+use crate::webauthn::{
+    AuthenticationChallenge, RegistrationChallenge, WebAuthnCredential, WebAuthnService,
+};
 use anyhow::anyhow;
-use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use bcrypt::{hash, verify};
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use ndith_core::config::{
+    public_frontend_base_url, ConfigError, Environment, JwtKeyringConfig, UserVerificationPolicy,
+    WebAuthnConfig,
+};
 use ndith_core::models::oauth::{
     AccountLinkRequest, OAuthAccount, OAuthAccountHealth, OAuthConnectionStatus, OAuthFlowResponse,
     OAuthProviderType, OAuthState, OAuthTokenStatus, OAuthTokens, OAuthUserInfo, RefreshPriority,
     TokenExpirationStatus, TokenNotificationTarget, TokenRefreshSchedule, TokenRefreshSummary,
 };
 use ndith_core::models::user::{MergeAccountsRequest, MergeAccountsResponse};
+use ndith_core::models::webauthn::{WebAuthnAssertionRequest, WebAuthnRegisterFinishRequest};
 use ndith_core::models::{
     Claims, CreateUserRequest, LoginRequest, OAuthLoginRequest, RegistrationValidationError,
     TokenPair, TotpSetupResponse, User, UserSession,
 };
 use ndith_core::{AppError, Result};
 use rand::Rng;
-use rsa::pkcs8::DecodePrivateKey;
-use rsa::traits::PublicKeyParts;
 use sqlx::PgPool;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -46,11 +58,8 @@ pub struct AuthService {
     access_token_ttl: i64,  // seconds (24 hours)
     refresh_token_ttl: i64, // seconds (30 days)
 
-    // RS256 JWT signing (for Convex verification via OIDC/JWKS)
-    rsa_encoding_key: Option<EncodingKey>,
-    rsa_decoding_key: Option<DecodingKey>,
-    rsa_public_key_n: Option<String>,
-    rsa_public_key_e: Option<String>,
+    // RS256 JWT signing keys (for Convex verification via OIDC/JWKS)
+    jwt_keyring: Arc<JwtKeyring>,
     jwt_issuer: String,
 
     // Performance optimization services
@@ -65,6 +74,12 @@ pub struct AuthService {
     oauth_health_monitor: Arc<OAuthHealthMonitor>,
     oauth_error_recovery: Arc<OAuthErrorRecoveryService>,
     oauth_security_logger: Arc<OAuthSecurityLogger>,
+
+    // WebAuthn passkeys
+    webauthn: Arc<WebAuthnService>,
+
+    // OpenID Connect provider for partner apps
+    oidc: Arc<OidcProviderService>,
 }
 
 fn apply_convex_claims(claims: &mut Claims, issuer: &str) {
@@ -74,6 +89,24 @@ fn apply_convex_claims(claims: &mut Claims, issuer: &str) {
 
 impl AuthService {
     pub fn new(db_pool: PgPool) -> Self {
+        Self::with_keyring(db_pool, create_kms_provider(), JwtKeyringConfig::default())
+    }
+
+    /// Build the service from the environment, failing on invalid signing
+    /// keyring settings or, outside development, without a real KMS
+    pub fn from_env(db_pool: PgPool, env: Environment) -> std::result::Result<Self, ConfigError> {
+        Ok(Self::with_keyring(
+            db_pool,
+            require_kms_provider(env)?,
+            JwtKeyringConfig::from_env()?,
+        ))
+    }
+
+    fn with_keyring(
+        db_pool: PgPool,
+        kms: Arc<dyn KmsProvider>,
+        keyring_config: JwtKeyringConfig,
+    ) -> Self {
         // Use environment variable or generate a random JWT secret for demo
         let jwt_secret = std::env::var("JWT_SECRET")
             .unwrap_or_else(|_| format!("jwt_secret_{}", rand::thread_rng().gen::<u64>()));
@@ -150,76 +183,54 @@ impl AuthService {
         let jwt_issuer = std::env::var("PUBLIC_BACKEND_BASE_URL")
             .unwrap_or_else(|_| "https://api.nodrakeinthe.house".to_string());
 
-        let (rsa_encoding_key, rsa_decoding_key, rsa_public_key_n, rsa_public_key_e) =
-            match std::env::var("JWT_RSA_PRIVATE_KEY") {
-                Ok(pem) => {
-                    // Normalize PEM: env vars may use literal \n instead of newlines
-                    let pem = pem.replace("\\n", "\n");
-                    match rsa::RsaPrivateKey::from_pkcs8_pem(&pem) {
-                        Ok(private_key) => {
-                            let public_key = private_key.to_public_key();
-                            // Encode the public key as PKCS#1 PEM for jsonwebtoken DecodingKey
-                            let pub_pem = rsa::pkcs1::EncodeRsaPublicKey::to_pkcs1_pem(
-                                &public_key,
-                                rsa::pkcs1::LineEnding::LF,
-                            );
-                            match (
-                                EncodingKey::from_rsa_pem(pem.as_bytes()),
-                                pub_pem.map(|p| DecodingKey::from_rsa_pem(p.as_bytes())),
-                            ) {
-                                (Ok(enc), Ok(Ok(dec))) => {
-                                    let n = URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be());
-                                    let e = URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be());
-                                    tracing::info!(
-                                        "✅ RS256 JWT signing initialized (issuer: {})",
-                                        jwt_issuer
-                                    );
-                                    (Some(enc), Some(dec), Some(n), Some(e))
-                                }
-                                (Err(e), _) => {
-                                    tracing::error!("❌ Failed to create RSA encoding key: {}", e);
-                                    (None, None, None, None)
-                                }
-                                (_, Err(e)) => {
-                                    tracing::error!("❌ Failed to create RSA decoding key: {}", e);
-                                    (None, None, None, None)
-                                }
-                                (_, Ok(Err(e))) => {
-                                    tracing::error!(
-                                        "❌ Failed to create RSA decoding key from PEM: {}",
-                                        e
-                                    );
-                                    (None, None, None, None)
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("❌ Failed to parse RSA private key: {}", e);
-                            (None, None, None, None)
-                        }
+        let access_token_ttl = 24 * 60 * 60; // 24 hours as required
+        let jwt_keyring = Arc::new(JwtKeyring::new(
+            db_pool.clone(),
+            kms,
+            keyring_config,
+            Duration::seconds(access_token_ttl),
+        ));
+
+        // Keys from before the keyring existed sign until the keyring is
+        // loaded, and are imported into it if it's empty
+        match std::env::var("JWT_RSA_PRIVATE_KEY") {
+            Ok(pem) => {
+                // Normalize PEM: env vars may use literal \n instead of newlines
+                let pem = pem.replace("\\n", "\n");
+                match jwt_keyring.load_legacy_key(&pem) {
+                    Ok(()) => {
+                        tracing::info!("✅ RS256 JWT signing initialized (issuer: {})", jwt_issuer)
                     }
+                    Err(e) => tracing::error!("❌ Failed to load RSA private key: {}", e),
                 }
-                Err(_) => {
-                    tracing::info!(
-                        "RS256 JWT signing not configured (JWT_RSA_PRIVATE_KEY not set), using HS256 only"
-                    );
-                    (None, None, None, None)
-                }
-            };
-
-        if rsa_encoding_key.is_none() {
-            tracing::warn!("JWT_RSA_PRIVATE_KEY not configured -- Convex will reject HS256 tokens");
+            }
+            Err(_) => {
+                tracing::info!(
+                    "JWT_RSA_PRIVATE_KEY not set; RS256 signing starts once the keyring is loaded"
+                );
+            }
         }
 
+        let webauthn_config = WebAuthnConfig::from_env().unwrap_or_else(|e| {
+            tracing::error!(
+                "Invalid WebAuthn configuration, using frontend origin: {}",
+                e
+            );
+            WebAuthnConfig::for_origin(&public_frontend_base_url())
+        });
+        let webauthn = Arc::new(WebAuthnService::new(db_pool.clone(), webauthn_config));
+        let oidc = Arc::new(OidcProviderService::new(
+            db_pool.clone(),
+            jwt_keyring.clone(),
+            jwt_issuer.clone(),
+        ));
+
         let auth_service = Self {
             db_pool,
             jwt_secret,
-            access_token_ttl: 24 * 60 * 60, // 24 hours as required
+            access_token_ttl,
             refresh_token_ttl: 30 * 24 * 60 * 60, // 30 days
-            rsa_encoding_key,
-            rsa_decoding_key,
-            rsa_public_key_n,
-            rsa_public_key_e,
+            jwt_keyring,
             jwt_issuer,
             performance_service,
             login_performance_service,
@@ -230,6 +241,8 @@ impl AuthService {
             oauth_health_monitor,
             oauth_error_recovery,
             oauth_security_logger,
+            webauthn,
+            oidc,
         };
 
         // Preload frequent users in background (don't block startup)
@@ -721,15 +734,33 @@ impl AuthService {
 
         // Check 2FA if enabled (keep this synchronous for security)
         if cached_user.totp_enabled {
-            let totp_code = request
-                .totp_code
-                .ok_or_else(|| AppError::TwoFactorRequired)?;
+            // A security key or a recovery code can stand in for the TOTP
+            let second_factor_valid = match (&request.webauthn, request.recovery_code.as_deref()) {
+                (Some(assertion), _) => {
+                    self.verify_webauthn_second_factor(cached_user.user_id, assertion)
+                        .await?
+                }
+                (None, Some(recovery_code)) => {
+                    self.redeem_recovery_code(cached_user.user_id, recovery_code)
+                        .await?
+                }
+                (None, None) => {
+                    let totp_code = request
+                        .totp_code
+                        .ok_or_else(|| AppError::TwoFactorRequired)?;
+
+                    let totp_secret =
+                        cached_user.totp_secret.ok_or_else(|| AppError::Internal {
+                            message: Some(
+                                "2FA configuration error. Please contact support".to_string(),
+                            ),
+                        })?;
 
-            let totp_secret = cached_user.totp_secret.ok_or_else(|| AppError::Internal {
-                message: Some("2FA configuration error. Please contact support".to_string()),
-            })?;
+                    self.verify_totp(&totp_secret, &totp_code)?
+                }
+            };
 
-            if !self.verify_totp(&totp_secret, &totp_code)? {
+            if !second_factor_valid {
                 // Record failed 2FA attempt
                 let login_time = login_start.elapsed().as_millis() as f64;
                 if let Err(e) = self
@@ -757,23 +788,10 @@ impl AuthService {
             .await?;
 
         // Generate access token using the shared helper (RS256 when configured)
-        let mut access_claims = Claims::new_access_token(
-            cached_user.user_id,
-            cached_user.email.clone(),
-            self.access_token_ttl,
-        );
-        apply_convex_claims(&mut access_claims, &self.jwt_issuer);
-        let access_token = if let Some(ref rsa_key) = self.rsa_encoding_key {
-            let mut header = Header::new(Algorithm::RS256);
-            header.kid = Some("ndith-1".to_string());
-            encode(&header, &access_claims, rsa_key)?
-        } else {
-            encode(
-                &Header::default(),
-                &access_claims,
-                &EncodingKey::from_secret(self.jwt_secret.as_ref()),
-            )?
-        };
+        let access_claims = self
+            .access_claims(cached_user.user_id, &cached_user.email)
+            .await?;
+        let access_token = self.encode_access_token(&access_claims)?;
 
         // Batch all database operations in a single transaction
         self.login_performance_service
@@ -1447,26 +1465,28 @@ impl AuthService {
     }
 
     // Generate JWT token pair with database storage
-    async fn generate_token_pair(&self, user_id: Uuid, email: &str) -> Result<TokenPair> {
-        // Generate access token (24-hour expiration as required)
-        let mut access_claims =
+    /// Access token claims for a user, with the issuer and audience Convex
+    /// requires and the account's current email verification state
+    async fn access_claims(&self, user_id: Uuid, email: &str) -> Result<Claims> {
+        let mut claims =
             Claims::new_access_token(user_id, email.to_string(), self.access_token_ttl);
 
         // Convex requires issuer and audience claims regardless of signing algorithm.
-        apply_convex_claims(&mut access_claims, &self.jwt_issuer);
+        apply_convex_claims(&mut claims, &self.jwt_issuer);
 
-        // Use RS256 if RSA key is configured, otherwise HS256
-        let access_token = if let Some(ref rsa_key) = self.rsa_encoding_key {
-            let mut header = Header::new(Algorithm::RS256);
-            header.kid = Some("ndith-1".to_string());
-            encode(&header, &access_claims, rsa_key)?
-        } else {
-            encode(
-                &Header::default(),
-                &access_claims,
-                &EncodingKey::from_secret(self.jwt_secret.as_ref()),
-            )?
-        };
+        let email_verified: Option<Option<bool>> =
+            sqlx::query_scalar("SELECT email_verified FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await?;
+        claims.email_verified = email_verified.flatten().unwrap_or(false);
+        Ok(claims)
+    }
+
+    async fn generate_token_pair(&self, user_id: Uuid, email: &str) -> Result<TokenPair> {
+        // Generate access token (24-hour expiration as required)
+        let access_claims = self.access_claims(user_id, email).await?;
+        let access_token = self.encode_access_token(&access_claims)?;
 
         // Generate refresh token
         let refresh_token_raw = format!("{}_{}", Uuid::new_v4(), rand::thread_rng().gen::<u64>());
@@ -1532,37 +1552,56 @@ impl AuthService {
             .await
     }
 
-    // Verify JWT token (tries RS256 first if configured, falls back to HS256)
+    // Sign with the keyring's current RS256 key, or HS256 if there is none
+    fn encode_access_token(&self, claims: &Claims) -> Result<String> {
+        let token = match self.jwt_keyring.signing_key() {
+            Some((kid, key)) => {
+                let mut header = Header::new(Algorithm::RS256);
+                header.kid = Some(kid);
+                encode(&header, claims, &key)?
+            }
+            None => encode(
+                &Header::default(),
+                claims,
+                &EncodingKey::from_secret(self.jwt_secret.as_ref()),
+            )?,
+        };
+        Ok(token)
+    }
+
+    // Verify JWT token (RS256 by kid from the keyring, HS256 otherwise)
     pub fn verify_token(&self, token: &str) -> Result<Claims> {
-        // Try RS256 verification first if RSA key is configured
-        if let Some(ref rsa_dec_key) = self.rsa_decoding_key {
+        let header = decode_header(token).map_err(|_| AppError::TokenInvalid)?;
+
+        let token_data = if header.alg == Algorithm::RS256 {
+            let kid = header.kid.ok_or(AppError::TokenInvalid)?;
+            let key = self.jwt_keyring.decoding_key(&kid).ok_or_else(|| {
+                tracing::warn!(kid = %kid, "RS256 token signed with unknown or retired key");
+                AppError::TokenInvalid
+            })?;
+
             let mut validation = Validation::new(Algorithm::RS256);
-            // Backend is both issuer and consumer — signature verification is
-            // sufficient. Convex validates iss/aud independently via OIDC.
-            validation.validate_aud = false;
-            match decode::<Claims>(token, rsa_dec_key, &validation) {
-                Ok(token_data) => {
-                    let claims = token_data.claims;
-                    if claims.is_expired() {
-                        return Err(AppError::TokenExpired);
-                    }
-                    return Ok(claims);
+            // The same keys sign ID tokens for OIDC clients, whose audience is
+            // the client_id; only our own access tokens are accepted here
+            validation.set_audience(&["convex"]);
+            decode::<Claims>(token, &key, &validation).map_err(|e| {
+                tracing::warn!("RS256 token verification failed: {}", e);
+                match e.kind() {
+                    jsonwebtoken::errors::ErrorKind::InvalidAudience => AppError::TokenInvalid,
+                    _ => AppError::from(e),
                 }
-                Err(e) => {
-                    tracing::warn!("RS256 token verification failed: {}", e);
-                }
-            }
-        }
-
-        // Fall back to HS256 verification (legacy tokens without aud claim)
-        let mut hs256_validation = Validation::new(Algorithm::HS256);
-        hs256_validation.validate_aud = false;
-        let token_data = decode::<Claims>(
-            token,
-            &DecodingKey::from_secret(self.jwt_secret.as_ref()),
-            &hs256_validation,
-        )
-        .map_err(|_| AppError::TokenInvalid)?;
+            })?
+        } else {
+            // HS256 tokens (no RSA key configured, or legacy tokens without aud claim)
+            let mut hs256_validation = Validation::new(Algorithm::HS256);
+            hs256_validation.validate_aud = false;
+            decode::<Claims>(
+                token,
+                &DecodingKey::from_secret(self.jwt_secret.as_ref()),
+                &hs256_validation,
+            )
+            .map_err(|_| AppError::TokenInvalid)?
+        };
 
         let claims = token_data.claims;
 
@@ -1578,21 +1617,14 @@ impl AuthService {
         &self.jwt_issuer
     }
 
-    /// Returns the JWKS JSON response containing the RSA public key
+    /// Keyring holding the RS256 signing keys
+    pub fn jwt_keyring(&self) -> &Arc<JwtKeyring> {
+        &self.jwt_keyring
+    }
+
+    /// Returns the JWKS JSON response with every published signing key
     pub fn jwks_response(&self) -> serde_json::Value {
-        match (&self.rsa_public_key_n, &self.rsa_public_key_e) {
-            (Some(n), Some(e)) => serde_json::json!({
-                "keys": [{
-                    "kty": "RSA",
-                    "use": "sig",
-                    "alg": "RS256",
-                    "kid": "ndith-1",
-                    "n": n,
-                    "e": e,
-                }]
-            }),
-            _ => serde_json::json!({ "keys": [] }),
-        }
+        self.jwt_keyring.jwks()
     }
 
     // Setup TOTP for user with temporary secret storage
@@ -1617,8 +1649,8 @@ impl AuthService {
             secret_b32
         );
 
-        // Generate backup codes
-        let backup_codes = self.generate_backup_codes();
+        // Generate recovery codes; they are only accepted once 2FA is enabled
+        let backup_codes = recovery_codes::generate_recovery_codes();
 
         // Store temporary secret (not enabled until verified)
         // We store it in totp_secret field but keep totp_enabled as false
@@ -1630,6 +1662,8 @@ impl AuthService {
         .execute(&self.db_pool)
         .await?;
 
+        self.replace_recovery_codes(user_id, &backup_codes).await?;
+
         Ok(TotpSetupResponse {
             secret: secret_b32,
             qr_code_url,
@@ -1737,6 +1771,12 @@ impl AuthService {
         .execute(&self.db_pool)
         .await?;
 
+        // Recovery codes only make sense alongside the TOTP they back up
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.db_pool)
+            .await?;
+
         // Invalidate login cache since user data changed
         if let Err(e) = self
             .login_performance_service
@@ -1757,6 +1797,341 @@ impl AuthService {
         Ok(())
     }
 
+    /// Number of unused recovery codes a user has left
+    pub async fn recovery_codes_remaining(&self, user_id: Uuid) -> Result<i64> {
+        let remaining: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM user_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(remaining)
+    }
+
+    /// Issue a new set of recovery codes, invalidating the old set. Requires
+    /// 2FA to be enabled and a valid TOTP.
+    pub async fn regenerate_recovery_codes(
+        &self,
+        user_id: Uuid,
+        totp_code: &str,
+    ) -> Result<Vec<String>> {
+        let (totp_secret, totp_enabled): (Option<String>, Option<bool>) =
+            sqlx::query_as("SELECT totp_secret, totp_enabled FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await?
+                .ok_or_else(|| AppError::NotFound {
+                    resource: "User".to_string(),
+                })?;
+
+        let totp_secret = match totp_secret {
+            Some(secret) if totp_enabled.unwrap_or(false) => secret,
+            _ => {
+                return Err(AppError::OperationNotAllowed {
+                    reason: "2FA is not enabled for this user".to_string(),
+                })
+            }
+        };
+
+        if !self.verify_totp(&totp_secret, totp_code)? {
+            return Err(AppError::TwoFactorInvalid);
+        }
+
+        let codes = recovery_codes::generate_recovery_codes();
+        self.replace_recovery_codes(user_id, &codes).await?;
+
+        self.log_audit_event(
+            user_id,
+            "recovery_codes_regenerated",
+            "user",
+            &user_id.to_string(),
+        )
+        .await?;
+
+        tracing::info!(user_id = %user_id, "Recovery codes regenerated");
+
+        Ok(codes)
+    }
+
+    /// Replace a user's recovery codes with a new set
+    async fn replace_recovery_codes(&self, user_id: Uuid, codes: &[String]) -> Result<()> {
+        let hashes = codes
+            .iter()
+            .map(|code| recovery_codes::hash_recovery_code(code))
+            .collect::<Option<Vec<String>>>()
+            .ok_or_else(|| AppError::Internal {
+                message: Some("Generated an invalid recovery code".to_string()),
+            })?;
+
+        let mut tx = self.db_pool.begin().await?;
+
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO user_recovery_codes (user_id, code_hash) SELECT $1, UNNEST($2::text[])",
+        )
+        .bind(user_id)
+        .bind(&hashes)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Use up a recovery code in place of a TOTP. Returns false if the code
+    /// is wrong or was already used.
+    pub async fn redeem_recovery_code(&self, user_id: Uuid, code: &str) -> Result<bool> {
+        let Some(code_hash) = recovery_codes::hash_recovery_code(code) else {
+            return Ok(false);
+        };
+
+        // The code is only spent if its use is recorded in the audit log
+        let mut tx = self.db_pool.begin().await?;
+
+        let redeemed = sqlx::query(
+            r#"
+            UPDATE user_recovery_codes SET used_at = NOW()
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(&code_hash)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            > 0;
+
+        if !redeemed {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (user_id, action, old_subject_type, old_subject_id, timestamp)
+            VALUES ($1, 'recovery_code_used', 'user', $2, NOW())
+            "#,
+        )
+        .bind(user_id)
+        .bind(user_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+        let remaining: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM user_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        tracing::warn!(
+            user_id = %user_id,
+            remaining_codes = remaining,
+            "Recovery code used in place of TOTP"
+        );
+
+        if let Err(e) = NotificationService::new(self.db_pool.clone())
+            .notify_recovery_code_used(user_id, remaining)
+            .await
+        {
+            tracing::warn!(user_id = %user_id, "Failed to send recovery code alert: {}", e);
+        }
+
+        Ok(true)
+    }
+
+    // ===== OpenID Connect provider =====
+
+    pub fn oidc(&self) -> &OidcProviderService {
+        &self.oidc
+    }
+
+    // ===== WebAuthn =====
+
+    pub fn webauthn(&self) -> &WebAuthnService {
+        &self.webauthn
+    }
+
+    /// Begin registering a passkey or security key for a signed-in user
+    pub async fn start_webauthn_registration(
+        &self,
+        user_id: Uuid,
+    ) -> Result<RegistrationChallenge> {
+        let user = self.get_user_by_id(user_id).await?;
+        self.webauthn.start_registration(user_id, &user.email).await
+    }
+
+    /// Verify and store a new passkey or security key
+    pub async fn finish_webauthn_registration(
+        &self,
+        user_id: Uuid,
+        request: WebAuthnRegisterFinishRequest,
+    ) -> Result<WebAuthnCredential> {
+        let credential = self
+            .webauthn
+            .finish_registration(
+                user_id,
+                request.challenge_id,
+                request.name.as_deref(),
+                &request.credential,
+            )
+            .await?;
+
+        self.log_audit_event(
+            user_id,
+            "webauthn_credential_registered",
+            "webauthn_credential",
+            &credential.id.to_string(),
+        )
+        .await?;
+
+        if let Err(e) = NotificationService::new(self.db_pool.clone())
+            .notify_webauthn_credential_added(user_id, &credential.name)
+            .await
+        {
+            tracing::warn!(user_id = %user_id, "Failed to send passkey alert: {}", e);
+        }
+
+        tracing::info!(user_id = %user_id, credential = %credential.id, "WebAuthn credential registered");
+
+        Ok(credential)
+    }
+
+    pub async fn delete_webauthn_credential(
+        &self,
+        user_id: Uuid,
+        credential_id: Uuid,
+    ) -> Result<()> {
+        self.webauthn
+            .delete_credential(user_id, credential_id)
+            .await?;
+
+        self.log_audit_event(
+            user_id,
+            "webauthn_credential_removed",
+            "webauthn_credential",
+            &credential_id.to_string(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn find_user_id_by_email(&self, email: &str) -> Result<Option<Uuid>> {
+        Ok(sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+            .bind(email)
+            .fetch_optional(&self.db_pool)
+            .await?)
+    }
+
+    /// Begin a passwordless passkey login. With an email the ceremony is
+    /// limited to that account's credentials; an unknown email gets the same
+    /// response as a discoverable login so accounts can't be probed.
+    pub async fn start_passkey_login(
+        &self,
+        email: Option<&str>,
+    ) -> Result<AuthenticationChallenge> {
+        let user_id = match email {
+            Some(email) => self.find_user_id_by_email(email).await?,
+            None => None,
+        };
+
+        self.webauthn
+            .start_authentication(user_id, UserVerificationPolicy::Required)
+            .await
+    }
+
+    /// Challenge for using a security key as the second factor of a
+    /// password login
+    pub async fn start_webauthn_second_factor(
+        &self,
+        email: &str,
+    ) -> Result<AuthenticationChallenge> {
+        let user_id = self.find_user_id_by_email(email).await?;
+
+        self.webauthn
+            .start_authentication(user_id, self.webauthn.config().user_verification)
+            .await
+    }
+
+    /// Passwordless login with a passkey. User verification on the
+    /// authenticator makes this multi-factor, so TOTP isn't asked for.
+    pub async fn passkey_login(
+        &self,
+        request: WebAuthnAssertionRequest,
+    ) -> Result<ndith_core::models::AuthResponse> {
+        use ndith_core::models::AuthResponse;
+
+        let credential = self
+            .webauthn
+            .finish_authentication(request.challenge_id, &request.credential, true)
+            .await
+            .map_err(|e| match e {
+                AppError::InvalidFieldValue { message, .. } => {
+                    tracing::warn!(reason = %message, "Passkey login rejected");
+                    AppError::InvalidCredentials
+                }
+                other => other,
+            })?;
+
+        let user = self.get_user_by_id(credential.user_id).await?;
+        let token_pair = self.generate_token_pair(user.id, &user.email).await?;
+
+        self.log_audit_event(
+            user.id,
+            "passkey_login",
+            "webauthn_credential",
+            &credential.id.to_string(),
+        )
+        .await?;
+
+        tracing::info!(user_id = %user.id, "User logged in with passkey");
+
+        Ok(AuthResponse {
+            user: user.to_profile(),
+            access_token: token_pair.access_token,
+            refresh_token: token_pair.refresh_token,
+        })
+    }
+
+    /// Check a security key assertion in place of a TOTP. Returns false if it
+    /// fails verification or the key belongs to someone else.
+    async fn verify_webauthn_second_factor(
+        &self,
+        user_id: Uuid,
+        assertion: &WebAuthnAssertionRequest,
+    ) -> Result<bool> {
+        let require_user_verification =
+            self.webauthn.config().user_verification == UserVerificationPolicy::Required;
+
+        match self
+            .webauthn
+            .finish_authentication(
+                assertion.challenge_id,
+                &assertion.credential,
+                require_user_verification,
+            )
+            .await
+        {
+            Ok(credential) if credential.user_id == user_id => Ok(true),
+            Ok(_) => {
+                tracing::warn!(user_id = %user_id, "Security key belongs to another account");
+                Ok(false)
+            }
+            Err(AppError::InvalidFieldValue { message, .. }) => {
+                tracing::warn!(user_id = %user_id, reason = %message, "Security key rejected");
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     // Verify TOTP code with proper error handling and clock skew tolerance
     fn verify_totp(&self, secret: &str, code: &str) -> Result<bool> {
         // Validate input
@@ -1836,6 +2211,18 @@ impl AuthService {
         Ok(())
     }
 
+    /// Drop cached login data for an address, e.g. after the account's
+    /// email changed away from it
+    pub async fn invalidate_login_cache(&self, email: &str) {
+        if let Err(e) = self
+            .login_performance_service
+            .invalidate_user_cache(email)
+            .await
+        {
+            tracing::warn!("Failed to invalidate login cache for {}: {}", email, e);
+        }
+    }
+
     // Get user by ID
     pub async fn get_user(&self, user_id: Uuid) -> Result<User> {
         self.get_user_by_id(user_id).await
@@ -2798,16 +3185,6 @@ impl AuthService {
         secret
     }
 
-    fn generate_backup_codes(&self) -> Vec<String> {
-        // Generate 8 backup codes with 6 digits each
-        (0..8)
-            .map(|_| {
-                let code: u32 = rand::thread_rng().gen_range(100000..999999);
-                format!("{:06}", code)
-            })
-            .collect()
-    }
-
     // Additional methods needed for tests
     pub async fn logout_user(&self, user_id: Uuid, _refresh_token: &str) -> Result<()> {
         // Invalidate the refresh token by deleting the session
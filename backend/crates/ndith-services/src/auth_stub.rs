@@ -77,6 +77,7 @@ impl AuthService {
             role: Default::default(),
             iss: String::new(),
             aud: String::new(),
+            email_verified: false,
         })
     }
 
@@ -0,0 +1,812 @@
+//! GDPR data export and right-to-erasure requests
+//!
+//! Both request types are queued in `data_export_requests` and worked off
+//! by a background poller, so any replica can pick them up and a request
+//! survives restarts. An export gathers every table holding the user's data
+//! into a zip with one JSON file per section and a `manifest.json` listing
+//! each file's record count and SHA-256. The archive is kept in the database
+//! until its download link expires; links are signed with an HMAC over the
+//! request id and expiry, so nothing secret is stored with the request. An
+//! erasure revokes the user's provider connections before deleting the
+//! account, and the request row is kept (without the user reference) as a
+//! record that it happened.
+
+use chrono::{DateTime, Duration, SubsecRound, Utc};
+use hmac::{Hmac, Mac};
+use ndith_core::config::DataRequestConfig;
+use ndith_core::models::{DataExportRequest, DataRequestStatus, DataRequestType};
+use ndith_core::{AppError, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::io::Write;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::token_vault::TokenVaultService;
+use crate::user::{AccountDeletionRequest, UserService};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Format identifier written to every export manifest
+pub const EXPORT_FORMAT: &str = "ndith-user-export";
+
+/// Bumped when sections or their layout change incompatibly
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+const REQUEST_COLUMNS: &str = r#"
+    id, user_id, request_type, status, requested_at, started_at, completed_at,
+    attempts, reason, error, manifest, export_url, expires_at, downloaded_at,
+    verification_token
+"#;
+
+/// One file in an export: the rows of `table` matching `filter` (with `t`
+/// as the table alias and `$1` as the user id), minus `redact`ed columns
+struct ExportSection {
+    name: &'static str,
+    table: &'static str,
+    filter: &'static str,
+    redact: &'static [&'static str],
+}
+
+impl ExportSection {
+    const fn by_user(name: &'static str, table: &'static str) -> Self {
+        Self {
+            name,
+            table,
+            filter: "t.user_id = $1",
+            redact: &[],
+        }
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}.json", self.name)
+    }
+
+    fn query(&self) -> String {
+        let redact: String = self
+            .redact
+            .iter()
+            .map(|column| format!(" - '{}'", column))
+            .collect();
+        format!(
+            "SELECT COALESCE(jsonb_agg(to_jsonb(t){}), '[]'::jsonb) FROM {} t WHERE {}",
+            redact, self.table, self.filter
+        )
+    }
+}
+
+/// Everything exported about a user. Credentials and token material are
+/// redacted; the rest is exported as stored.
+const EXPORT_SECTIONS: &[ExportSection] = &[
+    ExportSection {
+        name: "profile",
+        table: "users",
+        filter: "t.id = $1",
+        redact: &["password_hash", "totp_secret"],
+    },
+    ExportSection {
+        name: "connections",
+        table: "connections",
+        filter: "t.user_id = $1",
+        redact: &[
+            "access_token_encrypted",
+            "refresh_token_encrypted",
+            "data_key_id",
+        ],
+    },
+    ExportSection {
+        name: "oauth_accounts",
+        table: "oauth_accounts",
+        filter: "t.user_id = $1",
+        redact: &["access_token_encrypted", "refresh_token_encrypted"],
+    },
+    ExportSection {
+        name: "sessions",
+        table: "user_sessions",
+        filter: "t.user_id = $1",
+        redact: &["refresh_token_hash"],
+    },
+    ExportSection {
+        name: "webauthn_credentials",
+        table: "webauthn_credentials",
+        filter: "t.user_id = $1",
+        redact: &["public_key"],
+    },
+    ExportSection::by_user("oidc_consents", "oidc_consents"),
+    ExportSection::by_user("dnp_artists", "user_artist_blocks"),
+    ExportSection::by_user("dnp_tracks", "user_track_blocks"),
+    ExportSection::by_user("household_memberships", "household_members"),
+    ExportSection::by_user("household_enforced_blocks", "household_enforced_blocks"),
+    ExportSection::by_user("email_change_requests", "email_change_requests"),
+    ExportSection::by_user("community_list_subscriptions", "user_list_subscriptions"),
+    ExportSection::by_user("category_subscriptions", "category_subscriptions"),
+    ExportSection {
+        name: "owned_community_lists",
+        table: "community_lists",
+        filter: "t.owner_user_id = $1",
+        redact: &[],
+    },
+    ExportSection::by_user("library_tracks", "user_library_tracks"),
+    ExportSection::by_user("library_snapshots", "library_composition_snapshots"),
+    ExportSection::by_user("library_scans", "library_scan_results"),
+    ExportSection::by_user("library_regressions", "library_regressions"),
+    ExportSection::by_user("playlists", "playlists"),
+    ExportSection {
+        name: "playlist_tracks",
+        table: "playlist_tracks",
+        filter: "t.playlist_id IN (SELECT id FROM playlists WHERE user_id = $1)",
+        redact: &[],
+    },
+    ExportSection::by_user("action_batches", "action_batches"),
+    ExportSection {
+        name: "action_items",
+        table: "action_items",
+        filter: "t.batch_id IN (SELECT id FROM action_batches WHERE user_id = $1)",
+        redact: &[],
+    },
+    ExportSection::by_user("sanitization_plans", "sanitization_plans"),
+    ExportSection::by_user("enforcement_runs", "apple_music_enforcement_runs"),
+    ExportSection::by_user("artist_playcounts", "user_artist_playcounts"),
+    ExportSection::by_user("listening_history", "listening_history"),
+    ExportSection::by_user("listening_history_imports", "listening_history_imports"),
+    ExportSection::by_user("score_profiles", "user_score_profiles"),
+    ExportSection::by_user("notifications", "notifications"),
+    ExportSection::by_user("lifecycle_events", "user_lifecycle_events"),
+    ExportSection::by_user("audit_log", "audit_log"),
+    ExportSection {
+        name: "data_requests",
+        table: "data_export_requests",
+        filter: "t.user_id = $1",
+        redact: &["archive", "verification_token"],
+    },
+];
+
+/// A file in an export archive
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestSection {
+    pub name: String,
+    pub file: String,
+    pub records: usize,
+    pub sha256: String,
+}
+
+/// A section that couldn't be exported because this deployment lacks the
+/// table or column it reads
+#[derive(Debug, Clone, Serialize)]
+pub struct UnavailableSection {
+    pub name: String,
+    pub reason: String,
+}
+
+/// `manifest.json` at the root of every export archive
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportManifest {
+    pub format: &'static str,
+    pub version: u32,
+    pub request_id: Uuid,
+    pub user_id: Uuid,
+    pub generated_at: DateTime<Utc>,
+    pub total_records: usize,
+    pub sections: Vec<ManifestSection>,
+    pub unavailable: Vec<UnavailableSection>,
+}
+
+/// A built export archive
+pub struct ExportArchive {
+    pub manifest: ExportManifest,
+    pub archive: Vec<u8>,
+    pub sha256: String,
+}
+
+/// A finished export served through its download link
+pub struct ExportDownload {
+    pub file_name: String,
+    pub archive: Vec<u8>,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    to_hex(&Sha256::digest(data))
+}
+
+/// Zip `files` (name, contents) with deflate compression
+pub fn write_archive(files: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let zip_error = |e: zip::result::ZipError| AppError::Internal {
+        message: Some(format!("Failed to write export archive: {}", e)),
+    };
+
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in files {
+        zip.start_file(name.as_str(), options).map_err(zip_error)?;
+        zip.write_all(contents)
+            .map_err(|e| zip_error(zip::result::ZipError::Io(e)))?;
+    }
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+/// Signs and checks export download links
+pub struct DownloadLinkSigner {
+    key: Vec<u8>,
+}
+
+impl DownloadLinkSigner {
+    pub fn new(secret: &str) -> Self {
+        let mut mac =
+            HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+        mac.update(b"ndith-data-export-link-v1");
+        Self {
+            key: mac.finalize().into_bytes().to_vec(),
+        }
+    }
+
+    fn mac(&self, request_id: Uuid, expires_at: DateTime<Utc>) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC can take key of any size");
+        mac.update(format!("{}\n{}", request_id, expires_at.timestamp_micros()).as_bytes());
+        mac
+    }
+
+    pub fn token(&self, request_id: Uuid, expires_at: DateTime<Utc>) -> String {
+        to_hex(&self.mac(request_id, expires_at).finalize().into_bytes())
+    }
+
+    pub fn verify(&self, request_id: Uuid, expires_at: DateTime<Utc>, token: &str) -> bool {
+        match from_hex(token) {
+            Some(bytes) => self
+                .mac(request_id, expires_at)
+                .verify_slice(&bytes)
+                .is_ok(),
+            None => false,
+        }
+    }
+}
+
+pub struct DataRequestService {
+    db_pool: PgPool,
+    user_service: Arc<UserService>,
+    token_vault: Arc<TokenVaultService>,
+    signer: DownloadLinkSigner,
+    config: DataRequestConfig,
+}
+
+impl DataRequestService {
+    pub fn new(
+        db_pool: PgPool,
+        user_service: Arc<UserService>,
+        token_vault: Arc<TokenVaultService>,
+        config: DataRequestConfig,
+    ) -> Self {
+        Self {
+            db_pool,
+            user_service,
+            token_vault,
+            signer: DownloadLinkSigner::new(&config.link_secret),
+            config,
+        }
+    }
+
+    pub fn config(&self) -> &DataRequestConfig {
+        &self.config
+    }
+
+    /// Queue a full export of the user's data
+    pub async fn request_export(&self, user_id: Uuid) -> Result<DataExportRequest> {
+        self.enqueue(user_id, DataRequestType::Export, None).await
+    }
+
+    /// Queue erasure of the user's account once they've confirmed their email
+    pub async fn request_erasure(
+        &self,
+        user_id: Uuid,
+        request: &AccountDeletionRequest,
+    ) -> Result<DataExportRequest> {
+        self.user_service
+            .confirm_account_deletion(user_id, &request.confirmation_email)
+            .await?;
+        self.enqueue(user_id, DataRequestType::Deletion, request.reason.clone())
+            .await
+    }
+
+    async fn enqueue(
+        &self,
+        user_id: Uuid,
+        request_type: DataRequestType,
+        reason: Option<String>,
+    ) -> Result<DataExportRequest> {
+        let request = sqlx::query_as::<_, DataExportRequest>(&format!(
+            r#"
+            INSERT INTO data_export_requests (user_id, request_type, status, reason)
+            VALUES ($1, $2, 'pending', $3)
+            RETURNING {}
+            "#,
+            REQUEST_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(request_type)
+        .bind(reason)
+        .fetch_one(&self.db_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Conflict {
+                message: format!(
+                    "A data {} request is already in progress",
+                    match request_type {
+                        DataRequestType::Export => "export",
+                        DataRequestType::Deletion => "erasure",
+                    }
+                ),
+            },
+            e => e.into(),
+        })?;
+
+        tracing::info!(
+            request_id = %request.id,
+            user_id = %user_id,
+            request_type = ?request_type,
+            "Data request queued"
+        );
+        Ok(request)
+    }
+
+    /// The user's requests, newest first
+    pub async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<DataExportRequest>> {
+        let requests = sqlx::query_as::<_, DataExportRequest>(&format!(
+            "SELECT {} FROM data_export_requests WHERE user_id = $1 ORDER BY requested_at DESC",
+            REQUEST_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(requests
+            .into_iter()
+            .map(|r| self.with_download_link(r))
+            .collect())
+    }
+
+    pub async fn get_for_user(&self, user_id: Uuid, request_id: Uuid) -> Result<DataExportRequest> {
+        self.get(request_id)
+            .await?
+            .filter(|r| r.user_id == Some(user_id))
+            .map(|r| self.with_download_link(r))
+            .ok_or_else(|| AppError::NotFound {
+                resource: "Data request".to_string(),
+            })
+    }
+
+    async fn get(&self, request_id: Uuid) -> Result<Option<DataExportRequest>> {
+        let request = sqlx::query_as::<_, DataExportRequest>(&format!(
+            "SELECT {} FROM data_export_requests WHERE id = $1",
+            REQUEST_COLUMNS
+        ))
+        .bind(request_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(request)
+    }
+
+    /// Fill in the signed download link of an export that can be downloaded
+    fn with_download_link(&self, mut request: DataExportRequest) -> DataExportRequest {
+        request.export_url = match (request.request_type, request.status, request.expires_at) {
+            (DataRequestType::Export, DataRequestStatus::Completed, Some(expires_at))
+                if expires_at > Utc::now() =>
+            {
+                Some(format!(
+                    "/api/v1/data-exports/{}/download?token={}",
+                    request.id,
+                    self.signer.token(request.id, expires_at)
+                ))
+            }
+            _ => None,
+        };
+        request
+    }
+
+    /// The archive behind a download link, if the link is valid and unexpired
+    pub async fn download(&self, request_id: Uuid, token: &str) -> Result<ExportDownload> {
+        let not_found = || AppError::NotFound {
+            resource: "Data export".to_string(),
+        };
+
+        let (expires_at, archive): (Option<DateTime<Utc>>, Option<Vec<u8>>) = sqlx::query_as(
+            r#"
+            SELECT expires_at, archive
+            FROM data_export_requests
+            WHERE id = $1 AND request_type = 'export' AND status = 'completed'
+            "#,
+        )
+        .bind(request_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(not_found)?;
+
+        let expires_at = expires_at.ok_or_else(not_found)?;
+        if !self.signer.verify(request_id, expires_at, token) {
+            return Err(not_found());
+        }
+        if expires_at <= Utc::now() {
+            return Err(AppError::NotFound {
+                resource: "Data export (download link expired)".to_string(),
+            });
+        }
+        let archive = archive.ok_or_else(not_found)?;
+
+        sqlx::query(
+            r#"
+            UPDATE data_export_requests
+            SET downloaded_at = COALESCE(downloaded_at, NOW()), updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(request_id)
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(ExportDownload {
+            file_name: format!("ndith-export-{}.zip", request_id),
+            archive,
+        })
+    }
+
+    /// Claim and run the oldest pending request. Returns the request as it
+    /// stands afterwards, or `None` if the queue is empty.
+    pub async fn process_next(&self) -> Result<Option<DataExportRequest>> {
+        let claimed = sqlx::query_as::<_, DataExportRequest>(&format!(
+            r#"
+            UPDATE data_export_requests
+            SET status = 'processing', started_at = NOW(), attempts = attempts + 1,
+                updated_at = NOW()
+            WHERE id = (
+                SELECT id FROM data_export_requests
+                WHERE status = 'pending'
+                ORDER BY requested_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            REQUEST_COLUMNS
+        ))
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        let Some(request) = claimed else {
+            return Ok(None);
+        };
+
+        let outcome = match (request.request_type, request.user_id) {
+            (DataRequestType::Export, Some(user_id)) => self.run_export(&request, user_id).await,
+            (DataRequestType::Deletion, Some(user_id)) => self.run_erasure(&request, user_id).await,
+            // The account went away some other way; nothing left to do
+            (_, None) => {
+                self.record_failure(&request, "Account no longer exists", false)
+                    .await
+            }
+        };
+
+        if let Err(e) = outcome {
+            tracing::warn!(
+                request_id = %request.id,
+                attempt = request.attempts,
+                error = %e,
+                "Data request failed"
+            );
+            let retry = request.attempts < self.config.max_attempts;
+            self.record_failure(&request, &e.to_string(), retry).await?;
+        }
+
+        self.get(request.id).await
+    }
+
+    /// Work off the queue, then clean up stale claims and expired exports
+    pub async fn run_once(&self) -> Result<usize> {
+        self.requeue_stale().await?;
+
+        let mut processed = 0;
+        while self.process_next().await?.is_some() {
+            processed += 1;
+        }
+
+        self.expire_exports().await?;
+        Ok(processed)
+    }
+
+    /// Poll the queue on an interval in the background
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = std::time::Duration::from_secs(self.config.poll_interval_seconds);
+            let mut ticker = tokio::time::interval(period);
+
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_once().await {
+                    tracing::error!(error = %e, "Data request processing failed");
+                }
+            }
+        })
+    }
+
+    /// Gather every export section into a zip with a manifest
+    pub async fn build_export(&self, request_id: Uuid, user_id: Uuid) -> Result<ExportArchive> {
+        let mut files = Vec::new();
+        let mut sections = Vec::new();
+        let mut unavailable = Vec::new();
+
+        for section in EXPORT_SECTIONS {
+            let records = sqlx::query_scalar::<_, serde_json::Value>(&section.query())
+                .bind(user_id)
+                .fetch_one(&self.db_pool)
+                .await;
+
+            let records = match records {
+                Ok(records) => records,
+                // Undefined table or column: a feature this deployment lacks
+                Err(sqlx::Error::Database(e))
+                    if matches!(e.code().as_deref(), Some("42P01") | Some("42703")) =>
+                {
+                    unavailable.push(UnavailableSection {
+                        name: section.name.to_string(),
+                        reason: e.message().to_string(),
+                    });
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            let contents = serde_json::to_vec_pretty(&records)?;
+            sections.push(ManifestSection {
+                name: section.name.to_string(),
+                file: section.file_name(),
+                records: records.as_array().map_or(0, Vec::len),
+                sha256: sha256_hex(&contents),
+            });
+            files.push((section.file_name(), contents));
+        }
+
+        let manifest = ExportManifest {
+            format: EXPORT_FORMAT,
+            version: EXPORT_FORMAT_VERSION,
+            request_id,
+            user_id,
+            generated_at: Utc::now(),
+            total_records: sections.iter().map(|s| s.records).sum(),
+            sections,
+            unavailable,
+        };
+        files.insert(
+            0,
+            (
+                "manifest.json".to_string(),
+                serde_json::to_vec_pretty(&manifest)?,
+            ),
+        );
+
+        let archive = write_archive(&files)?;
+        Ok(ExportArchive {
+            sha256: sha256_hex(&archive),
+            manifest,
+            archive,
+        })
+    }
+
+    async fn run_export(&self, request: &DataExportRequest, user_id: Uuid) -> Result<()> {
+        let export = self.build_export(request.id, user_id).await?;
+        // Postgres keeps microseconds; the link signature covers the stored value
+        let expires_at =
+            (Utc::now() + Duration::hours(self.config.link_ttl_hours)).trunc_subsecs(6);
+
+        sqlx::query(
+            r#"
+            UPDATE data_export_requests
+            SET status = 'completed', completed_at = NOW(), error = NULL,
+                archive = $2, archive_sha256 = $3, manifest = $4, expires_at = $5,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(request.id)
+        .bind(&export.archive)
+        .bind(&export.sha256)
+        .bind(serde_json::to_value(&export.manifest)?)
+        .bind(expires_at)
+        .execute(&self.db_pool)
+        .await?;
+
+        tracing::info!(
+            request_id = %request.id,
+            user_id = %user_id,
+            records = export.manifest.total_records,
+            bytes = export.archive.len(),
+            "Data export ready"
+        );
+        Ok(())
+    }
+
+    async fn run_erasure(&self, request: &DataExportRequest, user_id: Uuid) -> Result<()> {
+        // Provider tokens are revoked before anything is deleted, so a
+        // failure here leaves the account intact for the retry
+        let connection_ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM connections WHERE user_id = $1 AND status IS DISTINCT FROM 'revoked'",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        for connection_id in &connection_ids {
+            self.token_vault
+                .revoke_connection(*connection_id)
+                .await
+                .map_err(|e| AppError::Internal {
+                    message: Some(format!(
+                        "Failed to revoke connection {}: {}",
+                        connection_id, e
+                    )),
+                })?;
+        }
+
+        let result = self.user_service.erase_account(user_id).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE data_export_requests
+            SET status = 'completed', completed_at = NOW(), error = NULL, manifest = $2,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(request.id)
+        .bind(serde_json::json!({
+            "revoked_connections": connection_ids.len(),
+            "deleted_at": result.deleted_at,
+            "data_retention_days": result.data_retention_days,
+            "cleanup_summary": result.cleanup_summary,
+        }))
+        .execute(&self.db_pool)
+        .await?;
+
+        tracing::info!(
+            request_id = %request.id,
+            user_id = %user_id,
+            revoked_connections = connection_ids.len(),
+            "Account erased"
+        );
+        Ok(())
+    }
+
+    /// Put a request back in the queue, or mark it failed for good
+    async fn record_failure(
+        &self,
+        request: &DataExportRequest,
+        error: &str,
+        retry: bool,
+    ) -> Result<()> {
+        let status = if retry {
+            DataRequestStatus::Pending
+        } else {
+            DataRequestStatus::Failed
+        };
+
+        sqlx::query(
+            "UPDATE data_export_requests SET status = $2, error = $3, updated_at = NOW() WHERE id = $1",
+        )
+        .bind(request.id)
+        .bind(status)
+        .bind(error)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Retry requests whose worker died mid-way
+    async fn requeue_stale(&self) -> Result<u64> {
+        let cutoff = Utc::now() - Duration::minutes(self.config.stale_after_minutes);
+        let requeued = sqlx::query(
+            r#"
+            UPDATE data_export_requests
+            SET status = CASE WHEN attempts >= $2
+                              THEN 'failed'::data_request_status
+                              ELSE 'pending'::data_request_status END,
+                error = 'Processing was interrupted',
+                updated_at = NOW()
+            WHERE status = 'processing' AND started_at < $1
+            "#,
+        )
+        .bind(cutoff)
+        .bind(self.config.max_attempts)
+        .execute(&self.db_pool)
+        .await?
+        .rows_affected();
+
+        if requeued > 0 {
+            tracing::warn!(count = requeued, "Requeued stalled data requests");
+        }
+        Ok(requeued)
+    }
+
+    /// Drop archives whose download link has expired
+    pub async fn expire_exports(&self) -> Result<u64> {
+        let expired = sqlx::query(
+            r#"
+            UPDATE data_export_requests
+            SET status = 'expired', archive = NULL, updated_at = NOW()
+            WHERE request_type = 'export' AND status = 'completed' AND expires_at <= NOW()
+            "#,
+        )
+        .execute(&self.db_pool)
+        .await?
+        .rows_affected();
+        Ok(expired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_download_link_signature() {
+        let signer = DownloadLinkSigner::new("link-secret");
+        let id = Uuid::new_v4();
+        let expires_at = Utc::now().trunc_subsecs(6);
+        let token = signer.token(id, expires_at);
+
+        assert!(signer.verify(id, expires_at, &token));
+        assert!(!signer.verify(Uuid::new_v4(), expires_at, &token));
+        assert!(!signer.verify(id, expires_at + Duration::hours(1), &token));
+        assert!(!signer.verify(id, expires_at, "not-hex"));
+        assert!(!DownloadLinkSigner::new("other-secret").verify(id, expires_at, &token));
+    }
+
+    #[test]
+    fn test_archive_round_trips() {
+        let files = vec![
+            ("manifest.json".to_string(), b"{\"version\":1}".to_vec()),
+            ("profile.json".to_string(), b"[]".to_vec()),
+        ];
+        let archive = write_archive(&files).unwrap();
+
+        let mut zip = zip::ZipArchive::new(std::io::Cursor::new(archive)).unwrap();
+        assert_eq!(zip.len(), 2);
+        let mut manifest = String::new();
+        zip.by_name("manifest.json")
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        assert_eq!(manifest, "{\"version\":1}");
+    }
+
+    #[test]
+    fn test_section_query_redacts_columns() {
+        let section = &EXPORT_SECTIONS[0];
+        assert_eq!(section.name, "profile");
+        assert_eq!(
+            section.query(),
+            "SELECT COALESCE(jsonb_agg(to_jsonb(t) - 'password_hash' - 'totp_secret'), '[]'::jsonb) FROM users t WHERE t.id = $1"
+        );
+
+        let mut names: Vec<_> = EXPORT_SECTIONS.iter().map(|s| s.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(
+            names.len(),
+            EXPORT_SECTIONS.len(),
+            "section names are unique"
+        );
+    }
+}
@@ -0,0 +1,585 @@
+//! Email verification and change-of-email
+//!
+//! Registration mails a single-use link that marks the address verified.
+//! Changing the address needs both ends to agree: one link goes to the
+//! current address and one to the new address, and the account's email is
+//! only switched once both have been followed. Tokens carry 256 bits of
+//! randomness and only their SHA-256 is stored. Actions that publish to
+//! other users (submitting offenses, evidence, timeline events and disputes)
+//! check `require_verified` first.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use ndith_core::config::EmailConfig;
+use ndith_core::models::{
+    EmailChangeConfirmation, EmailStatus, EmailTokenPurpose, PendingEmailChange,
+};
+use ndith_core::{AppError, Result};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::mailer::{EmailMessage, Mailer};
+
+fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn hash_token(token: &str) -> String {
+    let digest = Sha256::digest(token.as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn invalid_token() -> AppError {
+    AppError::InvalidFieldValue {
+        field: "token".to_string(),
+        message: "Link is invalid or has expired".to_string(),
+    }
+}
+
+fn normalize_email(email: &str) -> Result<String> {
+    let email = email.trim().to_lowercase();
+    let valid = email.len() <= 255
+        && email
+            .split_once('@')
+            .map(|(local, domain)| !local.is_empty() && domain.contains('.'))
+            .unwrap_or(false);
+    if !valid {
+        return Err(AppError::InvalidFieldValue {
+            field: "new_email".to_string(),
+            message: "Must be a valid email address".to_string(),
+        });
+    }
+    Ok(email)
+}
+
+#[derive(sqlx::FromRow)]
+struct TokenRow {
+    user_id: Uuid,
+    purpose: EmailTokenPurpose,
+    email: String,
+    change_request_id: Option<Uuid>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ChangeRow {
+    id: Uuid,
+    user_id: Uuid,
+    old_email: String,
+    new_email: String,
+    current_confirmed_at: Option<DateTime<Utc>>,
+    new_confirmed_at: Option<DateTime<Utc>>,
+}
+
+/// A completed email change, for callers that cache by address
+pub struct CompletedEmailChange {
+    pub user_id: Uuid,
+    pub old_email: String,
+}
+
+pub struct EmailVerificationService {
+    db_pool: PgPool,
+    mailer: Arc<dyn Mailer>,
+    config: EmailConfig,
+}
+
+impl EmailVerificationService {
+    pub fn new(db_pool: PgPool, mailer: Arc<dyn Mailer>, config: EmailConfig) -> Self {
+        Self {
+            db_pool,
+            mailer,
+            config,
+        }
+    }
+
+    fn link(&self, path: &str, token: &str) -> String {
+        format!(
+            "{}/{}?token={}",
+            self.config.link_base_url.trim_end_matches('/'),
+            path,
+            token
+        )
+    }
+
+    async fn issue_token(
+        &self,
+        executor: &mut sqlx::PgConnection,
+        user_id: Uuid,
+        purpose: EmailTokenPurpose,
+        email: &str,
+        change_request_id: Option<Uuid>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<String> {
+        let token = random_token();
+        sqlx::query(
+            r#"
+            INSERT INTO email_tokens (token_hash, user_id, purpose, email, change_request_id, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(hash_token(&token))
+        .bind(user_id)
+        .bind(purpose)
+        .bind(email)
+        .bind(change_request_id)
+        .bind(expires_at)
+        .execute(executor)
+        .await?;
+        Ok(token)
+    }
+
+    /// Fail with `EmailNotVerified` unless the user's address is verified
+    pub async fn require_verified(&self, user_id: Uuid) -> Result<()> {
+        let verified: Option<bool> =
+            sqlx::query_scalar("SELECT COALESCE(email_verified, FALSE) FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await?;
+        match verified {
+            Some(true) => Ok(()),
+            Some(false) => Err(AppError::EmailNotVerified),
+            None => Err(AppError::NotFound {
+                resource: "User".to_string(),
+            }),
+        }
+    }
+
+    /// The user's address, verification state and any pending change
+    pub async fn status(&self, user_id: Uuid) -> Result<EmailStatus> {
+        let (email, email_verified, email_verified_at): (
+            String,
+            Option<bool>,
+            Option<DateTime<Utc>>,
+        ) = sqlx::query_as(
+            "SELECT email, email_verified, email_verified_at FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            resource: "User".to_string(),
+        })?;
+
+        let pending_change: Option<PendingEmailChange> = sqlx::query_as(
+            r#"
+            SELECT new_email, current_confirmed_at, new_confirmed_at, created_at, expires_at
+            FROM email_change_requests
+            WHERE user_id = $1 AND completed_at IS NULL AND cancelled_at IS NULL
+              AND expires_at > NOW()
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(EmailStatus {
+            email,
+            email_verified: email_verified.unwrap_or(false),
+            email_verified_at,
+            pending_change,
+        })
+    }
+
+    /// Mail a fresh verification link, replacing any earlier one
+    pub async fn send_verification(&self, user_id: Uuid) -> Result<()> {
+        let (email, verified): (String, Option<bool>) =
+            sqlx::query_as("SELECT email, email_verified FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await?
+                .ok_or_else(|| AppError::NotFound {
+                    resource: "User".to_string(),
+                })?;
+        if verified.unwrap_or(false) {
+            return Err(AppError::Conflict {
+                message: "Email address is already verified".to_string(),
+            });
+        }
+
+        let last_sent: Option<DateTime<Utc>> = sqlx::query_scalar(
+            "SELECT MAX(created_at) FROM email_tokens WHERE user_id = $1 AND purpose = 'verify'",
+        )
+        .bind(user_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+        if let Some(last_sent) = last_sent {
+            let wait = self.config.resend_cooldown_seconds - (Utc::now() - last_sent).num_seconds();
+            if wait > 0 {
+                return Err(AppError::RateLimitExceeded {
+                    retry_after: Some(wait as u64),
+                });
+            }
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+        sqlx::query(
+            "UPDATE email_tokens SET used_at = NOW() WHERE user_id = $1 AND purpose = 'verify' AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+        let token = self
+            .issue_token(
+                &mut tx,
+                user_id,
+                EmailTokenPurpose::Verify,
+                &email,
+                None,
+                Utc::now() + Duration::hours(self.config.verification_ttl_hours),
+            )
+            .await?;
+        tx.commit().await?;
+
+        self.mailer
+            .send(&EmailMessage {
+                to: email,
+                subject: "Verify your email address".to_string(),
+                text: format!(
+                    "Confirm this is your address by opening the link below:\n\n{}\n\n\
+                     The link expires in {} hours. If you didn't create an account, ignore this email.",
+                    self.link("verify-email", &token),
+                    self.config.verification_ttl_hours
+                ),
+            })
+            .await?;
+
+        tracing::info!(user_id = %user_id, "Verification email sent");
+        Ok(())
+    }
+
+    /// Follow a verification link. The token only counts while the account
+    /// still has the address it was sent to.
+    pub async fn verify(&self, token: &str) -> Result<Uuid> {
+        let mut tx = self.db_pool.begin().await?;
+        let row: TokenRow = sqlx::query_as(
+            r#"
+            UPDATE email_tokens SET used_at = NOW()
+            WHERE token_hash = $1 AND purpose = 'verify' AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id, purpose, email, change_request_id
+            "#,
+        )
+        .bind(hash_token(token))
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(invalid_token)?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE users
+            SET email_verified = TRUE, email_verified_at = COALESCE(email_verified_at, NOW()), updated_at = NOW()
+            WHERE id = $1 AND LOWER(email) = LOWER($2)
+            "#,
+        )
+        .bind(row.user_id)
+        .bind(&row.email)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Err(invalid_token());
+        }
+        tx.commit().await?;
+
+        tracing::info!(user_id = %row.user_id, "Email address verified");
+        Ok(row.user_id)
+    }
+
+    /// Start changing the account's address. Mails a confirmation link to
+    /// both the current and the new address; any earlier pending change is
+    /// cancelled.
+    pub async fn request_change(
+        &self,
+        user_id: Uuid,
+        new_email: &str,
+        password: Option<&str>,
+    ) -> Result<EmailStatus> {
+        let new_email = normalize_email(new_email)?;
+        let (current_email, password_hash): (String, Option<String>) =
+            sqlx::query_as("SELECT email, password_hash FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await?
+                .ok_or_else(|| AppError::NotFound {
+                    resource: "User".to_string(),
+                })?;
+
+        // Accounts with a password re-enter it; OAuth-only accounts rely on
+        // the confirmation sent to the current address
+        if let Some(password_hash) = password_hash.filter(|hash| !hash.is_empty()) {
+            let password = password.ok_or_else(|| AppError::MissingField {
+                field: "password".to_string(),
+            })?;
+            if !bcrypt::verify(password, &password_hash).unwrap_or(false) {
+                return Err(AppError::InvalidCredentials);
+            }
+        }
+
+        if new_email == current_email.to_lowercase() {
+            return Err(AppError::InvalidFieldValue {
+                field: "new_email".to_string(),
+                message: "Must differ from the current address".to_string(),
+            });
+        }
+        let taken: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE LOWER(email) = $1)")
+                .bind(&new_email)
+                .fetch_one(&self.db_pool)
+                .await?;
+        if taken {
+            return Err(AppError::AlreadyExists {
+                resource: "User with this email".to_string(),
+            });
+        }
+
+        let expires_at = Utc::now() + Duration::hours(self.config.change_ttl_hours);
+        let mut tx = self.db_pool.begin().await?;
+        sqlx::query(
+            r#"
+            UPDATE email_change_requests SET cancelled_at = NOW()
+            WHERE user_id = $1 AND completed_at IS NULL AND cancelled_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+        let change_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO email_change_requests (user_id, old_email, new_email, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(user_id)
+        .bind(&current_email)
+        .bind(&new_email)
+        .bind(expires_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        let current_token = self
+            .issue_token(
+                &mut tx,
+                user_id,
+                EmailTokenPurpose::ChangeCurrent,
+                &current_email,
+                Some(change_id),
+                expires_at,
+            )
+            .await?;
+        let new_token = self
+            .issue_token(
+                &mut tx,
+                user_id,
+                EmailTokenPurpose::ChangeNew,
+                &new_email,
+                Some(change_id),
+                expires_at,
+            )
+            .await?;
+        tx.commit().await?;
+
+        self.mailer
+            .send(&EmailMessage {
+                to: current_email.clone(),
+                subject: "Confirm your email address change".to_string(),
+                text: format!(
+                    "Someone asked to change the email address on your account to {}.\n\n\
+                     If that was you, approve the change here:\n\n{}\n\n\
+                     If it wasn't, ignore this email and change your password; \
+                     the address won't change without this approval.",
+                    new_email,
+                    self.link("confirm-email-change", &current_token)
+                ),
+            })
+            .await?;
+        self.mailer
+            .send(&EmailMessage {
+                to: new_email.clone(),
+                subject: "Confirm your new email address".to_string(),
+                text: format!(
+                    "Confirm this address for your account by opening the link below:\n\n{}\n\n\
+                     The link expires in {} hours.",
+                    self.link("confirm-email-change", &new_token),
+                    self.config.change_ttl_hours
+                ),
+            })
+            .await?;
+
+        tracing::info!(user_id = %user_id, change_id = %change_id, "Email change requested");
+        self.status(user_id).await
+    }
+
+    /// Follow one of the two email change links. Once both are confirmed the
+    /// account's address is switched and marked verified.
+    pub async fn confirm_change(
+        &self,
+        token: &str,
+    ) -> Result<(EmailChangeConfirmation, Option<CompletedEmailChange>)> {
+        let mut tx = self.db_pool.begin().await?;
+        let token_row: TokenRow = sqlx::query_as(
+            r#"
+            UPDATE email_tokens SET used_at = NOW()
+            WHERE token_hash = $1 AND purpose IN ('change_current', 'change_new')
+              AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id, purpose, email, change_request_id
+            "#,
+        )
+        .bind(hash_token(token))
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(invalid_token)?;
+
+        // Locking the request serializes the two confirmations, so whichever
+        // lands second sees the first and completes the change
+        let change: ChangeRow = sqlx::query_as(
+            r#"
+            SELECT id, user_id, old_email, new_email, current_confirmed_at, new_confirmed_at
+            FROM email_change_requests
+            WHERE id = $1 AND completed_at IS NULL AND cancelled_at IS NULL AND expires_at > NOW()
+            FOR UPDATE
+            "#,
+        )
+        .bind(token_row.change_request_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(invalid_token)?;
+
+        let now = Utc::now();
+        let (current_confirmed_at, new_confirmed_at) = match token_row.purpose {
+            EmailTokenPurpose::ChangeCurrent => (Some(now), change.new_confirmed_at),
+            _ => (change.current_confirmed_at, Some(now)),
+        };
+        sqlx::query(
+            "UPDATE email_change_requests SET current_confirmed_at = $2, new_confirmed_at = $3 WHERE id = $1",
+        )
+        .bind(change.id)
+        .bind(current_confirmed_at)
+        .bind(new_confirmed_at)
+        .execute(&mut *tx)
+        .await?;
+
+        let completed = current_confirmed_at.is_some() && new_confirmed_at.is_some();
+        if completed {
+            let updated = sqlx::query(
+                r#"
+                UPDATE users
+                SET email = $2, email_verified = TRUE, email_verified_at = NOW(), updated_at = NOW()
+                WHERE id = $1 AND email = $3
+                "#,
+            )
+            .bind(change.user_id)
+            .bind(&change.new_email)
+            .bind(&change.old_email)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                    AppError::AlreadyExists {
+                        resource: "User with this email".to_string(),
+                    }
+                }
+                e => e.into(),
+            })?
+            .rows_affected();
+            if updated == 0 {
+                return Err(invalid_token());
+            }
+            sqlx::query("UPDATE email_change_requests SET completed_at = NOW() WHERE id = $1")
+                .bind(change.id)
+                .execute(&mut *tx)
+                .await?;
+            // Verification links sent to the old address are void now
+            sqlx::query(
+                "UPDATE email_tokens SET used_at = NOW() WHERE user_id = $1 AND used_at IS NULL",
+            )
+            .bind(change.user_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        let confirmation = EmailChangeConfirmation {
+            confirmed: token_row.purpose,
+            completed,
+            new_email: change.new_email.clone(),
+        };
+        if !completed {
+            tracing::info!(
+                user_id = %change.user_id,
+                confirmed = ?token_row.purpose,
+                "Email change half confirmed"
+            );
+            return Ok((confirmation, None));
+        }
+
+        tracing::info!(user_id = %change.user_id, change_id = %change.id, "Email address changed");
+        let notice = EmailMessage {
+            to: change.old_email.clone(),
+            subject: "Your email address was changed".to_string(),
+            text: format!(
+                "The email address on your account is now {}. \
+                 If you didn't make this change, contact support right away.",
+                change.new_email
+            ),
+        };
+        if let Err(e) = self.mailer.send(&notice).await {
+            tracing::warn!(error = %e, user_id = %change.user_id, "Failed to send email change notice");
+        }
+
+        Ok((
+            confirmation,
+            Some(CompletedEmailChange {
+                user_id: change.user_id,
+                old_email: change.old_email,
+            }),
+        ))
+    }
+
+    /// Abandon the pending email change
+    pub async fn cancel_change(&self, user_id: Uuid) -> Result<()> {
+        let cancelled = sqlx::query(
+            r#"
+            UPDATE email_change_requests SET cancelled_at = NOW()
+            WHERE user_id = $1 AND completed_at IS NULL AND cancelled_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.db_pool)
+        .await?
+        .rows_affected();
+        if cancelled == 0 {
+            return Err(AppError::NotFound {
+                resource: "Pending email change".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_are_random_and_hashed() {
+        let token = random_token();
+        assert_eq!(token.len(), 43);
+        assert_ne!(token, random_token());
+        assert_eq!(hash_token(&token).len(), 64);
+        assert_eq!(hash_token(&token), hash_token(&token));
+    }
+
+    #[test]
+    fn test_new_email_is_normalized_and_validated() {
+        assert_eq!(
+            normalize_email("  New@Example.COM ").unwrap(),
+            "new@example.com"
+        );
+        assert!(normalize_email("no-at-sign").is_err());
+        assert!(normalize_email("@example.com").is_err());
+        assert!(normalize_email("user@localhost").is_err());
+    }
+}
@@ -0,0 +1,752 @@
+//! Household accounts
+//!
+//! Families on one streaming plan join a household: the owner invites
+//! members and children by email, everyone but children can edit a shared
+//! blocklist, and the owner can enforce extra blocks on each child. None of
+//! this touches anyone's personal `user_artist_blocks`; the lists are merged
+//! per user by the `effective_artist_blocks` view, which enforcement reads,
+//! so each member's enforcement still runs against their own connections.
+
+use chrono::{Duration, Utc};
+use ndith_core::models::{
+    EffectiveBlock, Household, HouseholdBlock, HouseholdInvite, HouseholdMember, HouseholdOverview,
+    HouseholdRole,
+};
+use ndith_core::{AppError, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Members plus open invites, sized for a family streaming plan
+pub const MAX_HOUSEHOLD_SIZE: i64 = 6;
+
+/// How long an invite can be accepted
+const INVITE_TTL_DAYS: i64 = 7;
+
+const INVITE_COLUMNS: &str = r#"
+    i.id, i.household_id, h.name AS household_name, i.email, i.role,
+    i.invited_by, i.created_at, i.expires_at
+"#;
+
+pub struct HouseholdService {
+    db_pool: PgPool,
+}
+
+impl HouseholdService {
+    pub fn new(db_pool: PgPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// The user's household and role, if they belong to one
+    pub async fn membership(&self, user_id: Uuid) -> Result<Option<(Household, HouseholdRole)>> {
+        let row: Option<(Uuid, HouseholdRole)> =
+            sqlx::query_as("SELECT household_id, role FROM household_members WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await?;
+
+        match row {
+            Some((household_id, role)) => {
+                let household: Household = sqlx::query_as("SELECT * FROM households WHERE id = $1")
+                    .bind(household_id)
+                    .fetch_one(&self.db_pool)
+                    .await?;
+                Ok(Some((household, role)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn require_membership(&self, user_id: Uuid) -> Result<(Household, HouseholdRole)> {
+        self.membership(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound {
+                resource: "Household".to_string(),
+            })
+    }
+
+    async fn require_owner(&self, user_id: Uuid) -> Result<Household> {
+        let (household, role) = self.require_membership(user_id).await?;
+        if role != HouseholdRole::Owner {
+            tracing::warn!(
+                user_id = %user_id,
+                household_id = %household.id,
+                role = role.as_str(),
+                "Household management attempt by non-owner"
+            );
+            return Err(AppError::InsufficientPermissions);
+        }
+        Ok(household)
+    }
+
+    /// Create a household owned by the user
+    pub async fn create(&self, user_id: Uuid, name: &str) -> Result<Household> {
+        let name = validate_name(name)?;
+        if self.membership(user_id).await?.is_some() {
+            return Err(AppError::Conflict {
+                message: "Already a member of a household".to_string(),
+            });
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+        let household: Household =
+            sqlx::query_as("INSERT INTO households (name, owner_id) VALUES ($1, $2) RETURNING *")
+                .bind(name)
+                .bind(user_id)
+                .fetch_one(&mut *tx)
+                .await?;
+        sqlx::query(
+            "INSERT INTO household_members (household_id, user_id, role) VALUES ($1, $2, 'owner')",
+        )
+        .bind(household.id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        tracing::info!(household_id = %household.id, owner_id = %user_id, "Household created");
+        Ok(household)
+    }
+
+    /// The user's household, members and (for the owner) open invites
+    pub async fn overview(&self, user_id: Uuid) -> Result<HouseholdOverview> {
+        let (household, role) = self.require_membership(user_id).await?;
+
+        let members: Vec<HouseholdMember> = sqlx::query_as(
+            r#"
+            SELECT hm.user_id, u.email, hm.role, hm.joined_at
+            FROM household_members hm
+            JOIN users u ON u.id = hm.user_id
+            WHERE hm.household_id = $1
+            ORDER BY hm.role, hm.joined_at
+            "#,
+        )
+        .bind(household.id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let invites = if role == HouseholdRole::Owner {
+            sqlx::query_as(&format!(
+                r#"
+                SELECT {}
+                FROM household_invites i
+                JOIN households h ON h.id = i.household_id
+                WHERE i.household_id = $1
+                  AND i.accepted_at IS NULL AND i.declined_at IS NULL AND i.revoked_at IS NULL
+                  AND i.expires_at > NOW()
+                ORDER BY i.created_at
+                "#,
+                INVITE_COLUMNS
+            ))
+            .bind(household.id)
+            .fetch_all(&self.db_pool)
+            .await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(HouseholdOverview {
+            household,
+            role,
+            members,
+            invites,
+        })
+    }
+
+    /// Dissolve the owner's household; memberships, invites and household
+    /// blocks go with it
+    pub async fn dissolve(&self, owner_id: Uuid) -> Result<()> {
+        let household = self.require_owner(owner_id).await?;
+        sqlx::query("DELETE FROM households WHERE id = $1")
+            .bind(household.id)
+            .execute(&self.db_pool)
+            .await?;
+        tracing::info!(household_id = %household.id, "Household dissolved");
+        Ok(())
+    }
+
+    /// Invite someone by email as a member or child
+    pub async fn invite(
+        &self,
+        owner_id: Uuid,
+        email: &str,
+        role: HouseholdRole,
+    ) -> Result<HouseholdInvite> {
+        let household = self.require_owner(owner_id).await?;
+        if role == HouseholdRole::Owner {
+            return Err(AppError::InvalidFieldValue {
+                field: "role".to_string(),
+                message: "Must be 'member' or 'child'".to_string(),
+            });
+        }
+        let email = email.trim().to_lowercase();
+        if !email.contains('@') || email.len() > 255 {
+            return Err(AppError::InvalidFieldValue {
+                field: "email".to_string(),
+                message: "Must be a valid email address".to_string(),
+            });
+        }
+
+        let already_member: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM household_members hm
+                JOIN users u ON u.id = hm.user_id
+                WHERE hm.household_id = $1 AND LOWER(u.email) = $2
+            )
+            "#,
+        )
+        .bind(household.id)
+        .bind(&email)
+        .fetch_one(&self.db_pool)
+        .await?;
+        if already_member {
+            return Err(AppError::AlreadyExists {
+                resource: "Household member".to_string(),
+            });
+        }
+
+        // Re-inviting replaces an expired invite for the same address
+        sqlx::query(
+            r#"
+            UPDATE household_invites SET revoked_at = NOW()
+            WHERE household_id = $1 AND LOWER(email) = $2 AND expires_at <= NOW()
+              AND accepted_at IS NULL AND declined_at IS NULL AND revoked_at IS NULL
+            "#,
+        )
+        .bind(household.id)
+        .bind(&email)
+        .execute(&self.db_pool)
+        .await?;
+
+        if self.seats_taken(household.id).await? >= MAX_HOUSEHOLD_SIZE {
+            return Err(AppError::OperationNotAllowed {
+                reason: format!(
+                    "A household can have at most {} members including open invites",
+                    MAX_HOUSEHOLD_SIZE
+                ),
+            });
+        }
+
+        let invite_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO household_invites (household_id, email, role, invited_by, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(household.id)
+        .bind(&email)
+        .bind(role)
+        .bind(owner_id)
+        .bind(Utc::now() + Duration::days(INVITE_TTL_DAYS))
+        .fetch_one(&self.db_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => AppError::AlreadyExists {
+                resource: "Household invite".to_string(),
+            },
+            e => e.into(),
+        })?;
+
+        tracing::info!(
+            household_id = %household.id,
+            invite_id = %invite_id,
+            role = role.as_str(),
+            "Household invite created"
+        );
+        self.get_invite(invite_id).await
+    }
+
+    async fn seats_taken(&self, household_id: Uuid) -> Result<i64> {
+        let taken: i64 = sqlx::query_scalar(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM household_members WHERE household_id = $1)
+                + (SELECT COUNT(*) FROM household_invites
+                   WHERE household_id = $1 AND expires_at > NOW()
+                     AND accepted_at IS NULL AND declined_at IS NULL AND revoked_at IS NULL)
+            "#,
+        )
+        .bind(household_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(taken)
+    }
+
+    async fn get_invite(&self, invite_id: Uuid) -> Result<HouseholdInvite> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM household_invites i
+            JOIN households h ON h.id = i.household_id
+            WHERE i.id = $1
+            "#,
+            INVITE_COLUMNS
+        ))
+        .bind(invite_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            resource: "Household invite".to_string(),
+        })
+    }
+
+    /// Open invites addressed to the user's email
+    pub async fn invites_for(&self, user_id: Uuid) -> Result<Vec<HouseholdInvite>> {
+        let invites = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM household_invites i
+            JOIN households h ON h.id = i.household_id
+            JOIN users u ON LOWER(u.email) = LOWER(i.email)
+            WHERE u.id = $1
+              AND i.accepted_at IS NULL AND i.declined_at IS NULL AND i.revoked_at IS NULL
+              AND i.expires_at > NOW()
+            ORDER BY i.created_at DESC
+            "#,
+            INVITE_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(invites)
+    }
+
+    /// Withdraw an open invite (owner)
+    pub async fn revoke_invite(&self, owner_id: Uuid, invite_id: Uuid) -> Result<()> {
+        let household = self.require_owner(owner_id).await?;
+        let revoked = sqlx::query(
+            r#"
+            UPDATE household_invites SET revoked_at = NOW()
+            WHERE id = $1 AND household_id = $2
+              AND accepted_at IS NULL AND declined_at IS NULL AND revoked_at IS NULL
+            "#,
+        )
+        .bind(invite_id)
+        .bind(household.id)
+        .execute(&self.db_pool)
+        .await?
+        .rows_affected();
+        if revoked == 0 {
+            return Err(AppError::NotFound {
+                resource: "Household invite".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Join the household an open invite addressed to the user is for
+    pub async fn accept_invite(&self, user_id: Uuid, invite_id: Uuid) -> Result<HouseholdOverview> {
+        let invite = self.open_invite_for(user_id, invite_id).await?;
+        if self.membership(user_id).await?.is_some() {
+            return Err(AppError::Conflict {
+                message: "Leave your current household before joining another".to_string(),
+            });
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+        let claimed = sqlx::query(
+            r#"
+            UPDATE household_invites SET accepted_at = NOW()
+            WHERE id = $1 AND accepted_at IS NULL AND declined_at IS NULL AND revoked_at IS NULL
+            "#,
+        )
+        .bind(invite_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if claimed == 0 {
+            return Err(AppError::NotFound {
+                resource: "Household invite".to_string(),
+            });
+        }
+        sqlx::query(
+            "INSERT INTO household_members (household_id, user_id, role) VALUES ($1, $2, $3)",
+        )
+        .bind(invite.household_id)
+        .bind(user_id)
+        .bind(invite.role)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => AppError::Conflict {
+                message: "Leave your current household before joining another".to_string(),
+            },
+            e => e.into(),
+        })?;
+        tx.commit().await?;
+
+        tracing::info!(
+            household_id = %invite.household_id,
+            user_id = %user_id,
+            role = invite.role.as_str(),
+            "Household invite accepted"
+        );
+        self.overview(user_id).await
+    }
+
+    /// Turn down an open invite addressed to the user
+    pub async fn decline_invite(&self, user_id: Uuid, invite_id: Uuid) -> Result<()> {
+        self.open_invite_for(user_id, invite_id).await?;
+        sqlx::query("UPDATE household_invites SET declined_at = NOW() WHERE id = $1")
+            .bind(invite_id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn open_invite_for(&self, user_id: Uuid, invite_id: Uuid) -> Result<HouseholdInvite> {
+        self.invites_for(user_id)
+            .await?
+            .into_iter()
+            .find(|invite| invite.id == invite_id)
+            .ok_or_else(|| AppError::NotFound {
+                resource: "Household invite".to_string(),
+            })
+    }
+
+    /// Leave the household. Children are removed by the owner, and the
+    /// owner dissolves the household instead.
+    pub async fn leave(&self, user_id: Uuid) -> Result<()> {
+        let (household, role) = self.require_membership(user_id).await?;
+        match role {
+            HouseholdRole::Owner => Err(AppError::Conflict {
+                message: "The owner cannot leave; dissolve the household instead".to_string(),
+            }),
+            HouseholdRole::Child => Err(AppError::OperationNotAllowed {
+                reason: "Only the household owner can remove a child member".to_string(),
+            }),
+            HouseholdRole::Member => self.delete_member(household.id, user_id).await,
+        }
+    }
+
+    /// Remove a member or child (owner)
+    pub async fn remove_member(&self, owner_id: Uuid, member_id: Uuid) -> Result<()> {
+        let household = self.require_owner(owner_id).await?;
+        if member_id == owner_id {
+            return Err(AppError::Conflict {
+                message: "The owner cannot leave; dissolve the household instead".to_string(),
+            });
+        }
+        self.delete_member(household.id, member_id).await
+    }
+
+    async fn delete_member(&self, household_id: Uuid, user_id: Uuid) -> Result<()> {
+        let removed =
+            sqlx::query("DELETE FROM household_members WHERE household_id = $1 AND user_id = $2")
+                .bind(household_id)
+                .bind(user_id)
+                .execute(&self.db_pool)
+                .await?
+                .rows_affected();
+        if removed == 0 {
+            return Err(AppError::NotFound {
+                resource: "Household member".to_string(),
+            });
+        }
+        tracing::info!(household_id = %household_id, user_id = %user_id, "Household member removed");
+        Ok(())
+    }
+
+    /// Switch a member between `member` and `child` (owner). Blocks enforced
+    /// on a child are dropped when they become a member.
+    pub async fn set_member_role(
+        &self,
+        owner_id: Uuid,
+        member_id: Uuid,
+        role: HouseholdRole,
+    ) -> Result<()> {
+        let household = self.require_owner(owner_id).await?;
+        if role == HouseholdRole::Owner || member_id == owner_id {
+            return Err(AppError::InvalidFieldValue {
+                field: "role".to_string(),
+                message: "Only members and children can change role".to_string(),
+            });
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+        let updated = sqlx::query(
+            "UPDATE household_members SET role = $3 WHERE household_id = $1 AND user_id = $2",
+        )
+        .bind(household.id)
+        .bind(member_id)
+        .bind(role)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Err(AppError::NotFound {
+                resource: "Household member".to_string(),
+            });
+        }
+        if role == HouseholdRole::Member {
+            sqlx::query("DELETE FROM household_enforced_blocks WHERE user_id = $1")
+                .bind(member_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// The household's shared blocklist
+    pub async fn shared_blocks(&self, user_id: Uuid) -> Result<Vec<HouseholdBlock>> {
+        let (household, _) = self.require_membership(user_id).await?;
+        let blocks = sqlx::query_as(
+            r#"
+            SELECT hab.artist_id, a.canonical_name, hab.note, hab.added_by, hab.created_at
+            FROM household_artist_blocks hab
+            JOIN artists a ON a.id = hab.artist_id
+            WHERE hab.household_id = $1
+            ORDER BY hab.created_at DESC
+            "#,
+        )
+        .bind(household.id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(blocks)
+    }
+
+    /// Add an artist to the shared blocklist (owner or member)
+    pub async fn add_shared_block(
+        &self,
+        user_id: Uuid,
+        artist_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<()> {
+        let household = self.require_list_editor(user_id).await?;
+        let added = sqlx::query(
+            r#"
+            INSERT INTO household_artist_blocks (household_id, artist_id, note, added_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (household_id, artist_id) DO NOTHING
+            "#,
+        )
+        .bind(household.id)
+        .bind(artist_id)
+        .bind(note)
+        .bind(user_id)
+        .execute(&self.db_pool)
+        .await
+        .map_err(artist_fk_not_found)?
+        .rows_affected();
+        if added == 0 {
+            return Err(AppError::AlreadyExists {
+                resource: "Household block".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Remove an artist from the shared blocklist (owner or member)
+    pub async fn remove_shared_block(&self, user_id: Uuid, artist_id: Uuid) -> Result<()> {
+        let household = self.require_list_editor(user_id).await?;
+        let removed = sqlx::query(
+            "DELETE FROM household_artist_blocks WHERE household_id = $1 AND artist_id = $2",
+        )
+        .bind(household.id)
+        .bind(artist_id)
+        .execute(&self.db_pool)
+        .await?
+        .rows_affected();
+        if removed == 0 {
+            return Err(AppError::NotFound {
+                resource: "Household block".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn require_list_editor(&self, user_id: Uuid) -> Result<Household> {
+        let (household, role) = self.require_membership(user_id).await?;
+        if !role.can_edit_shared_list() {
+            return Err(AppError::OperationNotAllowed {
+                reason: "Children cannot change the household blocklist".to_string(),
+            });
+        }
+        Ok(household)
+    }
+
+    /// Blocks enforced on a child (owner)
+    pub async fn enforced_blocks(
+        &self,
+        owner_id: Uuid,
+        child_id: Uuid,
+    ) -> Result<Vec<HouseholdBlock>> {
+        let household = self.require_owner(owner_id).await?;
+        self.require_child(household.id, child_id).await?;
+        let blocks = sqlx::query_as(
+            r#"
+            SELECT heb.artist_id, a.canonical_name, heb.note, heb.added_by, heb.created_at
+            FROM household_enforced_blocks heb
+            JOIN artists a ON a.id = heb.artist_id
+            WHERE heb.household_id = $1 AND heb.user_id = $2
+            ORDER BY heb.created_at DESC
+            "#,
+        )
+        .bind(household.id)
+        .bind(child_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(blocks)
+    }
+
+    /// Enforce a block on a child that they cannot remove (owner)
+    pub async fn add_enforced_block(
+        &self,
+        owner_id: Uuid,
+        child_id: Uuid,
+        artist_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<()> {
+        let household = self.require_owner(owner_id).await?;
+        self.require_child(household.id, child_id).await?;
+        let added = sqlx::query(
+            r#"
+            INSERT INTO household_enforced_blocks (household_id, user_id, artist_id, note, added_by)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id, artist_id) DO NOTHING
+            "#,
+        )
+        .bind(household.id)
+        .bind(child_id)
+        .bind(artist_id)
+        .bind(note)
+        .bind(owner_id)
+        .execute(&self.db_pool)
+        .await
+        .map_err(artist_fk_not_found)?
+        .rows_affected();
+        if added == 0 {
+            return Err(AppError::AlreadyExists {
+                resource: "Enforced block".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Lift a block enforced on a child (owner)
+    pub async fn remove_enforced_block(
+        &self,
+        owner_id: Uuid,
+        child_id: Uuid,
+        artist_id: Uuid,
+    ) -> Result<()> {
+        let household = self.require_owner(owner_id).await?;
+        let removed = sqlx::query(
+            r#"
+            DELETE FROM household_enforced_blocks
+            WHERE household_id = $1 AND user_id = $2 AND artist_id = $3
+            "#,
+        )
+        .bind(household.id)
+        .bind(child_id)
+        .bind(artist_id)
+        .execute(&self.db_pool)
+        .await?
+        .rows_affected();
+        if removed == 0 {
+            return Err(AppError::NotFound {
+                resource: "Enforced block".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn require_child(&self, household_id: Uuid, user_id: Uuid) -> Result<()> {
+        let role: Option<HouseholdRole> = sqlx::query_scalar(
+            "SELECT role FROM household_members WHERE household_id = $1 AND user_id = $2",
+        )
+        .bind(household_id)
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        match role {
+            Some(HouseholdRole::Child) => Ok(()),
+            Some(_) => Err(AppError::OperationNotAllowed {
+                reason: "Blocks can only be enforced on child members".to_string(),
+            }),
+            None => Err(AppError::NotFound {
+                resource: "Household member".to_string(),
+            }),
+        }
+    }
+
+    /// Whether the household owner enforces a block on this artist for the user
+    pub async fn is_enforced(&self, user_id: Uuid, artist_id: Uuid) -> Result<bool> {
+        let enforced: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM household_enforced_blocks WHERE user_id = $1 AND artist_id = $2
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(artist_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(enforced)
+    }
+
+    /// Every artist blocked for the user, with where each block comes from
+    pub async fn effective_blocklist(&self, user_id: Uuid) -> Result<Vec<EffectiveBlock>> {
+        let blocks = sqlx::query_as(
+            r#"
+            SELECT
+                eab.artist_id,
+                a.canonical_name,
+                ARRAY_AGG(DISTINCT eab.source ORDER BY eab.source) AS sources,
+                (ARRAY_AGG(eab.note) FILTER (WHERE eab.note IS NOT NULL))[1] AS note,
+                BOOL_OR(eab.source = 'enforced') AS locked
+            FROM effective_artist_blocks eab
+            JOIN artists a ON a.id = eab.artist_id
+            WHERE eab.user_id = $1
+            GROUP BY eab.artist_id, a.canonical_name
+            ORDER BY a.canonical_name
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(blocks)
+    }
+}
+
+fn validate_name(name: &str) -> Result<&str> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return Err(AppError::InvalidFieldValue {
+            field: "name".to_string(),
+            message: "Name must be 1-100 characters".to_string(),
+        });
+    }
+    Ok(name)
+}
+
+fn artist_fk_not_found(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db) if db.is_foreign_key_violation() => AppError::NotFound {
+            resource: "Artist".to_string(),
+        },
+        e => e.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_owners_and_members_edit_shared_list() {
+        assert!(HouseholdRole::Owner.can_edit_shared_list());
+        assert!(HouseholdRole::Member.can_edit_shared_list());
+        assert!(!HouseholdRole::Child.can_edit_shared_list());
+    }
+
+    #[test]
+    fn test_household_name_is_trimmed_and_bounded() {
+        assert_eq!(validate_name("  The Smiths  ").unwrap(), "The Smiths");
+        assert!(validate_name("   ").is_err());
+        assert!(validate_name(&"x".repeat(101)).is_err());
+    }
+}
@@ -0,0 +1,660 @@
+//! JWT signing keyring
+//!
+//! Access tokens are signed RS256 with the current key from a keyring kept in
+//! `jwt_signing_keys`. Every key that hasn't retired is published in the
+//! JWKS, so verifiers such as Convex can check tokens from the previous key
+//! while the new one takes over.
+//!
+//! Rotation is scheduled: a successor is generated `publish_lead_hours`
+//! before it activates, giving verifiers time to refetch the JWKS, and the
+//! key it replaces retires once the longest-lived token it could have signed
+//! has expired. Private keys are envelope-encrypted with a data key from the
+//! KMS provider and only held in plaintext in memory.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm,
+};
+use anyhow::anyhow;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{DecodingKey, EncodingKey};
+use ndith_core::config::JwtKeyringConfig;
+use ndith_core::{AppError, Result};
+use rand::RngCore;
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding};
+use rsa::traits::PublicKeyParts;
+use rsa::RsaPrivateKey;
+use serde::Serialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use crate::kms::KmsProvider;
+
+/// kid of the key configured through `JWT_RSA_PRIVATE_KEY` before the keyring
+/// existed. Tokens already issued carry it, so an imported key keeps it.
+pub const LEGACY_KID: &str = "ndith-1";
+
+const RSA_KEY_BITS: usize = 2048;
+
+/// Slack on top of the token lifetime before a replaced key retires, so a
+/// token signed just before the switch isn't rejected for clock skew.
+const RETIREMENT_GRACE_MINUTES: i64 = 5;
+
+/// Serialises rotation across instances (`pg_advisory_xact_lock` key).
+const ROTATION_LOCK_ID: i64 = 0x6e64_6974_686a_776b;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct StoredSigningKey {
+    kid: String,
+    public_n: String,
+    public_e: String,
+    encrypted_private_key: Option<Vec<u8>>,
+    private_key_nonce: Option<Vec<u8>>,
+    encrypted_data_key: Option<Vec<u8>>,
+    data_key_id: Option<String>,
+    activates_at: DateTime<Utc>,
+    retires_at: Option<DateTime<Utc>>,
+}
+
+/// A key loaded into memory. `encoding` is missing when the private key
+/// couldn't be decrypted; such a key still verifies but never signs.
+#[derive(Clone)]
+struct KeyringEntry {
+    kid: String,
+    public_n: String,
+    public_e: String,
+    activates_at: DateTime<Utc>,
+    retires_at: Option<DateTime<Utc>>,
+    encoding: Option<EncodingKey>,
+    decoding: DecodingKey,
+}
+
+impl KeyringEntry {
+    fn is_live(&self, now: DateTime<Utc>) -> bool {
+        self.retires_at.map(|at| at > now).unwrap_or(true)
+    }
+}
+
+/// Summary of a key in the keyring
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SigningKeyInfo {
+    pub kid: String,
+    pub activates_at: DateTime<Utc>,
+    pub retires_at: Option<DateTime<Utc>>,
+    pub signing: bool,
+}
+
+/// What a rotation check decided to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RotationAction {
+    /// Nothing due
+    None,
+    /// No usable signing key; generate one that signs right away
+    GenerateImmediate,
+    /// Publish a successor that starts signing at the given time
+    Schedule(DateTime<Utc>),
+}
+
+/// Result of one rotation run
+#[derive(Debug, Clone, Default)]
+pub struct RotationOutcome {
+    pub generated: Option<String>,
+    pub retired: Vec<String>,
+}
+
+fn rotation_action(
+    entries: &[KeyringEntry],
+    now: DateTime<Utc>,
+    config: &JwtKeyringConfig,
+) -> RotationAction {
+    let Some(current) = signing_entry(entries, now) else {
+        return RotationAction::GenerateImmediate;
+    };
+    if entries.iter().any(|e| e.activates_at > now) {
+        return RotationAction::None;
+    }
+
+    // The successor always gets the full publish lead, so it can activate
+    // up to one check interval after the nominal rotation date
+    let due = current.activates_at + Duration::days(config.rotation_interval_days);
+    let lead = Duration::hours(config.publish_lead_hours);
+    if now + lead >= due {
+        RotationAction::Schedule(now + lead)
+    } else {
+        RotationAction::None
+    }
+}
+
+fn signing_entry(entries: &[KeyringEntry], now: DateTime<Utc>) -> Option<&KeyringEntry> {
+    entries
+        .iter()
+        .filter(|e| e.encoding.is_some() && e.activates_at <= now && e.is_live(now))
+        .max_by_key(|e| e.activates_at)
+}
+
+fn entry_from_private_key(
+    kid: String,
+    private_key: &RsaPrivateKey,
+    activates_at: DateTime<Utc>,
+    retires_at: Option<DateTime<Utc>>,
+) -> Result<KeyringEntry> {
+    let pem = private_key
+        .to_pkcs8_pem(LineEnding::LF)
+        .map_err(|e| anyhow!("Failed to encode RSA private key: {}", e))?;
+    let public_key = private_key.to_public_key();
+    let public_n = URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be());
+    let public_e = URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be());
+
+    Ok(KeyringEntry {
+        encoding: Some(EncodingKey::from_rsa_pem(pem.as_bytes())?),
+        decoding: DecodingKey::from_rsa_components(&public_n, &public_e)?,
+        kid,
+        public_n,
+        public_e,
+        activates_at,
+        retires_at,
+    })
+}
+
+fn new_kid(activates_at: DateTime<Utc>) -> String {
+    format!(
+        "ndith-{}-{}",
+        activates_at.format("%Y%m%d"),
+        &Uuid::new_v4().simple().to_string()[..8]
+    )
+}
+
+pub struct JwtKeyring {
+    db_pool: PgPool,
+    kms: Arc<dyn KmsProvider>,
+    config: JwtKeyringConfig,
+    max_token_lifetime: Duration,
+    legacy_key: RwLock<Option<RsaPrivateKey>>,
+    entries: RwLock<Vec<KeyringEntry>>,
+}
+
+impl JwtKeyring {
+    /// `max_token_lifetime` is the longest an access token stays valid; a
+    /// replaced key keeps verifying for that long.
+    pub fn new(
+        db_pool: PgPool,
+        kms: Arc<dyn KmsProvider>,
+        config: JwtKeyringConfig,
+        max_token_lifetime: Duration,
+    ) -> Self {
+        Self {
+            db_pool,
+            kms,
+            config,
+            max_token_lifetime,
+            legacy_key: RwLock::new(None),
+            entries: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn config(&self) -> &JwtKeyringConfig {
+        &self.config
+    }
+
+    /// Sign with a PKCS#8 PEM key as `LEGACY_KID` until the keyring has
+    /// been loaded. The first rotation check imports it into an empty
+    /// keyring so tokens it already signed keep verifying.
+    pub fn load_legacy_key(&self, pem: &str) -> Result<()> {
+        let private_key = RsaPrivateKey::from_pkcs8_pem(pem)
+            .map_err(|e| anyhow!("Failed to parse RSA private key: {}", e))?;
+        let entry = entry_from_private_key(
+            LEGACY_KID.to_string(),
+            &private_key,
+            DateTime::<Utc>::UNIX_EPOCH,
+            None,
+        )?;
+
+        *self.legacy_key.write().expect("keyring lock poisoned") = Some(private_key);
+        self.entries
+            .write()
+            .expect("keyring lock poisoned")
+            .push(entry);
+        Ok(())
+    }
+
+    /// kid and key to sign a new access token with, if any key is active
+    pub fn signing_key(&self) -> Option<(String, EncodingKey)> {
+        let entries = self.entries.read().expect("keyring lock poisoned");
+        signing_entry(&entries, Utc::now())
+            .and_then(|e| e.encoding.clone().map(|key| (e.kid.clone(), key)))
+    }
+
+    /// Verification key for a token's `kid`. Retired keys are not returned.
+    pub fn decoding_key(&self, kid: &str) -> Option<DecodingKey> {
+        let now = Utc::now();
+        let entries = self.entries.read().expect("keyring lock poisoned");
+        entries
+            .iter()
+            .find(|e| e.kid == kid && e.is_live(now))
+            .map(|e| e.decoding.clone())
+    }
+
+    /// JWKS document listing every key that hasn't retired, newest first
+    pub fn jwks(&self) -> serde_json::Value {
+        let now = Utc::now();
+        let entries = self.entries.read().expect("keyring lock poisoned");
+        let mut live: Vec<&KeyringEntry> = entries.iter().filter(|e| e.is_live(now)).collect();
+        live.sort_by_key(|e| std::cmp::Reverse(e.activates_at));
+
+        let keys: Vec<serde_json::Value> = live
+            .into_iter()
+            .map(|e| {
+                serde_json::json!({
+                    "kty": "RSA",
+                    "use": "sig",
+                    "alg": "RS256",
+                    "kid": e.kid,
+                    "n": e.public_n,
+                    "e": e.public_e,
+                })
+            })
+            .collect();
+
+        serde_json::json!({ "keys": keys })
+    }
+
+    /// Keys currently in the keyring, newest first
+    pub fn keys(&self) -> Vec<SigningKeyInfo> {
+        let now = Utc::now();
+        let entries = self.entries.read().expect("keyring lock poisoned");
+        let signing_kid = signing_entry(&entries, now).map(|e| e.kid.clone());
+
+        let mut keys: Vec<SigningKeyInfo> = entries
+            .iter()
+            .filter(|e| e.is_live(now))
+            .map(|e| SigningKeyInfo {
+                kid: e.kid.clone(),
+                activates_at: e.activates_at,
+                retires_at: e.retires_at,
+                signing: signing_kid.as_deref() == Some(e.kid.as_str()),
+            })
+            .collect();
+        keys.sort_by_key(|k| std::cmp::Reverse(k.activates_at));
+        keys
+    }
+
+    /// Reload the keyring from the database, replacing what's in memory.
+    /// The legacy key is kept only while the table is still empty.
+    pub async fn reload(&self) -> Result<()> {
+        let mut loaded = self.load_entries(&self.db_pool).await?;
+
+        if loaded.is_empty() {
+            if let Some(legacy) = self
+                .entries
+                .read()
+                .expect("keyring lock poisoned")
+                .iter()
+                .find(|e| e.kid == LEGACY_KID)
+            {
+                loaded.push(legacy.clone());
+            }
+        }
+
+        *self.entries.write().expect("keyring lock poisoned") = loaded;
+        Ok(())
+    }
+
+    /// Check the schedule once: import the legacy key into an empty
+    /// keyring, publish a successor when one is due, and retire keys whose
+    /// tokens have all expired. Safe to run from every instance.
+    pub async fn rotate_if_due(&self) -> Result<RotationOutcome> {
+        self.rotate(false).await
+    }
+
+    /// Replace the signing key now, e.g. after a suspected compromise.
+    /// Tokens signed with the old key stay valid until they expire.
+    pub async fn rotate_now(&self) -> Result<RotationOutcome> {
+        self.rotate(true).await
+    }
+
+    async fn rotate(&self, immediate: bool) -> Result<RotationOutcome> {
+        let mut outcome = RotationOutcome::default();
+        let mut tx = self.db_pool.begin().await?;
+
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(ROTATION_LOCK_ID)
+            .execute(&mut *tx)
+            .await?;
+
+        let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jwt_signing_keys")
+            .fetch_one(&mut *tx)
+            .await?;
+        let legacy_key = self
+            .legacy_key
+            .read()
+            .expect("keyring lock poisoned")
+            .clone();
+        if let (0, Some(legacy_key)) = (stored, legacy_key) {
+            self.store_key(&mut tx, LEGACY_KID, &legacy_key, Utc::now())
+                .await?;
+            tracing::info!(
+                kid = LEGACY_KID,
+                "Imported JWT_RSA_PRIVATE_KEY into the signing keyring"
+            );
+        }
+
+        let now = Utc::now();
+        let action = if immediate {
+            // A successor that hasn't signed anything yet is simply dropped
+            sqlx::query(
+                "DELETE FROM jwt_signing_keys WHERE activates_at > $1 AND retired_at IS NULL",
+            )
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+            RotationAction::GenerateImmediate
+        } else {
+            let entries = self.load_entries(&mut *tx).await?;
+            rotation_action(&entries, now, &self.config)
+        };
+
+        let activates_at = match action {
+            RotationAction::None => None,
+            RotationAction::GenerateImmediate => Some(now),
+            RotationAction::Schedule(at) => Some(at),
+        };
+
+        if let Some(activates_at) = activates_at {
+            let kid = self.insert_new_key(&mut tx, activates_at).await?;
+
+            // Keys the new one replaces stop verifying once the last token
+            // they could have signed has expired
+            let retires_at = activates_at
+                + self.max_token_lifetime
+                + Duration::minutes(RETIREMENT_GRACE_MINUTES);
+            sqlx::query(
+                r#"
+                UPDATE jwt_signing_keys
+                SET retires_at = $2
+                WHERE kid <> $1 AND retired_at IS NULL
+                  AND (retires_at IS NULL OR retires_at > $2)
+                "#,
+            )
+            .bind(&kid)
+            .bind(retires_at)
+            .execute(&mut *tx)
+            .await?;
+
+            tracing::info!(kid = %kid, activates_at = %activates_at, "Published new JWT signing key");
+            outcome.generated = Some(kid);
+        }
+
+        outcome.retired = sqlx::query_scalar(
+            r#"
+            UPDATE jwt_signing_keys
+            SET retired_at = NOW(),
+                encrypted_private_key = NULL,
+                private_key_nonce = NULL,
+                encrypted_data_key = NULL
+            WHERE retired_at IS NULL AND retires_at <= NOW()
+            RETURNING kid
+            "#,
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        for kid in &outcome.retired {
+            tracing::info!(kid = %kid, "Retired JWT signing key");
+        }
+
+        self.reload().await?;
+        Ok(outcome)
+    }
+
+    /// Check the schedule on an interval in the background
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = std::time::Duration::from_secs(self.config.check_interval_minutes * 60);
+            let mut ticker = tokio::time::interval(period);
+
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.rotate_if_due().await {
+                    tracing::error!(error = %e, "JWT signing key rotation failed");
+                }
+            }
+        })
+    }
+
+    async fn load_entries<'e, E>(&self, executor: E) -> Result<Vec<KeyringEntry>>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let stored = sqlx::query_as::<_, StoredSigningKey>(
+            r#"
+            SELECT kid, public_n, public_e, encrypted_private_key, private_key_nonce,
+                   encrypted_data_key, data_key_id, activates_at, retires_at
+            FROM jwt_signing_keys
+            WHERE retired_at IS NULL
+            ORDER BY activates_at
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        let mut entries = Vec::with_capacity(stored.len());
+        for key in stored {
+            let decoding = match DecodingKey::from_rsa_components(&key.public_n, &key.public_e) {
+                Ok(decoding) => decoding,
+                Err(e) => {
+                    tracing::error!(kid = %key.kid, error = %e, "Invalid JWT signing public key");
+                    continue;
+                }
+            };
+            let encoding = self.decrypt_private_key(&key).unwrap_or_else(|e| {
+                tracing::error!(
+                    kid = %key.kid,
+                    error = %e,
+                    "Failed to decrypt JWT signing key; it will verify but not sign"
+                );
+                None
+            });
+
+            entries.push(KeyringEntry {
+                kid: key.kid,
+                public_n: key.public_n,
+                public_e: key.public_e,
+                activates_at: key.activates_at,
+                retires_at: key.retires_at,
+                encoding,
+                decoding,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    async fn insert_new_key(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        activates_at: DateTime<Utc>,
+    ) -> Result<String> {
+        let private_key = tokio::task::spawn_blocking(|| {
+            RsaPrivateKey::new(&mut rand::rngs::OsRng, RSA_KEY_BITS)
+        })
+        .await
+        .map_err(|e| anyhow!("RSA key generation task failed: {}", e))?
+        .map_err(|e| anyhow!("Failed to generate RSA key: {}", e))?;
+
+        let kid = new_kid(activates_at);
+        self.store_key(tx, &kid, &private_key, activates_at).await?;
+        Ok(kid)
+    }
+
+    async fn store_key(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        kid: &str,
+        private_key: &RsaPrivateKey,
+        activates_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let pem = private_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .map_err(|e| anyhow!("Failed to encode RSA private key: {}", e))?;
+        let public_key = private_key.to_public_key();
+
+        let data_key_id = format!("jwt-signing-{}", kid);
+        let data_key = self.kms.generate_data_key(&data_key_id)?;
+        let cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(
+            &data_key.plaintext_key,
+        ));
+        let mut nonce_bytes = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+        let ciphertext = cipher
+            .encrypt(aes_gcm::Nonce::from_slice(&nonce_bytes), pem.as_bytes())
+            .map_err(|_| anyhow!("Failed to encrypt JWT signing key"))?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO jwt_signing_keys (
+                kid, public_n, public_e, encrypted_private_key, private_key_nonce,
+                encrypted_data_key, data_key_id, activates_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (kid) DO NOTHING
+            "#,
+        )
+        .bind(kid)
+        .bind(URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be()))
+        .bind(URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be()))
+        .bind(ciphertext)
+        .bind(nonce_bytes.to_vec())
+        .bind(&data_key.encrypted_key)
+        .bind(&data_key_id)
+        .bind(activates_at)
+        .execute(&mut **tx)
+        .await?;
+
+        if inserted.rows_affected() == 0 {
+            return Err(AppError::Conflict {
+                message: format!("JWT signing key {} already exists", kid),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn decrypt_private_key(&self, key: &StoredSigningKey) -> Result<Option<EncodingKey>> {
+        let (Some(ciphertext), Some(nonce), Some(encrypted_data_key), Some(data_key_id)) = (
+            &key.encrypted_private_key,
+            &key.private_key_nonce,
+            &key.encrypted_data_key,
+            &key.data_key_id,
+        ) else {
+            return Ok(None);
+        };
+        if nonce.len() != 12 {
+            return Err(anyhow!("Invalid nonce length").into());
+        }
+
+        let plaintext_key = self.kms.decrypt_data_key(encrypted_data_key, data_key_id)?;
+        let cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(&plaintext_key));
+        let pem = cipher
+            .decrypt(aes_gcm::Nonce::from_slice(nonce), ciphertext.as_ref())
+            .map_err(|_| anyhow!("Failed to decrypt JWT signing key"))?;
+
+        Ok(Some(EncodingKey::from_rsa_pem(&pem)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> JwtKeyringConfig {
+        JwtKeyringConfig {
+            rotation_interval_days: 30,
+            publish_lead_hours: 24,
+            check_interval_minutes: 60,
+        }
+    }
+
+    // Scheduling never signs, so a small key keeps the tests fast
+    fn test_key() -> RsaPrivateKey {
+        RsaPrivateKey::new(&mut rand::rngs::OsRng, 1024).unwrap()
+    }
+
+    fn entry(
+        key: &RsaPrivateKey,
+        kid: &str,
+        activates_at: DateTime<Utc>,
+        retires_at: Option<DateTime<Utc>>,
+    ) -> KeyringEntry {
+        entry_from_private_key(kid.to_string(), key, activates_at, retires_at).unwrap()
+    }
+
+    #[test]
+    fn test_rotation_schedule() {
+        let key = test_key();
+        let now = Utc::now();
+
+        assert_eq!(
+            rotation_action(&[], now, &config()),
+            RotationAction::GenerateImmediate
+        );
+
+        let fresh = [entry(&key, "a", now - Duration::days(28), None)];
+        assert_eq!(
+            rotation_action(&fresh, now, &config()),
+            RotationAction::None
+        );
+
+        // Within the publish lead of the rotation date
+        let due = [entry(&key, "a", now - Duration::days(29), None)];
+        assert_eq!(
+            rotation_action(&due, now, &config()),
+            RotationAction::Schedule(now + Duration::hours(24))
+        );
+
+        // Successor already published
+        let pending = [
+            entry(&key, "a", now - Duration::days(40), None),
+            entry(&key, "b", now + Duration::hours(2), None),
+        ];
+        assert_eq!(
+            rotation_action(&pending, now, &config()),
+            RotationAction::None
+        );
+
+        // The only key can't sign
+        let mut undecryptable = entry(&key, "a", now - Duration::days(1), None);
+        undecryptable.encoding = None;
+        assert_eq!(
+            rotation_action(&[undecryptable], now, &config()),
+            RotationAction::GenerateImmediate
+        );
+    }
+
+    #[test]
+    fn test_signing_key_switches_at_activation() {
+        let key = test_key();
+        let now = Utc::now();
+        let entries = [
+            entry(
+                &key,
+                "old",
+                now - Duration::days(30),
+                Some(now + Duration::hours(25)),
+            ),
+            entry(&key, "new", now + Duration::hours(1), None),
+        ];
+
+        assert_eq!(signing_entry(&entries, now).unwrap().kid, "old");
+        assert_eq!(
+            signing_entry(&entries, now + Duration::hours(2))
+                .unwrap()
+                .kid,
+            "new"
+        );
+        assert!(!entries[0].is_live(now + Duration::hours(26)));
+    }
+}
@@ -5,6 +5,7 @@
 //! configured via the `KMS_PROVIDER` environment variable.
 
 use anyhow::Result;
+use ndith_core::config::{ConfigError, Environment};
 use ndith_core::models::DataKey;
 use std::sync::Arc;
 
@@ -133,6 +134,27 @@ pub fn create_kms_provider() -> Arc<dyn KmsProvider> {
     }
 }
 
+/// Create the KMS provider, requiring a real one outside development.
+///
+/// The mock provider keeps its master key in the process, so anything it
+/// protects is only as safe as the host it runs on.
+pub fn require_kms_provider(
+    env: Environment,
+) -> std::result::Result<Arc<dyn KmsProvider>, ConfigError> {
+    let configured = std::env::var("KMS_PROVIDER").unwrap_or_default();
+    let provider_type =
+        KmsProviderType::from_env(&configured).ok_or_else(|| ConfigError::InvalidValue {
+            key: "KMS_PROVIDER".to_string(),
+            message: format!("Unknown provider '{}'", configured),
+        })?;
+
+    if provider_type == KmsProviderType::Mock && !env.is_development() {
+        return Err(ConfigError::ProductionRequired("KMS_PROVIDER".to_string()));
+    }
+
+    Ok(create_kms_provider())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
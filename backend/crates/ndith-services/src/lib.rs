@@ -7,6 +7,10 @@
 // Auth
 pub mod auth;
 pub mod auth_simple;
+pub mod jwt_keyring;
+pub mod oidc_provider;
+pub mod recovery_codes;
+pub mod webauthn;
 
 // Metrics
 pub mod metrics;
@@ -36,6 +40,7 @@ pub mod apple_music;
 pub mod apple_music_enforcement;
 pub mod playlist_repository;
 pub mod playlist_sanitizer;
+pub mod recently_played;
 pub mod spotify;
 pub mod spotify_library;
 pub mod tidal;
@@ -52,9 +57,15 @@ pub mod token_vault_repository;
 pub mod token_refresh_job;
 
 // Core services
+pub mod audit_chain;
 pub mod audit_logging;
+pub mod data_requests;
 pub mod dnp_list;
+pub mod email_verification;
+pub mod household;
 pub mod offense;
+pub mod roles;
+pub mod transparency;
 pub mod user;
 
 // Rate limiting and job queue
@@ -68,7 +79,8 @@ pub mod monitoring;
 pub mod registration_monitoring;
 pub mod registration_performance;
 
-// Notification service
+// Notification service and outgoing mail
+pub mod mailer;
 pub mod notification_service;
 
 // Stubs for testing
@@ -76,10 +88,16 @@ pub mod stubs;
 
 // ---- Re-exports ----
 
+pub use audit_chain::AuditChainService;
 pub use audit_logging::*;
 pub use auth::AuthService;
 pub use auth_simple::AuthService as SimpleAuthService;
+pub use data_requests::DataRequestService;
 pub use dnp_list::DnpListService;
+pub use email_verification::EmailVerificationService;
+pub use household::HouseholdService;
+pub use jwt_keyring::JwtKeyring;
+pub use mailer::{mailer_from_config, EmailMessage, FileMailer, HttpMailer, Mailer};
 pub use monitoring::*;
 pub use oauth::{BaseOAuthProvider, OAuthProvider, OAuthStateManager};
 pub use oauth_apple::{AppleOAuthConfig, AppleOAuthProvider, AppleOAuthService};
@@ -94,12 +112,16 @@ pub use oauth_health_monitor::{
 pub use oauth_spotify::SpotifyOAuthProvider;
 pub use oauth_youtube_music::YouTubeMusicOAuthProvider;
 pub use offense::OffenseService;
+pub use oidc_provider::OidcProviderService;
 pub use rate_limiting_middleware::{registration_rate_limit_middleware, RateLimitService};
+pub use roles::RoleService;
+pub use transparency::{TransparencyConfig, TransparencyStatsService};
 pub use user::UserService;
+pub use webauthn::WebAuthnService;
 
 pub use kms::{
-    create_kms_provider, KmsProvider, KmsProviderType, MockKmsProvider, VaultAuthMethod,
-    VaultConfig, VaultKmsProvider,
+    create_kms_provider, require_kms_provider, KmsProvider, KmsProviderType, MockKmsProvider,
+    VaultAuthMethod, VaultConfig, VaultKmsProvider,
 };
 
 pub use stubs::*;
@@ -126,6 +148,7 @@ pub use notification_service::NotificationService;
 
 pub use playlist_repository::PlaylistRepository;
 pub use playlist_sanitizer::PlaylistSanitizerService;
+pub use recently_played::{RecentPlay, RecentlyPlayedService};
 pub use spotify::{SpotifyConfig, SpotifyService};
 pub use spotify_library::SpotifyLibraryService;
 
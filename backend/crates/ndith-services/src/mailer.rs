@@ -0,0 +1,170 @@
+//! Outgoing mail
+//!
+//! Everything that sends mail goes through the `Mailer` trait so the
+//! transport can be swapped by configuration. `FileMailer` writes each
+//! message as an `.eml` file, which is what local development and tests
+//! use; `HttpMailer` hands messages to a mail provider's JSON API.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use ndith_core::config::{EmailConfig, MailTransport};
+use ndith_core::{AppError, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// A plain-text message to one recipient
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text: String,
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> Result<()>;
+}
+
+/// Build the mailer selected by `config.transport`
+pub fn mailer_from_config(config: &EmailConfig) -> Arc<dyn Mailer> {
+    match &config.transport {
+        MailTransport::File { dir } => {
+            Arc::new(FileMailer::new(dir.clone(), config.from_address.clone()))
+        }
+        MailTransport::Http { endpoint, api_key } => Arc::new(HttpMailer::new(
+            endpoint.clone(),
+            api_key.clone(),
+            config.from_address.clone(),
+        )),
+    }
+}
+
+/// Writes every message to `<dir>/<timestamp>-<id>.eml`
+pub struct FileMailer {
+    dir: PathBuf,
+    from: String,
+}
+
+impl FileMailer {
+    pub fn new(dir: impl Into<PathBuf>, from: String) -> Self {
+        Self {
+            dir: dir.into(),
+            from,
+        }
+    }
+
+    fn render(&self, message: &EmailMessage, message_id: Uuid) -> String {
+        format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMessage-ID: <{}@ndith.local>\r\n\
+             MIME-Version: 1.0\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n",
+            self.from,
+            message.to,
+            message.subject,
+            Utc::now().to_rfc2822(),
+            message_id,
+            message.text.replace('\n', "\r\n"),
+        )
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<()> {
+        let message_id = Uuid::new_v4();
+        let path = self.dir.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S%.3fZ"),
+            message_id
+        ));
+
+        let write = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            tokio::fs::write(&path, self.render(message, message_id)).await
+        };
+        write.await.map_err(|e| AppError::Internal {
+            message: Some(format!("Failed to write mail to {}: {}", path.display(), e)),
+        })?;
+
+        tracing::info!(path = %path.display(), subject = %message.subject, "Mail written to file sink");
+        Ok(())
+    }
+}
+
+/// POSTs `{from, to, subject, text}` to a mail provider endpoint
+pub struct HttpMailer {
+    client: reqwest::Client,
+    endpoint: String,
+    api_key: Option<String>,
+    from: String,
+}
+
+impl HttpMailer {
+    pub fn new(endpoint: String, api_key: Option<String>, from: String) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            endpoint,
+            api_key,
+            from,
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for HttpMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<()> {
+        let mut request = self.client.post(&self.endpoint).json(&serde_json::json!({
+            "from": self.from,
+            "to": message.to,
+            "subject": message.subject,
+            "text": message.text,
+        }));
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            tracing::warn!(error = %e, "Mail provider request failed");
+            AppError::ExternalServiceUnavailable {
+                service: "mail".to_string(),
+            }
+        })?;
+        if !response.status().is_success() {
+            tracing::warn!(status = %response.status(), "Mail provider rejected message");
+            return Err(AppError::ExternalServiceUnavailable {
+                service: "mail".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_mailer_writes_eml() {
+        let dir = tempfile::tempdir().unwrap();
+        let mailer = FileMailer::new(dir.path(), "noreply@example.com".to_string());
+        mailer
+            .send(&EmailMessage {
+                to: "user@example.com".to_string(),
+                subject: "Hello".to_string(),
+                text: "line one\nline two".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let entries: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(entries.len(), 1);
+        let contents = std::fs::read_to_string(entries[0].as_ref().unwrap().path()).unwrap();
+        assert!(contents.starts_with("From: noreply@example.com\r\nTo: user@example.com\r\n"));
+        assert!(contents.contains("Subject: Hello\r\n"));
+        assert!(contents.ends_with("\r\n\r\nline one\r\nline two\r\n"));
+    }
+}
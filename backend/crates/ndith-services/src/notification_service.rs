@@ -122,6 +122,75 @@ impl NotificationService {
         Ok(notification)
     }
 
+    /// Send a security alert when a 2FA recovery code is used to sign in
+    pub async fn notify_recovery_code_used(
+        &self,
+        user_id: Uuid,
+        remaining_codes: i64,
+    ) -> Result<Notification> {
+        let mut message = "A recovery code was used to sign in to your account instead of your authenticator app. If this wasn't you, change your password and regenerate your recovery codes.".to_string();
+        if remaining_codes <= 2 {
+            message.push_str(&format!(
+                " You have {} recovery code{} left.",
+                remaining_codes,
+                if remaining_codes == 1 { "" } else { "s" }
+            ));
+        }
+
+        let notification = Notification::new(
+            user_id,
+            NotificationType::SecurityAlert,
+            "Recovery Code Used".to_string(),
+            message,
+            Some(json!({
+                "event": "recovery_code_used",
+                "remaining_codes": remaining_codes,
+                "action_url": "/settings/security",
+            })),
+        );
+
+        self.save_notification(&notification).await?;
+
+        tracing::info!(
+            user_id = %user_id,
+            remaining_codes = remaining_codes,
+            "Sent SecurityAlert notification for recovery code use"
+        );
+
+        Ok(notification)
+    }
+
+    /// Send a security alert when a passkey or security key is added
+    pub async fn notify_webauthn_credential_added(
+        &self,
+        user_id: Uuid,
+        credential_name: &str,
+    ) -> Result<Notification> {
+        let notification = Notification::new(
+            user_id,
+            NotificationType::SecurityAlert,
+            "Passkey Added".to_string(),
+            format!(
+                "A passkey named \"{}\" was added to your account and can now be used to sign in. If this wasn't you, remove it and change your password.",
+                credential_name
+            ),
+            Some(json!({
+                "event": "webauthn_credential_added",
+                "credential_name": credential_name,
+                "action_url": "/settings/security",
+            })),
+        );
+
+        self.save_notification(&notification).await?;
+
+        tracing::info!(
+            user_id = %user_id,
+            "Sent SecurityAlert notification for new passkey"
+        );
+
+        Ok(notification)
+    }
+
     /// Save a notification to the database
     async fn save_notification(&self, notification: &Notification) -> Result<()> {
         if let Some(pool) = &self.db_pool {
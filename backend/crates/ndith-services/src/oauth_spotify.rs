@@ -85,6 +85,7 @@ impl SpotifyOAuthProvider {
                 "playlist-read-private".to_string(),
                 "playlist-modify-public".to_string(),
                 "playlist-modify-private".to_string(),
+                "user-read-recently-played".to_string(),
             ],
             additional_params: HashMap::new(),
         };
@@ -1,13 +1,35 @@
 use chrono::Utc;
 use ndith_core::error::{AppError, Result};
 use ndith_core::models::offense::{
-    AddEvidenceRequest, ArtistOffense, CreateOffenseRequest, FlaggedArtist, ImportLibraryRequest,
-    LibraryScanResponse, OffenseEvidence, OffenseSeverity, OffenseSummary, OffenseWithEvidence,
+    diff_snapshots, AddEvidenceRequest, AddOffenseEventRequest, ArtistOffense,
+    CreateOffenseRequest, DerivedLegalState, EvidenceStatus, FlaggedArtist, ImportLibraryRequest,
+    LibraryScanResponse, OffenseDispute, OffenseEvent, OffenseEventType, OffenseEvidence,
+    OffenseSeverity, OffenseSummary, OffenseTimeline, OffenseWithEvidence, ResolveDisputeRequest,
+    Revision, RevisionDiff, SubmitDisputeRequest, UpdateEvidenceRequest, UpdateOffenseRequest,
     UserLibraryTrack,
 };
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool, Postgres};
 use uuid::Uuid;
 
+const OFFENSE_COLUMNS: &str = "id, artist_id, category, severity, title, description, \
+     incident_date, incident_date_approximate, arrested, charged, convicted, settled, status, \
+     verified_at, verified_by, submitted_by, created_at, updated_at";
+
+const EVIDENCE_COLUMNS: &str = "id, offense_id, url, source_name, source_type, title, excerpt, \
+     published_date, archived_url, is_primary_source, credibility_score, submitted_by, created_at, \
+     link_status, last_checked_at, needs_rereview";
+
+const DISPUTE_COLUMNS: &str = "id, offense_id, evidence_id, reason, status, previous_status, \
+     submitted_by, resolution_notes, resolved_by, resolved_at, created_at";
+
+/// Open disputes a single user can have across all offenses
+pub const MAX_OPEN_DISPUTES_PER_USER: i64 = 5;
+
+const OFFENSE_EVENT_COLUMNS: &str =
+    "id, offense_id, event_type, event_date, event_date_approximate, \
+     description, evidence_id, source_url, status, source, confidence, reviewed_at, reviewed_by, \
+     submitted_by, created_at";
+
 /// Service for managing artist offenses and evidence
 pub struct OffenseService<'a> {
     db: &'a PgPool,
@@ -63,7 +85,7 @@ impl<'a> OffenseService<'a> {
             r#"
             SELECT id, offense_id, url, source_name, source_type, title, excerpt,
                    published_date, archived_url, is_primary_source, credibility_score,
-                   submitted_by, created_at
+                   submitted_by, created_at, link_status, last_checked_at, needs_rereview
             FROM offense_evidence
             WHERE offense_id = $1
             ORDER BY is_primary_source DESC, credibility_score DESC NULLS LAST
@@ -91,25 +113,31 @@ impl<'a> OffenseService<'a> {
     }
 
     /// Create a new offense (pending verification)
+    ///
+    /// The legacy legal-outcome flags are also recorded as pending timeline
+    /// events dated at the incident date, and are verified with the offense.
     pub async fn create_offense(
         &self,
         request: CreateOffenseRequest,
         submitted_by: Option<Uuid>,
     ) -> Result<ArtistOffense> {
-        let offense = sqlx::query_as::<_, ArtistOffense>(
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+        let offense = sqlx::query_as::<_, ArtistOffense>(&format!(
             r#"
             INSERT INTO artist_offenses (
-                artist_id, category, severity, title, description,
+                artist_id, category, severity, reported_severity, title, description,
                 incident_date, incident_date_approximate, arrested, charged,
                 convicted, settled, submitted_by
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
-            RETURNING id, artist_id, category, severity, title, description,
-                      incident_date, incident_date_approximate, arrested, charged,
-                      convicted, settled, status, verified_at, verified_by,
-                      submitted_by, created_at, updated_at
-            "#,
-        )
+            VALUES ($1, $2, $3, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING {OFFENSE_COLUMNS}
+            "#
+        ))
         .bind(request.artist_id)
         .bind(request.category)
         .bind(request.severity)
@@ -122,10 +150,43 @@ impl<'a> OffenseService<'a> {
         .bind(request.convicted.unwrap_or(false))
         .bind(request.settled.unwrap_or(false))
         .bind(submitted_by)
-        .fetch_one(self.db)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        let initial_events: Vec<OffenseEventType> = [
+            (OffenseEventType::Allegation, true),
+            (OffenseEventType::Arrest, offense.arrested),
+            (OffenseEventType::Charge, offense.charged),
+            (OffenseEventType::Conviction, offense.convicted),
+            (OffenseEventType::Settlement, offense.settled),
+        ]
+        .into_iter()
+        .filter_map(|(event_type, applies)| applies.then_some(event_type))
+        .collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO offense_events (
+                offense_id, event_type, event_date, event_date_approximate, source, submitted_by
+            )
+            SELECT $1, event_type, $3, $4, 'submission', $5
+            FROM UNNEST($2::offense_event_type[]) AS event_type
+            "#,
+        )
+        .bind(offense.id)
+        .bind(&initial_events)
+        .bind(offense.incident_date)
+        .bind(offense.incident_date_approximate)
+        .bind(submitted_by)
+        .execute(&mut *tx)
         .await
         .map_err(AppError::DatabaseQueryFailed)?;
 
+        record_offense_revision(&mut tx, offense.id, "created", submitted_by, None).await?;
+
+        tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
+
         Ok(offense)
     }
 
@@ -135,18 +196,22 @@ impl<'a> OffenseService<'a> {
         request: AddEvidenceRequest,
         submitted_by: Option<Uuid>,
     ) -> Result<OffenseEvidence> {
-        let evidence = sqlx::query_as::<_, OffenseEvidence>(
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+        let evidence = sqlx::query_as::<_, OffenseEvidence>(&format!(
             r#"
             INSERT INTO offense_evidence (
                 offense_id, url, source_name, source_type, title, excerpt,
                 published_date, is_primary_source, credibility_score, submitted_by
             )
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            RETURNING id, offense_id, url, source_name, source_type, title, excerpt,
-                      published_date, archived_url, is_primary_source, credibility_score,
-                      submitted_by, created_at
-            "#,
-        )
+            RETURNING {EVIDENCE_COLUMNS}
+            "#
+        ))
         .bind(request.offense_id)
         .bind(&request.url)
         .bind(&request.source_name)
@@ -157,25 +222,45 @@ impl<'a> OffenseService<'a> {
         .bind(request.is_primary_source.unwrap_or(false))
         .bind(request.credibility_score)
         .bind(submitted_by)
-        .fetch_one(self.db)
+        .fetch_one(&mut *tx)
         .await
         .map_err(AppError::DatabaseQueryFailed)?;
 
+        record_evidence_revision(&mut tx, evidence.id, "created", submitted_by, None).await?;
+
+        tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
+
         Ok(evidence)
     }
 
     /// Verify an offense (moderator action)
-    pub async fn verify_offense(&self, offense_id: Uuid, verified_by: Uuid) -> Result<()> {
+    ///
+    /// Timeline events recorded with the original submission are verified
+    /// along with it. Reviewer notes are required and kept on the revision.
+    pub async fn verify_offense(
+        &self,
+        offense_id: Uuid,
+        verified_by: Uuid,
+        reviewer_notes: &str,
+    ) -> Result<()> {
+        let reviewer_notes = require_notes(reviewer_notes)?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
         let result = sqlx::query(
             r#"
             UPDATE artist_offenses
-            SET status = 'verified', verified_at = NOW(), verified_by = $2
+            SET status = 'verified', verified_at = NOW(), verified_by = $2, updated_at = NOW()
             WHERE id = $1
             "#,
         )
         .bind(offense_id)
         .bind(verified_by)
-        .execute(self.db)
+        .execute(&mut *tx)
         .await
         .map_err(AppError::DatabaseQueryFailed)?;
 
@@ -185,9 +270,624 @@ impl<'a> OffenseService<'a> {
             });
         }
 
+        sqlx::query(
+            r#"
+            UPDATE offense_events
+            SET status = 'verified', reviewed_at = NOW(), reviewed_by = $2
+            WHERE offense_id = $1 AND source = 'submission' AND status = 'pending'
+            "#,
+        )
+        .bind(offense_id)
+        .bind(verified_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        sync_legal_state(&mut tx, offense_id).await?;
+        record_offense_revision(
+            &mut tx,
+            offense_id,
+            "verified",
+            Some(verified_by),
+            Some(reviewer_notes),
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
+
         Ok(())
     }
 
+    /// Edit an offense (moderator action). The previous state stays in the
+    /// revision history; reviewer notes are required.
+    pub async fn update_offense(
+        &self,
+        offense_id: Uuid,
+        request: UpdateOffenseRequest,
+        updated_by: Uuid,
+    ) -> Result<ArtistOffense> {
+        let reviewer_notes = require_notes(&request.reviewer_notes)?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+        // Severity edits change the reported severity; the timeline is then
+        // re-applied on top of it
+        let result = sqlx::query(
+            r#"
+            UPDATE artist_offenses
+            SET category = COALESCE($2, category),
+                reported_severity = COALESCE($3, reported_severity),
+                title = COALESCE($4, title),
+                description = COALESCE($5, description),
+                incident_date = COALESCE($6, incident_date),
+                incident_date_approximate = COALESCE($7, incident_date_approximate),
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(offense_id)
+        .bind(request.category)
+        .bind(request.severity)
+        .bind(&request.title)
+        .bind(&request.description)
+        .bind(request.incident_date)
+        .bind(request.incident_date_approximate)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound {
+                resource: "Offense".to_string(),
+            });
+        }
+
+        sync_legal_state(&mut tx, offense_id).await?;
+        record_offense_revision(
+            &mut tx,
+            offense_id,
+            "updated",
+            Some(updated_by),
+            Some(reviewer_notes),
+        )
+        .await?;
+
+        let offense = sqlx::query_as::<_, ArtistOffense>(&format!(
+            "SELECT {OFFENSE_COLUMNS} FROM artist_offenses WHERE id = $1"
+        ))
+        .bind(offense_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
+
+        Ok(offense)
+    }
+
+    /// Edit evidence (moderator action). Clears the link checker's re-review
+    /// flag, since the edit is the re-review. Reviewer notes are required.
+    pub async fn update_evidence(
+        &self,
+        offense_id: Uuid,
+        evidence_id: Uuid,
+        request: UpdateEvidenceRequest,
+        updated_by: Uuid,
+    ) -> Result<OffenseEvidence> {
+        let reviewer_notes = require_notes(&request.reviewer_notes)?;
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+        let evidence = sqlx::query_as::<_, OffenseEvidence>(&format!(
+            r#"
+            UPDATE offense_evidence
+            SET source_name = COALESCE($3, source_name),
+                source_type = COALESCE($4, source_type),
+                title = COALESCE($5, title),
+                excerpt = COALESCE($6, excerpt),
+                published_date = COALESCE($7, published_date),
+                is_primary_source = COALESCE($8, is_primary_source),
+                credibility_score = COALESCE($9, credibility_score),
+                needs_rereview = FALSE
+            WHERE id = $2 AND offense_id = $1
+            RETURNING {EVIDENCE_COLUMNS}
+            "#
+        ))
+        .bind(offense_id)
+        .bind(evidence_id)
+        .bind(&request.source_name)
+        .bind(&request.source_type)
+        .bind(&request.title)
+        .bind(&request.excerpt)
+        .bind(request.published_date)
+        .bind(request.is_primary_source)
+        .bind(request.credibility_score)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?
+        .ok_or_else(|| AppError::NotFound {
+            resource: "Evidence".to_string(),
+        })?;
+
+        record_evidence_revision(
+            &mut tx,
+            evidence_id,
+            "updated",
+            Some(updated_by),
+            Some(reviewer_notes),
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
+
+        Ok(evidence)
+    }
+
+    /// Get all revisions of an offense, newest first
+    pub async fn get_offense_revisions(&self, offense_id: Uuid) -> Result<Vec<Revision>> {
+        sqlx::query_as::<_, Revision>(
+            r#"
+            SELECT id, offense_id AS entity_id, revision_number, change_type, snapshot,
+                   changed_by, reviewer_notes, created_at
+            FROM offense_revisions
+            WHERE offense_id = $1
+            ORDER BY revision_number DESC
+            "#,
+        )
+        .bind(offense_id)
+        .fetch_all(self.db)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)
+    }
+
+    /// Get all revisions of an evidence item, newest first
+    pub async fn get_evidence_revisions(
+        &self,
+        offense_id: Uuid,
+        evidence_id: Uuid,
+    ) -> Result<Vec<Revision>> {
+        sqlx::query_as::<_, Revision>(
+            r#"
+            SELECT r.id, r.evidence_id AS entity_id, r.revision_number, r.change_type,
+                   r.snapshot, r.changed_by, r.reviewer_notes, r.created_at
+            FROM evidence_revisions r
+            JOIN offense_evidence e ON e.id = r.evidence_id
+            WHERE r.evidence_id = $2 AND e.offense_id = $1
+            ORDER BY r.revision_number DESC
+            "#,
+        )
+        .bind(offense_id)
+        .bind(evidence_id)
+        .fetch_all(self.db)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)
+    }
+
+    /// Diff two revisions of an offense. `to` defaults to the latest revision
+    /// and `from` to the one before it.
+    pub async fn diff_offense_revisions(
+        &self,
+        offense_id: Uuid,
+        from: Option<i32>,
+        to: Option<i32>,
+    ) -> Result<RevisionDiff> {
+        let revisions = self.get_offense_revisions(offense_id).await?;
+        diff_revisions(&revisions, from, to)
+    }
+
+    /// Diff two revisions of an evidence item. `to` defaults to the latest
+    /// revision and `from` to the one before it.
+    pub async fn diff_evidence_revisions(
+        &self,
+        offense_id: Uuid,
+        evidence_id: Uuid,
+        from: Option<i32>,
+        to: Option<i32>,
+    ) -> Result<RevisionDiff> {
+        let revisions = self.get_evidence_revisions(offense_id, evidence_id).await?;
+        diff_revisions(&revisions, from, to)
+    }
+
+    /// Dispute an offense. The offense keeps its status while the dispute is
+    /// open; it only changes if a moderator upholds the dispute.
+    pub async fn submit_dispute(
+        &self,
+        offense_id: Uuid,
+        request: SubmitDisputeRequest,
+        submitted_by: Uuid,
+    ) -> Result<OffenseDispute> {
+        if request.reason.trim().is_empty() {
+            return Err(AppError::MissingField {
+                field: "reason".to_string(),
+            });
+        }
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+        // Lock the offense so a dispute can't open while another is upheld
+        let current_status: EvidenceStatus =
+            sqlx::query_scalar("SELECT status FROM artist_offenses WHERE id = $1 FOR SHARE")
+                .bind(offense_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(AppError::DatabaseQueryFailed)?
+                .ok_or_else(|| AppError::NotFound {
+                    resource: "Offense".to_string(),
+                })?;
+
+        if current_status == EvidenceStatus::Rejected {
+            return Err(AppError::OperationNotAllowed {
+                reason: "Rejected offenses cannot be disputed".to_string(),
+            });
+        }
+
+        // Lock the submitter so concurrent disputes can't exceed the limit
+        sqlx::query("SELECT 1 FROM users WHERE id = $1 FOR NO KEY UPDATE")
+            .bind(submitted_by)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+        let open_disputes: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM offense_disputes WHERE submitted_by = $1 AND status = 'open'",
+        )
+        .bind(submitted_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        if open_disputes >= MAX_OPEN_DISPUTES_PER_USER {
+            return Err(AppError::OperationNotAllowed {
+                reason: format!(
+                    "A user can have at most {} open disputes",
+                    MAX_OPEN_DISPUTES_PER_USER
+                ),
+            });
+        }
+
+        let dispute = sqlx::query_as::<_, OffenseDispute>(&format!(
+            r#"
+            INSERT INTO offense_disputes (offense_id, evidence_id, reason, previous_status, submitted_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {DISPUTE_COLUMNS}
+            "#
+        ))
+        .bind(offense_id)
+        .bind(request.evidence_id)
+        .bind(request.reason.trim())
+        .bind(current_status)
+        .bind(submitted_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => AppError::AlreadyExists {
+                resource: "Open dispute".to_string(),
+            },
+            _ => AppError::DatabaseQueryFailed(e),
+        })?;
+
+        tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
+
+        Ok(dispute)
+    }
+
+    /// List disputes for an offense, newest first
+    pub async fn get_disputes(&self, offense_id: Uuid) -> Result<Vec<OffenseDispute>> {
+        sqlx::query_as::<_, OffenseDispute>(&format!(
+            r#"
+            SELECT {DISPUTE_COLUMNS}
+            FROM offense_disputes
+            WHERE offense_id = $1
+            ORDER BY created_at DESC
+            "#
+        ))
+        .bind(offense_id)
+        .fetch_all(self.db)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)
+    }
+
+    /// Resolve a dispute (moderator action).
+    ///
+    /// Upholding a dispute rejects the offense and closes any other open
+    /// disputes with it. Dismissing it leaves the offense unchanged.
+    pub async fn resolve_dispute(
+        &self,
+        offense_id: Uuid,
+        dispute_id: Uuid,
+        request: ResolveDisputeRequest,
+        resolved_by: Uuid,
+    ) -> Result<OffenseDispute> {
+        let reviewer_notes = require_notes(&request.reviewer_notes)?;
+        let resolution = if request.uphold {
+            "upheld"
+        } else {
+            "dismissed"
+        };
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+        let dispute = sqlx::query_as::<_, OffenseDispute>(&format!(
+            r#"
+            UPDATE offense_disputes
+            SET status = $3, resolution_notes = $4, resolved_by = $5, resolved_at = NOW()
+            WHERE id = $2 AND offense_id = $1 AND status = 'open'
+            RETURNING {DISPUTE_COLUMNS}
+            "#
+        ))
+        .bind(offense_id)
+        .bind(dispute_id)
+        .bind(resolution)
+        .bind(reviewer_notes)
+        .bind(resolved_by)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?
+        .ok_or_else(|| AppError::NotFound {
+            resource: "Open dispute".to_string(),
+        })?;
+
+        if request.uphold {
+            sqlx::query(
+                r#"
+                UPDATE offense_disputes
+                SET status = 'upheld', resolution_notes = $2, resolved_by = $3, resolved_at = NOW()
+                WHERE offense_id = $1 AND status = 'open'
+                "#,
+            )
+            .bind(offense_id)
+            .bind(reviewer_notes)
+            .bind(resolved_by)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+            sqlx::query(
+                "UPDATE artist_offenses SET status = 'rejected', updated_at = NOW() WHERE id = $1",
+            )
+            .bind(offense_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+            record_offense_revision(
+                &mut tx,
+                offense_id,
+                "dispute_resolved",
+                Some(resolved_by),
+                Some(reviewer_notes),
+            )
+            .await?;
+        }
+
+        tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
+
+        Ok(dispute)
+    }
+
+    /// Get the legal-status timeline for an offense
+    pub async fn get_offense_timeline(&self, offense_id: Uuid) -> Result<OffenseTimeline> {
+        let (reported_severity, severity): (OffenseSeverity, OffenseSeverity) =
+            sqlx::query_as("SELECT reported_severity, severity FROM artist_offenses WHERE id = $1")
+                .bind(offense_id)
+                .fetch_optional(self.db)
+                .await
+                .map_err(AppError::DatabaseQueryFailed)?
+                .ok_or_else(|| AppError::NotFound {
+                    resource: "Offense".to_string(),
+                })?;
+
+        let events = get_offense_events(self.db, offense_id).await?;
+        let derived = DerivedLegalState::from_events(reported_severity, &events);
+
+        Ok(OffenseTimeline {
+            offense_id,
+            legal_status: derived.legal_status,
+            reported_severity,
+            severity,
+            events,
+        })
+    }
+
+    /// Add an event to an offense's timeline.
+    ///
+    /// Events submitted by moderators are verified immediately and applied to
+    /// the offense; everyone else's wait for review.
+    pub async fn add_offense_event(
+        &self,
+        offense_id: Uuid,
+        request: AddOffenseEventRequest,
+        submitted_by: Uuid,
+        verified: bool,
+    ) -> Result<OffenseEvent> {
+        let status = if verified {
+            EvidenceStatus::Verified
+        } else {
+            EvidenceStatus::Pending
+        };
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+        let event = sqlx::query_as::<_, OffenseEvent>(&format!(
+            r#"
+            INSERT INTO offense_events (
+                offense_id, event_type, event_date, event_date_approximate, description,
+                evidence_id, source_url, status, source, submitted_by,
+                reviewed_at, reviewed_by
+            )
+            SELECT id, $2, $3, $4, $5, $6, $7, $8, 'manual', $9,
+                   CASE WHEN $10 THEN NOW() END, CASE WHEN $10 THEN $9 END
+            FROM artist_offenses
+            WHERE id = $1
+            RETURNING {OFFENSE_EVENT_COLUMNS}
+            "#
+        ))
+        .bind(offense_id)
+        .bind(request.event_type)
+        .bind(request.event_date)
+        .bind(request.event_date_approximate.unwrap_or(false))
+        .bind(&request.description)
+        .bind(request.evidence_id)
+        .bind(&request.source_url)
+        .bind(status)
+        .bind(submitted_by)
+        .bind(verified)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?
+        .ok_or_else(|| AppError::NotFound {
+            resource: "Offense".to_string(),
+        })?;
+
+        if verified {
+            sync_legal_state(&mut tx, offense_id).await?;
+            record_offense_revision(&mut tx, offense_id, "timeline", Some(submitted_by), None)
+                .await?;
+        }
+
+        tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
+
+        Ok(event)
+    }
+
+    /// Propose an event detected in the news (e.g. an acquittal) for review.
+    ///
+    /// Returns `None` if the offense already has a non-rejected event of the
+    /// same type on the same date, so repeated coverage doesn't queue
+    /// duplicates while a second charge or a later appeal still can be.
+    pub async fn propose_offense_event(
+        &self,
+        offense_id: Uuid,
+        event_type: OffenseEventType,
+        event_date: Option<chrono::NaiveDate>,
+        source_url: &str,
+        description: Option<&str>,
+        confidence: f64,
+    ) -> Result<Option<OffenseEvent>> {
+        let event = sqlx::query_as::<_, OffenseEvent>(&format!(
+            r#"
+            INSERT INTO offense_events (
+                offense_id, event_type, event_date, description, source_url, source, confidence
+            )
+            SELECT $1, $2, $3, $4, $5, 'news_pipeline', $6
+            WHERE NOT EXISTS (
+                SELECT 1 FROM offense_events
+                WHERE offense_id = $1 AND event_type = $2
+                  AND event_date IS NOT DISTINCT FROM $3
+                  AND status <> 'rejected'
+            )
+            RETURNING {OFFENSE_EVENT_COLUMNS}
+            "#
+        ))
+        .bind(offense_id)
+        .bind(event_type)
+        .bind(event_date)
+        .bind(description)
+        .bind(source_url)
+        .bind(confidence)
+        .fetch_optional(self.db)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        Ok(event)
+    }
+
+    /// Approve or reject a pending timeline event (moderator action).
+    /// Reviewer notes are required. An event that was already reviewed can't
+    /// be reviewed again.
+    pub async fn review_offense_event(
+        &self,
+        offense_id: Uuid,
+        event_id: Uuid,
+        approve: bool,
+        reviewer_notes: &str,
+        reviewed_by: Uuid,
+    ) -> Result<OffenseEvent> {
+        let reviewer_notes = require_notes(reviewer_notes)?;
+        let status = if approve {
+            EvidenceStatus::Verified
+        } else {
+            EvidenceStatus::Rejected
+        };
+
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+        let event = sqlx::query_as::<_, OffenseEvent>(&format!(
+            r#"
+            UPDATE offense_events
+            SET status = $3, reviewed_at = NOW(), reviewed_by = $4
+            WHERE id = $2 AND offense_id = $1 AND status = 'pending'
+            RETURNING {OFFENSE_EVENT_COLUMNS}
+            "#
+        ))
+        .bind(offense_id)
+        .bind(event_id)
+        .bind(status)
+        .bind(reviewed_by)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        let Some(event) = event else {
+            let exists: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM offense_events WHERE id = $2 AND offense_id = $1)",
+            )
+            .bind(offense_id)
+            .bind(event_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+            return Err(if exists {
+                AppError::Conflict {
+                    message: "Offense event has already been reviewed".to_string(),
+                }
+            } else {
+                AppError::NotFound {
+                    resource: "Offense event".to_string(),
+                }
+            });
+        };
+
+        sync_legal_state(&mut tx, offense_id).await?;
+        record_offense_revision(
+            &mut tx,
+            offense_id,
+            "timeline",
+            Some(reviewed_by),
+            Some(reviewer_notes),
+        )
+        .await?;
+
+        tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
+
+        Ok(event)
+    }
+
     /// Get all flagged artists (for browsing the database)
     pub async fn get_flagged_artists(
         &self,
@@ -370,6 +1070,7 @@ impl<'a> OffenseService<'a> {
                 .map_err(AppError::DatabaseQueryFailed)?;
         }
 
+        capture_library_snapshot(&mut tx, user_id, &request.provider).await?;
         tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
 
         Ok(total)
@@ -423,6 +1124,7 @@ impl<'a> OffenseService<'a> {
             .map_err(AppError::DatabaseQueryFailed)?;
 
         if request.tracks.is_empty() {
+            capture_library_snapshot(&mut tx, user_id, &request.provider).await?;
             tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
             return Ok(0);
         }
@@ -464,6 +1166,7 @@ impl<'a> OffenseService<'a> {
                 .map_err(AppError::DatabaseQueryFailed)?;
         }
 
+        capture_library_snapshot(&mut tx, user_id, &request.provider).await?;
         tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
 
         Ok(total)
@@ -716,7 +1419,7 @@ impl<'a> OffenseService<'a> {
                 r#"
                 SELECT id, offense_id, url, source_name, source_type, title, excerpt,
                        published_date, archived_url, is_primary_source, credibility_score,
-                       submitted_by, created_at
+                       submitted_by, created_at, link_status, last_checked_at, needs_rereview
                 FROM offense_evidence
                 WHERE offense_id = $1
                 ORDER BY is_primary_source DESC, credibility_score DESC NULLS LAST
@@ -746,6 +1449,8 @@ impl<'a> OffenseService<'a> {
                         excerpt: e.excerpt,
                         published_date: e.published_date,
                         credibility_score: e.credibility_score,
+                        archived_url: e.archived_url,
+                        link_status: e.link_status,
                     })
                     .collect(),
             });
@@ -764,3 +1469,197 @@ impl<'a> OffenseService<'a> {
         })
     }
 }
+
+/// Reviewer notes are required on moderator actions so the revision history
+/// records why a change was made.
+fn require_notes(notes: &str) -> Result<&str> {
+    let notes = notes.trim();
+    if notes.is_empty() {
+        return Err(AppError::MissingField {
+            field: "reviewer_notes".to_string(),
+        });
+    }
+    Ok(notes)
+}
+
+/// Record the composition of a provider library after a sync, raising
+/// regression alerts for removed tracks that came back. Call inside the
+/// transaction that wrote the library rows.
+pub async fn capture_library_snapshot(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    provider: &str,
+) -> Result<()> {
+    sqlx::query("SELECT capture_library_snapshot($1, $2)")
+        .bind(user_id)
+        .bind(provider)
+        .execute(conn)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+    Ok(())
+}
+
+/// A concurrent change took the same revision number; the caller's
+/// transaction is rolled back and the client can retry.
+fn revision_conflict(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Conflict {
+            message: "The record was changed concurrently, please retry".to_string(),
+        },
+        _ => AppError::DatabaseQueryFailed(e),
+    }
+}
+
+/// Append a snapshot of the offense's current row as its next revision
+async fn record_offense_revision(
+    conn: &mut PgConnection,
+    offense_id: Uuid,
+    change_type: &str,
+    changed_by: Option<Uuid>,
+    reviewer_notes: Option<&str>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO offense_revisions (
+            offense_id, revision_number, change_type, snapshot, changed_by, reviewer_notes
+        )
+        SELECT o.id,
+               COALESCE((SELECT MAX(revision_number) FROM offense_revisions WHERE offense_id = o.id), 0) + 1,
+               $2, to_jsonb(o), $3, $4
+        FROM artist_offenses o
+        WHERE o.id = $1
+        "#,
+    )
+    .bind(offense_id)
+    .bind(change_type)
+    .bind(changed_by)
+    .bind(reviewer_notes)
+    .execute(conn)
+    .await
+    .map_err(revision_conflict)?;
+
+    Ok(())
+}
+
+/// Append a snapshot of the evidence's current row as its next revision
+async fn record_evidence_revision(
+    conn: &mut PgConnection,
+    evidence_id: Uuid,
+    change_type: &str,
+    changed_by: Option<Uuid>,
+    reviewer_notes: Option<&str>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO evidence_revisions (
+            evidence_id, revision_number, change_type, snapshot, changed_by, reviewer_notes
+        )
+        SELECT e.id,
+               COALESCE((SELECT MAX(revision_number) FROM evidence_revisions WHERE evidence_id = e.id), 0) + 1,
+               $2, to_jsonb(e), $3, $4
+        FROM offense_evidence e
+        WHERE e.id = $1
+        "#,
+    )
+    .bind(evidence_id)
+    .bind(change_type)
+    .bind(changed_by)
+    .bind(reviewer_notes)
+    .execute(conn)
+    .await
+    .map_err(revision_conflict)?;
+
+    Ok(())
+}
+
+/// Diff two revisions from a newest-first list. `to` defaults to the latest
+/// revision and `from` to the one before `to`.
+fn diff_revisions(
+    revisions: &[Revision],
+    from: Option<i32>,
+    to: Option<i32>,
+) -> Result<RevisionDiff> {
+    let latest = revisions.first().ok_or_else(|| AppError::NotFound {
+        resource: "Revision".to_string(),
+    })?;
+    let to = to.unwrap_or(latest.revision_number);
+    let from = from.unwrap_or(to - 1);
+
+    let find = |number: i32| {
+        revisions
+            .iter()
+            .find(|r| r.revision_number == number)
+            .ok_or_else(|| AppError::NotFound {
+                resource: format!("Revision {number}"),
+            })
+    };
+    let after = find(to)?;
+
+    // The first revision is diffed against an empty snapshot
+    let before_snapshot = if from < 1 {
+        serde_json::Value::Null
+    } else {
+        find(from)?.snapshot.clone()
+    };
+
+    Ok(RevisionDiff {
+        from_revision: from.max(0),
+        to_revision: to,
+        changed_by: after.changed_by,
+        reviewer_notes: after.reviewer_notes.clone(),
+        changes: diff_snapshots(&before_snapshot, &after.snapshot),
+    })
+}
+
+async fn get_offense_events<'e, E>(executor: E, offense_id: Uuid) -> Result<Vec<OffenseEvent>>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, OffenseEvent>(&format!(
+        r#"
+        SELECT {OFFENSE_EVENT_COLUMNS}
+        FROM offense_events
+        WHERE offense_id = $1
+        ORDER BY event_date ASC NULLS FIRST, created_at ASC
+        "#
+    ))
+    .bind(offense_id)
+    .fetch_all(executor)
+    .await
+    .map_err(AppError::DatabaseQueryFailed)
+}
+
+/// Re-derive severity and the legacy legal-outcome flags from the offense's
+/// verified events.
+async fn sync_legal_state(conn: &mut PgConnection, offense_id: Uuid) -> Result<()> {
+    let reported_severity: OffenseSeverity =
+        sqlx::query_scalar("SELECT reported_severity FROM artist_offenses WHERE id = $1")
+            .bind(offense_id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(AppError::DatabaseQueryFailed)?;
+
+    let events = get_offense_events(&mut *conn, offense_id).await?;
+    let derived = DerivedLegalState::from_events(reported_severity, &events);
+
+    sqlx::query(
+        r#"
+        UPDATE artist_offenses
+        SET severity = $2, arrested = $3, charged = $4, convicted = $5, settled = $6,
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(offense_id)
+    .bind(derived.severity)
+    .bind(derived.arrested)
+    .bind(derived.charged)
+    .bind(derived.convicted)
+    .bind(derived.settled)
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::DatabaseQueryFailed)?;
+
+    Ok(())
+}
@@ -0,0 +1,1267 @@
+//! OpenID Connect provider
+//!
+//! Authorization code flow with mandatory PKCE (S256) for partner apps
+//! registered as clients. A signed-in user approves the requested scopes,
+//! the client redeems the code at the token endpoint, and gets an opaque
+//! access token, a refresh token when `offline_access` was granted, and an
+//! RS256 ID token for `openid`, signed with the same keyring as our own
+//! access tokens.
+//!
+//! Access and refresh tokens are opaque so they can be revoked immediately
+//! and can never be mistaken for a first-party API token. Refresh tokens
+//! rotate on every use; presenting a rotated refresh token or a redeemed
+//! code again revokes everything issued under that grant.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{encode, Algorithm, Header};
+use ndith_core::models::oidc::{
+    oidc_scope_description, CreateOidcClientRequest, OidcAuthorizationDecision,
+    OidcAuthorizationRequest, OidcIdTokenClaims, OidcTokenRequest, OidcTokenResponse,
+    OIDC_SCOPE_EMAIL, OIDC_SCOPE_OFFLINE_ACCESS, OIDC_SCOPE_OPENID, OIDC_SUPPORTED_SCOPES,
+};
+use ndith_core::{AppError, Result};
+use rand::RngCore;
+use reqwest::Url;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::fmt;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::jwt_keyring::JwtKeyring;
+
+pub const AUTHORIZATION_CODE_TTL_SECS: i64 = 60;
+pub const ACCESS_TOKEN_TTL_SECS: i64 = 60 * 60;
+pub const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+pub const ID_TOKEN_TTL_SECS: i64 = 60 * 60;
+
+const ACCESS_TOKEN_PREFIX: &str = "ndith_at_";
+const REFRESH_TOKEN_PREFIX: &str = "ndith_rt_";
+
+/// OAuth 2.0 error response (RFC 6749 §4.1.2.1, §5.2; RFC 6750 §3.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcError {
+    pub error: &'static str,
+    pub description: String,
+    /// Set when the error is reported to the client's redirect URI rather
+    /// than shown to the user
+    pub redirect_to: Option<String>,
+}
+
+impl OidcError {
+    fn new(error: &'static str, description: impl Into<String>) -> Self {
+        Self {
+            error,
+            description: description.into(),
+            redirect_to: None,
+        }
+    }
+
+    pub fn invalid_request(description: impl Into<String>) -> Self {
+        Self::new("invalid_request", description)
+    }
+
+    pub fn invalid_client(description: impl Into<String>) -> Self {
+        Self::new("invalid_client", description)
+    }
+
+    pub fn invalid_grant(description: impl Into<String>) -> Self {
+        Self::new("invalid_grant", description)
+    }
+
+    pub fn invalid_scope(description: impl Into<String>) -> Self {
+        Self::new("invalid_scope", description)
+    }
+
+    pub fn unsupported_grant_type(description: impl Into<String>) -> Self {
+        Self::new("unsupported_grant_type", description)
+    }
+
+    pub fn invalid_token(description: impl Into<String>) -> Self {
+        Self::new("invalid_token", description)
+    }
+
+    pub fn insufficient_scope(description: impl Into<String>) -> Self {
+        Self::new("insufficient_scope", description)
+    }
+
+    pub fn server_error(description: impl Into<String>) -> Self {
+        Self::new("server_error", description)
+    }
+
+    /// HTTP status for the token, revocation and resource endpoints
+    pub fn status_code(&self) -> u16 {
+        match self.error {
+            "invalid_client" | "invalid_token" => 401,
+            "insufficient_scope" => 403,
+            "server_error" => 500,
+            _ => 400,
+        }
+    }
+
+    fn redirect(mut self, redirect_uri: &str, state: Option<&str>, issuer: &str) -> Self {
+        let mut params = vec![
+            ("error", self.error.to_string()),
+            ("error_description", self.description.clone()),
+        ];
+        if let Some(state) = state {
+            params.push(("state", state.to_string()));
+        }
+        params.push(("iss", issuer.to_string()));
+        self.redirect_to = Some(redirect_with(redirect_uri, &params));
+        self
+    }
+}
+
+impl fmt::Display for OidcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.error, self.description)
+    }
+}
+
+impl From<AppError> for OidcError {
+    fn from(err: AppError) -> Self {
+        tracing::error!(error = %err, "OIDC provider error");
+        Self::server_error("The server could not complete the request")
+    }
+}
+
+impl From<sqlx::Error> for OidcError {
+    fn from(err: sqlx::Error) -> Self {
+        AppError::from(err).into()
+    }
+}
+
+/// A partner app allowed to sign users in
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct OidcClient {
+    pub client_id: String,
+    #[serde(skip_serializing)]
+    pub client_secret_hash: Option<String>,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub allowed_scopes: Vec<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl OidcClient {
+    pub fn is_confidential(&self) -> bool {
+        self.client_secret_hash.is_some()
+    }
+}
+
+/// A newly registered client. The secret is only ever returned here.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedOidcClient {
+    #[serde(flatten)]
+    pub client: OidcClient,
+    pub confidential: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+}
+
+/// Scopes a user has given a client
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct OidcConsent {
+    pub client_id: String,
+    pub client_name: String,
+    pub scopes: Vec<String>,
+    pub granted_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScopePrompt {
+    pub scope: String,
+    pub description: &'static str,
+}
+
+/// What the consent screen shows for a checked authorization request
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorizationPrompt {
+    pub client_id: String,
+    pub client_name: String,
+    pub scopes: Vec<ScopePrompt>,
+    /// The user has already granted every requested scope
+    pub consented: bool,
+}
+
+/// Who an access token acts for and what it may do
+#[derive(Debug, Clone)]
+pub struct OidcAccessGrant {
+    pub user_id: Uuid,
+    pub client_id: String,
+    pub scopes: Vec<String>,
+}
+
+impl OidcAccessGrant {
+    pub fn require_scope(&self, scope: &str) -> std::result::Result<(), OidcError> {
+        if self.scopes.iter().any(|s| s == scope) {
+            Ok(())
+        } else {
+            Err(OidcError::insufficient_scope(format!(
+                "The access token does not grant {}",
+                scope
+            )))
+        }
+    }
+}
+
+/// Client credentials from Basic auth or the request body
+#[derive(Debug, Clone, Default)]
+pub struct ClientCredentials {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+}
+
+struct ValidatedAuthorization {
+    client: OidcClient,
+    redirect_uri: String,
+    scopes: Vec<String>,
+    state: Option<String>,
+    nonce: Option<String>,
+    code_challenge: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct AuthorizationCodeRow {
+    grant_id: Uuid,
+    client_id: String,
+    user_id: Uuid,
+    redirect_uri: String,
+    scopes: Vec<String>,
+    nonce: Option<String>,
+    code_challenge: String,
+    auth_time: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    consumed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct TokenRow {
+    grant_id: Uuid,
+    client_id: String,
+    user_id: Uuid,
+    scopes: Vec<String>,
+    expires_at: DateTime<Utc>,
+    rotated_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+fn random_token(prefix: &str) -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("{}{}", prefix, URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Tokens carry 256 bits of randomness, so an unsalted hash is enough to
+/// store them and look them up directly
+fn hash_token(token: &str) -> String {
+    let digest = Sha256::digest(token.as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// PKCE S256 code challenge for a verifier (RFC 7636 §4.2)
+pub fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// RFC 7636 §4.1: 43-128 characters from the unreserved set
+fn is_valid_pkce_value(value: &str) -> bool {
+    (43..=128).contains(&value.len())
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~'))
+}
+
+/// Space-separated scope string to a list without duplicates
+fn parse_scopes(scope: &str) -> Vec<String> {
+    let mut scopes: Vec<String> = Vec::new();
+    for s in scope.split_whitespace() {
+        if !scopes.iter().any(|existing| existing == s) {
+            scopes.push(s.to_string());
+        }
+    }
+    scopes
+}
+
+/// Redirect URIs must be absolute without a fragment. Confidential clients
+/// need https (http only on loopback); public native apps may also use a
+/// private-use scheme such as `org.example.radio:/callback`.
+fn is_valid_redirect_uri(uri: &str, confidential: bool) -> bool {
+    let Ok(url) = Url::parse(uri) else {
+        return false;
+    };
+    if url.fragment().is_some() {
+        return false;
+    }
+
+    match url.scheme() {
+        "https" => true,
+        "http" => matches!(
+            url.host_str(),
+            Some("localhost") | Some("127.0.0.1") | Some("[::1]")
+        ),
+        scheme => !confidential && scheme.contains('.'),
+    }
+}
+
+fn redirect_with(redirect_uri: &str, params: &[(&str, String)]) -> String {
+    match Url::parse(redirect_uri) {
+        Ok(mut url) => {
+            {
+                let mut query = url.query_pairs_mut();
+                for (key, value) in params {
+                    query.append_pair(key, value);
+                }
+            }
+            url.to_string()
+        }
+        // Registered URIs are validated, so this is only reachable for
+        // rows edited by hand
+        Err(_) => redirect_uri.to_string(),
+    }
+}
+
+pub struct OidcProviderService {
+    db_pool: PgPool,
+    jwt_keyring: Arc<JwtKeyring>,
+    issuer: String,
+}
+
+impl OidcProviderService {
+    pub fn new(db_pool: PgPool, jwt_keyring: Arc<JwtKeyring>, issuer: String) -> Self {
+        Self {
+            db_pool,
+            jwt_keyring,
+            issuer,
+        }
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    // ===== Client registration =====
+
+    /// Register a partner app
+    pub async fn create_client(
+        &self,
+        created_by: Uuid,
+        request: CreateOidcClientRequest,
+    ) -> Result<CreatedOidcClient> {
+        let name = request.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(AppError::InvalidFieldValue {
+                field: "name".to_string(),
+                message: "Name must be between 1 and 100 characters".to_string(),
+            });
+        }
+        if request.redirect_uris.is_empty() {
+            return Err(AppError::InvalidFieldValue {
+                field: "redirect_uris".to_string(),
+                message: "At least one redirect URI is required".to_string(),
+            });
+        }
+        if let Some(uri) = request
+            .redirect_uris
+            .iter()
+            .find(|uri| !is_valid_redirect_uri(uri, request.confidential))
+        {
+            return Err(AppError::InvalidFieldValue {
+                field: "redirect_uris".to_string(),
+                message: format!("{} is not an allowed redirect URI", uri),
+            });
+        }
+
+        let allowed_scopes = match request.allowed_scopes {
+            Some(scopes) => {
+                if let Some(unknown) = scopes
+                    .iter()
+                    .find(|s| !OIDC_SUPPORTED_SCOPES.contains(&s.as_str()))
+                {
+                    return Err(AppError::InvalidFieldValue {
+                        field: "allowed_scopes".to_string(),
+                        message: format!("Unsupported scope {}", unknown),
+                    });
+                }
+                parse_scopes(&scopes.join(" "))
+            }
+            None => OIDC_SUPPORTED_SCOPES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        };
+
+        let client_id = Uuid::new_v4().simple().to_string();
+        let client_secret = request.confidential.then(|| random_token(""));
+
+        let client = sqlx::query_as::<_, OidcClient>(
+            r#"
+            INSERT INTO oidc_clients (
+                client_id, client_secret_hash, name, redirect_uris, allowed_scopes, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING client_id, client_secret_hash, name, redirect_uris, allowed_scopes,
+                      created_by, created_at, revoked_at
+            "#,
+        )
+        .bind(&client_id)
+        .bind(client_secret.as_deref().map(hash_token))
+        .bind(&name)
+        .bind(&request.redirect_uris)
+        .bind(&allowed_scopes)
+        .bind(created_by)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        tracing::info!(client_id = %client.client_id, name = %client.name, "Registered OIDC client");
+
+        Ok(CreatedOidcClient {
+            confidential: client.is_confidential(),
+            client,
+            client_secret,
+        })
+    }
+
+    pub async fn list_clients(&self) -> Result<Vec<OidcClient>> {
+        let clients = sqlx::query_as::<_, OidcClient>(
+            r#"
+            SELECT client_id, client_secret_hash, name, redirect_uris, allowed_scopes,
+                   created_by, created_at, revoked_at
+            FROM oidc_clients
+            ORDER BY created_at DESC
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(clients)
+    }
+
+    /// Disable a client and revoke every token issued to it
+    pub async fn revoke_client(&self, client_id: &str) -> Result<()> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let updated = sqlx::query(
+            "UPDATE oidc_clients SET revoked_at = NOW() WHERE client_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(client_id)
+        .execute(&mut *tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            return Err(AppError::NotFound {
+                resource: "OIDC client".to_string(),
+            });
+        }
+
+        sqlx::query(
+            "UPDATE oidc_tokens SET revoked_at = NOW() WHERE client_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(client_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        tracing::info!(client_id = %client_id, "Revoked OIDC client");
+        Ok(())
+    }
+
+    async fn find_client(&self, client_id: &str) -> Result<Option<OidcClient>> {
+        let client = sqlx::query_as::<_, OidcClient>(
+            r#"
+            SELECT client_id, client_secret_hash, name, redirect_uris, allowed_scopes,
+                   created_by, created_at, revoked_at
+            FROM oidc_clients
+            WHERE client_id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(client_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(client)
+    }
+
+    /// Check client credentials at the token and revocation endpoints.
+    /// Public clients only identify themselves.
+    pub async fn authenticate_client(
+        &self,
+        credentials: &ClientCredentials,
+    ) -> std::result::Result<OidcClient, OidcError> {
+        let client = self
+            .find_client(&credentials.client_id)
+            .await?
+            .ok_or_else(|| OidcError::invalid_client("Unknown client"))?;
+
+        if let Some(expected) = &client.client_secret_hash {
+            // Comparing digests, so timing says nothing about the secret
+            let presented = credentials.client_secret.as_deref().map(hash_token);
+            if presented.as_ref() != Some(expected) {
+                return Err(OidcError::invalid_client("Client authentication failed"));
+            }
+        }
+
+        Ok(client)
+    }
+
+    // ===== Authorization endpoint =====
+
+    async fn validate_authorization(
+        &self,
+        request: &OidcAuthorizationRequest,
+    ) -> std::result::Result<ValidatedAuthorization, OidcError> {
+        // Until the client and redirect URI check out, errors go to the user
+        // and never to the redirect URI (RFC 6749 §4.1.2.1)
+        let client = self
+            .find_client(&request.client_id)
+            .await?
+            .ok_or_else(|| OidcError::invalid_request("Unknown or disabled client_id"))?;
+        if !client.redirect_uris.contains(&request.redirect_uri) {
+            return Err(OidcError::invalid_request(
+                "redirect_uri is not registered for this client",
+            ));
+        }
+
+        let fail = |error: OidcError| {
+            Err(error.redirect(
+                &request.redirect_uri,
+                request.state.as_deref(),
+                &self.issuer,
+            ))
+        };
+
+        if request.response_type != "code" {
+            return fail(OidcError::new(
+                "unsupported_response_type",
+                "Only the authorization code flow is supported",
+            ));
+        }
+
+        let scopes = parse_scopes(&request.scope);
+        if scopes.is_empty() {
+            return fail(OidcError::invalid_scope("At least one scope is required"));
+        }
+        if let Some(scope) = scopes.iter().find(|s| !client.allowed_scopes.contains(s)) {
+            return fail(OidcError::invalid_scope(format!(
+                "Scope {} is not available to this client",
+                scope
+            )));
+        }
+
+        let code_challenge = match (
+            &request.code_challenge,
+            request.code_challenge_method.as_deref(),
+        ) {
+            (Some(challenge), Some("S256")) if is_valid_pkce_value(challenge) => challenge.clone(),
+            (Some(_), Some("S256")) => {
+                return fail(OidcError::invalid_request("Malformed code_challenge"))
+            }
+            (Some(_), _) => {
+                return fail(OidcError::invalid_request(
+                    "code_challenge_method must be S256",
+                ))
+            }
+            (None, _) => {
+                return fail(OidcError::invalid_request(
+                    "PKCE code_challenge is required",
+                ))
+            }
+        };
+
+        Ok(ValidatedAuthorization {
+            client,
+            redirect_uri: request.redirect_uri.clone(),
+            scopes,
+            state: request.state.clone(),
+            nonce: request.nonce.clone(),
+            code_challenge,
+        })
+    }
+
+    /// Check an authorization request before anyone has signed in, so the
+    /// browser can be sent to the consent screen or back to the client
+    pub async fn precheck_authorization(
+        &self,
+        request: &OidcAuthorizationRequest,
+    ) -> std::result::Result<(), OidcError> {
+        self.validate_authorization(request).await.map(|_| ())
+    }
+
+    /// Consent screen details for a signed-in user
+    pub async fn prepare_authorization(
+        &self,
+        user_id: Uuid,
+        request: &OidcAuthorizationRequest,
+    ) -> std::result::Result<AuthorizationPrompt, OidcError> {
+        let validated = self.validate_authorization(request).await?;
+        let consented = self
+            .has_consent(user_id, &validated.client.client_id, &validated.scopes)
+            .await?;
+
+        if !consented && request.prompt.as_deref() == Some("none") {
+            return Err(OidcError::new(
+                "consent_required",
+                "The user has not granted these scopes",
+            )
+            .redirect(
+                &validated.redirect_uri,
+                validated.state.as_deref(),
+                &self.issuer,
+            ));
+        }
+
+        Ok(AuthorizationPrompt {
+            client_id: validated.client.client_id,
+            client_name: validated.client.name,
+            scopes: validated
+                .scopes
+                .iter()
+                .map(|scope| ScopePrompt {
+                    scope: scope.clone(),
+                    description: oidc_scope_description(scope),
+                })
+                .collect(),
+            consented,
+        })
+    }
+
+    /// Record the user's decision and return where to send the browser: the
+    /// client's redirect URI with a code, or with `access_denied`
+    pub async fn authorize(
+        &self,
+        user_id: Uuid,
+        auth_time: DateTime<Utc>,
+        decision: &OidcAuthorizationDecision,
+    ) -> std::result::Result<String, OidcError> {
+        let validated = self.validate_authorization(&decision.request).await?;
+
+        if !decision.approve {
+            return Err(
+                OidcError::new("access_denied", "The user declined the request").redirect(
+                    &validated.redirect_uri,
+                    validated.state.as_deref(),
+                    &self.issuer,
+                ),
+            );
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO oidc_consents (user_id, client_id, scopes)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, client_id) DO UPDATE
+            SET scopes = ARRAY(
+                    SELECT DISTINCT unnest(oidc_consents.scopes || EXCLUDED.scopes)
+                ),
+                updated_at = NOW()
+            "#,
+        )
+        .bind(user_id)
+        .bind(&validated.client.client_id)
+        .bind(&validated.scopes)
+        .execute(&mut *tx)
+        .await?;
+
+        let code = random_token("");
+        sqlx::query(
+            r#"
+            INSERT INTO oidc_authorization_codes (
+                code_hash, client_id, user_id, redirect_uri, scopes, nonce,
+                code_challenge, auth_time, expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(hash_token(&code))
+        .bind(&validated.client.client_id)
+        .bind(user_id)
+        .bind(&validated.redirect_uri)
+        .bind(&validated.scopes)
+        .bind(&validated.nonce)
+        .bind(&validated.code_challenge)
+        .bind(auth_time)
+        .bind(Utc::now() + Duration::seconds(AUTHORIZATION_CODE_TTL_SECS))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        tracing::info!(
+            user_id = %user_id,
+            client_id = %validated.client.client_id,
+            scopes = %validated.scopes.join(" "),
+            "OIDC authorization granted"
+        );
+
+        let mut params = vec![("code", code)];
+        if let Some(state) = validated.state {
+            params.push(("state", state));
+        }
+        params.push(("iss", self.issuer.clone()));
+        Ok(redirect_with(&validated.redirect_uri, &params))
+    }
+
+    async fn has_consent(&self, user_id: Uuid, client_id: &str, scopes: &[String]) -> Result<bool> {
+        let granted: Option<Vec<String>> = sqlx::query_scalar(
+            "SELECT scopes FROM oidc_consents WHERE user_id = $1 AND client_id = $2",
+        )
+        .bind(user_id)
+        .bind(client_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        Ok(granted.is_some_and(|granted| scopes.iter().all(|s| granted.contains(s))))
+    }
+
+    // ===== Token endpoint =====
+
+    /// Redeem an authorization code or refresh token
+    pub async fn exchange(
+        &self,
+        credentials: &ClientCredentials,
+        request: &OidcTokenRequest,
+    ) -> std::result::Result<OidcTokenResponse, OidcError> {
+        let client = self.authenticate_client(credentials).await?;
+
+        match request.grant_type.as_str() {
+            "authorization_code" => self.exchange_code(&client, request).await,
+            "refresh_token" => self.exchange_refresh_token(&client, request).await,
+            "" => Err(OidcError::invalid_request("grant_type is required")),
+            other => Err(OidcError::unsupported_grant_type(format!(
+                "Unsupported grant_type {}",
+                other
+            ))),
+        }
+    }
+
+    async fn exchange_code(
+        &self,
+        client: &OidcClient,
+        request: &OidcTokenRequest,
+    ) -> std::result::Result<OidcTokenResponse, OidcError> {
+        let (Some(code), Some(redirect_uri), Some(code_verifier)) = (
+            request.code.as_deref(),
+            request.redirect_uri.as_deref(),
+            request.code_verifier.as_deref(),
+        ) else {
+            return Err(OidcError::invalid_request(
+                "code, redirect_uri and code_verifier are required",
+            ));
+        };
+        let code_hash = hash_token(code);
+
+        let row = sqlx::query_as::<_, AuthorizationCodeRow>(
+            r#"
+            SELECT grant_id, client_id, user_id, redirect_uri, scopes, nonce,
+                   code_challenge, auth_time, expires_at, consumed_at
+            FROM oidc_authorization_codes
+            WHERE code_hash = $1
+            "#,
+        )
+        .bind(&code_hash)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| OidcError::invalid_grant("Invalid authorization code"))?;
+
+        if row.consumed_at.is_some() {
+            return Err(self.code_replayed(row.grant_id).await);
+        }
+
+        // A request that fails these checks doesn't spend the code, so a
+        // stolen code can't be burned by guessing at the verifier
+        if row.expires_at <= Utc::now() {
+            return Err(OidcError::invalid_grant("Authorization code has expired"));
+        }
+        if row.client_id != client.client_id || row.redirect_uri != redirect_uri {
+            return Err(OidcError::invalid_grant("Invalid authorization code"));
+        }
+        if !is_valid_pkce_value(code_verifier)
+            || pkce_challenge(code_verifier) != row.code_challenge
+        {
+            return Err(OidcError::invalid_grant("PKCE verification failed"));
+        }
+
+        let consumed = sqlx::query(
+            r#"
+            UPDATE oidc_authorization_codes
+            SET consumed_at = NOW()
+            WHERE code_hash = $1 AND consumed_at IS NULL
+              AND client_id = $2 AND redirect_uri = $3 AND code_challenge = $4
+              AND expires_at > NOW()
+            "#,
+        )
+        .bind(&code_hash)
+        .bind(&client.client_id)
+        .bind(redirect_uri)
+        .bind(&row.code_challenge)
+        .execute(&self.db_pool)
+        .await?;
+        if consumed.rows_affected() == 0 {
+            // Redeemed by a concurrent request, or withdrawn in the meantime
+            return Err(self.code_replayed(row.grant_id).await);
+        }
+
+        let mut response = self
+            .issue_tokens(row.grant_id, client, row.user_id, &row.scopes)
+            .await?;
+
+        if row.scopes.iter().any(|s| s == OIDC_SCOPE_OPENID) {
+            response.id_token = Some(
+                self.issue_id_token(client, row.user_id, &row.scopes, row.auth_time, row.nonce)
+                    .await?,
+            );
+        }
+
+        Ok(response)
+    }
+
+    async fn exchange_refresh_token(
+        &self,
+        client: &OidcClient,
+        request: &OidcTokenRequest,
+    ) -> std::result::Result<OidcTokenResponse, OidcError> {
+        let refresh_token = request
+            .refresh_token
+            .as_deref()
+            .ok_or_else(|| OidcError::invalid_request("refresh_token is required"))?;
+        let token_hash = hash_token(refresh_token);
+
+        let row = sqlx::query_as::<_, TokenRow>(
+            r#"
+            SELECT grant_id, client_id, user_id, scopes, expires_at, rotated_at, revoked_at
+            FROM oidc_tokens
+            WHERE token_hash = $1 AND token_type = 'refresh'
+            "#,
+        )
+        .bind(&token_hash)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| OidcError::invalid_grant("Invalid refresh token"))?;
+
+        if row.revoked_at.is_some() {
+            return Err(OidcError::invalid_grant("Invalid refresh token"));
+        }
+        if row.rotated_at.is_some() {
+            return Err(self.refresh_token_replayed(row.grant_id).await);
+        }
+
+        // As with codes, a rejected request leaves the token usable
+        if row.client_id != client.client_id {
+            return Err(OidcError::invalid_grant("Invalid refresh token"));
+        }
+        if row.expires_at <= Utc::now() {
+            return Err(OidcError::invalid_grant("Refresh token has expired"));
+        }
+
+        let scopes = match request.scope.as_deref() {
+            Some(scope) => {
+                let requested = parse_scopes(scope);
+                if let Some(extra) = requested.iter().find(|s| !row.scopes.contains(s)) {
+                    return Err(OidcError::invalid_scope(format!(
+                        "Scope {} was not granted",
+                        extra
+                    )));
+                }
+                requested
+            }
+            None => row.scopes,
+        };
+
+        let rotated = sqlx::query(
+            r#"
+            UPDATE oidc_tokens
+            SET rotated_at = NOW()
+            WHERE token_hash = $1 AND token_type = 'refresh'
+              AND rotated_at IS NULL AND revoked_at IS NULL
+              AND client_id = $2 AND expires_at > NOW()
+            "#,
+        )
+        .bind(&token_hash)
+        .bind(&client.client_id)
+        .execute(&self.db_pool)
+        .await?;
+        if rotated.rows_affected() == 0 {
+            return Err(self.refresh_token_replayed(row.grant_id).await);
+        }
+
+        self.issue_tokens(row.grant_id, client, row.user_id, &scopes)
+            .await
+            .map_err(OidcError::from)
+    }
+
+    async fn issue_tokens(
+        &self,
+        grant_id: Uuid,
+        client: &OidcClient,
+        user_id: Uuid,
+        scopes: &[String],
+    ) -> Result<OidcTokenResponse> {
+        let access_token = random_token(ACCESS_TOKEN_PREFIX);
+        self.store_token(
+            &access_token,
+            "access",
+            grant_id,
+            client,
+            user_id,
+            scopes,
+            Utc::now() + Duration::seconds(ACCESS_TOKEN_TTL_SECS),
+        )
+        .await?;
+
+        let refresh_token = if scopes.iter().any(|s| s == OIDC_SCOPE_OFFLINE_ACCESS) {
+            let token = random_token(REFRESH_TOKEN_PREFIX);
+            self.store_token(
+                &token,
+                "refresh",
+                grant_id,
+                client,
+                user_id,
+                scopes,
+                Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS),
+            )
+            .await?;
+            Some(token)
+        } else {
+            None
+        };
+
+        Ok(OidcTokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: ACCESS_TOKEN_TTL_SECS,
+            scope: scopes.join(" "),
+            refresh_token,
+            id_token: None,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn store_token(
+        &self,
+        token: &str,
+        token_type: &str,
+        grant_id: Uuid,
+        client: &OidcClient,
+        user_id: Uuid,
+        scopes: &[String],
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO oidc_tokens (
+                token_hash, token_type, grant_id, client_id, user_id, scopes, expires_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(hash_token(token))
+        .bind(token_type)
+        .bind(grant_id)
+        .bind(&client.client_id)
+        .bind(user_id)
+        .bind(scopes)
+        .bind(expires_at)
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn issue_id_token(
+        &self,
+        client: &OidcClient,
+        user_id: Uuid,
+        scopes: &[String],
+        auth_time: DateTime<Utc>,
+        nonce: Option<String>,
+    ) -> Result<String> {
+        let (kid, key) = self
+            .jwt_keyring
+            .signing_key()
+            .ok_or_else(|| AppError::Internal {
+                message: Some("No RS256 signing key available for ID tokens".to_string()),
+            })?;
+
+        let (email, email_verified) = if scopes.iter().any(|s| s == OIDC_SCOPE_EMAIL) {
+            let (email, verified) = self.user_email(user_id).await?;
+            (Some(email), Some(verified))
+        } else {
+            (None, None)
+        };
+
+        let now = Utc::now();
+        let claims = OidcIdTokenClaims {
+            iss: self.issuer.clone(),
+            sub: user_id.to_string(),
+            aud: client.client_id.clone(),
+            exp: (now + Duration::seconds(ID_TOKEN_TTL_SECS)).timestamp(),
+            iat: now.timestamp(),
+            auth_time: auth_time.timestamp(),
+            nonce,
+            email,
+            email_verified,
+        };
+
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(kid);
+        Ok(encode(&header, &claims, &key)?)
+    }
+
+    async fn user_email(&self, user_id: Uuid) -> Result<(String, bool)> {
+        let row: (String, Option<bool>) =
+            sqlx::query_as("SELECT email, email_verified FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await?
+                .ok_or_else(|| AppError::NotFound {
+                    resource: "User".to_string(),
+                })?;
+
+        Ok((row.0, row.1.unwrap_or(false)))
+    }
+
+    // ===== Revocation, resource access and consent =====
+
+    /// Revoke an access or refresh token (RFC 7009). Unknown tokens and
+    /// tokens of other clients are ignored, as the RFC requires.
+    pub async fn revoke_token(
+        &self,
+        credentials: &ClientCredentials,
+        token: &str,
+    ) -> std::result::Result<(), OidcError> {
+        let client = self.authenticate_client(credentials).await?;
+
+        let revoked: Option<(String, Uuid)> = sqlx::query_as(
+            r#"
+            UPDATE oidc_tokens
+            SET revoked_at = NOW()
+            WHERE token_hash = $1 AND client_id = $2 AND revoked_at IS NULL
+            RETURNING token_type, grant_id
+            "#,
+        )
+        .bind(hash_token(token))
+        .bind(&client.client_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        // Access tokens minted from a revoked refresh token go with it
+        if let Some((token_type, grant_id)) = revoked {
+            if token_type == "refresh" {
+                self.revoke_grant(grant_id).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A replayed code means it leaked; nothing issued from it can be
+    /// trusted any more (RFC 6749 §4.1.2)
+    async fn code_replayed(&self, grant_id: Uuid) -> OidcError {
+        tracing::warn!(grant_id = %grant_id, "OIDC authorization code replayed; revoking grant");
+        if let Err(e) = self.revoke_grant(grant_id).await {
+            return e.into();
+        }
+        OidcError::invalid_grant("Invalid authorization code")
+    }
+
+    async fn refresh_token_replayed(&self, grant_id: Uuid) -> OidcError {
+        tracing::warn!(
+            grant_id = %grant_id,
+            "Rotated OIDC refresh token replayed; revoking grant"
+        );
+        if let Err(e) = self.revoke_grant(grant_id).await {
+            return e.into();
+        }
+        OidcError::invalid_grant("Invalid refresh token")
+    }
+
+    async fn revoke_grant(&self, grant_id: Uuid) -> Result<()> {
+        sqlx::query(
+            "UPDATE oidc_tokens SET revoked_at = NOW() WHERE grant_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(grant_id)
+        .execute(&self.db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Resolve a bearer access token presented to a resource endpoint
+    pub async fn authenticate_access_token(
+        &self,
+        token: &str,
+    ) -> std::result::Result<OidcAccessGrant, OidcError> {
+        let invalid = || OidcError::invalid_token("The access token is invalid or has expired");
+        if !token.starts_with(ACCESS_TOKEN_PREFIX) {
+            return Err(invalid());
+        }
+
+        let grant: Option<(Uuid, String, Vec<String>)> = sqlx::query_as(
+            r#"
+            SELECT t.user_id, t.client_id, t.scopes
+            FROM oidc_tokens t
+            JOIN oidc_clients c ON c.client_id = t.client_id
+            WHERE t.token_hash = $1 AND t.token_type = 'access'
+              AND t.revoked_at IS NULL AND t.expires_at > NOW()
+              AND c.revoked_at IS NULL
+            "#,
+        )
+        .bind(hash_token(token))
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        let (user_id, client_id, scopes) = grant.ok_or_else(invalid)?;
+        Ok(OidcAccessGrant {
+            user_id,
+            client_id,
+            scopes,
+        })
+    }
+
+    /// UserInfo claims for an access token (OpenID Connect Core §5.3)
+    pub async fn userinfo(
+        &self,
+        grant: &OidcAccessGrant,
+    ) -> std::result::Result<serde_json::Value, OidcError> {
+        grant.require_scope(OIDC_SCOPE_OPENID)?;
+
+        let mut claims = serde_json::json!({ "sub": grant.user_id.to_string() });
+        if grant.scopes.iter().any(|s| s == OIDC_SCOPE_EMAIL) {
+            let (email, verified) = self.user_email(grant.user_id).await?;
+            claims["email"] = serde_json::json!(email);
+            claims["email_verified"] = serde_json::json!(verified);
+        }
+
+        Ok(claims)
+    }
+
+    /// Apps the user has granted access to
+    pub async fn list_consents(&self, user_id: Uuid) -> Result<Vec<OidcConsent>> {
+        let consents = sqlx::query_as::<_, OidcConsent>(
+            r#"
+            SELECT c.client_id, cl.name AS client_name, c.scopes, c.granted_at, c.updated_at
+            FROM oidc_consents c
+            JOIN oidc_clients cl ON cl.client_id = c.client_id
+            WHERE c.user_id = $1 AND cl.revoked_at IS NULL
+            ORDER BY c.updated_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(consents)
+    }
+
+    /// Withdraw consent and revoke the app's tokens and unredeemed codes for
+    /// this user
+    pub async fn revoke_consent(&self, user_id: Uuid, client_id: &str) -> Result<()> {
+        let mut tx = self.db_pool.begin().await?;
+
+        let deleted =
+            sqlx::query("DELETE FROM oidc_consents WHERE user_id = $1 AND client_id = $2")
+                .bind(user_id)
+                .bind(client_id)
+                .execute(&mut *tx)
+                .await?;
+        if deleted.rows_affected() == 0 {
+            return Err(AppError::NotFound {
+                resource: "OIDC consent".to_string(),
+            });
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE oidc_tokens SET revoked_at = NOW()
+            WHERE user_id = $1 AND client_id = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(client_id)
+        .execute(&mut *tx)
+        .await?;
+
+        // Codes issued under the consent but not yet redeemed die with it
+        sqlx::query(
+            r#"
+            DELETE FROM oidc_authorization_codes
+            WHERE user_id = $1 AND client_id = $2 AND consumed_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(client_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        tracing::info!(user_id = %user_id, client_id = %client_id, "OIDC consent revoked");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkce_s256_rfc7636_example() {
+        // RFC 7636 Appendix B
+        let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+        assert!(is_valid_pkce_value(verifier));
+        assert_eq!(
+            pkce_challenge(verifier),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert!(!is_valid_pkce_value("too-short"));
+    }
+
+    #[test]
+    fn test_redirect_uri_rules() {
+        assert!(is_valid_redirect_uri(
+            "https://radio.example/callback",
+            true
+        ));
+        assert!(is_valid_redirect_uri("http://localhost:8080/cb", true));
+        assert!(!is_valid_redirect_uri(
+            "http://radio.example/callback",
+            true
+        ));
+        assert!(!is_valid_redirect_uri(
+            "https://radio.example/cb#frag",
+            true
+        ));
+        assert!(!is_valid_redirect_uri("org.example.radio:/callback", true));
+        assert!(is_valid_redirect_uri("org.example.radio:/callback", false));
+        assert!(!is_valid_redirect_uri("/relative", false));
+    }
+
+    #[test]
+    fn test_error_redirect_keeps_existing_query() {
+        let error = OidcError::invalid_scope("nope").redirect(
+            "https://radio.example/cb?app=1",
+            Some("xyz"),
+            "https://api.example",
+        );
+        let url = Url::parse(error.redirect_to.as_deref().unwrap()).unwrap();
+        let params: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+
+        assert_eq!(params[0], ("app".to_string(), "1".to_string()));
+        assert!(params.contains(&("error".to_string(), "invalid_scope".to_string())));
+        assert!(params.contains(&("state".to_string(), "xyz".to_string())));
+    }
+
+    #[test]
+    fn test_parse_scopes_dedupes() {
+        assert_eq!(
+            parse_scopes(" openid  dnp:read openid "),
+            vec!["openid".to_string(), "dnp:read".to_string()]
+        );
+    }
+}
@@ -305,7 +305,7 @@ impl<'a> PlaylistRepository<'a> {
                 JOIN artist_offenses ao ON ao.artist_id = a.id AND ao.status = 'verified'
             ),
             blocked_ids AS (
-                SELECT artist_id FROM user_artist_blocks WHERE user_id = $1
+                SELECT artist_id FROM effective_artist_blocks WHERE user_id = $1
             )
             SELECT
                 pt.playlist_id,
@@ -458,7 +458,7 @@ impl<'a> PlaylistRepository<'a> {
                        OR (pt.artist_name IS NOT NULL AND LOWER(pt.artist_name) = LOWER(a.canonical_name))
                 ) AS is_offending,
                 EXISTS (
-                    SELECT 1 FROM user_artist_blocks uab
+                    SELECT 1 FROM effective_artist_blocks uab
                     WHERE uab.user_id = $2 AND uab.artist_id = pt.artist_id
                 ) AS is_blocked,
                 pt.added_at
@@ -11,9 +11,10 @@ use deadpool_redis::{Config, Pool, Runtime};
 use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use sqlx::types::ipnetwork::IpNetwork;
 use std::collections::HashMap;
-use std::net::SocketAddr;
-use std::sync::Arc;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
 use tracing::{debug, error, warn};
 use uuid;
@@ -215,35 +216,79 @@ pub struct RateLimitStatus {
     pub time_until_reset: u64,
 }
 
+/// Proxies whose `X-Forwarded-For` and `X-Real-IP` headers are believed,
+/// from `TRUSTED_PROXIES` (comma-separated addresses or CIDR ranges)
+fn trusted_proxies() -> &'static [IpNetwork] {
+    static TRUSTED: OnceLock<Vec<IpNetwork>> = OnceLock::new();
+    TRUSTED.get_or_init(|| {
+        std::env::var("TRUSTED_PROXIES")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| match entry.parse::<IpNetwork>() {
+                Ok(network) => Some(network),
+                Err(e) => {
+                    warn!("Ignoring invalid TRUSTED_PROXIES entry '{}': {}", entry, e);
+                    None
+                }
+            })
+            .collect()
+    })
+}
+
 /// Extract client IP address from request
+///
+/// Forwarding headers are only honoured when the connection comes from a
+/// trusted proxy; anyone else could set them to dodge rate limits and IP
+/// blocks or to get another address blocked.
 pub fn extract_client_ip(
     headers: &HeaderMap,
     connect_info: Option<&ConnectInfo<SocketAddr>>,
 ) -> String {
-    // Try X-Forwarded-For header first (for proxies)
-    if let Some(forwarded) = headers.get("x-forwarded-for") {
-        if let Ok(forwarded_str) = forwarded.to_str() {
-            // Take the first IP in the chain
-            if let Some(first_ip) = forwarded_str.split(',').next() {
-                return first_ip.trim().to_string();
-            }
-        }
+    client_ip_behind(
+        headers,
+        connect_info.map(|ConnectInfo(addr)| addr.ip()),
+        trusted_proxies(),
+    )
+}
+
+fn client_ip_behind(headers: &HeaderMap, peer: Option<IpAddr>, trusted: &[IpNetwork]) -> String {
+    let Some(peer) = peer else {
+        return "unknown".to_string();
+    };
+    let is_trusted = |ip: IpAddr| trusted.iter().any(|network| network.contains(ip));
+    if !is_trusted(peer) {
+        return peer.to_string();
     }
 
-    // Try X-Real-IP header (for nginx)
-    if let Some(real_ip) = headers.get("x-real-ip") {
-        if let Ok(ip_str) = real_ip.to_str() {
-            return ip_str.to_string();
+    // Each proxy appends the address it received the request from, so the
+    // client is the rightmost entry that isn't one of our proxies
+    if let Some(forwarded) = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+    {
+        let hops: Vec<IpAddr> = forwarded
+            .split(',')
+            .filter_map(|hop| hop.trim().parse().ok())
+            .collect();
+        if let Some(client) = hops.iter().rev().find(|ip| !is_trusted(**ip)) {
+            return client.to_string();
+        }
+        if let Some(first) = hops.first() {
+            return first.to_string();
         }
     }
 
-    // Fall back to connection info
-    if let Some(ConnectInfo(addr)) = connect_info {
-        return addr.ip().to_string();
+    if let Some(real_ip) = headers
+        .get("x-real-ip")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<IpAddr>().ok())
+    {
+        return real_ip.to_string();
     }
 
-    // Default fallback
-    "unknown".to_string()
+    peer.to_string()
 }
 
 /// Rate limiting middleware for authentication endpoints
@@ -504,6 +549,43 @@ mod tests {
     use std::time::Duration;
     use tokio::time::sleep;
 
+    fn forwarded_headers(forwarded_for: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", forwarded_for.parse().unwrap());
+        headers.insert("x-real-ip", "203.0.113.9".parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_forwarding_headers_ignored_from_untrusted_peers() {
+        let trusted: Vec<IpNetwork> = vec!["10.0.0.0/8".parse().unwrap()];
+        let headers = forwarded_headers("198.51.100.7");
+        let peer = Some("192.0.2.1".parse().unwrap());
+
+        assert_eq!(client_ip_behind(&headers, peer, &trusted), "192.0.2.1");
+        assert_eq!(client_ip_behind(&headers, peer, &[]), "192.0.2.1");
+        assert_eq!(client_ip_behind(&headers, None, &trusted), "unknown");
+    }
+
+    #[test]
+    fn test_client_is_rightmost_untrusted_hop() {
+        let trusted: Vec<IpNetwork> = vec!["10.0.0.0/8".parse().unwrap()];
+        let peer = Some("10.0.0.2".parse().unwrap());
+
+        // A spoofed leading entry doesn't hide the address our proxy saw
+        let headers = forwarded_headers("1.2.3.4, 198.51.100.7, 10.0.0.5");
+        assert_eq!(client_ip_behind(&headers, peer, &trusted), "198.51.100.7");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", "203.0.113.9".parse().unwrap());
+        assert_eq!(client_ip_behind(&headers, peer, &trusted), "203.0.113.9");
+
+        assert_eq!(
+            client_ip_behind(&HeaderMap::new(), peer, &trusted),
+            "10.0.0.2"
+        );
+    }
+
     #[tokio::test]
     #[ignore] // Requires Redis connection
     async fn test_rate_limiting_basic() {
@@ -0,0 +1,140 @@
+//! Recently-played listening data from provider APIs
+//!
+//! Only Spotify exposes timestamped play history to third parties
+//! (`/v1/me/player/recently-played`, last 50 plays). Apple Music's recent
+//! tracks endpoint has no play times and YouTube Music has no history API, so
+//! those platforms are covered by data-export imports instead.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::TokenVaultService;
+use ndith_core::models::{Connection, ConnectionStatus, StreamingProvider};
+
+/// Scope required to read a Spotify user's play history
+pub const SPOTIFY_RECENTLY_PLAYED_SCOPE: &str = "user-read-recently-played";
+
+/// A play reported by a provider API
+#[derive(Debug, Clone)]
+pub struct RecentPlay {
+    pub played_at: DateTime<Utc>,
+    pub artist_name: String,
+    pub track_name: String,
+    pub album_name: Option<String>,
+    pub duration_ms: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyRecentlyPlayedResponse {
+    items: Vec<SpotifyPlayHistoryItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyPlayHistoryItem {
+    played_at: DateTime<Utc>,
+    track: SpotifyPlayedTrack,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyPlayedTrack {
+    name: String,
+    duration_ms: Option<i64>,
+    artists: Vec<SpotifyNamedEntity>,
+    album: Option<SpotifyNamedEntity>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpotifyNamedEntity {
+    name: String,
+}
+
+/// Fetches recently-played history for connected accounts
+pub struct RecentlyPlayedService {
+    token_vault: Arc<TokenVaultService>,
+    client: reqwest::Client,
+}
+
+impl RecentlyPlayedService {
+    pub fn new(token_vault: Arc<TokenVaultService>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            token_vault,
+            client,
+        }
+    }
+
+    /// Active Spotify connections that granted the recently-played scope.
+    /// Connections made before the scope was requested need to reconnect.
+    pub async fn spotify_connections(&self) -> Vec<Connection> {
+        self.token_vault
+            .get_all_connections()
+            .await
+            .into_iter()
+            .filter(|c| {
+                c.provider == StreamingProvider::Spotify
+                    && c.status == ConnectionStatus::Active
+                    && c.scopes.iter().any(|s| s == SPOTIFY_RECENTLY_PLAYED_SCOPE)
+            })
+            .collect()
+    }
+
+    /// Plays after `after` (or the last 50 plays), oldest first
+    pub async fn fetch_spotify(
+        &self,
+        connection: &Connection,
+        after: Option<DateTime<Utc>>,
+    ) -> Result<Vec<RecentPlay>> {
+        let token = self.token_vault.get_decrypted_token(connection.id).await?;
+
+        let mut url = "https://api.spotify.com/v1/me/player/recently-played?limit=50".to_string();
+        if let Some(after) = after {
+            url.push_str(&format!("&after={}", after.timestamp_millis()));
+        }
+
+        let response = self
+            .client
+            .get(&url)
+            .bearer_auth(&token.access_token)
+            .send()
+            .await
+            .context("Spotify recently-played request failed")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Failed to fetch Spotify recently played: {}",
+                response.status()
+            ));
+        }
+
+        let body: SpotifyRecentlyPlayedResponse = response
+            .json()
+            .await
+            .context("Invalid Spotify recently-played response")?;
+
+        let mut plays: Vec<RecentPlay> = body
+            .items
+            .into_iter()
+            .filter_map(|item| {
+                // Plays are credited to the primary artist, as in the exports
+                let artist = item.track.artists.into_iter().next()?;
+                Some(RecentPlay {
+                    played_at: item.played_at,
+                    artist_name: artist.name,
+                    track_name: item.track.name,
+                    album_name: item.track.album.map(|a| a.name),
+                    duration_ms: item.track.duration_ms,
+                })
+            })
+            .collect();
+        plays.sort_by_key(|p| p.played_at);
+
+        Ok(plays)
+    }
+}
@@ -0,0 +1,99 @@
+//! 2FA recovery codes
+//!
+//! Codes are ten random characters from an alphabet without look-alike
+//! characters, shown as `xxxxx-xxxxx`. That is 50 bits of entropy per code,
+//! enough that an unsalted SHA-256 hash is safe to store and can be looked up
+//! directly instead of bcrypt-comparing every code on each login.
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Number of codes in a set
+pub const RECOVERY_CODE_COUNT: usize = 8;
+
+const CODE_LENGTH: usize = 10;
+
+// No 0/o, 1/l/i, so codes survive being read aloud or copied by hand
+const ALPHABET: &[u8] = b"23456789abcdefghjkmnpqrstuvwxyz";
+
+/// Generate a new set of recovery codes
+pub fn generate_recovery_codes() -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let chars: String = (0..CODE_LENGTH)
+                .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())] as char)
+                .collect();
+            format!(
+                "{}-{}",
+                &chars[..CODE_LENGTH / 2],
+                &chars[CODE_LENGTH / 2..]
+            )
+        })
+        .collect()
+}
+
+/// Canonical form of a code as typed: case, spaces and dashes are ignored.
+/// Returns None if it can't be a recovery code.
+pub fn normalize_recovery_code(code: &str) -> Option<String> {
+    let normalized: String = code
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    let valid =
+        normalized.len() == CODE_LENGTH && normalized.bytes().all(|b| ALPHABET.contains(&b));
+    valid.then_some(normalized)
+}
+
+/// Hash of a code for storage and lookup
+pub fn hash_recovery_code(code: &str) -> Option<String> {
+    let normalized = normalize_recovery_code(code)?;
+    let digest = Sha256::digest(normalized.as_bytes());
+    Some(digest.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_codes_are_well_formed_and_distinct() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+        for code in &codes {
+            assert_eq!(code.len(), CODE_LENGTH + 1);
+            assert_eq!(code.as_bytes()[CODE_LENGTH / 2], b'-');
+            assert!(normalize_recovery_code(code).is_some());
+        }
+
+        let mut unique = codes.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), codes.len());
+    }
+
+    #[test]
+    fn normalization_ignores_case_spaces_and_dashes() {
+        let canonical = Some("abcdefghjk".to_string());
+        assert_eq!(normalize_recovery_code("abcde-fghjk"), canonical);
+        assert_eq!(normalize_recovery_code(" ABCDE FGHJK "), canonical);
+        assert_eq!(normalize_recovery_code("ab-cde-fg hjk"), canonical);
+    }
+
+    #[test]
+    fn hashing_ignores_formatting() {
+        let hash = hash_recovery_code("abcde-fghjk").unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash_recovery_code("ABCDE FGHJK"), Some(hash.clone()));
+        assert_eq!(hash_recovery_code("abcdefghjk"), Some(hash));
+    }
+
+    #[test]
+    fn rejects_non_codes() {
+        assert!(normalize_recovery_code("123456").is_none());
+        assert!(normalize_recovery_code("abcde-fghj0").is_none());
+        assert!(hash_recovery_code("").is_none());
+    }
+}
@@ -0,0 +1,214 @@
+//! Role grants and permission resolution
+//!
+//! Access tokens carry the `user` role, so moderator and admin rights come
+//! from rows in `user_roles`. The auth layer resolves a caller's effective
+//! permissions on every protected request: the token role and legacy scopes,
+//! plus any stored grants. Grants are cached briefly per user; granting or
+//! revoking a role drops that user's entry so the change applies at once on
+//! this replica and within the cache TTL elsewhere.
+
+use chrono::{DateTime, Utc};
+use moka::future::Cache;
+use ndith_core::models::{Claims, Permissions, RoleGrant, UserRole};
+use ndith_core::{AppError, Result};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::audit_logging::{AuditContext, AuditEventType, AuditLoggingService, AuditSeverity};
+
+/// How long stored grants are trusted before being re-read
+const GRANT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+pub struct RoleService {
+    db_pool: PgPool,
+    audit_logger: Arc<AuditLoggingService>,
+    grants: Cache<Uuid, Vec<UserRole>>,
+}
+
+impl RoleService {
+    pub fn new(db_pool: PgPool, audit_logger: Arc<AuditLoggingService>) -> Self {
+        Self {
+            db_pool,
+            audit_logger,
+            grants: Cache::builder()
+                .max_capacity(10_000)
+                .time_to_live(GRANT_CACHE_TTL)
+                .build(),
+        }
+    }
+
+    /// Effective permissions for an authenticated caller
+    ///
+    /// Service tokens have no user row, so only the token is consulted.
+    pub async fn permissions_for(&self, claims: &Claims) -> Result<Permissions> {
+        let mut permissions = claims.permissions();
+        if claims.role.is_service() {
+            return Ok(permissions);
+        }
+
+        let user_id = claims.user_id().map_err(|_| AppError::TokenInvalid)?;
+        for role in self.granted_roles(user_id).await? {
+            permissions.add_role(role);
+        }
+        Ok(permissions)
+    }
+
+    /// Roles stored for a user, served from the cache when fresh
+    pub async fn granted_roles(&self, user_id: Uuid) -> Result<Vec<UserRole>> {
+        if let Some(roles) = self.grants.get(&user_id).await {
+            return Ok(roles);
+        }
+
+        let roles: Vec<String> =
+            sqlx::query_scalar("SELECT role FROM user_roles WHERE user_id = $1 ORDER BY role")
+                .bind(user_id)
+                .fetch_all(&self.db_pool)
+                .await?;
+        let roles: Vec<UserRole> = roles.iter().map(|r| parse_role(r)).collect();
+
+        self.grants.insert(user_id, roles.clone()).await;
+        Ok(roles)
+    }
+
+    /// Stored grants for a user, with who granted them
+    pub async fn list_grants(&self, user_id: Uuid) -> Result<Vec<RoleGrant>> {
+        self.ensure_user_exists(user_id).await?;
+
+        let rows: Vec<(String, Option<Uuid>, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT role, granted_by, granted_at
+            FROM user_roles
+            WHERE user_id = $1
+            ORDER BY granted_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(role, granted_by, granted_at)| {
+                let role = parse_role(&role);
+                RoleGrant {
+                    role,
+                    permissions: role.permissions(),
+                    granted_by,
+                    granted_at,
+                }
+            })
+            .collect())
+    }
+
+    /// Grant a role to a user. Returns false if the user already held it.
+    pub async fn grant(&self, actor_id: Uuid, user_id: Uuid, role: UserRole) -> Result<bool> {
+        ensure_grantable(role)?;
+        self.ensure_user_exists(user_id).await?;
+
+        let granted = sqlx::query(
+            r#"
+            INSERT INTO user_roles (user_id, role, granted_by)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, role) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(role.as_str())
+        .bind(actor_id)
+        .execute(&self.db_pool)
+        .await?
+        .rows_affected()
+            > 0;
+
+        self.grants.invalidate(&user_id).await;
+        if granted {
+            self.audit(actor_id, user_id, role, "granted").await;
+        }
+        Ok(granted)
+    }
+
+    /// Revoke a role from a user. Returns false if the user did not hold it.
+    ///
+    /// Admins cannot revoke their own admin role, so an admin cannot lock
+    /// themselves out of the role API by accident.
+    pub async fn revoke(&self, actor_id: Uuid, user_id: Uuid, role: UserRole) -> Result<bool> {
+        ensure_grantable(role)?;
+        if actor_id == user_id && role == UserRole::Admin {
+            return Err(AppError::Conflict {
+                message: "Admins cannot revoke their own admin role".to_string(),
+            });
+        }
+        self.ensure_user_exists(user_id).await?;
+
+        let revoked = sqlx::query("DELETE FROM user_roles WHERE user_id = $1 AND role = $2")
+            .bind(user_id)
+            .bind(role.as_str())
+            .execute(&self.db_pool)
+            .await?
+            .rows_affected()
+            > 0;
+
+        self.grants.invalidate(&user_id).await;
+        if revoked {
+            self.audit(actor_id, user_id, role, "revoked").await;
+        }
+        Ok(revoked)
+    }
+
+    async fn ensure_user_exists(&self, user_id: Uuid) -> Result<()> {
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = $1)")
+            .bind(user_id)
+            .fetch_one(&self.db_pool)
+            .await?;
+        if !exists {
+            return Err(AppError::NotFound {
+                resource: "User".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn audit(&self, actor_id: Uuid, user_id: Uuid, role: UserRole, change: &str) {
+        let details = serde_json::json!({
+            "target_user_id": user_id,
+            "role": role.as_str(),
+            "permissions": role.permissions(),
+        });
+        let context = AuditContext {
+            user_id: Some(actor_id),
+            session_id: None,
+            ip_address: None,
+            user_agent: None,
+            correlation_id: None,
+        };
+        if let Err(e) = self
+            .audit_logger
+            .log_security_event(
+                AuditEventType::AdminAction,
+                AuditSeverity::Warning,
+                format!("Role {} {}", role.as_str(), change),
+                details,
+                Some(context),
+            )
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to audit role change");
+        }
+    }
+}
+
+fn parse_role(role: &str) -> UserRole {
+    role.parse().unwrap_or_default()
+}
+
+fn ensure_grantable(role: UserRole) -> Result<()> {
+    if !role.is_grantable() {
+        return Err(AppError::InvalidFieldValue {
+            field: "role".to_string(),
+            message: "Must be 'moderator' or 'admin'".to_string(),
+        });
+    }
+    Ok(())
+}
@@ -131,6 +131,7 @@ impl SpotifyService {
             .add_scope(Scope::new("playlist-modify-public".to_string()))
             .add_scope(Scope::new("user-follow-read".to_string()))
             .add_scope(Scope::new("user-follow-modify".to_string()))
+            .add_scope(Scope::new("user-read-recently-played".to_string()))
             .set_pkce_challenge(pkce_challenge)
             .url();
 
@@ -192,6 +193,7 @@ impl SpotifyService {
             "playlist-modify-public".to_string(),
             "user-follow-read".to_string(),
             "user-follow-modify".to_string(),
+            "user-read-recently-played".to_string(),
         ];
 
         // Store the connection using the token vault
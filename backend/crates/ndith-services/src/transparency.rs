@@ -0,0 +1,397 @@
+//! Public transparency statistics
+//!
+//! Aggregates that are safe to publish without authentication: the most
+//! blocked artists, blocks per offense category over time and enforcement
+//! volume per platform. Every published group must cover at least
+//! `min_cohort_size` distinct users (smaller groups are dropped in SQL and
+//! never leave the database), and counts below `noise_threshold` get
+//! two-sided geometric noise so a single user joining or leaving a group
+//! can't be read off the difference between two releases.
+//!
+//! Noise is derived from a keyed hash of the statistic, the group and the
+//! true value rather than drawn fresh on every call. Asking for the same
+//! figure repeatedly returns the same noisy answer, so it can't be averaged
+//! away, while any change in the true value draws new noise.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use ndith_core::config::{ConfigError, Environment};
+use ndith_core::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// Default smallest number of distinct users a published group may cover
+pub const DEFAULT_MIN_COHORT_SIZE: i64 = 10;
+
+/// Default privacy parameter for the geometric mechanism (noise scale ~1/ε)
+pub const DEFAULT_NOISE_EPSILON: f64 = 0.5;
+
+/// Counts at or above this are published without noise
+pub const DEFAULT_NOISE_THRESHOLD: i64 = 100;
+
+/// Privacy settings for published statistics
+#[derive(Debug, Clone)]
+pub struct TransparencyConfig {
+    pub min_cohort_size: i64,
+    pub noise_epsilon: f64,
+    pub noise_threshold: i64,
+    /// Secret mixed into the noise seed; without it anyone could recompute
+    /// the noise and subtract it
+    noise_secret: Vec<u8>,
+}
+
+impl TransparencyConfig {
+    pub fn new(
+        min_cohort_size: i64,
+        noise_epsilon: f64,
+        noise_threshold: i64,
+        noise_secret: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            min_cohort_size: min_cohort_size.max(1),
+            noise_epsilon: if noise_epsilon > 0.0 {
+                noise_epsilon
+            } else {
+                DEFAULT_NOISE_EPSILON
+            },
+            noise_threshold: noise_threshold.max(0),
+            noise_secret: noise_secret.into(),
+        }
+    }
+
+    /// Read settings from `PUBLIC_STATS_MIN_COHORT_SIZE`,
+    /// `PUBLIC_STATS_NOISE_EPSILON`, `PUBLIC_STATS_NOISE_THRESHOLD` and
+    /// `PUBLIC_STATS_NOISE_SECRET`. The secret is required outside
+    /// development; in development a random one is generated per process.
+    pub fn from_env(env: Environment) -> std::result::Result<Self, ConfigError> {
+        fn env_parse<T: std::str::FromStr>(key: &str) -> Option<T> {
+            std::env::var(key).ok().and_then(|v| v.parse().ok())
+        }
+
+        let secret = match std::env::var("PUBLIC_STATS_NOISE_SECRET")
+            .ok()
+            .filter(|s| !s.is_empty())
+        {
+            Some(secret) => secret.into_bytes(),
+            None if env.is_development() => process_noise_secret().to_vec(),
+            None => {
+                return Err(ConfigError::MissingRequired(
+                    "PUBLIC_STATS_NOISE_SECRET".to_string(),
+                ))
+            }
+        };
+
+        Ok(Self::new(
+            env_parse("PUBLIC_STATS_MIN_COHORT_SIZE").unwrap_or(DEFAULT_MIN_COHORT_SIZE),
+            env_parse("PUBLIC_STATS_NOISE_EPSILON").unwrap_or(DEFAULT_NOISE_EPSILON),
+            env_parse("PUBLIC_STATS_NOISE_THRESHOLD").unwrap_or(DEFAULT_NOISE_THRESHOLD),
+            secret,
+        ))
+    }
+
+    /// Publish a count for `group` of statistic `stat`.
+    ///
+    /// Counts below the noise threshold are perturbed; the result never drops
+    /// below the minimum cohort size so it can't hint that a group was close
+    /// to suppression.
+    pub fn noisy_count(&self, stat: &str, group: &str, count: i64) -> i64 {
+        if count >= self.noise_threshold {
+            return count;
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(&self.noise_secret);
+        hasher.update(stat.as_bytes());
+        hasher.update([0]);
+        hasher.update(group.as_bytes());
+        hasher.update([0]);
+        hasher.update(count.to_le_bytes());
+        let digest = hasher.finalize();
+
+        let noise = two_sided_geometric(self.noise_epsilon, &digest);
+        (count + noise).max(self.min_cohort_size)
+    }
+
+    /// Settings reported alongside published figures
+    pub fn disclosure(&self) -> PrivacyDisclosure {
+        PrivacyDisclosure {
+            min_cohort_size: self.min_cohort_size,
+            noise_epsilon: self.noise_epsilon,
+            noise_threshold: self.noise_threshold,
+        }
+    }
+}
+
+fn process_noise_secret() -> &'static [u8; 32] {
+    static SECRET: OnceLock<[u8; 32]> = OnceLock::new();
+    SECRET.get_or_init(|| {
+        tracing::warn!(
+            "PUBLIC_STATS_NOISE_SECRET is not set; using a development secret that changes on restart"
+        );
+        rand::random()
+    })
+}
+
+/// Map a hash to a uniform value in (0, 1)
+fn unit_interval(bytes: &[u8]) -> f64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&bytes[..8]);
+    // 53 bits of mantissa, offset by half a step so 0 and 1 are never hit
+    ((u64::from_le_bytes(buf) >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+}
+
+/// Two-sided geometric (discrete Laplace) noise with parameter ε, as the
+/// difference of two geometric draws with success probability 1 - e^-ε
+fn two_sided_geometric(epsilon: f64, digest: &[u8]) -> i64 {
+    let ln_alpha = -epsilon;
+    let draw = |bytes: &[u8]| (unit_interval(bytes).ln() / ln_alpha).floor() as i64;
+    draw(&digest[..8]) - draw(&digest[8..16])
+}
+
+/// Privacy settings the figures were published under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyDisclosure {
+    pub min_cohort_size: i64,
+    pub noise_epsilon: f64,
+    pub noise_threshold: i64,
+}
+
+/// An artist and how many users block them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockedArtistStat {
+    pub artist_id: Uuid,
+    pub artist_name: String,
+    pub users_blocking: i64,
+}
+
+/// Blocks of artists in an offense category during one week
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryBlocksStat {
+    pub week_start: NaiveDate,
+    pub category: String,
+    pub blocks: i64,
+    pub users: i64,
+}
+
+/// Enforcement activity on one platform during one week
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnforcementVolumeStat {
+    pub week_start: NaiveDate,
+    pub platform: String,
+    pub batches: i64,
+    pub actions: i64,
+    pub users: i64,
+}
+
+/// A published statistic with the privacy settings it was produced under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicStats<T> {
+    pub items: Vec<T>,
+    pub privacy: PrivacyDisclosure,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Computes the public transparency statistics from Postgres
+pub struct TransparencyStatsService {
+    pool: PgPool,
+    config: TransparencyConfig,
+}
+
+impl TransparencyStatsService {
+    pub fn new(pool: PgPool, config: TransparencyConfig) -> Self {
+        Self { pool, config }
+    }
+
+    fn publish<T>(&self, items: Vec<T>) -> PublicStats<T> {
+        PublicStats {
+            items,
+            privacy: self.config.disclosure(),
+            generated_at: Utc::now(),
+        }
+    }
+
+    /// Artists blocked by the most users
+    pub async fn most_blocked_artists(&self, limit: i64) -> Result<PublicStats<BlockedArtistStat>> {
+        let rows: Vec<(Uuid, String, i64)> = sqlx::query_as(
+            r#"
+            SELECT a.id, a.canonical_name, COUNT(DISTINCT b.user_id) AS users
+            FROM user_artist_blocks b
+            JOIN artists a ON a.id = b.artist_id
+            GROUP BY a.id, a.canonical_name
+            HAVING COUNT(DISTINCT b.user_id) >= $1
+            ORDER BY users DESC, a.canonical_name
+            LIMIT $2
+            "#,
+        )
+        .bind(self.config.min_cohort_size)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        let mut items: Vec<BlockedArtistStat> = rows
+            .into_iter()
+            .map(|(artist_id, artist_name, users)| BlockedArtistStat {
+                users_blocking: self.config.noisy_count(
+                    "most_blocked_artists",
+                    &artist_id.to_string(),
+                    users,
+                ),
+                artist_id,
+                artist_name,
+            })
+            .collect();
+        // Noise can reorder close neighbours; keep the published list sorted
+        items.sort_by(|a, b| {
+            b.users_blocking
+                .cmp(&a.users_blocking)
+                .then_with(|| a.artist_name.cmp(&b.artist_name))
+        });
+
+        Ok(self.publish(items))
+    }
+
+    /// Weekly blocks of artists with a verified offense, per offense category
+    pub async fn blocks_by_category(&self, weeks: i32) -> Result<PublicStats<CategoryBlocksStat>> {
+        let rows: Vec<(NaiveDate, String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT DATE_TRUNC('week', b.created_at)::date AS week_start,
+                   ao.category::text,
+                   COUNT(*) AS blocks,
+                   COUNT(DISTINCT b.user_id) AS users
+            FROM user_artist_blocks b
+            JOIN (
+                SELECT DISTINCT artist_id, category
+                FROM artist_offenses
+                WHERE status = 'verified'
+            ) ao ON ao.artist_id = b.artist_id
+            WHERE b.created_at >= DATE_TRUNC('week', NOW()) - make_interval(weeks => $2)
+            GROUP BY 1, 2
+            HAVING COUNT(DISTINCT b.user_id) >= $1
+            ORDER BY 1, 2
+            "#,
+        )
+        .bind(self.config.min_cohort_size)
+        .bind(weeks)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        let items = rows
+            .into_iter()
+            .map(|(week_start, category, blocks, users)| {
+                let group = format!("{}:{}", week_start, category);
+                CategoryBlocksStat {
+                    blocks: self.config.noisy_count("category_blocks", &group, blocks),
+                    users: self.config.noisy_count("category_users", &group, users),
+                    week_start,
+                    category,
+                }
+            })
+            .collect();
+
+        Ok(self.publish(items))
+    }
+
+    /// Weekly enforcement batches and completed actions, per platform
+    pub async fn enforcement_volume(
+        &self,
+        weeks: i32,
+    ) -> Result<PublicStats<EnforcementVolumeStat>> {
+        let rows: Vec<(NaiveDate, String, i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT DATE_TRUNC('week', ab.created_at)::date AS week_start,
+                   ab.provider,
+                   COUNT(DISTINCT ab.id) AS batches,
+                   COUNT(ai.id) AS actions,
+                   COUNT(DISTINCT ab.user_id) AS users
+            FROM action_batches ab
+            LEFT JOIN action_items ai ON ai.batch_id = ab.id AND ai.status = 'completed'
+            WHERE NOT COALESCE(ab.dry_run, FALSE)
+              AND ab.created_at >= DATE_TRUNC('week', NOW()) - make_interval(weeks => $2)
+            GROUP BY 1, 2
+            HAVING COUNT(DISTINCT ab.user_id) >= $1
+            ORDER BY 1, 2
+            "#,
+        )
+        .bind(self.config.min_cohort_size)
+        .bind(weeks)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?;
+
+        let items = rows
+            .into_iter()
+            .map(|(week_start, platform, batches, actions, users)| {
+                let group = format!("{}:{}", week_start, platform);
+                EnforcementVolumeStat {
+                    batches: self
+                        .config
+                        .noisy_count("enforcement_batches", &group, batches),
+                    actions: self
+                        .config
+                        .noisy_count("enforcement_actions", &group, actions),
+                    users: self.config.noisy_count("enforcement_users", &group, users),
+                    week_start,
+                    platform,
+                }
+            })
+            .collect();
+
+        Ok(self.publish(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> TransparencyConfig {
+        TransparencyConfig::new(10, 0.5, 100, b"test-secret".to_vec())
+    }
+
+    #[test]
+    fn noise_is_stable_for_the_same_value() {
+        let config = config();
+        let first = config.noisy_count("stat", "group", 42);
+        assert_eq!(first, config.noisy_count("stat", "group", 42));
+    }
+
+    #[test]
+    fn noise_depends_on_the_secret() {
+        let other = TransparencyConfig::new(10, 0.5, 100, b"other-secret".to_vec());
+        let differs = (10..60).any(|n| {
+            config().noisy_count("stat", "group", n) != other.noisy_count("stat", "group", n)
+        });
+        assert!(differs);
+    }
+
+    #[test]
+    fn large_counts_are_exact() {
+        assert_eq!(config().noisy_count("stat", "group", 100), 100);
+        assert_eq!(config().noisy_count("stat", "group", 5000), 5000);
+    }
+
+    #[test]
+    fn noisy_counts_never_fall_below_cohort_size() {
+        let config = config();
+        for n in 10..100 {
+            for group in ["a", "b", "c", "d"] {
+                assert!(config.noisy_count("stat", group, n) >= 10);
+            }
+        }
+    }
+
+    #[test]
+    fn noise_is_roughly_centred() {
+        let config = config();
+        let samples: Vec<i64> = (0..2000)
+            .map(|i| config.noisy_count("stat", &i.to_string(), 50) - 50)
+            .collect();
+        let mean = samples.iter().sum::<i64>() as f64 / samples.len() as f64;
+        assert!(mean.abs() < 0.5, "mean noise {}", mean);
+        assert!(samples.iter().any(|&n| n != 0));
+    }
+}
@@ -279,7 +279,17 @@ impl UserService {
         user_id: Uuid,
         request: AccountDeletionRequest,
     ) -> Result<AccountDeletionResult> {
-        // Verify the confirmation email matches the user's email
+        self.confirm_account_deletion(user_id, &request.confirmation_email)
+            .await?;
+        self.erase_account(user_id).await
+    }
+
+    /// Check that the confirmation email matches the user's email
+    pub async fn confirm_account_deletion(
+        &self,
+        user_id: Uuid,
+        confirmation_email: &str,
+    ) -> Result<()> {
         let user = sqlx::query!("SELECT email FROM users WHERE id = $1", user_id)
             .fetch_optional(&self.db_pool)
             .await
@@ -288,12 +298,17 @@ impl UserService {
                 resource: "User not found".to_string(),
             })?;
 
-        if user.email != request.confirmation_email {
+        if user.email != confirmation_email {
             return Err(AppError::InvalidRequestFormat(
                 "Email confirmation does not match".to_string(),
             ));
         }
 
+        Ok(())
+    }
+
+    /// Delete the account and its data, keeping anonymized audit entries
+    pub async fn erase_account(&self, user_id: Uuid) -> Result<AccountDeletionResult> {
         let mut tx = self
             .db_pool
             .begin()
@@ -360,26 +375,48 @@ impl UserService {
             owned_lists_updated as u32,
         );
 
-        // Anonymize audit log entries (keep for compliance but remove PII)
-        let audit_anonymized = sqlx::query!(
+        // Anonymize audit log entries (keep for compliance but remove PII).
+        // redacted_at and a redaction record tell chain verification these
+        // were changed on purpose.
+        let audit_anonymized: i64 = sqlx::query_scalar(
             r#"
-            UPDATE audit_log 
-            SET user_id = NULL, 
-                ip_address = NULL, 
-                user_agent = 'ANONYMIZED'
-            WHERE user_id = $1
+            WITH redacted AS (
+                UPDATE audit_log
+                SET user_id = NULL,
+                    ip_address = NULL,
+                    user_agent = 'ANONYMIZED',
+                    redacted_at = NOW()
+                WHERE user_id = $1
+                RETURNING tenant_id, chain_seq
+            ), recorded AS (
+                INSERT INTO audit_log_redactions (tenant_id, chain_seq, reason)
+                SELECT tenant_id, chain_seq, 'account_erasure'
+                FROM redacted
+                WHERE chain_seq IS NOT NULL
+            )
+            SELECT COUNT(*) FROM redacted
             "#,
-            user_id
         )
-        .execute(&mut *tx)
+        .bind(user_id)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(AppError::DatabaseQueryFailed)?
-        .rows_affected();
+        .map_err(AppError::DatabaseQueryFailed)?;
         cleanup_summary.insert(
             "audit_entries_anonymized".to_string(),
             audit_anonymized as u32,
         );
 
+        // Finished exports hold a copy of everything being erased
+        let exports_deleted = sqlx::query(
+            "DELETE FROM data_export_requests WHERE user_id = $1 AND request_type = 'export'",
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseQueryFailed)?
+        .rows_affected();
+        cleanup_summary.insert("data_exports".to_string(), exports_deleted as u32);
+
         // Finally, delete the user account
         sqlx::query!("DELETE FROM users WHERE id = $1", user_id)
             .execute(&mut *tx)
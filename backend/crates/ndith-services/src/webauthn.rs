@@ -0,0 +1,1319 @@
+//! WebAuthn passkeys
+//!
+//! Registration and authentication ceremonies for passkeys, used both for
+//! passwordless login and as an alternative to TOTP. Only what the ceremonies
+//! need is implemented here: a CBOR reader for attestation objects and COSE
+//! keys, and ES256, EdDSA and RS256 signature checks via ring.
+//!
+//! Attestation follows `WebAuthnConfig::attestation`. Without vendor trust
+//! anchors the only statements we can verify are `none` and packed self
+//! attestation; other formats are accepted as unattested or rejected
+//! depending on the policy.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use ndith_core::config::{AttestationPolicy, UserVerificationPolicy, WebAuthnConfig};
+use ndith_core::models::webauthn::{AssertionCredential, RegistrationCredential};
+use ndith_core::{AppError, Result};
+use rand::RngCore;
+use ring::signature;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// COSE algorithm identifiers we accept
+pub const COSE_ALG_ES256: i64 = -7;
+pub const COSE_ALG_EDDSA: i64 = -8;
+pub const COSE_ALG_RS256: i64 = -257;
+const SUPPORTED_ALGORITHMS: [i64; 3] = [COSE_ALG_ES256, COSE_ALG_EDDSA, COSE_ALG_RS256];
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+const CHALLENGE_BYTES: usize = 32;
+const DEFAULT_CREDENTIAL_NAME: &str = "Passkey";
+const MAX_CREDENTIAL_NAME_LENGTH: usize = 100;
+const MAX_CBOR_DEPTH: usize = 16;
+const KNOWN_TRANSPORTS: [&str; 6] = ["ble", "hybrid", "internal", "nfc", "smart-card", "usb"];
+
+const CREDENTIAL_COLUMNS: &str = "id, user_id, credential_id, public_key, algorithm, sign_count, \
+     transports, aaguid, attestation_format, name, created_at, last_used_at";
+
+fn invalid(message: impl Into<String>) -> AppError {
+    AppError::InvalidFieldValue {
+        field: "credential".to_string(),
+        message: message.into(),
+    }
+}
+
+fn decode_base64url(value: &str, what: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| invalid(format!("{} is not valid base64url", what)))
+}
+
+/// The WebAuthn user handle for an account: its UUID bytes, base64url encoded
+pub fn user_handle(user_id: Uuid) -> String {
+    URL_SAFE_NO_PAD.encode(user_id.as_bytes())
+}
+
+// ===== CBOR =====
+
+/// The subset of CBOR used by attestation objects and COSE keys
+#[derive(Debug, Clone, PartialEq)]
+enum Cbor {
+    Integer(i128),
+    Bytes(Vec<u8>),
+    Text(String),
+    Map(Vec<(Cbor, Cbor)>),
+    /// Arrays and simple values, which the ceremonies never look inside
+    Other,
+}
+
+impl Cbor {
+    /// Decode one item from the start of `data`, returning it and the number
+    /// of bytes it took up
+    fn decode_prefix(data: &[u8]) -> Result<(Cbor, usize)> {
+        let mut reader = CborReader { data, pos: 0 };
+        let value = reader.read(0)?;
+        Ok((value, reader.pos))
+    }
+
+    fn decode(data: &[u8]) -> Result<Cbor> {
+        let (value, used) = Self::decode_prefix(data)?;
+        if used != data.len() {
+            return Err(invalid("Unexpected data after CBOR item"));
+        }
+        Ok(value)
+    }
+
+    fn get(&self, key: &Cbor) -> Option<&Cbor> {
+        match self {
+            Cbor::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn get_text(&self, key: &str) -> Option<&Cbor> {
+        self.get(&Cbor::Text(key.to_string()))
+    }
+
+    fn get_int(&self, key: i128) -> Option<&Cbor> {
+        self.get(&Cbor::Integer(key))
+    }
+
+    fn as_int(&self) -> Option<i128> {
+        match self {
+            Cbor::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Cbor::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    fn as_text(&self) -> Option<&str> {
+        match self {
+            Cbor::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+struct CborReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CborReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| invalid("Truncated CBOR"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn argument(&mut self, info: u8) -> Result<u64> {
+        let width = match info {
+            0..=23 => return Ok(u64::from(info)),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(invalid("Indefinite-length CBOR is not supported")),
+        };
+        Ok(self
+            .take(width)?
+            .iter()
+            .fold(0, |acc, byte| (acc << 8) | u64::from(*byte)))
+    }
+
+    /// Length of a string or container. Every element takes at least one
+    /// byte, so anything longer than the remaining input is malformed.
+    fn length(&mut self, info: u8) -> Result<usize> {
+        let len = self.argument(info)?;
+        usize::try_from(len)
+            .ok()
+            .filter(|len| *len <= self.data.len() - self.pos)
+            .ok_or_else(|| invalid("Truncated CBOR"))
+    }
+
+    fn read(&mut self, depth: usize) -> Result<Cbor> {
+        if depth > MAX_CBOR_DEPTH {
+            return Err(invalid("CBOR is nested too deeply"));
+        }
+
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+
+        match major {
+            0 => Ok(Cbor::Integer(i128::from(self.argument(info)?))),
+            1 => Ok(Cbor::Integer(-1 - i128::from(self.argument(info)?))),
+            2 => {
+                let len = self.length(info)?;
+                Ok(Cbor::Bytes(self.take(len)?.to_vec()))
+            }
+            3 => {
+                let len = self.length(info)?;
+                let text = std::str::from_utf8(self.take(len)?)
+                    .map_err(|_| invalid("CBOR text is not valid UTF-8"))?;
+                Ok(Cbor::Text(text.to_string()))
+            }
+            4 => {
+                let len = self.length(info)?;
+                for _ in 0..len {
+                    self.read(depth + 1)?;
+                }
+                Ok(Cbor::Other)
+            }
+            5 => {
+                let len = self.length(info)?;
+                let mut entries = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let key = self.read(depth + 1)?;
+                    let value = self.read(depth + 1)?;
+                    entries.push((key, value));
+                }
+                Ok(Cbor::Map(entries))
+            }
+            // Tags don't change anything we read, so take the tagged item as is
+            6 => {
+                self.argument(info)?;
+                self.read(depth + 1)
+            }
+            // false, true, null and undefined; floats never appear
+            _ => match info {
+                20..=23 => Ok(Cbor::Other),
+                _ => Err(invalid("Unsupported CBOR value")),
+            },
+        }
+    }
+}
+
+// ===== Keys and authenticator data =====
+
+/// Credential public key, parsed from its COSE_Key encoding
+#[derive(Debug, Clone, PartialEq)]
+enum CosePublicKey {
+    /// Uncompressed SEC1 P-256 point
+    Es256 {
+        point: Vec<u8>,
+    },
+    EdDsa {
+        x: Vec<u8>,
+    },
+    Rs256 {
+        n: Vec<u8>,
+        e: Vec<u8>,
+    },
+}
+
+impl CosePublicKey {
+    fn parse(encoded: &[u8]) -> Result<Self> {
+        let key = Cbor::decode(encoded)?;
+        let kty = key.get_int(1).and_then(Cbor::as_int);
+        let crv = key.get_int(-1).and_then(Cbor::as_int);
+        let param = |label: i128| key.get_int(label).and_then(Cbor::as_bytes);
+        let alg = key
+            .get_int(3)
+            .and_then(Cbor::as_int)
+            .and_then(|alg| i64::try_from(alg).ok())
+            .ok_or_else(|| invalid("Credential key has no algorithm"))?;
+
+        match alg {
+            COSE_ALG_ES256 => match (kty, crv, param(-2), param(-3)) {
+                (Some(2), Some(1), Some(x), Some(y)) if x.len() == 32 && y.len() == 32 => {
+                    Ok(Self::Es256 {
+                        point: [&[0x04u8][..], x, y].concat(),
+                    })
+                }
+                _ => Err(invalid("ES256 credential key must be a P-256 EC2 key")),
+            },
+            COSE_ALG_EDDSA => match (kty, crv, param(-2)) {
+                (Some(1), Some(6), Some(x)) if x.len() == 32 => Ok(Self::EdDsa { x: x.to_vec() }),
+                _ => Err(invalid("EdDSA credential key must be an Ed25519 OKP key")),
+            },
+            COSE_ALG_RS256 => match (kty, param(-1), param(-2)) {
+                (Some(3), Some(n), Some(e)) => Ok(Self::Rs256 {
+                    n: n.to_vec(),
+                    e: e.to_vec(),
+                }),
+                _ => Err(invalid("RS256 credential key must be an RSA key")),
+            },
+            _ => Err(invalid(format!("Unsupported credential algorithm {}", alg))),
+        }
+    }
+
+    fn algorithm(&self) -> i64 {
+        match self {
+            Self::Es256 { .. } => COSE_ALG_ES256,
+            Self::EdDsa { .. } => COSE_ALG_EDDSA,
+            Self::Rs256 { .. } => COSE_ALG_RS256,
+        }
+    }
+
+    fn verify(&self, message: &[u8], sig: &[u8]) -> bool {
+        match self {
+            Self::Es256 { point } => {
+                signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_ASN1, point)
+                    .verify(message, sig)
+                    .is_ok()
+            }
+            Self::EdDsa { x } => signature::UnparsedPublicKey::new(&signature::ED25519, x)
+                .verify(message, sig)
+                .is_ok(),
+            Self::Rs256 { n, e } => signature::RsaPublicKeyComponents { n, e }
+                .verify(&signature::RSA_PKCS1_2048_8192_SHA256, message, sig)
+                .is_ok(),
+        }
+    }
+}
+
+/// Credential data an authenticator includes when it creates a credential
+struct AttestedCredential {
+    aaguid: [u8; 16],
+    credential_id: Vec<u8>,
+    public_key: Vec<u8>,
+}
+
+struct AuthenticatorData {
+    rp_id_hash: Vec<u8>,
+    flags: u8,
+    sign_count: u32,
+    attested_credential: Option<AttestedCredential>,
+}
+
+impl AuthenticatorData {
+    fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < 37 {
+            return Err(invalid("Authenticator data is too short"));
+        }
+
+        let flags = data[32];
+        let sign_count = u32::from_be_bytes([data[33], data[34], data[35], data[36]]);
+
+        let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+            let rest = &data[37..];
+            if rest.len() < 18 {
+                return Err(invalid("Attested credential data is too short"));
+            }
+
+            let mut aaguid = [0u8; 16];
+            aaguid.copy_from_slice(&rest[..16]);
+            let id_len = usize::from(u16::from_be_bytes([rest[16], rest[17]]));
+            let rest = &rest[18..];
+            if rest.len() < id_len {
+                return Err(invalid("Attested credential data is too short"));
+            }
+
+            // The key is followed by extensions, so only take as much as it uses
+            let (credential_id, rest) = rest.split_at(id_len);
+            let (_, key_len) = Cbor::decode_prefix(rest)?;
+
+            Some(AttestedCredential {
+                aaguid,
+                credential_id: credential_id.to_vec(),
+                public_key: rest[..key_len].to_vec(),
+            })
+        } else {
+            None
+        };
+
+        Ok(Self {
+            rp_id_hash: data[..32].to_vec(),
+            flags,
+            sign_count,
+            attested_credential,
+        })
+    }
+
+    fn user_present(&self) -> bool {
+        self.flags & FLAG_USER_PRESENT != 0
+    }
+
+    fn user_verified(&self) -> bool {
+        self.flags & FLAG_USER_VERIFIED != 0
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    ceremony_type: String,
+    challenge: String,
+    origin: String,
+    #[serde(rename = "crossOrigin", default)]
+    cross_origin: bool,
+}
+
+// ===== Ceremony verification =====
+
+/// A registration that passed verification, ready to be stored
+#[derive(Debug, Clone)]
+pub struct VerifiedRegistration {
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub algorithm: i64,
+    pub sign_count: u32,
+    pub aaguid: Option<Uuid>,
+    pub attestation_format: String,
+    pub transports: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VerifiedAssertion {
+    pub sign_count: u32,
+    pub user_verified: bool,
+}
+
+/// Check client data from either ceremony and return its SHA-256 hash
+fn verify_client_data(
+    config: &WebAuthnConfig,
+    client_data_json: &[u8],
+    expected_type: &str,
+    expected_challenge: &str,
+) -> Result<Vec<u8>> {
+    let client_data: CollectedClientData = serde_json::from_slice(client_data_json)
+        .map_err(|_| invalid("clientDataJSON is malformed"))?;
+
+    if client_data.ceremony_type != expected_type {
+        return Err(invalid(format!("Expected a {} response", expected_type)));
+    }
+    if decode_base64url(&client_data.challenge, "Challenge")?
+        != decode_base64url(expected_challenge, "Challenge")?
+    {
+        return Err(invalid("Challenge does not match"));
+    }
+    if !config.origins.contains(&client_data.origin) {
+        return Err(invalid(format!(
+            "Origin {} is not allowed",
+            client_data.origin
+        )));
+    }
+    if client_data.cross_origin {
+        return Err(invalid("Cross-origin requests are not allowed"));
+    }
+
+    Ok(Sha256::digest(client_data_json).to_vec())
+}
+
+fn verify_authenticator_flags(
+    config: &WebAuthnConfig,
+    auth_data: &AuthenticatorData,
+    require_user_verification: bool,
+) -> Result<()> {
+    if auth_data.rp_id_hash.as_slice() != Sha256::digest(config.rp_id.as_bytes()).as_slice() {
+        return Err(invalid("Credential is scoped to a different site"));
+    }
+    if !auth_data.user_present() {
+        return Err(invalid("Authenticator did not confirm user presence"));
+    }
+    if require_user_verification && !auth_data.user_verified() {
+        return Err(invalid("Authenticator did not verify the user"));
+    }
+    Ok(())
+}
+
+/// Check the attestation statement as far as the policy asks for
+fn verify_attestation(
+    policy: AttestationPolicy,
+    format: &str,
+    statement: &Cbor,
+    auth_data: &[u8],
+    client_data_hash: &[u8],
+    key: &CosePublicKey,
+) -> Result<()> {
+    if policy == AttestationPolicy::None {
+        return Ok(());
+    }
+
+    let verified = match format {
+        "none" => {
+            if policy == AttestationPolicy::Direct {
+                return Err(invalid("Authenticator did not provide attestation"));
+            }
+            true
+        }
+        // Self attestation: signed with the new credential's own key
+        "packed" if statement.get_text("x5c").is_none() => {
+            let alg = statement.get_text("alg").and_then(Cbor::as_int);
+            let sig = statement
+                .get_text("sig")
+                .and_then(Cbor::as_bytes)
+                .ok_or_else(|| invalid("Attestation statement has no signature"))?;
+
+            if alg != Some(i128::from(key.algorithm())) {
+                return Err(invalid(
+                    "Attestation algorithm does not match the credential key",
+                ));
+            }
+            if !key.verify(&[auth_data, client_data_hash].concat(), sig) {
+                return Err(invalid("Attestation signature is invalid"));
+            }
+            true
+        }
+        // Certificate-based statements need vendor trust anchors we don't carry
+        _ => false,
+    };
+
+    if !verified && policy == AttestationPolicy::Direct {
+        return Err(invalid(format!(
+            "Attestation format '{}' can't be verified",
+            format
+        )));
+    }
+    Ok(())
+}
+
+/// Verify the response to a registration ceremony
+pub fn verify_registration(
+    config: &WebAuthnConfig,
+    expected_challenge: &str,
+    credential: &RegistrationCredential,
+) -> Result<VerifiedRegistration> {
+    let client_data_json =
+        decode_base64url(&credential.response.client_data_json, "clientDataJSON")?;
+    let client_data_hash = verify_client_data(
+        config,
+        &client_data_json,
+        "webauthn.create",
+        expected_challenge,
+    )?;
+
+    let attestation_object = Cbor::decode(&decode_base64url(
+        &credential.response.attestation_object,
+        "attestationObject",
+    )?)?;
+    let format = attestation_object
+        .get_text("fmt")
+        .and_then(Cbor::as_text)
+        .ok_or_else(|| invalid("Attestation object has no format"))?;
+    let statement = attestation_object
+        .get_text("attStmt")
+        .ok_or_else(|| invalid("Attestation object has no statement"))?;
+    let raw_auth_data = attestation_object
+        .get_text("authData")
+        .and_then(Cbor::as_bytes)
+        .ok_or_else(|| invalid("Attestation object has no authenticator data"))?;
+
+    let auth_data = AuthenticatorData::parse(raw_auth_data)?;
+    verify_authenticator_flags(
+        config,
+        &auth_data,
+        config.user_verification == UserVerificationPolicy::Required,
+    )?;
+
+    let attested = auth_data
+        .attested_credential
+        .as_ref()
+        .ok_or_else(|| invalid("Authenticator data has no credential"))?;
+    let credential_id = URL_SAFE_NO_PAD.encode(&attested.credential_id);
+    if credential_id != credential.id.trim_end_matches('=') {
+        return Err(invalid("Credential ID does not match authenticator data"));
+    }
+
+    let key = CosePublicKey::parse(&attested.public_key)?;
+    verify_attestation(
+        config.attestation,
+        format,
+        statement,
+        raw_auth_data,
+        &client_data_hash,
+        &key,
+    )?;
+
+    Ok(VerifiedRegistration {
+        credential_id,
+        public_key: attested.public_key.clone(),
+        algorithm: key.algorithm(),
+        sign_count: auth_data.sign_count,
+        aaguid: Some(Uuid::from_bytes(attested.aaguid)).filter(|aaguid| !aaguid.is_nil()),
+        attestation_format: format.to_string(),
+        transports: credential
+            .response
+            .transports
+            .iter()
+            .filter(|transport| KNOWN_TRANSPORTS.contains(&transport.as_str()))
+            .cloned()
+            .collect(),
+    })
+}
+
+/// Verify the response to an authentication ceremony against a stored key
+pub fn verify_assertion(
+    config: &WebAuthnConfig,
+    expected_challenge: &str,
+    credential: &AssertionCredential,
+    public_key: &[u8],
+    stored_sign_count: i64,
+    require_user_verification: bool,
+) -> Result<VerifiedAssertion> {
+    let client_data_json =
+        decode_base64url(&credential.response.client_data_json, "clientDataJSON")?;
+    let client_data_hash = verify_client_data(
+        config,
+        &client_data_json,
+        "webauthn.get",
+        expected_challenge,
+    )?;
+
+    let raw_auth_data =
+        decode_base64url(&credential.response.authenticator_data, "authenticatorData")?;
+    let auth_data = AuthenticatorData::parse(&raw_auth_data)?;
+    verify_authenticator_flags(config, &auth_data, require_user_verification)?;
+
+    let key = CosePublicKey::parse(public_key)?;
+    let sig = decode_base64url(&credential.response.signature, "signature")?;
+    if !key.verify(
+        &[raw_auth_data.as_slice(), &client_data_hash].concat(),
+        &sig,
+    ) {
+        return Err(invalid("Assertion signature is invalid"));
+    }
+
+    // Authenticators without a counter always report zero. Otherwise the
+    // counter has to move forward, or the key may have been cloned.
+    if (auth_data.sign_count != 0 || stored_sign_count != 0)
+        && i64::from(auth_data.sign_count) <= stored_sign_count
+    {
+        return Err(invalid(
+            "Signature counter did not increase; the authenticator may have been cloned",
+        ));
+    }
+
+    Ok(VerifiedAssertion {
+        sign_count: auth_data.sign_count,
+        user_verified: auth_data.user_verified(),
+    })
+}
+
+// ===== Ceremony options =====
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserEntity {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialParameters {
+    #[serde(rename = "type")]
+    pub credential_type: &'static str,
+    pub alg: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialDescriptor {
+    #[serde(rename = "type")]
+    pub credential_type: &'static str,
+    pub id: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub transports: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    pub resident_key: &'static str,
+    pub require_resident_key: bool,
+    pub user_verification: &'static str,
+}
+
+/// `publicKey` options for `navigator.credentials.create()`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialCreationOptions {
+    pub rp: RelyingParty,
+    pub user: UserEntity,
+    pub challenge: String,
+    pub pub_key_cred_params: Vec<CredentialParameters>,
+    pub timeout: u64,
+    pub attestation: &'static str,
+    pub exclude_credentials: Vec<CredentialDescriptor>,
+    pub authenticator_selection: AuthenticatorSelection,
+}
+
+/// `publicKey` options for `navigator.credentials.get()`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialRequestOptions {
+    pub challenge: String,
+    pub timeout: u64,
+    pub rp_id: String,
+    pub allow_credentials: Vec<CredentialDescriptor>,
+    pub user_verification: &'static str,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistrationChallenge {
+    pub challenge_id: Uuid,
+    pub public_key: CredentialCreationOptions,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthenticationChallenge {
+    pub challenge_id: Uuid,
+    pub public_key: CredentialRequestOptions,
+}
+
+// ===== Storage =====
+
+/// A registered passkey or security key
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct WebAuthnCredential {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub user_id: Uuid,
+    pub credential_id: String,
+    #[serde(skip_serializing)]
+    pub public_key: Vec<u8>,
+    pub algorithm: i32,
+    #[serde(skip_serializing)]
+    pub sign_count: i64,
+    pub transports: Vec<String>,
+    pub aaguid: Option<Uuid>,
+    pub attestation_format: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl WebAuthnCredential {
+    fn descriptor(&self) -> CredentialDescriptor {
+        CredentialDescriptor {
+            credential_type: "public-key",
+            id: self.credential_id.clone(),
+            transports: self.transports.clone(),
+        }
+    }
+}
+
+fn credential_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_CREDENTIAL_NAME_LENGTH {
+        return Err(AppError::InvalidFieldValue {
+            field: "name".to_string(),
+            message: format!(
+                "Name must be between 1 and {} characters",
+                MAX_CREDENTIAL_NAME_LENGTH
+            ),
+        });
+    }
+    Ok(name.to_string())
+}
+
+/// WebAuthn ceremonies and credential storage
+#[derive(Clone)]
+pub struct WebAuthnService {
+    db_pool: PgPool,
+    config: WebAuthnConfig,
+}
+
+impl WebAuthnService {
+    pub fn new(db_pool: PgPool, config: WebAuthnConfig) -> Self {
+        Self { db_pool, config }
+    }
+
+    pub fn config(&self) -> &WebAuthnConfig {
+        &self.config
+    }
+
+    fn timeout_ms(&self) -> u64 {
+        self.config.challenge_ttl.as_millis() as u64
+    }
+
+    /// Begin registering a new credential for a user
+    pub async fn start_registration(
+        &self,
+        user_id: Uuid,
+        email: &str,
+    ) -> Result<RegistrationChallenge> {
+        let existing = self.list_credentials(user_id).await?;
+        let (challenge_id, challenge) = self.issue_challenge(Some(user_id), "registration").await?;
+
+        Ok(RegistrationChallenge {
+            challenge_id,
+            public_key: CredentialCreationOptions {
+                rp: RelyingParty {
+                    id: self.config.rp_id.clone(),
+                    name: self.config.rp_name.clone(),
+                },
+                user: UserEntity {
+                    id: user_handle(user_id),
+                    name: email.to_string(),
+                    display_name: email.to_string(),
+                },
+                challenge,
+                pub_key_cred_params: SUPPORTED_ALGORITHMS
+                    .iter()
+                    .map(|alg| CredentialParameters {
+                        credential_type: "public-key",
+                        alg: *alg,
+                    })
+                    .collect(),
+                timeout: self.timeout_ms(),
+                attestation: self.config.attestation.conveyance(),
+                exclude_credentials: existing
+                    .iter()
+                    .map(WebAuthnCredential::descriptor)
+                    .collect(),
+                authenticator_selection: AuthenticatorSelection {
+                    resident_key: "preferred",
+                    require_resident_key: false,
+                    user_verification: self.config.user_verification.as_str(),
+                },
+            },
+        })
+    }
+
+    /// Verify a registration response and store the new credential
+    pub async fn finish_registration(
+        &self,
+        user_id: Uuid,
+        challenge_id: Uuid,
+        name: Option<&str>,
+        credential: &RegistrationCredential,
+    ) -> Result<WebAuthnCredential> {
+        let name = credential_name(name.unwrap_or(DEFAULT_CREDENTIAL_NAME))?;
+
+        let (challenge_user, challenge) = self.take_challenge(challenge_id, "registration").await?;
+        if challenge_user != Some(user_id) {
+            return Err(invalid("Challenge was issued to a different account"));
+        }
+
+        let verified = verify_registration(&self.config, &challenge, credential)?;
+
+        sqlx::query_as::<_, WebAuthnCredential>(&format!(
+            r#"
+            INSERT INTO webauthn_credentials
+                (user_id, credential_id, public_key, algorithm, sign_count, transports,
+                 aaguid, attestation_format, name)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (credential_id) DO NOTHING
+            RETURNING {}
+            "#,
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(&verified.credential_id)
+        .bind(&verified.public_key)
+        .bind(verified.algorithm as i32)
+        .bind(i64::from(verified.sign_count))
+        .bind(&verified.transports)
+        .bind(verified.aaguid)
+        .bind(&verified.attestation_format)
+        .bind(&name)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| AppError::Conflict {
+            message: "This credential is already registered".to_string(),
+        })
+    }
+
+    /// Begin an authentication ceremony. With a user only their credentials
+    /// are allowed; without one the authenticator offers a discoverable
+    /// passkey.
+    pub async fn start_authentication(
+        &self,
+        user_id: Option<Uuid>,
+        user_verification: UserVerificationPolicy,
+    ) -> Result<AuthenticationChallenge> {
+        let allow_credentials = match user_id {
+            Some(user_id) => self
+                .list_credentials(user_id)
+                .await?
+                .iter()
+                .map(WebAuthnCredential::descriptor)
+                .collect(),
+            None => Vec::new(),
+        };
+        let (challenge_id, challenge) = self.issue_challenge(user_id, "authentication").await?;
+
+        Ok(AuthenticationChallenge {
+            challenge_id,
+            public_key: CredentialRequestOptions {
+                challenge,
+                timeout: self.timeout_ms(),
+                rp_id: self.config.rp_id.clone(),
+                allow_credentials,
+                user_verification: user_verification.as_str(),
+            },
+        })
+    }
+
+    /// Verify an authentication response and advance the credential's
+    /// signature counter. Returns the credential that signed.
+    pub async fn finish_authentication(
+        &self,
+        challenge_id: Uuid,
+        credential: &AssertionCredential,
+        require_user_verification: bool,
+    ) -> Result<WebAuthnCredential> {
+        let (challenge_user, challenge) =
+            self.take_challenge(challenge_id, "authentication").await?;
+
+        let stored = sqlx::query_as::<_, WebAuthnCredential>(&format!(
+            "SELECT {} FROM webauthn_credentials WHERE credential_id = $1",
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(credential.id.trim_end_matches('='))
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| invalid("Credential is not registered"))?;
+
+        if let Some(user_id) = challenge_user {
+            if user_id != stored.user_id {
+                return Err(invalid("Credential belongs to a different account"));
+            }
+        }
+        if let Some(handle) = credential.response.user_handle.as_deref() {
+            if !handle.is_empty() && handle.trim_end_matches('=') != user_handle(stored.user_id) {
+                return Err(invalid("User handle does not match the credential"));
+            }
+        }
+
+        let verified = verify_assertion(
+            &self.config,
+            &challenge,
+            credential,
+            &stored.public_key,
+            stored.sign_count,
+            require_user_verification,
+        )?;
+
+        // Conditional so that two assertions racing with the same counter
+        // can't both be accepted
+        sqlx::query_as::<_, WebAuthnCredential>(&format!(
+            r#"
+            UPDATE webauthn_credentials SET sign_count = $2, last_used_at = NOW()
+            WHERE id = $1 AND (sign_count < $2 OR $2 = 0)
+            RETURNING {}
+            "#,
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(stored.id)
+        .bind(i64::from(verified.sign_count))
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| invalid("Signature counter did not increase"))
+    }
+
+    /// A user's credentials, oldest first
+    pub async fn list_credentials(&self, user_id: Uuid) -> Result<Vec<WebAuthnCredential>> {
+        Ok(sqlx::query_as::<_, WebAuthnCredential>(&format!(
+            "SELECT {} FROM webauthn_credentials WHERE user_id = $1 ORDER BY created_at",
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?)
+    }
+
+    pub async fn rename_credential(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        name: &str,
+    ) -> Result<WebAuthnCredential> {
+        let name = credential_name(name)?;
+
+        sqlx::query_as::<_, WebAuthnCredential>(&format!(
+            "UPDATE webauthn_credentials SET name = $3 WHERE id = $1 AND user_id = $2 RETURNING {}",
+            CREDENTIAL_COLUMNS
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(&name)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound {
+            resource: "WebAuthn credential".to_string(),
+        })
+    }
+
+    pub async fn delete_credential(&self, user_id: Uuid, id: Uuid) -> Result<()> {
+        let deleted =
+            sqlx::query("DELETE FROM webauthn_credentials WHERE id = $1 AND user_id = $2")
+                .bind(id)
+                .bind(user_id)
+                .execute(&self.db_pool)
+                .await?
+                .rows_affected();
+
+        if deleted == 0 {
+            return Err(AppError::NotFound {
+                resource: "WebAuthn credential".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn issue_challenge(
+        &self,
+        user_id: Option<Uuid>,
+        ceremony: &str,
+    ) -> Result<(Uuid, String)> {
+        let mut bytes = [0u8; CHALLENGE_BYTES];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let challenge = URL_SAFE_NO_PAD.encode(bytes);
+        let expires_at = Utc::now()
+            + chrono::Duration::from_std(self.config.challenge_ttl)
+                .unwrap_or_else(|_| chrono::Duration::minutes(5));
+
+        // Clear out challenges nobody came back for
+        sqlx::query("DELETE FROM webauthn_challenges WHERE expires_at < NOW()")
+            .execute(&self.db_pool)
+            .await?;
+
+        let challenge_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO webauthn_challenges (user_id, ceremony, challenge, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(user_id)
+        .bind(ceremony)
+        .bind(&challenge)
+        .bind(expires_at)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok((challenge_id, challenge))
+    }
+
+    /// Consume a challenge. It is gone afterwards whether or not the
+    /// ceremony succeeds, so a response can't be retried or replayed.
+    async fn take_challenge(
+        &self,
+        challenge_id: Uuid,
+        ceremony: &str,
+    ) -> Result<(Option<Uuid>, String)> {
+        let row: Option<(Option<Uuid>, String, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            DELETE FROM webauthn_challenges WHERE id = $1 AND ceremony = $2
+            RETURNING user_id, challenge, expires_at
+            "#,
+        )
+        .bind(challenge_id)
+        .bind(ceremony)
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        match row {
+            Some((user_id, challenge, expires_at)) if expires_at > Utc::now() => {
+                Ok((user_id, challenge))
+            }
+            _ => Err(invalid("Challenge is unknown or has expired")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndith_core::models::webauthn::{
+        AuthenticatorAssertionResponse, AuthenticatorAttestationResponse,
+    };
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+
+    const ORIGIN: &str = "https://nodrakeinthe.house";
+    const CHALLENGE: &str = "dGVzdC1jaGFsbGVuZ2UtYnl0ZXMtMDEyMzQ1Njc4OWFi";
+
+    // Minimal CBOR encoding for building authenticator responses
+    fn cbor_head(major: u8, len: usize) -> Vec<u8> {
+        match len {
+            0..=23 => vec![(major << 5) | len as u8],
+            24..=255 => vec![(major << 5) | 24, len as u8],
+            _ => vec![(major << 5) | 25, (len >> 8) as u8, len as u8],
+        }
+    }
+
+    fn cbor_int(value: i64) -> Vec<u8> {
+        if value >= 0 {
+            cbor_head(0, value as usize)
+        } else {
+            cbor_head(1, (-1 - value) as usize)
+        }
+    }
+
+    fn cbor_bytes(bytes: &[u8]) -> Vec<u8> {
+        [cbor_head(2, bytes.len()), bytes.to_vec()].concat()
+    }
+
+    fn cbor_text(text: &str) -> Vec<u8> {
+        [cbor_head(3, text.len()), text.as_bytes().to_vec()].concat()
+    }
+
+    fn cbor_map(entries: &[(Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+        let mut out = cbor_head(5, entries.len());
+        for (key, value) in entries {
+            out.extend(key);
+            out.extend(value);
+        }
+        out
+    }
+
+    struct Authenticator {
+        key_pair: EcdsaKeyPair,
+        credential_id: Vec<u8>,
+        rng: SystemRandom,
+    }
+
+    impl Authenticator {
+        fn new() -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 =
+                EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+            let key_pair =
+                EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+                    .unwrap();
+            Self {
+                key_pair,
+                credential_id: vec![7; 16],
+                rng,
+            }
+        }
+
+        fn cose_key(&self) -> Vec<u8> {
+            let point = self.key_pair.public_key().as_ref();
+            cbor_map(&[
+                (cbor_int(1), cbor_int(2)),
+                (cbor_int(3), cbor_int(COSE_ALG_ES256)),
+                (cbor_int(-1), cbor_int(1)),
+                (cbor_int(-2), cbor_bytes(&point[1..33])),
+                (cbor_int(-3), cbor_bytes(&point[33..65])),
+            ])
+        }
+
+        fn auth_data(&self, flags: u8, sign_count: u32, attested: bool) -> Vec<u8> {
+            let mut data = Sha256::digest("nodrakeinthe.house".as_bytes()).to_vec();
+            data.push(flags);
+            data.extend(sign_count.to_be_bytes());
+            if attested {
+                data.extend([0u8; 16]);
+                data.extend((self.credential_id.len() as u16).to_be_bytes());
+                data.extend(&self.credential_id);
+                data.extend(self.cose_key());
+            }
+            data
+        }
+
+        fn sign(&self, auth_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+            let message = [auth_data, &Sha256::digest(client_data_json)].concat();
+            self.key_pair
+                .sign(&self.rng, &message)
+                .unwrap()
+                .as_ref()
+                .to_vec()
+        }
+
+        fn client_data(ceremony: &str, origin: &str) -> Vec<u8> {
+            serde_json::to_vec(&serde_json::json!({
+                "type": ceremony,
+                "challenge": CHALLENGE,
+                "origin": origin,
+            }))
+            .unwrap()
+        }
+
+        fn register(&self, self_attest: bool) -> RegistrationCredential {
+            let client_data = Self::client_data("webauthn.create", ORIGIN);
+            let auth_data = self.auth_data(FLAG_USER_PRESENT | FLAG_ATTESTED_CREDENTIAL, 0, true);
+            let (fmt, statement) = if self_attest {
+                let sig = self.sign(&auth_data, &client_data);
+                (
+                    "packed",
+                    cbor_map(&[
+                        (cbor_text("alg"), cbor_int(COSE_ALG_ES256)),
+                        (cbor_text("sig"), cbor_bytes(&sig)),
+                    ]),
+                )
+            } else {
+                ("none", cbor_map(&[]))
+            };
+            let attestation_object = cbor_map(&[
+                (cbor_text("fmt"), cbor_text(fmt)),
+                (cbor_text("attStmt"), statement),
+                (cbor_text("authData"), cbor_bytes(&auth_data)),
+            ]);
+
+            RegistrationCredential {
+                id: URL_SAFE_NO_PAD.encode(&self.credential_id),
+                response: AuthenticatorAttestationResponse {
+                    client_data_json: URL_SAFE_NO_PAD.encode(&client_data),
+                    attestation_object: URL_SAFE_NO_PAD.encode(attestation_object),
+                    transports: vec!["usb".to_string(), "bogus".to_string()],
+                },
+            }
+        }
+
+        fn assert(&self, flags: u8, sign_count: u32) -> AssertionCredential {
+            let client_data = Self::client_data("webauthn.get", ORIGIN);
+            let auth_data = self.auth_data(flags, sign_count, false);
+            let signature = self.sign(&auth_data, &client_data);
+
+            AssertionCredential {
+                id: URL_SAFE_NO_PAD.encode(&self.credential_id),
+                response: AuthenticatorAssertionResponse {
+                    client_data_json: URL_SAFE_NO_PAD.encode(&client_data),
+                    authenticator_data: URL_SAFE_NO_PAD.encode(auth_data),
+                    signature: URL_SAFE_NO_PAD.encode(signature),
+                    user_handle: None,
+                },
+            }
+        }
+    }
+
+    fn config(attestation: AttestationPolicy) -> WebAuthnConfig {
+        WebAuthnConfig {
+            attestation,
+            ..WebAuthnConfig::for_origin(ORIGIN)
+        }
+    }
+
+    #[test]
+    fn cbor_reads_nested_values_and_rejects_truncation() {
+        let encoded = cbor_map(&[
+            (cbor_text("a"), cbor_int(-257)),
+            (cbor_int(3), cbor_bytes(&[1, 2, 3])),
+        ]);
+        let value = Cbor::decode(&encoded).unwrap();
+        assert_eq!(value.get_text("a").and_then(Cbor::as_int), Some(-257));
+        assert_eq!(
+            value.get_int(3).and_then(Cbor::as_bytes),
+            Some(&[1u8, 2, 3][..])
+        );
+
+        assert!(Cbor::decode(&encoded[..encoded.len() - 1]).is_err());
+        // Claims a 65535-byte string with nothing behind it
+        assert!(Cbor::decode(&[0x59, 0xff, 0xff]).is_err());
+    }
+
+    #[test]
+    fn registers_and_asserts_with_none_attestation() {
+        let authenticator = Authenticator::new();
+        let config = config(AttestationPolicy::None);
+
+        let registration =
+            verify_registration(&config, CHALLENGE, &authenticator.register(false)).unwrap();
+        assert_eq!(registration.algorithm, COSE_ALG_ES256);
+        assert_eq!(registration.attestation_format, "none");
+        assert_eq!(registration.transports, vec!["usb"]);
+        assert!(registration.aaguid.is_none());
+
+        let assertion = authenticator.assert(FLAG_USER_PRESENT, 5);
+        let verified = verify_assertion(
+            &config,
+            CHALLENGE,
+            &assertion,
+            &registration.public_key,
+            0,
+            false,
+        )
+        .unwrap();
+        assert_eq!(verified.sign_count, 5);
+        assert!(!verified.user_verified);
+
+        // Same counter again looks like a cloned authenticator
+        assert!(verify_assertion(
+            &config,
+            CHALLENGE,
+            &assertion,
+            &registration.public_key,
+            5,
+            false
+        )
+        .is_err());
+        // User verification required but not performed
+        assert!(verify_assertion(
+            &config,
+            CHALLENGE,
+            &assertion,
+            &registration.public_key,
+            0,
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn direct_attestation_requires_a_verifiable_statement() {
+        let authenticator = Authenticator::new();
+        let config = config(AttestationPolicy::Direct);
+
+        assert!(verify_registration(&config, CHALLENGE, &authenticator.register(false)).is_err());
+        let registration =
+            verify_registration(&config, CHALLENGE, &authenticator.register(true)).unwrap();
+        assert_eq!(registration.attestation_format, "packed");
+    }
+
+    #[test]
+    fn rejects_wrong_origin_challenge_and_signature() {
+        let authenticator = Authenticator::new();
+        let config = config(AttestationPolicy::None);
+        let registration =
+            verify_registration(&config, CHALLENGE, &authenticator.register(false)).unwrap();
+
+        let other_origin = WebAuthnConfig {
+            origins: vec!["https://evil.example".to_string()],
+            ..config.clone()
+        };
+        assert!(
+            verify_registration(&other_origin, CHALLENGE, &authenticator.register(false)).is_err()
+        );
+        assert!(verify_registration(&config, "b3RoZXI", &authenticator.register(false)).is_err());
+
+        let mut tampered = authenticator.assert(FLAG_USER_PRESENT, 1);
+        tampered.response.authenticator_data =
+            URL_SAFE_NO_PAD.encode(authenticator.auth_data(FLAG_USER_PRESENT, 2, false));
+        assert!(verify_assertion(
+            &config,
+            CHALLENGE,
+            &tampered,
+            &registration.public_key,
+            0,
+            false
+        )
+        .is_err());
+    }
+}
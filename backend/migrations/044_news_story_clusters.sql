@@ -0,0 +1,62 @@
+-- Story clustering for near-duplicate news articles
+-- Syndicated copies, reposts and mirrors of the same report are grouped into
+-- a single story with one primary source, so evidence is counted once per story.
+
+CREATE TABLE IF NOT EXISTS news_stories (
+    id UUID PRIMARY KEY,
+    primary_article_id UUID REFERENCES news_articles(id) ON DELETE SET NULL,
+    article_count INTEGER NOT NULL DEFAULT 1,
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE news_articles
+ADD COLUMN IF NOT EXISTS story_id UUID REFERENCES news_stories(id) ON DELETE SET NULL;
+
+ALTER TABLE news_articles
+ADD COLUMN IF NOT EXISTS is_story_primary BOOLEAN NOT NULL DEFAULT TRUE;
+
+ALTER TABLE offense_evidence
+ADD COLUMN IF NOT EXISTS story_id UUID REFERENCES news_stories(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_news_articles_story
+    ON news_articles(story_id)
+    WHERE story_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_offense_evidence_story
+    ON offense_evidence(offense_id, story_id)
+    WHERE story_id IS NOT NULL;
+
+-- Evidence score counts each story once per offense (using its most credible
+-- copy); evidence without a story counts individually as before.
+CREATE OR REPLACE FUNCTION calculate_evidence_score(p_artist_id UUID)
+RETURNS FLOAT AS $$
+DECLARE
+    avg_credibility FLOAT;
+    verified_count INTEGER;
+    total_count INTEGER;
+BEGIN
+    SELECT
+        COALESCE(AVG(e.credibility_score), 0),
+        COUNT(*) FILTER (WHERE o.status = 'verified'),
+        COUNT(*)
+    INTO avg_credibility, verified_count, total_count
+    FROM artist_offenses o
+    LEFT JOIN (
+        SELECT offense_id, MAX(credibility_score) AS credibility_score
+        FROM offense_evidence
+        GROUP BY offense_id, COALESCE(story_id, id)
+    ) e ON e.offense_id = o.id
+    WHERE o.artist_id = p_artist_id;
+
+    IF total_count = 0 THEN
+        RETURN 0;
+    END IF;
+
+    RETURN (avg_credibility / 5.0) * (0.5 + 0.5 * (verified_count::FLOAT / GREATEST(total_count, 1)));
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON COLUMN news_articles.story_id IS 'Story cluster this article belongs to; near-duplicates share a story';
+COMMENT ON COLUMN offense_evidence.story_id IS 'Story cluster of the evidence article; evidence is scored once per story';
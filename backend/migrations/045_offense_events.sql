@@ -0,0 +1,118 @@
+-- Offense legal-status timeline
+-- Replaces the single incident_date + arrested/charged/convicted/settled flags
+-- with a dated event history, so "charged 2021, acquitted 2023" and appeals
+-- can be represented. The legacy flags and severity are kept in sync with the
+-- latest verified events by the offense service.
+
+-- Lifecycle event types
+CREATE TYPE offense_event_type AS ENUM (
+    'allegation',
+    'arrest',
+    'charge',
+    'plea',
+    'conviction',
+    'acquittal',
+    'dismissal',
+    'settlement',
+    'appeal',
+    'apology'
+);
+
+-- Severity as originally reported, before timeline adjustments
+-- (e.g. an acquittal lowers severity, a conviction raises it)
+ALTER TABLE artist_offenses
+ADD COLUMN IF NOT EXISTS reported_severity offense_severity;
+
+UPDATE artist_offenses SET reported_severity = severity WHERE reported_severity IS NULL;
+
+ALTER TABLE artist_offenses
+ALTER COLUMN reported_severity SET NOT NULL,
+ALTER COLUMN reported_severity SET DEFAULT 'moderate';
+
+CREATE TABLE offense_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    offense_id UUID NOT NULL REFERENCES artist_offenses(id) ON DELETE CASCADE,
+
+    -- What happened and when
+    event_type offense_event_type NOT NULL,
+    event_date DATE,
+    event_date_approximate BOOLEAN NOT NULL DEFAULT FALSE,
+    description TEXT,
+
+    -- Supporting evidence
+    evidence_id UUID REFERENCES offense_evidence(id) ON DELETE SET NULL,
+    source_url TEXT,
+
+    -- Review: only verified events drive legal status, severity and recency
+    status evidence_status NOT NULL DEFAULT 'pending',
+    source VARCHAR(50) NOT NULL DEFAULT 'manual',  -- 'manual', 'submission', 'news_pipeline', 'backfill'
+    confidence FLOAT,
+    reviewed_at TIMESTAMPTZ,
+    reviewed_by UUID REFERENCES users(id),
+
+    -- Metadata
+    submitted_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_offense_events_offense ON offense_events(offense_id, event_date);
+CREATE INDEX idx_offense_events_pending ON offense_events(created_at)
+    WHERE status = 'pending';
+
+-- Backfill events from the legacy flags, dated at the incident date
+INSERT INTO offense_events (offense_id, event_type, event_date, event_date_approximate, status, source)
+SELECT o.id, e.event_type::offense_event_type, o.incident_date,
+       COALESCE(o.incident_date_approximate, FALSE),
+       CASE WHEN o.status = 'verified' THEN 'verified'::evidence_status ELSE 'pending'::evidence_status END,
+       'backfill'
+FROM artist_offenses o
+CROSS JOIN LATERAL (
+    VALUES
+        ('allegation', TRUE),
+        ('arrest', COALESCE(o.arrested, FALSE)),
+        ('charge', COALESCE(o.charged, FALSE)),
+        ('conviction', COALESCE(o.convicted, FALSE)),
+        ('settlement', COALESCE(o.settled, FALSE))
+) AS e(event_type, applies)
+WHERE e.applies;
+
+-- Recency follows the timeline: an offense is as recent as its latest verified
+-- or pending inculpatory event (acquittals, dismissals and apologies don't make
+-- an offense "newer"), falling back to the incident date.
+CREATE OR REPLACE FUNCTION calculate_recency_score(p_artist_id UUID)
+RETURNS FLOAT AS $$
+DECLARE
+    most_recent DATE;
+    years_ago FLOAT;
+BEGIN
+    SELECT MAX(COALESCE(ev.latest_event, o.incident_date)) INTO most_recent
+    FROM artist_offenses o
+    LEFT JOIN LATERAL (
+        SELECT MAX(e.event_date) AS latest_event
+        FROM offense_events e
+        WHERE e.offense_id = o.id
+        AND e.status IN ('verified', 'pending')
+        AND e.event_type NOT IN ('acquittal', 'dismissal', 'apology')
+    ) ev ON TRUE
+    WHERE o.artist_id = p_artist_id
+    AND o.status IN ('verified', 'pending');
+
+    IF most_recent IS NULL THEN
+        RETURN 0;
+    END IF;
+
+    years_ago := (CURRENT_DATE - most_recent)::FLOAT / 365.25;
+
+    -- Decay: 1.0 if <1yr, 0.75 if <3yr, 0.5 if <5yr, 0.25 if <10yr, 0.1 if older
+    RETURN CASE
+        WHEN years_ago < 1 THEN 1.0
+        WHEN years_ago < 3 THEN 0.75
+        WHEN years_ago < 5 THEN 0.5
+        WHEN years_ago < 10 THEN 0.25
+        ELSE 0.1
+    END;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON TABLE offense_events IS 'Dated legal lifecycle events for an offense; verified events drive legal status and severity';
+COMMENT ON COLUMN artist_offenses.reported_severity IS 'Severity as originally reported; severity is derived from this and the event timeline';
@@ -0,0 +1,99 @@
+-- Evidence link health checking and snapshot archiving
+-- A background job periodically re-fetches every evidence URL, records the
+-- HTTP status, redirect chain and content hash, and keeps a local text
+-- snapshot so excerpts stay verifiable after the source rots.
+
+-- Latest link health on the evidence row itself
+ALTER TABLE offense_evidence
+ADD COLUMN IF NOT EXISTS link_status VARCHAR(20) NOT NULL DEFAULT 'unchecked',  -- 'unchecked', 'ok', 'redirected', 'changed', 'dead', 'error'
+ADD COLUMN IF NOT EXISTS http_status INTEGER,
+ADD COLUMN IF NOT EXISTS final_url TEXT,
+ADD COLUMN IF NOT EXISTS content_hash VARCHAR(64),
+ADD COLUMN IF NOT EXISTS last_checked_at TIMESTAMPTZ,
+ADD COLUMN IF NOT EXISTS consecutive_failures INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS needs_rereview BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS idx_offense_evidence_last_checked
+    ON offense_evidence(last_checked_at NULLS FIRST);
+
+CREATE INDEX IF NOT EXISTS idx_offense_evidence_rereview
+    ON offense_evidence(offense_id)
+    WHERE needs_rereview = TRUE;
+
+-- History of every check
+CREATE TABLE IF NOT EXISTS evidence_link_checks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    evidence_id UUID NOT NULL REFERENCES offense_evidence(id) ON DELETE CASCADE,
+    link_status VARCHAR(20) NOT NULL,
+    http_status INTEGER,
+    final_url TEXT,
+    redirect_chain JSONB NOT NULL DEFAULT '[]'::jsonb,
+    content_hash VARCHAR(64),
+    error TEXT,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_evidence_link_checks_evidence
+    ON evidence_link_checks(evidence_id, checked_at DESC);
+
+-- Extracted text snapshots, one per distinct content version
+CREATE TABLE IF NOT EXISTS evidence_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    evidence_id UUID NOT NULL REFERENCES offense_evidence(id) ON DELETE CASCADE,
+    content_hash VARCHAR(64) NOT NULL,
+    url TEXT NOT NULL,
+    title TEXT,
+    text_content TEXT NOT NULL,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_evidence_snapshot UNIQUE (evidence_id, content_hash)
+);
+
+-- Share of an artist's checked evidence that is still verifiable (0-1)
+ALTER TABLE artist_research_quality
+ADD COLUMN IF NOT EXISTS link_health_score DECIMAL(4,3);
+
+-- Evidence score counts each story once per offense (using its most credible
+-- copy). Credibility is discounted for links that have died without a local
+-- snapshot (half) or whose content changed since it was reviewed (three
+-- quarters).
+CREATE OR REPLACE FUNCTION calculate_evidence_score(p_artist_id UUID)
+RETURNS FLOAT AS $$
+DECLARE
+    avg_credibility FLOAT;
+    verified_count INTEGER;
+    total_count INTEGER;
+BEGIN
+    SELECT
+        COALESCE(AVG(e.credibility_score), 0),
+        COUNT(*) FILTER (WHERE o.status = 'verified'),
+        COUNT(*)
+    INTO avg_credibility, verified_count, total_count
+    FROM artist_offenses o
+    LEFT JOIN (
+        SELECT ev.offense_id,
+               MAX(
+                   ev.credibility_score * CASE
+                       WHEN ev.link_status = 'dead' AND NOT EXISTS (
+                           SELECT 1 FROM evidence_snapshots s WHERE s.evidence_id = ev.id
+                       ) THEN 0.5
+                       WHEN ev.link_status = 'changed' THEN 0.75
+                       ELSE 1.0
+                   END
+               ) AS credibility_score
+        FROM offense_evidence ev
+        GROUP BY ev.offense_id, COALESCE(ev.story_id, ev.id)
+    ) e ON e.offense_id = o.id
+    WHERE o.artist_id = p_artist_id;
+
+    IF total_count = 0 THEN
+        RETURN 0;
+    END IF;
+
+    RETURN (avg_credibility / 5.0) * (0.5 + 0.5 * (verified_count::FLOAT / GREATEST(total_count, 1)));
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON COLUMN offense_evidence.link_status IS 'Result of the latest link health check';
+COMMENT ON COLUMN offense_evidence.needs_rereview IS 'Link died or its content changed since review; evidence should be re-reviewed';
+COMMENT ON TABLE evidence_snapshots IS 'Extracted text of evidence pages so excerpts stay verifiable after link rot';
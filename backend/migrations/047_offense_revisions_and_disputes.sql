@@ -0,0 +1,86 @@
+-- Offense and evidence edit history with a dispute/review workflow
+-- Every change to an offense or evidence row appends a full snapshot as a new
+-- revision, so who changed severity, category or description (and why) can be
+-- audited and diffed. Users can dispute an offense; a reviewer resolves the
+-- dispute with required notes.
+
+CREATE TABLE offense_revisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    offense_id UUID NOT NULL REFERENCES artist_offenses(id) ON DELETE CASCADE,
+    revision_number INTEGER NOT NULL,
+    change_type VARCHAR(30) NOT NULL,  -- 'created', 'updated', 'verified', 'disputed', 'dispute_resolved', 'timeline', 'backfill'
+    snapshot JSONB NOT NULL,
+    changed_by UUID REFERENCES users(id),
+    reviewer_notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_offense_revision UNIQUE (offense_id, revision_number)
+);
+
+CREATE TABLE evidence_revisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    evidence_id UUID NOT NULL REFERENCES offense_evidence(id) ON DELETE CASCADE,
+    revision_number INTEGER NOT NULL,
+    change_type VARCHAR(30) NOT NULL,  -- 'created', 'updated', 'backfill'
+    snapshot JSONB NOT NULL,
+    changed_by UUID REFERENCES users(id),
+    reviewer_notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_evidence_revision UNIQUE (evidence_id, revision_number)
+);
+
+-- Revisions are append-only
+CREATE OR REPLACE FUNCTION prevent_revision_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'Revisions are append-only and cannot be modified';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER offense_revisions_append_only
+    BEFORE UPDATE ON offense_revisions
+    FOR EACH ROW EXECUTE FUNCTION prevent_revision_update();
+
+CREATE TRIGGER evidence_revisions_append_only
+    BEFORE UPDATE ON evidence_revisions
+    FOR EACH ROW EXECUTE FUNCTION prevent_revision_update();
+
+-- User-submitted disputes
+CREATE TABLE offense_disputes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    offense_id UUID NOT NULL REFERENCES artist_offenses(id) ON DELETE CASCADE,
+    evidence_id UUID REFERENCES offense_evidence(id) ON DELETE SET NULL,
+    reason TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'open',  -- 'open', 'upheld', 'dismissed'
+    -- Offense status when the dispute was opened, restored if it is dismissed
+    previous_status evidence_status NOT NULL,
+    submitted_by UUID NOT NULL REFERENCES users(id),
+    resolution_notes TEXT,
+    resolved_by UUID REFERENCES users(id),
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_offense_revisions_offense ON offense_revisions(offense_id, revision_number DESC);
+CREATE INDEX idx_evidence_revisions_evidence ON evidence_revisions(evidence_id, revision_number DESC);
+CREATE INDEX idx_offense_disputes_offense ON offense_disputes(offense_id, created_at DESC);
+CREATE INDEX idx_offense_disputes_open ON offense_disputes(created_at) WHERE status = 'open';
+
+-- Only one open dispute per user per offense
+CREATE UNIQUE INDEX idx_offense_disputes_one_open
+    ON offense_disputes(offense_id, submitted_by)
+    WHERE status = 'open';
+
+-- Seed revision 1 for existing rows
+INSERT INTO offense_revisions (offense_id, revision_number, change_type, snapshot, changed_by)
+SELECT o.id, 1, 'backfill', to_jsonb(o), COALESCE(o.verified_by, o.submitted_by)
+FROM artist_offenses o;
+
+INSERT INTO evidence_revisions (evidence_id, revision_number, change_type, snapshot, changed_by)
+SELECT e.id, 1, 'backfill', to_jsonb(e), e.submitted_by
+FROM offense_evidence e;
+
+COMMENT ON TABLE offense_revisions IS 'Append-only full snapshots of artist_offenses rows after each change';
+COMMENT ON TABLE evidence_revisions IS 'Append-only full snapshots of offense_evidence rows after each change';
+COMMENT ON TABLE offense_disputes IS 'User disputes of offenses; an open dispute puts the offense in disputed status';
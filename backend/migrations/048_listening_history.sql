@@ -0,0 +1,63 @@
+-- Listening history ingestion
+-- Individual plays imported from provider data exports (Spotify extended
+-- streaming history, Apple Music play activity, Google Takeout, Last.fm) and
+-- synced from recently-played APIs. Plays are resolved to canonical artists
+-- and rolled up into user_artist_playcounts, replacing simulated numbers in
+-- revenue attribution.
+
+CREATE TABLE listening_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    platform VARCHAR(50) NOT NULL,
+    source VARCHAR(50) NOT NULL,  -- 'spotify_extended_history', 'apple_music_play_activity', 'youtube_takeout', 'lastfm_scrobbles', 'spotify_recently_played'
+
+    played_at TIMESTAMPTZ NOT NULL,
+    artist_name TEXT NOT NULL,
+    track_name TEXT NOT NULL DEFAULT '',
+    album_name TEXT,
+    ms_played BIGINT,
+
+    -- Set once the artist name is resolved; the play is then counted in
+    -- user_artist_playcounts exactly once
+    artist_id UUID REFERENCES artists(id) ON DELETE SET NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Re-importing overlapping exports must not double count
+    CONSTRAINT unique_listening_event UNIQUE (user_id, platform, played_at, artist_name, track_name)
+);
+
+CREATE INDEX idx_listening_history_user ON listening_history(user_id, played_at DESC);
+CREATE INDEX idx_listening_history_unresolved ON listening_history(user_id)
+    WHERE artist_id IS NULL;
+
+-- One row per import run
+CREATE TABLE listening_history_imports (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    source VARCHAR(50) NOT NULL,
+    platform VARCHAR(50) NOT NULL,
+    events_received INTEGER NOT NULL DEFAULT 0,
+    events_new INTEGER NOT NULL DEFAULT 0,
+    skipped_short INTEGER NOT NULL DEFAULT 0,
+    skipped_invalid INTEGER NOT NULL DEFAULT 0,
+    plays_attributed INTEGER NOT NULL DEFAULT 0,
+    playcount_periods_updated INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_listening_history_imports_user ON listening_history_imports(user_id, created_at DESC);
+
+-- Cursor for scheduled recently-played syncs
+CREATE TABLE listening_history_sync_state (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    platform VARCHAR(50) NOT NULL,
+    last_played_at TIMESTAMPTZ,
+    last_synced_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+
+    PRIMARY KEY (user_id, platform)
+);
+
+COMMENT ON TABLE listening_history IS 'Individual plays from imported exports and provider APIs; source of real playcounts';
+COMMENT ON TABLE listening_history_sync_state IS 'Latest play synced per user and platform from recently-played APIs';
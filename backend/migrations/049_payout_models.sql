@@ -0,0 +1,25 @@
+-- Payout model support
+-- Rates can now be defined per country, falling back to the default
+-- (country_code NULL) row when a market has no rate of its own. Amounts stay
+-- in USD. royalty_share is the portion of subscription revenue paid out to
+-- rights holders, used by the user-centric model to split a subscriber's
+-- monthly payment between the artists they played.
+
+ALTER TABLE platform_payout_rates ADD COLUMN country_code CHAR(2);
+ALTER TABLE platform_payout_rates ADD COLUMN royalty_share DECIMAL(5, 4) NOT NULL DEFAULT 0.7000;
+
+ALTER TABLE platform_payout_rates
+    ADD CONSTRAINT chk_payout_rate_royalty_share CHECK (royalty_share > 0 AND royalty_share <= 1);
+
+ALTER TABLE platform_payout_rates DROP CONSTRAINT unique_platform_rate;
+CREATE UNIQUE INDEX unique_platform_rate_country
+    ON platform_payout_rates(platform, rate_tier, COALESCE(country_code, ''), effective_date);
+
+CREATE INDEX idx_payout_rates_country
+    ON platform_payout_rates(platform, country_code, effective_date DESC);
+
+-- Country-specific Spotify rates (USD-converted estimates)
+INSERT INTO platform_payout_rates (platform, rate_per_stream, rate_per_minute, subscription_monthly, rate_tier, country_code, effective_date, source_url, notes) VALUES
+('spotify', 0.0045, NULL, 14.50, 'standard', 'GB', '2024-01-01', 'https://www.spotify.com/uk/premium/', 'GBP 11.99 converted to USD'),
+('spotify', 0.0012, NULL, 4.05, 'standard', 'BR', '2024-01-01', 'https://www.spotify.com/br-pt/premium/', 'BRL 21.90 converted to USD'),
+('spotify', 0.0006, NULL, 1.43, 'standard', 'IN', '2024-01-01', 'https://www.spotify.com/in-en/premium/', 'INR 119 converted to USD');
@@ -0,0 +1,196 @@
+-- Versioned trouble score profiles
+-- A profile is a named set of component weights, optionally restricted to a
+-- subset of offense categories. Editing a profile appends a new version rather
+-- than changing the old one, so every stored score can be traced back to the
+-- exact weights that produced it. Shared profiles have no owner; users can
+-- also keep private profiles and pick one to apply to their own views.
+-- The default profile drives the stored artist_trouble_scores.
+
+CREATE TABLE trouble_score_profiles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    owner_user_id UUID REFERENCES users(id) ON DELETE CASCADE,  -- NULL for shared profiles
+    description TEXT,
+
+    severity_weight FLOAT NOT NULL,
+    evidence_weight FLOAT NOT NULL,
+    recency_weight FLOAT NOT NULL,
+    community_weight FLOAT NOT NULL,
+    revenue_weight FLOAT NOT NULL,
+
+    -- Only offenses in these categories count; NULL counts every category
+    categories offense_category[],
+
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    created_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT profile_weights_range_check CHECK (
+        severity_weight BETWEEN 0 AND 1 AND evidence_weight BETWEEN 0 AND 1
+        AND recency_weight BETWEEN 0 AND 1 AND community_weight BETWEEN 0 AND 1
+        AND revenue_weight BETWEEN 0 AND 1
+    ),
+    CONSTRAINT profile_weights_sum_check CHECK (
+        ABS(severity_weight + evidence_weight + recency_weight + community_weight + revenue_weight - 1.0) < 0.01
+    ),
+    -- The stored scores are computed in SQL, which applies weights only
+    CONSTRAINT default_profile_shared_check CHECK (
+        NOT is_default OR (owner_user_id IS NULL AND categories IS NULL)
+    )
+);
+
+CREATE UNIQUE INDEX unique_shared_profile_version
+    ON trouble_score_profiles(name, version) WHERE owner_user_id IS NULL;
+CREATE UNIQUE INDEX unique_user_profile_version
+    ON trouble_score_profiles(owner_user_id, name, version) WHERE owner_user_id IS NOT NULL;
+CREATE UNIQUE INDEX unique_default_profile
+    ON trouble_score_profiles(is_default) WHERE is_default;
+CREATE INDEX idx_trouble_profiles_owner ON trouble_score_profiles(owner_user_id, name, version DESC);
+
+-- Profile each user applies to their own scores
+CREATE TABLE user_score_profiles (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    profile_id UUID NOT NULL REFERENCES trouble_score_profiles(id) ON DELETE CASCADE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Seed the default profile from the active legacy weights
+INSERT INTO trouble_score_profiles (name, version, description, severity_weight, evidence_weight, recency_weight, community_weight, revenue_weight, is_default)
+SELECT 'default', 1, 'Balanced weighting across all components',
+       severity_weight, evidence_weight, recency_weight, community_weight, revenue_weight, TRUE
+FROM trouble_score_weights
+WHERE is_active = TRUE
+LIMIT 1;
+
+INSERT INTO trouble_score_profiles (name, version, description, severity_weight, evidence_weight, recency_weight, community_weight, revenue_weight, is_default)
+SELECT 'default', 1, 'Balanced weighting across all components', 0.35, 0.20, 0.15, 0.15, 0.15, TRUE
+WHERE NOT EXISTS (SELECT 1 FROM trouble_score_profiles);
+
+COMMENT ON TABLE trouble_score_weights IS 'Superseded by trouble_score_profiles; kept for reference';
+
+-- Record which profile version produced each score
+ALTER TABLE artist_trouble_scores ADD COLUMN profile_id UUID REFERENCES trouble_score_profiles(id);
+ALTER TABLE trouble_score_history ADD COLUMN profile_id UUID REFERENCES trouble_score_profiles(id);
+
+-- 022 added a (UUID, TEXT) overload writing to a table that never existed.
+-- String literals resolve to that overload, so API recalculations silently did
+-- nothing. Replace both with a single profile-aware function.
+DROP FUNCTION IF EXISTS recalculate_trouble_score(UUID, TEXT);
+DROP FUNCTION IF EXISTS recalculate_trouble_score(UUID, VARCHAR);
+
+CREATE OR REPLACE FUNCTION recalculate_trouble_score(p_artist_id UUID, p_trigger_reason TEXT DEFAULT 'manual')
+RETURNS artist_trouble_scores AS $$
+DECLARE
+    result artist_trouble_scores;
+    profile trouble_score_profiles;
+    v_severity FLOAT;
+    v_evidence FLOAT;
+    v_recency FLOAT;
+    v_community FLOAT;
+    v_revenue FLOAT := 0; -- Will be populated by revenue service
+    v_total FLOAT;
+    v_tier trouble_tier;
+    v_offense_count INTEGER;
+    v_verified_count INTEGER;
+    v_block_count INTEGER;
+    v_egregious INTEGER;
+    v_severe INTEGER;
+    v_moderate INTEGER;
+    v_minor INTEGER;
+    v_first_date DATE;
+    v_last_date DATE;
+BEGIN
+    SELECT * INTO profile FROM trouble_score_profiles WHERE is_default LIMIT 1;
+
+    IF profile.id IS NULL THEN
+        RAISE EXCEPTION 'No default trouble score profile configured';
+    END IF;
+
+    -- Calculate component scores
+    v_severity := calculate_severity_score(p_artist_id);
+    v_evidence := calculate_evidence_score(p_artist_id);
+    v_recency := calculate_recency_score(p_artist_id);
+    v_community := calculate_community_score(p_artist_id);
+
+    -- Calculate total weighted score
+    v_total := v_severity * profile.severity_weight +
+               v_evidence * profile.evidence_weight +
+               v_recency * profile.recency_weight +
+               v_community * profile.community_weight +
+               v_revenue * profile.revenue_weight;
+
+    -- Determine tier
+    v_tier := CASE
+        WHEN v_total >= 0.75 THEN 'critical'
+        WHEN v_total >= 0.50 THEN 'high'
+        WHEN v_total >= 0.25 THEN 'moderate'
+        ELSE 'low'
+    END;
+
+    -- Get offense counts
+    SELECT
+        COUNT(*),
+        COUNT(*) FILTER (WHERE status = 'verified'),
+        COUNT(*) FILTER (WHERE severity = 'egregious'),
+        COUNT(*) FILTER (WHERE severity = 'severe'),
+        COUNT(*) FILTER (WHERE severity = 'moderate'),
+        COUNT(*) FILTER (WHERE severity = 'minor'),
+        MIN(incident_date),
+        MAX(incident_date)
+    INTO v_offense_count, v_verified_count, v_egregious, v_severe, v_moderate, v_minor, v_first_date, v_last_date
+    FROM artist_offenses
+    WHERE artist_id = p_artist_id;
+
+    -- Get block count
+    SELECT COUNT(*) INTO v_block_count
+    FROM user_artist_blocks
+    WHERE artist_id = p_artist_id;
+
+    -- Upsert the score record
+    INSERT INTO artist_trouble_scores (
+        artist_id, severity_score, evidence_score, recency_score, community_score, revenue_score,
+        total_score, trouble_tier, offense_count, verified_offense_count, block_count,
+        egregious_count, severe_count, moderate_count, minor_count,
+        first_offense_date, last_offense_date, last_calculated_at, profile_id
+    ) VALUES (
+        p_artist_id, v_severity, v_evidence, v_recency, v_community, v_revenue,
+        v_total, v_tier, v_offense_count, v_verified_count, v_block_count,
+        v_egregious, v_severe, v_moderate, v_minor,
+        v_first_date, v_last_date, NOW(), profile.id
+    )
+    ON CONFLICT (artist_id) DO UPDATE SET
+        severity_score = EXCLUDED.severity_score,
+        evidence_score = EXCLUDED.evidence_score,
+        recency_score = EXCLUDED.recency_score,
+        community_score = EXCLUDED.community_score,
+        revenue_score = EXCLUDED.revenue_score,
+        total_score = EXCLUDED.total_score,
+        trouble_tier = EXCLUDED.trouble_tier,
+        offense_count = EXCLUDED.offense_count,
+        verified_offense_count = EXCLUDED.verified_offense_count,
+        block_count = EXCLUDED.block_count,
+        egregious_count = EXCLUDED.egregious_count,
+        severe_count = EXCLUDED.severe_count,
+        moderate_count = EXCLUDED.moderate_count,
+        minor_count = EXCLUDED.minor_count,
+        first_offense_date = EXCLUDED.first_offense_date,
+        last_offense_date = EXCLUDED.last_offense_date,
+        last_calculated_at = NOW(),
+        profile_id = EXCLUDED.profile_id
+    RETURNING * INTO result;
+
+    -- Record history
+    INSERT INTO trouble_score_history (
+        artist_id, severity_score, evidence_score, recency_score, community_score, revenue_score,
+        total_score, trouble_tier, trigger_reason, profile_id
+    ) VALUES (
+        p_artist_id, v_severity, v_evidence, v_recency, v_community, v_revenue,
+        v_total, v_tier, p_trigger_reason, profile.id
+    );
+
+    RETURN result;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE INDEX idx_trouble_history_profile ON trouble_score_history(profile_id);
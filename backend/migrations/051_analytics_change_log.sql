@@ -0,0 +1,73 @@
+-- Change log feeding the DuckDB analytics replica
+-- Row-level triggers record the key of every inserted, updated or deleted row
+-- in the replicated tables. The replication job drains the log in id order,
+-- re-reads the current rows by key (a key that no longer exists is a delete)
+-- and removes the entries it applied. Entries from transactions that haven't
+-- committed yet are invisible to the job and stay queued for the next run, so
+-- none are skipped. TRUNCATE is not captured; re-snapshot the table instead.
+
+CREATE TABLE analytics_change_log (
+    id BIGSERIAL PRIMARY KEY,
+    table_name VARCHAR(64) NOT NULL,
+    row_key TEXT NOT NULL,  -- primary key columns as text, joined with ':'
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_analytics_change_log_table ON analytics_change_log(table_name, id);
+
+-- Trigger arguments are the primary key column names
+CREATE OR REPLACE FUNCTION log_analytics_change()
+RETURNS TRIGGER AS $$
+DECLARE
+    new_key TEXT;
+    old_key TEXT;
+    i INTEGER;
+BEGIN
+    IF TG_OP <> 'DELETE' THEN
+        new_key := to_jsonb(NEW) ->> TG_ARGV[0];
+        FOR i IN 1 .. TG_NARGS - 1 LOOP
+            new_key := new_key || ':' || (to_jsonb(NEW) ->> TG_ARGV[i]);
+        END LOOP;
+        INSERT INTO analytics_change_log (table_name, row_key) VALUES (TG_TABLE_NAME, new_key);
+    END IF;
+
+    IF TG_OP <> 'INSERT' THEN
+        old_key := to_jsonb(OLD) ->> TG_ARGV[0];
+        FOR i IN 1 .. TG_NARGS - 1 LOOP
+            old_key := old_key || ':' || (to_jsonb(OLD) ->> TG_ARGV[i]);
+        END LOOP;
+        -- Updates only need the old key when the key itself changed
+        IF new_key IS DISTINCT FROM old_key THEN
+            INSERT INTO analytics_change_log (table_name, row_key) VALUES (TG_TABLE_NAME, old_key);
+        END IF;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER action_batches_analytics_change
+    AFTER INSERT OR UPDATE OR DELETE ON action_batches
+    FOR EACH ROW EXECUTE FUNCTION log_analytics_change('id');
+
+CREATE TRIGGER action_items_analytics_change
+    AFTER INSERT OR UPDATE OR DELETE ON action_items
+    FOR EACH ROW EXECUTE FUNCTION log_analytics_change('id');
+
+CREATE TRIGGER user_library_tracks_analytics_change
+    AFTER INSERT OR UPDATE OR DELETE ON user_library_tracks
+    FOR EACH ROW EXECUTE FUNCTION log_analytics_change('id');
+
+CREATE TRIGGER artist_offenses_analytics_change
+    AFTER INSERT OR UPDATE OR DELETE ON artist_offenses
+    FOR EACH ROW EXECUTE FUNCTION log_analytics_change('id');
+
+CREATE TRIGGER user_artist_blocks_analytics_change
+    AFTER INSERT OR UPDATE OR DELETE ON user_artist_blocks
+    FOR EACH ROW EXECUTE FUNCTION log_analytics_change('user_id', 'artist_id');
+
+CREATE TRIGGER user_artist_playcounts_analytics_change
+    AFTER INSERT OR UPDATE OR DELETE ON user_artist_playcounts
+    FOR EACH ROW EXECUTE FUNCTION log_analytics_change('id');
+
+COMMENT ON TABLE analytics_change_log IS 'Keys of changed rows awaiting replication to the DuckDB analytics store';
@@ -0,0 +1,153 @@
+-- User lifecycle events for cohort and funnel analytics
+-- One row per milestone in a user's journey: registration, connecting a
+-- provider, library scans, enforcement runs, rollbacks and disconnects.
+-- Rows are written by triggers on the source tables so nothing depends on
+-- handlers remembering to log. Disconnects delete the connection row, which is
+-- why these can't be derived from current state alone.
+
+CREATE TABLE user_lifecycle_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL,  -- no FK: events outlive the rows that produced them
+    event_type VARCHAR(20) NOT NULL CHECK (event_type IN (
+        'registered', 'connected', 'scanned', 'enforced', 'rolled_back', 'disconnected'
+    )),
+    provider VARCHAR(50),  -- NULL for registration
+    source_id UUID NOT NULL,  -- row that produced the event (user, connection, scan, batch or run)
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A rollback touches many items; record one event per batch
+CREATE UNIQUE INDEX unique_lifecycle_event_source ON user_lifecycle_events(event_type, source_id);
+CREATE INDEX idx_lifecycle_events_user ON user_lifecycle_events(user_id, occurred_at);
+
+CREATE OR REPLACE FUNCTION record_lifecycle_event(
+    p_user_id UUID,
+    p_event_type TEXT,
+    p_provider TEXT,
+    p_source_id UUID,
+    p_occurred_at TIMESTAMPTZ
+) RETURNS VOID AS $$
+BEGIN
+    IF p_user_id IS NULL THEN
+        RETURN;
+    END IF;
+
+    INSERT INTO user_lifecycle_events (user_id, event_type, provider, source_id, occurred_at)
+    VALUES (p_user_id, p_event_type, p_provider, p_source_id, COALESCE(p_occurred_at, NOW()))
+    ON CONFLICT (event_type, source_id) DO NOTHING;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION lifecycle_user_registered() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM record_lifecycle_event(NEW.id, 'registered', NULL, NEW.id, NEW.created_at);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION lifecycle_connection_changed() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        PERFORM record_lifecycle_event(NEW.user_id, 'connected', NEW.provider, NEW.id, NEW.created_at);
+    ELSE
+        PERFORM record_lifecycle_event(OLD.user_id, 'disconnected', OLD.provider, OLD.id, NOW());
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION lifecycle_library_scanned() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM record_lifecycle_event(NEW.user_id, 'scanned', NEW.provider, NEW.id, NEW.scan_started_at);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION lifecycle_batch_enforced() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM record_lifecycle_event(NEW.user_id, 'enforced', NEW.provider, NEW.id, NEW.created_at);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION lifecycle_item_rolled_back() RETURNS TRIGGER AS $$
+DECLARE
+    batch action_batches;
+BEGIN
+    SELECT * INTO batch FROM action_batches WHERE id = NEW.batch_id;
+    PERFORM record_lifecycle_event(batch.user_id, 'rolled_back', batch.provider, batch.id, NOW());
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION lifecycle_apple_music_run() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        PERFORM record_lifecycle_event(NEW.user_id, 'enforced', 'apple_music', NEW.id, NEW.started_at);
+    ELSIF NEW.status = 'rolled_back' AND OLD.status IS DISTINCT FROM 'rolled_back' THEN
+        PERFORM record_lifecycle_event(NEW.user_id, 'rolled_back', 'apple_music', NEW.id, NOW());
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER users_lifecycle_registered
+    AFTER INSERT ON users
+    FOR EACH ROW EXECUTE FUNCTION lifecycle_user_registered();
+
+CREATE TRIGGER connections_lifecycle
+    AFTER INSERT OR DELETE ON connections
+    FOR EACH ROW EXECUTE FUNCTION lifecycle_connection_changed();
+
+CREATE TRIGGER library_scan_results_lifecycle
+    AFTER INSERT ON library_scan_results
+    FOR EACH ROW EXECUTE FUNCTION lifecycle_library_scanned();
+
+CREATE TRIGGER action_batches_lifecycle_enforced
+    AFTER INSERT ON action_batches
+    FOR EACH ROW WHEN (NOT COALESCE(NEW.dry_run, FALSE))
+    EXECUTE FUNCTION lifecycle_batch_enforced();
+
+CREATE TRIGGER action_items_lifecycle_rolled_back
+    AFTER UPDATE OF status ON action_items
+    FOR EACH ROW WHEN (NEW.status = 'rolled_back' AND OLD.status IS DISTINCT FROM 'rolled_back')
+    EXECUTE FUNCTION lifecycle_item_rolled_back();
+
+CREATE TRIGGER apple_music_runs_lifecycle
+    AFTER INSERT OR UPDATE OF status ON apple_music_enforcement_runs
+    FOR EACH ROW EXECUTE FUNCTION lifecycle_apple_music_run();
+
+-- Backfill from existing rows. Disconnects before this migration are lost,
+-- and rollback times are approximated by the batch completion time.
+INSERT INTO user_lifecycle_events (user_id, event_type, provider, source_id, occurred_at)
+SELECT id, 'registered', NULL, id, COALESCE(created_at, NOW()) FROM users
+UNION ALL
+SELECT user_id, 'connected', provider, id, COALESCE(created_at, NOW())
+FROM connections WHERE user_id IS NOT NULL
+UNION ALL
+SELECT user_id, 'scanned', provider, id, scan_started_at FROM library_scan_results
+UNION ALL
+SELECT user_id, 'enforced', provider, id, COALESCE(created_at, NOW())
+FROM action_batches WHERE user_id IS NOT NULL AND NOT COALESCE(dry_run, FALSE)
+UNION ALL
+SELECT ab.user_id, 'rolled_back', ab.provider, ab.id, COALESCE(ab.completed_at, ab.created_at, NOW())
+FROM action_batches ab
+WHERE ab.user_id IS NOT NULL
+  AND EXISTS (SELECT 1 FROM action_items ai WHERE ai.batch_id = ab.id AND ai.status = 'rolled_back')
+UNION ALL
+SELECT user_id, 'enforced', 'apple_music', id, started_at FROM apple_music_enforcement_runs
+UNION ALL
+SELECT user_id, 'rolled_back', 'apple_music', id, COALESCE(completed_at, started_at)
+FROM apple_music_enforcement_runs WHERE status = 'rolled_back'
+ON CONFLICT (event_type, source_id) DO NOTHING;
+
+-- Replicate events and connection health to the DuckDB analytics store
+CREATE TRIGGER user_lifecycle_events_analytics_change
+    AFTER INSERT OR UPDATE OR DELETE ON user_lifecycle_events
+    FOR EACH ROW EXECUTE FUNCTION log_analytics_change('id');
+
+CREATE TRIGGER connections_analytics_change
+    AFTER INSERT OR UPDATE OR DELETE ON connections
+    FOR EACH ROW EXECUTE FUNCTION log_analytics_change('id');
+
+COMMENT ON TABLE user_lifecycle_events IS 'User journey milestones feeding cohort and funnel analytics';
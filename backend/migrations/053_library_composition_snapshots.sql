@@ -0,0 +1,170 @@
+-- Library composition snapshots and regression alerts
+-- Every library sync records what share of a provider library comes from
+-- flagged artists, broken down by offense category and trouble tier, plus the
+-- share of estimated revenue going to them. Comparing consecutive snapshots
+-- shows how enforcement (and re-additions) moved those numbers.
+--
+-- A regression is a track that enforcement removed which turns up again in a
+-- later sync, whether re-added by the user or restored by the provider.
+
+CREATE TABLE library_composition_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider VARCHAR(50) NOT NULL,
+
+    total_tracks INTEGER NOT NULL DEFAULT 0,
+    flagged_tracks INTEGER NOT NULL DEFAULT 0,   -- by artists with a verified offense
+    blocked_tracks INTEGER NOT NULL DEFAULT 0,   -- by artists on the user's block list
+    tracks_by_category JSONB NOT NULL DEFAULT '{}',  -- category -> tracks (a track counts once per category)
+    tracks_by_tier JSONB NOT NULL DEFAULT '{}',      -- trouble tier -> tracks
+
+    -- Estimated revenue from the user's plays over the previous 30 days
+    total_revenue DECIMAL(12, 6) NOT NULL DEFAULT 0,
+    flagged_revenue DECIMAL(12, 6) NOT NULL DEFAULT 0,
+
+    regressions_detected INTEGER NOT NULL DEFAULT 0,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_library_snapshots_user ON library_composition_snapshots(user_id, provider, captured_at DESC);
+
+CREATE TABLE library_regressions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider VARCHAR(50) NOT NULL,
+    provider_track_id VARCHAR(255) NOT NULL,
+    track_name VARCHAR(500),
+    artist_id UUID REFERENCES artists(id) ON DELETE SET NULL,
+    artist_name VARCHAR(255),
+    removed_at TIMESTAMPTZ NOT NULL,  -- when enforcement removed the track
+    snapshot_id UUID REFERENCES library_composition_snapshots(id) ON DELETE SET NULL,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    acknowledged_at TIMESTAMPTZ,
+
+    -- One alert per removal that came back
+    CONSTRAINT unique_library_regression UNIQUE (user_id, provider, provider_track_id, removed_at)
+);
+
+CREATE INDEX idx_library_regressions_open ON library_regressions(user_id, detected_at DESC)
+    WHERE acknowledged_at IS NULL;
+
+-- Library tracks with the artist resolved by id, or by name as the library
+-- scan does when the import couldn't link one
+CREATE OR REPLACE FUNCTION library_track_artists(p_user_id UUID, p_provider TEXT)
+RETURNS TABLE (
+    provider_track_id VARCHAR,
+    track_name VARCHAR,
+    artist_name VARCHAR,
+    artist_id UUID,
+    last_synced TIMESTAMPTZ
+) AS $$
+    SELECT ult.provider_track_id, ult.track_name, ult.artist_name,
+           COALESCE(ult.artist_id, (
+               SELECT a.id FROM artists a
+               WHERE LOWER(a.canonical_name) = LOWER(ult.artist_name)
+               LIMIT 1
+           )),
+           ult.last_synced
+    FROM user_library_tracks ult
+    WHERE ult.user_id = p_user_id AND ult.provider = p_provider
+$$ LANGUAGE sql STABLE;
+
+-- Record the composition of a provider library and raise regressions.
+-- Call inside the sync transaction, after the library rows are written.
+CREATE OR REPLACE FUNCTION capture_library_snapshot(p_user_id UUID, p_provider TEXT)
+RETURNS library_composition_snapshots AS $$
+DECLARE
+    result library_composition_snapshots;
+    v_regressions INTEGER;
+BEGIN
+    INSERT INTO library_composition_snapshots (
+        user_id, provider, total_tracks, flagged_tracks, blocked_tracks,
+        tracks_by_category, tracks_by_tier, total_revenue, flagged_revenue
+    )
+    SELECT
+        p_user_id,
+        p_provider,
+        counts.total,
+        counts.flagged,
+        counts.blocked,
+        COALESCE((
+            SELECT jsonb_object_agg(c.category, c.tracks)
+            FROM (
+                SELECT ao.category::text AS category, COUNT(DISTINCT t.provider_track_id) AS tracks
+                FROM library_track_artists(p_user_id, p_provider) t
+                JOIN artist_offenses ao ON ao.artist_id = t.artist_id AND ao.status = 'verified'
+                GROUP BY ao.category
+            ) c
+        ), '{}'),
+        COALESCE((
+            SELECT jsonb_object_agg(s.tier, s.tracks)
+            FROM (
+                SELECT ats.trouble_tier::text AS tier, COUNT(*) AS tracks
+                FROM library_track_artists(p_user_id, p_provider) t
+                JOIN artist_trouble_scores ats ON ats.artist_id = t.artist_id
+                GROUP BY ats.trouble_tier
+            ) s
+        ), '{}'),
+        revenue.total,
+        revenue.flagged
+    FROM (
+        SELECT
+            COUNT(*) AS total,
+            COUNT(*) FILTER (WHERE EXISTS (
+                SELECT 1 FROM artist_offenses ao
+                WHERE ao.artist_id = t.artist_id AND ao.status = 'verified'
+            )) AS flagged,
+            COUNT(*) FILTER (WHERE EXISTS (
+                SELECT 1 FROM user_artist_blocks b
+                WHERE b.user_id = p_user_id AND b.artist_id = t.artist_id
+            )) AS blocked
+        FROM library_track_artists(p_user_id, p_provider) t
+    ) counts,
+    (
+        SELECT
+            COALESCE(SUM(p.estimated_revenue), 0) AS total,
+            COALESCE(SUM(p.estimated_revenue) FILTER (WHERE EXISTS (
+                SELECT 1 FROM artist_offenses ao
+                WHERE ao.artist_id = p.artist_id AND ao.status = 'verified'
+            )), 0) AS flagged
+        FROM user_artist_playcounts p
+        WHERE p.user_id = p_user_id
+          AND p.platform = p_provider
+          AND p.period_start >= CURRENT_DATE - 30
+    ) revenue
+    RETURNING * INTO result;
+
+    -- Tracks enforcement removed that were seen again by a sync after the removal
+    INSERT INTO library_regressions (
+        user_id, provider, provider_track_id, track_name, artist_id, artist_name, removed_at, snapshot_id
+    )
+    SELECT DISTINCT ON (t.provider_track_id)
+        p_user_id, p_provider, t.provider_track_id, t.track_name, t.artist_id, t.artist_name,
+        ai.created_at, result.id
+    FROM library_track_artists(p_user_id, p_provider) t
+    JOIN action_items ai ON ai.entity_id = t.provider_track_id
+        AND ai.status = 'completed'
+        AND ai.action LIKE 'remove%'
+    JOIN action_batches ab ON ab.id = ai.batch_id
+        AND ab.user_id = p_user_id
+        AND ab.provider = p_provider
+        AND NOT COALESCE(ab.dry_run, FALSE)
+    WHERE t.last_synced > ai.created_at
+    ORDER BY t.provider_track_id, ai.created_at DESC
+    ON CONFLICT (user_id, provider, provider_track_id, removed_at) DO NOTHING;
+
+    GET DIAGNOSTICS v_regressions = ROW_COUNT;
+
+    IF v_regressions > 0 THEN
+        UPDATE library_composition_snapshots
+        SET regressions_detected = v_regressions
+        WHERE id = result.id
+        RETURNING * INTO result;
+    END IF;
+
+    RETURN result;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON TABLE library_composition_snapshots IS 'Composition of a provider library at each sync';
+COMMENT ON TABLE library_regressions IS 'Enforcement-removed tracks that reappeared in a later sync';
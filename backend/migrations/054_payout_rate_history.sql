@@ -0,0 +1,120 @@
+-- Payout rate management
+-- Rates are added and corrected through the admin API instead of migrations.
+-- Each rate row covers its platform/tier/country from effective_date until the
+-- next row for the same key takes over; every change to a row is kept in
+-- platform_payout_rate_changes with the values before and after.
+--
+-- Stored playcount revenue is priced at the standard, country-independent
+-- rate in effect at the play period, so adding or correcting one of those
+-- rates reprices the playcounts it covers.
+
+ALTER TABLE platform_payout_rates ADD COLUMN created_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+CREATE TABLE platform_payout_rate_changes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rate_id UUID NOT NULL REFERENCES platform_payout_rates(id) ON DELETE CASCADE,
+    change_type VARCHAR(20) NOT NULL CHECK (change_type IN ('created', 'corrected')),
+    previous_values JSONB,                 -- NULL for created
+    new_values JSONB NOT NULL,
+    reason TEXT,
+    playcounts_recalculated INTEGER NOT NULL DEFAULT 0,
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_payout_rate_changes_rate ON platform_payout_rate_changes(rate_id, changed_at DESC);
+CREATE INDEX idx_payout_rate_changes_recent ON platform_payout_rate_changes(changed_at DESC);
+
+CREATE TRIGGER payout_rate_updated
+    BEFORE UPDATE ON platform_payout_rates
+    FOR EACH ROW
+    EXECUTE FUNCTION update_playcount_timestamp();
+
+-- Standard per-stream rate in effect on a date, as used for stored playcount
+-- revenue. NULL when the platform had no rate yet.
+CREATE OR REPLACE FUNCTION payout_rate_on(p_platform VARCHAR, p_date DATE)
+RETURNS DECIMAL AS $$
+    SELECT rate_per_stream
+    FROM platform_payout_rates
+    WHERE platform = p_platform
+    AND rate_tier = 'standard'
+    AND country_code IS NULL
+    AND effective_date <= p_date
+    AND (end_date IS NULL OR end_date >= p_date)
+    ORDER BY effective_date DESC
+    LIMIT 1
+$$ LANGUAGE sql STABLE;
+
+-- Reprice a platform's playcounts from a period onwards at the rate in effect
+-- for each period. Periods before the platform's first rate are left alone.
+-- Returns the number of playcounts whose revenue changed.
+CREATE OR REPLACE FUNCTION recalculate_playcount_revenue(p_platform VARCHAR, p_from DATE)
+RETURNS INTEGER AS $$
+DECLARE
+    v_updated INTEGER;
+BEGIN
+    WITH rated AS (
+        SELECT pc.id, payout_rate_on(pc.platform, pc.period_start) AS rate
+        FROM user_artist_playcounts pc
+        WHERE pc.platform = p_platform
+        AND pc.period_start >= p_from
+    )
+    UPDATE user_artist_playcounts pc
+    SET rate_used = rated.rate,
+        estimated_revenue = pc.play_count * rated.rate
+    FROM rated
+    WHERE pc.id = rated.id
+    AND rated.rate IS NOT NULL
+    AND (pc.rate_used IS DISTINCT FROM rated.rate
+         OR pc.estimated_revenue IS DISTINCT FROM pc.play_count * rated.rate);
+
+    GET DIAGNOSTICS v_updated = ROW_COUNT;
+    RETURN v_updated;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Price single playcounts at the rate for their period rather than today's
+CREATE OR REPLACE FUNCTION calculate_playcount_revenue(p_playcount_id UUID)
+RETURNS DECIMAL AS $$
+DECLARE
+    v_platform VARCHAR;
+    v_play_count INTEGER;
+    v_period_start DATE;
+    v_rate DECIMAL;
+    v_revenue DECIMAL;
+BEGIN
+    SELECT platform, play_count, period_start INTO v_platform, v_play_count, v_period_start
+    FROM user_artist_playcounts
+    WHERE id = p_playcount_id;
+
+    v_rate := COALESCE(payout_rate_on(v_platform, v_period_start), get_payout_rate(v_platform));
+    v_revenue := v_play_count * v_rate;
+
+    UPDATE user_artist_playcounts
+    SET estimated_revenue = v_revenue, rate_used = v_rate
+    WHERE id = p_playcount_id;
+
+    RETURN v_revenue;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Seeded rates predate the change log; record them as created
+INSERT INTO platform_payout_rate_changes (rate_id, change_type, new_values, reason, changed_at)
+SELECT id, 'created',
+       jsonb_build_object(
+           'platform', platform,
+           'rate_tier', rate_tier,
+           'country_code', country_code,
+           'rate_per_stream', rate_per_stream,
+           'rate_per_minute', rate_per_minute,
+           'subscription_monthly', subscription_monthly,
+           'royalty_share', royalty_share,
+           'effective_date', effective_date,
+           'source_url', source_url,
+           'notes', notes
+       ),
+       'Seeded by migration',
+       created_at
+FROM platform_payout_rates;
+
+COMMENT ON TABLE platform_payout_rate_changes IS 'History of payout rate additions and corrections';
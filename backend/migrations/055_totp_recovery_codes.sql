@@ -0,0 +1,20 @@
+-- 2FA recovery codes
+-- Single-use codes a user can enter instead of a TOTP when they lose their
+-- authenticator. Only SHA-256 hashes are stored; the codes themselves are
+-- shown once when generated. A set is issued with the TOTP secret at setup,
+-- only accepted once 2FA is enabled, and replaced wholesale on regeneration.
+
+CREATE TABLE user_recovery_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash VARCHAR(64) NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_recovery_code UNIQUE (user_id, code_hash)
+);
+
+CREATE INDEX idx_recovery_codes_unused ON user_recovery_codes(user_id)
+    WHERE used_at IS NULL;
+
+COMMENT ON TABLE user_recovery_codes IS 'Hashed single-use 2FA recovery codes';
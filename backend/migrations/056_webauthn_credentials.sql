@@ -0,0 +1,39 @@
+-- WebAuthn passkeys
+-- Credentials registered by a user's authenticators, usable for passwordless
+-- login and as an alternative to TOTP once 2FA is enabled. The public key is
+-- stored as the COSE key the authenticator returned. sign_count is the last
+-- counter seen; an assertion with a counter that doesn't move forward points
+-- at a cloned authenticator and is rejected.
+
+CREATE TABLE webauthn_credentials (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    credential_id TEXT NOT NULL UNIQUE,        -- base64url, as sent by the client
+    public_key BYTEA NOT NULL,                 -- COSE_Key
+    algorithm INTEGER NOT NULL,                -- COSE algorithm identifier
+    sign_count BIGINT NOT NULL DEFAULT 0,
+    transports TEXT[] NOT NULL DEFAULT '{}',
+    aaguid UUID,
+    attestation_format VARCHAR(32) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_webauthn_credentials_user ON webauthn_credentials(user_id);
+
+-- Outstanding ceremony challenges. A row is deleted when its ceremony is
+-- finished, successfully or not, so each challenge can only be answered once.
+CREATE TABLE webauthn_challenges (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID REFERENCES users(id) ON DELETE CASCADE,   -- NULL for discoverable passkey login
+    ceremony VARCHAR(20) NOT NULL CHECK (ceremony IN ('registration', 'authentication')),
+    challenge TEXT NOT NULL,                   -- base64url
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_webauthn_challenges_expires ON webauthn_challenges(expires_at);
+
+COMMENT ON TABLE webauthn_credentials IS 'WebAuthn passkeys and security keys per user';
+COMMENT ON TABLE webauthn_challenges IS 'Single-use WebAuthn ceremony challenges';
@@ -0,0 +1,30 @@
+-- JWT signing keyring
+-- RS256 keys used to sign access tokens. Every key that hasn't retired is
+-- published at /.well-known/jwks.json; the newest key whose activates_at has
+-- passed signs new tokens. A successor is published ahead of its activation
+-- so verifiers that cache the JWKS already know it by the time it signs, and
+-- the key it replaces keeps verifying until every token it signed has
+-- expired (retires_at).
+--
+-- Private keys are stored as AES-256-GCM ciphertext under a data key from
+-- the configured KMS provider, and are wiped once the key retires.
+
+CREATE TABLE jwt_signing_keys (
+    kid VARCHAR(64) PRIMARY KEY,
+    algorithm VARCHAR(10) NOT NULL DEFAULT 'RS256',
+    public_n TEXT NOT NULL,                    -- base64url RSA modulus
+    public_e TEXT NOT NULL,                    -- base64url RSA exponent
+    encrypted_private_key BYTEA,               -- PKCS#8 PEM, NULL once retired
+    private_key_nonce BYTEA,
+    encrypted_data_key BYTEA,
+    data_key_id VARCHAR(100),
+    activates_at TIMESTAMPTZ NOT NULL,
+    retires_at TIMESTAMPTZ,                    -- set when a successor is scheduled
+    retired_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_jwt_signing_keys_live ON jwt_signing_keys(activates_at)
+    WHERE retired_at IS NULL;
+
+COMMENT ON TABLE jwt_signing_keys IS 'RS256 access token signing keys published in the JWKS';
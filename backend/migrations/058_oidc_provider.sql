@@ -0,0 +1,69 @@
+-- OpenID Connect provider ("Sign in with NDITH")
+-- Partner apps registered as clients get an authorization code once a user
+-- consents, redeem it with PKCE for an access token (and optionally a
+-- refresh token and ID token), and use the access token for the scopes the
+-- user granted. Codes, access tokens and refresh tokens are random strings
+-- stored as SHA-256 hashes.
+--
+-- Every token issued from one authorization code shares a grant_id, so a
+-- replayed code or refresh token revokes the whole grant.
+
+CREATE TABLE oidc_clients (
+    client_id VARCHAR(64) PRIMARY KEY,
+    client_secret_hash TEXT,                   -- NULL for public clients
+    name VARCHAR(100) NOT NULL,
+    redirect_uris TEXT[] NOT NULL,
+    allowed_scopes TEXT[] NOT NULL,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ
+);
+
+-- Scopes a user has agreed to give a client; revoking consent revokes the
+-- client's tokens for that user
+CREATE TABLE oidc_consents (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    client_id VARCHAR(64) NOT NULL REFERENCES oidc_clients(client_id) ON DELETE CASCADE,
+    scopes TEXT[] NOT NULL,
+    granted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, client_id)
+);
+
+CREATE TABLE oidc_authorization_codes (
+    code_hash TEXT PRIMARY KEY,
+    grant_id UUID NOT NULL DEFAULT gen_random_uuid(),
+    client_id VARCHAR(64) NOT NULL REFERENCES oidc_clients(client_id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    redirect_uri TEXT NOT NULL,
+    scopes TEXT[] NOT NULL,
+    nonce TEXT,
+    code_challenge TEXT NOT NULL,              -- S256
+    auth_time TIMESTAMPTZ NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_oidc_authorization_codes_expires ON oidc_authorization_codes(expires_at);
+
+CREATE TABLE oidc_tokens (
+    token_hash TEXT PRIMARY KEY,
+    token_type VARCHAR(10) NOT NULL CHECK (token_type IN ('access', 'refresh')),
+    grant_id UUID NOT NULL,
+    client_id VARCHAR(64) NOT NULL REFERENCES oidc_clients(client_id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    scopes TEXT[] NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    rotated_at TIMESTAMPTZ,                    -- refresh token exchanged for a new one
+    revoked_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_oidc_tokens_grant ON oidc_tokens(grant_id);
+CREATE INDEX idx_oidc_tokens_user_client ON oidc_tokens(user_id, client_id);
+
+COMMENT ON TABLE oidc_clients IS 'Partner apps allowed to sign users in with NDITH';
+COMMENT ON TABLE oidc_consents IS 'Scopes each user has granted each OIDC client';
+COMMENT ON TABLE oidc_authorization_codes IS 'Single-use OIDC authorization codes';
+COMMENT ON TABLE oidc_tokens IS 'Access and refresh tokens issued to OIDC clients';
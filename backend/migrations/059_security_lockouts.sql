@@ -0,0 +1,34 @@
+-- Shared brute-force and IP block state
+-- Failed logins and suspicious requests are counted in Redis sliding windows;
+-- security_attempts holds the same counters while Redis is unreachable.
+-- Account lockouts and IP blocks are always recorded in security_lockouts so
+-- every replica sees them, they survive a Redis flush, and admins can list
+-- and lift them.
+
+CREATE TABLE security_attempts (
+    id BIGSERIAL PRIMARY KEY,
+    counter VARCHAR(20) NOT NULL,              -- account_failure | ip_failure | ip_suspicious
+    subject TEXT NOT NULL,                     -- normalized email or IP address
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_security_attempts_subject
+    ON security_attempts(counter, subject, occurred_at);
+
+CREATE TABLE security_lockouts (
+    kind VARCHAR(10) NOT NULL CHECK (kind IN ('account', 'ip')),
+    subject TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    failure_count INTEGER NOT NULL,
+    locked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    locked_until TIMESTAMPTZ NOT NULL,
+    unlocked_at TIMESTAMPTZ,
+    unlocked_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    PRIMARY KEY (kind, subject)
+);
+
+CREATE INDEX idx_security_lockouts_active
+    ON security_lockouts(locked_until) WHERE unlocked_at IS NULL;
+
+COMMENT ON TABLE security_attempts IS 'Failed login and suspicious request counters used while Redis is unavailable';
+COMMENT ON TABLE security_lockouts IS 'Account lockouts and IP blocks shared by every API replica';
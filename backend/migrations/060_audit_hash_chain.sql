@@ -0,0 +1,103 @@
+-- Tamper-evident audit log
+-- Every row inserted into audit_log from here on joins its tenant's hash
+-- chain: it gets the next sequence number, a SHA-256 over its content, and an
+-- entry hash over the previous entry's hash and its own content hash. The
+-- chain is built by a trigger so every writer is covered, including raw SQL
+-- and other triggers. Editing, deleting or reordering a chained row breaks
+-- the chain; signed checkpoints of each head (written by the API) also catch
+-- a truncated tail or a rewritten chain.
+--
+-- GDPR anonymisation may still rewrite personal data in a chained row, but
+-- must set redacted_at; verification then checks the row's links only.
+-- Rows written before this migration keep a NULL chain_seq and aren't chained.
+
+ALTER TABLE audit_log ADD COLUMN tenant_id TEXT NOT NULL DEFAULT 'default';
+ALTER TABLE audit_log ADD COLUMN chain_seq BIGINT;
+ALTER TABLE audit_log ADD COLUMN prev_hash CHAR(64);
+ALTER TABLE audit_log ADD COLUMN content_hash CHAR(64);
+ALTER TABLE audit_log ADD COLUMN entry_hash CHAR(64);
+ALTER TABLE audit_log ADD COLUMN redacted_at TIMESTAMPTZ;
+
+CREATE UNIQUE INDEX idx_audit_log_chain
+    ON audit_log(tenant_id, chain_seq) WHERE chain_seq IS NOT NULL;
+
+-- Last entry of each chain; the row lock serialises appends per tenant
+CREATE TABLE audit_chain_heads (
+    tenant_id TEXT PRIMARY KEY,
+    last_seq BIGINT NOT NULL,
+    last_hash CHAR(64) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE audit_checkpoints (
+    id BIGSERIAL PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    chain_seq BIGINT NOT NULL,
+    entry_hash CHAR(64) NOT NULL,
+    key_id VARCHAR(16) NOT NULL,               -- identifies the signing key
+    signature CHAR(64) NOT NULL,               -- hex HMAC-SHA256
+    created_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (tenant_id, chain_seq)
+);
+
+-- Canonical content of an entry. The verifier in the API builds the same
+-- array itself rather than calling this function.
+CREATE OR REPLACE FUNCTION audit_log_content(entry audit_log)
+RETURNS TEXT AS $$
+    SELECT json_build_array(
+        entry.tenant_id,
+        entry.chain_seq,
+        entry.event_type,
+        entry.severity,
+        entry.user_id,
+        entry.session_id,
+        host(entry.ip_address),
+        entry.user_agent,
+        entry.resource_type,
+        entry.resource_id,
+        entry.action,
+        entry.details,
+        entry.before_state,
+        entry.after_state,
+        entry.old_subject_type,
+        entry.old_subject_id,
+        to_char(entry.timestamp AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US"Z"'),
+        entry.correlation_id
+    )::text
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION audit_log_chain_append()
+RETURNS TRIGGER AS $$
+DECLARE
+    head audit_chain_heads%ROWTYPE;
+BEGIN
+    NEW.timestamp := COALESCE(NEW.timestamp, NOW());
+    NEW.redacted_at := NULL;
+
+    INSERT INTO audit_chain_heads (tenant_id, last_seq, last_hash)
+    VALUES (NEW.tenant_id, 0, repeat('0', 64))
+    ON CONFLICT (tenant_id) DO NOTHING;
+
+    SELECT * INTO head FROM audit_chain_heads WHERE tenant_id = NEW.tenant_id FOR UPDATE;
+
+    NEW.chain_seq := head.last_seq + 1;
+    NEW.prev_hash := head.last_hash;
+    NEW.content_hash := encode(sha256(convert_to(audit_log_content(NEW), 'UTF8')), 'hex');
+    NEW.entry_hash := encode(sha256(convert_to(NEW.prev_hash || NEW.content_hash, 'UTF8')), 'hex');
+
+    UPDATE audit_chain_heads
+    SET last_seq = NEW.chain_seq, last_hash = NEW.entry_hash, updated_at = NOW()
+    WHERE tenant_id = NEW.tenant_id;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_chain_trigger
+    BEFORE INSERT ON audit_log
+    FOR EACH ROW
+    EXECUTE FUNCTION audit_log_chain_append();
+
+COMMENT ON COLUMN audit_log.redacted_at IS 'Set when personal data in a chained entry was anonymised; its content hash no longer matches';
+COMMENT ON TABLE audit_chain_heads IS 'Latest sequence number and entry hash of each tenant''s audit chain';
+COMMENT ON TABLE audit_checkpoints IS 'HMAC-signed snapshots of audit chain heads';
@@ -0,0 +1,39 @@
+-- Asynchronous GDPR data exports and erasure requests
+--
+-- data_export_requests becomes the queue for both request types. A worker
+-- claims pending rows, builds the zipped export (kept here so any replica
+-- can serve the download) or erases the account, and records the outcome.
+-- Erasure requests outlive the account they erased, so the user reference
+-- is nulled instead of cascading.
+
+ALTER TABLE data_export_requests
+    DROP CONSTRAINT IF EXISTS data_export_requests_user_id_fkey;
+ALTER TABLE data_export_requests
+    ADD CONSTRAINT data_export_requests_user_id_fkey
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE data_export_requests ADD COLUMN reason TEXT;
+ALTER TABLE data_export_requests ADD COLUMN started_at TIMESTAMPTZ;
+ALTER TABLE data_export_requests ADD COLUMN attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE data_export_requests ADD COLUMN error TEXT;
+-- Export manifest, or the cleanup summary of an erasure
+ALTER TABLE data_export_requests ADD COLUMN manifest JSONB;
+ALTER TABLE data_export_requests ADD COLUMN archive BYTEA;
+ALTER TABLE data_export_requests ADD COLUMN archive_sha256 CHAR(64);
+ALTER TABLE data_export_requests ADD COLUMN downloaded_at TIMESTAMPTZ;
+
+UPDATE data_export_requests SET status = 'pending' WHERE status IS NULL;
+ALTER TABLE data_export_requests ALTER COLUMN status SET NOT NULL;
+ALTER TABLE data_export_requests ALTER COLUMN requested_at SET NOT NULL;
+
+-- One open request of each type per user
+CREATE UNIQUE INDEX idx_data_export_requests_open
+    ON data_export_requests(user_id, request_type)
+    WHERE status IN ('pending', 'processing');
+
+CREATE INDEX idx_data_export_requests_queue
+    ON data_export_requests(requested_at)
+    WHERE status = 'pending';
+
+COMMENT ON COLUMN data_export_requests.archive IS 'Zipped export; cleared when the download link expires';
+COMMENT ON COLUMN data_export_requests.manifest IS 'Export manifest or erasure cleanup summary';
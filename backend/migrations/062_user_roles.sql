@@ -0,0 +1,23 @@
+-- Role grants for the permission model
+--
+-- A user holds the implicit 'user' role plus any grants recorded here. Each
+-- role maps to a fixed set of named permissions in code; grants and
+-- revocations go through the admin API and are written to the audit log.
+
+CREATE TABLE user_roles (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL CHECK (role IN ('moderator', 'admin')),
+    granted_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    granted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, role)
+);
+
+CREATE INDEX idx_user_roles_role ON user_roles(role);
+
+-- Carry over roles set directly on the users table
+INSERT INTO user_roles (user_id, role)
+SELECT id, role FROM users WHERE role IN ('moderator', 'admin')
+ON CONFLICT DO NOTHING;
+
+COMMENT ON TABLE user_roles IS 'Roles granted to users on top of the implicit user role';
+COMMENT ON COLUMN users.role IS 'Deprecated: superseded by user_roles';
@@ -0,0 +1,89 @@
+-- Household accounts for families sharing one streaming plan
+--
+-- A household has one owner and any number of members and children, up to
+-- the plan size enforced in code. Each user belongs to at most one
+-- household. Owners and members curate a shared blocklist that is merged
+-- into every member's effective blocklist; owners can additionally enforce
+-- blocks on individual children, which the child cannot remove. Enforcement
+-- still runs per user against that user's own connected accounts, reading
+-- the effective_artist_blocks view instead of user_artist_blocks.
+
+CREATE TYPE household_role AS ENUM ('owner', 'member', 'child');
+
+CREATE TABLE households (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE household_members (
+    household_id UUID NOT NULL REFERENCES households(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role household_role NOT NULL,
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (household_id, user_id)
+);
+
+-- One household per user, and exactly one owner per household
+CREATE UNIQUE INDEX idx_household_members_user ON household_members(user_id);
+CREATE UNIQUE INDEX idx_household_members_owner
+    ON household_members(household_id)
+    WHERE role = 'owner';
+
+CREATE TABLE household_invites (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    household_id UUID NOT NULL REFERENCES households(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    role household_role NOT NULL CHECK (role IN ('member', 'child')),
+    invited_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    accepted_at TIMESTAMPTZ,
+    declined_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+-- One open invite per address per household
+CREATE UNIQUE INDEX idx_household_invites_open
+    ON household_invites(household_id, LOWER(email))
+    WHERE accepted_at IS NULL AND declined_at IS NULL AND revoked_at IS NULL;
+CREATE INDEX idx_household_invites_email ON household_invites(LOWER(email));
+
+CREATE TABLE household_artist_blocks (
+    household_id UUID NOT NULL REFERENCES households(id) ON DELETE CASCADE,
+    artist_id UUID NOT NULL REFERENCES artists(id) ON DELETE CASCADE,
+    note TEXT,
+    added_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (household_id, artist_id)
+);
+
+-- Blocks the owner enforces on a child; removed with the membership
+CREATE TABLE household_enforced_blocks (
+    household_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    artist_id UUID NOT NULL REFERENCES artists(id) ON DELETE CASCADE,
+    note TEXT,
+    added_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, artist_id),
+    FOREIGN KEY (household_id, user_id)
+        REFERENCES household_members(household_id, user_id) ON DELETE CASCADE
+);
+
+-- Everything blocked for a user: their own list, their household's shared
+-- list and any blocks enforced on them. An artist can appear once per source.
+CREATE VIEW effective_artist_blocks AS
+    SELECT uab.user_id, uab.artist_id, uab.note, 'personal'::text AS source
+    FROM user_artist_blocks uab
+    UNION ALL
+    SELECT hm.user_id, hab.artist_id, hab.note, 'household'::text AS source
+    FROM household_artist_blocks hab
+    JOIN household_members hm ON hm.household_id = hab.household_id
+    UNION ALL
+    SELECT heb.user_id, heb.artist_id, heb.note, 'enforced'::text AS source
+    FROM household_enforced_blocks heb;
+
+COMMENT ON VIEW effective_artist_blocks IS 'Personal, household and enforced artist blocks per user; read by enforcement';
@@ -0,0 +1,47 @@
+-- Email verification and change-of-email
+--
+-- Registration mails a verification link; `users.email_verified` flips once
+-- it's followed. Changing the address opens an email_change_requests row and
+-- mails one link to the current address and one to the new address; the
+-- change is applied only after both are confirmed. Tokens are single use and
+-- stored as SHA-256 hashes.
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email_verified_at TIMESTAMPTZ;
+UPDATE users SET email_verified = FALSE WHERE email_verified IS NULL;
+UPDATE users SET email_verified_at = updated_at
+WHERE email_verified = TRUE AND email_verified_at IS NULL;
+
+CREATE TYPE email_token_purpose AS ENUM ('verify', 'change_current', 'change_new');
+
+CREATE TABLE email_change_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    old_email VARCHAR(255) NOT NULL,
+    new_email VARCHAR(255) NOT NULL,
+    current_confirmed_at TIMESTAMPTZ,
+    new_confirmed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    completed_at TIMESTAMPTZ,
+    cancelled_at TIMESTAMPTZ
+);
+
+-- At most one change in flight per user
+CREATE UNIQUE INDEX idx_email_change_requests_open
+    ON email_change_requests(user_id)
+    WHERE completed_at IS NULL AND cancelled_at IS NULL;
+
+CREATE TABLE email_tokens (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose email_token_purpose NOT NULL,
+    -- The address the token was mailed to and confirms
+    email VARCHAR(255) NOT NULL,
+    change_request_id UUID REFERENCES email_change_requests(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    CHECK ((purpose = 'verify') = (change_request_id IS NULL))
+);
+
+CREATE INDEX idx_email_tokens_user ON email_tokens(user_id, purpose, created_at DESC);
@@ -0,0 +1,48 @@
+-- Link evidence to the story clusters of 044
+-- Evidence rows take the story of the news article with the same URL, whichever
+-- of the two is written first, so calculate_evidence_score can count each
+-- story once.
+
+CREATE OR REPLACE FUNCTION offense_evidence_set_story()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.story_id IS NULL AND NEW.url IS NOT NULL THEN
+        SELECT story_id INTO NEW.story_id
+        FROM news_articles
+        WHERE url = NEW.url;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_offense_evidence_set_story ON offense_evidence;
+CREATE TRIGGER trg_offense_evidence_set_story
+    BEFORE INSERT OR UPDATE OF url ON offense_evidence
+    FOR EACH ROW EXECUTE FUNCTION offense_evidence_set_story();
+
+CREATE OR REPLACE FUNCTION news_article_propagate_story()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE offense_evidence
+    SET story_id = NEW.story_id
+    WHERE url = NEW.url
+      AND story_id IS DISTINCT FROM NEW.story_id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_news_article_propagate_story ON news_articles;
+CREATE TRIGGER trg_news_article_propagate_story
+    AFTER INSERT OR UPDATE OF story_id ON news_articles
+    FOR EACH ROW
+    WHEN (NEW.story_id IS NOT NULL)
+    EXECUTE FUNCTION news_article_propagate_story();
+
+UPDATE offense_evidence e
+SET story_id = a.story_id
+FROM news_articles a
+WHERE a.url = e.url
+  AND a.story_id IS NOT NULL
+  AND e.story_id IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_offense_evidence_url ON offense_evidence(url);
@@ -0,0 +1,38 @@
+-- Recency only follows verified timeline events
+-- Pending proposals (e.g. from the news pipeline) are unreviewed and must not
+-- make an offense "newer"; this matches how DerivedLegalState ignores them.
+
+CREATE OR REPLACE FUNCTION calculate_recency_score(p_artist_id UUID)
+RETURNS FLOAT AS $$
+DECLARE
+    most_recent DATE;
+    years_ago FLOAT;
+BEGIN
+    SELECT MAX(COALESCE(ev.latest_event, o.incident_date)) INTO most_recent
+    FROM artist_offenses o
+    LEFT JOIN LATERAL (
+        SELECT MAX(e.event_date) AS latest_event
+        FROM offense_events e
+        WHERE e.offense_id = o.id
+        AND e.status = 'verified'
+        AND e.event_type NOT IN ('acquittal', 'dismissal', 'apology')
+    ) ev ON TRUE
+    WHERE o.artist_id = p_artist_id
+    AND o.status IN ('verified', 'pending');
+
+    IF most_recent IS NULL THEN
+        RETURN 0;
+    END IF;
+
+    years_ago := (CURRENT_DATE - most_recent)::FLOAT / 365.25;
+
+    -- Decay: 1.0 if <1yr, 0.75 if <3yr, 0.5 if <5yr, 0.25 if <10yr, 0.1 if older
+    RETURN CASE
+        WHEN years_ago < 1 THEN 1.0
+        WHEN years_ago < 3 THEN 0.75
+        WHEN years_ago < 5 THEN 0.5
+        WHEN years_ago < 10 THEN 0.25
+        ELSE 0.1
+    END;
+END;
+$$ LANGUAGE plpgsql;
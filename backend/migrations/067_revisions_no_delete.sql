@@ -0,0 +1,37 @@
+-- Revisions cannot be deleted either
+-- 047 only blocked UPDATE, and its ON DELETE CASCADE foreign keys let deleting
+-- an offense or evidence row silently erase its history. Revisions now block
+-- deletes, and an offense or evidence row with history cannot be deleted.
+
+CREATE OR REPLACE FUNCTION prevent_revision_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'Revisions are append-only and cannot be modified or deleted';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER offense_revisions_no_delete
+    BEFORE DELETE ON offense_revisions
+    FOR EACH ROW EXECUTE FUNCTION prevent_revision_update();
+
+CREATE TRIGGER evidence_revisions_no_delete
+    BEFORE DELETE ON evidence_revisions
+    FOR EACH ROW EXECUTE FUNCTION prevent_revision_update();
+
+CREATE TRIGGER offense_revisions_no_truncate
+    BEFORE TRUNCATE ON offense_revisions
+    FOR EACH STATEMENT EXECUTE FUNCTION prevent_revision_update();
+
+CREATE TRIGGER evidence_revisions_no_truncate
+    BEFORE TRUNCATE ON evidence_revisions
+    FOR EACH STATEMENT EXECUTE FUNCTION prevent_revision_update();
+
+ALTER TABLE offense_revisions
+    DROP CONSTRAINT offense_revisions_offense_id_fkey,
+    ADD CONSTRAINT offense_revisions_offense_id_fkey
+        FOREIGN KEY (offense_id) REFERENCES artist_offenses(id) ON DELETE RESTRICT;
+
+ALTER TABLE evidence_revisions
+    DROP CONSTRAINT evidence_revisions_evidence_id_fkey,
+    ADD CONSTRAINT evidence_revisions_evidence_id_fkey
+        FOREIGN KEY (evidence_id) REFERENCES offense_evidence(id) ON DELETE RESTRICT;
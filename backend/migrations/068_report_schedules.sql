@@ -0,0 +1,36 @@
+-- Analytics report schedules and generated reports
+--
+-- Both belong to the user who created them (reports generated by a schedule
+-- belong to the schedule's owner) and are only visible to that user. They
+-- used to live in process memory, so a restart dropped every schedule and
+-- other replicas never saw them. A report row is deleted once it expires and
+-- its files are removed.
+
+CREATE TABLE report_schedules (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    report_type TEXT NOT NULL,
+    format TEXT NOT NULL,
+    schedule JSONB NOT NULL,
+    recipients TEXT[] NOT NULL DEFAULT '{}',
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_run TIMESTAMPTZ,
+    next_run TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_report_schedules_owner ON report_schedules(owner_id);
+CREATE INDEX idx_report_schedules_due ON report_schedules(next_run) WHERE enabled;
+
+CREATE TABLE generated_reports (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    schedule_id UUID REFERENCES report_schedules(id) ON DELETE SET NULL,
+    report JSONB NOT NULL,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_generated_reports_owner ON generated_reports(owner_id, created_at DESC);
+CREATE INDEX idx_generated_reports_expiry ON generated_reports(expires_at)
+    WHERE expires_at IS NOT NULL;
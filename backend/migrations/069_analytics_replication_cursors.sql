@@ -0,0 +1,37 @@
+-- Per-replica cursors over the analytics change log
+--
+-- The replication job used to delete the log entries it applied, so with more
+-- than one DuckDB replica each change reached only whichever replica read it
+-- first. Entries now stay in the log until they age out, and every replica
+-- (consumer) keeps its own cursor per table.
+--
+-- Each entry records the id of the transaction that wrote it. A consumer
+-- reads entries from transactions below the oldest transaction still running
+-- (pg_snapshot_xmin), so every such transaction has finished and its entries
+-- are visible, then moves its cursor to that horizon. Entries from
+-- transactions still running wait for the next run. Applying an entry twice
+-- is harmless.
+--
+-- Entries older than the retention period are deleted, along with cursors
+-- that haven't moved in that time; a replica without a cursor re-snapshots
+-- its tables. Existing replicas re-snapshot once after this migration.
+
+ALTER TABLE analytics_change_log
+    ADD COLUMN IF NOT EXISTS xact_id xid8 NOT NULL DEFAULT pg_current_xact_id();
+
+CREATE INDEX IF NOT EXISTS idx_analytics_change_log_xact
+    ON analytics_change_log(table_name, xact_id, id);
+CREATE INDEX IF NOT EXISTS idx_analytics_change_log_changed_at
+    ON analytics_change_log(changed_at);
+
+CREATE TABLE analytics_replication_cursors (
+    consumer_id VARCHAR(128) NOT NULL,
+    table_name VARCHAR(64) NOT NULL,
+    -- Every entry from a transaction below this id has been applied
+    applied_before xid8 NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (consumer_id, table_name)
+);
+
+COMMENT ON TABLE analytics_change_log IS 'Keys of changed rows, read by each DuckDB analytics replica until they age out';
+COMMENT ON TABLE analytics_replication_cursors IS 'How far each DuckDB analytics replica has applied the change log, per table';
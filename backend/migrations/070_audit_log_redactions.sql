@@ -0,0 +1,32 @@
+-- Redactions of chained audit entries are recorded
+-- Chain verification skips the content check of an entry with redacted_at
+-- set, so setting it was enough to hide an edit. Every redaction now also
+-- writes a row here; rows can't be changed or removed, and verification
+-- flags a redacted entry that has no record.
+
+CREATE TABLE audit_log_redactions (
+    id BIGSERIAL PRIMARY KEY,
+    tenant_id TEXT NOT NULL,
+    chain_seq BIGINT NOT NULL,
+    reason TEXT NOT NULL,
+    redacted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_log_redactions_entry ON audit_log_redactions(tenant_id, chain_seq);
+
+CREATE OR REPLACE FUNCTION prevent_audit_redaction_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'Audit redaction records are append-only and cannot be modified or deleted';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_redactions_no_update
+    BEFORE UPDATE OR DELETE ON audit_log_redactions
+    FOR EACH ROW EXECUTE FUNCTION prevent_audit_redaction_change();
+
+CREATE TRIGGER audit_log_redactions_no_truncate
+    BEFORE TRUNCATE ON audit_log_redactions
+    FOR EACH STATEMENT EXECUTE FUNCTION prevent_audit_redaction_change();
+
+COMMENT ON TABLE audit_log_redactions IS 'Append-only record of each anonymised audit chain entry';
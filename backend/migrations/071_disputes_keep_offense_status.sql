@@ -0,0 +1,23 @@
+-- Open disputes no longer change the offense status
+-- Disputes used to move an offense to 'disputed' as soon as they were
+-- submitted. The status now only changes when a moderator upholds a dispute,
+-- so offenses parked in 'disputed' by an open dispute get their status back.
+
+UPDATE artist_offenses o
+SET status = d.previous_status, updated_at = NOW()
+FROM (
+    SELECT DISTINCT ON (offense_id) offense_id, previous_status
+    FROM offense_disputes
+    WHERE status = 'open'
+    ORDER BY offense_id, created_at
+) d
+WHERE o.id = d.offense_id
+  AND o.status = 'disputed'
+  AND d.previous_status <> 'disputed';
+
+CREATE INDEX idx_offense_disputes_open_by_user
+    ON offense_disputes(submitted_by)
+    WHERE status = 'open';
+
+COMMENT ON COLUMN offense_disputes.previous_status IS 'Offense status when the dispute was opened';
+COMMENT ON TABLE offense_disputes IS 'User disputes of offenses; upholding one rejects the offense, an open one changes nothing';
@@ -4,19 +4,25 @@
 //! Uses DuckDB for high-performance analytics queries.
 
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::models::AuthenticatedUser;
 use crate::{AppError, AppState, Result};
 use ndith_analytics::analytics_service::{
     dashboard::TimeRange,
-    reporting::{ReportFormat, ReportType},
+    reporting::{
+        Report, ReportFormat, ReportRequest, ReportSchedule, ReportStatus, ReportType,
+        ScheduledReport,
+    },
 };
+use ndith_analytics::ReportScheduler;
 
 /// Query parameters for dashboard requests
 #[derive(Debug, Deserialize)]
@@ -58,6 +64,12 @@ pub struct TrendQuery {
     /// Maximum results to return
     #[serde(default = "default_limit")]
     pub limit: i32,
+    /// Days for the comparison period (defaults to period_days)
+    pub comparison_days: Option<i32>,
+    /// Earlier periods used as the spike detection baseline
+    pub baseline_periods: Option<i32>,
+    /// Z-score above which mentions count as a spike
+    pub spike_threshold: Option<f64>,
 }
 
 fn default_period() -> i32 {
@@ -443,9 +455,50 @@ pub async fn get_system_health_handler(
 // Trend Analysis Endpoints
 // ============================================================================
 
+/// Trend service over the DuckDB store, configured from the query
+fn trend_service(
+    state: &AppState,
+    query: &TrendQuery,
+) -> Result<ndith_analytics::TrendAnalysisService> {
+    let duckdb =
+        state
+            .analytics_db
+            .clone()
+            .ok_or_else(|| AppError::ExternalServiceUnavailable {
+                service: "analytics_db".to_string(),
+            })?;
+
+    if !(1..=90).contains(&query.period_days) {
+        return Err(AppError::InvalidFieldValue {
+            field: "period_days".to_string(),
+            message: "Must be between 1 and 90".to_string(),
+        });
+    }
+
+    let defaults = ndith_analytics::TrendConfig::default();
+    let config = ndith_analytics::TrendConfig {
+        current_period_days: query.period_days,
+        comparison_period_days: query
+            .comparison_days
+            .unwrap_or(query.period_days)
+            .clamp(1, 90),
+        max_results: query.limit,
+        baseline_periods: query
+            .baseline_periods
+            .unwrap_or(defaults.baseline_periods)
+            .clamp(1, 12),
+        spike_z_threshold: query.spike_threshold.unwrap_or(defaults.spike_z_threshold),
+        ..defaults
+    };
+
+    Ok(ndith_analytics::TrendAnalysisService::with_config(
+        duckdb, config,
+    ))
+}
+
 /// Get trend summary
 pub async fn get_trend_summary_handler(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     _user: AuthenticatedUser,
     Query(query): Query<TrendQuery>,
 ) -> Result<Json<serde_json::Value>> {
@@ -455,115 +508,132 @@ pub async fn get_trend_summary_handler(
         "Get trend summary request"
     );
 
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "data": {
-            "period": format!("Last {} days vs previous {} days", query.period_days, query.period_days),
-            "top_rising_artists": [],
-            "top_falling_artists": [],
-            "new_offense_trends": [],
-            "content_volume_trend": {
-                "metric_name": "content_volume",
-                "current_value": 0.0,
-                "previous_value": 0.0,
-                "change_percentage": 0.0,
-                "direction": "stable"
-            },
-            "user_activity_trend": {
-                "metric_name": "user_activity",
-                "current_value": 0.0,
-                "previous_value": 0.0,
-                "change_percentage": 0.0,
-                "direction": "stable"
-            }
+    let service = trend_service(&state, &query)?;
+
+    match service.get_trend_summary().await {
+        Ok(summary) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": summary
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get trend summary: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
         }
-    })))
+    }
 }
 
 /// Get artist trend
 pub async fn get_artist_trend_handler(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(artist_id): Path<Uuid>,
     _user: AuthenticatedUser,
+    Query(query): Query<TrendQuery>,
 ) -> Result<Json<serde_json::Value>> {
     tracing::info!(artist_id = %artist_id, "Get artist trend request");
 
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "data": {
-            "artist_id": artist_id,
-            "artist_name": "",
-            "current_mentions": 0,
-            "previous_mentions": 0,
-            "change_percentage": 0.0,
-            "direction": "stable",
-            "offense_trend": {
-                "current_count": 0,
-                "previous_count": 0,
-                "change_percentage": 0.0,
-                "direction": "stable",
-                "top_categories": []
-            },
-            "sentiment_trend": {
-                "current_score": 0.0,
-                "previous_score": 0.0,
-                "change": 0.0,
-                "direction": "stable"
-            },
-            "mention_history": []
+    let service = trend_service(&state, &query)?;
+
+    match service.get_artist_trend(artist_id).await {
+        Ok(trend) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": trend
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get artist trend: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
         }
-    })))
+    }
 }
 
 /// Get platform trends
 pub async fn get_platform_trends_handler(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     _user: AuthenticatedUser,
+    Query(query): Query<TrendQuery>,
 ) -> Result<Json<serde_json::Value>> {
     tracing::info!("Get platform trends request");
 
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "data": {
-            "platforms": []
+    let service = trend_service(&state, &query)?;
+
+    match service.get_platform_trends().await {
+        Ok(platforms) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": {
+                "platforms": platforms
+            }
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get platform trends: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
         }
-    })))
+    }
 }
 
 /// Get rising artists
 pub async fn get_rising_artists_handler(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     _user: AuthenticatedUser,
     Query(query): Query<TrendQuery>,
 ) -> Result<Json<serde_json::Value>> {
     tracing::info!(limit = query.limit, "Get rising artists request");
 
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "data": {
-            "period_days": query.period_days,
-            "artists": [],
-            "total": 0
+    let service = trend_service(&state, &query)?;
+
+    match service.get_rising_artists(query.limit).await {
+        Ok(artists) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": {
+                "period_days": query.period_days,
+                "total": artists.len(),
+                "artists": artists
+            }
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get rising artists: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
         }
-    })))
+    }
 }
 
 /// Get falling artists
 pub async fn get_falling_artists_handler(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     _user: AuthenticatedUser,
     Query(query): Query<TrendQuery>,
 ) -> Result<Json<serde_json::Value>> {
     tracing::info!(limit = query.limit, "Get falling artists request");
 
-    Ok(Json(serde_json::json!({
-        "success": true,
-        "data": {
-            "period_days": query.period_days,
-            "artists": [],
-            "total": 0
+    let service = trend_service(&state, &query)?;
+
+    match service.get_falling_artists(query.limit).await {
+        Ok(artists) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": {
+                "period_days": query.period_days,
+                "total": artists.len(),
+                "artists": artists
+            }
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get falling artists: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
         }
-    })))
+    }
 }
 
 // ============================================================================
@@ -619,10 +689,37 @@ pub async fn get_report_types_handler(
     })))
 }
 
+fn report_scheduler(state: &AppState) -> Result<Arc<ReportScheduler>> {
+    state
+        .report_scheduler
+        .clone()
+        .ok_or_else(|| AppError::ExternalServiceUnavailable {
+            service: "analytics_db".to_string(),
+        })
+}
+
+fn report_store_error(e: anyhow::Error) -> AppError {
+    tracing::error!("Report store error: {:#}", e);
+    AppError::Internal {
+        message: Some(e.to_string()),
+    }
+}
+
+/// One of the user's reports; other users' reports are not found
+async fn owned_report(state: &AppState, user_id: Uuid, report_id: Uuid) -> Result<Report> {
+    report_scheduler(state)?
+        .get_report(user_id, report_id)
+        .await
+        .map_err(report_store_error)?
+        .ok_or_else(|| AppError::NotFound {
+            resource: "Report".to_string(),
+        })
+}
+
 /// Generate a report
 pub async fn generate_report_handler(
-    State(_state): State<AppState>,
-    _user: AuthenticatedUser,
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
     Json(request): Json<GenerateReportRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>)> {
     tracing::info!(
@@ -631,79 +728,244 @@ pub async fn generate_report_handler(
         "Generate report request"
     );
 
-    let report_type = parse_report_type(&request.report_type);
-    let format = parse_report_format(&request.format);
-    let time_range = parse_time_range(&request.time_range);
+    let scheduler = report_scheduler(&state)?;
 
-    // Return accepted response with report ID
-    let report_id = Uuid::new_v4();
+    let report_request = ReportRequest {
+        report_type: parse_report_type(&request.report_type),
+        format: parse_report_format(&request.format),
+        time_range: parse_time_range(&request.time_range),
+        include_details: request.include_details,
+        filters: None,
+    };
 
-    Ok((
-        StatusCode::ACCEPTED,
-        Json(serde_json::json!({
-            "success": true,
-            "message": "Report generation started",
-            "data": {
-                "report_id": report_id,
-                "report_type": format!("{:?}", report_type),
-                "format": format!("{:?}", format),
-                "time_range": format!("{:?}", time_range),
-                "status": "generating",
-                "estimated_completion": chrono::Utc::now() + chrono::Duration::seconds(30)
-            }
-        })),
-    ))
+    match scheduler.generate(user.id, report_request).await {
+        Ok(report) if report.status == ReportStatus::Ready => Ok((
+            StatusCode::CREATED,
+            Json(serde_json::json!({
+                "success": true,
+                "data": report
+            })),
+        )),
+        Ok(report) => Ok((
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "success": false,
+                "error": report.error,
+                "data": report
+            })),
+        )),
+        Err(e) => {
+            tracing::error!("Failed to generate report: {}", e);
+            Ok((
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "success": false,
+                    "error": e.to_string()
+                })),
+            ))
+        }
+    }
+}
+
+/// List generated reports
+pub async fn list_reports_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let reports = report_scheduler(&state)?
+        .list_reports(user.id)
+        .await
+        .map_err(report_store_error)?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "total": reports.len(),
+            "reports": reports
+        }
+    })))
 }
 
 /// Get report status
 pub async fn get_report_status_handler(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(report_id): Path<Uuid>,
-    _user: AuthenticatedUser,
+    user: AuthenticatedUser,
 ) -> Result<Json<serde_json::Value>> {
     tracing::info!(report_id = %report_id, "Get report status request");
 
-    // In production, would look up actual report status
+    let report = owned_report(&state, user.id, report_id).await?;
+
     Ok(Json(serde_json::json!({
         "success": true,
-        "data": {
-            "report_id": report_id,
-            "status": "ready",
-            "generated_at": chrono::Utc::now().to_rfc3339(),
-            "expires_at": (chrono::Utc::now() + chrono::Duration::days(7)).to_rfc3339(),
-            "file_path": null,
-            "file_size_bytes": null,
-            "summary": {
-                "total_records": 0,
-                "key_metrics": [],
-                "highlights": []
-            }
-        }
+        "data": report
     })))
 }
 
-/// Download a report
+/// Query for report downloads
+#[derive(Debug, Deserialize)]
+pub struct DownloadReportQuery {
+    /// Section to download for CSV and Parquet reports (defaults to the first)
+    pub section: Option<String>,
+}
+
+/// Download a report file
 pub async fn download_report_handler(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Path(report_id): Path<Uuid>,
-    _user: AuthenticatedUser,
-) -> Result<Json<serde_json::Value>> {
+    user: AuthenticatedUser,
+    Query(query): Query<DownloadReportQuery>,
+) -> Result<Response> {
     tracing::info!(report_id = %report_id, "Download report request");
 
-    // In production, would return actual file or redirect to storage URL
+    let report = owned_report(&state, user.id, report_id).await?;
+
+    if report.status != ReportStatus::Ready {
+        return Err(AppError::OperationNotAllowed {
+            reason: format!("Report is {:?}", report.status).to_lowercase(),
+        });
+    }
+
+    let file = match query.section.as_deref() {
+        Some(section) => report
+            .files
+            .iter()
+            .find(|f| f.section.as_deref() == Some(section)),
+        None => report.files.first(),
+    }
+    .ok_or_else(|| AppError::NotFound {
+        resource: "Report file".to_string(),
+    })?;
+
+    let bytes = tokio::fs::read(&file.path).await.map_err(|e| {
+        tracing::error!(report_id = %report_id, error = %e, "Failed to read report file");
+        AppError::NotFound {
+            resource: "Report file".to_string(),
+        }
+    })?;
+
+    let filename = std::path::Path::new(&file.path)
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_else(|| format!("{}.{}", report_id, report.format.extension()));
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                report.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// Request to schedule a recurring report
+#[derive(Debug, Deserialize)]
+pub struct CreateReportScheduleRequest {
+    pub report_type: String,
+    #[serde(default = "default_format")]
+    pub format: String,
+    pub schedule: ReportSchedule,
+    #[serde(default)]
+    pub recipients: Vec<String>,
+}
+
+/// Schedule a recurring report
+pub async fn create_report_schedule_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<CreateReportScheduleRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    tracing::info!(
+        user_id = %user.id,
+        report_type = %request.report_type,
+        "Create report schedule request"
+    );
+
+    let scheduler = report_scheduler(&state)?;
+
+    let hour = match request.schedule {
+        ReportSchedule::Daily { hour }
+        | ReportSchedule::Weekly { hour, .. }
+        | ReportSchedule::Monthly { hour, .. } => hour,
+    };
+    if hour > 23 {
+        return Err(AppError::InvalidFieldValue {
+            field: "schedule.hour".to_string(),
+            message: "Must be between 0 and 23 (UTC)".to_string(),
+        });
+    }
+
+    let schedule = scheduler
+        .add_schedule(ScheduledReport {
+            id: Uuid::new_v4(),
+            owner_id: user.id,
+            report_type: parse_report_type(&request.report_type),
+            format: parse_report_format(&request.format),
+            schedule: request.schedule,
+            recipients: request.recipients,
+            enabled: true,
+            last_run: None,
+            next_run: None,
+        })
+        .await
+        .map_err(report_store_error)?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": schedule
+        })),
+    ))
+}
+
+/// List report schedules
+pub async fn list_report_schedules_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let schedules = report_scheduler(&state)?
+        .list_schedules(user.id)
+        .await
+        .map_err(report_store_error)?;
+
     Ok(Json(serde_json::json!({
         "success": true,
         "data": {
-            "report_id": report_id,
-            "download_url": format!("/api/v1/analytics/reports/{}/file", report_id),
-            "expires_at": (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339()
+            "schedules": schedules
         }
     })))
 }
 
+/// Delete a report schedule
+pub async fn delete_report_schedule_handler(
+    State(state): State<AppState>,
+    Path(schedule_id): Path<Uuid>,
+    user: AuthenticatedUser,
+) -> Result<StatusCode> {
+    let removed = report_scheduler(&state)?
+        .remove_schedule(user.id, schedule_id)
+        .await
+        .map_err(report_store_error)?;
+    if !removed {
+        return Err(AppError::NotFound {
+            resource: "Report schedule".to_string(),
+        });
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Export data to Parquet
 pub async fn export_to_parquet_handler(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     _user: AuthenticatedUser,
     Query(query): Query<ExportQuery>,
 ) -> Result<(StatusCode, Json<serde_json::Value>)> {
@@ -722,22 +984,33 @@ pub async fn export_to_parquet_handler(
         });
     }
 
-    let export_id = Uuid::new_v4();
-
-    Ok((
-        StatusCode::ACCEPTED,
-        Json(serde_json::json!({
-            "success": true,
-            "message": "Export started",
-            "data": {
-                "export_id": export_id,
-                "table": query.table,
-                "format": "parquet",
-                "status": "processing",
-                "estimated_completion": chrono::Utc::now() + chrono::Duration::seconds(60)
-            }
-        })),
-    ))
+    match report_scheduler(&state)?
+        .reporting()
+        .export_to_parquet(&query.table)
+        .await
+    {
+        Ok(file_path) => Ok((
+            StatusCode::CREATED,
+            Json(serde_json::json!({
+                "success": true,
+                "data": {
+                    "table": query.table,
+                    "format": "parquet",
+                    "file_path": file_path
+                }
+            })),
+        )),
+        Err(e) => {
+            tracing::error!("Failed to export {} to Parquet: {}", query.table, e);
+            Ok((
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "success": false,
+                    "error": e.to_string()
+                })),
+            ))
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -762,19 +1035,31 @@ pub struct TroubleScoreQuery {
     pub offset: i32,
     /// Filter by tier: low, moderate, high, critical
     pub min_tier: Option<String>,
+    /// Score with this profile version; defaults to the user's saved profile
+    pub profile_id: Option<Uuid>,
 }
 
 /// Get trouble score for a specific artist
 pub async fn get_artist_trouble_score_handler(
     State(state): State<AppState>,
     Path(artist_id): Path<Uuid>,
-    _user: AuthenticatedUser,
+    user: AuthenticatedUser,
+    Query(query): Query<ScoreProfileQuery>,
 ) -> Result<Json<serde_json::Value>> {
-    tracing::info!(artist_id = %artist_id, "Get artist trouble score request");
+    tracing::info!(artist_id = %artist_id, profile_id = ?query.profile_id, "Get artist trouble score request");
 
     let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
 
-    match service.get_artist_score(artist_id).await {
+    let score = match scoring_profile(&service, query.profile_id, user.id).await? {
+        Some(profile) => {
+            service
+                .get_artist_score_with_profile(artist_id, &profile)
+                .await
+        }
+        None => service.get_artist_score(artist_id).await,
+    };
+
+    match score {
         Ok(Some(score)) => Ok(Json(serde_json::json!({
             "success": true,
             "data": score
@@ -796,7 +1081,7 @@ pub async fn get_artist_trouble_score_handler(
 /// Get trouble score leaderboard
 pub async fn get_trouble_leaderboard_handler(
     State(state): State<AppState>,
-    _user: AuthenticatedUser,
+    user: AuthenticatedUser,
     Query(query): Query<TroubleScoreQuery>,
 ) -> Result<Json<serde_json::Value>> {
     tracing::info!(
@@ -808,6 +1093,37 @@ pub async fn get_trouble_leaderboard_handler(
 
     let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
 
+    if let Some(profile) = scoring_profile(&service, query.profile_id, user.id).await? {
+        let limit = (query.limit.max(0) + query.offset.max(0)) as usize;
+
+        return match service
+            .what_if(&profile.weights, profile.categories.as_deref(), limit)
+            .await
+        {
+            Ok(result) => Ok(Json(serde_json::json!({
+                "success": true,
+                "data": {
+                    "entries": result
+                        .leaderboard
+                        .into_iter()
+                        .skip(query.offset.max(0) as usize)
+                        .collect::<Vec<_>>(),
+                    "profile": ndith_analytics::ProfileRef::from(&profile),
+                    "distribution": result.distribution,
+                    "limit": query.limit,
+                    "offset": query.offset
+                }
+            }))),
+            Err(e) => {
+                tracing::error!("Failed to score leaderboard with profile: {}", e);
+                Ok(Json(serde_json::json!({
+                    "success": false,
+                    "error": e.to_string()
+                })))
+            }
+        };
+    }
+
     let min_tier = query
         .min_tier
         .as_ref()
@@ -843,13 +1159,22 @@ pub async fn get_trouble_leaderboard_handler(
 /// Get trouble tier distribution
 pub async fn get_tier_distribution_handler(
     State(state): State<AppState>,
-    _user: AuthenticatedUser,
+    user: AuthenticatedUser,
+    Query(query): Query<ScoreProfileQuery>,
 ) -> Result<Json<serde_json::Value>> {
-    tracing::info!("Get tier distribution request");
+    tracing::info!(profile_id = ?query.profile_id, "Get tier distribution request");
 
     let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
 
-    match service.get_tier_distribution().await {
+    let distribution = match scoring_profile(&service, query.profile_id, user.id).await? {
+        Some(profile) => service
+            .what_if(&profile.weights, profile.categories.as_deref(), 0)
+            .await
+            .map(|result| result.distribution),
+        None => service.get_tier_distribution().await,
+    };
+
+    match distribution {
         Ok(distribution) => Ok(Json(serde_json::json!({
             "success": true,
             "data": distribution
@@ -865,25 +1190,12 @@ pub async fn get_tier_distribution_handler(
 }
 
 /// Recalculate trouble scores (admin endpoint)
-/// Requires admin role for access
 pub async fn recalculate_trouble_scores_handler(
     State(state): State<AppState>,
     user: AuthenticatedUser,
-    claims: crate::models::Claims,
 ) -> Result<(StatusCode, Json<serde_json::Value>)> {
-    // Verify admin access
-    if !claims.has_admin_access() {
-        tracing::warn!(
-            user_id = %user.id,
-            role = ?claims.role,
-            "Unauthorized attempt to recalculate trouble scores - admin role required"
-        );
-        return Err(AppError::InsufficientPermissions);
-    }
-
     tracing::info!(
         user_id = %user.id,
-        role = ?claims.role,
         "Admin recalculating trouble scores"
     );
 
@@ -939,6 +1251,420 @@ pub async fn get_artist_score_history_handler(
     }
 }
 
+/// Query parameters for endpoints that score with a profile
+#[derive(Debug, Deserialize)]
+pub struct ScoreProfileQuery {
+    /// Profile version to score with; defaults to the user's saved profile
+    pub profile_id: Option<Uuid>,
+}
+
+/// Load a profile the user may use: shared, or their own
+async fn visible_profile(
+    service: &ndith_analytics::TroubleScoreService,
+    profile_id: Uuid,
+    user_id: Uuid,
+) -> Result<ndith_analytics::ScoreProfile> {
+    let profile = service.get_profile(profile_id).await.map_err(|e| {
+        tracing::error!("Failed to get score profile: {}", e);
+        AppError::Internal {
+            message: Some(e.to_string()),
+        }
+    })?;
+
+    match profile {
+        Some(profile)
+            if profile.owner_user_id.is_none() || profile.owner_user_id == Some(user_id) =>
+        {
+            Ok(profile)
+        }
+        _ => Err(AppError::NotFound {
+            resource: "score profile".to_string(),
+        }),
+    }
+}
+
+/// Profile to score with: the requested one, otherwise the user's saved
+/// profile. None when the stored scores (default profile) apply.
+async fn scoring_profile(
+    service: &ndith_analytics::TroubleScoreService,
+    requested: Option<Uuid>,
+    user_id: Uuid,
+) -> Result<Option<ndith_analytics::ScoreProfile>> {
+    if let Some(profile_id) = requested {
+        return visible_profile(service, profile_id, user_id)
+            .await
+            .map(Some);
+    }
+
+    let saved = service.get_user_profile(user_id).await.map_err(|e| {
+        tracing::error!("Failed to get score profile: {}", e);
+        AppError::Internal {
+            message: Some(e.to_string()),
+        }
+    })?;
+
+    Ok(saved.filter(|profile| !profile.is_default))
+}
+
+/// Reject categories that are not offense categories
+async fn validate_categories(
+    service: &ndith_analytics::TroubleScoreService,
+    categories: Option<&[String]>,
+) -> Result<()> {
+    let Some(categories) = categories else {
+        return Ok(());
+    };
+
+    if categories.is_empty() {
+        return Err(AppError::InvalidFieldValue {
+            field: "categories".to_string(),
+            message: "Omit categories to count every category".to_string(),
+        });
+    }
+
+    let known = service.offense_categories().await.map_err(|e| {
+        tracing::error!("Failed to get offense categories: {}", e);
+        AppError::Internal {
+            message: Some(e.to_string()),
+        }
+    })?;
+
+    if let Some(unknown) = categories.iter().find(|c| !known.contains(c)) {
+        return Err(AppError::InvalidFieldValue {
+            field: "categories".to_string(),
+            message: format!("Unknown offense category: {}", unknown),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_weights(weights: &ndith_analytics::ScoreWeights) -> Result<()> {
+    weights.validate().map_err(|e| AppError::InvalidFieldValue {
+        field: "weights".to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Explain an artist's score component by component
+pub async fn explain_artist_trouble_score_handler(
+    State(state): State<AppState>,
+    Path(artist_id): Path<Uuid>,
+    user: AuthenticatedUser,
+    Query(query): Query<ScoreProfileQuery>,
+) -> Result<Json<serde_json::Value>> {
+    tracing::info!(artist_id = %artist_id, profile_id = ?query.profile_id, "Explain trouble score request");
+
+    let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
+
+    let profile = match query.profile_id {
+        Some(profile_id) => visible_profile(&service, profile_id, user.id).await?,
+        None => service.effective_profile(user.id).await.map_err(|e| {
+            tracing::error!("Failed to get score profile: {}", e);
+            AppError::Internal {
+                message: Some(e.to_string()),
+            }
+        })?,
+    };
+
+    match service.explain_artist(artist_id, &profile).await {
+        Ok(Some(explanation)) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": explanation
+        }))),
+        Ok(None) => Err(AppError::NotFound {
+            resource: "artist".to_string(),
+        }),
+        Err(e) => {
+            tracing::error!("Failed to explain trouble score: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Candidate weights for a what-if simulation
+#[derive(Debug, Deserialize)]
+pub struct WhatIfRequest {
+    pub weights: ndith_analytics::ScoreWeights,
+    pub categories: Option<Vec<String>>,
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+}
+
+/// Recompute the leaderboard and tier distribution under candidate weights
+/// without persisting anything
+pub async fn trouble_score_what_if_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    Json(request): Json<WhatIfRequest>,
+) -> Result<Json<serde_json::Value>> {
+    tracing::info!(weights = ?request.weights, categories = ?request.categories, "Trouble score what-if request");
+
+    validate_weights(&request.weights)?;
+
+    let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
+    validate_categories(&service, request.categories.as_deref()).await?;
+
+    match service
+        .what_if(
+            &request.weights,
+            request.categories.as_deref(),
+            request.limit.clamp(1, 500) as usize,
+        )
+        .await
+    {
+        Ok(result) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": result
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to run what-if simulation: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// List shared profiles and the user's own profiles
+pub async fn list_score_profiles_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
+
+    match service.list_profiles(Some(user.id)).await {
+        Ok(profiles) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": profiles
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to list score profiles: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Request to create a profile or a new version of one
+#[derive(Debug, Deserialize)]
+pub struct CreateScoreProfileRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub weights: ndith_analytics::ScoreWeights,
+    pub categories: Option<Vec<String>>,
+    /// Shared profiles are visible to everyone and require `analytics.global`
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// Create a profile, or a new version if the name already exists
+pub async fn create_score_profile_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    permissions: crate::models::Permissions,
+    Json(request): Json<CreateScoreProfileRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    if request.shared && !permissions.has(crate::models::Permission::AnalyticsGlobal) {
+        tracing::warn!(
+            user_id = %user.id,
+            "Unauthorized attempt to create shared score profile - analytics.global required"
+        );
+        return Err(AppError::InsufficientPermissions);
+    }
+
+    let name = request.name.trim();
+    if name.is_empty() || name.len() > 100 {
+        return Err(AppError::InvalidFieldValue {
+            field: "name".to_string(),
+            message: "Name must be 1-100 characters".to_string(),
+        });
+    }
+    validate_weights(&request.weights)?;
+
+    let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
+    validate_categories(&service, request.categories.as_deref()).await?;
+
+    tracing::info!(
+        user_id = %user.id,
+        name = %name,
+        shared = request.shared,
+        "Create score profile version"
+    );
+
+    let new_profile = ndith_analytics::NewScoreProfile {
+        name: name.to_string(),
+        owner_user_id: (!request.shared).then_some(user.id),
+        description: request.description,
+        weights: request.weights,
+        categories: request.categories,
+        created_by: user.id,
+    };
+
+    match service.create_profile_version(new_profile).await {
+        Ok(profile) => Ok((
+            StatusCode::CREATED,
+            Json(serde_json::json!({
+                "success": true,
+                "data": profile
+            })),
+        )),
+        Err(e) => {
+            tracing::error!("Failed to create score profile: {}", e);
+            Ok((
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "success": false,
+                    "error": e.to_string()
+                })),
+            ))
+        }
+    }
+}
+
+/// Get a profile version along with every version of the same profile
+pub async fn get_score_profile_handler(
+    State(state): State<AppState>,
+    Path(profile_id): Path<Uuid>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
+    let profile = visible_profile(&service, profile_id, user.id).await?;
+
+    match service
+        .get_profile_versions(&profile.name, profile.owner_user_id)
+        .await
+    {
+        Ok(versions) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": {
+                "profile": profile,
+                "versions": versions
+            }
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get score profile versions: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Make a shared profile version the default for stored scores (admin endpoint)
+pub async fn set_default_score_profile_handler(
+    State(state): State<AppState>,
+    Path(profile_id): Path<Uuid>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
+    let profile = visible_profile(&service, profile_id, user.id).await?;
+    if profile.owner_user_id.is_some() || profile.categories.is_some() {
+        return Err(AppError::OperationNotAllowed {
+            reason: "Only shared profiles without a category filter can be the default".to_string(),
+        });
+    }
+
+    tracing::info!(
+        user_id = %user.id,
+        profile_id = %profile_id,
+        "Admin changing default score profile"
+    );
+
+    match service.set_default_profile(profile_id).await {
+        Ok(profile) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": profile
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to set default score profile: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Get the profile applied to the user's views
+pub async fn get_my_score_profile_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
+
+    match service.get_user_profile(user.id).await {
+        Ok(selected) => {
+            let is_custom = selected.is_some();
+            let profile = match selected {
+                Some(profile) => profile,
+                None => service.get_default_profile().await.map_err(|e| {
+                    tracing::error!("Failed to get default score profile: {}", e);
+                    AppError::Internal {
+                        message: Some(e.to_string()),
+                    }
+                })?,
+            };
+            Ok(Json(serde_json::json!({
+                "success": true,
+                "data": {
+                    "profile": profile,
+                    "is_custom": is_custom
+                }
+            })))
+        }
+        Err(e) => {
+            tracing::error!("Failed to get user score profile: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Request to choose a profile; null resets to the default
+#[derive(Debug, Deserialize)]
+pub struct SetScoreProfileRequest {
+    pub profile_id: Option<Uuid>,
+}
+
+/// Choose the profile applied to the user's views
+pub async fn set_my_score_profile_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<SetScoreProfileRequest>,
+) -> Result<Json<serde_json::Value>> {
+    let service = ndith_analytics::TroubleScoreService::new(state.db_pool.clone());
+
+    if let Some(profile_id) = request.profile_id {
+        visible_profile(&service, profile_id, user.id).await?;
+    }
+
+    match service.set_user_profile(user.id, request.profile_id).await {
+        Ok(()) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": {
+                "profile_id": request.profile_id
+            }
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to set user score profile: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
 // ============================================================================
 // Revenue Tracking Endpoints
 // ============================================================================
@@ -953,6 +1679,10 @@ pub struct RevenueQuery {
     pub platform: Option<String>,
     /// Filter by trouble tier: low, moderate, high, critical
     pub min_tier: Option<String>,
+    /// ISO country code for country-specific payout rates
+    pub country: Option<String>,
+    /// Subscription tier for payout rates (defaults to standard)
+    pub rate_tier: Option<String>,
 }
 
 fn default_days() -> i32 {
@@ -974,8 +1704,27 @@ pub async fn get_user_revenue_distribution_handler(
         .as_ref()
         .and_then(|p| ndith_analytics::RevenuePlatform::parse_platform(p));
 
+    let country = match query.country.as_deref() {
+        Some(c) if c.len() == 2 && c.chars().all(|ch| ch.is_ascii_alphabetic()) => {
+            Some(c.to_ascii_uppercase())
+        }
+        Some(_) => {
+            return Err(AppError::InvalidFieldValue {
+                field: "country".to_string(),
+                message: "Expected a two-letter ISO country code".to_string(),
+            })
+        }
+        None => None,
+    };
+
     match service
-        .get_user_revenue_distribution(user.id, platform, query.days)
+        .get_user_revenue_distribution(
+            user.id,
+            platform,
+            query.days,
+            country.as_deref(),
+            query.rate_tier.as_deref(),
+        )
         .await
     {
         Ok(distribution) => Ok(Json(serde_json::json!({
@@ -1078,24 +1827,187 @@ pub async fn get_artist_revenue_breakdown_handler(
     user: AuthenticatedUser,
     Query(query): Query<RevenueQuery>,
 ) -> Result<Json<serde_json::Value>> {
-    tracing::info!(
-        artist_id = %artist_id,
-        user_id = %user.id,
-        "Get artist revenue breakdown"
-    );
+    tracing::info!(
+        artist_id = %artist_id,
+        user_id = %user.id,
+        "Get artist revenue breakdown"
+    );
+
+    let service = ndith_analytics::RevenueService::new(state.db_pool.clone());
+
+    match service
+        .get_artist_revenue(user.id, artist_id, query.days)
+        .await
+    {
+        Ok(breakdown) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": breakdown
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get artist revenue: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Get all platform payout rates
+pub async fn get_payout_rates_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    tracing::info!("Get payout rates request");
+
+    let service = ndith_analytics::RevenueService::new(state.db_pool.clone());
+
+    match service.get_all_payout_rates().await {
+        Ok(rates) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": {
+                "rates": rates,
+                "note": "Rates are estimates based on industry reports and may vary"
+            }
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get payout rates: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Query parameters for payout rate history
+#[derive(Debug, Deserialize)]
+pub struct PayoutRateHistoryQuery {
+    pub platform: Option<String>,
+    pub tier: Option<String>,
+    pub country: Option<String>,
+}
+
+/// List every payout rate entry, including superseded ones
+pub async fn get_payout_rate_history_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    Query(query): Query<PayoutRateHistoryQuery>,
+) -> Result<Json<serde_json::Value>> {
+    let platform = match query.platform.as_deref() {
+        Some(p) => Some(
+            ndith_analytics::RevenuePlatform::parse_platform(p).ok_or_else(|| {
+                AppError::InvalidFieldValue {
+                    field: "platform".to_string(),
+                    message: format!("Unknown platform: {}", p),
+                }
+            })?,
+        ),
+        None => None,
+    };
+
+    let filter = ndith_analytics::PayoutRateFilter {
+        platform,
+        rate_tier: query.tier,
+        country_code: query.country.map(|c| c.to_uppercase()),
+    };
+
+    let service = ndith_analytics::PayoutRateService::new(state.db_pool.clone());
+
+    match service.list_rates(&filter).await {
+        Ok(rates) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": {
+                "rates": rates
+            }
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to list payout rate history: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Add a payout rate entry (admin endpoint)
+pub async fn create_payout_rate_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<ndith_analytics::NewPayoutRate>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    let rate = request
+        .normalize()
+        .map_err(|e| AppError::InvalidFieldValue {
+            field: "rate".to_string(),
+            message: e.to_string(),
+        })?;
+
+    tracing::info!(
+        user_id = %user.id,
+        platform = %rate.platform,
+        tier = %rate.rate_tier,
+        country = ?rate.country_code,
+        effective_date = %rate.effective_date,
+        "Admin adding payout rate"
+    );
+
+    let service = ndith_analytics::PayoutRateService::new(state.db_pool.clone());
+
+    match service.add_rate(rate, user.id).await {
+        Ok(Some(update)) => Ok((
+            StatusCode::CREATED,
+            Json(serde_json::json!({
+                "success": true,
+                "data": update
+            })),
+        )),
+        Ok(None) => Err(AppError::Conflict {
+            message: "A rate for this platform, tier and country already starts on that date"
+                .to_string(),
+        }),
+        Err(e) => {
+            tracing::error!("Failed to add payout rate: {}", e);
+            Ok((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "success": false,
+                    "error": e.to_string()
+                })),
+            ))
+        }
+    }
+}
+
+/// Correct a payout rate entry and reprice affected playcounts (admin endpoint)
+pub async fn correct_payout_rate_handler(
+    State(state): State<AppState>,
+    Path(rate_id): Path<Uuid>,
+    user: AuthenticatedUser,
+    Json(correction): Json<ndith_analytics::PayoutRateCorrection>,
+) -> Result<Json<serde_json::Value>> {
+    correction
+        .validate()
+        .map_err(|e| AppError::InvalidFieldValue {
+            field: "correction".to_string(),
+            message: e.to_string(),
+        })?;
 
-    let service = ndith_analytics::RevenueService::new(state.db_pool.clone());
+    tracing::info!(user_id = %user.id, rate_id = %rate_id, "Admin correcting payout rate");
 
-    match service
-        .get_artist_revenue(user.id, artist_id, query.days)
-        .await
-    {
-        Ok(breakdown) => Ok(Json(serde_json::json!({
+    let service = ndith_analytics::PayoutRateService::new(state.db_pool.clone());
+
+    match service.correct_rate(rate_id, correction, user.id).await {
+        Ok(Some(update)) => Ok(Json(serde_json::json!({
             "success": true,
-            "data": breakdown
+            "data": update
         }))),
+        Ok(None) => Err(AppError::NotFound {
+            resource: "payout rate".to_string(),
+        }),
         Err(e) => {
-            tracing::error!("Failed to get artist revenue: {}", e);
+            tracing::error!("Failed to correct payout rate: {}", e);
             Ok(Json(serde_json::json!({
                 "success": false,
                 "error": e.to_string()
@@ -1104,25 +2016,34 @@ pub async fn get_artist_revenue_breakdown_handler(
     }
 }
 
-/// Get all platform payout rates
-pub async fn get_payout_rates_handler(
+/// Get a payout rate entry with its additions and corrections
+pub async fn get_payout_rate_changes_handler(
     State(state): State<AppState>,
+    Path(rate_id): Path<Uuid>,
     _user: AuthenticatedUser,
 ) -> Result<Json<serde_json::Value>> {
-    tracing::info!("Get payout rates request");
-
-    let service = ndith_analytics::RevenueService::new(state.db_pool.clone());
+    let service = ndith_analytics::PayoutRateService::new(state.db_pool.clone());
 
-    match service.get_all_payout_rates().await {
-        Ok(rates) => Ok(Json(serde_json::json!({
+    let rate = service
+        .get_rate(rate_id)
+        .await
+        .map_err(|e| AppError::Internal {
+            message: Some(e.to_string()),
+        })?
+        .ok_or_else(|| AppError::NotFound {
+            resource: "payout rate".to_string(),
+        })?;
+
+    match service.rate_changes(rate_id).await {
+        Ok(changes) => Ok(Json(serde_json::json!({
             "success": true,
             "data": {
-                "rates": rates,
-                "note": "Rates are estimates based on industry reports and may vary"
+                "rate": rate,
+                "changes": changes
             }
         }))),
         Err(e) => {
-            tracing::error!("Failed to get payout rates: {}", e);
+            tracing::error!("Failed to get payout rate changes: {}", e);
             Ok(Json(serde_json::json!({
                 "success": false,
                 "error": e.to_string()
@@ -1173,6 +2094,103 @@ pub async fn get_global_problematic_revenue_handler(
     }
 }
 
+// ============================================================================
+// Listening History Import Endpoints
+// ============================================================================
+
+/// Query parameters for listening history imports
+#[derive(Debug, Deserialize)]
+pub struct ListeningImportQuery {
+    /// spotify_extended_history, apple_music_play_activity, youtube_takeout, lastfm_scrobbles
+    pub source: String,
+    /// Platform the plays were streamed on (required for Last.fm scrobbles)
+    pub platform: Option<String>,
+}
+
+/// Import a listening history export. The request body is the raw export
+/// file (JSON or CSV, depending on the source).
+pub async fn import_listening_history_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListeningImportQuery>,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>> {
+    tracing::info!(
+        user_id = %user.id,
+        source = %query.source,
+        bytes = body.len(),
+        "Import listening history request"
+    );
+
+    let source = ndith_analytics::ListeningSource::parse_source(&query.source)
+        .filter(|s| *s != ndith_analytics::ListeningSource::SpotifyRecentlyPlayed)
+        .ok_or_else(|| AppError::InvalidFieldValue {
+            field: "source".to_string(),
+            message: "Valid options: spotify_extended_history, apple_music_play_activity, youtube_takeout, lastfm_scrobbles".to_string(),
+        })?;
+
+    let platform = match query.platform.as_deref() {
+        Some(p) => Some(
+            ndith_analytics::RevenuePlatform::parse_platform(p).ok_or_else(|| {
+                AppError::InvalidFieldValue {
+                    field: "platform".to_string(),
+                    message: format!("Unknown platform '{}'", p),
+                }
+            })?,
+        ),
+        None => None,
+    };
+
+    let service = ndith_analytics::ListeningHistoryService::new(state.db_pool.clone());
+
+    match service
+        .import_export(user.id, source, platform, &body)
+        .await
+    {
+        Ok(summary) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": summary
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to import listening history: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Query parameters for listing listening history imports
+#[derive(Debug, Deserialize)]
+pub struct ListeningImportsQuery {
+    #[serde(default = "default_limit")]
+    pub limit: i32,
+}
+
+/// List the user's past listening history imports
+pub async fn get_listening_imports_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListeningImportsQuery>,
+) -> Result<Json<serde_json::Value>> {
+    let service = ndith_analytics::ListeningHistoryService::new(state.db_pool.clone());
+
+    match service.get_imports(user.id, query.limit as i64).await {
+        Ok(imports) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": imports
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get listening imports: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
 // ============================================================================
 // Category Revenue Endpoints (Simulated by Offense Category)
 // ============================================================================
@@ -1351,7 +2369,10 @@ pub async fn get_enforcement_analytics_handler(
         "Get enforcement analytics request"
     );
 
-    let service = ndith_analytics::EnforcementAnalyticsService::new(state.db_pool.clone());
+    let mut service = ndith_analytics::EnforcementAnalyticsService::new(state.db_pool.clone());
+    if let Some(duckdb) = state.analytics_db.clone() {
+        service = service.with_replica(duckdb);
+    }
 
     let analytics_query = ndith_analytics::EnforcementAnalyticsQuery {
         provider: query.provider,
@@ -1375,7 +2396,8 @@ pub async fn get_enforcement_analytics_handler(
                 "actions_by_provider": analytics.stats.actions_by_provider,
                 "time_series": analytics.time_series
             },
-            "generated_at": analytics.generated_at.to_rfc3339()
+            "generated_at": analytics.generated_at.to_rfc3339(),
+            "freshness": analytics.freshness
         }))),
         Err(e) => {
             tracing::error!("Failed to get enforcement analytics: {}", e);
@@ -1387,6 +2409,352 @@ pub async fn get_enforcement_analytics_handler(
     }
 }
 
+/// Replication progress of each table copied into DuckDB (admin endpoint)
+pub async fn get_replication_status_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let duckdb =
+        state
+            .analytics_db
+            .clone()
+            .ok_or_else(|| AppError::ExternalServiceUnavailable {
+                service: "analytics_db".to_string(),
+            })?;
+
+    let service = crate::runtime::replication_service(state.db_pool.clone(), duckdb);
+
+    match service.status().await {
+        Ok(tables) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": tables
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get replication status: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Query parameters for the library impact timeline
+#[derive(Debug, Deserialize)]
+pub struct LibraryImpactQuery {
+    pub provider: Option<String>,
+    #[serde(default = "default_impact_days")]
+    pub days: i32,
+}
+
+fn default_impact_days() -> i32 {
+    90
+}
+
+/// Library composition over time, alongside the enforcement runs that moved it
+pub async fn get_library_impact_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<LibraryImpactQuery>,
+) -> Result<Json<serde_json::Value>> {
+    if !(1..=365).contains(&query.days) {
+        return Err(AppError::InvalidFieldValue {
+            field: "days".to_string(),
+            message: "Must be between 1 and 365".to_string(),
+        });
+    }
+
+    tracing::info!(
+        user_id = %user.id,
+        provider = ?query.provider,
+        days = query.days,
+        "Get library impact request"
+    );
+
+    let service = ndith_analytics::LibraryImpactService::new(state.db_pool.clone());
+
+    match service
+        .timeline(user.id, query.provider.as_deref(), query.days)
+        .await
+    {
+        Ok(timeline) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": timeline
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get library impact timeline: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Query parameters for regression alerts
+#[derive(Debug, Deserialize)]
+pub struct RegressionsQuery {
+    #[serde(default)]
+    pub include_acknowledged: bool,
+}
+
+/// Removed tracks that reappeared in the user's library
+pub async fn get_library_regressions_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<RegressionsQuery>,
+) -> Result<Json<serde_json::Value>> {
+    let service = ndith_analytics::LibraryImpactService::new(state.db_pool.clone());
+
+    match service
+        .regressions(user.id, query.include_acknowledged)
+        .await
+    {
+        Ok(regressions) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": regressions
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to get library regressions: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Dismiss a regression alert
+pub async fn acknowledge_library_regression_handler(
+    State(state): State<AppState>,
+    Path(regression_id): Path<Uuid>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let service = ndith_analytics::LibraryImpactService::new(state.db_pool.clone());
+
+    match service.acknowledge_regression(user.id, regression_id).await {
+        Ok(true) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": {
+                "id": regression_id,
+                "acknowledged": true
+            }
+        }))),
+        Ok(false) => Err(AppError::NotFound {
+            resource: "library regression".to_string(),
+        }),
+        Err(e) => {
+            tracing::error!("Failed to acknowledge library regression: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+// ============================================================================
+// Cohort and Funnel Endpoints
+// ============================================================================
+
+/// Query parameters for weekly retention cohorts
+#[derive(Debug, Deserialize)]
+pub struct CohortsQuery {
+    /// Event that places users in a cohort (default: registered)
+    #[serde(default = "default_cohort_event")]
+    pub event: String,
+    /// Event counted as activity; omit to count any event
+    pub activity: Option<String>,
+    pub provider: Option<String>,
+    /// Cohort entry dates, inclusive (default: the last 90 days)
+    pub start: Option<chrono::NaiveDate>,
+    pub end: Option<chrono::NaiveDate>,
+    #[serde(default = "default_cohort_weeks")]
+    pub weeks: u32,
+}
+
+fn default_cohort_event() -> String {
+    "registered".to_string()
+}
+
+fn default_cohort_weeks() -> u32 {
+    8
+}
+
+/// Query parameters for conversion funnels
+#[derive(Debug, Deserialize)]
+pub struct FunnelsQuery {
+    /// Comma-separated stages, in order
+    #[serde(default = "default_funnel_steps")]
+    pub steps: String,
+    pub provider: Option<String>,
+    /// Entry dates for the first step, inclusive (default: the last 90 days)
+    pub start: Option<chrono::NaiveDate>,
+    pub end: Option<chrono::NaiveDate>,
+    /// Later events must happen within this many days of entering
+    pub window_days: Option<u32>,
+    #[serde(default)]
+    pub by_provider: bool,
+}
+
+fn default_funnel_steps() -> String {
+    "registered,connected,scanned,enforced".to_string()
+}
+
+fn parse_stage(field: &str, value: &str) -> Result<ndith_analytics::LifecycleStage> {
+    value
+        .trim()
+        .parse()
+        .map_err(|e: anyhow::Error| AppError::InvalidFieldValue {
+            field: field.to_string(),
+            message: e.to_string(),
+        })
+}
+
+/// Resolve an inclusive date range, defaulting to the last 90 days
+fn cohort_date_range(
+    start: Option<chrono::NaiveDate>,
+    end: Option<chrono::NaiveDate>,
+) -> Result<(chrono::NaiveDate, chrono::NaiveDate)> {
+    let end = end.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let start = start.unwrap_or(end - chrono::Duration::days(90));
+
+    if start > end {
+        return Err(AppError::InvalidFieldValue {
+            field: "start".to_string(),
+            message: "Must not be after end".to_string(),
+        });
+    }
+
+    Ok((start, end))
+}
+
+fn cohort_service(state: &AppState) -> Result<ndith_analytics::CohortAnalyticsService> {
+    let duckdb =
+        state
+            .analytics_db
+            .clone()
+            .ok_or_else(|| AppError::ExternalServiceUnavailable {
+                service: "analytics_db".to_string(),
+            })?;
+
+    Ok(ndith_analytics::CohortAnalyticsService::new(duckdb))
+}
+
+/// Weekly retention matrix by cohort (admin endpoint)
+pub async fn get_cohorts_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<CohortsQuery>,
+) -> Result<Json<serde_json::Value>> {
+    let service = cohort_service(&state)?;
+
+    let cohort_event = parse_stage("event", &query.event)?;
+    let activity_event = query
+        .activity
+        .as_deref()
+        .map(|a| parse_stage("activity", a))
+        .transpose()?;
+    let (start, end) = cohort_date_range(query.start, query.end)?;
+
+    if !(1..=52).contains(&query.weeks) {
+        return Err(AppError::InvalidFieldValue {
+            field: "weeks".to_string(),
+            message: "Must be between 1 and 52".to_string(),
+        });
+    }
+
+    tracing::info!(
+        user_id = %user.id,
+        event = ?cohort_event,
+        activity = ?activity_event,
+        provider = ?query.provider,
+        "Cohort retention request"
+    );
+
+    let cohort_query = ndith_analytics::CohortQuery {
+        cohort_event,
+        activity_event,
+        provider: query.provider,
+        start,
+        end,
+        weeks: query.weeks,
+    };
+
+    match service.retention(&cohort_query).await {
+        Ok(matrix) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": matrix
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to compute cohort retention: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
+/// Conversion funnel, optionally per provider (admin endpoint)
+pub async fn get_funnels_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<FunnelsQuery>,
+) -> Result<Json<serde_json::Value>> {
+    let service = cohort_service(&state)?;
+
+    let steps = query
+        .steps
+        .split(',')
+        .map(|step| parse_stage("steps", step))
+        .collect::<Result<Vec<_>>>()?;
+    if steps.len() < 2 || steps.len() > 10 {
+        return Err(AppError::InvalidFieldValue {
+            field: "steps".to_string(),
+            message: "A funnel needs 2-10 steps".to_string(),
+        });
+    }
+    if steps[0].event_type().is_none() {
+        return Err(AppError::InvalidFieldValue {
+            field: "steps".to_string(),
+            message: "The first step must be a recorded event".to_string(),
+        });
+    }
+    let (start, end) = cohort_date_range(query.start, query.end)?;
+
+    tracing::info!(
+        user_id = %user.id,
+        steps = ?steps,
+        provider = ?query.provider,
+        window_days = ?query.window_days,
+        "Funnel request"
+    );
+
+    let funnel_query = ndith_analytics::FunnelQuery {
+        steps,
+        provider: query.provider,
+        start,
+        end,
+        window_days: query.window_days,
+        by_provider: query.by_provider,
+    };
+
+    match service.funnel(&funnel_query).await {
+        Ok(report) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": report
+        }))),
+        Err(e) => {
+            tracing::error!("Failed to compute funnel: {}", e);
+            Ok(Json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
+}
+
 // ============================================================================
 // User Activity Summary Endpoints (US-025)
 // ============================================================================
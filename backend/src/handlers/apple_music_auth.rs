@@ -77,6 +77,7 @@ async fn upsert_user_library_cache(
                 .map_err(AppError::DatabaseQueryFailed)?
                 .rows_affected() as i64;
 
+        crate::services::offense::capture_library_snapshot(&mut tx, user_id, provider).await?;
         tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
         return Ok(SyncDiffStats {
             added: 0,
@@ -140,6 +141,7 @@ async fn upsert_user_library_cache(
     .map_err(AppError::DatabaseQueryFailed)?
     .rows_affected() as i64;
 
+    crate::services::offense::capture_library_snapshot(&mut tx, user_id, provider).await?;
     tx.commit().await.map_err(AppError::DatabaseQueryFailed)?;
 
     let total = upserted - removed;
@@ -0,0 +1,115 @@
+//! Admin endpoints for the hash-chained audit log
+
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::models::AuthenticatedUser;
+use crate::services::audit_chain::DEFAULT_TENANT;
+use crate::services::{AuditContext, AuditEventType, AuditSeverity};
+use crate::{AppState, Result};
+
+/// Chain and sequence range to verify or export; defaults to the whole
+/// default tenant chain
+#[derive(Debug, Deserialize)]
+pub struct AuditChainRangeQuery {
+    pub tenant: Option<String>,
+    pub from_seq: Option<i64>,
+    pub to_seq: Option<i64>,
+}
+
+impl AuditChainRangeQuery {
+    fn tenant(&self) -> &str {
+        self.tenant.as_deref().unwrap_or(DEFAULT_TENANT)
+    }
+}
+
+/// GET /api/v1/audit/verify (admin)
+///
+/// Reports gaps, edited entries and checkpoint mismatches in the range
+pub async fn verify_chain_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    Query(query): Query<AuditChainRangeQuery>,
+) -> Result<Json<serde_json::Value>> {
+    let report = state
+        .audit_chain
+        .verify(query.tenant(), query.from_seq, query.to_seq)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": report
+    })))
+}
+
+/// GET /api/v1/audit/export (admin)
+///
+/// Downloads the range as JSON Lines that can be verified offline
+pub async fn export_chain_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<AuditChainRangeQuery>,
+) -> Result<Response> {
+    let tenant = query.tenant();
+    let body = state
+        .audit_chain
+        .export_jsonl(tenant, query.from_seq, query.to_seq)
+        .await?;
+
+    // Compliance exports are themselves audited
+    if let Err(e) = state
+        .audit_logger
+        .log_security_event(
+            AuditEventType::AdminAction,
+            AuditSeverity::Info,
+            "audit_chain_exported".to_string(),
+            serde_json::json!({
+                "tenant_id": tenant,
+                "from_seq": query.from_seq,
+                "to_seq": query.to_seq,
+            }),
+            Some(AuditContext {
+                user_id: Some(user.id),
+                session_id: None,
+                ip_address: None,
+                user_agent: None,
+                correlation_id: None,
+            }),
+        )
+        .await
+    {
+        tracing::warn!(error = %e, "Failed to audit audit chain export");
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"audit-{}.jsonl\"", tenant),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// POST /api/v1/audit/checkpoints (admin)
+///
+/// Signs every chain head that moved since its last checkpoint
+pub async fn create_checkpoint_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let checkpoints = state.audit_chain.checkpoint_all().await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": checkpoints
+    })))
+}
@@ -1,10 +1,19 @@
 use crate::{
-    models::{AuthResponse, LoginRequest, RefreshTokenRequest, RegisterRequest, TotpVerifyRequest},
+    models::{
+        AuthResponse, LoginRequest, RecoveryCodesRegenerateRequest, RefreshTokenRequest,
+        RegisterRequest, TotpVerifyRequest,
+    },
+    services::rate_limiting_middleware::extract_client_ip,
     services::registration_monitoring::RegistrationMonitoringService,
     AppError, AppState, Result,
 };
-use axum::{extract::State, http::StatusCode, response::Json};
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
 use serde_json;
+use std::net::SocketAddr;
 use std::time::Instant;
 
 /// Register a new user with monitoring
@@ -163,6 +172,20 @@ pub async fn register_handler(
         );
     }
 
+    // A mail failure shouldn't fail the registration; the user can ask for
+    // another link from their account
+    if let Err(e) = state
+        .email_verification
+        .send_verification(response.user.id)
+        .await
+    {
+        tracing::warn!(
+            user_id = %response.user.id,
+            error = %e,
+            "Failed to send verification email"
+        );
+    }
+
     Ok((
         StatusCode::CREATED,
         Json(serde_json::json!({
@@ -176,14 +199,53 @@ pub async fn register_handler(
 /// Login user
 pub async fn login_handler(
     State(state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Json(request): Json<LoginRequest>,
 ) -> Result<Json<serde_json::Value>> {
     tracing::info!(email = %request.email, "User login attempt");
 
-    let response = state.auth_service.login(request).await.map_err(|e| {
-        tracing::warn!(error = %e, "Login failed");
-        e
-    })?;
+    let client_ip = extract_client_ip(&headers, connect_info.as_ref());
+    let email = request.email.clone();
+
+    // Locked accounts, blocked IPs and attempts inside the progressive delay
+    // are turned away before the password is checked
+    if let Err(rejection) = state.security_monitor.check_login(&email, &client_ip).await {
+        // Retrying a little early after a typo is normal; only pressing on
+        // against a lockout counts towards blocking the IP
+        if rejection.is_lockout() {
+            tracing::warn!(ip = %client_ip, "Login attempt rejected by lockout");
+            state
+                .security_monitor
+                .record_suspicious_activity(&client_ip, "login_attempt_while_locked")
+                .await;
+        }
+        return Err(rejection.into());
+    }
+
+    let response = match state.auth_service.login(request).await {
+        Ok(response) => response,
+        Err(e) => {
+            if matches!(e, AppError::InvalidCredentials | AppError::TwoFactorInvalid) {
+                let outcome = state
+                    .security_monitor
+                    .record_failed_login(&email, &client_ip)
+                    .await;
+                if outcome.account_locked || outcome.ip_blocked {
+                    tracing::warn!(
+                        ip = %client_ip,
+                        account_locked = outcome.account_locked,
+                        ip_blocked = outcome.ip_blocked,
+                        "Login failures triggered a lockout"
+                    );
+                }
+            }
+            tracing::warn!(error = %e, "Login failed");
+            return Err(e);
+        }
+    };
+
+    state.security_monitor.record_successful_login(&email).await;
 
     tracing::info!(user_id = %response.user.id, "User logged in successfully");
 
@@ -366,6 +428,57 @@ pub async fn disable_2fa_handler(
     }
 }
 
+/// Get how many 2FA recovery codes the user has left
+pub async fn get_recovery_codes_handler(
+    State(state): State<AppState>,
+    user: crate::models::AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let enabled = state.auth_service.get_totp_status(user.id).await?;
+    let remaining = state.auth_service.recovery_codes_remaining(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "totp_enabled": enabled,
+            "remaining": if enabled { remaining } else { 0 },
+            "total": crate::services::recovery_codes::RECOVERY_CODE_COUNT
+        }
+    })))
+}
+
+/// Replace the user's 2FA recovery codes, invalidating the old set
+pub async fn regenerate_recovery_codes_handler(
+    State(state): State<AppState>,
+    user: crate::models::AuthenticatedUser,
+    Json(request): Json<RecoveryCodesRegenerateRequest>,
+) -> Result<Json<serde_json::Value>> {
+    tracing::info!(user_id = %user.id, "Recovery code regeneration attempt");
+
+    if request.totp_code.len() != 6 || !request.totp_code.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AppError::InvalidFieldValue {
+            field: "totp_code".to_string(),
+            message: "TOTP code must be 6 digits".to_string(),
+        });
+    }
+
+    let codes = state
+        .auth_service
+        .regenerate_recovery_codes(user.id, &request.totp_code)
+        .await
+        .map_err(|e| {
+            tracing::warn!(error = %e, user_id = %user.id, "Recovery code regeneration failed");
+            e
+        })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "recovery_codes": codes
+        },
+        "message": "Recovery codes regenerated. Previous codes no longer work."
+    })))
+}
+
 /// Logout user
 pub async fn logout_handler(
     State(_state): State<AppState>,
@@ -413,6 +413,12 @@ pub async fn remove_from_dnp_handler(
         "Remove from DNP list request"
     );
 
+    if state.households.is_enforced(user.id, artist_id).await? {
+        return Err(AppError::OperationNotAllowed {
+            reason: "This artist is blocked by your household owner".to_string(),
+        });
+    }
+
     state.dnp_service.remove_from_dnp_list(user.id, artist_id).await
         .map_err(|e| {
             tracing::warn!(error = %e, user_id = %user.id, artist_id = %artist_id, "Failed to remove from DNP list");
@@ -831,6 +837,27 @@ pub async fn get_revenue_impact_handler(
     .await
     .unwrap_or_default();
 
+    // The user's own plays of blocked artists over the last 30 days, from
+    // imported or synced listening history
+    let user_plays: std::collections::HashMap<Uuid, (i64, rust_decimal::Decimal)> =
+        sqlx::query_as::<_, (Uuid, i64, rust_decimal::Decimal)>(
+            r#"
+        SELECT pc.artist_id, SUM(pc.play_count)::bigint, COALESCE(SUM(pc.estimated_revenue), 0)
+        FROM user_artist_playcounts pc
+        JOIN user_artist_blocks uab ON uab.artist_id = pc.artist_id AND uab.user_id = pc.user_id
+        WHERE pc.user_id = $1
+        AND pc.period_end >= CURRENT_DATE - 30
+        GROUP BY pc.artist_id
+    "#,
+        )
+        .bind(user.id)
+        .fetch_all(&state.db_pool)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(artist_id, streams, revenue)| (artist_id, (streams, revenue)))
+        .collect();
+
     // Get platform rates for reference
     let rates = sqlx::query_as::<_, (String, rust_decimal::Decimal)>(
         "SELECT platform, rate_per_stream FROM platform_streaming_rates ORDER BY rate_per_stream DESC"
@@ -850,16 +877,22 @@ pub async fn get_revenue_impact_handler(
         .filter_map(|(_, _, _, _, rev)| *rev)
         .sum();
 
+    let your_streams: i64 = user_plays.values().map(|(streams, _)| streams).sum();
+    let your_revenue: rust_decimal::Decimal = user_plays.values().map(|(_, rev)| *rev).sum();
+
     // Format artist breakdown
     let artists: Vec<serde_json::Value> = artist_stats
         .iter()
         .map(|(id, name, monthly, total, rev)| {
+            let yours = user_plays.get(id);
             serde_json::json!({
                 "artist_id": id,
                 "name": name,
                 "monthly_streams": monthly,
                 "total_streams": total,
-                "estimated_monthly_revenue": rev.map(|r| format!("${:.2}", r))
+                "estimated_monthly_revenue": rev.map(|r| format!("${:.2}", r)),
+                "your_streams_last_30_days": yours.map(|(streams, _)| *streams).unwrap_or(0),
+                "your_revenue_last_30_days": format!("${:.4}", yours.map(|(_, r)| *r).unwrap_or_default())
             })
         })
         .collect();
@@ -884,6 +917,12 @@ pub async fn get_revenue_impact_handler(
                 "estimated_monthly_revenue_diverted": format!("${:.2}", total_revenue),
                 "note": "Revenue estimates based on average platform payouts. Actual revenue varies by contract."
             },
+            "your_listening": {
+                "has_listening_history": !user_plays.is_empty(),
+                "streams_last_30_days": your_streams,
+                "revenue_last_30_days": format!("${:.4}", your_revenue),
+                "note": "From your imported or synced listening history. Import a streaming history export to see what you actually paid blocked artists."
+            },
             "artists": artists,
             "platform_rates": platform_rates,
             "methodology": {
@@ -0,0 +1,149 @@
+//! Email verification and change-of-email
+//!
+//! The verify and confirm routes are public: they're reached from links in
+//! mail, and the token itself identifies the account.
+
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use std::net::SocketAddr;
+
+use crate::models::{AuthenticatedUser, ChangeEmailRequest, EmailTokenRequest};
+use crate::services::rate_limiting_middleware::extract_client_ip;
+use crate::{AppError, AppState, Result};
+
+/// Follow a verification link
+pub async fn verify_email_handler(
+    State(state): State<AppState>,
+    Json(request): Json<EmailTokenRequest>,
+) -> Result<Json<serde_json::Value>> {
+    let user_id = state.email_verification.verify(&request.token).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": { "user_id": user_id, "email_verified": true },
+        "message": "Email address verified"
+    })))
+}
+
+/// Mail the caller a new verification link
+pub async fn resend_verification_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    state.email_verification.send_verification(user.id).await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "success": true,
+            "message": "Verification email sent"
+        })),
+    ))
+}
+
+/// The caller's address, whether it's verified, and any pending change
+pub async fn get_email_status_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let status = state.email_verification.status(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": status
+    })))
+}
+
+/// Start changing the caller's address; both addresses get a link to confirm
+pub async fn request_email_change_handler(
+    State(state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    user: AuthenticatedUser,
+    Json(request): Json<ChangeEmailRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    // The password is checked against the same lockouts as login, so a
+    // stolen session can't be used to guess it
+    let client_ip = extract_client_ip(&headers, connect_info.as_ref());
+    state
+        .security_monitor
+        .check_login(&user.email, &client_ip)
+        .await?;
+
+    let status = match state
+        .email_verification
+        .request_change(user.id, &request.new_email, request.password.as_deref())
+        .await
+    {
+        Ok(status) => status,
+        Err(AppError::InvalidCredentials) => {
+            state
+                .security_monitor
+                .record_failed_login(&user.email, &client_ip)
+                .await;
+            return Err(AppError::InvalidCredentials);
+        }
+        Err(e) => return Err(e),
+    };
+    if request.password.is_some() {
+        state
+            .security_monitor
+            .record_successful_login(&user.email)
+            .await;
+    }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": status,
+            "message": "Confirm the change from both your current and your new email address"
+        })),
+    ))
+}
+
+/// Abandon the caller's pending email change
+pub async fn cancel_email_change_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    state.email_verification.cancel_change(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Email change cancelled"
+    })))
+}
+
+/// Follow one of the two email change links
+pub async fn confirm_email_change_handler(
+    State(state): State<AppState>,
+    Json(request): Json<EmailTokenRequest>,
+) -> Result<Json<serde_json::Value>> {
+    let (confirmation, completed) = state
+        .email_verification
+        .confirm_change(&request.token)
+        .await?;
+
+    let message = match completed {
+        Some(change) => {
+            // Logins cached under the old address must not outlive the change
+            state
+                .auth_service
+                .invalidate_login_cache(&change.old_email)
+                .await;
+            tracing::info!(user_id = %change.user_id, "Email change completed");
+            "Email address changed"
+        }
+        None => "Confirmed; waiting for the other address to confirm",
+    };
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": confirmation,
+        "message": message
+    })))
+}
@@ -315,7 +315,7 @@ async fn get_blocked_artist_names(
     let rows: Vec<(Option<String>,)> = sqlx::query_as(
         r#"
         SELECT DISTINCT a.canonical_name as name
-        FROM user_artist_blocks uab
+        FROM effective_artist_blocks uab
         JOIN artists a ON uab.artist_id = a.id
         WHERE uab.user_id = $1
 
@@ -0,0 +1,308 @@
+//! Household accounts: membership, invites, the shared household blocklist
+//! and blocks the owner enforces on children
+//!
+//! Ownership and role checks live in `HouseholdService`; every route here
+//! only requires an authenticated user.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::models::{
+    AuthenticatedUser, CreateHouseholdRequest, HouseholdBlockRequest, InviteHouseholdMemberRequest,
+    UpdateHouseholdMemberRequest,
+};
+use crate::{AppState, Result};
+
+/// Create a household owned by the caller
+pub async fn create_household_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<CreateHouseholdRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    let household = state.households.create(user.id, &request.name).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": household,
+            "message": "Household created"
+        })),
+    ))
+}
+
+/// The caller's household, its members and (for the owner) open invites
+pub async fn get_household_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let overview = state.households.overview(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": overview
+    })))
+}
+
+/// Dissolve the caller's household (owner)
+pub async fn dissolve_household_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    state.households.dissolve(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Household dissolved"
+    })))
+}
+
+/// Leave the caller's household (members)
+pub async fn leave_household_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    state.households.leave(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Left household"
+    })))
+}
+
+/// Invite someone by email as a member or child (owner)
+pub async fn create_invite_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<InviteHouseholdMemberRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    let invite = state
+        .households
+        .invite(user.id, &request.email, request.role)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": invite,
+            "message": "Invite sent"
+        })),
+    ))
+}
+
+/// Withdraw an open invite (owner)
+pub async fn revoke_invite_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(invite_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    state.households.revoke_invite(user.id, invite_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Invite revoked"
+    })))
+}
+
+/// Open invites addressed to the caller's email
+pub async fn list_my_invites_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let invites = state.households.invites_for(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": invites
+    })))
+}
+
+/// Join the household an invite is for
+pub async fn accept_invite_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(invite_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    let overview = state.households.accept_invite(user.id, invite_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": overview,
+        "message": "Joined household"
+    })))
+}
+
+/// Turn down an invite
+pub async fn decline_invite_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(invite_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    state.households.decline_invite(user.id, invite_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Invite declined"
+    })))
+}
+
+/// Switch a member between member and child (owner)
+pub async fn update_member_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(member_id): Path<Uuid>,
+    Json(request): Json<UpdateHouseholdMemberRequest>,
+) -> Result<Json<serde_json::Value>> {
+    state
+        .households
+        .set_member_role(user.id, member_id, request.role)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": { "user_id": member_id, "role": request.role },
+        "message": "Member updated"
+    })))
+}
+
+/// Remove a member or child (owner)
+pub async fn remove_member_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(member_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    state.households.remove_member(user.id, member_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Member removed"
+    })))
+}
+
+/// The household's shared blocklist
+pub async fn get_shared_blocklist_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let blocks = state.households.shared_blocks(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": blocks
+    })))
+}
+
+/// Add an artist to the shared blocklist (owner or member)
+pub async fn add_shared_block_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<HouseholdBlockRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    state
+        .households
+        .add_shared_block(user.id, request.artist_id, request.note.as_deref())
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": { "artist_id": request.artist_id },
+            "message": "Artist added to household blocklist"
+        })),
+    ))
+}
+
+/// Remove an artist from the shared blocklist (owner or member)
+pub async fn remove_shared_block_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(artist_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    state
+        .households
+        .remove_shared_block(user.id, artist_id)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Artist removed from household blocklist"
+    })))
+}
+
+/// Blocks enforced on a child (owner)
+pub async fn get_enforced_blocks_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(child_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    let blocks = state.households.enforced_blocks(user.id, child_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": blocks
+    })))
+}
+
+/// Enforce a block on a child (owner)
+pub async fn add_enforced_block_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(child_id): Path<Uuid>,
+    Json(request): Json<HouseholdBlockRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    state
+        .households
+        .add_enforced_block(
+            user.id,
+            child_id,
+            request.artist_id,
+            request.note.as_deref(),
+        )
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": { "user_id": child_id, "artist_id": request.artist_id },
+            "message": "Block enforced"
+        })),
+    ))
+}
+
+/// Lift a block enforced on a child (owner)
+pub async fn remove_enforced_block_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((child_id, artist_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<serde_json::Value>> {
+    state
+        .households
+        .remove_enforced_block(user.id, child_id, artist_id)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Enforced block removed"
+    })))
+}
+
+/// The caller's effective blocklist: personal, household and enforced
+/// blocks merged, with enforced entries marked as locked
+pub async fn get_effective_blocklist_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let blocks = state.households.effective_blocklist(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": blocks
+    })))
+}
@@ -1,9 +1,12 @@
 pub mod apple_music_auth;
+pub mod audit;
 pub mod auth;
 pub mod category;
 pub mod connections;
 pub mod dnp;
+pub mod email_verification;
 pub mod enforcement;
+pub mod household;
 pub mod login_health;
 pub mod oauth;
 pub mod offense;
@@ -11,11 +14,15 @@ pub mod oidc;
 pub mod playlist_sanitizer;
 pub mod provider_library_sync_status;
 pub mod registration_health;
+pub mod roles;
+pub mod security;
 pub mod spotify_connection;
 pub mod spotify_enforcement;
 pub mod sync;
 pub mod tidal_connection;
+pub mod transparency;
 pub mod user;
+pub mod webauthn;
 pub mod youtube_connection;
 
 #[cfg(feature = "analytics")]
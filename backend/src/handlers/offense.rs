@@ -13,10 +13,13 @@ use uuid::Uuid;
 
 use crate::error::{AppError, Result};
 use crate::models::offense::{
-    AddEvidenceRequest, CreateOffenseRequest, FlaggedArtist, ImportLibraryRequest,
-    LibraryScanResponse, OffenseSeverity, OffenseSummary, OffenseWithEvidence,
+    AddEvidenceRequest, AddOffenseEventRequest, ArtistOffense, CreateOffenseRequest, FlaggedArtist,
+    ImportLibraryRequest, LibraryScanResponse, OffenseDispute, OffenseEvent, OffenseEvidence,
+    OffenseSeverity, OffenseSummary, OffenseTimeline, OffenseWithEvidence, PublicOffenseDispute,
+    ResolveDisputeRequest, ReviewNotesRequest, ReviewOffenseEventRequest, Revision, RevisionDiff,
+    SubmitDisputeRequest, UpdateEvidenceRequest, UpdateOffenseRequest,
 };
-use crate::models::{AuthenticatedUser, Claims};
+use crate::models::{AuthenticatedUser, Permission, Permissions};
 use crate::services::offense::OffenseService;
 use crate::services::PlaylistRepository;
 use crate::AppState;
@@ -66,12 +69,13 @@ pub async fn get_offense(
     Ok(Json(offense))
 }
 
-/// Create a new offense (requires authentication)
+/// Create a new offense (requires a verified email)
 pub async fn create_offense(
     State(state): State<AppState>,
     user: AuthenticatedUser,
     Json(request): Json<CreateOffenseRequest>,
 ) -> Result<impl IntoResponse> {
+    state.email_verification.require_verified(user.id).await?;
     let offense_service = OffenseService::new(&state.db_pool);
     let offense = offense_service
         .create_offense(request, Some(user.id))
@@ -79,48 +83,229 @@ pub async fn create_offense(
     Ok((StatusCode::CREATED, Json(offense)))
 }
 
-/// Add evidence to an offense (requires authentication)
+/// Add evidence to an offense (requires a verified email)
 pub async fn add_evidence(
     State(state): State<AppState>,
     user: AuthenticatedUser,
     Json(request): Json<AddEvidenceRequest>,
 ) -> Result<impl IntoResponse> {
+    state.email_verification.require_verified(user.id).await?;
     let offense_service = OffenseService::new(&state.db_pool);
     let evidence = offense_service.add_evidence(request, Some(user.id)).await?;
     Ok((StatusCode::CREATED, Json(evidence)))
 }
 
-/// Verify an offense (moderator only)
-/// Requires moderator or admin role to verify offenses
+/// Verify an offense (requires `offense.verify`)
 pub async fn verify_offense(
     State(state): State<AppState>,
     user: AuthenticatedUser,
-    claims: Claims,
     Path(offense_id): Path<Uuid>,
+    Json(request): Json<ReviewNotesRequest>,
 ) -> Result<StatusCode> {
-    // Check if user has moderator role
-    if !claims.has_moderator_access() {
-        tracing::warn!(
-            user_id = %user.id,
-            offense_id = %offense_id,
-            role = ?claims.role,
-            "Unauthorized attempt to verify offense - moderator role required"
-        );
-        return Err(AppError::InsufficientPermissions);
-    }
-
     tracing::info!(
         user_id = %user.id,
         offense_id = %offense_id,
-        role = ?claims.role,
         "Moderator verifying offense"
     );
 
     let offense_service = OffenseService::new(&state.db_pool);
-    offense_service.verify_offense(offense_id, user.id).await?;
+    offense_service
+        .verify_offense(offense_id, user.id, &request.reviewer_notes)
+        .await?;
     Ok(StatusCode::OK)
 }
 
+/// Get the legal-status timeline for an offense. Public, so the users who
+/// submitted and reviewed each event are left out.
+pub async fn get_offense_timeline(
+    State(state): State<AppState>,
+    Path(offense_id): Path<Uuid>,
+) -> Result<Json<OffenseTimeline>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let mut timeline = offense_service.get_offense_timeline(offense_id).await?;
+    timeline.events = timeline
+        .events
+        .into_iter()
+        .map(OffenseEvent::without_submitters)
+        .collect();
+    Ok(Json(timeline))
+}
+
+/// Add a legal lifecycle event to an offense (requires a verified email).
+/// Events from community moderators are verified immediately; others await review.
+pub async fn add_offense_event(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    permissions: Permissions,
+    Path(offense_id): Path<Uuid>,
+    Json(request): Json<AddOffenseEventRequest>,
+) -> Result<impl IntoResponse> {
+    state.email_verification.require_verified(user.id).await?;
+    let offense_service = OffenseService::new(&state.db_pool);
+    let event = offense_service
+        .add_offense_event(
+            offense_id,
+            request,
+            user.id,
+            permissions.has(Permission::CommunityModerate),
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(event)))
+}
+
+/// Approve or reject a pending offense event (requires `community.moderate`)
+pub async fn review_offense_event(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((offense_id, event_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<ReviewOffenseEventRequest>,
+) -> Result<Json<OffenseEvent>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let event = offense_service
+        .review_offense_event(
+            offense_id,
+            event_id,
+            request.approve,
+            &request.reviewer_notes,
+            user.id,
+        )
+        .await?;
+    Ok(Json(event))
+}
+
+/// Edit an offense (requires `offense.verify`). Requires reviewer notes.
+pub async fn update_offense(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(offense_id): Path<Uuid>,
+    Json(request): Json<UpdateOffenseRequest>,
+) -> Result<Json<ArtistOffense>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let offense = offense_service
+        .update_offense(offense_id, request, user.id)
+        .await?;
+    Ok(Json(offense))
+}
+
+/// Edit evidence on an offense (requires `offense.verify`). Requires reviewer notes.
+pub async fn update_evidence(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((offense_id, evidence_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<UpdateEvidenceRequest>,
+) -> Result<Json<OffenseEvidence>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let evidence = offense_service
+        .update_evidence(offense_id, evidence_id, request, user.id)
+        .await?;
+    Ok(Json(evidence))
+}
+
+/// Query parameters for diffing two revisions
+#[derive(Debug, Deserialize)]
+pub struct RevisionDiffQuery {
+    pub from: Option<i32>,
+    pub to: Option<i32>,
+}
+
+/// List an offense's revision history. Public, so the users behind each
+/// change are left out.
+pub async fn get_offense_revisions(
+    State(state): State<AppState>,
+    Path(offense_id): Path<Uuid>,
+) -> Result<Json<Vec<Revision>>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let revisions = offense_service.get_offense_revisions(offense_id).await?;
+    Ok(Json(
+        revisions
+            .into_iter()
+            .map(Revision::without_submitters)
+            .collect(),
+    ))
+}
+
+/// Diff two revisions of an offense (defaults to the latest change)
+pub async fn diff_offense_revisions(
+    State(state): State<AppState>,
+    Path(offense_id): Path<Uuid>,
+    Query(query): Query<RevisionDiffQuery>,
+) -> Result<Json<RevisionDiff>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let diff = offense_service
+        .diff_offense_revisions(offense_id, query.from, query.to)
+        .await?;
+    Ok(Json(diff.without_submitters()))
+}
+
+/// List an evidence item's revision history
+pub async fn get_evidence_revisions(
+    State(state): State<AppState>,
+    Path((offense_id, evidence_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<Vec<Revision>>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let revisions = offense_service
+        .get_evidence_revisions(offense_id, evidence_id)
+        .await?;
+    Ok(Json(
+        revisions
+            .into_iter()
+            .map(Revision::without_submitters)
+            .collect(),
+    ))
+}
+
+/// Diff two revisions of an evidence item (defaults to the latest change)
+pub async fn diff_evidence_revisions(
+    State(state): State<AppState>,
+    Path((offense_id, evidence_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<RevisionDiffQuery>,
+) -> Result<Json<RevisionDiff>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let diff = offense_service
+        .diff_evidence_revisions(offense_id, evidence_id, query.from, query.to)
+        .await?;
+    Ok(Json(diff.without_submitters()))
+}
+
+/// Dispute an offense (requires a verified email)
+pub async fn submit_dispute(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(offense_id): Path<Uuid>,
+    Json(request): Json<SubmitDisputeRequest>,
+) -> Result<impl IntoResponse> {
+    state.email_verification.require_verified(user.id).await?;
+    let offense_service = OffenseService::new(&state.db_pool);
+    let dispute = offense_service
+        .submit_dispute(offense_id, request, user.id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(dispute)))
+}
+
+/// List disputes for an offense, without who submitted or resolved them
+pub async fn get_disputes(
+    State(state): State<AppState>,
+    Path(offense_id): Path<Uuid>,
+) -> Result<Json<Vec<PublicOffenseDispute>>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let disputes = offense_service.get_disputes(offense_id).await?;
+    Ok(Json(disputes.into_iter().map(Into::into).collect()))
+}
+
+/// Uphold or dismiss a dispute (requires `community.moderate`). Requires reviewer notes.
+pub async fn resolve_dispute(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((offense_id, dispute_id)): Path<(Uuid, Uuid)>,
+    Json(request): Json<ResolveDisputeRequest>,
+) -> Result<Json<OffenseDispute>> {
+    let offense_service = OffenseService::new(&state.db_pool);
+    let dispute = offense_service
+        .resolve_dispute(offense_id, dispute_id, request, user.id)
+        .await?;
+    Ok(Json(dispute))
+}
+
 /// Import library tracks from file upload
 pub async fn import_library(
     State(state): State<AppState>,
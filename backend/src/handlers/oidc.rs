@@ -1,6 +1,30 @@
-use axum::{extract::State, response::Json};
+//! OpenID Connect discovery and provider endpoints
+//!
+//! The `/oauth2/*` endpoints speak plain OAuth to partner apps and answer
+//! with RFC 6749 error bodies. The `/api/v1/oidc/*` endpoints back the
+//! consent screen and the user's "connected apps" settings, and use the
+//! usual API envelope.
 
-use crate::AppState;
+use axum::{
+    extract::{Path, Query, RawQuery, State},
+    http::{
+        header::{AUTHORIZATION, CACHE_CONTROL, PRAGMA, WWW_AUTHENTICATE},
+        HeaderMap, HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Json, Redirect, Response},
+    Form,
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{TimeZone, Utc};
+
+use crate::config::public_frontend_base_url;
+use crate::models::{
+    AuthenticatedUser, Claims, CreateOidcClientRequest, OidcAuthorizationDecision,
+    OidcAuthorizationRequest, OidcRevocationRequest, OidcTokenRequest, OIDC_SCOPE_DNP_READ,
+    OIDC_SUPPORTED_SCOPES,
+};
+use crate::services::oidc_provider::{ClientCredentials, OidcAccessGrant, OidcError};
+use crate::{AppError, AppState, Result};
 
 /// GET /.well-known/openid-configuration
 pub async fn openid_configuration(State(state): State<AppState>) -> Json<serde_json::Value> {
@@ -8,9 +32,19 @@ pub async fn openid_configuration(State(state): State<AppState>) -> Json<serde_j
     Json(serde_json::json!({
         "issuer": issuer,
         "jwks_uri": format!("{}/.well-known/jwks.json", issuer),
+        "authorization_endpoint": format!("{}/oauth2/authorize", issuer),
+        "token_endpoint": format!("{}/oauth2/token", issuer),
+        "userinfo_endpoint": format!("{}/oauth2/userinfo", issuer),
+        "revocation_endpoint": format!("{}/oauth2/revoke", issuer),
+        "scopes_supported": OIDC_SUPPORTED_SCOPES,
+        "response_types_supported": ["code"],
+        "grant_types_supported": ["authorization_code", "refresh_token"],
+        "code_challenge_methods_supported": ["S256"],
+        "token_endpoint_auth_methods_supported": ["client_secret_basic", "client_secret_post", "none"],
         "id_token_signing_alg_values_supported": ["RS256"],
         "subject_types_supported": ["public"],
-        "response_types_supported": ["id_token"],
+        "claims_supported": ["iss", "sub", "aud", "exp", "iat", "auth_time", "nonce", "email", "email_verified"],
+        "authorization_response_iss_parameter_supported": true,
     }))
 }
 
@@ -18,3 +52,430 @@ pub async fn openid_configuration(State(state): State<AppState>) -> Json<serde_j
 pub async fn jwks(State(state): State<AppState>) -> Json<serde_json::Value> {
     Json(state.auth_service.jwks_response())
 }
+
+/// POST /api/v1/admin/jwt-keys/rotate (admin)
+///
+/// Replaces the signing key now, e.g. after a suspected compromise. Tokens
+/// signed with the old key stay valid until they expire.
+pub async fn rotate_signing_key_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let outcome = state.auth_service.jwt_keyring().rotate_now().await?;
+
+    tracing::warn!(
+        admin_id = %user.id,
+        kid = outcome.generated.as_deref().unwrap_or_default(),
+        retired = ?outcome.retired,
+        "JWT signing key rotated on demand"
+    );
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "kid": outcome.generated,
+            "retired": outcome.retired
+        }
+    })))
+}
+
+/// RFC 6749 §5.2 error body, with the challenge RFC 6750 §3 asks for on
+/// resource endpoints
+fn oauth_error_response(error: &OidcError) -> Response {
+    let status = StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::BAD_REQUEST);
+    let mut response = (
+        status,
+        Json(serde_json::json!({
+            "error": error.error,
+            "error_description": error.description,
+        })),
+    )
+        .into_response();
+
+    let headers = response.headers_mut();
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    let challenge = match error.error {
+        "invalid_client" => Some("Basic realm=\"ndith\"".to_string()),
+        "invalid_token" | "insufficient_scope" => {
+            Some(format!("Bearer realm=\"ndith\", error=\"{}\"", error.error))
+        }
+        _ => None,
+    };
+    if let Some(value) = challenge.and_then(|c| HeaderValue::from_str(&c).ok()) {
+        headers.insert(WWW_AUTHENTICATE, value);
+    }
+
+    response
+}
+
+/// Client credentials from `client_secret_basic` or the form body. Using
+/// both at once is rejected (RFC 6749 §2.3).
+fn client_credentials(
+    headers: &HeaderMap,
+    client_id: Option<&str>,
+    client_secret: Option<&str>,
+) -> std::result::Result<ClientCredentials, OidcError> {
+    let basic = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Basic "));
+
+    match basic {
+        Some(encoded) => {
+            if client_secret.is_some() {
+                return Err(OidcError::invalid_request(
+                    "Use only one client authentication method",
+                ));
+            }
+            // Issued client IDs and secrets are URL-safe, so the form
+            // encoding RFC 6749 §2.3.1 applies inside Basic is a no-op
+            let decoded = STANDARD
+                .decode(encoded.trim())
+                .ok()
+                .and_then(|bytes| String::from_utf8(bytes).ok())
+                .ok_or_else(|| OidcError::invalid_client("Malformed Basic credentials"))?;
+            let (id, secret) = decoded
+                .split_once(':')
+                .ok_or_else(|| OidcError::invalid_client("Malformed Basic credentials"))?;
+            if client_id.is_some_and(|body_id| body_id != id) {
+                return Err(OidcError::invalid_request("client_id does not match"));
+            }
+            Ok(ClientCredentials {
+                client_id: id.to_string(),
+                client_secret: Some(secret.to_string()),
+            })
+        }
+        None => Ok(ClientCredentials {
+            client_id: client_id
+                .ok_or_else(|| OidcError::invalid_client("client_id is required"))?
+                .to_string(),
+            client_secret: client_secret.map(str::to_string),
+        }),
+    }
+}
+
+async fn bearer_grant(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> std::result::Result<OidcAccessGrant, OidcError> {
+    let token = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or_else(|| OidcError::invalid_token("A bearer access token is required"))?;
+
+    state
+        .auth_service
+        .oidc()
+        .authenticate_access_token(token.trim())
+        .await
+}
+
+/// GET /oauth2/authorize
+///
+/// Checks the request, then hands it to the frontend consent screen, which
+/// signs the user in if needed and calls back into `/api/v1/oidc/authorize`.
+pub async fn authorize_redirect_handler(
+    State(state): State<AppState>,
+    Query(request): Query<OidcAuthorizationRequest>,
+    RawQuery(raw_query): RawQuery,
+) -> Response {
+    match state
+        .auth_service
+        .oidc()
+        .precheck_authorization(&request)
+        .await
+    {
+        Ok(()) => {
+            let frontend_base = public_frontend_base_url();
+            let redirect_to = format!(
+                "{}/oauth/authorize?{}",
+                frontend_base.trim_end_matches('/'),
+                raw_query.unwrap_or_default()
+            );
+            Redirect::to(&redirect_to).into_response()
+        }
+        Err(OidcError {
+            redirect_to: Some(redirect_to),
+            ..
+        }) => Redirect::to(&redirect_to).into_response(),
+        Err(error) => oauth_error_response(&error),
+    }
+}
+
+/// POST /oauth2/token
+pub async fn token_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(request): Form<OidcTokenRequest>,
+) -> Response {
+    let credentials = match client_credentials(
+        &headers,
+        request.client_id.as_deref(),
+        request.client_secret.as_deref(),
+    ) {
+        Ok(credentials) => credentials,
+        Err(error) => return oauth_error_response(&error),
+    };
+
+    match state
+        .auth_service
+        .oidc()
+        .exchange(&credentials, &request)
+        .await
+    {
+        Ok(tokens) => {
+            tracing::info!(
+                client_id = %credentials.client_id,
+                grant_type = %request.grant_type,
+                "OIDC tokens issued"
+            );
+            (
+                [(CACHE_CONTROL, "no-store"), (PRAGMA, "no-cache")],
+                Json(tokens),
+            )
+                .into_response()
+        }
+        Err(error) => {
+            tracing::warn!(
+                client_id = %credentials.client_id,
+                grant_type = %request.grant_type,
+                error = %error,
+                "OIDC token request rejected"
+            );
+            oauth_error_response(&error)
+        }
+    }
+}
+
+/// POST /oauth2/revoke
+pub async fn revoke_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(request): Form<OidcRevocationRequest>,
+) -> Response {
+    let result = match client_credentials(
+        &headers,
+        request.client_id.as_deref(),
+        request.client_secret.as_deref(),
+    ) {
+        Ok(credentials) => {
+            state
+                .auth_service
+                .oidc()
+                .revoke_token(&credentials, &request.token)
+                .await
+        }
+        Err(error) => Err(error),
+    };
+
+    match result {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(error) => oauth_error_response(&error),
+    }
+}
+
+/// GET or POST /oauth2/userinfo
+pub async fn userinfo_handler(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let result = match bearer_grant(&state, &headers).await {
+        Ok(grant) => state.auth_service.oidc().userinfo(&grant).await,
+        Err(error) => Err(error),
+    };
+
+    match result {
+        Ok(claims) => Json(claims).into_response(),
+        Err(error) => oauth_error_response(&error),
+    }
+}
+
+/// GET /api/v1/partner/dnp/list
+///
+/// The user's DNP list for a partner app holding `dnp:read`
+pub async fn partner_dnp_list_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Response {
+    let grant = match bearer_grant(&state, &headers)
+        .await
+        .and_then(|grant| grant.require_scope(OIDC_SCOPE_DNP_READ).map(|_| grant))
+    {
+        Ok(grant) => grant,
+        Err(error) => return oauth_error_response(&error),
+    };
+
+    match state.dnp_service.get_user_dnp_list(grant.user_id).await {
+        Ok(list) => Json(serde_json::json!({
+            "success": true,
+            "data": list
+        }))
+        .into_response(),
+        Err(e) => {
+            tracing::warn!(error = %e, user_id = %grant.user_id, "Failed to get DNP list");
+            AppError::Internal {
+                message: Some(e.to_string()),
+            }
+            .into_response()
+        }
+    }
+}
+
+/// Consent-screen errors either send the browser back to the client or are
+/// shown to the user
+fn consent_error(error: OidcError) -> Result<Json<serde_json::Value>> {
+    match error.redirect_to {
+        Some(redirect_to) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": { "redirect_to": redirect_to }
+        }))),
+        None if error.error == "server_error" => Err(AppError::Internal {
+            message: Some(error.description),
+        }),
+        None => Err(AppError::InvalidFieldValue {
+            field: "authorization_request".to_string(),
+            message: error.description,
+        }),
+    }
+}
+
+/// GET /api/v1/oidc/authorize
+///
+/// What the consent screen should show for an authorization request
+pub async fn authorization_prompt_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(request): Query<OidcAuthorizationRequest>,
+) -> Result<Json<serde_json::Value>> {
+    match state
+        .auth_service
+        .oidc()
+        .prepare_authorization(user.id, &request)
+        .await
+    {
+        Ok(prompt) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": prompt
+        }))),
+        Err(error) => consent_error(error),
+    }
+}
+
+/// POST /api/v1/oidc/authorize
+///
+/// Approve or deny an authorization request; the response says where to
+/// send the browser
+pub async fn authorization_decision_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    claims: Claims,
+    Json(decision): Json<OidcAuthorizationDecision>,
+) -> Result<Json<serde_json::Value>> {
+    // The session token's issue time is the closest we have to when the
+    // user last authenticated
+    let auth_time = Utc
+        .timestamp_opt(claims.iat, 0)
+        .single()
+        .unwrap_or_else(Utc::now);
+
+    match state
+        .auth_service
+        .oidc()
+        .authorize(user.id, auth_time, &decision)
+        .await
+    {
+        Ok(redirect_to) => Ok(Json(serde_json::json!({
+            "success": true,
+            "data": { "redirect_to": redirect_to }
+        }))),
+        Err(error) => consent_error(error),
+    }
+}
+
+/// GET /api/v1/oidc/consents
+pub async fn list_consents_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let consents = state.auth_service.oidc().list_consents(user.id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": consents
+    })))
+}
+
+/// DELETE /api/v1/oidc/consents/:client_id
+pub async fn revoke_consent_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(client_id): Path<String>,
+) -> Result<Json<serde_json::Value>> {
+    state
+        .auth_service
+        .oidc()
+        .revoke_consent(user.id, &client_id)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Access revoked"
+    })))
+}
+
+/// GET /api/v1/oidc/clients (admin)
+pub async fn list_clients_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let clients = state.auth_service.oidc().list_clients().await?;
+    let clients: Vec<serde_json::Value> = clients
+        .into_iter()
+        .map(|client| {
+            let confidential = client.is_confidential();
+            let mut value = serde_json::json!(client);
+            value["confidential"] = serde_json::json!(confidential);
+            value
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": clients
+    })))
+}
+
+/// POST /api/v1/oidc/clients (admin)
+///
+/// The client secret is only returned in this response
+pub async fn create_client_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<CreateOidcClientRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    let client = state
+        .auth_service
+        .oidc()
+        .create_client(user.id, request)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": client
+        })),
+    ))
+}
+
+/// DELETE /api/v1/oidc/clients/:client_id (admin)
+pub async fn revoke_client_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+    Path(client_id): Path<String>,
+) -> Result<Json<serde_json::Value>> {
+    state.auth_service.oidc().revoke_client(&client_id).await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Client revoked"
+    })))
+}
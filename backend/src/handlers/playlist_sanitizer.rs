@@ -237,7 +237,7 @@ async fn setup_sanitizer(
 
 /// Get Spotify artist IDs on the user's blocklist.
 ///
-/// Queries the user_artist_blocks and category_subscriptions tables,
+/// Queries the effective_artist_blocks and category_subscriptions tables,
 /// extracting Spotify IDs from the artists.external_ids JSONB field.
 async fn get_blocked_spotify_ids(
     state: &AppState,
@@ -246,7 +246,7 @@ async fn get_blocked_spotify_ids(
     let rows: Vec<(String,)> = sqlx::query_as(
         r#"
         SELECT DISTINCT a.external_ids->>'spotify' AS spotify_id
-        FROM user_artist_blocks uab
+        FROM effective_artist_blocks uab
         JOIN artists a ON uab.artist_id = a.id
         WHERE uab.user_id = $1
           AND a.external_ids->>'spotify' IS NOT NULL
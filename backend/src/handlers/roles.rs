@@ -0,0 +1,130 @@
+//! Role grants (admin) and the caller's own permissions
+//!
+//! The admin routes are guarded by `users.manage` where they are registered.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::models::{AuthenticatedUser, Permission, Permissions, UserRole};
+use crate::{AppError, AppState, Result};
+
+fn parse_grantable_role(role: &str) -> Result<UserRole> {
+    let parsed: UserRole = role.parse().unwrap_or_default();
+    if !parsed.is_grantable() || parsed.as_str() != role.to_lowercase() {
+        return Err(AppError::InvalidFieldValue {
+            field: "role".to_string(),
+            message: "Must be 'moderator' or 'admin'".to_string(),
+        });
+    }
+    Ok(parsed)
+}
+
+/// Catalog of grantable roles and the permissions each carries (admin)
+pub async fn list_roles_handler() -> Result<Json<serde_json::Value>> {
+    let roles: Vec<serde_json::Value> = UserRole::GRANTABLE
+        .iter()
+        .map(|role| {
+            serde_json::json!({
+                "role": role,
+                "permissions": role.permissions(),
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "roles": roles,
+            "permissions": Permission::ALL,
+        }
+    })))
+}
+
+/// Roles granted to a user (admin)
+pub async fn get_user_roles_handler(
+    State(state): State<AppState>,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    let grants = state.roles.list_grants(user_id).await?;
+    let permissions = Permissions::from_roles(grants.iter().map(|grant| grant.role));
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "user_id": user_id,
+            "roles": grants,
+            "permissions": permissions.iter().collect::<Vec<_>>(),
+        }
+    })))
+}
+
+/// Grant a role to a user (admin)
+pub async fn grant_role_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((user_id, role)): Path<(Uuid, String)>,
+) -> Result<Json<serde_json::Value>> {
+    let role = parse_grantable_role(&role)?;
+    let granted = state.roles.grant(user.id, user_id, role).await?;
+
+    tracing::info!(
+        admin_id = %user.id,
+        user_id = %user_id,
+        role = role.as_str(),
+        granted = granted,
+        "Role grant requested"
+    );
+
+    let message = if granted {
+        "Role granted"
+    } else {
+        "User already holds this role"
+    };
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": { "user_id": user_id, "role": role, "changed": granted },
+        "message": message
+    })))
+}
+
+/// Revoke a role from a user (admin)
+pub async fn revoke_role_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((user_id, role)): Path<(Uuid, String)>,
+) -> Result<Json<serde_json::Value>> {
+    let role = parse_grantable_role(&role)?;
+    let revoked = state.roles.revoke(user.id, user_id, role).await?;
+    if !revoked {
+        return Err(AppError::NotFound {
+            resource: "Role grant".to_string(),
+        });
+    }
+
+    tracing::info!(
+        admin_id = %user.id,
+        user_id = %user_id,
+        role = role.as_str(),
+        "Role revoked"
+    );
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": { "user_id": user_id, "role": role, "changed": true },
+        "message": "Role revoked"
+    })))
+}
+
+/// The caller's roles and effective permissions
+pub async fn get_my_permissions_handler(
+    permissions: Permissions,
+) -> Result<Json<serde_json::Value>> {
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": permissions
+    })))
+}
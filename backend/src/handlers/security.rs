@@ -0,0 +1,63 @@
+//! Admin endpoints for login lockouts and IP blocks
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+
+use crate::middleware::LockKind;
+use crate::models::AuthenticatedUser;
+use crate::{AppError, AppState, Result};
+
+/// List active account lockouts and IP blocks (admin)
+pub async fn list_lockouts_handler(
+    State(state): State<AppState>,
+    _user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let lockouts = state.security_monitor.active_lockouts().await?;
+    let stats = state.security_monitor.get_security_stats().await;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": {
+            "lockouts": lockouts,
+            "stats": stats
+        }
+    })))
+}
+
+/// Lift an account lockout or IP block (admin)
+///
+/// `kind` is `account` (subject is the email) or `ip`
+pub async fn unlock_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((kind, subject)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>> {
+    let kind = LockKind::parse(&kind).ok_or_else(|| AppError::InvalidFieldValue {
+        field: "kind".to_string(),
+        message: "Must be 'account' or 'ip'".to_string(),
+    })?;
+
+    let lifted = state
+        .security_monitor
+        .unlock(kind, &subject, user.id)
+        .await?;
+    if !lifted {
+        return Err(AppError::NotFound {
+            resource: "Active lockout".to_string(),
+        });
+    }
+
+    tracing::info!(
+        admin_id = %user.id,
+        kind = kind.as_str(),
+        subject = %subject,
+        "Lockout lifted"
+    );
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Lockout lifted"
+    })))
+}
@@ -259,7 +259,7 @@ async fn get_blocked_spotify_ids(
     let rows: Vec<(String,)> = sqlx::query_as(
         r#"
         SELECT DISTINCT a.external_ids->>'spotify' AS spotify_id
-        FROM user_artist_blocks uab
+        FROM effective_artist_blocks uab
         JOIN artists a ON uab.artist_id = a.id
         WHERE uab.user_id = $1
           AND a.external_ids->>'spotify' IS NOT NULL
@@ -292,7 +292,7 @@ async fn get_blocked_artist_names(
     let rows: Vec<(String,)> = sqlx::query_as(
         r#"
         SELECT DISTINCT LOWER(a.canonical_name)
-        FROM user_artist_blocks uab
+        FROM effective_artist_blocks uab
         JOIN artists a ON uab.artist_id = a.id
         WHERE uab.user_id = $1
 
@@ -1826,7 +1826,7 @@ async fn get_blocked_artist_ids(state: &AppState, user_id: Uuid) -> Result<Vec<U
     let rows: Vec<(Uuid,)> = sqlx::query_as(
         r#"
         SELECT DISTINCT a.id
-        FROM user_artist_blocks uab
+        FROM effective_artist_blocks uab
         JOIN artists a ON uab.artist_id = a.id
         WHERE uab.user_id = $1
 
@@ -1861,13 +1861,14 @@ async fn get_blocked_artists_with_details(
     state: &AppState,
     user_id: Uuid,
 ) -> Result<Vec<BlockedArtistInfo>, AppError> {
-    // Get directly blocked artists
+    // Get directly blocked artists, including household and enforced blocks
     let direct_blocks: Vec<(Uuid, String, Option<String>)> = sqlx::query_as(
         r#"
-        SELECT a.id, a.canonical_name, uab.note
-        FROM user_artist_blocks uab
+        SELECT DISTINCT ON (a.id) a.id, a.canonical_name, uab.note
+        FROM effective_artist_blocks uab
         JOIN artists a ON uab.artist_id = a.id
         WHERE uab.user_id = $1
+        ORDER BY a.id, uab.note NULLS LAST
         "#,
     )
     .bind(user_id)
@@ -1895,7 +1896,7 @@ async fn get_blocked_artists_with_details(
         JOIN artists a ON ao.artist_id = a.id
         WHERE cs.user_id = $1
         AND a.id NOT IN (
-            SELECT artist_id FROM user_artist_blocks WHERE user_id = $1
+            SELECT artist_id FROM effective_artist_blocks WHERE user_id = $1
         )
         "#,
     )
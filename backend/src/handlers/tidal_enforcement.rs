@@ -322,7 +322,7 @@ async fn get_blocked_artists(
     let rows: Vec<(Option<String>, Option<String>)> = sqlx::query_as(
         r#"
         SELECT DISTINCT a.canonical_name as name, a.tidal_id
-        FROM user_artist_blocks uab
+        FROM effective_artist_blocks uab
         JOIN artists a ON uab.artist_id = a.id
         WHERE uab.user_id = $1
 
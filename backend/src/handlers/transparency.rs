@@ -0,0 +1,120 @@
+//! Public transparency statistics (no auth required)
+//!
+//! Figures are k-anonymous and noised by `TransparencyStatsService`; results
+//! are cached in Redis so repeated requests neither hit Postgres nor see a
+//! different answer until the cache expires.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::future::Future;
+
+use crate::error::Result;
+use crate::services::transparency::{PublicStats, TransparencyStatsService};
+use crate::AppState;
+
+const CACHE_TTL_SECONDS: u64 = 3600; // 1 hour
+
+#[derive(Debug, Deserialize)]
+pub struct MostBlockedQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimeSeriesQuery {
+    pub weeks: Option<i32>,
+}
+
+fn stats_service(state: &AppState) -> TransparencyStatsService {
+    TransparencyStatsService::new(state.db_pool.clone(), state.transparency_config.clone())
+}
+
+/// Serve a statistic from Redis, computing and caching it on a miss
+async fn cached_stats<T, F, Fut>(
+    state: &AppState,
+    cache_key: String,
+    compute: F,
+) -> Result<Json<serde_json::Value>>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<PublicStats<T>>>,
+{
+    use redis::AsyncCommands;
+
+    if let Ok(mut conn) = state.redis_pool.get().await {
+        let cached: Option<String> = conn.get(&cache_key).await.unwrap_or(None);
+        if let Some(cached_json) = cached {
+            if let Ok(stats) = serde_json::from_str::<PublicStats<T>>(&cached_json) {
+                return Ok(Json(serde_json::json!({
+                    "success": true,
+                    "data": stats,
+                    "cached": true
+                })));
+            }
+        }
+    }
+
+    let stats = compute().await?;
+
+    if let Ok(mut conn) = state.redis_pool.get().await {
+        if let Ok(stats_json) = serde_json::to_string(&stats) {
+            let _: std::result::Result<(), _> =
+                conn.set_ex(&cache_key, stats_json, CACHE_TTL_SECONDS).await;
+        }
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": stats,
+        "cached": false
+    })))
+}
+
+/// Artists blocked by the most users
+pub async fn get_most_blocked_artists_handler(
+    State(state): State<AppState>,
+    Query(query): Query<MostBlockedQuery>,
+) -> Result<Json<serde_json::Value>> {
+    let limit = query.limit.unwrap_or(25).clamp(1, 100);
+    let service = stats_service(&state);
+
+    cached_stats(
+        &state,
+        format!("public_stats:most_blocked:{}", limit),
+        || service.most_blocked_artists(limit),
+    )
+    .await
+}
+
+/// Weekly blocks per offense category
+pub async fn get_category_blocks_handler(
+    State(state): State<AppState>,
+    Query(query): Query<TimeSeriesQuery>,
+) -> Result<Json<serde_json::Value>> {
+    let weeks = query.weeks.unwrap_or(12).clamp(1, 52);
+    let service = stats_service(&state);
+
+    cached_stats(&state, format!("public_stats:categories:{}", weeks), || {
+        service.blocks_by_category(weeks)
+    })
+    .await
+}
+
+/// Weekly enforcement volume per platform
+pub async fn get_enforcement_volume_handler(
+    State(state): State<AppState>,
+    Query(query): Query<TimeSeriesQuery>,
+) -> Result<Json<serde_json::Value>> {
+    let weeks = query.weeks.unwrap_or(12).clamp(1, 52);
+    let service = stats_service(&state);
+
+    cached_stats(
+        &state,
+        format!("public_stats:enforcement:{}", weeks),
+        || service.enforcement_volume(weeks),
+    )
+    .await
+}
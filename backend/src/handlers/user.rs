@@ -1,12 +1,16 @@
 use crate::{
     models::AuthenticatedUser,
     services::user::{AccountDeletionRequest, UpdateUserProfileRequest},
+    services::{AuditContext, AuditEventType, AuditSeverity},
     AppState, Result,
 };
 use axum::{
-    extract::{Json as ExtractJson, State},
-    response::Json,
+    extract::{Json as ExtractJson, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
+use serde::Deserialize;
+use uuid::Uuid;
 
 /// Get user profile
 pub async fn get_profile_handler(
@@ -35,29 +39,130 @@ pub async fn update_profile_handler(
     })))
 }
 
-/// Export user data
+/// Queue a full export of the user's data
+///
+/// The export is built in the background; its download link appears on the
+/// request once it's ready.
 pub async fn export_data_handler(
     State(state): State<AppState>,
     user: AuthenticatedUser,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    let request = state.data_requests.request_export(user.id).await?;
+    audit_data_request(&state, user.id, "data_export_requested", request.id).await;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": request
+        })),
+    ))
+}
+
+/// Queue erasure of the user's account
+///
+/// Provider connections are revoked and the account deleted in the
+/// background once the confirmation email matches.
+pub async fn delete_account_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    ExtractJson(request): ExtractJson<AccountDeletionRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    let request = state
+        .data_requests
+        .request_erasure(user.id, &request)
+        .await?;
+    audit_data_request(&state, user.id, "account_erasure_requested", request.id).await;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": request
+        })),
+    ))
+}
+
+/// List the user's export and erasure requests
+pub async fn list_data_requests_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
 ) -> Result<Json<serde_json::Value>> {
-    let export_data = state.user_service.export_user_data(user.id).await?;
+    let requests = state.data_requests.list_for_user(user.id).await?;
 
     Ok(Json(serde_json::json!({
         "success": true,
-        "data": export_data
+        "data": requests
     })))
 }
 
-/// Delete user account
-pub async fn delete_account_handler(
+/// Get one of the user's export or erasure requests
+pub async fn get_data_request_handler(
     State(state): State<AppState>,
     user: AuthenticatedUser,
-    ExtractJson(request): ExtractJson<AccountDeletionRequest>,
+    Path(request_id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>> {
-    let deletion_result = state.user_service.delete_account(user.id, request).await?;
+    let request = state
+        .data_requests
+        .get_for_user(user.id, request_id)
+        .await?;
 
     Ok(Json(serde_json::json!({
         "success": true,
-        "data": deletion_result
+        "data": request
     })))
 }
+
+#[derive(Debug, Deserialize)]
+pub struct DataExportDownloadQuery {
+    pub token: String,
+}
+
+/// GET /api/v1/data-exports/:id/download?token=...
+///
+/// Public: the signed, time-limited link is the credential
+pub async fn download_data_export_handler(
+    State(state): State<AppState>,
+    Path(request_id): Path<Uuid>,
+    Query(query): Query<DataExportDownloadQuery>,
+) -> Result<Response> {
+    let download = state
+        .data_requests
+        .download(request_id, &query.token)
+        .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", download.file_name),
+            ),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        download.archive,
+    )
+        .into_response())
+}
+
+async fn audit_data_request(state: &AppState, user_id: Uuid, action: &str, request_id: Uuid) {
+    if let Err(e) = state
+        .audit_logger
+        .log_security_event(
+            AuditEventType::DataAccess,
+            AuditSeverity::Info,
+            action.to_string(),
+            serde_json::json!({ "request_id": request_id }),
+            Some(AuditContext {
+                user_id: Some(user_id),
+                session_id: None,
+                ip_address: None,
+                user_agent: None,
+                correlation_id: None,
+            }),
+        )
+        .await
+    {
+        tracing::warn!(error = %e, action = action, "Failed to audit data request");
+    }
+}
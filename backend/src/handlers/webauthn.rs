@@ -0,0 +1,173 @@
+//! WebAuthn passkey handlers
+//!
+//! Registration and credential management need a signed-in user. Passkey
+//! login and the security-key second factor challenge are public.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::models::{
+    AuthenticatedUser, WebAuthnAssertionRequest, WebAuthnCredentialRenameRequest,
+    WebAuthnLoginStartRequest, WebAuthnRegisterFinishRequest,
+};
+use crate::{AppError, AppState, Result};
+
+/// Start registering a passkey or security key
+pub async fn start_registration_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let challenge = state
+        .auth_service
+        .start_webauthn_registration(user.id)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": challenge
+    })))
+}
+
+/// Finish registering a passkey or security key
+pub async fn finish_registration_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<WebAuthnRegisterFinishRequest>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    tracing::info!(user_id = %user.id, "WebAuthn registration attempt");
+
+    let credential = state
+        .auth_service
+        .finish_webauthn_registration(user.id, request)
+        .await
+        .map_err(|e| {
+            tracing::warn!(error = %e, user_id = %user.id, "WebAuthn registration failed");
+            e
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "success": true,
+            "data": credential,
+            "message": "Passkey registered"
+        })),
+    ))
+}
+
+/// List the user's passkeys and security keys
+pub async fn list_credentials_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<serde_json::Value>> {
+    let credentials = state
+        .auth_service
+        .webauthn()
+        .list_credentials(user.id)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": credentials
+    })))
+}
+
+/// Rename a passkey
+pub async fn rename_credential_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(credential_id): Path<Uuid>,
+    Json(request): Json<WebAuthnCredentialRenameRequest>,
+) -> Result<Json<serde_json::Value>> {
+    let credential = state
+        .auth_service
+        .webauthn()
+        .rename_credential(user.id, credential_id, &request.name)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": credential
+    })))
+}
+
+/// Remove a passkey
+pub async fn delete_credential_handler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(credential_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    state
+        .auth_service
+        .delete_webauthn_credential(user.id, credential_id)
+        .await?;
+
+    tracing::info!(user_id = %user.id, credential = %credential_id, "WebAuthn credential removed");
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "message": "Passkey removed"
+    })))
+}
+
+/// Start a passwordless passkey login
+pub async fn start_login_handler(
+    State(state): State<AppState>,
+    Json(request): Json<WebAuthnLoginStartRequest>,
+) -> Result<Json<serde_json::Value>> {
+    let challenge = state
+        .auth_service
+        .start_passkey_login(request.email.as_deref())
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": challenge
+    })))
+}
+
+/// Finish a passwordless passkey login
+pub async fn finish_login_handler(
+    State(state): State<AppState>,
+    Json(request): Json<WebAuthnAssertionRequest>,
+) -> Result<Json<serde_json::Value>> {
+    let response = state
+        .auth_service
+        .passkey_login(request)
+        .await
+        .map_err(|e| {
+            tracing::warn!(error = %e, "Passkey login failed");
+            e
+        })?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": response,
+        "message": "Login successful"
+    })))
+}
+
+/// Challenge for a security key to answer in place of a TOTP at password login
+pub async fn start_second_factor_handler(
+    State(state): State<AppState>,
+    Json(request): Json<WebAuthnLoginStartRequest>,
+) -> Result<Json<serde_json::Value>> {
+    let email = request.email.ok_or_else(|| AppError::InvalidFieldValue {
+        field: "email".to_string(),
+        message: "Email is required".to_string(),
+    })?;
+
+    let challenge = state
+        .auth_service
+        .start_webauthn_second_factor(&email)
+        .await?;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "data": challenge
+    })))
+}
@@ -26,6 +26,10 @@ use std::sync::Arc;
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 
+use crate::middleware::permissions::requires;
+
+#[cfg(feature = "analytics")]
+use axum::extract::DefaultBodyLimit;
 #[cfg(not(feature = "news"))]
 use axum::routing::any;
 #[cfg(any(feature = "analytics", feature = "news"))]
@@ -36,8 +40,12 @@ use axum::{http::StatusCode, response::IntoResponse};
 // ---- Root-local modules (handlers, middleware, metrics, monitoring stay here) ----
 pub mod backfill_orchestrator;
 pub mod handlers;
+#[cfg(feature = "analytics")]
+pub mod listening_history_sync;
 pub mod middleware;
 pub mod monitoring;
+#[cfg(feature = "analytics")]
+pub mod report_delivery;
 pub mod runtime;
 
 // metrics module from ndith-services (not a root-local module)
@@ -85,7 +93,8 @@ pub use ndith_services as services;
 pub use backfill_orchestrator::{BackfillOrchestrator, BackfillProgress, BackfillResult};
 #[cfg(feature = "news")]
 pub use ndith_news::{
-    NewsPipelineConfig, NewsPipelineOrchestrator, ScheduledPipelineHandle, ScheduledPipelineRunner,
+    EvidenceLinkChecker, EvidenceLinkCheckerConfig, NewsPipelineConfig, NewsPipelineOrchestrator,
+    ScheduledPipelineHandle, ScheduledPipelineRunner,
 };
 pub use ndith_services::catalog_sync::{
     CatalogSyncOrchestrator, CreditsSyncService, OrchestratorBuilder,
@@ -120,17 +129,37 @@ pub struct AppState {
     pub auth_service: Arc<AuthService>,
     pub rate_limiter: Arc<RateLimitService>,
     pub audit_logger: Arc<AuditLoggingService>,
+    /// Checkpoints, verification and export of the hash-chained audit log
+    pub audit_chain: Arc<ndith_services::AuditChainService>,
+    /// Login lockouts and IP blocks shared across replicas
+    pub security_monitor: Arc<crate::middleware::SecurityMonitor>,
+    /// Role grants behind the per-route permission checks
+    pub roles: Arc<ndith_services::RoleService>,
     pub dnp_service: Arc<DnpListService>,
     pub user_service: Arc<UserService>,
+    /// Household membership and the shared and enforced blocklists
+    pub households: Arc<ndith_services::HouseholdService>,
+    /// Verification and change of account email addresses
+    pub email_verification: Arc<ndith_services::EmailVerificationService>,
+    /// Queued GDPR exports and account erasures
+    pub data_requests: Arc<ndith_services::DataRequestService>,
     pub monitoring: Arc<MonitoringSystem>,
     pub metrics: Arc<MetricsCollector>,
     pub catalog_sync: Arc<CatalogSyncOrchestrator>,
     pub platform_config: PlatformSyncConfig,
+    /// Privacy settings for the public transparency statistics
+    pub transparency_config: ndith_services::TransparencyConfig,
     pub credits_sync: Option<Arc<CreditsSyncService>>,
     pub backfill_orchestrator: Option<Arc<BackfillOrchestrator>>,
     /// News pipeline for artist research (feature-gated)
     #[cfg(feature = "news")]
     pub news_pipeline: Option<Arc<NewsPipelineOrchestrator>>,
+    /// DuckDB analytics store for trends and reporting (feature-gated)
+    #[cfg(feature = "analytics")]
+    pub analytics_db: Option<Arc<ndith_analytics::DuckDbClient>>,
+    /// On-demand and scheduled report generation (requires analytics_db)
+    #[cfg(feature = "analytics")]
+    pub report_scheduler: Option<Arc<ndith_analytics::ReportScheduler>>,
     pub apple_music_service: Arc<ndith_services::AppleMusicService>,
     /// Circuit breaker for provider API calls (US-026)
     pub circuit_breaker: Arc<CircuitBreakerService>,
@@ -168,6 +197,28 @@ pub fn create_router(state: AppState) -> Router {
         )
         .route("/login", post(handlers::auth::login_handler))
         .route("/refresh", post(handlers::auth::refresh_token_handler))
+        // Links mailed by the email verification and change flows
+        .route(
+            "/verify-email",
+            post(handlers::email_verification::verify_email_handler),
+        )
+        .route(
+            "/email-change/confirm",
+            post(handlers::email_verification::confirm_email_change_handler),
+        )
+        // WebAuthn passkey login and security-key second factor
+        .route(
+            "/webauthn/login/start",
+            post(handlers::webauthn::start_login_handler),
+        )
+        .route(
+            "/webauthn/login/finish",
+            post(handlers::webauthn::finish_login_handler),
+        )
+        .route(
+            "/webauthn/2fa/start",
+            post(handlers::webauthn::start_second_factor_handler),
+        )
         // OAuth routes
         .route(
             "/oauth/:provider/initiate",
@@ -217,13 +268,110 @@ pub fn create_router(state: AppState) -> Router {
             "/users/profile",
             put(handlers::user::update_profile_handler),
         )
-        .route("/users/export", get(handlers::user::export_data_handler))
+        .route(
+            "/users/permissions",
+            get(handlers::roles::get_my_permissions_handler),
+        )
+        .route(
+            "/users/email",
+            get(handlers::email_verification::get_email_status_handler),
+        )
+        .route(
+            "/users/email/change",
+            post(handlers::email_verification::request_email_change_handler),
+        )
+        .route(
+            "/users/email/change",
+            delete(handlers::email_verification::cancel_email_change_handler),
+        )
+        .route("/users/export", post(handlers::user::export_data_handler))
         .route(
             "/users/account",
             delete(handlers::user::delete_account_handler),
         )
+        .route(
+            "/users/data-requests",
+            get(handlers::user::list_data_requests_handler),
+        )
+        .route(
+            "/users/data-requests/:request_id",
+            get(handlers::user::get_data_request_handler),
+        )
+        // Household routes
+        .route(
+            "/households",
+            post(handlers::household::create_household_handler),
+        )
+        .route(
+            "/households/me",
+            get(handlers::household::get_household_handler),
+        )
+        .route(
+            "/households/me",
+            delete(handlers::household::dissolve_household_handler),
+        )
+        .route(
+            "/households/me/leave",
+            post(handlers::household::leave_household_handler),
+        )
+        .route(
+            "/households/me/invites",
+            post(handlers::household::create_invite_handler),
+        )
+        .route(
+            "/households/me/invites/:invite_id",
+            delete(handlers::household::revoke_invite_handler),
+        )
+        .route(
+            "/households/invites",
+            get(handlers::household::list_my_invites_handler),
+        )
+        .route(
+            "/households/invites/:invite_id/accept",
+            post(handlers::household::accept_invite_handler),
+        )
+        .route(
+            "/households/invites/:invite_id/decline",
+            post(handlers::household::decline_invite_handler),
+        )
+        .route(
+            "/households/me/members/:user_id",
+            put(handlers::household::update_member_handler),
+        )
+        .route(
+            "/households/me/members/:user_id",
+            delete(handlers::household::remove_member_handler),
+        )
+        .route(
+            "/households/me/blocklist",
+            get(handlers::household::get_shared_blocklist_handler),
+        )
+        .route(
+            "/households/me/blocklist",
+            post(handlers::household::add_shared_block_handler),
+        )
+        .route(
+            "/households/me/blocklist/:artist_id",
+            delete(handlers::household::remove_shared_block_handler),
+        )
+        .route(
+            "/households/me/members/:user_id/enforced",
+            get(handlers::household::get_enforced_blocks_handler),
+        )
+        .route(
+            "/households/me/members/:user_id/enforced",
+            post(handlers::household::add_enforced_block_handler),
+        )
+        .route(
+            "/households/me/members/:user_id/enforced/:artist_id",
+            delete(handlers::household::remove_enforced_block_handler),
+        )
         // Auth routes (protected)
         .route("/auth/logout", post(handlers::auth::logout_handler))
+        .route(
+            "/auth/verify-email/resend",
+            post(handlers::email_verification::resend_verification_handler),
+        )
         // 2FA routes
         .route("/auth/2fa/setup", post(handlers::auth::setup_2fa_handler))
         .route("/auth/2fa/verify", post(handlers::auth::verify_2fa_handler))
@@ -231,6 +379,145 @@ pub fn create_router(state: AppState) -> Router {
             "/auth/2fa/disable",
             post(handlers::auth::disable_2fa_handler),
         )
+        .route(
+            "/auth/2fa/recovery-codes",
+            get(handlers::auth::get_recovery_codes_handler),
+        )
+        .route(
+            "/auth/2fa/recovery-codes/regenerate",
+            post(handlers::auth::regenerate_recovery_codes_handler),
+        )
+        // WebAuthn credential management
+        .route(
+            "/auth/webauthn/register/start",
+            post(handlers::webauthn::start_registration_handler),
+        )
+        .route(
+            "/auth/webauthn/register/finish",
+            post(handlers::webauthn::finish_registration_handler),
+        )
+        .route(
+            "/auth/webauthn/credentials",
+            get(handlers::webauthn::list_credentials_handler),
+        )
+        .route(
+            "/auth/webauthn/credentials/:credential_id",
+            put(handlers::webauthn::rename_credential_handler),
+        )
+        .route(
+            "/auth/webauthn/credentials/:credential_id",
+            delete(handlers::webauthn::delete_credential_handler),
+        )
+        // OpenID Connect consent screen and connected apps
+        .route(
+            "/oidc/authorize",
+            get(handlers::oidc::authorization_prompt_handler),
+        )
+        .route(
+            "/oidc/authorize",
+            post(handlers::oidc::authorization_decision_handler),
+        )
+        .route("/oidc/consents", get(handlers::oidc::list_consents_handler))
+        .route(
+            "/oidc/consents/:client_id",
+            delete(handlers::oidc::revoke_consent_handler),
+        )
+        // Audit log chain verification and export (admin)
+        .route(
+            "/audit/verify",
+            requires(
+                Permission::AuditManage,
+                get(handlers::audit::verify_chain_handler),
+            ),
+        )
+        .route(
+            "/audit/export",
+            requires(
+                Permission::AuditManage,
+                get(handlers::audit::export_chain_handler),
+            ),
+        )
+        .route(
+            "/audit/checkpoints",
+            requires(
+                Permission::AuditManage,
+                post(handlers::audit::create_checkpoint_handler),
+            ),
+        )
+        // Login lockouts and IP blocks (admin)
+        .route(
+            "/security/lockouts",
+            requires(
+                Permission::UsersManage,
+                get(handlers::security::list_lockouts_handler),
+            ),
+        )
+        .route(
+            "/security/lockouts/:kind/:subject",
+            requires(
+                Permission::UsersManage,
+                delete(handlers::security::unlock_handler),
+            ),
+        )
+        // OpenID Connect client registration (admin)
+        .route(
+            "/oidc/clients",
+            requires(
+                Permission::OidcClientsManage,
+                get(handlers::oidc::list_clients_handler),
+            ),
+        )
+        .route(
+            "/oidc/clients",
+            requires(
+                Permission::OidcClientsManage,
+                post(handlers::oidc::create_client_handler),
+            ),
+        )
+        .route(
+            "/oidc/clients/:client_id",
+            requires(
+                Permission::OidcClientsManage,
+                delete(handlers::oidc::revoke_client_handler),
+            ),
+        )
+        // JWT signing key rotation (admin)
+        .route(
+            "/admin/jwt-keys/rotate",
+            requires(
+                Permission::KeysManage,
+                post(handlers::oidc::rotate_signing_key_handler),
+            ),
+        )
+        // Role grants (admin)
+        .route(
+            "/admin/roles",
+            requires(
+                Permission::UsersManage,
+                get(handlers::roles::list_roles_handler),
+            ),
+        )
+        .route(
+            "/admin/users/:user_id/roles",
+            requires(
+                Permission::UsersManage,
+                get(handlers::roles::get_user_roles_handler),
+            ),
+        )
+        .route(
+            "/admin/users/:user_id/roles/:role",
+            requires(
+                Permission::UsersManage,
+                put(handlers::roles::grant_role_handler),
+            ),
+        )
+        .route(
+            "/admin/users/:user_id/roles/:role",
+            requires(
+                Permission::UsersManage,
+                delete(handlers::roles::revoke_role_handler),
+            ),
+        )
         // OAuth account management (protected)
         .route(
             "/auth/oauth/:provider/link",
@@ -260,6 +547,10 @@ pub fn create_router(state: AppState) -> Router {
             "/dnp/list/:artist_id",
             put(handlers::dnp::update_dnp_entry_handler),
         )
+        .route(
+            "/dnp/effective",
+            get(handlers::household::get_effective_blocklist_handler),
+        )
         // Track-level blocking routes
         .route("/dnp/tracks", post(handlers::dnp::add_track_block_handler))
         .route("/dnp/tracks", get(handlers::dnp::get_track_blocks_handler))
@@ -335,7 +626,46 @@ pub fn create_router(state: AppState) -> Router {
         )
         .route(
             "/offenses/:offense_id/verify",
-            post(handlers::offense::verify_offense),
+            requires(
+                Permission::OffenseVerify,
+                post(handlers::offense::verify_offense),
+            ),
+        )
+        .route(
+            "/offenses/:offense_id/events",
+            post(handlers::offense::add_offense_event),
+        )
+        .route(
+            "/offenses/:offense_id/events/:event_id/review",
+            requires(
+                Permission::CommunityModerate,
+                post(handlers::offense::review_offense_event),
+            ),
+        )
+        .route(
+            "/offenses/:offense_id/edit",
+            requires(
+                Permission::OffenseVerify,
+                put(handlers::offense::update_offense),
+            ),
+        )
+        .route(
+            "/offenses/:offense_id/evidence/:evidence_id/edit",
+            requires(
+                Permission::OffenseVerify,
+                put(handlers::offense::update_evidence),
+            ),
+        )
+        .route(
+            "/offenses/:offense_id/dispute",
+            post(handlers::offense::submit_dispute),
+        )
+        .route(
+            "/offenses/:offense_id/disputes/:dispute_id/resolve",
+            requires(
+                Permission::CommunityModerate,
+                post(handlers::offense::resolve_dispute),
+            ),
         )
         // Category subscription routes
         .route("/categories", get(handlers::category::get_categories))
@@ -357,7 +687,13 @@ pub fn create_router(state: AppState) -> Router {
         )
         // Catalog sync routes
         .route("/sync/status", get(handlers::sync::get_sync_status_handler))
-        .route("/sync/trigger", post(handlers::sync::trigger_sync_handler))
+        .route(
+            "/sync/trigger",
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::sync::trigger_sync_handler),
+            ),
+        )
         .route("/sync/runs", get(handlers::sync::get_sync_runs_handler))
         .route(
             "/sync/runs/:run_id",
@@ -365,7 +701,10 @@ pub fn create_router(state: AppState) -> Router {
         )
         .route(
             "/sync/runs/:run_id/cancel",
-            post(handlers::sync::cancel_sync_run_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::sync::cancel_sync_run_handler),
+            ),
         )
         .route(
             "/sync/resolve-identity",
@@ -373,7 +712,10 @@ pub fn create_router(state: AppState) -> Router {
         )
         .route(
             "/sync/merge-artists",
-            post(handlers::sync::merge_artists_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::sync::merge_artists_handler),
+            ),
         )
         .route(
             "/sync/search",
@@ -391,7 +733,10 @@ pub fn create_router(state: AppState) -> Router {
         // Credits sync routes
         .route(
             "/sync/credits",
-            post(handlers::sync::trigger_credits_sync_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::sync::trigger_credits_sync_handler),
+            ),
         )
         .route(
             "/sync/credits/status",
@@ -399,21 +744,33 @@ pub fn create_router(state: AppState) -> Router {
         )
         .route(
             "/sync/credits/:artist_id",
-            post(handlers::sync::trigger_artist_credits_sync_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::sync::trigger_artist_credits_sync_handler),
+            ),
         )
         // Bulk import routes
         .route(
             "/sync/import-charts",
-            post(handlers::sync::import_charts_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::sync::import_charts_handler),
+            ),
         )
         .route(
             "/sync/import-musicbrainz",
-            post(handlers::sync::import_musicbrainz_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::sync::import_musicbrainz_handler),
+            ),
         )
         // Offense backfill routes
         .route(
             "/sync/backfill-offenses",
-            post(handlers::sync::backfill_offenses_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::sync::backfill_offenses_handler),
+            ),
         )
         .route(
             "/sync/backfill-status",
@@ -600,17 +957,53 @@ pub fn create_router(state: AppState) -> Router {
         .route(
             "/connections/youtube",
             delete(handlers::youtube_connection::youtube_disconnect_handler),
-        )
-        .layer(axum::middleware::from_fn_with_state(
-            state.auth_service.clone(),
-            crate::middleware::auth::auth_middleware,
-        ));
+        );
+    let protected_routes = authenticated(&state, protected_routes);
 
     // Public offense database routes (no auth required to browse)
     let offense_public_routes = Router::new()
         .route("/", get(handlers::offense::get_flagged_artists))
         .route("/query", get(handlers::offense::get_category_artists))
-        .route("/:offense_id", get(handlers::offense::get_offense));
+        .route("/:offense_id", get(handlers::offense::get_offense))
+        .route(
+            "/:offense_id/timeline",
+            get(handlers::offense::get_offense_timeline),
+        )
+        .route(
+            "/:offense_id/revisions",
+            get(handlers::offense::get_offense_revisions),
+        )
+        .route(
+            "/:offense_id/revisions/diff",
+            get(handlers::offense::diff_offense_revisions),
+        )
+        .route(
+            "/:offense_id/evidence/:evidence_id/revisions",
+            get(handlers::offense::get_evidence_revisions),
+        )
+        .route(
+            "/:offense_id/evidence/:evidence_id/revisions/diff",
+            get(handlers::offense::diff_evidence_revisions),
+        )
+        .route(
+            "/:offense_id/disputes",
+            get(handlers::offense::get_disputes),
+        );
+
+    // Public transparency statistics (k-anonymous, cached)
+    let public_stats_routes = Router::new()
+        .route(
+            "/artists/most-blocked",
+            get(handlers::transparency::get_most_blocked_artists_handler),
+        )
+        .route(
+            "/categories",
+            get(handlers::transparency::get_category_blocks_handler),
+        )
+        .route(
+            "/enforcement",
+            get(handlers::transparency::get_enforcement_volume_handler),
+        );
 
     Router::new()
         // OIDC discovery + JWKS (must be top-level, public, for Convex JWT verification)
@@ -619,6 +1012,20 @@ pub fn create_router(state: AppState) -> Router {
             get(handlers::oidc::openid_configuration),
         )
         .route("/.well-known/jwks.json", get(handlers::oidc::jwks))
+        // OpenID Connect provider endpoints for partner apps
+        .route(
+            "/oauth2/authorize",
+            get(handlers::oidc::authorize_redirect_handler),
+        )
+        .route("/oauth2/token", post(handlers::oidc::token_handler))
+        .route("/oauth2/revoke", post(handlers::oidc::revoke_handler))
+        .route("/oauth2/userinfo", get(handlers::oidc::userinfo_handler))
+        .route("/oauth2/userinfo", post(handlers::oidc::userinfo_handler))
+        // Partner app resources, authorized by OIDC access token scopes
+        .route(
+            "/api/v1/partner/dnp/list",
+            get(handlers::oidc::partner_dnp_list_handler),
+        )
         // Health check endpoints
         .route("/health", get(health_check))
         .route("/health/ready", get(readiness_check_endpoint))
@@ -652,6 +1059,13 @@ pub fn create_router(state: AppState) -> Router {
         .nest("/api/v1/auth", auth_routes)
         // Public offense browsing routes
         .nest("/api/v1/offenses", offense_public_routes)
+        // Public transparency statistics
+        .nest("/api/v1/public/stats", public_stats_routes)
+        // Signed, time-limited GDPR export downloads
+        .route(
+            "/api/v1/data-exports/:request_id/download",
+            get(handlers::user::download_data_export_handler),
+        )
         // Public Apple Music auth route
         .route(
             "/api/v1/apple-music/auth/developer-token",
@@ -659,6 +1073,11 @@ pub fn create_router(state: AppState) -> Router {
         )
         // Protected API routes
         .nest("/api/v1", protected_routes)
+        // Blocked IPs are turned away before any handler runs
+        .layer(axum::middleware::from_fn_with_state(
+            state.security_monitor.clone(),
+            crate::middleware::security::ip_block_middleware,
+        ))
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
@@ -687,7 +1106,6 @@ pub fn create_analytics_router(state: AppState) -> Router {
 
 #[cfg(feature = "news")]
 pub fn create_news_router(state: AppState) -> Router {
-    let auth_service = state.auth_service.clone();
     let metrics = state.metrics.clone();
 
     let protected_routes = add_news_routes(Router::new());
@@ -704,13 +1122,7 @@ pub fn create_news_router(state: AppState) -> Router {
             "/api/v1/news/research/trigger",
             post(handlers::news::trigger_research_handler),
         )
-        .nest(
-            "/api/v1",
-            protected_routes.layer(axum::middleware::from_fn_with_state(
-                auth_service,
-                crate::middleware::auth::auth_middleware,
-            )),
-        )
+        .nest("/api/v1", authenticated(&state, protected_routes))
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
@@ -728,7 +1140,6 @@ pub fn create_news_router(state: AppState) -> Router {
 
 #[allow(dead_code)]
 fn create_scoped_service_router(state: AppState, protected_routes: Router<AppState>) -> Router {
-    let auth_service = state.auth_service.clone();
     let metrics = state.metrics.clone();
 
     Router::new()
@@ -738,13 +1149,7 @@ fn create_scoped_service_router(state: AppState, protected_routes: Router<AppSta
         .route("/metrics", get(metrics_endpoint))
         .route("/metrics/prometheus", get(prometheus_metrics_endpoint))
         .route("/monitoring", get(comprehensive_monitoring_endpoint))
-        .nest(
-            "/api/v1",
-            protected_routes.layer(axum::middleware::from_fn_with_state(
-                auth_service,
-                crate::middleware::auth::auth_middleware,
-            )),
-        )
+        .nest("/api/v1", authenticated(&state, protected_routes))
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
@@ -760,6 +1165,19 @@ fn create_scoped_service_router(state: AppState, protected_routes: Router<AppSta
         .with_state(state)
 }
 
+/// Put `routes` behind authentication and resolve the caller's permissions
+fn authenticated(state: &AppState, routes: Router<AppState>) -> Router<AppState> {
+    routes
+        .layer(axum::middleware::from_fn_with_state(
+            state.roles.clone(),
+            crate::middleware::permissions::resolve_permissions,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.auth_service.clone(),
+            crate::middleware::auth::auth_middleware,
+        ))
+}
+
 fn add_full_platform_routes(router: Router<AppState>) -> Router<AppState> {
     add_news_routes(add_analytics_routes(add_graph_routes(router)))
 }
@@ -821,7 +1239,10 @@ fn add_graph_routes(router: Router<AppState>) -> Router<AppState> {
         )
         .route(
             "/graph/sync/trigger",
-            post(handlers::graph::trigger_sync_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::graph::trigger_sync_handler),
+            ),
         )
         .route(
             "/graph/health",
@@ -844,6 +1265,10 @@ fn add_graph_routes(router: Router<AppState>) -> Router<AppState> {
         .route("/graph/*path", any(full_platform_unavailable))
 }
 
+/// Upload limit for listening history exports
+#[cfg(feature = "analytics")]
+const LISTENING_IMPORT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
 #[cfg(feature = "analytics")]
 fn add_analytics_routes(router: Router<AppState>) -> Router<AppState> {
     router
@@ -885,19 +1310,47 @@ fn add_analytics_routes(router: Router<AppState>) -> Router<AppState> {
         )
         .route(
             "/analytics/reports",
-            post(handlers::analytics_v2::generate_report_handler),
+            requires(
+                Permission::AnalyticsGlobal,
+                get(handlers::analytics_v2::list_reports_handler)
+                    .post(handlers::analytics_v2::generate_report_handler),
+            ),
+        )
+        .route(
+            "/analytics/reports/schedules",
+            requires(
+                Permission::AnalyticsGlobal,
+                get(handlers::analytics_v2::list_report_schedules_handler)
+                    .post(handlers::analytics_v2::create_report_schedule_handler),
+            ),
+        )
+        .route(
+            "/analytics/reports/schedules/:schedule_id",
+            requires(
+                Permission::AnalyticsGlobal,
+                delete(handlers::analytics_v2::delete_report_schedule_handler),
+            ),
         )
         .route(
             "/analytics/reports/:report_id",
-            get(handlers::analytics_v2::get_report_status_handler),
+            requires(
+                Permission::AnalyticsGlobal,
+                get(handlers::analytics_v2::get_report_status_handler),
+            ),
         )
         .route(
             "/analytics/reports/:report_id/download",
-            get(handlers::analytics_v2::download_report_handler),
+            requires(
+                Permission::AnalyticsGlobal,
+                get(handlers::analytics_v2::download_report_handler),
+            ),
         )
         .route(
             "/analytics/export/parquet",
-            post(handlers::analytics_v2::export_to_parquet_handler),
+            requires(
+                Permission::AnalyticsGlobal,
+                post(handlers::analytics_v2::export_to_parquet_handler),
+            ),
         )
         .route(
             "/analytics/trouble-scores/artist/:artist_id",
@@ -917,7 +1370,39 @@ fn add_analytics_routes(router: Router<AppState>) -> Router<AppState> {
         )
         .route(
             "/analytics/trouble-scores/recalculate",
-            post(handlers::analytics_v2::recalculate_trouble_scores_handler),
+            requires(
+                Permission::AnalyticsGlobal,
+                post(handlers::analytics_v2::recalculate_trouble_scores_handler),
+            ),
+        )
+        .route(
+            "/analytics/trouble-scores/artist/:artist_id/explain",
+            get(handlers::analytics_v2::explain_artist_trouble_score_handler),
+        )
+        .route(
+            "/analytics/trouble-scores/what-if",
+            post(handlers::analytics_v2::trouble_score_what_if_handler),
+        )
+        .route(
+            "/analytics/trouble-scores/profiles",
+            get(handlers::analytics_v2::list_score_profiles_handler)
+                .post(handlers::analytics_v2::create_score_profile_handler),
+        )
+        .route(
+            "/analytics/trouble-scores/profiles/me",
+            get(handlers::analytics_v2::get_my_score_profile_handler)
+                .put(handlers::analytics_v2::set_my_score_profile_handler),
+        )
+        .route(
+            "/analytics/trouble-scores/profiles/:profile_id",
+            get(handlers::analytics_v2::get_score_profile_handler),
+        )
+        .route(
+            "/analytics/trouble-scores/profiles/:profile_id/default",
+            requires(
+                Permission::AnalyticsGlobal,
+                post(handlers::analytics_v2::set_default_score_profile_handler),
+            ),
         )
         .route(
             "/analytics/revenue/distribution",
@@ -939,10 +1424,42 @@ fn add_analytics_routes(router: Router<AppState>) -> Router<AppState> {
             "/analytics/revenue/artist/:artist_id",
             get(handlers::analytics_v2::get_artist_revenue_breakdown_handler),
         )
+        .route(
+            "/analytics/listening-history/import",
+            post(handlers::analytics_v2::import_listening_history_handler)
+                // Full streaming history exports run to tens of megabytes
+                .layer(DefaultBodyLimit::max(LISTENING_IMPORT_MAX_BYTES)),
+        )
+        .route(
+            "/analytics/listening-history/imports",
+            get(handlers::analytics_v2::get_listening_imports_handler),
+        )
         .route(
             "/analytics/payout-rates",
             get(handlers::analytics_v2::get_payout_rates_handler),
         )
+        .route(
+            "/analytics/payout-rates",
+            requires(
+                Permission::AnalyticsGlobal,
+                post(handlers::analytics_v2::create_payout_rate_handler),
+            ),
+        )
+        .route(
+            "/analytics/payout-rates/history",
+            get(handlers::analytics_v2::get_payout_rate_history_handler),
+        )
+        .route(
+            "/analytics/payout-rates/:rate_id",
+            requires(
+                Permission::AnalyticsGlobal,
+                put(handlers::analytics_v2::correct_payout_rate_handler),
+            ),
+        )
+        .route(
+            "/analytics/payout-rates/:rate_id/changes",
+            get(handlers::analytics_v2::get_payout_rate_changes_handler),
+        )
         .route(
             "/analytics/category-revenue",
             get(handlers::analytics_v2::get_global_category_revenue_handler),
@@ -967,6 +1484,39 @@ fn add_analytics_routes(router: Router<AppState>) -> Router<AppState> {
             "/analytics/enforcement",
             get(handlers::analytics_v2::get_enforcement_analytics_handler),
         )
+        .route(
+            "/analytics/library-impact",
+            get(handlers::analytics_v2::get_library_impact_handler),
+        )
+        .route(
+            "/analytics/library-impact/regressions",
+            get(handlers::analytics_v2::get_library_regressions_handler),
+        )
+        .route(
+            "/analytics/library-impact/regressions/:regression_id/acknowledge",
+            post(handlers::analytics_v2::acknowledge_library_regression_handler),
+        )
+        .route(
+            "/analytics/replication/status",
+            requires(
+                Permission::AnalyticsGlobal,
+                get(handlers::analytics_v2::get_replication_status_handler),
+            ),
+        )
+        .route(
+            "/analytics/cohorts",
+            requires(
+                Permission::AnalyticsGlobal,
+                get(handlers::analytics_v2::get_cohorts_handler),
+            ),
+        )
+        .route(
+            "/analytics/funnels",
+            requires(
+                Permission::AnalyticsGlobal,
+                get(handlers::analytics_v2::get_funnels_handler),
+            ),
+        )
         .route(
             "/analytics/summary",
             get(handlers::analytics_v2::get_user_activity_summary_handler),
@@ -1003,7 +1553,10 @@ fn add_news_routes(router: Router<AppState>) -> Router<AppState> {
         )
         .route(
             "/news/offenses/:offense_id/verify",
-            post(handlers::news::verify_offense_handler),
+            requires(
+                Permission::OffenseVerify,
+                post(handlers::news::verify_offense_handler),
+            ),
         )
         .route(
             "/news/pipeline/status",
@@ -1011,7 +1564,10 @@ fn add_news_routes(router: Router<AppState>) -> Router<AppState> {
         )
         .route(
             "/news/pipeline/trigger",
-            post(handlers::news::trigger_pipeline_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::news::trigger_pipeline_handler),
+            ),
         )
         .route("/news/sources", get(handlers::news::get_sources_handler))
         .route("/news/trending", get(handlers::news::get_trending_handler))
@@ -1030,7 +1586,10 @@ fn add_news_routes(router: Router<AppState>) -> Router<AppState> {
         )
         .route(
             "/news/research/artists/:id/trigger",
-            post(handlers::news::trigger_artist_research_handler),
+            requires(
+                Permission::SyncTrigger,
+                post(handlers::news::trigger_artist_research_handler),
+            ),
         )
         .route(
             "/news/research/queue",
@@ -0,0 +1,156 @@
+//! Listening History Sync Job
+//!
+//! Periodically pulls recently-played history for connected Spotify accounts
+//! and ingests it alongside imported exports. Spotify only keeps the last 50
+//! plays, so the interval should be short enough that heavy listeners don't
+//! overflow it between runs.
+
+use chrono::Duration;
+use ndith_analytics::{
+    ListeningEvent, ListeningHistoryService, ListeningSource, ParsedExport, RevenuePlatform,
+};
+use ndith_services::RecentlyPlayedService;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::models::Connection;
+
+/// Summary of one sync run
+#[derive(Debug, Clone, Default)]
+pub struct ListeningSyncStats {
+    pub connections: usize,
+    pub synced: usize,
+    pub failed: usize,
+    pub plays_new: usize,
+}
+
+/// Scheduled recently-played sync
+pub struct ListeningHistorySyncJob {
+    listening_history: ListeningHistoryService,
+    recently_played: RecentlyPlayedService,
+    interval: Duration,
+}
+
+impl ListeningHistorySyncJob {
+    pub fn new(
+        db_pool: PgPool,
+        recently_played: RecentlyPlayedService,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            listening_history: ListeningHistoryService::new(db_pool),
+            recently_played,
+            interval,
+        }
+    }
+
+    /// Run the sync on an interval in the background
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = self
+                .interval
+                .to_std()
+                .unwrap_or(std::time::Duration::from_secs(3600));
+            let mut ticker = tokio::time::interval(period);
+
+            loop {
+                ticker.tick().await;
+                let stats = self.run_once().await;
+                tracing::info!(
+                    connections = stats.connections,
+                    synced = stats.synced,
+                    failed = stats.failed,
+                    plays_new = stats.plays_new,
+                    "Listening history sync completed"
+                );
+            }
+        })
+    }
+
+    /// Sync every eligible connection once
+    pub async fn run_once(&self) -> ListeningSyncStats {
+        let connections = self.recently_played.spotify_connections().await;
+        let mut stats = ListeningSyncStats {
+            connections: connections.len(),
+            ..Default::default()
+        };
+
+        for connection in &connections {
+            match self.sync_connection(connection).await {
+                Ok(plays_new) => {
+                    stats.synced += 1;
+                    stats.plays_new += plays_new;
+                }
+                Err(e) => {
+                    stats.failed += 1;
+                    tracing::warn!(
+                        user_id = %connection.user_id,
+                        connection_id = %connection.id,
+                        error = %e,
+                        "Recently-played sync failed"
+                    );
+                    let _ = self
+                        .listening_history
+                        .record_sync(
+                            connection.user_id,
+                            RevenuePlatform::Spotify,
+                            None,
+                            Some(&e.to_string()),
+                        )
+                        .await;
+                }
+            }
+        }
+
+        stats
+    }
+
+    async fn sync_connection(&self, connection: &Connection) -> anyhow::Result<usize> {
+        let cursor = self
+            .listening_history
+            .get_sync_cursor(connection.user_id, RevenuePlatform::Spotify)
+            .await?;
+
+        let plays = self
+            .recently_played
+            .fetch_spotify(connection, cursor)
+            .await?;
+        let latest = plays.last().map(|p| p.played_at);
+
+        // Spotify only lists plays it counted as streams, so every entry
+        // qualifies regardless of track length
+        let parsed = ParsedExport {
+            events: plays
+                .into_iter()
+                .map(|play| ListeningEvent {
+                    played_at: play.played_at,
+                    artist_name: play.artist_name,
+                    track_name: play.track_name,
+                    album_name: play.album_name,
+                    ms_played: None,
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        let plays_new = if parsed.events.is_empty() {
+            0
+        } else {
+            self.listening_history
+                .ingest(
+                    connection.user_id,
+                    ListeningSource::SpotifyRecentlyPlayed,
+                    RevenuePlatform::Spotify,
+                    parsed,
+                )
+                .await?
+                .events_new
+        };
+
+        self.listening_history
+            .record_sync(connection.user_id, RevenuePlatform::Spotify, latest, None)
+            .await?;
+
+        Ok(plays_new)
+    }
+}
@@ -2,10 +2,12 @@
 pub mod auth;
 pub mod cors;
 pub mod latency;
+pub mod permissions;
 pub mod security;
 pub mod tracing;
 
 pub use auth::*;
 pub use cors::*;
 pub use latency::*;
+pub use permissions::*;
 pub use security::*;
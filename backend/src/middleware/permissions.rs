@@ -0,0 +1,84 @@
+//! Per-route permission guards
+//!
+//! Routes declare what they need where they are registered:
+//!
+//! ```ignore
+//! .route("/audit/verify", requires(Permission::AuditManage, get(handler)))
+//! ```
+//!
+//! `resolve_permissions` runs inside the auth layer and stores the caller's
+//! effective [`Permissions`] in the request extensions, and the guard added
+//! by [`requires`] rejects the request with 403 unless the permission is
+//! present. Handlers that only need a permission for part of their work take
+//! `Permissions` as an extractor instead.
+
+use crate::models::{Claims, Permission, Permissions};
+use crate::services::RoleService;
+use crate::AppError;
+use axum::{
+    extract::{Request, State},
+    middleware::{from_fn_with_state, Next},
+    response::Response,
+    routing::MethodRouter,
+};
+use std::sync::Arc;
+
+/// Resolve the caller's permissions once per request
+///
+/// Must run after `auth_middleware`. If stored grants cannot be read the
+/// caller keeps only what the token itself carries.
+pub async fn resolve_permissions(
+    State(roles): State<Arc<RoleService>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if let Some(claims) = request.extensions().get::<Claims>().cloned() {
+        let permissions = match roles.permissions_for(&claims).await {
+            Ok(permissions) => permissions,
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    user_id = %claims.sub,
+                    "Failed to load role grants, using token permissions"
+                );
+                claims.permissions()
+            }
+        };
+        request.extensions_mut().insert(permissions);
+    }
+
+    next.run(request).await
+}
+
+/// Reject the request unless the caller holds `permission`
+pub async fn require_permission(
+    State(permission): State<Permission>,
+    permissions: Permissions,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if !permissions.has(permission) {
+        let user_id = request
+            .extensions()
+            .get::<Claims>()
+            .map(|claims| claims.sub.clone())
+            .unwrap_or_default();
+        tracing::warn!(
+            user_id = %user_id,
+            permission = permission.as_str(),
+            path = %request.uri().path(),
+            "Unauthorized request - missing permission"
+        );
+        return Err(AppError::InsufficientPermissions);
+    }
+
+    Ok(next.run(request).await)
+}
+
+/// Guard every method of `route` with `permission`
+pub fn requires<S>(permission: Permission, route: MethodRouter<S>) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    route.route_layer(from_fn_with_state(permission, require_permission))
+}
@@ -1,12 +1,24 @@
 use axum::{
-    extract::Request,
+    extract::{ConnectInfo, Request, State},
     http::{HeaderName, HeaderValue, StatusCode},
     middleware::Next,
     response::Response,
 };
-use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use deadpool_redis::Pool as RedisPool;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::config::BruteForceConfig;
+use crate::services::rate_limiting_middleware::extract_client_ip;
+use crate::services::{AuditContext, AuditEventType, AuditLoggingService, AuditSeverity};
+use crate::AppError;
 
 /// Security headers middleware for SOC2 compliance
 pub async fn security_headers_middleware(
@@ -65,173 +77,801 @@ pub async fn security_headers_middleware(
     Ok(response)
 }
 
-/// Rate limiting and suspicious activity detection
-#[derive(Clone)]
-pub struct SecurityMonitor {
-    failed_attempts: Arc<RwLock<HashMap<String, FailedAttemptTracker>>>,
-    suspicious_ips: Arc<RwLock<HashMap<String, SuspiciousActivity>>>,
+/// Counters kept per account or IP over the sliding window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Counter {
+    AccountFailures,
+    IpFailures,
+    IpSuspicious,
+}
+
+impl Counter {
+    fn as_str(self) -> &'static str {
+        match self {
+            Counter::AccountFailures => "account_failure",
+            Counter::IpFailures => "ip_failure",
+            Counter::IpSuspicious => "ip_suspicious",
+        }
+    }
+}
+
+/// What a lockout applies to: one account's logins, or every request from an IP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockKind {
+    Account,
+    Ip,
+}
+
+impl LockKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LockKind::Account => "account",
+            LockKind::Ip => "ip",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "account" => Some(LockKind::Account),
+            "ip" => Some(LockKind::Ip),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
-struct FailedAttemptTracker {
+/// An active account lockout or IP block
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Lockout {
+    pub kind: String,
+    pub subject: String,
+    pub reason: String,
+    pub failure_count: i32,
+    pub locked_at: DateTime<Utc>,
+    pub locked_until: DateTime<Utc>,
+}
+
+/// Events in the current window and when the latest one happened
+#[derive(Debug, Clone, Copy, Default)]
+struct WindowCount {
     count: u32,
-    first_attempt: chrono::DateTime<chrono::Utc>,
-    last_attempt: chrono::DateTime<chrono::Utc>,
-    locked_until: Option<chrono::DateTime<chrono::Utc>>,
+    last_at: Option<DateTime<Utc>>,
+}
+
+/// Result of recording a failed login
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FailedLoginOutcome {
+    pub account_failures: u32,
+    pub account_locked: bool,
+    pub ip_blocked: bool,
+    /// Seconds before the account may try again
+    pub retry_after_secs: u64,
+}
+
+/// Why a login attempt was turned away before its password was checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginRejection {
+    IpBlocked(DateTime<Utc>),
+    AccountLocked(DateTime<Utc>),
+    /// The account's progressive delay since its last failure hasn't passed
+    Delayed(DateTime<Utc>),
 }
 
-#[derive(Debug, Clone)]
-struct SuspiciousActivity {
-    request_count: u32,
-    first_request: chrono::DateTime<chrono::Utc>,
-    last_request: chrono::DateTime<chrono::Utc>,
-    blocked_until: Option<chrono::DateTime<chrono::Utc>>,
+impl LoginRejection {
+    /// Whether the attempt ran into a lockout or IP block, rather than
+    /// coming a little early after a failure
+    pub fn is_lockout(&self) -> bool {
+        !matches!(self, LoginRejection::Delayed(_))
+    }
+}
+
+impl From<LoginRejection> for AppError {
+    fn from(rejection: LoginRejection) -> Self {
+        match rejection {
+            LoginRejection::IpBlocked(until)
+            | LoginRejection::AccountLocked(until)
+            | LoginRejection::Delayed(until) => retry_after(until),
+        }
+    }
+}
+
+/// Seconds to skip Redis after it fails before trying it again
+const REDIS_RETRY_SECS: i64 = 30;
+
+/// Wait required after `failures` failed logins: nothing for the free
+/// attempts, then doubling from the base delay up to the cap
+fn progressive_delay(config: &BruteForceConfig, failures: u32) -> Duration {
+    if failures <= config.delay_free_attempts {
+        return Duration::zero();
+    }
+    let doublings = (failures - config.delay_free_attempts - 1).min(20);
+    let delay_ms = config
+        .delay_base_ms
+        .saturating_mul(1i64 << doublings)
+        .min(config.delay_max_ms);
+    Duration::milliseconds(delay_ms)
+}
+
+fn normalize_account(account: &str) -> String {
+    account.trim().to_lowercase()
+}
+
+fn counter_key(counter: Counter, subject: &str) -> String {
+    format!("security:{}:{}", counter.as_str(), subject)
+}
+
+fn lock_key(kind: LockKind, subject: &str) -> String {
+    format!("security:lock:{}:{}", kind.as_str(), subject)
+}
+
+fn retry_after(until: DateTime<Utc>) -> AppError {
+    let secs = (until - Utc::now()).num_milliseconds().max(0) as u64;
+    AppError::RateLimitExceeded {
+        retry_after: Some(secs.div_ceil(1000).max(1)),
+    }
+}
+
+/// Brute-force and suspicious activity detection shared by every replica
+///
+/// Failed logins are counted in sliding windows per account and per IP.
+/// Counters live in Redis and fall back to Postgres while Redis is
+/// unreachable. Lockouts and IP blocks are cached in Redis and always
+/// recorded in Postgres, which is copied back into Redis once it recovers.
+/// Storage errors fail open, like the rate limiter.
+#[derive(Clone)]
+pub struct SecurityMonitor {
+    db_pool: PgPool,
+    redis_pool: Option<RedisPool>,
+    config: BruteForceConfig,
+    audit_logger: Arc<AuditLoggingService>,
+    redis_down_until: Arc<RwLock<Option<DateTime<Utc>>>>,
+    redis_needs_resync: Arc<AtomicBool>,
 }
 
 impl SecurityMonitor {
-    pub fn new() -> Self {
+    pub fn new(
+        db_pool: PgPool,
+        redis_pool: Option<RedisPool>,
+        config: BruteForceConfig,
+        audit_logger: Arc<AuditLoggingService>,
+    ) -> Self {
         Self {
-            failed_attempts: Arc::new(RwLock::new(HashMap::new())),
-            suspicious_ips: Arc::new(RwLock::new(HashMap::new())),
+            db_pool,
+            redis_pool,
+            config,
+            audit_logger,
+            redis_down_until: Arc::new(RwLock::new(None)),
+            redis_needs_resync: Arc::new(AtomicBool::new(false)),
         }
     }
 
-    /// Record a failed login attempt
-    pub async fn record_failed_attempt(&self, identifier: String) -> bool {
-        let mut attempts = self.failed_attempts.write().await;
-        let now = chrono::Utc::now();
+    pub fn config(&self) -> &BruteForceConfig {
+        &self.config
+    }
 
-        let tracker = attempts.entry(identifier).or_insert(FailedAttemptTracker {
-            count: 0,
-            first_attempt: now,
-            last_attempt: now,
-            locked_until: None,
-        });
+    /// Reject a login attempt before checking the password when the IP is
+    /// blocked, the account is locked, or the account's progressive delay
+    /// since its last failure hasn't passed
+    pub async fn check_login(
+        &self,
+        account: &str,
+        ip: &str,
+    ) -> std::result::Result<(), LoginRejection> {
+        let account = normalize_account(account);
+
+        if let Some(until) = self.locked_until(LockKind::Ip, ip).await {
+            return Err(LoginRejection::IpBlocked(until));
+        }
+        if let Some(until) = self.locked_until(LockKind::Account, &account).await {
+            return Err(LoginRejection::AccountLocked(until));
+        }
+
+        let failures = self.count(Counter::AccountFailures, &account).await;
+        if let Some(last_at) = failures.last_at {
+            let ready_at = last_at + progressive_delay(&self.config, failures.count);
+            if ready_at > Utc::now() {
+                return Err(LoginRejection::Delayed(ready_at));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Count a failed login against the account and the IP, locking either
+    /// once it crosses its threshold
+    pub async fn record_failed_login(&self, account: &str, ip: &str) -> FailedLoginOutcome {
+        let account = normalize_account(account);
+        let account_failures = self.record(Counter::AccountFailures, &account).await;
+        let ip_failures = self.record(Counter::IpFailures, ip).await;
+
+        let mut outcome = FailedLoginOutcome {
+            account_failures: account_failures.count,
+            retry_after_secs: progressive_delay(&self.config, account_failures.count).num_seconds()
+                as u64,
+            ..Default::default()
+        };
 
-        // Reset counter if more than 1 hour has passed
-        if now.signed_duration_since(tracker.first_attempt).num_hours() > 1 {
-            tracker.count = 0;
-            tracker.first_attempt = now;
+        if account_failures.count >= self.config.account_max_failures {
+            let reason = format!(
+                "{} failed logins within {} seconds",
+                account_failures.count, self.config.window_secs
+            );
+            self.lock(
+                LockKind::Account,
+                &account,
+                &reason,
+                account_failures.count,
+                Duration::seconds(self.config.account_lockout_secs),
+            )
+            .await;
+            self.report("account_lockout", &reason, Some(ip), 0.6).await;
+            outcome.account_locked = true;
+            outcome.retry_after_secs = self.config.account_lockout_secs as u64;
         }
 
-        tracker.count += 1;
-        tracker.last_attempt = now;
+        if ip_failures.count >= self.config.ip_max_failures {
+            let reason = format!(
+                "{} failed logins across accounts within {} seconds",
+                ip_failures.count, self.config.window_secs
+            );
+            self.lock(
+                LockKind::Ip,
+                ip,
+                &reason,
+                ip_failures.count,
+                Duration::seconds(self.config.ip_block_secs),
+            )
+            .await;
+            self.report("ip_blocked", &reason, Some(ip), 0.8).await;
+            outcome.ip_blocked = true;
+        }
 
-        // Lock account after 5 failed attempts
-        if tracker.count >= 5 {
-            tracker.locked_until = Some(now + chrono::Duration::hours(1));
-            return true; // Account is locked
+        outcome
+    }
+
+    /// Reset the account's failures after a successful login. The IP's
+    /// count is kept so one good account can't launder an IP's failures.
+    pub async fn record_successful_login(&self, account: &str) {
+        self.clear(Counter::AccountFailures, &normalize_account(account))
+            .await;
+    }
+
+    /// Record suspicious activity from an IP; returns true once it's blocked
+    pub async fn record_suspicious_activity(&self, ip: &str, activity: &str) -> bool {
+        let activity_count = self.record(Counter::IpSuspicious, ip).await;
+        if activity_count.count < self.config.suspicious_activity_limit {
+            return false;
         }
 
-        false
+        let reason = format!(
+            "{} suspicious requests within {} seconds (last: {})",
+            activity_count.count, self.config.window_secs, activity
+        );
+        self.lock(
+            LockKind::Ip,
+            ip,
+            &reason,
+            activity_count.count,
+            Duration::seconds(self.config.ip_block_secs),
+        )
+        .await;
+        self.report("ip_blocked", &reason, Some(ip), 0.8).await;
+        true
+    }
+
+    /// Delete attempts that have left the window. Counters only prune the
+    /// subject they record, so attempts against accounts and IPs that never
+    /// come back would otherwise stay forever.
+    pub async fn prune_attempts(&self) -> anyhow::Result<u64> {
+        let window_start = Utc::now() - Duration::seconds(self.config.window_secs);
+        let deleted = sqlx::query("DELETE FROM security_attempts WHERE occurred_at <= $1")
+            .bind(window_start)
+            .execute(&self.db_pool)
+            .await?
+            .rows_affected();
+
+        Ok(deleted)
+    }
+
+    /// Prune expired attempts once per window in the background
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = std::time::Duration::from_secs(self.config.window_secs.max(60) as u64);
+            let mut ticker = tokio::time::interval(period);
+
+            loop {
+                ticker.tick().await;
+                match self.prune_attempts().await {
+                    Ok(0) => {}
+                    Ok(deleted) => {
+                        tracing::debug!(deleted, "Pruned expired security attempts")
+                    }
+                    Err(e) => tracing::warn!(error = %e, "Failed to prune security attempts"),
+                }
+            }
+        })
     }
 
     /// Check if an account is locked
-    pub async fn is_locked(&self, identifier: &str) -> bool {
-        let attempts = self.failed_attempts.read().await;
-        if let Some(tracker) = attempts.get(identifier) {
-            if let Some(locked_until) = tracker.locked_until {
-                return chrono::Utc::now() < locked_until;
+    pub async fn is_locked(&self, account: &str) -> bool {
+        self.locked_until(LockKind::Account, &normalize_account(account))
+            .await
+            .is_some()
+    }
+
+    /// Check if an IP is blocked
+    pub async fn is_ip_blocked(&self, ip: &str) -> bool {
+        self.locked_until(LockKind::Ip, ip).await.is_some()
+    }
+
+    /// When an IP block ends, if the IP is blocked
+    pub async fn ip_blocked_until(&self, ip: &str) -> Option<DateTime<Utc>> {
+        self.locked_until(LockKind::Ip, ip).await
+    }
+
+    /// Active lockouts and IP blocks, newest first
+    pub async fn active_lockouts(&self) -> anyhow::Result<Vec<Lockout>> {
+        let lockouts = sqlx::query_as::<_, Lockout>(
+            r#"
+            SELECT kind, subject, reason, failure_count, locked_at, locked_until
+            FROM security_lockouts
+            WHERE unlocked_at IS NULL AND locked_until > NOW()
+            ORDER BY locked_at DESC
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(lockouts)
+    }
+
+    /// Lift a lockout or IP block and reset its counters. Returns false if
+    /// there was nothing active to lift.
+    pub async fn unlock(
+        &self,
+        kind: LockKind,
+        subject: &str,
+        unlocked_by: Uuid,
+    ) -> anyhow::Result<bool> {
+        let subject = match kind {
+            LockKind::Account => normalize_account(subject),
+            LockKind::Ip => subject.to_string(),
+        };
+
+        let lifted = sqlx::query(
+            r#"
+            UPDATE security_lockouts
+            SET unlocked_at = NOW(), unlocked_by = $3
+            WHERE kind = $1 AND subject = $2 AND unlocked_at IS NULL AND locked_until > NOW()
+            "#,
+        )
+        .bind(kind.as_str())
+        .bind(&subject)
+        .bind(unlocked_by)
+        .execute(&self.db_pool)
+        .await?
+        .rows_affected()
+            > 0;
+
+        if let Some(mut conn) = self.redis().await {
+            let result: redis::RedisResult<()> = conn.del(lock_key(kind, &subject)).await;
+            if let Err(e) = result {
+                self.redis_failed(&e).await;
             }
         }
-        false
+
+        let counters: &[Counter] = match kind {
+            LockKind::Account => &[Counter::AccountFailures],
+            LockKind::Ip => &[Counter::IpFailures, Counter::IpSuspicious],
+        };
+        for counter in counters {
+            self.clear(*counter, &subject).await;
+        }
+
+        if lifted {
+            let details = serde_json::json!({
+                "kind": kind.as_str(),
+                "subject": subject,
+            });
+            let context = AuditContext {
+                user_id: Some(unlocked_by),
+                session_id: None,
+                ip_address: None,
+                user_agent: None,
+                correlation_id: None,
+            };
+            if let Err(e) = self
+                .audit_logger
+                .log_security_event(
+                    AuditEventType::AdminAction,
+                    AuditSeverity::Warning,
+                    format!("Lifted {} lockout", kind.as_str()),
+                    details,
+                    Some(context),
+                )
+                .await
+            {
+                tracing::warn!(error = %e, "Failed to audit lockout removal");
+            }
+        }
+
+        Ok(lifted)
     }
 
-    /// Clear failed attempts for successful login
-    pub async fn clear_failed_attempts(&self, identifier: &str) {
-        let mut attempts = self.failed_attempts.write().await;
-        attempts.remove(identifier);
+    /// Get security statistics
+    pub async fn get_security_stats(&self) -> SecurityStats {
+        let counts: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT kind, COUNT(*)
+            FROM security_lockouts
+            WHERE unlocked_at IS NULL AND locked_until > NOW()
+            GROUP BY kind
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to count active lockouts");
+            Vec::new()
+        });
+        let count_of = |kind: LockKind| {
+            counts
+                .iter()
+                .find(|(k, _)| k == kind.as_str())
+                .map_or(0, |(_, n)| *n as usize)
+        };
+
+        SecurityStats {
+            active_lockouts: count_of(LockKind::Account),
+            blocked_ips: count_of(LockKind::Ip),
+            redis_available: self.redis().await.is_some(),
+        }
     }
 
-    /// Record suspicious activity from IP
-    pub async fn record_suspicious_activity(&self, ip: String) -> bool {
-        let mut activities = self.suspicious_ips.write().await;
-        let now = chrono::Utc::now();
+    async fn report(&self, activity: &str, description: &str, ip: Option<&str>, risk: f64) {
+        let context = AuditContext {
+            user_id: None,
+            session_id: None,
+            ip_address: ip.and_then(|ip| ip.parse().ok()),
+            user_agent: None,
+            correlation_id: None,
+        };
+        if let Err(e) = self
+            .audit_logger
+            .log_suspicious_activity(
+                activity.to_string(),
+                description.to_string(),
+                Some(context),
+                Some(risk),
+            )
+            .await
+        {
+            tracing::warn!(error = %e, activity = activity, "Failed to audit suspicious activity");
+        }
+    }
 
-        let activity = activities.entry(ip).or_insert(SuspiciousActivity {
-            request_count: 0,
-            first_request: now,
-            last_request: now,
-            blocked_until: None,
-        });
+    // ===== Storage: Redis first, Postgres while it's down =====
 
-        // Reset counter if more than 1 hour has passed
-        if now
-            .signed_duration_since(activity.first_request)
-            .num_hours()
-            > 1
+    /// A Redis connection, unless Redis failed within the last
+    /// REDIS_RETRY_SECS. Lockouts recorded while it was down are copied
+    /// back in on the first connection after an outage.
+    async fn redis(&self) -> Option<deadpool_redis::Connection> {
+        let pool = self.redis_pool.as_ref()?;
+        if self
+            .redis_down_until
+            .read()
+            .await
+            .is_some_and(|until| Utc::now() < until)
         {
-            activity.request_count = 0;
-            activity.first_request = now;
+            return None;
         }
 
-        activity.request_count += 1;
-        activity.last_request = now;
+        let mut conn = match pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                self.redis_failed(&e).await;
+                return None;
+            }
+        };
 
-        // Block IP after 100 requests in 1 hour
-        if activity.request_count >= 100 {
-            activity.blocked_until = Some(now + chrono::Duration::hours(24));
-            return true; // IP is blocked
+        if self.redis_needs_resync.swap(false, Ordering::SeqCst) {
+            if let Err(e) = self.resync_lockouts(&mut conn).await {
+                self.redis_failed(&e).await;
+                return None;
+            }
+            *self.redis_down_until.write().await = None;
+            tracing::info!("Redis is back; lockouts resynced from Postgres");
         }
 
-        false
+        Some(conn)
     }
 
-    /// Check if an IP is blocked
-    pub async fn is_ip_blocked(&self, ip: &str) -> bool {
-        let activities = self.suspicious_ips.read().await;
-        if let Some(activity) = activities.get(ip) {
-            if let Some(blocked_until) = activity.blocked_until {
-                return chrono::Utc::now() < blocked_until;
+    async fn redis_failed(&self, error: &(dyn std::fmt::Display + Sync)) {
+        tracing::warn!(
+            error = %error,
+            retry_secs = REDIS_RETRY_SECS,
+            "Redis unavailable for security state; using Postgres"
+        );
+        *self.redis_down_until.write().await =
+            Some(Utc::now() + Duration::seconds(REDIS_RETRY_SECS));
+        self.redis_needs_resync.store(true, Ordering::SeqCst);
+    }
+
+    async fn resync_lockouts(&self, conn: &mut deadpool_redis::Connection) -> anyhow::Result<()> {
+        for lockout in self.active_lockouts().await? {
+            let Some(kind) = LockKind::parse(&lockout.kind) else {
+                continue;
+            };
+            redis_set_lock(conn, kind, &lockout.subject, lockout.locked_until).await?;
+        }
+        Ok(())
+    }
+
+    async fn locked_until(&self, kind: LockKind, subject: &str) -> Option<DateTime<Utc>> {
+        if let Some(mut conn) = self.redis().await {
+            let result: redis::RedisResult<Option<i64>> = conn.get(lock_key(kind, subject)).await;
+            match result {
+                Ok(until) => {
+                    return until
+                        .and_then(DateTime::from_timestamp_millis)
+                        .filter(|until| *until > Utc::now());
+                }
+                Err(e) => self.redis_failed(&e).await,
             }
         }
-        false
+
+        let result: sqlx::Result<Option<DateTime<Utc>>> = sqlx::query_scalar(
+            r#"
+            SELECT locked_until FROM security_lockouts
+            WHERE kind = $1 AND subject = $2 AND unlocked_at IS NULL AND locked_until > NOW()
+            "#,
+        )
+        .bind(kind.as_str())
+        .bind(subject)
+        .fetch_optional(&self.db_pool)
+        .await;
+
+        result.unwrap_or_else(|e| {
+            tracing::error!(error = %e, "Failed to read lockout state");
+            None
+        })
     }
 
-    /// Get security statistics
-    pub async fn get_security_stats(&self) -> SecurityStats {
-        let attempts = self.failed_attempts.read().await;
-        let activities = self.suspicious_ips.read().await;
-
-        let active_lockouts = attempts
-            .values()
-            .filter(|tracker| {
-                tracker
-                    .locked_until
-                    .is_some_and(|until| chrono::Utc::now() < until)
-            })
-            .count();
-
-        let blocked_ips = activities
-            .values()
-            .filter(|activity| {
-                activity
-                    .blocked_until
-                    .is_some_and(|until| chrono::Utc::now() < until)
-            })
-            .count();
+    async fn lock(
+        &self,
+        kind: LockKind,
+        subject: &str,
+        reason: &str,
+        failures: u32,
+        duration: Duration,
+    ) {
+        let locked_until = Utc::now() + duration;
+        tracing::warn!(
+            kind = kind.as_str(),
+            subject = %subject,
+            locked_until = %locked_until,
+            "{}", reason
+        );
 
-        SecurityStats {
-            total_failed_attempts: attempts.len(),
-            active_lockouts,
-            total_suspicious_ips: activities.len(),
-            blocked_ips,
+        let stored = sqlx::query(
+            r#"
+            INSERT INTO security_lockouts (kind, subject, reason, failure_count, locked_until)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (kind, subject) DO UPDATE
+            SET reason = EXCLUDED.reason,
+                failure_count = EXCLUDED.failure_count,
+                locked_at = NOW(),
+                locked_until = GREATEST(
+                    CASE WHEN security_lockouts.unlocked_at IS NULL
+                         THEN security_lockouts.locked_until END,
+                    EXCLUDED.locked_until
+                ),
+                unlocked_at = NULL,
+                unlocked_by = NULL
+            "#,
+        )
+        .bind(kind.as_str())
+        .bind(subject)
+        .bind(reason)
+        .bind(failures as i32)
+        .bind(locked_until)
+        .execute(&self.db_pool)
+        .await;
+        if let Err(e) = stored {
+            tracing::error!(error = %e, "Failed to store lockout");
+        }
+
+        if let Some(mut conn) = self.redis().await {
+            if let Err(e) = redis_set_lock(&mut conn, kind, subject, locked_until).await {
+                self.redis_failed(&e).await;
+            }
+        }
+    }
+
+    async fn record(&self, counter: Counter, subject: &str) -> WindowCount {
+        let now = Utc::now();
+        let window_start = now - Duration::seconds(self.config.window_secs);
+
+        if let Some(mut conn) = self.redis().await {
+            let key = counter_key(counter, subject);
+            let member = format!("{}-{}", now.timestamp_millis(), Uuid::new_v4());
+            let result: redis::RedisResult<(u32,)> = redis::pipe()
+                .atomic()
+                .zrembyscore(&key, 0, window_start.timestamp_millis())
+                .ignore()
+                .zadd(&key, member, now.timestamp_millis())
+                .ignore()
+                .pexpire(&key, self.config.window_secs * 1000)
+                .ignore()
+                .zcard(&key)
+                .query_async(&mut conn)
+                .await;
+            match result {
+                Ok((count,)) => {
+                    return WindowCount {
+                        count,
+                        last_at: Some(now),
+                    }
+                }
+                Err(e) => self.redis_failed(&e).await,
+            }
+        }
+
+        let result = async {
+            let mut tx = self.db_pool.begin().await?;
+            sqlx::query(
+                "DELETE FROM security_attempts WHERE counter = $1 AND subject = $2 AND occurred_at <= $3",
+            )
+            .bind(counter.as_str())
+            .bind(subject)
+            .bind(window_start)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                "INSERT INTO security_attempts (counter, subject, occurred_at) VALUES ($1, $2, $3)",
+            )
+            .bind(counter.as_str())
+            .bind(subject)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+            let count: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM security_attempts WHERE counter = $1 AND subject = $2",
+            )
+            .bind(counter.as_str())
+            .bind(subject)
+            .fetch_one(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok::<_, sqlx::Error>(count)
+        }
+        .await;
+
+        match result {
+            Ok(count) => WindowCount {
+                count: count as u32,
+                last_at: Some(now),
+            },
+            Err(e) => {
+                tracing::error!(error = %e, counter = counter.as_str(), "Failed to record security event");
+                WindowCount::default()
+            }
+        }
+    }
+
+    async fn count(&self, counter: Counter, subject: &str) -> WindowCount {
+        let window_start = Utc::now() - Duration::seconds(self.config.window_secs);
+
+        if let Some(mut conn) = self.redis().await {
+            let key = counter_key(counter, subject);
+            let result: redis::RedisResult<(u32, Vec<(String, i64)>)> = redis::pipe()
+                .zrembyscore(&key, 0, window_start.timestamp_millis())
+                .ignore()
+                .zcard(&key)
+                .zrange_withscores(&key, -1, -1)
+                .query_async(&mut conn)
+                .await;
+            match result {
+                Ok((count, latest)) => {
+                    return WindowCount {
+                        count,
+                        last_at: latest
+                            .first()
+                            .and_then(|(_, score)| DateTime::from_timestamp_millis(*score)),
+                    }
+                }
+                Err(e) => self.redis_failed(&e).await,
+            }
+        }
+
+        let result: sqlx::Result<(i64, Option<DateTime<Utc>>)> = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), MAX(occurred_at)
+            FROM security_attempts
+            WHERE counter = $1 AND subject = $2 AND occurred_at > $3
+            "#,
+        )
+        .bind(counter.as_str())
+        .bind(subject)
+        .bind(window_start)
+        .fetch_one(&self.db_pool)
+        .await;
+
+        match result {
+            Ok((count, last_at)) => WindowCount {
+                count: count as u32,
+                last_at,
+            },
+            Err(e) => {
+                tracing::error!(error = %e, counter = counter.as_str(), "Failed to read security counter");
+                WindowCount::default()
+            }
+        }
+    }
+
+    async fn clear(&self, counter: Counter, subject: &str) {
+        if let Some(mut conn) = self.redis().await {
+            let result: redis::RedisResult<()> = conn.del(counter_key(counter, subject)).await;
+            if let Err(e) = result {
+                self.redis_failed(&e).await;
+            }
+        }
+
+        // Rows written during a Redis outage would otherwise be counted again
+        // the next time Redis is down
+        let result =
+            sqlx::query("DELETE FROM security_attempts WHERE counter = $1 AND subject = $2")
+                .bind(counter.as_str())
+                .bind(subject)
+                .execute(&self.db_pool)
+                .await;
+        if let Err(e) = result {
+            tracing::error!(error = %e, counter = counter.as_str(), "Failed to clear security counter");
         }
     }
 }
 
-impl Default for SecurityMonitor {
-    fn default() -> Self {
-        Self::new()
+async fn redis_set_lock(
+    conn: &mut deadpool_redis::Connection,
+    kind: LockKind,
+    subject: &str,
+    locked_until: DateTime<Utc>,
+) -> redis::RedisResult<()> {
+    let ttl_ms = (locked_until - Utc::now()).num_milliseconds();
+    if ttl_ms <= 0 {
+        return Ok(());
     }
+    redis::cmd("SET")
+        .arg(lock_key(kind, subject))
+        .arg(locked_until.timestamp_millis())
+        .arg("PX")
+        .arg(ttl_ms)
+        .query_async(conn)
+        .await
 }
 
 #[derive(Debug, serde::Serialize)]
 pub struct SecurityStats {
-    pub total_failed_attempts: usize,
     pub active_lockouts: usize,
-    pub total_suspicious_ips: usize,
     pub blocked_ips: usize,
+    pub redis_available: bool,
+}
+
+/// Reject every request from a blocked IP
+pub async fn ip_block_middleware(
+    State(monitor): State<Arc<SecurityMonitor>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let client_ip = extract_client_ip(request.headers(), connect_info.as_ref());
+
+    if let Some(until) = monitor.ip_blocked_until(&client_ip).await {
+        tracing::warn!(ip = %client_ip, path = %request.uri().path(), "Request from blocked IP");
+        return Err(retry_after(until));
+    }
+
+    Ok(next.run(request).await)
 }
 
 /// Vulnerability scanner integration
@@ -391,3 +1031,38 @@ pub struct VulnerabilitySummary {
     pub info: usize,
     pub last_scan: Option<chrono::DateTime<chrono::Utc>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progressive_delay_doubles_up_to_cap() {
+        let config = BruteForceConfig {
+            window_secs: 3600,
+            account_max_failures: 5,
+            ip_max_failures: 50,
+            suspicious_activity_limit: 100,
+            account_lockout_secs: 3600,
+            ip_block_secs: 86400,
+            delay_free_attempts: 2,
+            delay_base_ms: 1000,
+            delay_max_ms: 30000,
+        };
+
+        assert_eq!(progressive_delay(&config, 0), Duration::zero());
+        assert_eq!(progressive_delay(&config, 2), Duration::zero());
+        assert_eq!(progressive_delay(&config, 3), Duration::seconds(1));
+        assert_eq!(progressive_delay(&config, 5), Duration::seconds(4));
+        assert_eq!(progressive_delay(&config, 10), Duration::seconds(30));
+        assert_eq!(progressive_delay(&config, u32::MAX), Duration::seconds(30));
+    }
+
+    #[test]
+    fn test_lock_kind_round_trips() {
+        for kind in [LockKind::Account, LockKind::Ip] {
+            assert_eq!(LockKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(LockKind::parse("user"), None);
+    }
+}
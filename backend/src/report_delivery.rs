@@ -0,0 +1,102 @@
+//! Report Delivery
+//!
+//! Webhook sink for scheduled reports. Each delivery POSTs the report
+//! metadata with its files inlined as base64, so receivers don't need access
+//! to the report directory. Files over the inline limit are listed without
+//! content.
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use ndith_analytics::{Report, ReportSink, ScheduledReport};
+use std::path::Path;
+use std::time::Duration;
+
+/// Largest file inlined into a webhook payload
+const MAX_INLINE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// POSTs generated reports to a webhook URL
+pub struct WebhookSink {
+    url: String,
+    bearer_token: Option<String>,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: String, bearer_token: Option<String>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .context("Failed to create report webhook HTTP client")?;
+
+        Ok(Self {
+            url,
+            bearer_token,
+            client,
+        })
+    }
+}
+
+#[async_trait]
+impl ReportSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn deliver(&self, schedule: &ScheduledReport, report: &Report) -> Result<()> {
+        let mut files = Vec::with_capacity(report.files.len());
+        for file in &report.files {
+            let filename = Path::new(&file.path)
+                .file_name()
+                .map(|f| f.to_string_lossy().into_owned())
+                .unwrap_or_default();
+
+            let content = if file.size_bytes as u64 <= MAX_INLINE_BYTES {
+                let bytes = tokio::fs::read(&file.path)
+                    .await
+                    .with_context(|| format!("Failed to read {}", file.path))?;
+                Some(base64::engine::general_purpose::STANDARD.encode(bytes))
+            } else {
+                None
+            };
+
+            files.push(serde_json::json!({
+                "filename": filename,
+                "section": file.section,
+                "size_bytes": file.size_bytes,
+                "content_base64": content,
+            }));
+        }
+
+        let payload = serde_json::json!({
+            "schedule_id": schedule.id,
+            "recipients": schedule.recipients,
+            "report": {
+                "id": report.id,
+                "report_type": report.report_type,
+                "format": report.format,
+                "time_range": report.time_range,
+                "generated_at": report.generated_at,
+                "expires_at": report.expires_at,
+                "summary": report.summary,
+            },
+            "files": files,
+        });
+
+        let mut request = self.client.post(&self.url).json(&payload);
+        if let Some(token) = &self.bearer_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Report webhook request failed")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Report webhook returned {}", response.status()));
+        }
+
+        Ok(())
+    }
+}
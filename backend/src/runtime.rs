@@ -1,9 +1,19 @@
-use crate::config::TokenRefreshConfig;
+use crate::config::{
+    AuditChainConfig, BruteForceConfig, DataRequestConfig, EmailConfig, Environment,
+    TokenRefreshConfig,
+};
+#[cfg(feature = "analytics")]
+use crate::listening_history_sync::ListeningHistorySyncJob;
+use crate::middleware::SecurityMonitor;
 use crate::services::catalog_sync::{
     AppleMusicSyncWorker, CrossPlatformIdentityResolver, DeezerSyncWorker, SpotifySyncWorker,
 };
+#[cfg(feature = "analytics")]
+use crate::services::RecentlyPlayedService;
 use crate::services::{
-    AppleMusicConfig, AppleMusicService, NotificationService, TokenRefreshBackgroundJob,
+    mailer_from_config, AppleMusicConfig, AppleMusicService, AuditChainService, DataRequestService,
+    EmailVerificationService, HouseholdService, NotificationService, RoleService,
+    TokenRefreshBackgroundJob, TransparencyConfig,
 };
 use crate::{
     create_pool, create_redis_pool, create_router, run_migrations, validate_cors_config, AppState,
@@ -13,11 +23,14 @@ use crate::{
     RedisConfiguration, TokenVaultService, UserService,
 };
 #[cfg(feature = "news")]
-use crate::{NewsPipelineConfig, NewsPipelineOrchestrator, ScheduledPipelineRunner};
+use crate::{
+    EvidenceLinkChecker, EvidenceLinkCheckerConfig, NewsPipelineConfig, NewsPipelineOrchestrator,
+    ScheduledPipelineRunner,
+};
 use axum::Router;
 #[cfg(feature = "news")]
 use chrono::Duration;
-use std::{env, sync::Arc};
+use std::{env, net::SocketAddr, sync::Arc};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -101,6 +114,9 @@ pub async fn run_service(mode: ServiceMode) -> Result<(), Box<dyn std::error::Er
     if args.len() > 1 && args[1] == "migrate" {
         return run_migration_command().await;
     }
+    if args.len() > 1 && args[1] == "verify-audit" {
+        return run_audit_verify_command(args.get(2).cloned()).await;
+    }
 
     init_tracing();
 
@@ -179,7 +195,17 @@ pub async fn run_service(mode: ServiceMode) -> Result<(), Box<dyn std::error::Er
         "Circuit breaker initialized"
     );
 
-    let auth_service = Arc::new(AuthService::new(db_pool.clone()));
+    let auth_service = Arc::new(
+        AuthService::from_env(db_pool.clone(), Environment::from_env())
+            .map_err(|e| format!("Authentication configuration error: {}", e))?,
+    );
+    let jwt_keyring = auth_service.jwt_keyring().clone();
+    tracing::info!(
+        rotation_interval_days = jwt_keyring.config().rotation_interval_days,
+        publish_lead_hours = jwt_keyring.config().publish_lead_hours,
+        "Starting JWT signing key rotation"
+    );
+    jwt_keyring.start();
     let rate_limiter = Arc::new(
         RateLimitService::new(
             &env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string()),
@@ -187,8 +213,34 @@ pub async fn run_service(mode: ServiceMode) -> Result<(), Box<dyn std::error::Er
         .map_err(|e| format!("Failed to initialize rate limiter: {}", e))?,
     );
     let audit_logger = Arc::new(AuditLoggingService::new(db_pool.clone()));
+    let audit_chain_config = AuditChainConfig::from_env(Environment::from_env())
+        .map_err(|e| format!("Audit chain configuration error: {}", e))?;
+    let audit_chain = Arc::new(AuditChainService::new(db_pool.clone(), audit_chain_config));
+    tracing::info!(
+        key_id = audit_chain.key_id(),
+        interval_minutes = audit_chain.config().checkpoint_interval_minutes,
+        "Starting audit chain checkpoints"
+    );
+    audit_chain.clone().start();
+    let security_monitor = Arc::new(SecurityMonitor::new(
+        db_pool.clone(),
+        Some(redis_pool.clone()),
+        BruteForceConfig::from_env(),
+        audit_logger.clone(),
+    ));
+    security_monitor.clone().start();
+    let roles = Arc::new(RoleService::new(db_pool.clone(), audit_logger.clone()));
     let dnp_service = Arc::new(DnpListService::new(db_pool.clone()));
     let user_service = Arc::new(UserService::new(db_pool.clone()));
+    let households = Arc::new(HouseholdService::new(db_pool.clone()));
+    let email_config = EmailConfig::from_env();
+    let email_verification = Arc::new(EmailVerificationService::new(
+        db_pool.clone(),
+        mailer_from_config(&email_config),
+        email_config,
+    ));
+    let transparency_config = TransparencyConfig::from_env(Environment::from_env())
+        .map_err(|e| format!("Public statistics configuration error: {}", e))?;
     tracing::info!("Core services initialized successfully");
 
     let platform_config = PlatformSyncConfig::from_env();
@@ -246,7 +298,7 @@ pub async fn run_service(mode: ServiceMode) -> Result<(), Box<dyn std::error::Er
 
     #[cfg(feature = "news")]
     let (backfill_orchestrator, news_pipeline) = if mode.should_start_news_pipeline() {
-        initialize_full_platform_services(db_pool.clone()).await
+        initialize_full_platform_services(db_pool.clone()).await?
     } else {
         tracing::info!(
             service_mode = mode.as_str(),
@@ -272,6 +324,21 @@ pub async fn run_service(mode: ServiceMode) -> Result<(), Box<dyn std::error::Er
         "Token vault service initialized"
     );
 
+    let data_request_config = DataRequestConfig::from_env(Environment::from_env())
+        .map_err(|e| format!("Data request configuration error: {}", e))?;
+    let data_requests = Arc::new(DataRequestService::new(
+        db_pool.clone(),
+        user_service.clone(),
+        token_vault.clone(),
+        data_request_config,
+    ));
+    tracing::info!(
+        poll_interval_seconds = data_requests.config().poll_interval_seconds,
+        link_ttl_hours = data_requests.config().link_ttl_hours,
+        "Starting GDPR data request processing"
+    );
+    data_requests.clone().start();
+
     let notification_service = Arc::new(NotificationService::new(db_pool.clone()));
     tracing::info!("Notification service initialized");
 
@@ -302,6 +369,27 @@ pub async fn run_service(mode: ServiceMode) -> Result<(), Box<dyn std::error::Er
         );
     }
 
+    #[cfg(feature = "analytics")]
+    if mode.should_start_token_refresh() {
+        let sync_interval_minutes: i64 = env::var("LISTENING_HISTORY_SYNC_INTERVAL_MINUTES")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .unwrap_or(30);
+
+        let _listening_sync = Arc::new(ListeningHistorySyncJob::new(
+            db_pool.clone(),
+            RecentlyPlayedService::new(token_vault.clone()),
+            chrono::Duration::minutes(sync_interval_minutes),
+        ))
+        .start();
+
+        tracing::info!(
+            service_mode = mode.as_str(),
+            sync_interval_minutes = sync_interval_minutes,
+            "Listening history sync started"
+        );
+    }
+
     let apple_music_config = AppleMusicConfig::default();
     let apple_music_service = Arc::new(
         AppleMusicService::new(apple_music_config, token_vault)
@@ -309,22 +397,44 @@ pub async fn run_service(mode: ServiceMode) -> Result<(), Box<dyn std::error::Er
     );
     tracing::info!("Apple Music enforcement service initialized");
 
+    #[cfg(feature = "analytics")]
+    let analytics_db = initialize_analytics_db().await;
+    #[cfg(feature = "analytics")]
+    if let Some(duckdb) = &analytics_db {
+        start_analytics_replication(duckdb.clone(), db_pool.clone(), mode);
+    }
+    #[cfg(feature = "analytics")]
+    let report_scheduler = analytics_db
+        .clone()
+        .map(|duckdb| initialize_report_scheduler(duckdb, db_pool.clone(), mode));
+
     let app_state = AppState {
         db_pool,
         redis_pool,
         auth_service,
         rate_limiter,
         audit_logger,
+        audit_chain,
+        security_monitor,
+        roles,
         dnp_service,
         user_service,
+        households,
+        email_verification,
+        data_requests,
         monitoring,
         metrics,
         catalog_sync,
         platform_config,
+        transparency_config,
         credits_sync,
         backfill_orchestrator,
         #[cfg(feature = "news")]
         news_pipeline,
+        #[cfg(feature = "analytics")]
+        analytics_db,
+        #[cfg(feature = "analytics")]
+        report_scheduler,
         apple_music_service,
         circuit_breaker,
         test_user_id: None,
@@ -338,10 +448,128 @@ pub async fn run_service(mode: ServiceMode) -> Result<(), Box<dyn std::error::Er
         "Server running"
     );
 
-    axum::serve(listener, app).await?;
+    // Peer addresses identify clients when no proxy sets X-Forwarded-For
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
 
+/// Open the DuckDB analytics store. Trend endpoints report it as unavailable
+/// rather than failing startup when it can't be opened.
+#[cfg(feature = "analytics")]
+async fn initialize_analytics_db() -> Option<Arc<ndith_analytics::DuckDbClient>> {
+    let path = env::var("DUCKDB_PATH").unwrap_or_else(|_| "data/analytics.duckdb".to_string());
+
+    let client = match ndith_analytics::DuckDbClient::new(&path) {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::warn!(path = %path, error = %e, "DuckDB analytics store unavailable");
+            return None;
+        }
+    };
+
+    if let Err(e) = client.initialize_schema().await {
+        tracing::warn!(path = %path, error = %e, "Failed to initialize DuckDB schema");
+        return None;
+    }
+
+    tracing::info!(path = %path, "DuckDB analytics store initialized");
+    Some(Arc::new(client))
+}
+
+/// Start copying analytics tables from Postgres into DuckDB for modes that
+/// run background jobs
+#[cfg(feature = "analytics")]
+fn start_analytics_replication(
+    duckdb: Arc<ndith_analytics::DuckDbClient>,
+    db_pool: sqlx::PgPool,
+    mode: ServiceMode,
+) {
+    if !matches!(mode, ServiceMode::Monolith | ServiceMode::Analytics) {
+        return;
+    }
+
+    let interval_secs: u64 = env::var("ANALYTICS_REPLICATION_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60);
+
+    let replication = Arc::new(replication_service(db_pool, duckdb));
+    let _replication = replication.start(std::time::Duration::from_secs(interval_secs));
+    tracing::info!(
+        service_mode = mode.as_str(),
+        interval_secs = interval_secs,
+        "Analytics replication started"
+    );
+}
+
+/// Replication job for this instance's DuckDB replica. Each replica reads the
+/// change log under its own ANALYTICS_REPLICA_ID.
+#[cfg(feature = "analytics")]
+pub fn replication_service(
+    db_pool: sqlx::PgPool,
+    duckdb: Arc<ndith_analytics::DuckDbClient>,
+) -> ndith_analytics::ReplicationService {
+    let consumer_id = env::var("ANALYTICS_REPLICA_ID").unwrap_or_else(|_| "default".to_string());
+    let retention_hours: i64 = env::var("ANALYTICS_CHANGE_LOG_RETENTION_HOURS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(168);
+
+    ndith_analytics::ReplicationService::new(db_pool, duckdb)
+        .with_consumer_id(consumer_id)
+        .with_retention(chrono::Duration::hours(retention_hours.max(1)))
+}
+
+/// Build the report scheduler with the delivery sinks configured in the
+/// environment, and start it for modes that run background jobs
+#[cfg(feature = "analytics")]
+fn initialize_report_scheduler(
+    duckdb: Arc<ndith_analytics::DuckDbClient>,
+    db_pool: sqlx::PgPool,
+    mode: ServiceMode,
+) -> Arc<ndith_analytics::ReportScheduler> {
+    let output_dir = env::var("REPORT_OUTPUT_DIR").unwrap_or_else(|_| "data/reports".to_string());
+    let retention_days: i64 = env::var("REPORT_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(7);
+
+    let reporting = ndith_analytics::ReportingService::new(
+        duckdb.clone(),
+        ndith_analytics::DashboardService::new(duckdb.clone(), db_pool.clone()),
+        ndith_analytics::TrendAnalysisService::new(duckdb),
+        output_dir,
+    )
+    .with_retention(chrono::Duration::days(retention_days));
+
+    let mut scheduler = ndith_analytics::ReportScheduler::new(Arc::new(reporting), db_pool);
+    if let Ok(dir) = env::var("REPORT_DELIVERY_DIR") {
+        scheduler = scheduler.with_sink(Arc::new(ndith_analytics::FilesystemSink::new(dir)));
+    }
+    if let Ok(url) = env::var("REPORT_WEBHOOK_URL") {
+        match crate::report_delivery::WebhookSink::new(url, env::var("REPORT_WEBHOOK_TOKEN").ok()) {
+            Ok(sink) => scheduler = scheduler.with_sink(Arc::new(sink)),
+            Err(e) => tracing::error!(error = %e, "Report webhook delivery disabled"),
+        }
+    }
+
+    let scheduler = Arc::new(scheduler);
+    if matches!(mode, ServiceMode::Monolith | ServiceMode::Analytics) {
+        let _report_scheduler = scheduler.clone().start(std::time::Duration::from_secs(60));
+        tracing::info!(
+            service_mode = mode.as_str(),
+            retention_days = retention_days,
+            "Report scheduler started"
+        );
+    }
+
+    scheduler
+}
+
 async fn run_migration_command() -> Result<(), Box<dyn std::error::Error>> {
     tracing_subscriber::registry()
         .with(
@@ -365,6 +593,52 @@ async fn run_migration_command() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// `verify-audit [tenant]`: verify one audit chain, or all of them, and
+/// print each report. Fails if any chain doesn't verify.
+async fn run_audit_verify_command(
+    tenant: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
+        )
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    let db_config = DatabaseConfig::default();
+    let db_pool = create_pool(db_config)
+        .await
+        .map_err(|e| format!("Failed to create database pool: {}", e))?;
+    let audit_chain_config = AuditChainConfig::from_env(Environment::from_env())
+        .map_err(|e| format!("Audit chain configuration error: {}", e))?;
+    let audit_chain = AuditChainService::new(db_pool, audit_chain_config);
+
+    let tenants = match tenant {
+        Some(tenant) => vec![tenant],
+        None => audit_chain
+            .heads()
+            .await?
+            .into_iter()
+            .map(|head| head.tenant_id)
+            .collect(),
+    };
+
+    let mut failed = Vec::new();
+    for tenant in tenants {
+        let report = audit_chain.verify(&tenant, None, None).await?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        if !report.valid {
+            failed.push(tenant);
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(format!("Audit chain verification failed for: {}", failed.join(", ")).into());
+    }
+    tracing::info!("Audit chains verified");
+    Ok(())
+}
+
 fn init_tracing() {
     tracing_subscriber::registry()
         .with(
@@ -384,10 +658,30 @@ fn init_tracing() {
 #[cfg(feature = "news")]
 async fn initialize_full_platform_services(
     db_pool: sqlx::PgPool,
-) -> (
-    Option<Arc<BackfillOrchestrator>>,
-    Option<Arc<NewsPipelineOrchestrator>>,
-) {
+) -> Result<
+    (
+        Option<Arc<BackfillOrchestrator>>,
+        Option<Arc<NewsPipelineOrchestrator>>,
+    ),
+    Box<dyn std::error::Error>,
+> {
+    // tokio::time::interval panics on a zero period
+    let link_check_interval_hours: i64 = match std::env::var("EVIDENCE_LINK_CHECK_INTERVAL_HOURS")
+    {
+        Ok(value) => value
+            .parse::<i64>()
+            .ok()
+            .filter(|hours| *hours > 0)
+            .ok_or_else(|| {
+                format!(
+                    "Evidence link checker configuration error: \
+                     EVIDENCE_LINK_CHECK_INTERVAL_HOURS must be a positive number of hours, got '{}'",
+                    value
+                )
+            })?,
+        Err(_) => 24,
+    };
+
     let news_config = NewsPipelineConfig::default();
     let news_pipeline = Arc::new(NewsPipelineOrchestrator::with_database(
         news_config,
@@ -410,13 +704,24 @@ async fn initialize_full_platform_services(
         "News pipeline started"
     );
 
-    (
+    let _link_checker = Arc::new(EvidenceLinkChecker::new(
+        EvidenceLinkCheckerConfig::default(),
+        db_pool.clone(),
+    ))
+    .start(Duration::hours(link_check_interval_hours));
+
+    tracing::info!(
+        link_check_interval_hours = link_check_interval_hours,
+        "Evidence link checker started"
+    );
+
+    Ok((
         Some(Arc::new(BackfillOrchestrator::with_news_pipeline(
             db_pool,
             news_pipeline.clone(),
         ))),
         Some(news_pipeline),
-    )
+    ))
 }
 
 #[cfg(not(feature = "news"))]
@@ -37,6 +37,8 @@ async fn test_auth_service_integration() {
         email: "test_auth@example.com".to_string(),
         password: "testpassword123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let token_pair = auth_service.login_user(login_request).await;
@@ -109,6 +111,8 @@ async fn test_auth_service_invalid_credentials() {
         email: "nonexistent@example.com".to_string(),
         password: "wrongpassword".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(login_request).await;
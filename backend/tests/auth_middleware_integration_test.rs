@@ -52,6 +52,8 @@ async fn test_auth_middleware_with_valid_token() {
         email: register_request.email,
         password: register_request.password,
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let token_pair = auth_service.login_user(login_request).await.unwrap();
@@ -40,6 +40,8 @@ async fn test_user_registration_and_login() {
         email: register_request.email.clone(),
         password: register_request.password.clone(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let token_pair = auth_service.login_user(login_request).await.unwrap();
@@ -103,6 +105,8 @@ async fn test_refresh_token_functionality() {
         email: register_request.email,
         password: register_request.password,
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let initial_tokens = auth_service.login_user(login_request).await.unwrap();
@@ -135,6 +139,8 @@ async fn test_invalid_credentials() {
         email: "nonexistent@example.com".to_string(),
         password: "password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(login_request).await;
@@ -156,6 +162,8 @@ async fn test_invalid_credentials() {
         email: register_request.email,
         password: "wrong_password".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(wrong_login).await;
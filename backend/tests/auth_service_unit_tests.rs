@@ -88,6 +88,7 @@ async fn test_validate_access_token_rejects_expired_token() {
             role: Default::default(),
             iss: String::new(),
             aud: String::new(),
+            email_verified: false,
         },
         &EncodingKey::from_secret(TEST_JWT_SECRET.as_bytes()),
     )
@@ -77,6 +77,8 @@ async fn test_user_login_fixed() {
         email: unique_email,
         password: "secure_password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let token_pair = auth_service.login_user(login_request).await.unwrap();
@@ -107,10 +109,14 @@ async fn test_totp_setup_fixed() {
     assert!(totp_setup.qr_code_url.contains("otpauth://totp/"));
     assert_eq!(totp_setup.backup_codes.len(), 8);
 
-    // Verify backup codes are 6 digits
+    // Verify backup codes are well-formed recovery codes
     for code in &totp_setup.backup_codes {
-        assert_eq!(code.len(), 6);
-        assert!(code.chars().all(|c| c.is_ascii_digit()));
+        assert!(
+            music_streaming_blocklist_backend::services::recovery_codes::normalize_recovery_code(
+                code
+            )
+            .is_some()
+        );
     }
 }
 
@@ -125,6 +125,8 @@ impl TestDataFactory {
             email,
             password: "SecurePassword123!".to_string(),
             totp_code: None,
+            recovery_code: None,
+            webauthn: None,
         }
     }
 
@@ -348,6 +348,7 @@ fn test_claims_model() {
         role: Default::default(),
         iss: String::new(),
         aud: String::new(),
+        email_verified: false,
     };
 
     assert_eq!(claims.sub, user_id.to_string());
@@ -17,6 +17,8 @@ async fn simple_auth_service_accepts_database_configuration() {
         email: "missing@example.com".to_string(),
         password: "password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(login).await;
@@ -34,6 +36,8 @@ fn workflow_request_models_construct_cleanly() {
         email: register.email.clone(),
         password: register.password.clone(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     assert_eq!(register.email, login.email);
@@ -37,6 +37,8 @@ async fn test_database_and_auth_integration_fixed() {
         email: unique_email,
         password: "secure_password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let token_pair = auth_service.login_user(login_request).await.unwrap();
@@ -161,6 +163,8 @@ async fn test_complete_user_workflow_simplified() {
         email: unique_email.clone(),
         password: "secure_password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let _token_pair = auth_service.login_user(login_request).await.unwrap();
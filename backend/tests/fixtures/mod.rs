@@ -133,6 +133,8 @@ impl TestFixtures {
             email: email.to_string(),
             password: "SecureTestPassword123!".to_string(),
             totp_code: None,
+            recovery_code: None,
+            webauthn: None,
         }
     }
 
@@ -142,6 +144,8 @@ impl TestFixtures {
             email: email.to_string(),
             password: "SecureTestPassword123!".to_string(),
             totp_code: Some(totp_code.to_string()),
+            recovery_code: None,
+            webauthn: None,
         }
     }
 
@@ -70,6 +70,8 @@ async fn test_auth_handlers_structure() {
         email: "test@example.com".to_string(),
         password: "password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let refresh_request = models::RefreshTokenRequest {
@@ -45,10 +45,14 @@ async fn test_totp_setup_success() {
     assert!(setup_response.qr_code_url.contains(&setup_response.secret));
     assert_eq!(setup_response.backup_codes.len(), 8);
 
-    // Verify backup codes are 6 digits
+    // Verify backup codes are well-formed recovery codes
     for code in &setup_response.backup_codes {
-        assert_eq!(code.len(), 6);
-        assert!(code.chars().all(|c| c.is_ascii_digit()));
+        assert!(
+            music_streaming_blocklist_backend::services::recovery_codes::normalize_recovery_code(
+                code
+            )
+            .is_some()
+        );
     }
 
     // Verify 2FA is not yet enabled
@@ -240,6 +244,8 @@ async fn test_login_with_totp_success() {
         email: user_email,
         password: user_password,
         totp_code: Some(login_totp_code),
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(login_request).await;
@@ -274,6 +280,8 @@ async fn test_login_with_totp_missing_code() {
         email: user_email,
         password: user_password,
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(login_request).await;
@@ -308,6 +316,8 @@ async fn test_login_with_totp_invalid_code() {
         email: user_email,
         password: user_password,
         totp_code: Some("123456".to_string()),
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(login_request).await;
@@ -85,6 +85,8 @@ async fn test_user_login_invalid_credentials(#[future] test_db: TestDatabase) {
         email: "nonexistent@example.com".to_string(),
         password: "password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(login_request).await;
@@ -134,10 +136,14 @@ async fn test_totp_setup_and_enable(#[future] test_db: TestDatabase) {
     assert!(totp_setup.qr_code_url.contains("otpauth://totp/"));
     assert_eq!(totp_setup.backup_codes.len(), 8);
 
-    // Verify backup codes format
+    // Verify backup codes are well-formed recovery codes
     for code in &totp_setup.backup_codes {
-        assert_eq!(code.len(), 6);
-        assert!(code.chars().all(|c| c.is_ascii_digit()));
+        assert!(
+            music_streaming_blocklist_backend::services::recovery_codes::normalize_recovery_code(
+                code
+            )
+            .is_some()
+        );
     }
 
     // Generate valid TOTP code and enable
@@ -37,6 +37,8 @@ async fn test_database_and_auth_integration() {
         email: unique_email,
         password: "secure_password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let token_pair = auth_service.login_user(login_request).await.unwrap();
@@ -88,6 +90,8 @@ async fn test_invalid_login_credentials() {
         email: "nonexistent@example.com".to_string(),
         password: "password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(login_request).await;
@@ -110,6 +114,8 @@ async fn test_invalid_login_credentials() {
         email: unique_email,
         password: "wrong_password".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let result = auth_service.login_user(wrong_password_login).await;
@@ -142,6 +148,8 @@ async fn test_token_validation() {
         email: unique_email.clone(),
         password: "password123".to_string(),
         totp_code: None,
+        recovery_code: None,
+        webauthn: None,
     };
 
     let token_pair = auth_service.login_user(login_request).await.unwrap();
@@ -1,7 +1,7 @@
 import { ConvexError, v } from "convex/values";
 import type { Doc } from "./_generated/dataModel";
 import { mutation, query, type MutationCtx } from "./_generated/server";
-import { nowIso, requireCurrentUser } from "./lib/auth";
+import { nowIso, requireCurrentUser, requireVerifiedUser } from "./lib/auth";
 
 export const list = query({
   args: {},
@@ -299,7 +299,12 @@ export const create = mutation({
     visibility: v.optional(v.string()),
   },
   handler: async (ctx, args) => {
-    const { user } = await requireCurrentUser(ctx);
+    const visibility = args.visibility ?? "public";
+    // Publishing a list needs a verified email; private lists don't
+    const { user } =
+      visibility === "public"
+        ? await requireVerifiedUser(ctx)
+        : await requireCurrentUser(ctx);
     const now = nowIso();
 
     const listId = await ctx.db.insert("communityLists", {
@@ -311,7 +316,7 @@ export const create = mutation({
       governanceUrl: args.governanceUrl,
       updateCadence: args.updateCadence ?? "manual",
       version: 1,
-      visibility: args.visibility ?? "public",
+      visibility,
       metadata: {},
       createdAt: now,
       updatedAt: now,
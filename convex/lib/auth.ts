@@ -37,6 +37,19 @@ export async function requireCurrentUser(ctx: AnyCtx) {
   return { identity, user };
 }
 
+/**
+ * Like requireCurrentUser, for actions other users see (offense submissions,
+ * public community lists). The identity token is checked first so a
+ * verification made since the last sync counts straight away.
+ */
+export async function requireVerifiedUser(ctx: AnyCtx) {
+  const { identity, user } = await requireCurrentUser(ctx);
+  if (!(identity.emailVerified ?? user.emailVerified ?? false)) {
+    throw new ConvexError("Verify your email address to do this.");
+  }
+  return { identity, user };
+}
+
 export async function requireOwner(ctx: AnyCtx) {
   const { identity, user } = await requireCurrentUser(ctx);
   const roles = user.roles ?? [];
@@ -0,0 +1,77 @@
+/// <reference types="vite/client" />
+import { convexTest } from "convex-test";
+import { expect, test, describe } from "vitest";
+import { api } from "./_generated/api";
+import schema from "./schema";
+
+const modules = import.meta.glob("./**/*.ts");
+
+// Tokens carry the account's email_verified claim, which Convex exposes
+// as identity.emailVerified
+const TEST_IDENTITY = {
+  tokenIdentifier: "test|user_001",
+  email: "reporter@test.com",
+  name: "Test Reporter",
+};
+
+function setupUserAndArtist(t: ReturnType<typeof convexTest>) {
+  return t.run(async (ctx) => {
+    const now = new Date().toISOString();
+
+    // Synced before the address was verified
+    const userId = await ctx.db.insert("users", {
+      legacyKey: "test:user:001",
+      authSubject: TEST_IDENTITY.tokenIdentifier,
+      email: TEST_IDENTITY.email,
+      emailVerified: false,
+      displayName: "Test Reporter",
+      createdAt: now,
+      updatedAt: now,
+    });
+
+    const artistId = await ctx.db.insert("artists", {
+      legacyKey: "test:artist:drake",
+      canonicalName: "Drake",
+      externalIds: { spotify: "3TVXtAsR1Inumwj472S9r4" },
+      metadata: {},
+      aliases: [],
+      status: "active",
+      createdAt: now,
+      updatedAt: now,
+    });
+
+    return { userId, artistId };
+  });
+}
+
+const submission = {
+  category: "violence",
+  severity: "moderate",
+  title: "Test incident",
+  description: "Reported for testing",
+};
+
+describe("offenses:submit", () => {
+  test("accepts a user whose token says the address is verified", async () => {
+    const t = convexTest(schema, modules);
+    const { userId, artistId } = await setupUserAndArtist(t);
+
+    const offense = await t
+      .withIdentity({ ...TEST_IDENTITY, emailVerified: true })
+      .mutation(api.offenses.submit, { artistId, ...submission });
+
+    expect(offense?.status).toBe("pending");
+    expect(offense?.submittedByUserId).toBe(userId);
+  });
+
+  test("rejects a user whose address isn't verified", async () => {
+    const t = convexTest(schema, modules);
+    const { artistId } = await setupUserAndArtist(t);
+
+    await expect(
+      t
+        .withIdentity({ ...TEST_IDENTITY, emailVerified: false })
+        .mutation(api.offenses.submit, { artistId, ...submission }),
+    ).rejects.toThrow("Verify your email address");
+  });
+});
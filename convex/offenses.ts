@@ -1,7 +1,7 @@
 import { ConvexError, v } from "convex/values";
 import { internalMutation, mutation, query } from "./_generated/server";
 import { internal } from "./_generated/api";
-import { nowIso, requireCurrentUser } from "./lib/auth";
+import { nowIso, requireCurrentUser, requireVerifiedUser } from "./lib/auth";
 
 export const listByArtist = query({
   args: {
@@ -132,7 +132,7 @@ export const submit = mutation({
     proceduralState: v.optional(v.string()),
   },
   handler: async (ctx, args) => {
-    const { user } = await requireCurrentUser(ctx);
+    const { user } = await requireVerifiedUser(ctx);
     const offenseId = await ctx.db.insert("artistOffenses", {
       legacyKey: `runtime:offense:${args.artistId}:${Date.now()}`,
       artistId: args.artistId,
@@ -173,7 +173,7 @@ export const addEvidence = mutation({
     credibilityScore: v.optional(v.number()),
   },
   handler: async (ctx, args) => {
-    const { user } = await requireCurrentUser(ctx);
+    const { user } = await requireVerifiedUser(ctx);
     const offense = await ctx.db.get(args.offenseId);
     if (!offense) {
       throw new ConvexError("Offense not found.");
@@ -13,6 +13,9 @@ export type Route =
   | 'graph'
   | 'oauth-callback'
   | 'oauth-error'
+  | 'oauth-authorize'
+  | 'verify-email'
+  | 'confirm-email-change'
   | 'dnp'
   | 'playlist-sanitizer'
   | 'artist-profile'
@@ -47,6 +50,9 @@ const routeToPath: Record<Route, string> = {
   'graph': '/graph',
   'oauth-callback': '/auth/callback',
   'oauth-error': '/auth/error',
+  'oauth-authorize': '/oauth/authorize',
+  'verify-email': '/verify-email',
+  'confirm-email-change': '/confirm-email-change',
   'dnp': '/dnp',
   'playlist-sanitizer': '/playlist-sanitizer',
   'artist-profile': '/artist',
@@ -66,6 +72,9 @@ const routeMeta: Record<Route, { title: string; description: string }> = {
   'graph': { title: 'Graph Explorer', description: 'Explore artist collaboration networks' },
   'oauth-callback': { title: 'Connecting...', description: 'Processing authentication' },
   'oauth-error': { title: 'Connection Error', description: 'There was a problem connecting' },
+  'oauth-authorize': { title: 'Authorize App', description: 'Allow an app to access your account' },
+  'verify-email': { title: 'Verify Email', description: 'Confirming your email address' },
+  'confirm-email-change': { title: 'Confirm Email Change', description: 'Confirming your new email address' },
   'dnp': { title: 'DNP List', description: 'Your Do Not Play list' },
   'playlist-sanitizer': { title: 'Playlists', description: 'Browse, grade, and sanitize your playlists' },
   'artist-profile': { title: 'Artist Profile', description: 'View artist details and evidence' },
@@ -119,6 +128,9 @@ export function navigateToArtist(artistId: string) {
 function parseRoute(path: string): { route: Route; params: Record<string, string> } {
   if (path.startsWith('/auth/callback')) return { route: 'oauth-callback', params: {} };
   if (path.startsWith('/auth/error')) return { route: 'oauth-error', params: {} };
+  if (path.startsWith('/oauth/authorize')) return { route: 'oauth-authorize', params: {} };
+  if (path.startsWith('/verify-email')) return { route: 'verify-email', params: {} };
+  if (path.startsWith('/confirm-email-change')) return { route: 'confirm-email-change', params: {} };
 
   // Handle dynamic artist profile route: /artist/:id
   const artistMatch = path.match(/^\/artist\/([^\/]+)/);